@@ -0,0 +1,655 @@
+//! CSS transitions and `@keyframes` animations
+//!
+//! Transitions are started when the computed value of a property changes between two
+//! frames (i.e. because the node is now hovered or a dynamic CSS override changed),
+//! animations are started when a node gets an `animation-name`. Both are evaluated
+//! on every frame, the interpolated values are stored in `UiDescription::animated_properties`
+//! and override the styled values when the display list is built.
+
+use azul_css::{
+    Css, CssKeyframes, CssProperty, CssPropertyType, CssPropertyValue, StyleAnimationDirection,
+    StyleAnimationFillMode, StyleAnimationIterationCount, StyleTimingFunction,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    mem,
+    time::{Duration, Instant},
+};
+
+use crate::{diff::DomDiff, id_tree::NodeId, ui_description::UiDescription};
+
+/// The transitions and animations that are currently running on the nodes of one DOM
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AnimationState {
+    /// Running transitions, per node and property
+    pub transitions: BTreeMap<NodeId, BTreeMap<CssPropertyType, RunningTransition>>,
+    /// `@keyframes` animations, per node (a node can only run one animation at a time)
+    pub animations: BTreeMap<NodeId, RunningAnimation>,
+}
+
+/// Transition of a single property from the old to the new computed value
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningTransition {
+    /// Value that was displayed when the transition started
+    pub from: CssProperty,
+    /// New computed value of the property
+    pub to: CssProperty,
+    /// When the transition starts (the `delay` is already added)
+    pub start: Instant,
+    pub duration: Duration,
+    pub timing_function: StyleTimingFunction,
+}
+
+/// `@keyframes` animation that is applied to a node via `animation-name`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningAnimation {
+    /// Name of the `@keyframes` rule
+    pub name: String,
+    /// When the `animation-name` was applied to the node (the `animation-delay` is not added)
+    pub start: Instant,
+    /// Whether all iterations of the animation have been played
+    pub finished: bool,
+}
+
+impl AnimationState {
+    /// Returns whether any transition or animation is still running, i.e. if the
+    /// next frame has to be rendered even if nothing else changed
+    pub fn is_animating(&self) -> bool {
+        !self.transitions.is_empty() || self.animations.values().any(|a| !a.finished)
+    }
+}
+
+impl RunningTransition {
+    /// Returns the value of the transition at the given time, `None` if the transition has finished
+    pub fn get_value(&self, now: Instant) -> Option<CssProperty> {
+        if now < self.start {
+            return Some(self.from.clone());
+        }
+
+        let elapsed = now.duration_since(self.start);
+        if elapsed >= self.duration {
+            return None;
+        }
+
+        let t = self
+            .timing_function
+            .evaluate(elapsed.as_secs_f32() / self.duration.as_secs_f32());
+        Some(
+            self.from
+                .interpolate(&self.to, t)
+                .unwrap_or_else(|| self.to.clone()),
+        )
+    }
+}
+
+/// Starts the transitions and animations of a freshly styled `UiDescription` by comparing
+/// it with the last frame and evaluates them at the time `now`
+pub(crate) fn update_animations<T>(
+    ui_description: &mut UiDescription<T>,
+    previous_frame: Option<(&UiDescription<T>, &DomDiff)>,
+    css: &Css,
+    now: Instant,
+) {
+    let mut animation_state = AnimationState::default();
+
+    for node_id in ui_description.styled_nodes.linear_iter() {
+        // Fast path: most nodes don't have any transitions or animations
+        let css_constraints = &ui_description.styled_nodes[node_id].css_constraints;
+        if !css_constraints.contains_key(&CssPropertyType::Transition)
+            && !css_constraints.contains_key(&CssPropertyType::AnimationName)
+        {
+            continue;
+        }
+
+        let style = ui_description.get_computed_style(node_id);
+        let previous = previous_frame.and_then(|(previous, diff)| {
+            diff.get_old_node_id(node_id)
+                .map(|old_node_id| (previous, old_node_id))
+        });
+
+        if let Some(CssProperty::AnimationName(CssPropertyValue::Exact(name))) =
+            style.get(&CssPropertyType::AnimationName)
+        {
+            // The animation keeps running as long as the node has the same animation-name
+            let animation = previous
+                .and_then(|(previous, old_node_id)| {
+                    previous.animations.animations.get(&old_node_id)
+                })
+                .filter(|animation| animation.name == name.0)
+                .cloned()
+                .unwrap_or_else(|| RunningAnimation {
+                    name: name.0.clone(),
+                    start: now,
+                    finished: false,
+                });
+            animation_state.animations.insert(node_id, animation);
+        }
+
+        let transition = match style.get(&CssPropertyType::Transition) {
+            Some(CssProperty::Transition(CssPropertyValue::Exact(transition))) => transition,
+            _ => continue,
+        };
+
+        // Transitions can only start if the node already existed in the last frame
+        let (previous, old_node_id) = match previous {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let old_style = previous.get_computed_style(old_node_id);
+        let old_transitions = previous.animations.transitions.get(&old_node_id);
+        let old_animated_properties = previous.animated_properties.get(&old_node_id);
+        let mut transitions = BTreeMap::new();
+
+        for (property_type, new_value) in &style {
+            let item = match transition.get_item(*property_type) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            let running = old_transitions.and_then(|t| t.get(property_type));
+
+            // The target value didn't change, keep the running transition
+            if let Some(running) = running.filter(|running| running.to == *new_value) {
+                transitions.insert(*property_type, running.clone());
+                continue;
+            }
+
+            let old_value = match old_style.get(property_type) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            if old_value == new_value {
+                continue;
+            }
+
+            // Start from the value that is currently on the screen, so that
+            // interrupted transitions don't jump
+            let from = old_animated_properties
+                .and_then(|properties| properties.get(property_type))
+                .unwrap_or(old_value);
+
+            if item.duration.milliseconds == 0 || from.interpolate(new_value, 0.0).is_none() {
+                continue;
+            }
+
+            transitions.insert(
+                *property_type,
+                RunningTransition {
+                    from: from.clone(),
+                    to: new_value.clone(),
+                    start: now + item.delay.to_duration(),
+                    duration: item.duration.to_duration(),
+                    timing_function: item.timing_function,
+                },
+            );
+        }
+
+        if !transitions.is_empty() {
+            animation_state.transitions.insert(node_id, transitions);
+        }
+    }
+
+    ui_description.animations = animation_state;
+    tick_animations(ui_description, css, now);
+}
+
+/// Evaluates all running transitions and animations at the time `now` and updates
+/// the `animated_properties`. Finished transitions are removed.
+///
+/// Returns whether any of the animated properties that can trigger a relayout changed.
+pub(crate) fn tick_animations<T>(
+    ui_description: &mut UiDescription<T>,
+    css: &Css,
+    now: Instant,
+) -> bool {
+    let mut animation_state =
+        mem::replace(&mut ui_description.animations, AnimationState::default());
+    let mut animated_properties = BTreeMap::<NodeId, BTreeMap<CssPropertyType, CssProperty>>::new();
+
+    for (node_id, animation) in animation_state.animations.iter_mut() {
+        let keyframes = match css.get_keyframes(&animation.name) {
+            Some(s) => s,
+            None => {
+                animation.finished = true;
+                continue;
+            }
+        };
+
+        let style = ui_description.get_computed_style(*node_id);
+        let (progress, finished) = get_animation_progress(&style, animation.start, now);
+        animation.finished = finished;
+
+        let progress = match progress {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let timing_function = match style.get(&CssPropertyType::AnimationTimingFunction) {
+            Some(CssProperty::AnimationTimingFunction(CssPropertyValue::Exact(t))) => t.0,
+            _ => StyleTimingFunction::default(),
+        };
+
+        animated_properties
+            .entry(*node_id)
+            .or_insert_with(BTreeMap::new)
+            .extend(interpolate_keyframes(
+                keyframes,
+                &style,
+                progress,
+                timing_function,
+            ));
+    }
+
+    // Transitions override animations
+    for (node_id, transitions) in animation_state.transitions.iter_mut() {
+        transitions.retain(
+            |property_type, transition| match transition.get_value(now) {
+                Some(value) => {
+                    animated_properties
+                        .entry(*node_id)
+                        .or_insert_with(BTreeMap::new)
+                        .insert(*property_type, value);
+                    true
+                }
+                None => false,
+            },
+        );
+    }
+    animation_state
+        .transitions
+        .retain(|_, transitions| !transitions.is_empty());
+
+    let layout_changed = !get_layout_properties(&ui_description.animated_properties)
+        .eq(get_layout_properties(&animated_properties));
+
+    ui_description.animations = animation_state;
+    ui_description.animated_properties = animated_properties;
+
+    layout_changed
+}
+
+/// Returns the animated properties that can trigger a relayout
+fn get_layout_properties(
+    animated_properties: &BTreeMap<NodeId, BTreeMap<CssPropertyType, CssProperty>>,
+) -> impl Iterator<Item = (&NodeId, &CssPropertyType, &CssProperty)> {
+    animated_properties
+        .iter()
+        .flat_map(|(node_id, properties)| {
+            properties
+                .iter()
+                .filter(|(property_type, _)| property_type.can_trigger_relayout())
+                .map(move |(property_type, property)| (node_id, property_type, property))
+        })
+}
+
+/// Returns the progress (0.0 - 1.0) of the animation in the current iteration, with the
+/// `animation-direction` already applied, and whether the animation has finished.
+///
+/// The progress is `None` if the animation isn't applied at the time `now`, i.e. during
+/// the delay or after the animation has finished (depending on the `animation-fill-mode`).
+fn get_animation_progress(
+    style: &BTreeMap<CssPropertyType, CssProperty>,
+    start: Instant,
+    now: Instant,
+) -> (Option<f32>, bool) {
+    use azul_css::StyleAnimationDirection::*;
+
+    let duration = match style.get(&CssPropertyType::AnimationDuration) {
+        Some(CssProperty::AnimationDuration(CssPropertyValue::Exact(d))) => d.0.milliseconds,
+        _ => 0,
+    } as f32;
+    let delay = match style.get(&CssPropertyType::AnimationDelay) {
+        Some(CssProperty::AnimationDelay(CssPropertyValue::Exact(d))) => d.0.milliseconds,
+        _ => 0,
+    } as f32;
+    let iterations = match style.get(&CssPropertyType::AnimationIterationCount) {
+        Some(CssProperty::AnimationIterationCount(CssPropertyValue::Exact(i))) => *i,
+        _ => StyleAnimationIterationCount::default(),
+    };
+    let iterations = match iterations {
+        StyleAnimationIterationCount::Count(c) => c.get().max(0.0),
+        StyleAnimationIterationCount::Infinite => f32::INFINITY,
+    };
+    let direction = match style.get(&CssPropertyType::AnimationDirection) {
+        Some(CssProperty::AnimationDirection(CssPropertyValue::Exact(d))) => *d,
+        _ => StyleAnimationDirection::default(),
+    };
+    let fill_mode = match style.get(&CssPropertyType::AnimationFillMode) {
+        Some(CssProperty::AnimationFillMode(CssPropertyValue::Exact(f))) => Some(*f),
+        _ => None,
+    };
+
+    let fills_backwards = match fill_mode {
+        Some(StyleAnimationFillMode::Backwards) | Some(StyleAnimationFillMode::Both) => true,
+        _ => false,
+    };
+    let fills_forwards = match fill_mode {
+        Some(StyleAnimationFillMode::Forwards) | Some(StyleAnimationFillMode::Both) => true,
+        _ => false,
+    };
+
+    let apply_direction = |progress: f32, iteration: f32| {
+        let is_odd = iteration % 2.0 == 1.0;
+        let is_reversed = match direction {
+            Normal => false,
+            Reverse => true,
+            Alternate => is_odd,
+            AlternateReverse => !is_odd,
+        };
+        if is_reversed {
+            1.0 - progress
+        } else {
+            progress
+        }
+    };
+
+    let elapsed = now.saturating_duration_since(start).as_secs_f32() * 1000.0;
+    let active_time = elapsed - delay;
+
+    if active_time < 0.0 {
+        return (
+            if fills_backwards {
+                Some(apply_direction(0.0, 0.0))
+            } else {
+                None
+            },
+            false,
+        );
+    }
+
+    let total_duration = if duration > 0.0 {
+        duration * iterations
+    } else {
+        0.0
+    };
+
+    if active_time >= total_duration {
+        let progress = if fills_forwards {
+            // Animations that stop on a whole iteration end on 100% of the last iteration
+            if iterations > 0.0 && iterations.fract() == 0.0 {
+                Some(apply_direction(1.0, iterations - 1.0))
+            } else {
+                Some(apply_direction(iterations.fract(), iterations.floor()))
+            }
+        } else {
+            None
+        };
+        return (progress, true);
+    }
+
+    let iteration = (active_time / duration).floor();
+    let progress = active_time / duration - iteration;
+    (Some(apply_direction(progress, iteration)), false)
+}
+
+/// Interpolates the properties of the keyframes at the given progress (0.0 - 1.0). The timing
+/// function applies to each pair of keyframes. If the `0%` or `100%` keyframe is missing,
+/// the computed value of the node is used instead.
+fn interpolate_keyframes(
+    keyframes: &CssKeyframes,
+    style: &BTreeMap<CssPropertyType, CssProperty>,
+    progress: f32,
+    timing_function: StyleTimingFunction,
+) -> BTreeMap<CssPropertyType, CssProperty> {
+    let property_types = keyframes
+        .keyframes
+        .iter()
+        .flat_map(|keyframe| keyframe.properties.iter().map(|p| p.get_type()))
+        .collect::<BTreeSet<_>>();
+
+    property_types
+        .into_iter()
+        .filter_map(|property_type| {
+            let mut frames = keyframes
+                .keyframes
+                .iter()
+                .filter_map(|keyframe| {
+                    let property = keyframe
+                        .properties
+                        .iter()
+                        .rev()
+                        .find(|p| p.get_type() == property_type)?;
+                    Some((keyframe.offset.get() / 100.0, property))
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(computed_value) = style.get(&property_type) {
+                if frames.first().map_or(true, |(offset, _)| *offset > 0.0) {
+                    frames.insert(0, (0.0, computed_value));
+                }
+                if frames.last().map_or(true, |(offset, _)| *offset < 1.0) {
+                    frames.push((1.0, computed_value));
+                }
+            }
+
+            let (first_offset, first) = *frames.first()?;
+            let (last_offset, last) = *frames.last()?;
+
+            let value = if progress <= first_offset {
+                first.clone()
+            } else if progress >= last_offset {
+                last.clone()
+            } else {
+                let end = frames.iter().position(|(offset, _)| *offset > progress)?;
+                let (start_offset, from) = frames[end - 1];
+                let (end_offset, to) = frames[end];
+                let t = timing_function
+                    .evaluate((progress - start_offset) / (end_offset - start_offset));
+                // Properties that can't be interpolated flip at the half of the keyframe
+                from.interpolate(to, t).unwrap_or_else(|| {
+                    if t < 0.5 {
+                        from.clone()
+                    } else {
+                        to.clone()
+                    }
+                })
+            };
+
+            Some((property_type, value))
+        })
+        .collect()
+}
+
+#[test]
+fn test_css_transition() {
+    use crate::{
+        diff::diff_arena,
+        dom::Dom,
+        ui_state::ui_state_from_dom,
+        window::{WindowSize, WindowTheme},
+    };
+    use azul_css::{
+        ColorU, CssDeclaration, CssDuration, CssPath, CssPathSelector, CssRuleBlock, LayoutWidth,
+        PixelValue, StyleBackgroundContent, StyleTransition, StyleTransitionItem, Stylesheet,
+        TransitionProperty,
+    };
+
+    struct Data;
+
+    let create_css = |color: ColorU| Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![CssRuleBlock {
+                path: CssPath {
+                    selectors: vec![CssPathSelector::Class("button".into())],
+                },
+                declarations: vec![
+                    CssDeclaration::Static(CssProperty::from(StyleTransition(vec![
+                        StyleTransitionItem {
+                            property: TransitionProperty::All,
+                            duration: CssDuration::from_millis(1000),
+                            timing_function: StyleTimingFunction::Linear,
+                            delay: CssDuration::from_millis(0),
+                        },
+                    ]))),
+                    CssDeclaration::Static(CssProperty::from(StyleBackgroundContent::Color(color))),
+                    CssDeclaration::Static(CssProperty::from(LayoutWidth(PixelValue::px(100.0)))),
+                ],
+                media_queries: Vec::new(),
+                important: false,
+            }],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let style = |css: &Css, previous: Option<&UiDescription<Data>>, now: Instant| {
+        let mut ui_state = ui_state_from_dom(Dom::div().with_class("button"), None);
+        let previous_frame = previous.map(|previous| {
+            let diff = diff_arena(
+                &previous.ui_descr_arena,
+                previous.ui_descr_root,
+                &ui_state.dom.arena,
+                ui_state.dom.root,
+            );
+            (previous, diff)
+        });
+        let mut ui_description = UiDescription::match_css_to_dom(
+            &mut ui_state,
+            css,
+            &mut None,
+            &mut None,
+            &BTreeMap::new(),
+            false,
+            &WindowSize::default(),
+            WindowTheme::default(),
+            None,
+        );
+        ui_description.update_animations(
+            previous_frame
+                .as_ref()
+                .map(|(previous, diff)| (*previous, diff)),
+            css,
+            now,
+        );
+        ui_description
+    };
+
+    let start = Instant::now();
+    let node_id = NodeId::new(0);
+    let black = create_css(ColorU::BLACK);
+    let white = create_css(ColorU::WHITE);
+
+    let first_frame = style(&black, None, start);
+    assert!(!first_frame.is_animating());
+
+    // Color changes: transition from black to white
+    let mut second_frame = style(&white, Some(&first_frame), start);
+    assert!(second_frame.is_animating());
+    assert!(second_frame.has_same_layout(
+        &first_frame,
+        &diff_arena(
+            &first_frame.ui_descr_arena,
+            first_frame.ui_descr_root,
+            &second_frame.ui_descr_arena,
+            second_frame.ui_descr_root,
+        )
+    ));
+
+    // Color is not layout-relevant, so no relayout is necessary
+    assert!(!second_frame.tick_animations(&white, start + Duration::from_millis(500)));
+    assert_eq!(
+        second_frame.animated_properties[&node_id][&CssPropertyType::BackgroundImage],
+        CssProperty::from(StyleBackgroundContent::Color(ColorU {
+            r: 128,
+            g: 128,
+            b: 128,
+            a: 255
+        }))
+    );
+
+    second_frame.tick_animations(&white, start + Duration::from_millis(1000));
+    assert!(!second_frame.is_animating());
+    assert!(second_frame.animated_properties.is_empty());
+}
+
+#[test]
+fn test_css_keyframe_animation() {
+    use crate::{
+        dom::Dom,
+        ui_state::ui_state_from_dom,
+        window::{WindowSize, WindowTheme},
+    };
+    use azul_css::{
+        CssDeclaration, CssDuration, CssKeyframe, CssPath, CssPathSelector, CssRuleBlock,
+        LayoutWidth, PercentageValue, PixelValue, StyleAnimationDuration, StyleAnimationName,
+        StyleAnimationTimingFunction, Stylesheet,
+    };
+
+    struct Data;
+
+    let width = |px: f32| CssProperty::from(LayoutWidth(PixelValue::px(px)));
+
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![CssRuleBlock {
+                path: CssPath {
+                    selectors: vec![CssPathSelector::Class("grow".into())],
+                },
+                declarations: vec![
+                    CssDeclaration::Static(width(100.0)),
+                    CssDeclaration::Static(CssProperty::from(StyleAnimationName("grow".into()))),
+                    CssDeclaration::Static(CssProperty::from(StyleAnimationDuration(
+                        CssDuration::from_millis(1000),
+                    ))),
+                    CssDeclaration::Static(CssProperty::from(StyleAnimationTimingFunction(
+                        StyleTimingFunction::Linear,
+                    ))),
+                    CssDeclaration::Static(CssProperty::from(StyleAnimationDirection::Alternate)),
+                    CssDeclaration::Static(CssProperty::from(StyleAnimationIterationCount::Count(
+                        azul_css::FloatValue::new(2.0),
+                    ))),
+                ],
+                media_queries: Vec::new(),
+                important: false,
+            }],
+            keyframes: vec![CssKeyframes {
+                name: "grow".into(),
+                keyframes: vec![CssKeyframe {
+                    offset: PercentageValue::new(50.0),
+                    properties: vec![width(200.0)],
+                }],
+            }],
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let mut ui_state = ui_state_from_dom(Dom::<Data>::div().with_class("grow"), None);
+    let mut ui_description = UiDescription::match_css_to_dom(
+        &mut ui_state,
+        &css,
+        &mut None,
+        &mut None,
+        &BTreeMap::new(),
+        false,
+        &WindowSize::default(),
+        WindowTheme::default(),
+        None,
+    );
+
+    let start = Instant::now();
+    let node_id = NodeId::new(0);
+    ui_description.update_animations(None, &css, start);
+    assert!(ui_description.is_animating());
+
+    // The width is layout-relevant, so every tick triggers a relayout
+    let at = |ms: u64| start + Duration::from_millis(ms);
+    assert!(ui_description.tick_animations(&css, at(250)));
+    assert_eq!(
+        ui_description.animated_properties[&node_id][&CssPropertyType::Width],
+        width(150.0)
+    );
+
+    // Second iteration runs backwards
+    ui_description.tick_animations(&css, at(1750));
+    assert_eq!(
+        ui_description.animated_properties[&node_id][&CssPropertyType::Width],
+        width(150.0)
+    );
+
+    // Finished, no fill-mode: the computed value is used again
+    assert!(ui_description.tick_animations(&css, at(2000)));
+    assert!(!ui_description.is_animating());
+    assert!(ui_description.animated_properties.is_empty());
+}
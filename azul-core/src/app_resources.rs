@@ -1,5 +1,11 @@
-use azul_css::{LayoutPoint, LayoutSize};
-use std::{fmt, path::PathBuf};
+use azul_css::{
+    Css, CssFontFace, CssFontFaceSource, FontFeature, FontVariation, LayoutPoint, LayoutSize,
+    StyleFontStretch, StyleFontStyle, StyleFontWeight,
+};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     display_list::GlyphInstance, ui_solver::ResolvedTextLayoutOptions, FastHashMap, FastHashSet,
@@ -143,6 +149,8 @@ pub struct AppResources {
     pub css_ids_to_image_ids: FastHashMap<CssImageId, ImageId>,
     /// Same as CssImageId -> ImageId, but for fonts, i.e. "Roboto" -> FontId(9)
     pub css_ids_to_font_ids: FastHashMap<CssFontId, FontId>,
+    /// The faces of the font families declared via `@font-face` rules
+    pub css_font_faces: FastHashMap<CssFontId, Vec<(FontFaceStyle, FontId)>>,
     /// Stores where the images were loaded from
     pub image_sources: FastHashMap<ImageId, ImageSource>,
     /// Stores where the fonts were loaded from
@@ -162,9 +170,14 @@ pub struct AppResources {
     /// The only thing remaining in memory permanently is the FontSource (which is only
     /// the string of the file path where the font was loaded from, so no huge memory pressure).
     /// The reason for this agressive strategy is that the
-    pub last_frame_font_keys: FastHashMap<ImmediateFontId, FastHashSet<Au>>,
+    pub last_frame_font_keys: FastHashMap<ImmediateFontId, FastHashSet<FontInstanceDescriptor>>,
+    /// Fonts that couldn't be loaded (i.e. system fonts that aren't installed), so that
+    /// their source isn't looked up again in every frame
+    pub failed_font_loads: FastHashSet<ImmediateFontId>,
     /// Stores long texts across frames
     pub text_cache: TextCache,
+    /// Language of the dictionary that is used to hyphenate texts with `hyphens: auto`
+    pub hyphenation_language: HyphenationLanguage,
 }
 
 macro_rules! unique_id {
@@ -224,8 +237,107 @@ impl fmt::Display for FontSource {
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ImmediateFontId {
-    Resolved(FontId),
-    Unresolved(CssFontId),
+    /// A registered font, plus the styles that the font face is missing
+    Resolved(FontId, FontSynthesis),
+    /// A system font, the face is selected when the font is loaded
+    Unresolved(CssFontId, FontFaceStyle),
+}
+
+/// The `font-weight`, `font-style` and `font-stretch` of a font face - either the
+/// properties of a text (i.e. the requested face) or of an available face of a family
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontFaceStyle {
+    pub weight: StyleFontWeight,
+    pub style: StyleFontStyle,
+    pub stretch: StyleFontStretch,
+}
+
+impl FontFaceStyle {
+    /// Selects the face that matches `self` best out of the `faces` of a font family, following
+    /// the font matching algorithm of CSS: the `font-stretch` is matched first, then the
+    /// `font-style`, then the `font-weight`.
+    pub fn find_best_match<'a, T>(
+        &self,
+        faces: &'a [(FontFaceStyle, T)],
+    ) -> Option<&'a (FontFaceStyle, T)> {
+        faces.iter().min_by_key(|(face, _)| {
+            (
+                self.stretch_distance(face.stretch),
+                self.style_distance(face.style),
+                self.weight_distance(face.weight),
+            )
+        })
+    }
+
+    /// Narrower faces are preferred for condensed text, wider faces for expanded text
+    fn stretch_distance(&self, stretch: StyleFontStretch) -> (bool, u8) {
+        let (desired, available) = (self.stretch as i8, stretch as i8);
+        let distance = (desired - available).abs() as u8;
+        if self.stretch <= StyleFontStretch::Normal {
+            (available > desired, distance)
+        } else {
+            (available < desired, distance)
+        }
+    }
+
+    /// Italic text falls back to oblique faces and vice versa, normal text prefers oblique faces
+    fn style_distance(&self, style: StyleFontStyle) -> u8 {
+        use azul_css::StyleFontStyle::*;
+        match (self.style, style) {
+            (Normal, Normal) | (Italic, Italic) | (Oblique, Oblique) => 0,
+            (Italic, Oblique) | (Oblique, Italic) | (Normal, Oblique) => 1,
+            (Italic, Normal) | (Oblique, Normal) | (Normal, Italic) => 2,
+        }
+    }
+
+    /// For weights between 400 and 500, the weights up to 500 are tried first, then the
+    /// lighter weights, then the heavier weights. Lighter text prefers lighter faces,
+    /// heavier text (above 500) prefers heavier faces.
+    fn weight_distance(&self, weight: StyleFontWeight) -> (u8, u16) {
+        let (desired, available) = (self.weight.0, weight.0);
+        let distance = (desired as i32 - available as i32).abs() as u16;
+        let rank = if (400..=500).contains(&desired) {
+            if (desired..=500).contains(&available) {
+                0
+            } else if available < desired {
+                1
+            } else {
+                2
+            }
+        } else if desired < 400 {
+            if available <= desired {
+                0
+            } else {
+                1
+            }
+        } else if available >= desired {
+            0
+        } else {
+            1
+        };
+        (rank, distance)
+    }
+}
+
+/// Styles that have to be synthesized by the renderer, because the
+/// font family has no face with the requested weight or style
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontSynthesis {
+    /// The glyphs have to be emboldened
+    pub bold: bool,
+    /// The glyphs have to be slanted
+    pub oblique: bool,
+}
+
+impl FontSynthesis {
+    /// Compares the `requested` style of a text to the style of the `available` face
+    pub fn new(requested: &FontFaceStyle, available: &FontFaceStyle) -> Self {
+        FontSynthesis {
+            bold: requested.weight.is_bold() && !available.weight.is_bold(),
+            oblique: requested.style != StyleFontStyle::Normal
+                && available.style == StyleFontStyle::Normal,
+        }
+    }
 }
 
 /// Raw image made up of raw pixels (either BRGA8 or A8)
@@ -242,22 +354,53 @@ pub struct LoadedFont {
     pub font_bytes: Vec<u8>,
     /// Index of the font in case the bytes indicate a font collection
     pub font_index: i32,
-    pub font_instances: FastHashMap<Au, FontInstanceKey>,
+    /// Styles that the font face is missing, applied to all font instances
+    pub synthesis: FontSynthesis,
+    pub font_instances: FastHashMap<FontInstanceDescriptor, FontInstanceKey>,
 }
 
 impl LoadedFont {
     /// Creates a new loaded font with 0 font instances
-    pub fn new(font_key: FontKey, font_bytes: Vec<u8>, font_index: i32) -> Self {
+    pub fn new(
+        font_key: FontKey,
+        font_bytes: Vec<u8>,
+        font_index: i32,
+        synthesis: FontSynthesis,
+    ) -> Self {
         Self {
             font_key,
             font_bytes,
             font_index,
+            synthesis,
             font_instances: FastHashMap::default(),
         }
     }
 
-    pub fn delete_font_instance(&mut self, size: &Au) {
-        self.font_instances.remove(size);
+    pub fn delete_font_instance(&mut self, descriptor: &FontInstanceDescriptor) {
+        self.font_instances.remove(descriptor);
+    }
+}
+
+/// Size and OpenType settings of a font instance: texts of the same font and size that are
+/// shaped with different `font-feature-settings` / `font-variant-numeric` or rendered with
+/// different `font-variation-settings` don't share their font instance
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontInstanceDescriptor {
+    pub size: Au,
+    /// OpenType features that the text is shaped with (in addition to kerning and ligatures)
+    pub features: Vec<FontFeature>,
+    /// Values of the variation axes, in case the font is a variable font
+    pub variations: Vec<FontVariation>,
+}
+
+impl FontInstanceDescriptor {
+    /// Font instance with the default features and variation axes of the font
+    pub fn from_size(size: Au) -> Self {
+        Self {
+            size,
+            features: Vec::new(),
+            variations: Vec::new(),
+        }
     }
 }
 
@@ -301,6 +444,25 @@ impl TextCache {
     }
 }
 
+/// Language of the hyphenation dictionary, see `AppResources::set_hyphenation_language`.
+/// The dictionaries are only available if azul is built with the `hyphenation` feature
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HyphenationLanguage {
+    EnglishUS,
+    EnglishGB,
+    German,
+    French,
+    Spanish,
+    Italian,
+    Dutch,
+}
+
+impl Default for HyphenationLanguage {
+    fn default() -> Self {
+        HyphenationLanguage::EnglishUS
+    }
+}
+
 /// Text broken up into `Tab`, `Word()`, `Return` characters
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Words {
@@ -345,11 +507,20 @@ pub enum WordType {
     Return,
     /// Space character
     Space,
+    /// Position between two parts of a word where the line may break, i.e. after
+    /// the `/` of an URL or between two CJK characters (empty range)
+    BreakOpportunity,
+    /// Position between two parts of a word where the line may break with a hyphen,
+    /// either a soft hyphen (`U+00AD`) or a hyphenation point of the dictionary
+    Hyphen,
+    /// Position between two parts of a word where the line may only break if the word
+    /// doesn't fit on a line by itself (`overflow-wrap: break-word`, empty range)
+    EmergencyBreak,
 }
 
 /// A paragraph of words that are shaped and scaled (* but not yet layouted / positioned*!)
 /// according to their final size in pixels.
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct ScaledWords {
     /// Font size (in pixels) that was used to scale these words
     pub font_size_px: f32,
@@ -364,6 +535,30 @@ pub struct ScaledWords {
     pub space_advance_px: f32,
     /// Glyph index of the space character
     pub space_codepoint: u32,
+    /// Glyph index + horizontal advance of the hyphen character in each font of the fallback
+    /// chain (indexed like `ScaledWord::glyph_fonts`), the hyphen is inserted at the end of
+    /// lines that break at a `WordType::Hyphen`
+    pub hyphen_glyphs: Vec<(u32, f32)>,
+    /// Glyph index + horizontal advance of the ellipsis character (`…`) in each font of the
+    /// fallback chain, the ellipsis is inserted at the end of lines that are cut off
+    /// (`text-overflow: ellipsis` and `line-clamp`)
+    pub ellipsis_glyphs: Vec<(u32, f32)>,
+    /// Metrics of the text decoration lines of each font of the fallback chain
+    pub font_metrics: Vec<FontMetrics>,
+}
+
+/// Position + thickness of the text decoration lines of a font, scaled to the font size.
+///
+/// The positions are the top edge of the line in pixels, relative to the baseline
+/// (positive values are below the baseline).
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
+pub struct FontMetrics {
+    pub underline_position: f32,
+    pub underline_thickness: f32,
+    pub strikeout_position: f32,
+    pub strikeout_thickness: f32,
+    /// Ascender of the font (negative), the overline is drawn at the ascender
+    pub ascender: f32,
 }
 
 /// Word that is scaled (to a font / font instance), but not yet positioned
@@ -374,6 +569,12 @@ pub struct ScaledWord {
     /// Horizontal advances of each glyph, necessary for
     /// hit-testing characters later on (for text selection).
     pub glyph_positions: Vec<GlyphPosition>,
+    /// Index of the font that each glyph was shaped with, in the fallback chain of
+    /// the text (`0` = the primary font, higher indices are fallback fonts)
+    pub glyph_fonts: Vec<usize>,
+    /// Bidi embedding level of the word (even = left-to-right, odd = right-to-left),
+    /// the glyphs of the word are already stored in visual order
+    pub bidi_level: u8,
     /// The sum of the width of all the characters in this word
     pub word_width: f32,
 }
@@ -389,6 +590,9 @@ pub struct WordPositions {
     /// Index of the word at which the line breaks + length of line
     /// (useful for text selection + horizontal centering)
     pub line_breaks: Vec<(WordIndex, LineLength)>,
+    /// Indices of the words that are followed by a hyphen, because the
+    /// line breaks after them at a `WordType::Hyphen`
+    pub hyphenated_words: Vec<WordIndex>,
     /// Horizontal width of the last line (in pixels), necessary for inline layout later on,
     /// so that the next text run can contine where the last text run left off.
     ///
@@ -410,6 +614,50 @@ pub struct WordPositions {
 #[derive(Debug, Clone, PartialEq)]
 pub struct LayoutedGlyphs {
     pub glyphs: Vec<GlyphInstance>,
+    /// Index of the font in the fallback chain of the text, one per glyph
+    /// (see `ScaledWord::glyph_fonts`)
+    pub glyph_fonts: Vec<usize>,
+    /// Index of the first glyph + y position of the baseline of each line
+    pub line_baselines: Vec<(usize, f32)>,
+}
+
+impl LayoutedGlyphs {
+    /// Splits the glyphs into runs of consecutive glyphs that use the same font, returns
+    /// the index of the font in the fallback chain + the glyphs of the run
+    pub fn font_runs(&self) -> Vec<(usize, &[GlyphInstance])> {
+        let font_of = |glyph_idx: usize| self.glyph_fonts.get(glyph_idx).cloned().unwrap_or(0);
+        let mut runs = Vec::new();
+        let mut run_start = 0;
+
+        for glyph_idx in 1..=self.glyphs.len() {
+            if glyph_idx == self.glyphs.len() || font_of(glyph_idx) != font_of(run_start) {
+                runs.push((font_of(run_start), &self.glyphs[run_start..glyph_idx]));
+                run_start = glyph_idx;
+            }
+        }
+
+        runs
+    }
+
+    /// Splits the glyphs into lines, returns the baseline of the line + the glyphs
+    /// of the line and their fonts
+    pub fn lines(&self) -> Vec<(f32, &[GlyphInstance], &[usize])> {
+        self.line_baselines
+            .iter()
+            .enumerate()
+            .map(|(line_idx, (line_start, baseline))| {
+                let line_end = self
+                    .line_baselines
+                    .get(line_idx + 1)
+                    .map_or(self.glyphs.len(), |(next_line_start, _)| *next_line_start);
+                (
+                    *baseline,
+                    &self.glyphs[*line_start..line_end],
+                    &self.glyph_fonts[*line_start..line_end],
+                )
+            })
+            .collect()
+    }
 }
 
 /// Iterator over glyphs that returns information about the cluster that this glyph belongs to.
@@ -597,6 +845,12 @@ impl AppResources {
 
     pub fn add_font_source(&mut self, font_id: FontId, font_source: FontSource) {
         self.font_sources.insert(font_id, font_source);
+        // The new source might load, even if the previous one couldn't be loaded
+        self.failed_font_loads
+            .retain(|failed_font| match failed_font {
+                ImmediateFontId::Resolved(failed_font_id, _) => *failed_font_id != font_id,
+                ImmediateFontId::Unresolved(_, _) => true,
+            });
     }
 
     /// Given a `FontId`, returns the bytes for that font or `None`, if the `FontId` is invalid.
@@ -613,6 +867,117 @@ impl AppResources {
         self.font_sources.remove(id);
     }
 
+    /// Registers the fonts declared in the `@font-face` rules of the `css`, so that
+    /// they can be used by their family name in the `font-family` property.
+    ///
+    /// Every face of a family gets its own `FontId`. If the source of a face changed (i.e.
+    /// because the stylesheet was hot-reloaded), the face gets a new `FontId` - the old font
+    /// is then garbage-collected at the end of the frame and the new font gets loaded instead.
+    ///
+    /// Replaces the previously registered `@font-face` rules, so it should be called once
+    /// when the stylesheet is set or hot-reloaded, not in every frame.
+    pub fn add_css_font_faces(&mut self, css: &Css) {
+        self.add_font_faces(css.get_font_faces());
+    }
+
+    /// Same as `add_css_font_faces`, but registers the `@font-face` rules of several
+    /// stylesheets at once (i.e. the stylesheets of all windows of an application)
+    pub fn add_font_faces<'a, I: IntoIterator<Item = &'a CssFontFace>>(&mut self, font_faces: I) {
+        let mut css_font_faces = FastHashMap::<CssFontId, Vec<(FontFaceStyle, FontId)>>::default();
+
+        for font_face in font_faces {
+            let font_source = match get_font_face_source(font_face) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            let face_style = FontFaceStyle {
+                weight: font_face.weight,
+                style: font_face.style,
+                stretch: font_face.stretch,
+            };
+
+            let existing_font_id = self
+                .css_font_faces
+                .get(&font_face.family)
+                .into_iter()
+                .flat_map(|faces| faces.iter())
+                .find(|(s, id)| *s == face_style && self.get_font_source(id) == Some(&font_source))
+                .map(|(_, id)| *id);
+
+            let font_id = match existing_font_id {
+                Some(id) => id,
+                None => {
+                    let id = FontId::new();
+                    self.add_font_source(id, font_source);
+                    id
+                }
+            };
+
+            // Same as in a browser, later faces override earlier faces with the same style
+            let faces = css_font_faces
+                .entry(font_face.family.clone())
+                .or_insert_with(Vec::new);
+            faces.retain(|(s, _)| *s != face_style);
+            faces.push((face_style, font_id));
+        }
+
+        // Delete the sources of the faces that were removed or replaced
+        let old_font_faces = std::mem::replace(&mut self.css_font_faces, css_font_faces);
+        for (family, faces) in old_font_faces {
+            for (_, font_id) in faces {
+                let is_used = self
+                    .css_font_faces
+                    .values()
+                    .any(|faces| faces.iter().any(|(_, id)| *id == font_id));
+                if is_used {
+                    continue;
+                }
+                self.delete_font_source(&font_id);
+                if self.css_ids_to_font_ids.get(&family) == Some(&font_id) {
+                    self.css_ids_to_font_ids.remove(&family);
+                }
+            }
+        }
+
+        // The FontId of the family name is the face that is used for regular text
+        for (family, faces) in self.css_font_faces.iter() {
+            if let Some((_, font_id)) = FontFaceStyle::default().find_best_match(faces) {
+                self.css_ids_to_font_ids.insert(family.clone(), *font_id);
+            }
+        }
+    }
+
+    /// Resolves the font family `css_font_id` to a font, selecting the face that matches the
+    /// `face_style` best (in case the family was declared via `@font-face` rules).
+    ///
+    /// If the selected face is not bold or italic enough, the returned `FontSynthesis`
+    /// signals that the renderer has to synthesize the bold or oblique glyphs.
+    /// Fonts that aren't registered are resolved when they are loaded from the system.
+    pub fn resolve_font_id(
+        &self,
+        css_font_id: &str,
+        face_style: &FontFaceStyle,
+    ) -> ImmediateFontId {
+        if let Some(faces) = self.css_font_faces.get(css_font_id) {
+            if let Some((available, font_id)) = face_style.find_best_match(faces) {
+                return ImmediateFontId::Resolved(
+                    *font_id,
+                    FontSynthesis::new(face_style, available),
+                );
+            }
+        }
+
+        match self.get_css_font_id(css_font_id) {
+            // Fonts that were added manually are assumed to be regular faces
+            Some(font_id) => ImmediateFontId::Resolved(
+                *font_id,
+                FontSynthesis::new(face_style, &FontFaceStyle::default()),
+            ),
+            None => ImmediateFontId::Unresolved(css_font_id.to_string(), *face_style),
+        }
+    }
+
     // -- TextId cache
 
     /// Adds a string to the internal text cache, but only store it as a string,
@@ -635,7 +1000,240 @@ impl AppResources {
         self.text_cache.clear_all_texts();
     }
 
+    /// Sets the language of the dictionary that texts with `hyphens: auto` are
+    /// hyphenated with (default: `HyphenationLanguage::EnglishUS`)
+    pub fn set_hyphenation_language(&mut self, language: HyphenationLanguage) {
+        self.hyphenation_language = language;
+    }
+
     pub fn get_loaded_font(&self, font_id: &ImmediateFontId) -> Option<&LoadedFont> {
         self.currently_registered_fonts.get(font_id)
     }
 }
+
+/// Returns the first source of the `@font-face` that can be loaded: font files that
+/// don't exist are skipped, if none of the sources exist, the first source is used
+fn get_font_face_source(font_face: &CssFontFace) -> Option<FontSource> {
+    font_face
+        .sources
+        .iter()
+        .find(|source| match source {
+            CssFontFaceSource::Url(path) => Path::new(path).exists(),
+            CssFontFaceSource::Local(_) => true,
+        })
+        .or_else(|| font_face.sources.first())
+        .map(|source| match source {
+            CssFontFaceSource::Url(path) => FontSource::File(PathBuf::from(path)),
+            CssFontFaceSource::Local(name) => FontSource::System(name.clone()),
+        })
+}
+
+#[test]
+fn test_font_face_style_find_best_match() {
+    use azul_css::StyleFontStyle::*;
+
+    fn face(weight: u16, style: StyleFontStyle, stretch: StyleFontStretch) -> FontFaceStyle {
+        FontFaceStyle {
+            weight: StyleFontWeight(weight),
+            style,
+            stretch,
+        }
+    }
+
+    let faces = vec![
+        (face(300, Normal, StyleFontStretch::Normal), "light"),
+        (face(400, Normal, StyleFontStretch::Normal), "regular"),
+        (face(700, Normal, StyleFontStretch::Normal), "bold"),
+        (face(400, Italic, StyleFontStretch::Normal), "italic"),
+        (face(400, Normal, StyleFontStretch::Condensed), "condensed"),
+    ];
+    let best_match = |weight, style, stretch| {
+        face(weight, style, stretch)
+            .find_best_match(&faces)
+            .map(|(_, name)| *name)
+    };
+
+    assert_eq!(
+        best_match(400, Normal, StyleFontStretch::Normal),
+        Some("regular")
+    );
+    assert_eq!(
+        best_match(700, Normal, StyleFontStretch::Normal),
+        Some("bold")
+    );
+    assert_eq!(
+        best_match(900, Normal, StyleFontStretch::Normal),
+        Some("bold")
+    );
+    assert_eq!(
+        best_match(600, Normal, StyleFontStretch::Normal),
+        Some("bold")
+    );
+    assert_eq!(
+        best_match(500, Normal, StyleFontStretch::Normal),
+        Some("regular")
+    );
+    assert_eq!(
+        best_match(100, Normal, StyleFontStretch::Normal),
+        Some("light")
+    );
+    assert_eq!(
+        best_match(400, Oblique, StyleFontStretch::Normal),
+        Some("italic")
+    );
+    // there is no bold italic face: the style is matched before the weight
+    assert_eq!(
+        best_match(700, Italic, StyleFontStretch::Normal),
+        Some("italic")
+    );
+    assert_eq!(
+        best_match(400, Normal, StyleFontStretch::ExtraCondensed),
+        Some("condensed")
+    );
+    assert_eq!(
+        best_match(400, Normal, StyleFontStretch::Expanded),
+        Some("regular")
+    );
+
+    assert_eq!(
+        FontSynthesis::new(&face(700, Italic, StyleFontStretch::Normal), &faces[3].0),
+        FontSynthesis {
+            bold: true,
+            oblique: false,
+        }
+    );
+}
+
+#[test]
+fn test_add_css_font_faces() {
+    use azul_css::Stylesheet;
+
+    fn font_face(family: &str, source: &str, weight: StyleFontWeight) -> CssFontFace {
+        CssFontFace {
+            family: family.into(),
+            sources: vec![CssFontFaceSource::Url(source.into())],
+            weight,
+            style: StyleFontStyle::Normal,
+            stretch: StyleFontStretch::Normal,
+        }
+    }
+
+    fn css(font_faces: Vec<CssFontFace>) -> Css {
+        Css {
+            stylesheets: vec![Stylesheet {
+                font_faces,
+                ..Default::default()
+            }],
+        }
+    }
+
+    let mut app_resources = AppResources::new();
+
+    app_resources.add_css_font_faces(&css(vec![
+        font_face("Roboto", "Roboto-Bold.ttf", StyleFontWeight::BOLD),
+        font_face("Roboto", "Roboto-Regular.ttf", StyleFontWeight::NORMAL),
+    ]));
+    let font_id = *app_resources.get_css_font_id("Roboto").unwrap();
+    assert_eq!(
+        app_resources.get_font_source(&font_id),
+        Some(&FontSource::File(PathBuf::from("Roboto-Regular.ttf")))
+    );
+
+    // Bold text uses the bold face, italic text uses the regular face with synthesized italics
+    let bold = FontFaceStyle {
+        weight: StyleFontWeight::BOLD,
+        ..Default::default()
+    };
+    let bold_font_id = match app_resources.resolve_font_id("Roboto", &bold) {
+        ImmediateFontId::Resolved(id, synthesis) => {
+            assert_eq!(synthesis, FontSynthesis::default());
+            id
+        }
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(
+        app_resources.get_font_source(&bold_font_id),
+        Some(&FontSource::File(PathBuf::from("Roboto-Bold.ttf")))
+    );
+    let italic = FontFaceStyle {
+        style: StyleFontStyle::Italic,
+        ..Default::default()
+    };
+    assert_eq!(
+        app_resources.resolve_font_id("Roboto", &italic),
+        ImmediateFontId::Resolved(
+            font_id,
+            FontSynthesis {
+                bold: false,
+                oblique: true,
+            }
+        )
+    );
+    assert_eq!(
+        app_resources.resolve_font_id("Arial", &italic),
+        ImmediateFontId::Unresolved("Arial".into(), italic)
+    );
+
+    // Re-adding the same font faces doesn't change the FontId, removed faces are deleted
+    app_resources.add_css_font_faces(&css(vec![font_face(
+        "Roboto",
+        "Roboto-Regular.ttf",
+        StyleFontWeight::NORMAL,
+    )]));
+    assert_eq!(app_resources.get_css_font_id("Roboto"), Some(&font_id));
+    assert!(!app_resources.has_font_source(&bold_font_id));
+
+    // Changing the source (i.e. on hot-reload) registers a new font
+    app_resources.add_css_font_faces(&css(vec![font_face(
+        "Roboto",
+        "Roboto-Light.ttf",
+        StyleFontWeight::NORMAL,
+    )]));
+    let new_font_id = *app_resources.get_css_font_id("Roboto").unwrap();
+    assert!(new_font_id != font_id);
+    assert!(!app_resources.has_font_source(&font_id));
+    assert_eq!(
+        app_resources.get_font_source(&new_font_id),
+        Some(&FontSource::File(PathBuf::from("Roboto-Light.ttf")))
+    );
+}
+
+#[test]
+fn test_layouted_glyphs_font_runs() {
+    let glyph = |index| GlyphInstance {
+        index,
+        point: LayoutPoint::zero(),
+        size: LayoutSize::zero(),
+    };
+
+    let layouted_glyphs = LayoutedGlyphs {
+        glyphs: (0..5).map(glyph).collect(),
+        glyph_fonts: vec![0, 0, 1, 1, 0],
+        line_baselines: vec![(0, 10.0), (3, 30.0)],
+    };
+    let runs = layouted_glyphs.font_runs();
+    assert_eq!(
+        runs.iter()
+            .map(|(font, glyphs)| (*font, glyphs.len()))
+            .collect::<Vec<_>>(),
+        vec![(0, 2), (1, 2), (0, 1)]
+    );
+    assert_eq!(runs[2].1[0].index, 4);
+
+    let lines = layouted_glyphs.lines();
+    assert_eq!(
+        lines
+            .iter()
+            .map(|(baseline, glyphs, fonts)| (*baseline, glyphs.len(), fonts.to_vec()))
+            .collect::<Vec<_>>(),
+        vec![(10.0, 3, vec![0, 0, 1]), (30.0, 2, vec![1, 0])]
+    );
+
+    let empty = LayoutedGlyphs {
+        glyphs: Vec::new(),
+        glyph_fonts: Vec::new(),
+        line_baselines: Vec::new(),
+    };
+    assert!(empty.font_runs().is_empty());
+    assert!(empty.lines().is_empty());
+}
@@ -300,6 +300,9 @@ pub struct CallbackInfo<'a, 'b, T: 'a> {
     pub cursor_relative_to_item: Option<(f32, f32)>,
     /// The (x, y) position of the mouse cursor, **relative to top left of the window**.
     pub cursor_in_viewport: Option<(f32, f32)>,
+    /// If the hit node is a `NodeType::RichText` node, the index of the span
+    /// that the mouse cursor is over (`None` if the cursor is between words)
+    pub hit_text_span: Option<usize>,
 }
 pub type CallbackReturn = UpdateScreen;
 pub type CallbackType<T> = fn(CallbackInfo<T>) -> CallbackReturn;
@@ -406,6 +409,7 @@ impl<'a, 'b, T: 'a> fmt::Debug for CallbackInfo<'a, 'b, T> {
              hit_test_items: {:?}, \
              cursor_relative_to_item: {:?}, \
              cursor_in_viewport: {:?}, \
+             hit_text_span: {:?}, \
              }}",
             self.focus_target,
             self.window_id,
@@ -414,6 +418,7 @@ impl<'a, 'b, T: 'a> fmt::Debug for CallbackInfo<'a, 'b, T> {
             self.hit_test_items,
             self.cursor_relative_to_item,
             self.cursor_in_viewport,
+            self.hit_text_span,
         )
     }
 }
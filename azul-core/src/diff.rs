@@ -1,11 +1,14 @@
-#![allow(unused_variables)]
-#![allow(dead_code)]
+//! DOM diffing - compares the DOM of the last frame with the DOM of the current frame,
+//! so that only the nodes that have changed need to be restyled and relayouted
 
 use crate::{
-    dom::{Dom, NodeData},
-    id_tree::{NodeHierarchy, NodeId},
+    dom::{Dom, NodeData, NodeType},
+    id_tree::{Arena, NodeHierarchy, NodeId},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    marker::PhantomData,
 };
-use std::{collections::BTreeMap, marker::PhantomData};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub(crate) struct DomRange<F: FrameMarker> {
@@ -42,18 +45,51 @@ pub struct DomDiff {
     pub(crate) changed_nodes: Vec<DomChange>,
     /// Which items simply need updating in terms of image source?
     pub(crate) only_replace_images: Vec<NodeId>,
-    /// Which nodes / subtrees need re-styling?
+    /// Which nodes / subtrees need re-styling? Sorted by the start node, the ranges don't overlap
     pub(crate) need_restyling: Vec<DomRange<NewState>>,
-    /// Which nodes need a re-layout?
-    /// For example A would be:
+    /// Which nodes need a re-layout because their content (text, image) or type changed?
+    /// Changes in the layout-relevant CSS properties are not tracked here, since they
+    /// are only known after restyling
     pub(crate) need_relayout: Vec<DomRange<NewState>>,
+    /// Maps the nodes of the new DOM to the nodes of the old DOM, for every node that
+    /// has a corresponding node in the old DOM (regardless if the node changed or not)
+    pub(crate) old_node_ids: BTreeMap<NodeId, NodeId>,
+    /// Nodes (in the new DOM) whose children were added, removed or restyled because
+    /// they changed themselves - necessary for the `+`, `~`, `:nth-last-child`,
+    /// `:nth-of-type` and `:empty` selectors, which depend on the siblings of a node
+    pub(crate) changed_children: BTreeSet<NodeId>,
 }
 
-type TreeDepth = usize;
-type ParentNodeId = NodeId;
-type LeafNodeId = NodeId;
+impl<F: FrameMarker> DomNode<F> {
+    fn new(id: NodeId) -> Self {
+        Self {
+            id,
+            marker: PhantomData,
+        }
+    }
+}
 
 impl<F: FrameMarker + PartialEq> DomRange<F> {
+    /// Creates a range that only contains the node itself
+    fn single(node_id: NodeId) -> Self {
+        Self {
+            start: DomNode::new(node_id),
+            end: DomNode::new(node_id),
+        }
+    }
+
+    /// Creates a range that spans the node and all of its children (recursive)
+    fn subtree(node_id: NodeId, hierarchy: &NodeHierarchy) -> Self {
+        let mut last_descendant = node_id;
+        while let Some(last_child) = hierarchy[last_descendant].last_child {
+            last_descendant = last_child;
+        }
+        Self {
+            start: DomNode::new(node_id),
+            end: DomNode::new(last_descendant),
+        }
+    }
+
     /// Is `other` a subtree of `self`? - Assumes that the DOM was
     /// constructed in a linear order, i.e. the child being within
     /// the parents start / end bounds
@@ -61,92 +97,293 @@ impl<F: FrameMarker + PartialEq> DomRange<F> {
         other.start.id.index() >= self.start.id.index()
             && other.end.id.index() <= self.end.id.index()
     }
+}
+
+impl DomDiff {
+    /// Returns whether the node (in the new DOM) has to be restyled, i.e. if the node,
+    /// one of its parents or its position in the parent has changed, or the node was added
+    pub fn needs_restyle(&self, node_id: NodeId) -> bool {
+        match self
+            .need_restyling
+            .binary_search_by(|range| range.start.id.cmp(&node_id))
+        {
+            Ok(_) => true,
+            Err(0) => false,
+            Err(idx) => self.need_restyling[idx - 1].contains(&DomRange::single(node_id)),
+        }
+    }
+
+    /// Returns whether the DOM needs to be relayouted, regardless of the CSS: nodes
+    /// were added or removed or the content (text, images) of a node changed
+    pub fn needs_relayout(&self) -> bool {
+        !self.changed_nodes.is_empty() || !self.need_relayout.is_empty()
+    }
+
+    /// Returns whether the old and the new DOM are (semantically) identical
+    pub fn is_empty(&self) -> bool {
+        !self.needs_relayout() && self.need_restyling.is_empty()
+    }
+
+    /// Returns the `NodeId` in the old DOM of a node in the new DOM, `None` if the node was added
+    pub fn get_old_node_id(&self, node_id: NodeId) -> Option<NodeId> {
+        self.old_node_ids.get(&node_id).cloned()
+    }
 
-    /// Compares two DOM ranges *without* looking at the DOM hashes (not equivalent to `==`)
-    pub fn equals_range(&self, other: &Self) -> bool {
-        other.start == self.start && other.end == self.end
+    /// Returns whether children of the node (in the new DOM) were added, removed or changed
+    /// their type, classes, ids or attributes, so that selectors depending on the siblings
+    /// of a node might not match anymore
+    pub fn children_changed(&self, node_id: NodeId) -> bool {
+        self.changed_children.contains(&node_id)
+    }
+
+    /// Returns the image nodes (in the new DOM) whose image source has changed
+    pub fn get_replaced_images(&self) -> &[NodeId] {
+        &self.only_replace_images
     }
 }
 
 // In order to test two DOM nodes for "equality", you'd need to
-// test if the node type, the classes and the ids are the same.
+// test if the node type, the classes, the ids and the attributes are the same.
 // The rest of the attributes can be ignored, since they are not
 // used by the CSS engine.
 //
-// Right now the CSS doesn't support adjacent modifiers ("+" selectors),
-// which will make this algorithm a bit more complex (but should be
-// solvable with adjacency lists).
-//
-// for each leaf node (sorted by depth):
-//     - if the node has only changed its position:
-//         - node doesn't need restyle (but may need re-layout when "+" selectors are implemented)
-//     - else insert it it
-//     - add the end added / removed nodes
+// Both trees are walked in parallel, children are matched by their
+// index in the parent (this is the common case for re-rendered lists
+// and tables: items only get appended or removed at the end):
 //
-// for each parent node (sorted by depth, bubble up):
-//     - if the parent has changed:
-//
-//     - ask if that child has affected that parents layout
-//          - if yes, set the parent to be restyled
-//     - ask if that child affects its siblings layout
-//          - if yes, add the siblings to the changeset
+// for each (old node, new node) pair:
+//     - if the type, the classes, the ids or the attributes changed:
+//         - the node and all of its children need to be restyled,
+//           since CSS paths can reference their parents
+//     - if the node used to be the last child, but isn't anymore (or vice versa):
+//         - restyle the subtree, because of the :last-child selector
+//     - if the text / image of the node changed:
+//         - the node needs to be relayouted, but not restyled
+//     - extra children in the new DOM are inserted as "added" (+ need restyling),
+//       extra children in the old DOM are inserted as "removed"
+//     - if one of the children was added, removed or changed its type, classes, ids
+//       or attributes, the node is marked in `changed_children`, so that the caller
+//       can restyle the siblings if the CSS has selectors that depend on them (`a + b`)
 //
+// Whether a layout-relevant CSS property changed is only known after the
+// restyle, so that has to be checked by the caller.
+
+const NODE_CHANGED_NOTHING: u8 = 0x00;
+const NODE_CHANGED_TYPE: u8 = 0x01;
+const NODE_CHANGED_CLASSES: u8 = 0x02;
+const NODE_CHANGED_IDS: u8 = 0x04;
+/// The node type stayed the same, but the content (text, image, etc.) changed
+const NODE_CHANGED_CONTENT: u8 = 0x08;
+const NODE_CHANGED_ATTRIBUTES: u8 = 0x10;
 
-const NODE_CHANGED_NOTHING: u8 = 0x01;
-const NODE_CHANGED_TYPE: u8 = 0x02;
-const NODE_CHANGED_CLASSES: u8 = 0x04;
-const NODE_CHANGED_IDS: u8 = 0x08;
+const NODE_NEEDS_RESTYLE: u8 =
+    NODE_CHANGED_TYPE | NODE_CHANGED_CLASSES | NODE_CHANGED_IDS | NODE_CHANGED_ATTRIBUTES;
+const NODE_NEEDS_RELAYOUT: u8 = NODE_CHANGED_TYPE | NODE_CHANGED_CONTENT;
 
 fn node_needs_restyle<T>(old: &NodeData<T>, new: &NodeData<T>) -> u8 {
+    // Fast path: Most nodes don't change between frames
+    if old.calculate_node_data_hash() == new.calculate_node_data_hash() {
+        return NODE_CHANGED_NOTHING;
+    }
+
     let mut result = NODE_CHANGED_NOTHING;
 
-    if old.get_node_type() != new.get_node_type() {
-        result &= NODE_CHANGED_TYPE;
+    if old.get_node_type().get_path() != new.get_node_type().get_path() {
+        result |= NODE_CHANGED_TYPE;
+    } else if old.get_node_type() != new.get_node_type() {
+        result |= NODE_CHANGED_CONTENT;
     }
 
     if old.get_classes() != new.get_classes() {
-        result &= NODE_CHANGED_CLASSES;
+        result |= NODE_CHANGED_CLASSES;
     }
 
     if old.get_ids() != new.get_ids() {
-        result &= NODE_CHANGED_CLASSES;
+        result |= NODE_CHANGED_IDS;
+    }
+
+    if old.get_attributes() != new.get_attributes() {
+        result |= NODE_CHANGED_ATTRIBUTES;
     }
 
     result
 }
 
-fn get_leaf_nodes_by_depth<T: FrameMarker>(
-    hierarchy: &NodeHierarchy,
-) -> BTreeMap<TreeDepth, BTreeMap<ParentNodeId, Vec<DomNode<T>>>> {
-    let mut map = BTreeMap::new();
-    let parent_nodes = hierarchy.get_parents_sorted_by_depth();
+/// Diffs the old and the new DOM, see `diff_arena`
+pub fn diff_dom_tree<T>(old: &Dom<T>, new: &Dom<T>) -> DomDiff {
+    diff_arena(&old.arena, old.root, &new.arena, new.root)
+}
 
-    for (parent_depth, parent_id) in parent_nodes {
-        for child_id in parent_id
-            .children(hierarchy)
-            .filter(|child| hierarchy[*child].first_child.is_some())
-        {
-            map.entry(parent_depth + 1)
-                .or_insert_with(|| BTreeMap::new())
-                .entry(parent_id)
-                .or_insert_with(|| Vec::new())
-                .push(DomNode {
-                    id: child_id,
-                    marker: PhantomData,
-                });
+/// Diffs two arena-allocated DOMs (for example the `ui_descr_arena` of the last
+/// frame with the DOM of the current frame), starting at the given root nodes
+pub fn diff_arena<T>(
+    old: &Arena<NodeData<T>>,
+    old_root: NodeId,
+    new: &Arena<NodeData<T>>,
+    new_root: NodeId,
+) -> DomDiff {
+    let mut diff = DomDiff::default();
+    if old.is_empty() || new.is_empty() {
+        if !new.is_empty() {
+            let added = DomRange::subtree(new_root, &new.node_layout);
+            diff.changed_nodes.push(DomChange::Added(added));
+            diff.need_restyling.push(added);
+        }
+        if !old.is_empty() {
+            let removed = DomRange::subtree(old_root, &old.node_layout);
+            diff.changed_nodes.push(DomChange::Removed(removed));
+        }
+        return diff;
+    }
+    diff_node(old, new, old_root, new_root, false, &mut diff);
+    diff
+}
+
+/// Diffs the node and its children, returns whether the type, classes, ids
+/// or attributes of the node itself changed
+fn diff_node<T>(
+    old: &Arena<NodeData<T>>,
+    new: &Arena<NodeData<T>>,
+    old_id: NodeId,
+    new_id: NodeId,
+    parent_needs_restyle: bool,
+    diff: &mut DomDiff,
+) -> bool {
+    let old_data = &old.node_data[old_id];
+    let new_data = &new.node_data[new_id];
+    let changes = node_needs_restyle(old_data, new_data);
+
+    let last_child_changed = old.node_layout[old_id].next_sibling.is_none()
+        != new.node_layout[new_id].next_sibling.is_none();
+
+    let needs_restyle =
+        parent_needs_restyle || changes & NODE_NEEDS_RESTYLE != 0 || last_child_changed;
+
+    // Children of a restyled node are contained in the range of the parent
+    if needs_restyle && !parent_needs_restyle {
+        diff.need_restyling
+            .push(DomRange::subtree(new_id, &new.node_layout));
+    }
+
+    if changes & NODE_NEEDS_RELAYOUT != 0 {
+        diff.need_relayout.push(DomRange::single(new_id));
+    }
+
+    if let (NodeType::Image(_), NodeType::Image(_), NODE_CHANGED_CONTENT) =
+        (old_data.get_node_type(), new_data.get_node_type(), changes)
+    {
+        diff.only_replace_images.push(new_id);
+    }
+
+    diff.old_node_ids.insert(new_id, old_id);
+
+    let mut old_children = old_id.children(&old.node_layout);
+    let mut new_children = new_id.children(&new.node_layout);
+    let mut children_changed = false;
+
+    loop {
+        match (old_children.next(), new_children.next()) {
+            (Some(old_child), Some(new_child)) => {
+                children_changed |= diff_node(old, new, old_child, new_child, needs_restyle, diff);
+            }
+            (None, Some(new_child)) => {
+                let added = DomRange::subtree(new_child, &new.node_layout);
+                diff.changed_nodes.push(DomChange::Added(added));
+                if !needs_restyle {
+                    diff.need_restyling.push(added);
+                }
+                children_changed = true;
+            }
+            (Some(old_child), None) => {
+                let removed = DomRange::subtree(old_child, &old.node_layout);
+                diff.changed_nodes.push(DomChange::Removed(removed));
+                children_changed = true;
+            }
+            (None, None) => break,
         }
     }
 
-    map
+    if children_changed {
+        diff.changed_children.insert(new_id);
+    }
+
+    changes & NODE_NEEDS_RESTYLE != 0
 }
 
-pub(crate) fn diff_dom_tree<T>(old: &Dom<T>, new: Dom<T>) -> DomDiff {
-    // TODO!
+#[test]
+fn test_dom_diff_unchanged() {
+    struct Data;
+
+    let create_dom = || -> Dom<Data> {
+        Dom::div()
+            .with_class("table")
+            .with_child(Dom::label("a"))
+            .with_child(Dom::label("b"))
+    };
+
+    let diff = diff_dom_tree(&create_dom(), &create_dom());
+    assert!(diff.is_empty());
+    assert!(!diff.needs_restyle(NodeId::new(0)));
+    assert!(!diff.needs_restyle(NodeId::new(2)));
+    assert_eq!(diff.get_old_node_id(NodeId::new(2)), Some(NodeId::new(2)));
+}
+
+#[test]
+fn test_dom_diff_changes() {
+    struct Data;
+
+    let old: Dom<Data> = Dom::div()
+        .with_child(Dom::div().with_class("a").with_child(Dom::label("1")))
+        .with_child(Dom::label("2"))
+        .with_child(Dom::label("3"));
+
+    // Changed class of the first div, changed text of the second label,
+    // appended a new label (the third label is not the last child anymore)
+    let new: Dom<Data> = Dom::div()
+        .with_child(Dom::div().with_class("b").with_child(Dom::label("1")))
+        .with_child(Dom::label("2 - changed"))
+        .with_child(Dom::label("3"))
+        .with_child(Dom::label("4"));
+
+    let diff = diff_dom_tree(&old, &new);
+
+    assert!(!diff.needs_restyle(NodeId::new(0)));
+    // class changed: node + children need restyle
+    assert!(diff.needs_restyle(NodeId::new(1)));
+    assert!(diff.needs_restyle(NodeId::new(2)));
+    // only the text changed: no restyle, but relayout
+    assert!(!diff.needs_restyle(NodeId::new(3)));
+    assert_eq!(diff.need_relayout, vec![DomRange::single(NodeId::new(3))]);
+    // :last-child changed
+    assert!(diff.needs_restyle(NodeId::new(4)));
+    // added node
+    assert!(diff.needs_restyle(NodeId::new(5)));
+    assert_eq!(diff.get_old_node_id(NodeId::new(5)), None);
+    assert_eq!(
+        diff.changed_nodes,
+        vec![DomChange::Added(DomRange::single(NodeId::new(5)))]
+    );
+    assert!(diff.needs_relayout());
+    // children were added / changed their class, relevant for `+` and `~` selectors
+    assert!(diff.children_changed(NodeId::new(0)));
+    assert!(!diff.children_changed(NodeId::new(1)));
+}
 
-    // let old_leaf_nodes = get_leaf_nodes_by_depth(&old.arena.node_layout, OldState { });
-    // let new_leaf_nodes = get_leaf_nodes_by_depth(&new.arena.node_layout, NewState { });
+#[test]
+fn test_node_needs_restyle() {
+    struct Data;
 
-    // depth -> parents (in order) -> [leaf children]
+    let a: NodeData<Data> = NodeData::div();
+    let b: NodeData<Data> = NodeData::div().with_ids(vec!["id".into()]);
+    let c: NodeData<Data> = NodeData::label("text");
+    let d: NodeData<Data> = NodeData::div().with_attributes(vec![("type".into(), "a".into())]);
 
-    DomDiff::default()
+    assert_eq!(node_needs_restyle(&a, &a), NODE_CHANGED_NOTHING);
+    assert_eq!(node_needs_restyle(&a, &b), NODE_CHANGED_IDS);
+    assert_eq!(node_needs_restyle(&a, &c), NODE_CHANGED_TYPE);
+    assert_eq!(node_needs_restyle(&a, &d), NODE_CHANGED_ATTRIBUTES);
+    assert_eq!(
+        node_needs_restyle(&c, &NodeData::label("other text")),
+        NODE_CHANGED_CONTENT
+    );
 }
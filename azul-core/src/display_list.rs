@@ -1,16 +1,17 @@
 use azul_css::{
     BoxShadowClipMode, BoxShadowPreDisplayItem, ColorU, CssPropertyValue, LayoutPoint, LayoutRect,
-    LayoutSize, LinearGradient, RadialGradient, StyleBackgroundPosition, StyleBackgroundRepeat,
-    StyleBackgroundSize, StyleBorderBottomColor, StyleBorderBottomLeftRadius,
-    StyleBorderBottomRightRadius, StyleBorderBottomStyle, StyleBorderBottomWidth,
-    StyleBorderLeftColor, StyleBorderLeftStyle, StyleBorderLeftWidth, StyleBorderRightColor,
-    StyleBorderRightStyle, StyleBorderRightWidth, StyleBorderTopColor, StyleBorderTopLeftRadius,
-    StyleBorderTopRightRadius, StyleBorderTopStyle, StyleBorderTopWidth,
+    LayoutSize, LayoutTransform, LinearGradient, RadialGradient, StyleBackgroundPosition,
+    StyleBackgroundRepeat, StyleBackgroundSize, StyleBorderBottomColor,
+    StyleBorderBottomLeftRadius, StyleBorderBottomRightRadius, StyleBorderBottomStyle,
+    StyleBorderBottomWidth, StyleBorderLeftColor, StyleBorderLeftStyle, StyleBorderLeftWidth,
+    StyleBorderRightColor, StyleBorderRightStyle, StyleBorderRightWidth, StyleBorderTopColor,
+    StyleBorderTopLeftRadius, StyleBorderTopRightRadius, StyleBorderTopStyle, StyleBorderTopWidth,
 };
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use crate::{
     app_resources::{FontInstanceKey, ImageInfo, ImageKey},
+    callbacks::{HitTestItem, PipelineId},
     dom::ScrollTagId,
     ui_solver::ExternalScrollId,
 };
@@ -79,6 +80,184 @@ impl CachedDisplayList {
             root: DisplayListMsg::Frame(DisplayListFrame::root(size)),
         }
     }
+
+    /// Software hit-test of the display list, without going through WebRender.
+    ///
+    /// Returns all tagged items under the `cursor` (in logical window coordinates)
+    /// in back-to-front order, the same order that the `App` uses for calling
+    /// callbacks. Items inside of a scroll frame are offset by the scroll position
+    /// of that frame (looked up in `scroll_positions`) and clipped to the scroll frame.
+    /// Fixed frames ignore the scroll position of their parents, sticky frames stick
+    /// to the edges of their nearest scroll frame. For transformed frames, the cursor
+    /// is transformed into the (untransformed) coordinate space of the frame.
+    pub fn hit_test(
+        &self,
+        pipeline_id: PipelineId,
+        cursor: LayoutPoint,
+        scroll_positions: &BTreeMap<ExternalScrollId, LayoutPoint>,
+    ) -> Vec<HitTestItem> {
+        let window_rect = LayoutRect::new(LayoutPoint::zero(), self.root.get_size());
+        let context = HitTestContext {
+            pipeline_id,
+            cursor,
+            scroll_positions,
+            window_rect,
+        };
+        let mut items = Vec::new();
+        hit_test_display_list_msg(
+            &self.root,
+            &context,
+            cursor,
+            LayoutPoint::zero(),
+            None,
+            window_rect,
+            &mut items,
+        );
+        items
+    }
+}
+
+/// Parameters of the software hit-test that stay the same for every item
+struct HitTestContext<'a> {
+    pipeline_id: PipelineId,
+    cursor: LayoutPoint,
+    scroll_positions: &'a BTreeMap<ExternalScrollId, LayoutPoint>,
+    /// Viewport of fixed frames
+    window_rect: LayoutRect,
+}
+
+/// `cursor` is the cursor in the coordinate space of the nearest transformed frame
+/// (or the window), `viewport` is the rect of the nearest scroll frame (or the window),
+/// which sticky frames stick to
+fn hit_test_display_list_msg(
+    msg: &DisplayListMsg,
+    context: &HitTestContext,
+    cursor: LayoutPoint,
+    scroll_offset: LayoutPoint,
+    clip: Option<LayoutRect>,
+    viewport: LayoutRect,
+    items: &mut Vec<HitTestItem>,
+) {
+    let (cursor, scroll_offset, clip, viewport) = match msg.get_frame().position {
+        FramePosition::Static => (cursor, scroll_offset, clip, viewport),
+        FramePosition::Fixed => (
+            context.cursor,
+            LayoutPoint::zero(),
+            None,
+            context.window_rect,
+        ),
+        FramePosition::Sticky(sticky) => {
+            let offset =
+                sticky.get_offset(offset_rect(msg.get_frame().rect, scroll_offset), viewport);
+            (
+                cursor,
+                LayoutPoint::new(scroll_offset.x - offset.x, scroll_offset.y - offset.y),
+                clip,
+                viewport,
+            )
+        }
+    };
+
+    // The transform is applied before scrolling, so the cursor is un-scrolled
+    // first and everything below the frame is in the untransformed space
+    let (cursor, scroll_offset, clip) = match msg.get_frame().transform {
+        None => (cursor, scroll_offset, clip),
+        Some(transform) => {
+            if clip.map(|c| !c.contains(&cursor)).unwrap_or(false) {
+                return;
+            }
+            let inverse = match transform.inverse() {
+                Some(s) => s,
+                None => return, // transform is flattened to a line or a point
+            };
+            let unscrolled_cursor =
+                LayoutPoint::new(cursor.x + scroll_offset.x, cursor.y + scroll_offset.y);
+            (
+                inverse.transform_point(unscrolled_cursor),
+                LayoutPoint::zero(),
+                None,
+            )
+        }
+    };
+
+    let (frame, child_scroll_offset, child_clip, child_viewport) = match msg {
+        DisplayListMsg::Frame(f) => (f, scroll_offset, clip, viewport),
+        DisplayListMsg::ScrollFrame(sf) => {
+            let scroll_position = context
+                .scroll_positions
+                .get(&sf.scroll_id)
+                .cloned()
+                .unwrap_or(LayoutPoint::zero());
+            let frame_rect = offset_rect(sf.frame.rect, scroll_offset);
+            (
+                &sf.frame,
+                LayoutPoint::new(
+                    scroll_offset.x + scroll_position.x,
+                    scroll_offset.y + scroll_position.y,
+                ),
+                Some(match clip {
+                    Some(c) => intersect_rect(c, frame_rect),
+                    None => frame_rect,
+                }),
+                frame_rect,
+            )
+        }
+    };
+
+    let frame_rect = offset_rect(frame.rect, scroll_offset);
+    let is_clipped = clip.map(|c| !c.contains(&cursor)).unwrap_or(false)
+        || frame
+            .clip_rect
+            .map(|c| !offset_rect(c, scroll_offset).contains(&cursor))
+            .unwrap_or(false);
+
+    if let Some(tag) = frame.tag {
+        if !is_clipped && frame_rect.contains(&cursor) {
+            items.push(HitTestItem {
+                pipeline: context.pipeline_id,
+                tag,
+                point_in_viewport: context.cursor,
+                point_relative_to_item: LayoutPoint::new(
+                    cursor.x - frame_rect.origin.x,
+                    cursor.y - frame_rect.origin.y,
+                ),
+            });
+        }
+    }
+
+    for child in &frame.children {
+        hit_test_display_list_msg(
+            child,
+            context,
+            cursor,
+            child_scroll_offset,
+            child_clip,
+            child_viewport,
+            items,
+        );
+    }
+}
+
+/// Moves the rect by the negative scroll offset
+fn offset_rect(rect: LayoutRect, scroll_offset: LayoutPoint) -> LayoutRect {
+    LayoutRect::new(
+        LayoutPoint::new(
+            rect.origin.x - scroll_offset.x,
+            rect.origin.y - scroll_offset.y,
+        ),
+        rect.size,
+    )
+}
+
+fn intersect_rect(a: LayoutRect, b: LayoutRect) -> LayoutRect {
+    let min_x = a.min_x().max(b.min_x());
+    let min_y = a.min_y().max(b.min_y());
+    let max_x = a.max_x().min(b.max_x()).max(min_x);
+    let max_y = a.max_y().min(b.max_y()).max(min_y);
+    LayoutRect::new(
+        LayoutPoint::new(min_x, min_y),
+        LayoutSize::new(max_x - min_x, max_y - min_y),
+    )
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -113,10 +292,14 @@ impl DisplayListMsg {
     }
 
     pub fn get_size(&self) -> LayoutSize {
+        self.get_frame().rect.size
+    }
+
+    pub fn get_frame(&self) -> &DisplayListFrame {
         use self::DisplayListMsg::*;
         match self {
-            Frame(f) => f.rect.size,
-            ScrollFrame(sf) => sf.frame.rect.size,
+            Frame(f) => f,
+            ScrollFrame(sf) => &sf.frame,
         }
     }
 }
@@ -141,6 +324,13 @@ pub struct DisplayListFrame {
     pub border_radius: StyleBorderRadius,
     pub clip_rect: Option<LayoutRect>,
     pub tag: Option<ItemTag>,
+    /// Whether the frame scrolls with its parent scroll frames
+    pub position: FramePosition,
+    /// Transform of the frame and its children (`transform` + `transform-origin`),
+    /// in the same coordinate space as the `rect`
+    pub transform: Option<LayoutTransform>,
+    /// Filters of the frame and its children (`filter` + `opacity`), applied in order
+    pub filters: Vec<FrameFilter>,
     pub content: Vec<LayoutRectContent>,
     pub children: Vec<DisplayListMsg>,
 }
@@ -155,6 +345,15 @@ impl fmt::Debug for DisplayListFrame {
         if let Some(tag) = &self.tag {
             write!(f, "\r\ntag: ({}, {}),", tag.0, tag.1)?;
         }
+        if self.position != FramePosition::Static {
+            write!(f, "\r\nposition: {:#?},", self.position)?;
+        }
+        if let Some(transform) = &self.transform {
+            write!(f, "\r\ntransform: {:#?},", transform)?;
+        }
+        if !self.filters.is_empty() {
+            write!(f, "\r\nfilters: {:#?},", self.filters)?;
+        }
         if !self.content.is_empty() {
             write!(f, "\r\ncontent: {:#?}", self.content)?;
         }
@@ -175,12 +374,119 @@ impl DisplayListFrame {
                 size: dimensions,
             },
             border_radius: StyleBorderRadius::default(),
+            position: FramePosition::Static,
+            transform: None,
+            filters: Vec::new(),
             content: vec![],
             children: vec![],
         }
     }
 }
 
+/// Position of a frame relative to its parent scroll frames
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum FramePosition {
+    /// Frame scrolls with its parent scroll frames
+    Static,
+    /// Frame is positioned relative to the window (`position: fixed`),
+    /// it isn't scrolled or clipped by its parent frames
+    Fixed,
+    /// Frame scrolls with its parent scroll frames, but sticks to the edges
+    /// of the nearest scroll frame (`position: sticky`)
+    Sticky(StickyFrame),
+}
+
+impl Default for FramePosition {
+    fn default() -> Self {
+        FramePosition::Static
+    }
+}
+
+/// Resolved `filter` function or `opacity` of a frame, equivalent to a WebRender filter
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub enum FrameFilter {
+    /// Opacity, from `0.0` (transparent) to `1.0` (opaque)
+    Opacity(f32),
+    /// Gaussian blur, the radius (standard deviation) is in pixels
+    Blur(f32),
+    /// `0.0` leaves the content unchanged, `1.0` is completely gray
+    Grayscale(f32),
+    /// `1.0` leaves the content unchanged, `0.0` is completely black
+    Brightness(f32),
+    /// Shadow that follows the shape (alpha channel) of the content
+    DropShadow {
+        offset: LayoutPoint,
+        blur_radius: f32,
+        color: ColorU,
+    },
+}
+
+/// Offsets of a `position: sticky` frame, equivalent to a WebRender sticky frame
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct StickyFrame {
+    /// Distance to the top edge of the nearest scroll frame at which the frame starts
+    /// to stick, `None` if the frame doesn't stick to the top edge
+    pub top: Option<f32>,
+    pub right: Option<f32>,
+    pub bottom: Option<f32>,
+    pub left: Option<f32>,
+    /// How far the frame can be moved up / down before it leaves its containing block
+    pub vertical_offset_bounds: StickyOffsetBounds,
+    /// How far the frame can be moved left / right before it leaves its containing block
+    pub horizontal_offset_bounds: StickyOffsetBounds,
+}
+
+/// Minimum (negative) and maximum (positive) offset of a sticky frame on one axis
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct StickyOffsetBounds {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl StickyFrame {
+    /// Returns how far the frame has to be moved to stick to the edges of the `viewport`
+    /// (the scroll frame rect) - both rects have to be in the same (scrolled) coordinate space
+    pub fn get_offset(&self, frame_rect: LayoutRect, viewport: LayoutRect) -> LayoutPoint {
+        LayoutPoint::new(
+            get_sticky_offset(
+                (frame_rect.min_x(), frame_rect.max_x()),
+                (viewport.min_x(), viewport.max_x()),
+                (self.left, self.right),
+                self.horizontal_offset_bounds,
+            ),
+            get_sticky_offset(
+                (frame_rect.min_y(), frame_rect.max_y()),
+                (viewport.min_y(), viewport.max_y()),
+                (self.top, self.bottom),
+                self.vertical_offset_bounds,
+            ),
+        )
+    }
+}
+
+/// Sticky offset on one axis - if both edges are set, the start (top / left) edge wins
+fn get_sticky_offset(
+    (frame_start, frame_end): (f32, f32),
+    (viewport_start, viewport_end): (f32, f32),
+    (margin_start, margin_end): (Option<f32>, Option<f32>),
+    bounds: StickyOffsetBounds,
+) -> f32 {
+    let mut offset = 0.0;
+
+    if let Some(margin_end) = margin_end {
+        offset = (viewport_end - margin_end - frame_end).min(0.0);
+    }
+
+    if let Some(margin_start) = margin_start {
+        let start_offset = viewport_start + margin_start - frame_start;
+        if start_offset > 0.0 {
+            offset = start_offset;
+        }
+    }
+
+    offset.max(bounds.min).min(bounds.max)
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ImageRendering {
     Auto,
@@ -316,6 +622,39 @@ pub enum LayoutRectContent {
         shadow: StyleBoxShadow,
         clip_mode: BoxShadowClipMode,
     },
+    /// Horizontal line, i.e. an underline or a strikethrough line of a text
+    Line {
+        /// Area of the line - for wavy lines, the area that the wave oscillates in
+        bounds: LayoutRect,
+        thickness: f32,
+        color: ColorU,
+        style: LineStyle,
+        clip: Option<LayoutRect>,
+    },
+    /// Content with shadows below it (`text-shadow`), the first shadow is drawn on top
+    Shadowed {
+        shadows: Vec<TextShadow>,
+        content: Vec<LayoutRectContent>,
+    },
+}
+
+/// Style of a `LayoutRectContent::Line`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LineStyle {
+    Solid,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+/// Shadow of the content of a `LayoutRectContent::Shadowed`, the shadow is a copy
+/// of the content in the shadow color, moved by the offset and blurred
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct TextShadow {
+    pub offset: LayoutPoint,
+    pub color: ColorU,
+    /// Radius (standard deviation) of the gaussian blur in pixels
+    pub blur_radius: f32,
 }
 
 #[derive(Clone, PartialEq, PartialOrd)]
@@ -349,3 +688,161 @@ impl RectBackground {
         }
     }
 }
+
+#[test]
+fn test_hit_test_display_list() {
+    let pipeline_id = PipelineId::DUMMY;
+    let scroll_id = ExternalScrollId(1, pipeline_id);
+
+    let mut child = DisplayListFrame::root(LayoutSize::new(50.0, 50.0));
+    child.rect.origin = LayoutPoint::new(0.0, 100.0);
+    child.tag = Some((2, 0));
+
+    let mut scroll_frame = DisplayListFrame::root(LayoutSize::new(100.0, 100.0));
+    scroll_frame.tag = Some((1, 0));
+    scroll_frame.children.push(DisplayListMsg::Frame(child));
+
+    let display_list = CachedDisplayList {
+        root: DisplayListMsg::ScrollFrame(DisplayListScrollFrame {
+            content_rect: LayoutRect::new(LayoutPoint::zero(), LayoutSize::new(100.0, 150.0)),
+            scroll_id,
+            scroll_tag: ScrollTagId(1),
+            frame: scroll_frame,
+        }),
+    };
+
+    // Child is outside of the scroll frame, so it is clipped
+    let mut scroll_positions = BTreeMap::new();
+    let items = display_list.hit_test(
+        pipeline_id,
+        LayoutPoint::new(10.0, 110.0),
+        &scroll_positions,
+    );
+    assert!(items.is_empty());
+
+    // Scrolled into view, so the child is hit (back-to-front order)
+    scroll_positions.insert(scroll_id, LayoutPoint::new(0.0, 50.0));
+    let items = display_list.hit_test(pipeline_id, LayoutPoint::new(10.0, 60.0), &scroll_positions);
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].tag, (1, 0));
+    assert_eq!(items[1].tag, (2, 0));
+    assert_eq!(
+        items[1].point_relative_to_item,
+        LayoutPoint::new(10.0, 10.0)
+    );
+}
+
+#[test]
+fn test_hit_test_fixed_and_sticky_frames() {
+    let pipeline_id = PipelineId::DUMMY;
+    let scroll_id = ExternalScrollId(1, pipeline_id);
+
+    // Header that sticks to the top of the scroll frame
+    let mut header = DisplayListFrame::root(LayoutSize::new(100.0, 10.0));
+    header.rect.origin = LayoutPoint::new(0.0, 20.0);
+    header.tag = Some((2, 0));
+    header.position = FramePosition::Sticky(StickyFrame {
+        top: Some(0.0),
+        right: None,
+        bottom: None,
+        left: None,
+        vertical_offset_bounds: StickyOffsetBounds {
+            min: -20.0,
+            max: 120.0,
+        },
+        horizontal_offset_bounds: StickyOffsetBounds { min: 0.0, max: 0.0 },
+    });
+
+    // Toolbar that doesn't move when the scroll frame scrolls
+    let mut toolbar = DisplayListFrame::root(LayoutSize::new(20.0, 20.0));
+    toolbar.rect.origin = LayoutPoint::new(80.0, 80.0);
+    toolbar.tag = Some((3, 0));
+    toolbar.position = FramePosition::Fixed;
+
+    let mut scroll_frame = DisplayListFrame::root(LayoutSize::new(100.0, 100.0));
+    scroll_frame.tag = Some((1, 0));
+    scroll_frame.children.push(DisplayListMsg::Frame(header));
+    scroll_frame.children.push(DisplayListMsg::Frame(toolbar));
+
+    let display_list = CachedDisplayList {
+        root: DisplayListMsg::ScrollFrame(DisplayListScrollFrame {
+            content_rect: LayoutRect::new(LayoutPoint::zero(), LayoutSize::new(100.0, 150.0)),
+            scroll_id,
+            scroll_tag: ScrollTagId(1),
+            frame: scroll_frame,
+        }),
+    };
+
+    let mut scroll_positions = BTreeMap::new();
+    scroll_positions.insert(scroll_id, LayoutPoint::new(0.0, 50.0));
+
+    // The header would be scrolled out of view, but it sticks to the top edge
+    let items = display_list.hit_test(pipeline_id, LayoutPoint::new(10.0, 5.0), &scroll_positions);
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].tag, (2, 0));
+    assert_eq!(items[1].point_relative_to_item, LayoutPoint::new(10.0, 5.0));
+
+    // The toolbar stays at the same position
+    let items = display_list.hit_test(pipeline_id, LayoutPoint::new(90.0, 90.0), &scroll_positions);
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].tag, (3, 0));
+    let items = display_list.hit_test(pipeline_id, LayoutPoint::new(90.0, 40.0), &scroll_positions);
+    assert_eq!(items.len(), 1);
+}
+
+#[test]
+fn test_hit_test_transformed_frames() {
+    use azul_css::{
+        FloatValue, PixelValue, StyleTransform, StyleTransformFunction, StyleTransformOrigin,
+    };
+
+    let pipeline_id = PipelineId::DUMMY;
+
+    // 100x20 bar, rotated by 90 degrees around its center, so that it covers
+    // the area from (40, -40) to (60, 60) instead of (0, 0) to (100, 20)
+    let mut bar = DisplayListFrame::root(LayoutSize::new(100.0, 20.0));
+    bar.tag = Some((2, 0));
+    bar.transform = Some(
+        StyleTransform(vec![StyleTransformFunction::Rotate(FloatValue::new(90.0))])
+            .get_layout_transform(StyleTransformOrigin::default(), bar.rect),
+    );
+
+    let mut child = DisplayListFrame::root(LayoutSize::new(10.0, 20.0));
+    child.rect.origin = LayoutPoint::new(90.0, 0.0);
+    child.tag = Some((3, 0));
+    bar.children.push(DisplayListMsg::Frame(child));
+
+    let mut root = DisplayListFrame::root(LayoutSize::new(200.0, 200.0));
+    root.tag = Some((1, 0));
+    root.children.push(DisplayListMsg::Frame(bar));
+    let display_list = CachedDisplayList {
+        root: DisplayListMsg::Frame(root),
+    };
+    let scroll_positions = BTreeMap::new();
+
+    // Inside of the untransformed rect, but outside of the rotated rect
+    let items = display_list.hit_test(pipeline_id, LayoutPoint::new(90.0, 10.0), &scroll_positions);
+    assert_eq!(items.len(), 1);
+
+    // The right end of the bar (and the child) is now at the bottom
+    let items = display_list.hit_test(pipeline_id, LayoutPoint::new(55.0, 55.0), &scroll_positions);
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[2].tag, (3, 0));
+    assert_eq!(items[2].point_in_viewport, LayoutPoint::new(55.0, 55.0));
+    let relative = items[2].point_relative_to_item;
+    assert!((relative.x - 5.0).abs() < 0.001 && (relative.y - 5.0).abs() < 0.001);
+
+    // The translation of a percentage is relative to the size of the frame
+    let transform = StyleTransform(vec![StyleTransformFunction::Translate(
+        PixelValue::percent(50.0),
+        PixelValue::px(10.0),
+    )])
+    .get_layout_transform(
+        StyleTransformOrigin::default(),
+        LayoutRect::new(LayoutPoint::zero(), LayoutSize::new(100.0, 20.0)),
+    );
+    assert_eq!(
+        transform.transform_point(LayoutPoint::new(0.0, 0.0)),
+        LayoutPoint::new(50.0, 10.0)
+    );
+}
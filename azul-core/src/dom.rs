@@ -93,6 +93,9 @@ pub enum NodeType<T> {
     Label(DomString),
     /// Larger amount of text, that has to be cached
     Text(TextId),
+    /// Paragraph of text spans with different styles (i.e. a sentence with one bold
+    /// word or a link), laid out inline as one text block
+    RichText(Vec<TextSpan<T>>),
     /// An image that is rendered by WebRender. The id is acquired by the
     /// `AppState::add_image()` function
     Image(ImageId),
@@ -112,6 +115,7 @@ impl<T> NodeType<T> {
             Label(s) => Some(format!("{}", s)),
             Image(id) => Some(format!("image({:?})", id)),
             Text(t) => Some(format!("textid({:?})", t)),
+            RichText(spans) => Some(spans.iter().map(|span| span.text.as_str()).collect()),
             GlTexture(g) => Some(format!("gltexture({:?})", g)),
             IFrame(i) => Some(format!("iframe({:?})", i)),
         }
@@ -127,6 +131,7 @@ impl<T> fmt::Debug for NodeType<T> {
             Div => write!(f, "NodeType::Div"),
             Label(a) => write!(f, "NodeType::Label {{ {:?} }}", a),
             Text(a) => write!(f, "NodeType::Text {{ {:?} }}", a),
+            RichText(a) => write!(f, "NodeType::RichText {{ {:?} }}", a),
             Image(a) => write!(f, "NodeType::Image {{ {:?} }}", a),
             GlTexture((ptr, cb)) => write!(
                 f,
@@ -149,6 +154,7 @@ impl<T> Clone for NodeType<T> {
             Div => Div,
             Label(a) => Label(a.clone()),
             Text(a) => Text(a.clone()),
+            RichText(a) => RichText(a.clone()),
             Image(a) => Image(a.clone()),
             GlTexture((ptr, a)) => GlTexture((ptr.clone(), a.clone())),
             IFrame((ptr, a)) => IFrame((ptr.clone(), a.clone())),
@@ -168,6 +174,7 @@ impl<T> Hash for NodeType<T> {
             Div => {}
             Label(a) => a.hash(state),
             Text(a) => a.hash(state),
+            RichText(a) => a.hash(state),
             Image(a) => a.hash(state),
             GlTexture((ptr, a)) => {
                 ptr.hash(state);
@@ -188,6 +195,7 @@ impl<T> PartialEq for NodeType<T> {
             (Div, Div) => true,
             (Label(a), Label(b)) => a == b,
             (Text(a), Text(b)) => a == b,
+            (RichText(a), RichText(b)) => a == b,
             (Image(a), Image(b)) => a == b,
             (GlTexture((ptr_a, a)), GlTexture((ptr_b, b))) => a == b && ptr_a == ptr_b,
             (IFrame((ptr_a, a)), IFrame((ptr_b, b))) => a == b && ptr_a == ptr_b,
@@ -204,7 +212,7 @@ impl<T> NodeType<T> {
         use self::NodeType::*;
         match self {
             Div => NodeTypePath::Div,
-            Label(_) | Text(_) => NodeTypePath::P,
+            Label(_) | Text(_) | RichText(_) => NodeTypePath::P,
             Image(_) => NodeTypePath::Img,
             GlTexture(_) => NodeTypePath::Texture,
             IFrame(_) => NodeTypePath::IFrame,
@@ -212,6 +220,81 @@ impl<T> NodeType<T> {
     }
 }
 
+/// One span of a `NodeType::RichText` paragraph. The text of all spans of a
+/// paragraph is laid out as one text block, so that the lines wrap across spans.
+pub struct TextSpan<T> {
+    /// Text of the span
+    pub text: DomString,
+    /// Properties that override the style of the paragraph for this span
+    /// (`font-family`, `font-size`, `font-weight`, `color`, etc.)
+    pub style: Vec<CssProperty>,
+    /// Callbacks that are called when the cursor is over the span. Only
+    /// `EventFilter::Hover` callbacks are supported, all others are ignored.
+    pub callbacks: Vec<(EventFilter, Callback<T>)>,
+}
+
+impl<T> TextSpan<T> {
+    /// Creates a new span with the style of the paragraph and no callbacks
+    #[inline]
+    pub fn new<S: Into<DomString>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            style: Vec::new(),
+            callbacks: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn with_style(mut self, property: CssProperty) -> Self {
+        self.style.push(property);
+        self
+    }
+
+    #[inline]
+    pub fn with_callback<O: Into<EventFilter>>(mut self, on: O, callback: CallbackType<T>) -> Self {
+        self.callbacks.push((on.into(), Callback(callback)));
+        self
+    }
+}
+
+// #[derive(Debug, Clone, PartialEq, Hash, Eq)] for TextSpan<T>
+
+impl<T> fmt::Debug for TextSpan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TextSpan {{ text: {:?}, style: {:?}, callbacks: {:?} }}",
+            self.text, self.style, self.callbacks
+        )
+    }
+}
+
+impl<T> Clone for TextSpan<T> {
+    fn clone(&self) -> Self {
+        Self {
+            text: self.text.clone(),
+            style: self.style.clone(),
+            callbacks: self.callbacks.clone(),
+        }
+    }
+}
+
+impl<T> Hash for TextSpan<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.style.hash(state);
+        self.callbacks.hash(state);
+    }
+}
+
+impl<T> PartialEq for TextSpan<T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.text == rhs.text && self.style == rhs.style && self.callbacks == rhs.callbacks
+    }
+}
+
+impl<T> Eq for TextSpan<T> {}
+
 /// When to call a callback action - `On::MouseOver`, `On::MouseOut`, etc.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum On {
@@ -514,6 +597,9 @@ pub struct NodeData<T> {
     ids: Vec<DomString>,
     /// `.myclass .otherclass`
     classes: Vec<DomString>,
+    /// `name="value"` pairs that can be selected with CSS attribute
+    /// selectors (`[name="value"]`), but aren't used otherwise
+    attributes: Vec<(DomString, DomString)>,
     /// `On::MouseUp` -> `Callback(my_button_click_handler)`
     callbacks: Vec<(EventFilter, Callback<T>)>,
     /// Usually not set by the user directly - `FakeWindow::add_default_callback`
@@ -600,6 +686,7 @@ impl<T> PartialEq for NodeData<T> {
         self.node_type == other.node_type
             && self.ids == other.ids
             && self.classes == other.classes
+            && self.attributes == other.attributes
             && self.callbacks == other.callbacks
             && self.default_callback_ids == other.default_callback_ids
             && self.dynamic_css_overrides == other.dynamic_css_overrides
@@ -625,6 +712,9 @@ impl<T> Hash for NodeData<T> {
         for class in &self.classes {
             class.hash(state);
         }
+        for attribute in &self.attributes {
+            attribute.hash(state);
+        }
         for callback in &self.callbacks {
             callback.hash(state);
         }
@@ -645,6 +735,7 @@ impl<T> Clone for NodeData<T> {
             node_type: self.node_type.clone(),
             ids: self.ids.clone(),
             classes: self.classes.clone(),
+            attributes: self.attributes.clone(),
             callbacks: self.callbacks.clone(),
             default_callback_ids: self.default_callback_ids.clone(),
             dynamic_css_overrides: self.dynamic_css_overrides.clone(),
@@ -719,6 +810,12 @@ fn node_data_to_string<T>(node_data: &NodeData<T>) -> String {
         )
     };
 
+    let attributes = node_data
+        .attributes
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name.as_str(), value.as_str()))
+        .collect::<String>();
+
     let draggable = if node_data.is_draggable {
         format!(" draggable=\"true\"")
     } else {
@@ -774,8 +871,15 @@ fn node_data_to_string<T>(node_data: &NodeData<T>) -> String {
     };
 
     format!(
-        "{}{}{}{}{}{}{}",
-        id_string, class_string, tabindex, draggable, callbacks, default_callbacks, css_overrides
+        "{}{}{}{}{}{}{}{}",
+        id_string,
+        class_string,
+        attributes,
+        tabindex,
+        draggable,
+        callbacks,
+        default_callbacks,
+        css_overrides
     )
 }
 
@@ -787,6 +891,7 @@ impl<T> fmt::Debug for NodeData<T> {
              \tnode_type: {:?}, \
              \tids: {:?}, \
              \tclasses: {:?}, \
+             \tattributes: {:?}, \
              \tcallbacks: {:?}, \
              \tdefault_callback_ids: {:?}, \
              \tdynamic_css_overrides: {:?}, \
@@ -796,6 +901,7 @@ impl<T> fmt::Debug for NodeData<T> {
             self.node_type,
             self.ids,
             self.classes,
+            self.attributes,
             self.callbacks,
             self.default_callback_ids,
             self.dynamic_css_overrides,
@@ -815,6 +921,7 @@ impl<T> NodeData<T> {
             node_type,
             ids: Vec::new(),
             classes: Vec::new(),
+            attributes: Vec::new(),
             callbacks: Vec::new(),
             default_callback_ids: Vec::new(),
             dynamic_css_overrides: Vec::new(),
@@ -841,6 +948,14 @@ impl<T> NodeData<T> {
             .any(|self_class| self_class.equals_str(class))
     }
 
+    /// Returns the value of the attribute with the given name
+    pub fn get_attribute(&self, name: &str) -> Option<&DomString> {
+        self.attributes
+            .iter()
+            .find(|(self_name, _)| self_name.equals_str(name))
+            .map(|(_, value)| value)
+    }
+
     pub fn calculate_node_data_hash(&self) -> DomHash {
         use std::collections::hash_map::DefaultHasher as HashAlgorithm;
 
@@ -868,6 +983,12 @@ impl<T> NodeData<T> {
         Self::new(NodeType::Text(text_id))
     }
 
+    /// Shorthand for `NodeData::new(NodeType::RichText(spans))`
+    #[inline(always)]
+    pub fn rich_text(spans: Vec<TextSpan<T>>) -> Self {
+        Self::new(NodeType::RichText(spans))
+    }
+
     /// Shorthand for `NodeData::new(NodeType::Image(image_id))`
     #[inline(always)]
     pub fn image(image: ImageId) -> Self {
@@ -902,6 +1023,10 @@ impl<T> NodeData<T> {
         &self.classes
     }
     #[inline(always)]
+    pub const fn get_attributes(&self) -> &Vec<(DomString, DomString)> {
+        &self.attributes
+    }
+    #[inline(always)]
     pub const fn get_callbacks(&self) -> &Vec<(EventFilter, Callback<T>)> {
         &self.callbacks
     }
@@ -935,6 +1060,10 @@ impl<T> NodeData<T> {
         self.classes = classes;
     }
     #[inline(always)]
+    pub fn set_attributes(&mut self, attributes: Vec<(DomString, DomString)>) {
+        self.attributes = attributes;
+    }
+    #[inline(always)]
     pub fn set_callbacks(&mut self, callbacks: Vec<(EventFilter, Callback<T>)>) {
         self.callbacks = callbacks;
     }
@@ -974,6 +1103,10 @@ impl<T> NodeData<T> {
         Self { classes, ..self }
     }
     #[inline(always)]
+    pub fn with_attributes(self, attributes: Vec<(DomString, DomString)>) -> Self {
+        Self { attributes, ..self }
+    }
+    #[inline(always)]
     pub fn with_callbacks(self, callbacks: Vec<(EventFilter, Callback<T>)>) -> Self {
         Self { callbacks, ..self }
     }
@@ -1286,6 +1419,12 @@ impl<T> Dom<T> {
         Self::new(NodeType::Text(text_id))
     }
 
+    /// Shorthand for `Dom::new(NodeType::RichText(spans))`
+    #[inline]
+    pub fn rich_text(spans: Vec<TextSpan<T>>) -> Self {
+        Self::new(NodeType::RichText(spans))
+    }
+
     /// Shorthand for `Dom::new(NodeType::Image(image_id))`
     #[inline]
     pub fn image(image: ImageId) -> Self {
@@ -1429,6 +1568,17 @@ impl<T> Dom<T> {
         self
     }
 
+    /// Same as `add_attribute`, but easier to use for method chaining in a builder-style pattern
+    #[inline]
+    pub fn with_attribute<S: Into<DomString>, V: Into<DomString>>(
+        mut self,
+        name: S,
+        value: V,
+    ) -> Self {
+        self.add_attribute(name, value);
+        self
+    }
+
     /// Same as `event`, but easier to use for method chaining in a builder-style pattern
     #[inline]
     pub fn with_callback<O: Into<EventFilter>>(mut self, on: O, callback: CallbackType<T>) -> Self {
@@ -1480,6 +1630,14 @@ impl<T> Dom<T> {
         self.arena.node_data[self.head].classes.push(class.into());
     }
 
+    /// Adds a `name="value"` attribute, which can be selected via `[name="value"]` in the CSS
+    #[inline]
+    pub fn add_attribute<S: Into<DomString>, V: Into<DomString>>(&mut self, name: S, value: V) {
+        self.arena.node_data[self.head]
+            .attributes
+            .push((name.into(), value.into()));
+    }
+
     #[inline]
     pub fn add_callback<O: Into<EventFilter>>(&mut self, on: O, callback: CallbackType<T>) {
         self.arena.node_data[self.head]
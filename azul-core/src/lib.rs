@@ -1,5 +1,6 @@
 //! Shared datatypes for azul-* crates
 
+pub mod animation;
 pub mod app;
 pub mod app_resources;
 pub mod r#async;
@@ -9,6 +10,7 @@ pub mod display_list;
 pub mod dom;
 pub mod gl;
 pub mod id_tree;
+pub mod query;
 pub mod style;
 pub mod ui_description;
 pub mod ui_solver;
@@ -0,0 +1,287 @@
+//! Queries over the styled and layouted UI, i.e. "find all nodes matching `#submit`
+//! and return their bounds" - mostly useful for assertions in integration tests
+//!
+//! The query runs on the `UiDescription`s (styled DOMs) and the `LayoutResult`s of a
+//! window, which can be obtained from the `FakeWindow`, the `CallbackInfo` or a
+//! `HeadlessApp`, so it doesn't have to run inside of a callback.
+
+use azul_css::{CssPath, CssProperty, CssPropertyType};
+use std::collections::BTreeMap;
+
+use crate::{
+    dom::{DomId, NodeData, NodeType},
+    id_tree::NodeId,
+    style::{construct_html_cascade_tree, matches_html_element},
+    ui_description::UiDescription,
+    ui_solver::{LayoutResult, PositionedRectangle},
+};
+
+#[cfg(feature = "css_parser")]
+use azul_css_parser::CssPathParseError;
+
+/// Query over the styled DOMs and layout results of one window
+pub struct DomQuery<'a, T> {
+    ui_descriptions: &'a BTreeMap<DomId, UiDescription<T>>,
+    layout_results: &'a BTreeMap<DomId, LayoutResult>,
+}
+
+/// Node that was found by a `DomQuery`
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult<'a> {
+    /// DOM and node ID of the matched node
+    pub node_id: (DomId, NodeId),
+    /// Layouted rectangle of the node, `None` if the DOM hasn't been layouted yet
+    pub bounds: Option<&'a PositionedRectangle>,
+    /// Text of the node, if the node is a `Label`, `Text` or `RichText` node
+    pub text: Option<String>,
+    /// Computed style of the node (after cascading, inheritance, dynamic CSS
+    /// overrides and running animations), only contains the properties that are set
+    pub style: BTreeMap<CssPropertyType, CssProperty>,
+}
+
+impl<'a> QueryResult<'a> {
+    /// Returns the computed value of a CSS property, if the property is set on this node
+    pub fn get_style_property(&self, property_type: CssPropertyType) -> Option<&CssProperty> {
+        self.style.get(&property_type)
+    }
+}
+
+impl<'a, T> DomQuery<'a, T> {
+    pub fn new(
+        ui_descriptions: &'a BTreeMap<DomId, UiDescription<T>>,
+        layout_results: &'a BTreeMap<DomId, LayoutResult>,
+    ) -> Self {
+        Self {
+            ui_descriptions,
+            layout_results,
+        }
+    }
+
+    /// Returns all nodes that match the CSS path, in DOM order. Note that `:hover`,
+    /// `:active` and `:focus` never match, since the query doesn't know about the window state.
+    pub fn query(&self, path: &CssPath) -> Vec<QueryResult<'a>> {
+        self.ui_descriptions
+            .iter()
+            .flat_map(|(dom_id, ui_description)| {
+                let node_hierarchy = &ui_description.ui_descr_arena.node_layout;
+                let html_tree = construct_html_cascade_tree(
+                    &ui_description.ui_descr_arena.node_data,
+                    node_hierarchy,
+                    &node_hierarchy.get_parents_sorted_by_depth(),
+                    None,
+                    &BTreeMap::new(),
+                    false,
+                );
+
+                node_hierarchy
+                    .linear_iter()
+                    .filter(|node_id| {
+                        matches_html_element(path, *node_id, node_hierarchy, &html_tree)
+                    })
+                    .map(|node_id| self.get_node(dom_id, node_id))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the first node (in DOM order) that matches the CSS path
+    pub fn query_first(&self, path: &CssPath) -> Option<QueryResult<'a>> {
+        self.query(path).into_iter().next()
+    }
+
+    /// Same as `query`, but parses the CSS path from a string first
+    #[cfg(feature = "css_parser")]
+    pub fn query_str<'b>(
+        &self,
+        path: &'b str,
+    ) -> Result<Vec<QueryResult<'a>>, CssPathParseError<'b>> {
+        use azul_css_parser::parse_css_path;
+        Ok(self.query(&parse_css_path(path)?))
+    }
+
+    /// Returns the bounds, text and computed style of a single node
+    fn get_node(&self, dom_id: &DomId, node_id: NodeId) -> QueryResult<'a> {
+        let ui_description = &self.ui_descriptions[dom_id];
+        let layout_result = self.layout_results.get(dom_id);
+        let node_data = &ui_description.ui_descr_arena.node_data[node_id];
+
+        QueryResult {
+            node_id: (dom_id.clone(), node_id),
+            bounds: layout_result.and_then(|lr| lr.rects.get(node_id)),
+            text: get_text(node_data, node_id, layout_result),
+            style: get_animated_style(ui_description, node_id),
+        }
+    }
+}
+
+/// Returns the text of a `Label`, `Text` or `RichText` node - the text of `Text` nodes
+/// is only known after the layout, since it is stored in the `AppResources`
+fn get_text<T>(
+    node_data: &NodeData<T>,
+    node_id: NodeId,
+    layout_result: Option<&LayoutResult>,
+) -> Option<String> {
+    match node_data.get_node_type() {
+        NodeType::Label(label) => Some(label.as_str().to_string()),
+        NodeType::RichText(spans) => Some(spans.iter().map(|span| span.text.as_str()).collect()),
+        NodeType::Text(_) => layout_result?
+            .word_cache
+            .get(&node_id)
+            .map(|words| words.internal_str.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the computed style of a node, with the current values of the animated properties
+fn get_animated_style<T>(
+    ui_description: &UiDescription<T>,
+    node_id: NodeId,
+) -> BTreeMap<CssPropertyType, CssProperty> {
+    let mut style = ui_description.get_computed_style(node_id);
+    if let Some(animated_properties) = ui_description.animated_properties.get(&node_id) {
+        style.extend(animated_properties.clone());
+    }
+    style
+}
+
+#[test]
+fn test_dom_query() {
+    use crate::{
+        dom::Dom,
+        style::match_dom_selectors,
+        ui_state::ui_state_from_dom,
+        window::{WindowSize, WindowTheme},
+    };
+    use azul_css::{
+        ColorU, Css, CssDeclaration, CssPathSelector, CssRuleBlock, DynamicCssProperty,
+        StyleTextColor, Stylesheet,
+    };
+
+    struct Data;
+
+    let dom: Dom<Data> = Dom::div()
+        .with_class("header")
+        .with_child(Dom::label("Hello").with_id("submit"))
+        .with_child(
+            Dom::label("World")
+                .with_class("item")
+                .with_css_override("color", CssProperty::from(StyleTextColor(ColorU::RED))),
+        )
+        .with_child(Dom::div().with_child(Dom::label("Nested").with_class("nested")));
+
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![
+                CssRuleBlock {
+                    path: CssPath {
+                        selectors: vec![CssPathSelector::Class("header".into())],
+                    },
+                    declarations: vec![CssDeclaration::Static(CssProperty::from(StyleTextColor(
+                        ColorU::BLACK,
+                    )))],
+                    media_queries: Vec::new(),
+                    important: false,
+                },
+                CssRuleBlock {
+                    path: CssPath {
+                        selectors: vec![CssPathSelector::Class("item".into())],
+                    },
+                    declarations: vec![CssDeclaration::Dynamic(DynamicCssProperty {
+                        dynamic_id: "color".into(),
+                        default_value: CssProperty::from(StyleTextColor(ColorU::WHITE)),
+                    })],
+                    media_queries: Vec::new(),
+                    important: false,
+                },
+            ],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let ui_state = ui_state_from_dom(dom, None);
+    let ui_description = match_dom_selectors(
+        &ui_state,
+        &css,
+        &mut None,
+        &mut None,
+        &BTreeMap::new(),
+        false,
+        &WindowSize::default(),
+        WindowTheme::default(),
+        None,
+    );
+
+    let mut ui_descriptions = BTreeMap::new();
+    ui_descriptions.insert(DomId::ROOT_ID, ui_description);
+    let layout_results = BTreeMap::new();
+    let query = DomQuery::new(&ui_descriptions, &layout_results);
+
+    // ".header #submit"
+    let submit = query.query(&CssPath {
+        selectors: vec![
+            CssPathSelector::Class("header".into()),
+            CssPathSelector::Children,
+            CssPathSelector::Id("submit".into()),
+        ],
+    });
+    assert_eq!(submit.len(), 1);
+    assert_eq!(submit[0].node_id, (DomId::ROOT_ID, NodeId::new(1)));
+    assert_eq!(submit[0].text, Some("Hello".to_string()));
+    assert_eq!(submit[0].bounds, None);
+    // color is inherited from the .header
+    assert_eq!(
+        submit[0].get_style_property(CssPropertyType::TextColor),
+        Some(&CssProperty::from(StyleTextColor(ColorU::BLACK)))
+    );
+
+    // dynamic CSS overrides are applied
+    let item = query
+        .query_first(&CssPath {
+            selectors: vec![CssPathSelector::Class("item".into())],
+        })
+        .unwrap();
+    assert_eq!(item.node_id, (DomId::ROOT_ID, NodeId::new(2)));
+    assert_eq!(
+        item.get_style_property(CssPropertyType::TextColor),
+        Some(&CssProperty::from(StyleTextColor(ColorU::RED)))
+    );
+
+    // ".header > #submit"
+    let direct_submit = query.query(&CssPath {
+        selectors: vec![
+            CssPathSelector::Class("header".into()),
+            CssPathSelector::DirectChildren,
+            CssPathSelector::Id("submit".into()),
+        ],
+    });
+    assert_eq!(direct_submit.len(), 1);
+    assert_eq!(direct_submit[0].node_id, (DomId::ROOT_ID, NodeId::new(1)));
+
+    // ".header .nested" matches the grandchild, ".header > .nested" doesn't
+    let nested = query.query(&CssPath {
+        selectors: vec![
+            CssPathSelector::Class("header".into()),
+            CssPathSelector::Children,
+            CssPathSelector::Class("nested".into()),
+        ],
+    });
+    assert_eq!(nested.len(), 1);
+    assert_eq!(nested[0].node_id, (DomId::ROOT_ID, NodeId::new(4)));
+    assert_eq!(nested[0].text, Some("Nested".to_string()));
+    assert!(query
+        .query(&CssPath {
+            selectors: vec![
+                CssPathSelector::Class("header".into()),
+                CssPathSelector::DirectChildren,
+                CssPathSelector::Class("nested".into()),
+            ],
+        })
+        .is_empty());
+
+    assert!(query
+        .query(&CssPath {
+            selectors: vec![CssPathSelector::Id("nonexistent".into())],
+        })
+        .is_empty());
+}
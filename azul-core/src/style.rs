@@ -1,23 +1,36 @@
 //! DOM tree to CSS style tree cascading
 
 use azul_css::{
-    Css, CssContentGroup, CssNthChildSelector::*, CssPath, CssPathPseudoSelector, CssPathSelector,
+    get_specificity, Css, CssColorScheme, CssContentGroup, CssDeclaration, CssMediaOrientation,
+    CssMediaQuery, CssNthChildSelector, CssNthChildSelector::*, CssPath, CssPathPseudoSelector,
+    CssPathSelector, CssRuleBlock,
 };
 use std::{collections::BTreeMap, fmt};
 
 use crate::{
+    animation::AnimationState,
     callbacks::{FocusTarget, HitTestItem},
-    dom::{DomId, NodeData},
+    diff::DomDiff,
+    dom::{DomId, NodeData, NodeType},
     id_tree::{NodeDataContainer, NodeHierarchy, NodeId},
-    ui_description::{StyledNode, UiDescription},
+    ui_description::{PseudoState, StyledNode, UiDescription},
     ui_state::{ActiveHover, HoverGroup, UiState},
+    window::{WindowSize, WindowTheme},
 };
 
 /// Has all the necessary information about the style CSS path
 pub struct HtmlCascadeInfo<'a, T: 'a> {
     pub node_data: &'a NodeData<T>,
     pub index_in_parent: usize,
+    /// Same as `index_in_parent`, but counted from the last child (necessary for `:nth-last-child`)
+    pub index_from_end: usize,
+    /// Index among the siblings with the same node type (necessary for `:nth-of-type`)
+    pub index_of_type: usize,
+    /// Same as `index_of_type`, but counted from the last sibling of the same node type
+    pub index_of_type_from_end: usize,
     pub is_last_child: bool,
+    /// Whether the node has no children and no text (necessary for `:empty`)
+    pub is_empty: bool,
     pub is_hovered_over: bool,
     pub is_focused: bool,
     pub is_active: bool,
@@ -30,14 +43,22 @@ impl<'a, T: 'a> fmt::Debug for HtmlCascadeInfo<'a, T> {
             "HtmlCascadeInfo {{ \
              node_data: {:?}, \
              index_in_parent: {}, \
+             index_from_end: {}, \
+             index_of_type: {}, \
+             index_of_type_from_end: {}, \
              is_last_child: {:?}, \
+             is_empty: {:?}, \
              is_hovered_over: {:?}, \
              is_focused: {:?}, \
              is_active: {:?}, \
              }}",
             self.node_data,
             self.index_in_parent,
+            self.index_from_end,
+            self.index_of_type,
+            self.index_of_type_from_end,
             self.is_last_child,
+            self.is_empty,
             self.is_hovered_over,
             self.is_focused,
             self.is_active,
@@ -52,49 +73,64 @@ pub fn matches_html_element<'a, T>(
     node_hierarchy: &NodeHierarchy,
     html_node_tree: &NodeDataContainer<HtmlCascadeInfo<'a, T>>,
 ) -> bool {
-    use self::CssGroupSplitReason::*;
-
     if css_path.selectors.is_empty() {
         return false;
     }
 
-    let mut current_node = Some(node_id);
-    let mut direct_parent_has_to_match = false;
-    let mut last_selector_matched = true;
-
-    for (content_group, reason) in CssGroupIterator::new(&css_path.selectors) {
-        let cur_node_id = match current_node {
-            Some(c) => c,
-            None => {
-                // The node has no parent, but the CSS path
-                // still has an extra limitation - only valid if the
-                // next content group is a "*" element
-                return *content_group == [&CssPathSelector::Global];
-            }
-        };
-        let current_selector_matches =
-            selector_group_matches(&content_group, &html_node_tree[cur_node_id]);
+    let content_groups = CssGroupIterator::new(&css_path.selectors).collect::<Vec<_>>();
+    content_groups_match(&content_groups, node_id, node_hierarchy, html_node_tree)
+}
 
-        if direct_parent_has_to_match && !current_selector_matches {
-            // If the element was a ">" element and the current,
-            // direct parent does not match, return false
-            return false; // not executed (maybe this is the bug)
-        }
+/// Matches the content groups (from right to left, as returned by the `CssGroupIterator`):
+/// the first group has to match the node itself, the remaining groups have to match a parent
+/// or a previous sibling of the node, depending on the combinator in front of the first group
+fn content_groups_match<'a, T>(
+    content_groups: &[(CssContentGroup, CssGroupSplitReason)],
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    html_node_tree: &NodeDataContainer<HtmlCascadeInfo<'a, T>>,
+) -> bool {
+    use self::CssGroupSplitReason::*;
 
-        // If the current selector matches, but the previous one didn't,
-        // that means that the CSS path chain is broken and therefore doesn't match the element
-        if current_selector_matches && !last_selector_matched {
-            return false;
-        }
+    let ((content_group, reason), remaining_groups) = match content_groups.split_first() {
+        Some(s) => s,
+        None => return true,
+    };
 
-        // Important: Set if the current selector has matched the element
-        last_selector_matched = current_selector_matches;
-        // Select if the next content group has to exactly match or if it can potentially be skipped
-        direct_parent_has_to_match = reason == DirectChildren;
-        current_node = node_hierarchy[cur_node_id].parent;
+    if !selector_group_matches(content_group, &html_node_tree[node_id]) {
+        return false;
     }
 
-    last_selector_matched
+    if remaining_groups.is_empty() {
+        return true;
+    }
+
+    let remaining_groups_match = |next_node_id: NodeId| {
+        content_groups_match(
+            remaining_groups,
+            next_node_id,
+            node_hierarchy,
+            html_node_tree,
+        )
+    };
+
+    // Skip the node itself, the iterators start at the current node
+    match reason {
+        Children => node_id
+            .ancestors(node_hierarchy)
+            .skip(1)
+            .any(remaining_groups_match),
+        DirectChildren => node_hierarchy[node_id]
+            .parent
+            .map_or(false, remaining_groups_match),
+        GeneralSibling => node_id
+            .preceding_siblings(node_hierarchy)
+            .skip(1)
+            .any(remaining_groups_match),
+        AdjacentSibling => node_hierarchy[node_id]
+            .previous_sibling
+            .map_or(false, remaining_groups_match),
+    }
 }
 
 pub fn match_dom_selectors<T>(
@@ -104,8 +140,30 @@ pub fn match_dom_selectors<T>(
     pending_focus_target: &mut Option<FocusTarget>,
     hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
     is_mouse_down: bool,
+    window_size: &WindowSize,
+    window_theme: WindowTheme,
+    previous_frame: Option<(&UiDescription<T>, &DomDiff)>,
 ) -> UiDescription<T> {
-    use azul_css::CssDeclaration;
+    let media_query_results = css
+        .rules()
+        .map(|rule| rule_matches_media(rule, window_size, window_theme))
+        .collect::<Vec<_>>();
+
+    // If the window was resized so that a different set of @media rules applies,
+    // the styles of the last frame can't be reused
+    let previous_frame =
+        previous_frame.filter(|(previous, _)| previous.media_query_results == media_query_results);
+
+    // Rules that apply to the window, in the order of the cascade: declarations of later rules
+    // override the earlier ones, so the rules are sorted by importance and specificity - rules
+    // with the same specificity keep their source order (across all stylesheets)
+    let mut cascaded_rules = css
+        .rules()
+        .zip(media_query_results.iter())
+        .filter(|(_, matches_media)| **matches_media)
+        .map(|(rule, _)| rule)
+        .collect::<Vec<_>>();
+    cascaded_rules.sort_by_key(|rule| (rule.important, get_specificity(&rule.path)));
 
     let non_leaf_nodes = ui_state.dom.arena.node_layout.get_parents_sorted_by_depth();
 
@@ -134,31 +192,51 @@ pub fn match_dom_selectors<T>(
         &mut html_tree,
     );
 
+    let pseudo_states = html_tree.transform(|html_node, _| PseudoState {
+        is_hovered_over: html_node.is_hovered_over,
+        is_active: html_node.is_active,
+        is_focused: html_node.is_focused,
+    });
+
+    // Nodes that didn't change since the last frame can reuse their old style
+    let reused_styles = match previous_frame {
+        Some((previous, diff)) => get_reusable_styles(
+            &ui_state.dom.arena.node_layout,
+            &pseudo_states,
+            previous,
+            diff,
+            css.rules()
+                .any(|rule| selectors_depend_on_siblings(&rule.path.selectors)),
+        ),
+        None => ui_state.dom.arena.node_data.transform(|_, _| None),
+    };
+
     // First, apply all rules normally (no inheritance) of CSS values
     // This is an O(n^2) operation, but it can be parallelized in the future
-    let mut styled_nodes = ui_state
-        .dom
-        .arena
-        .node_data
-        .transform(|_, node_id| StyledNode {
-            css_constraints: css
-                .rules()
-                .filter(|rule| {
-                    matches_html_element(
-                        &rule.path,
-                        node_id,
-                        &ui_state.dom.arena.node_layout,
-                        &html_tree,
-                    )
-                })
-                .flat_map(|matched_rule| {
-                    matched_rule
-                        .declarations
-                        .iter()
-                        .map(|declaration| (declaration.get_type(), declaration.clone()))
-                })
-                .collect(),
-        });
+    let mut styled_nodes = ui_state.dom.arena.node_data.transform(|_, node_id| {
+        match (previous_frame, reused_styles[node_id]) {
+            (Some((previous, _)), Some(old_node_id)) => previous.styled_nodes[old_node_id].clone(),
+            _ => StyledNode {
+                css_constraints: cascaded_rules
+                    .iter()
+                    .filter(|rule| {
+                        matches_html_element(
+                            &rule.path,
+                            node_id,
+                            &ui_state.dom.arena.node_layout,
+                            &html_tree,
+                        )
+                    })
+                    .flat_map(|matched_rule| {
+                        matched_rule
+                            .declarations
+                            .iter()
+                            .map(|declaration| (declaration.get_type(), declaration.clone()))
+                    })
+                    .collect(),
+            },
+        }
+    });
 
     // Then, inherit all values of the parent to the children, but only if the property is
     // inheritable and isn't yet set. NOTE: This step can't be parallelized!
@@ -167,13 +245,17 @@ pub fn match_dom_selectors<T>(
             .css_constraints
             .values()
             .filter(|prop| prop.is_inheritable())
-            .cloned()
+            .map(get_inherited_declaration)
             .collect();
         if inherited_rules.is_empty() {
             continue;
         }
 
-        for child_id in parent_id.children(&ui_state.dom.arena.node_layout) {
+        // Reused styles already contain the inherited rules
+        for child_id in parent_id
+            .children(&ui_state.dom.arena.node_layout)
+            .filter(|child_id| reused_styles[*child_id].is_none())
+        {
             for inherited_rule in &inherited_rules {
                 // Only override the rule if the child already has an inherited rule, don't override it
                 let inherited_rule_type = inherited_rule.get_type();
@@ -209,7 +291,177 @@ pub fn match_dom_selectors<T>(
         ui_descr_root: ui_state.dom.root,
         styled_nodes,
         selected_hover_nodes,
+        pseudo_states,
+        media_query_results,
+        animations: AnimationState::default(),
+        animated_properties: BTreeMap::new(),
+    }
+}
+
+/// Returns whether the rule applies to a window with the given size and theme, i.e. whether
+/// one of its `@media` queries matches (rules outside of `@media` rules always apply)
+pub fn rule_matches_media(
+    rule: &CssRuleBlock,
+    window_size: &WindowSize,
+    window_theme: WindowTheme,
+) -> bool {
+    rule.media_queries.is_empty()
+        || rule
+            .media_queries
+            .iter()
+            .any(|query| media_query_matches(query, window_size, window_theme))
+}
+
+/// Evaluates a `@media` query against the logical size, hidpi factor and theme of the window
+pub fn media_query_matches(
+    query: &CssMediaQuery,
+    window_size: &WindowSize,
+    window_theme: WindowTheme,
+) -> bool {
+    use azul_css::CssMediaFeature::*;
+
+    let width = window_size.dimensions.width;
+    let height = window_size.dimensions.height;
+    let resolution = window_size.hidpi_factor;
+
+    query.features.iter().all(|feature| match feature {
+        MinWidth(w) => width >= w.to_pixels(),
+        MaxWidth(w) => width <= w.to_pixels(),
+        MinHeight(h) => height >= h.to_pixels(),
+        MaxHeight(h) => height <= h.to_pixels(),
+        Orientation(CssMediaOrientation::Portrait) => height >= width,
+        Orientation(CssMediaOrientation::Landscape) => width > height,
+        MinResolution(r) => resolution >= r.get(),
+        MaxResolution(r) => resolution <= r.get(),
+        Resolution(r) => (resolution - r.get()).abs() < 0.01,
+        PrefersColorScheme(CssColorScheme::Light) => window_theme == WindowTheme::Light,
+        PrefersColorScheme(CssColorScheme::Dark) => window_theme == WindowTheme::Dark,
+    })
+}
+
+/// Returns the declaration that the children of a node inherit. A `font-size` in `em` or `%`
+/// is relative to the font size of the parent, so it is inherited as `1em` (the font size
+/// of the parent) - otherwise the font size would be multiplied again for every child.
+fn get_inherited_declaration(declaration: &CssDeclaration) -> CssDeclaration {
+    use azul_css::{CssProperty, CssPropertyValue, PixelValue, SizeMetric, StyleFontSize};
+
+    let is_relative_to_parent = |font_size: &StyleFontSize| match font_size.0.metric {
+        SizeMetric::Em | SizeMetric::Percent => true,
+        SizeMetric::Calc(calc) => calc.em.get() != 0.0 || calc.has_percent(),
+        _ => false,
+    };
+
+    match declaration {
+        CssDeclaration::Static(CssProperty::FontSize(CssPropertyValue::Exact(font_size)))
+            if is_relative_to_parent(font_size) =>
+        {
+            CssDeclaration::Static(CssProperty::FontSize(CssPropertyValue::Exact(
+                StyleFontSize(PixelValue::em(1.0)),
+            )))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Returns for each node the `NodeId` in the previous frame whose style can be reused:
+/// the node (and all of its parents) must be unchanged according to the `DomDiff` and
+/// must have the same `:hover`, `:active` and `:focus` state as in the previous frame.
+///
+/// If the CSS has selectors that depend on the siblings of a node (`a + b`), the node
+/// must also have the same siblings and children as in the previous frame and all of its
+/// preceding siblings must have the same pseudo-state (for `a:hover + b`)
+fn get_reusable_styles<T>(
+    node_hierarchy: &NodeHierarchy,
+    pseudo_states: &NodeDataContainer<PseudoState>,
+    previous: &UiDescription<T>,
+    diff: &DomDiff,
+    depends_on_siblings: bool,
+) -> NodeDataContainer<Option<NodeId>> {
+    let mut reused_styles = NodeDataContainer::new(vec![None; node_hierarchy.len()]);
+
+    // Parents always have a lower NodeId than their children
+    for node_id in node_hierarchy.linear_iter() {
+        if diff.needs_restyle(node_id) {
+            continue;
+        }
+
+        if depends_on_siblings
+            && (diff.children_changed(node_id)
+                || node_hierarchy[node_id]
+                    .parent
+                    .map_or(false, |parent_id| diff.children_changed(parent_id)))
+        {
+            continue;
+        }
+
+        if depends_on_siblings
+            && preceding_siblings_changed_pseudo_state(
+                node_hierarchy,
+                pseudo_states,
+                previous,
+                diff,
+                node_id,
+            )
+        {
+            continue;
+        }
+
+        let parent_is_reused = match node_hierarchy[node_id].parent {
+            Some(parent_id) => reused_styles[parent_id].is_some(),
+            None => true,
+        };
+
+        if !parent_is_reused {
+            continue;
+        }
+
+        reused_styles[node_id] = diff.get_old_node_id(node_id).filter(|old_node_id| {
+            previous.styled_nodes.get(*old_node_id).is_some()
+                && previous.pseudo_states.get(*old_node_id) == Some(&pseudo_states[node_id])
+        });
     }
+
+    reused_styles
+}
+
+/// Returns whether any of the preceding siblings of the node is hovered, active or focused
+/// differently than in the previous frame
+fn preceding_siblings_changed_pseudo_state<T>(
+    node_hierarchy: &NodeHierarchy,
+    pseudo_states: &NodeDataContainer<PseudoState>,
+    previous: &UiDescription<T>,
+    diff: &DomDiff,
+    node_id: NodeId,
+) -> bool {
+    let mut sibling = node_hierarchy[node_id].previous_sibling;
+
+    while let Some(sibling_id) = sibling {
+        let old_pseudo_state = diff
+            .get_old_node_id(sibling_id)
+            .and_then(|old_node_id| previous.pseudo_states.get(old_node_id));
+        if old_pseudo_state != Some(&pseudo_states[sibling_id]) {
+            return true;
+        }
+        sibling = node_hierarchy[sibling_id].previous_sibling;
+    }
+
+    false
+}
+
+/// Returns whether the selectors can match differently if the siblings or children
+/// of a node change, even if the node itself didn't change
+fn selectors_depend_on_siblings(selectors: &[CssPathSelector]) -> bool {
+    use azul_css::{CssPathPseudoSelector::*, CssPathSelector::*};
+
+    selectors.iter().any(|selector| match selector {
+        AdjacentSibling | GeneralSibling => true,
+        PseudoSelector(NthLastChild(_))
+        | PseudoSelector(NthOfType(_))
+        | PseudoSelector(NthLastOfType(_))
+        | PseudoSelector(Empty) => true,
+        Not(selectors) => selectors_depend_on_siblings(selectors),
+        _ => false,
+    })
 }
 
 pub struct CssGroupIterator<'a> {
@@ -222,6 +474,8 @@ pub struct CssGroupIterator<'a> {
 pub enum CssGroupSplitReason {
     Children,
     DirectChildren,
+    AdjacentSibling,
+    GeneralSibling,
 }
 
 impl<'a> CssGroupIterator<'a> {
@@ -259,6 +513,14 @@ impl<'a> Iterator for CssGroupIterator<'a> {
                     self.last_reason = CssGroupSplitReason::DirectChildren;
                     break;
                 }
+                AdjacentSibling => {
+                    self.last_reason = CssGroupSplitReason::AdjacentSibling;
+                    break;
+                }
+                GeneralSibling => {
+                    self.last_reason = CssGroupSplitReason::GeneralSibling;
+                    break;
+                }
                 other => current_path.push(other),
             }
             new_idx -= 1;
@@ -278,7 +540,7 @@ impl<'a> Iterator for CssGroupIterator<'a> {
                 Some((current_path, self.last_reason))
             }
         } else {
-            // skip the "Children | DirectChildren" element itself
+            // skip the combinator ("Children | DirectChildren | ...") element itself
             self.current_idx = new_idx - 1;
             Some((current_path, self.last_reason))
         }
@@ -293,45 +555,56 @@ pub fn construct_html_cascade_tree<'a, T>(
     hovered_items: &BTreeMap<NodeId, HitTestItem>,
     is_mouse_down: bool,
 ) -> NodeDataContainer<HtmlCascadeInfo<'a, T>> {
+    // The position in the parent is filled in below, nodes without a
+    // parent (the root node) are treated as the only child of their parent
     let mut nodes = (0..node_hierarchy.len())
-        .map(|_| HtmlCascadeInfo {
-            node_data: &input[NodeId::new(0)],
-            index_in_parent: 0,
-            is_last_child: false,
-            is_hovered_over: false,
-            is_active: false,
-            is_focused: false,
+        .map(|node_index| {
+            let node_id = NodeId::new(node_index);
+            let node_data = &input[node_id];
+            let is_hovered_over = hovered_items.contains_key(&node_id);
+            let has_text = match node_data.get_node_type() {
+                NodeType::Label(_) | NodeType::Text(_) | NodeType::RichText(_) => true,
+                _ => false,
+            };
+            HtmlCascadeInfo {
+                node_data,
+                index_in_parent: 1,
+                index_from_end: 1,
+                index_of_type: 1,
+                index_of_type_from_end: 1,
+                is_last_child: true,
+                is_empty: node_hierarchy[node_id].first_child.is_none() && !has_text,
+                is_hovered_over,
+                is_active: is_hovered_over && is_mouse_down,
+                is_focused: focused_item == Some(node_id),
+            }
         })
         .collect::<Vec<_>>();
 
     for (_depth, parent_id) in node_depths_sorted {
-        // Note: :nth-child() starts at 1 instead of 0
-        let index_in_parent = parent_id.preceding_siblings(node_hierarchy).count();
-
-        let is_parent_hovered_over = hovered_items.contains_key(parent_id);
-        let parent_html_matcher = HtmlCascadeInfo {
-            node_data: &input[*parent_id],
-            index_in_parent: index_in_parent, // necessary for nth-child
-            is_last_child: node_hierarchy[*parent_id].next_sibling.is_none(), // Necessary for :last selectors
-            is_hovered_over: is_parent_hovered_over,
-            is_active: is_parent_hovered_over && is_mouse_down,
-            is_focused: focused_item == Some(*parent_id),
-        };
+        let children = parent_id.children(node_hierarchy).collect::<Vec<_>>();
 
-        nodes[parent_id.index()] = parent_html_matcher;
-
-        for (child_idx, child_id) in parent_id.children(node_hierarchy).enumerate() {
-            let is_child_hovered_over = hovered_items.contains_key(&child_id);
-            let child_html_matcher = HtmlCascadeInfo {
-                node_data: &input[child_id],
-                index_in_parent: child_idx + 1, // necessary for nth-child
-                is_last_child: node_hierarchy[child_id].next_sibling.is_none(),
-                is_hovered_over: is_child_hovered_over,
-                is_active: is_child_hovered_over && is_mouse_down,
-                is_focused: focused_item == Some(child_id),
-            };
+        let mut children_of_type = BTreeMap::new();
+        for child_id in &children {
+            *children_of_type
+                .entry(input[*child_id].get_node_type().get_path())
+                .or_insert(0) += 1;
+        }
 
-            nodes[child_id.index()] = child_html_matcher;
+        let mut current_index_of_type = BTreeMap::new();
+        for (child_idx, child_id) in children.iter().enumerate() {
+            let node_type = input[*child_id].get_node_type().get_path();
+            let index_of_type = current_index_of_type.entry(node_type).or_insert(0);
+            *index_of_type += 1;
+
+            let child_html_matcher = &mut nodes[child_id.index()];
+            // Note: :nth-child() starts at 1 instead of 0
+            child_html_matcher.index_in_parent = child_idx + 1;
+            child_html_matcher.index_from_end = children.len() - child_idx;
+            child_html_matcher.index_of_type = *index_of_type;
+            child_html_matcher.index_of_type_from_end =
+                children_of_type[&node_type] - *index_of_type + 1;
+            child_html_matcher.is_last_child = child_idx + 1 == children.len();
         }
     }
 
@@ -409,7 +682,7 @@ fn match_hover_selectors<'a, T>(
     btree_map
 }
 
-/// Matches a single group of items, panics on Children or DirectChildren selectors
+/// Matches a single group of items, panics on combinators (Children, DirectChildren, etc.)
 ///
 /// The intent is to "split" the CSS path into groups by selectors, then store and cache
 /// whether the direct or any parent has matched the path correctly
@@ -447,6 +720,27 @@ pub fn selector_group_matches<'a, T>(
                     return false;
                 }
             }
+            Not(selectors) => {
+                let selectors = selectors.iter().collect::<Vec<_>>();
+                if selector_group_matches(&selectors, html_node) {
+                    return false;
+                }
+            }
+            Attribute(attribute) => {
+                let attribute_matches = match (
+                    html_node.node_data.get_attribute(&attribute.name),
+                    &attribute.value,
+                ) {
+                    (None, _) => false,
+                    (Some(_), None) => true,
+                    (Some(value), Some((operator, expected))) => {
+                        operator.matches(value.as_str(), expected)
+                    }
+                };
+                if !attribute_matches {
+                    return false;
+                }
+            }
             PseudoSelector(CssPathPseudoSelector::First) => {
                 // Notice: index_in_parent is 1-indexed
                 if html_node.index_in_parent != 1 {
@@ -459,30 +753,31 @@ pub fn selector_group_matches<'a, T>(
                     return false;
                 }
             }
-            PseudoSelector(CssPathPseudoSelector::NthChild(x)) => match *x {
-                Number(value) => {
-                    if html_node.index_in_parent != value {
-                        return false;
-                    }
+            PseudoSelector(CssPathPseudoSelector::NthChild(x)) => {
+                if !nth_child_matches(*x, html_node.index_in_parent) {
+                    return false;
                 }
-                Even => {
-                    if html_node.index_in_parent % 2 == 0 {
-                        return false;
-                    }
+            }
+            PseudoSelector(CssPathPseudoSelector::NthLastChild(x)) => {
+                if !nth_child_matches(*x, html_node.index_from_end) {
+                    return false;
                 }
-                Odd => {
-                    if html_node.index_in_parent % 2 == 1 {
-                        return false;
-                    }
+            }
+            PseudoSelector(CssPathPseudoSelector::NthOfType(x)) => {
+                if !nth_child_matches(*x, html_node.index_of_type) {
+                    return false;
                 }
-                Pattern { repeat, offset } => {
-                    if html_node.index_in_parent >= offset
-                        && ((html_node.index_in_parent - offset) % repeat != 0)
-                    {
-                        return false;
-                    }
+            }
+            PseudoSelector(CssPathPseudoSelector::NthLastOfType(x)) => {
+                if !nth_child_matches(*x, html_node.index_of_type_from_end) {
+                    return false;
                 }
-            },
+            }
+            PseudoSelector(CssPathPseudoSelector::Empty) => {
+                if !html_node.is_empty {
+                    return false;
+                }
+            }
             PseudoSelector(CssPathPseudoSelector::Hover) => {
                 if !html_node.is_hovered_over {
                     return false;
@@ -498,8 +793,8 @@ pub fn selector_group_matches<'a, T>(
                     return false;
                 }
             }
-            DirectChildren | Children => {
-                panic!("Unreachable: DirectChildren or Children in CSS path!");
+            DirectChildren | Children | AdjacentSibling | GeneralSibling => {
+                panic!("Unreachable: combinator in CSS content group!");
             }
         }
     }
@@ -507,6 +802,18 @@ pub fn selector_group_matches<'a, T>(
     true
 }
 
+/// Returns whether the (1-indexed) index matches a `:nth-child()` selector - the `Pattern`
+/// selects every `repeat`-th index, starting at `offset` (i.e. `2n+3` selects 3, 5, 7, ...)
+fn nth_child_matches(selector: CssNthChildSelector, index: usize) -> bool {
+    match selector {
+        Number(value) => index == value,
+        Even => index % 2 == 0,
+        Odd => index % 2 == 1,
+        Pattern { repeat: 0, offset } => index == offset,
+        Pattern { repeat, offset } => index >= offset && (index - offset) % repeat == 0,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub enum UpdateFocusWarning {
     FocusInvalidNodeId(NodeId),
@@ -737,3 +1044,670 @@ fn test_css_group_iterator() {
 
     assert_eq!(it.next(), None);
 }
+
+#[test]
+fn test_sibling_and_attribute_selectors() {
+    use crate::dom::*;
+    use azul_css::{CssNthChildSelector::*, CssPathPseudoSelector::*, CssPathSelector::*, *};
+
+    struct DataModel;
+
+    //  0: [div .root]
+    //   |-- 1: [p .a]
+    //   |-- 2: [p .b type="x-large"]
+    //   |-- 3: [div]
+    //   |-- 4: [div]
+    //   |    |-- 5: [p .c]
+    let dom: Dom<DataModel> = Dom::div()
+        .with_class("root")
+        .with_child(Dom::label("1").with_class("a"))
+        .with_child(
+            Dom::label("2")
+                .with_class("b")
+                .with_attribute("type", "x-large"),
+        )
+        .with_child(Dom::div())
+        .with_child(Dom::div().with_child(Dom::label("3").with_class("c")));
+
+    let node_hierarchy = &dom.arena.node_layout;
+    let nodes_sorted: Vec<_> = node_hierarchy.get_parents_sorted_by_depth();
+    let html_node_tree = construct_html_cascade_tree(
+        &dom.arena.node_data,
+        &node_hierarchy,
+        &nodes_sorted,
+        None,
+        &BTreeMap::new(),
+        false,
+    );
+
+    let matching_nodes = |selectors: Vec<CssPathSelector>| {
+        let css_path = CssPath { selectors };
+        node_hierarchy
+            .linear_iter()
+            .filter(|node_id| {
+                matches_html_element(&css_path, *node_id, &node_hierarchy, &html_node_tree)
+            })
+            .map(|node_id| node_id.index())
+            .collect::<Vec<_>>()
+    };
+
+    let attribute = |name: &str, value: Option<(CssAttributeOperator, &str)>| {
+        Attribute(CssAttributeSelector {
+            name: name.into(),
+            value: value.map(|(operator, value)| (operator, value.into())),
+        })
+    };
+
+    // ".root p" - descendant, but not a direct child
+    assert_eq!(
+        matching_nodes(vec![Class("root".into()), Children, Type(NodeTypePath::P)]),
+        vec![1, 2, 5]
+    );
+    // ".root > p"
+    assert_eq!(
+        matching_nodes(vec![
+            Class("root".into()),
+            DirectChildren,
+            Type(NodeTypePath::P)
+        ]),
+        vec![1, 2]
+    );
+    // ".a + .b", ".b + .a", ".a + div"
+    assert_eq!(
+        matching_nodes(vec![Class("a".into()), AdjacentSibling, Class("b".into())]),
+        vec![2]
+    );
+    assert!(matching_nodes(vec![Class("b".into()), AdjacentSibling, Class("a".into())]).is_empty());
+    assert!(matching_nodes(vec![
+        Class("a".into()),
+        AdjacentSibling,
+        Type(NodeTypePath::Div)
+    ])
+    .is_empty());
+    // ".a ~ div", ".a ~ div p"
+    assert_eq!(
+        matching_nodes(vec![
+            Class("a".into()),
+            GeneralSibling,
+            Type(NodeTypePath::Div)
+        ]),
+        vec![3, 4]
+    );
+    assert_eq!(
+        matching_nodes(vec![
+            Class("a".into()),
+            GeneralSibling,
+            Type(NodeTypePath::Div),
+            Children,
+            Type(NodeTypePath::P),
+        ]),
+        vec![5]
+    );
+    // "p:not(.a)"
+    assert_eq!(
+        matching_nodes(vec![Type(NodeTypePath::P), Not(vec![Class("a".into())])]),
+        vec![2, 5]
+    );
+    // "[type]", "[type|=x]", "[type^=x-]", "[type=x]"
+    assert_eq!(matching_nodes(vec![attribute("type", None)]), vec![2]);
+    assert_eq!(
+        matching_nodes(vec![attribute(
+            "type",
+            Some((CssAttributeOperator::DashMatch, "x"))
+        )]),
+        vec![2]
+    );
+    assert_eq!(
+        matching_nodes(vec![attribute(
+            "type",
+            Some((CssAttributeOperator::StartsWith, "x-"))
+        )]),
+        vec![2]
+    );
+    assert!(matching_nodes(vec![attribute(
+        "type",
+        Some((CssAttributeOperator::Equals, "x"))
+    )])
+    .is_empty());
+    // ":empty" - labels have text, so they are never empty
+    assert_eq!(matching_nodes(vec![PseudoSelector(Empty)]), vec![3]);
+    // ":nth-child(even)", ":nth-last-child(2)"
+    assert_eq!(
+        matching_nodes(vec![PseudoSelector(NthChild(Even))]),
+        vec![2, 4]
+    );
+    assert_eq!(
+        matching_nodes(vec![PseudoSelector(NthLastChild(Number(2)))]),
+        vec![3]
+    );
+    // "p:nth-of-type(2)", ".root > div:nth-last-of-type(1)"
+    assert_eq!(
+        matching_nodes(vec![
+            Type(NodeTypePath::P),
+            PseudoSelector(NthOfType(Number(2)))
+        ]),
+        vec![2]
+    );
+    assert_eq!(
+        matching_nodes(vec![
+            Class("root".into()),
+            DirectChildren,
+            Type(NodeTypePath::Div),
+            PseudoSelector(NthLastOfType(Number(1))),
+        ]),
+        vec![4]
+    );
+}
+
+#[test]
+fn test_incremental_restyle() {
+    use crate::{diff::diff_dom_tree, dom::Dom, ui_state::ui_state_from_dom};
+    use azul_css::*;
+
+    struct Data;
+
+    let rule = |class: &str, color: ColorU| CssRuleBlock {
+        path: CssPath {
+            selectors: vec![CssPathSelector::Class(class.into())],
+        },
+        declarations: vec![CssDeclaration::Static(CssProperty::from(StyleTextColor(
+            color,
+        )))],
+        media_queries: Vec::new(),
+        important: false,
+    };
+
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![rule("a", ColorU::RED), rule("b", ColorU::BLACK)],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let style = |dom: Dom<Data>, previous_frame: Option<(&UiDescription<Data>, &DomDiff)>| {
+        match_dom_selectors(
+            &ui_state_from_dom(dom, None),
+            &css,
+            &mut None,
+            &mut None,
+            &BTreeMap::new(),
+            false,
+            &WindowSize::default(),
+            WindowTheme::default(),
+            previous_frame,
+        )
+    };
+
+    let old_dom = Dom::div()
+        .with_child(Dom::label("1").with_class("a"))
+        .with_child(Dom::label("2").with_class("a"))
+        .with_child(Dom::label("3").with_class("a"));
+
+    let new_dom = || {
+        Dom::div()
+            .with_child(Dom::label("1").with_class("b"))
+            .with_child(Dom::label("2").with_class("a"))
+            .with_child(Dom::label("3").with_class("a"))
+    };
+
+    let mut previous = style(old_dom.clone(), None);
+    let diff = diff_dom_tree(&old_dom, &new_dom());
+
+    // Mark the style of the unchanged node, to see if it gets reused
+    let marker = StyledNode::default();
+    previous.styled_nodes[NodeId::new(2)] = marker.clone();
+
+    let incremental = style(new_dom(), Some((&previous, &diff)));
+    let full = style(new_dom(), None);
+
+    assert_eq!(
+        incremental.styled_nodes[NodeId::new(0)],
+        full.styled_nodes[NodeId::new(0)]
+    );
+    assert_eq!(
+        incremental.styled_nodes[NodeId::new(1)],
+        full.styled_nodes[NodeId::new(1)]
+    );
+    assert_eq!(incremental.styled_nodes[NodeId::new(2)], marker);
+    assert_eq!(
+        incremental.styled_nodes[NodeId::new(3)],
+        full.styled_nodes[NodeId::new(3)]
+    );
+    assert!(full.has_same_layout(&previous, &diff));
+}
+
+#[test]
+fn test_incremental_restyle_siblings() {
+    use crate::{diff::diff_dom_tree, dom::Dom, ui_state::ui_state_from_dom};
+    use azul_css::*;
+
+    struct Data;
+
+    // ".a + .b { color: red; }"
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![CssRuleBlock {
+                path: CssPath {
+                    selectors: vec![
+                        CssPathSelector::Class("a".into()),
+                        CssPathSelector::AdjacentSibling,
+                        CssPathSelector::Class("b".into()),
+                    ],
+                },
+                declarations: vec![CssDeclaration::Static(CssProperty::from(StyleTextColor(
+                    ColorU::RED,
+                )))],
+                media_queries: Vec::new(),
+                important: false,
+            }],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let style = |dom: Dom<Data>, previous_frame: Option<(&UiDescription<Data>, &DomDiff)>| {
+        match_dom_selectors(
+            &ui_state_from_dom(dom, None),
+            &css,
+            &mut None,
+            &mut None,
+            &BTreeMap::new(),
+            false,
+            &WindowSize::default(),
+            WindowTheme::default(),
+            previous_frame,
+        )
+    };
+
+    let old_dom = Dom::div()
+        .with_child(Dom::label("1").with_class("b"))
+        .with_child(Dom::label("2").with_class("b"));
+
+    // The second label didn't change, but its previous sibling did
+    let new_dom = || {
+        Dom::div()
+            .with_child(Dom::label("1").with_class("a"))
+            .with_child(Dom::label("2").with_class("b"))
+    };
+
+    let previous = style(old_dom.clone(), None);
+    let diff = diff_dom_tree(&old_dom, &new_dom());
+    assert!(!diff.needs_restyle(NodeId::new(2)));
+
+    let incremental = style(new_dom(), Some((&previous, &diff)));
+    assert_eq!(
+        incremental.styled_nodes[NodeId::new(2)]
+            .css_constraints
+            .get(&CssPropertyType::TextColor),
+        Some(&CssDeclaration::Static(CssProperty::from(StyleTextColor(
+            ColorU::RED
+        ))))
+    );
+}
+
+#[test]
+fn test_incremental_restyle_sibling_pseudo_state() {
+    use crate::{
+        callbacks::PipelineId, diff::diff_dom_tree, dom::Dom, ui_state::ui_state_from_dom,
+    };
+    use azul_css::*;
+
+    struct Data;
+
+    // ".a:hover + .b { color: red; }"
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![CssRuleBlock {
+                path: CssPath {
+                    selectors: vec![
+                        CssPathSelector::Class("a".into()),
+                        CssPathSelector::PseudoSelector(CssPathPseudoSelector::Hover),
+                        CssPathSelector::AdjacentSibling,
+                        CssPathSelector::Class("b".into()),
+                    ],
+                },
+                declarations: vec![CssDeclaration::Static(CssProperty::from(StyleTextColor(
+                    ColorU::RED,
+                )))],
+                media_queries: Vec::new(),
+                important: false,
+            }],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let dom = || {
+        Dom::div()
+            .with_child(Dom::label("1").with_class("a"))
+            .with_child(Dom::label("2").with_class("b"))
+    };
+
+    let style = |hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
+                 previous_frame: Option<(&UiDescription<Data>, &DomDiff)>| {
+        match_dom_selectors(
+            &ui_state_from_dom(dom(), None),
+            &css,
+            &mut None,
+            &mut None,
+            hovered_nodes,
+            false,
+            &WindowSize::default(),
+            WindowTheme::default(),
+            previous_frame,
+        )
+    };
+
+    // The DOM and the pseudo-state of the second label don't change,
+    // only the first label is hovered in the second frame
+    let mut hovered_nodes = BTreeMap::new();
+    hovered_nodes.insert(
+        NodeId::new(1),
+        HitTestItem {
+            pipeline: PipelineId::DUMMY,
+            tag: (1, 0),
+            point_in_viewport: LayoutPoint::zero(),
+            point_relative_to_item: LayoutPoint::zero(),
+        },
+    );
+
+    let previous = style(&BTreeMap::new(), None);
+    let diff = diff_dom_tree(&dom(), &dom());
+    assert!(!diff.needs_restyle(NodeId::new(2)));
+
+    let incremental = style(&hovered_nodes, Some((&previous, &diff)));
+    assert_eq!(
+        incremental.styled_nodes[NodeId::new(2)]
+            .css_constraints
+            .get(&CssPropertyType::TextColor),
+        Some(&CssDeclaration::Static(CssProperty::from(StyleTextColor(
+            ColorU::RED
+        ))))
+    );
+}
+
+#[test]
+fn test_inherit_relative_font_size() {
+    use crate::{dom::Dom, ui_state::ui_state_from_dom};
+    use azul_css::*;
+
+    struct Data;
+
+    let rule = |class: &str, font_size: PixelValue| CssRuleBlock {
+        path: CssPath {
+            selectors: vec![CssPathSelector::Class(class.into())],
+        },
+        declarations: vec![CssDeclaration::Static(CssProperty::from(StyleFontSize(
+            font_size,
+        )))],
+        media_queries: Vec::new(),
+        important: false,
+    };
+
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![
+                rule("em", PixelValue::em(2.0)),
+                rule("px", PixelValue::px(20.0)),
+            ],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let dom: Dom<Data> = Dom::div()
+        .with_child(Dom::div().with_class("em").with_child(Dom::label("1")))
+        .with_child(Dom::div().with_class("px").with_child(Dom::label("2")));
+
+    let ui_description = match_dom_selectors(
+        &ui_state_from_dom(dom, None),
+        &css,
+        &mut None,
+        &mut None,
+        &BTreeMap::new(),
+        false,
+        &WindowSize::default(),
+        WindowTheme::default(),
+        None,
+    );
+
+    let get_font_size = |node_id: usize| {
+        ui_description.styled_nodes[NodeId::new(node_id)]
+            .css_constraints
+            .get(&CssPropertyType::FontSize)
+            .cloned()
+    };
+
+    // "2em" is relative to the parent, the child has the same font size as its parent
+    assert_eq!(
+        get_font_size(2),
+        Some(CssDeclaration::Static(CssProperty::from(StyleFontSize(
+            PixelValue::em(1.0)
+        ))))
+    );
+    assert_eq!(
+        get_font_size(4),
+        Some(CssDeclaration::Static(CssProperty::from(StyleFontSize(
+            PixelValue::px(20.0)
+        ))))
+    );
+}
+
+#[test]
+fn test_cascade_specificity_and_important() {
+    use crate::{dom::Dom, ui_state::ui_state_from_dom};
+    use azul_css::{CssPathPseudoSelector::*, CssPathSelector::*, *};
+
+    struct Data;
+
+    let blue = ColorU {
+        r: 0,
+        g: 0,
+        b: 255,
+        a: 255,
+    };
+
+    let rule = |selectors: Vec<CssPathSelector>, color: ColorU, important: bool| CssRuleBlock {
+        path: CssPath { selectors },
+        declarations: vec![CssDeclaration::Static(CssProperty::from(StyleTextColor(
+            color,
+        )))],
+        media_queries: Vec::new(),
+        important,
+    };
+
+    // The first stylesheet is the "native" style, the second one overrides it
+    let native = Stylesheet {
+        rules: vec![
+            rule(
+                vec![Id("main".into()), Children, Class("a".into())],
+                ColorU::BLACK,
+                false,
+            ),
+            rule(
+                vec![Class("b".into()), Class("c".into())],
+                ColorU::BLACK,
+                false,
+            ),
+            rule(vec![Class("d".into())], blue, true),
+            rule(
+                vec![
+                    Class("e".into()),
+                    PseudoSelector(NthChild(CssNthChildSelector::Number(4))),
+                ],
+                ColorU::BLACK,
+                false,
+            ),
+        ],
+        keyframes: Vec::new(),
+        font_faces: Vec::new(),
+    };
+
+    let user = Stylesheet {
+        rules: vec![
+            rule(vec![Class("a".into())], ColorU::RED, false),
+            rule(
+                vec![Class("c".into()), Class("b".into())],
+                ColorU::WHITE,
+                false,
+            ),
+            rule(
+                vec![Id("main".into()), DirectChildren, Class("d".into())],
+                ColorU::RED,
+                false,
+            ),
+            rule(vec![Class("d".into())], ColorU::WHITE, true),
+            rule(vec![Class("e".into())], ColorU::RED, false),
+        ],
+        keyframes: Vec::new(),
+        font_faces: Vec::new(),
+    };
+
+    let mut css = Css {
+        stylesheets: vec![native, user],
+    };
+    css.sort_by_specificity();
+
+    let dom: Dom<Data> = Dom::div()
+        .with_id("main")
+        .with_child(Dom::label("1").with_class("a"))
+        .with_child(Dom::label("2").with_class("b").with_class("c"))
+        .with_child(Dom::label("3").with_class("d"))
+        .with_child(Dom::label("4").with_class("e"));
+
+    let ui_description = match_dom_selectors(
+        &ui_state_from_dom(dom, None),
+        &css,
+        &mut None,
+        &mut None,
+        &BTreeMap::new(),
+        false,
+        &WindowSize::default(),
+        WindowTheme::default(),
+        None,
+    );
+
+    let color = |node_id: usize| {
+        ui_description.styled_nodes[NodeId::new(node_id)]
+            .css_constraints
+            .get(&CssPropertyType::TextColor)
+            .cloned()
+    };
+    let expected = |color: ColorU| {
+        Some(CssDeclaration::Static(CssProperty::from(StyleTextColor(
+            color,
+        ))))
+    };
+
+    // A more specific rule wins, even if it's in an earlier stylesheet
+    assert_eq!(color(1), expected(ColorU::BLACK));
+    // Same specificity: the rule of the later stylesheet wins
+    assert_eq!(color(2), expected(ColorU::WHITE));
+    // !important wins over more specific rules, the later !important rule wins
+    assert_eq!(color(3), expected(ColorU::WHITE));
+    // Pseudo-selectors count as classes
+    assert_eq!(color(4), expected(ColorU::BLACK));
+}
+
+#[test]
+fn test_media_queries() {
+    use crate::{diff::diff_dom_tree, dom::Dom, ui_state::ui_state_from_dom, window::LogicalSize};
+    use azul_css::*;
+
+    struct Data;
+
+    // ".a { color: black; } @media (min-width: 600px) { .a { color: red; } }"
+    let rule = |color: ColorU, media_queries: Vec<CssMediaQuery>| CssRuleBlock {
+        path: CssPath {
+            selectors: vec![CssPathSelector::Class("a".into())],
+        },
+        declarations: vec![CssDeclaration::Static(CssProperty::from(StyleTextColor(
+            color,
+        )))],
+        media_queries,
+        important: false,
+    };
+
+    let css = Css {
+        stylesheets: vec![Stylesheet {
+            rules: vec![
+                rule(ColorU::BLACK, Vec::new()),
+                rule(
+                    ColorU::RED,
+                    vec![CssMediaQuery {
+                        features: vec![CssMediaFeature::MinWidth(PixelValueNoPercent(
+                            PixelValue::px(600.0),
+                        ))],
+                    }],
+                ),
+            ],
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }],
+    };
+
+    let window_size = |width: f32| WindowSize {
+        dimensions: LogicalSize::new(width, 400.0),
+        ..WindowSize::default()
+    };
+
+    let dom = || Dom::<Data>::div().with_class("a");
+    let diff = diff_dom_tree(&dom(), &dom());
+
+    let style = |width: f32, previous_frame: Option<(&UiDescription<Data>, &DomDiff)>| {
+        match_dom_selectors(
+            &ui_state_from_dom(dom(), None),
+            &css,
+            &mut None,
+            &mut None,
+            &BTreeMap::new(),
+            false,
+            &window_size(width),
+            WindowTheme::default(),
+            previous_frame,
+        )
+    };
+
+    let color = |ui_description: &UiDescription<Data>| {
+        ui_description.styled_nodes[NodeId::new(0)]
+            .css_constraints
+            .get(&CssPropertyType::TextColor)
+            .cloned()
+    };
+
+    let wide = style(800.0, None);
+    assert_eq!(
+        color(&wide),
+        Some(CssDeclaration::Static(StyleTextColor(ColorU::RED).into()))
+    );
+
+    // After resizing the window, the styles of the last frame can't be reused
+    let narrow = style(400.0, Some((&wide, &diff)));
+    assert_eq!(
+        color(&narrow),
+        Some(CssDeclaration::Static(StyleTextColor(ColorU::BLACK).into()))
+    );
+
+    let dark = CssMediaQuery {
+        features: vec![
+            CssMediaFeature::PrefersColorScheme(CssColorScheme::Dark),
+            CssMediaFeature::Orientation(CssMediaOrientation::Landscape),
+        ],
+    };
+    assert!(media_query_matches(
+        &dark,
+        &window_size(800.0),
+        WindowTheme::Dark
+    ));
+    assert!(!media_query_matches(
+        &dark,
+        &window_size(300.0),
+        WindowTheme::Dark
+    ));
+    assert!(!media_query_matches(
+        &dark,
+        &window_size(800.0),
+        WindowTheme::Light
+    ));
+}
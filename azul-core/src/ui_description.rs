@@ -1,11 +1,14 @@
 use azul_css::{Css, CssDeclaration, CssProperty, CssPropertyType};
-use std::{collections::BTreeMap, fmt};
+use std::{collections::BTreeMap, fmt, mem, time::Instant};
 
 use crate::{
+    animation::AnimationState,
     callbacks::{FocusTarget, HitTestItem},
+    diff::DomDiff,
     dom::{DomId, DomString, NodeData},
     id_tree::{Arena, NodeDataContainer, NodeId},
     ui_state::{HoverGroup, UiState},
+    window::{WindowSize, WindowTheme},
     FastHashMap,
 };
 
@@ -24,6 +27,17 @@ pub struct UiDescription<T> {
     /// that have a non-:hover path, for example if we have `#thing:hover`, then all nodes selected by `#thing`
     /// need to get a TagId, otherwise, they can't be hit-tested.
     pub selected_hover_nodes: BTreeMap<NodeId, HoverGroup>,
+    /// The `:hover`, `:active` and `:focus` state of each node at the time it was styled,
+    /// so that the next frame knows which nodes need to be restyled
+    pub pseudo_states: NodeDataContainer<PseudoState>,
+    /// For each rule of the `Css`, whether its `@media` queries matched the window
+    /// at the time this DOM was styled
+    pub media_query_results: Vec<bool>,
+    /// The CSS transitions and `@keyframes` animations that are running on this DOM
+    pub animations: AnimationState,
+    /// The current values of all animated properties - override the styled values
+    /// (including the dynamic CSS overrides) of the nodes
+    pub animated_properties: BTreeMap<NodeId, BTreeMap<CssPropertyType, CssProperty>>,
 }
 
 impl<T> fmt::Debug for UiDescription<T> {
@@ -37,6 +51,10 @@ impl<T> fmt::Debug for UiDescription<T> {
             styled_nodes: {:?},
             dynamic_css_overrides: {:?},
             selected_hover_nodes: {:?},
+            pseudo_states: {:?},
+            media_query_results: {:?},
+            animations: {:?},
+            animated_properties: {:?},
         }}",
             self.dom_id,
             self.ui_descr_arena,
@@ -44,6 +62,10 @@ impl<T> fmt::Debug for UiDescription<T> {
             self.styled_nodes,
             self.dynamic_css_overrides,
             self.selected_hover_nodes,
+            self.pseudo_states,
+            self.media_query_results,
+            self.animations,
+            self.animated_properties,
         )
     }
 }
@@ -57,6 +79,10 @@ impl<T> Clone for UiDescription<T> {
             styled_nodes: self.styled_nodes.clone(),
             dynamic_css_overrides: self.dynamic_css_overrides.clone(),
             selected_hover_nodes: self.selected_hover_nodes.clone(),
+            pseudo_states: self.pseudo_states.clone(),
+            media_query_results: self.media_query_results.clone(),
+            animations: self.animations.clone(),
+            animated_properties: self.animated_properties.clone(),
         }
     }
 }
@@ -80,6 +106,9 @@ impl<T> Default for UiDescription<T> {
             &mut focus_target,
             &hovered_nodes,
             is_mouse_down,
+            &WindowSize::default(),
+            WindowTheme::default(),
+            None,
         )
     }
 }
@@ -88,6 +117,12 @@ impl<T> UiDescription<T> {
     /// Applies the styles to the nodes calculated from the `layout_screen`
     /// function and calculates the final display list that is submitted to the
     /// renderer.
+    ///
+    /// The `@media` rules of the `Css` are evaluated against the `window_size` and `window_theme`.
+    ///
+    /// If the styled DOM of the last frame and the diff to the current DOM are given
+    /// (`previous_frame`), unchanged nodes reuse the styles of the last frame. Note that
+    /// the last frame has to be styled with the same `Css`.
     pub fn match_css_to_dom(
         ui_state: &mut UiState<T>,
         style: &Css,
@@ -95,6 +130,9 @@ impl<T> UiDescription<T> {
         pending_focus_target: &mut Option<FocusTarget>,
         hovered_nodes: &BTreeMap<NodeId, HitTestItem>,
         is_mouse_down: bool,
+        window_size: &WindowSize,
+        window_theme: WindowTheme,
+        previous_frame: Option<(&UiDescription<T>, &DomDiff)>,
     ) -> Self {
         use crate::ui_state::ui_state_create_tags_for_hover_nodes;
 
@@ -105,6 +143,9 @@ impl<T> UiDescription<T> {
             pending_focus_target,
             hovered_nodes,
             is_mouse_down,
+            window_size,
+            window_theme,
+            previous_frame,
         );
 
         // Important: Create all the tags for the :hover and :active selectors
@@ -112,6 +153,92 @@ impl<T> UiDescription<T> {
 
         ui_description
     }
+
+    /// Starts the CSS transitions and animations of this (freshly styled) frame and evaluates
+    /// them at the time `now`. Transitions can only be started if the `previous_frame` is
+    /// given, since they need to know which values changed.
+    pub fn update_animations(
+        &mut self,
+        previous_frame: Option<(&UiDescription<T>, &DomDiff)>,
+        css: &Css,
+        now: Instant,
+    ) {
+        crate::animation::update_animations(self, previous_frame, css, now)
+    }
+
+    /// Evaluates the running transitions and animations at the time `now`, without restyling.
+    /// Returns whether an animated property that can trigger a relayout changed.
+    pub fn tick_animations(&mut self, css: &Css, now: Instant) -> bool {
+        crate::animation::tick_animations(self, css, now)
+    }
+
+    /// Returns whether any CSS transition or animation is still running
+    pub fn is_animating(&self) -> bool {
+        self.animations.is_animating()
+    }
+
+    /// Returns whether the layout of the `previous` frame can be reused for this frame:
+    /// no nodes were added or removed, no text or images changed and none of
+    /// the CSS properties that can trigger a relayout changed (including animated properties).
+    pub fn has_same_layout(&self, previous: &UiDescription<T>, diff: &DomDiff) -> bool {
+        if diff.needs_relayout() || self.dynamic_css_overrides != previous.dynamic_css_overrides {
+            return false;
+        }
+
+        self.styled_nodes.linear_iter().all(|node_id| {
+            let old_node_id = match diff.get_old_node_id(node_id) {
+                Some(s) => s,
+                None => return false,
+            };
+            match previous.styled_nodes.get(old_node_id) {
+                Some(old_style) => {
+                    old_style.has_same_layout(&self.styled_nodes[node_id])
+                        && previous
+                            .animated_layout_properties(old_node_id)
+                            .eq(self.animated_layout_properties(node_id))
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Returns the animated properties of a node that can trigger a relayout
+    fn animated_layout_properties(
+        &self,
+        node_id: NodeId,
+    ) -> impl Iterator<Item = (&CssPropertyType, &CssProperty)> {
+        self.animated_properties
+            .get(&node_id)
+            .into_iter()
+            .flat_map(|properties| properties.iter())
+            .filter(|(property_type, _)| property_type.can_trigger_relayout())
+    }
+
+    /// Resolves the CSS declarations of a node, including the dynamic CSS overrides.
+    /// Animated properties are not included, see `animated_properties`.
+    pub fn get_computed_style(&self, node_id: NodeId) -> BTreeMap<CssPropertyType, CssProperty> {
+        let overrides = self.dynamic_css_overrides.get(&node_id);
+
+        self.styled_nodes[node_id]
+            .css_constraints
+            .iter()
+            .map(|(property_type, declaration)| {
+                let property = match declaration {
+                    CssDeclaration::Static(property) => property.clone(),
+                    CssDeclaration::Dynamic(dynamic_property) => overrides
+                        .and_then(|o| o.get(&dynamic_property.dynamic_id.clone().into()))
+                        // same as in the display list: overrides of the wrong type are ignored
+                        .filter(|o| {
+                            mem::discriminant(*o)
+                                == mem::discriminant(&dynamic_property.default_value)
+                        })
+                        .unwrap_or(&dynamic_property.default_value)
+                        .clone(),
+                };
+                (*property_type, property)
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Hash, PartialOrd, Eq, Ord)]
@@ -119,3 +246,24 @@ pub struct StyledNode {
     /// The CSS constraints, after the cascading step
     pub css_constraints: BTreeMap<CssPropertyType, CssDeclaration>,
 }
+
+impl StyledNode {
+    /// Returns whether all the CSS properties that can trigger a relayout are the same
+    pub fn has_same_layout(&self, other: &StyledNode) -> bool {
+        self.layout_constraints().eq(other.layout_constraints())
+    }
+
+    fn layout_constraints(&self) -> impl Iterator<Item = (&CssPropertyType, &CssDeclaration)> {
+        self.css_constraints
+            .iter()
+            .filter(|(property_type, _)| property_type.can_trigger_relayout())
+    }
+}
+
+/// The `:hover`, `:active` and `:focus` state of a node
+#[derive(Debug, Default, Copy, Clone, PartialEq, Hash, PartialOrd, Eq, Ord)]
+pub struct PseudoState {
+    pub is_hovered_over: bool,
+    pub is_active: bool,
+    pub is_focused: bool,
+}
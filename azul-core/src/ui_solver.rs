@@ -1,6 +1,7 @@
 use azul_css::{
-    ColorU as StyleColorU, LayoutRect, LayoutSize, Overflow, PixelValue, StyleFontSize,
-    StyleTextAlignmentHorz, StyleTextAlignmentVert, StyleTextColor,
+    ColorU as StyleColorU, FontFeature, FontVariation, LayoutPoint, LayoutRect, LayoutSize,
+    Overflow, PixelValue, StyleFontSize, StyleTextAlignmentHorz, StyleTextAlignmentVert,
+    StyleTextColor, StyleTextOverflow, StyleWhiteSpace,
 };
 use std::collections::BTreeMap;
 
@@ -144,14 +145,45 @@ pub struct OverflowingScrollNode {
 
 #[derive(Debug, Default, Clone)]
 pub struct LayoutResult {
+    /// The rectangle that the DOM was layouted in (usually the window size),
+    /// the layout can only be reused for the same bounds
+    pub bounding_rect: LayoutRect,
     pub rects: NodeDataContainer<PositionedRectangle>,
     pub word_cache: BTreeMap<NodeId, Words>,
-    pub scaled_words: BTreeMap<NodeId, (ScaledWords, FontInstanceKey)>,
-    pub positioned_word_cache: BTreeMap<NodeId, (WordPositions, FontInstanceKey)>,
+    /// Shaped words + the font instances of the font fallback chain of the text
+    /// (indexed by `ScaledWord::glyph_fonts`)
+    pub scaled_words: BTreeMap<NodeId, (ScaledWords, Vec<FontInstanceKey>)>,
+    pub positioned_word_cache: BTreeMap<NodeId, (WordPositions, Vec<FontInstanceKey>)>,
     pub layouted_glyph_cache: BTreeMap<NodeId, LayoutedGlyphs>,
+    /// Maps the words and font instances of the `NodeType::RichText` nodes to their spans
+    pub rich_text_spans: BTreeMap<NodeId, RichTextSpans>,
     pub node_depths: Vec<(usize, NodeId)>,
 }
 
+/// Maps the shaped text of a `NodeType::RichText` paragraph back to the spans it was created from
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RichTextSpans {
+    /// Span of each font instance of the paragraph (each span has its own font fallback
+    /// chain, so this is indexed by `ScaledWord::glyph_fonts`)
+    pub font_spans: Vec<usize>,
+    /// Span of each word of the paragraph
+    pub word_spans: Vec<usize>,
+    /// Bounds of each word of the paragraph (same coordinate space as the
+    /// `PositionedRectangle::bounds`), only known after the final layout
+    pub word_bounds: Vec<LayoutRect>,
+}
+
+impl RichTextSpans {
+    /// Returns the index of the span that contains the `point`, if the point is over any word
+    pub fn hit_test(&self, point: &LayoutPoint) -> Option<usize> {
+        self.word_bounds
+            .iter()
+            .zip(self.word_spans.iter())
+            .find(|(bounds, _)| bounds.contains(point))
+            .map(|(_, span_idx)| *span_idx)
+    }
+}
+
 /// Layout options that can impact the flow of word positions
 #[derive(Debug, Clone, PartialEq, PartialOrd, Default)]
 pub struct TextLayoutOptions {
@@ -176,6 +208,18 @@ pub struct TextLayoutOptions {
     ///
     /// TODO: Currently unused!
     pub holes: Vec<LayoutRect>,
+    /// Whether the lines wrap at the `max_horizontal_width` (`white-space`). Note that
+    /// whitespace is collapsed when the text is split into words, not during the layout.
+    pub white_space: StyleWhiteSpace,
+    /// Whether lines that are wider than the `max_horizontal_width` end with an ellipsis
+    pub text_overflow: StyleTextOverflow,
+    /// Maximum number of lines (`line-clamp`), the following lines are cut off
+    pub max_lines: Option<usize>,
+    /// OpenType features that the text is shaped with (`font-variant-numeric`, followed
+    /// by `font-feature-settings`), in addition to kerning and ligatures
+    pub font_features: Vec<FontFeature>,
+    /// Values of the variation axes of variable fonts (`font-variation-settings`)
+    pub font_variations: Vec<FontVariation>,
 }
 
 /// Same as `TextLayoutOptions`, but with the widths / heights of the `PixelValue`s
@@ -203,6 +247,18 @@ pub struct ResolvedTextLayoutOptions {
     ///
     /// TODO: Currently unused!
     pub holes: Vec<LayoutRect>,
+    /// Whether the lines wrap at the `max_horizontal_width` (`white-space`). Note that
+    /// whitespace is collapsed when the text is split into words, not during the layout.
+    pub white_space: StyleWhiteSpace,
+    /// Whether lines that are wider than the `max_horizontal_width` end with an ellipsis
+    pub text_overflow: StyleTextOverflow,
+    /// Maximum number of lines (`line-clamp`), the following lines are cut off
+    pub max_lines: Option<usize>,
+    /// OpenType features that the text is shaped with (`font-variant-numeric`, followed
+    /// by `font-feature-settings`), in addition to kerning and ligatures
+    pub font_features: Vec<FontFeature>,
+    /// Values of the variation axes of variable fonts (`font-variation-settings`)
+    pub font_variations: Vec<FontVariation>,
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
@@ -281,3 +337,22 @@ pub struct LayoutedRectangle {
     /// Determines if the rect should be clipped or not (TODO: x / y as separate fields!)
     pub overflow: Overflow,
 }
+
+#[test]
+fn test_rich_text_spans_hit_test() {
+    let spans = RichTextSpans {
+        font_spans: vec![0, 1],
+        word_spans: vec![0, 0, 1],
+        word_bounds: vec![
+            LayoutRect::new(LayoutPoint::new(0.0, 0.0), LayoutSize::new(40.0, 16.0)),
+            LayoutRect::new(LayoutPoint::new(45.0, 0.0), LayoutSize::new(30.0, 16.0)),
+            LayoutRect::new(LayoutPoint::new(0.0, 16.0), LayoutSize::new(50.0, 16.0)),
+        ],
+    };
+
+    assert_eq!(spans.hit_test(&LayoutPoint::new(10.0, 5.0)), Some(0));
+    assert_eq!(spans.hit_test(&LayoutPoint::new(50.0, 5.0)), Some(0));
+    assert_eq!(spans.hit_test(&LayoutPoint::new(20.0, 20.0)), Some(1));
+    // gap between the first two words
+    assert_eq!(spans.hit_test(&LayoutPoint::new(42.0, 5.0)), None);
+}
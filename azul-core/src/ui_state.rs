@@ -5,8 +5,8 @@ use crate::{
     app::{AppState, RuntimeError},
     callbacks::{Callback, DefaultCallbackId, LayoutInfo},
     dom::{
-        Dom, DomId, DomString, FocusEventFilter, HoverEventFilter, NotEventFilter, TabIndex, TagId,
-        WindowEventFilter,
+        Dom, DomId, DomString, FocusEventFilter, HoverEventFilter, NodeType, NotEventFilter,
+        TabIndex, TagId, WindowEventFilter,
     },
     id_tree::NodeId,
     window::WindowId,
@@ -48,6 +48,9 @@ pub struct UiState<T> {
     pub not_default_callbacks: BTreeMap<NodeId, BTreeMap<NotEventFilter, DefaultCallbackId>>,
     pub window_callbacks: BTreeMap<NodeId, BTreeMap<WindowEventFilter, Callback<T>>>,
     pub window_default_callbacks: BTreeMap<NodeId, BTreeMap<WindowEventFilter, DefaultCallbackId>>,
+    /// Hover callbacks of the spans of `NodeType::RichText` nodes, by span index. The node
+    /// itself is hit-tested, which span was hit is determined from the text layout.
+    pub span_callbacks: BTreeMap<NodeId, BTreeMap<usize, BTreeMap<HoverEventFilter, Callback<T>>>>,
 }
 
 impl<T> fmt::Debug for UiState<T> {
@@ -71,6 +74,7 @@ impl<T> fmt::Debug for UiState<T> {
                 not_default_callbacks: {:?}, \
                 window_callbacks: {:?}, \
                 window_default_callbacks: {:?}, \
+                span_callbacks: {:?}, \
             }}",
             self.dom,
             self.dynamic_css_overrides,
@@ -87,6 +91,7 @@ impl<T> fmt::Debug for UiState<T> {
             self.not_default_callbacks,
             self.window_callbacks,
             self.window_default_callbacks,
+            self.span_callbacks,
         )
     }
 }
@@ -207,6 +212,7 @@ pub fn ui_state_from_dom<T>(
     let mut not_default_callbacks = BTreeMap::new();
     let mut window_callbacks = BTreeMap::new();
     let mut window_default_callbacks = BTreeMap::new();
+    let mut span_callbacks = BTreeMap::new();
 
     // data.callbacks, HoverEventFilter, Callback<T>, as_hover_event_filter, hover_callbacks, <node_tag_id> (optional)
     macro_rules! filter_and_insert_callbacks {
@@ -352,6 +358,34 @@ pub fn ui_state_from_dom<T>(
                 );
             }
 
+            if let NodeType::RichText(spans) = node.get_node_type() {
+                let node_span_callbacks: BTreeMap<usize, BTreeMap<HoverEventFilter, Callback<T>>> =
+                    spans
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(span_idx, span)| {
+                            let callbacks = span
+                                .callbacks
+                                .iter()
+                                .filter_map(|(event_filter, cb)| {
+                                    event_filter.as_hover_event_filter().map(|evt| (evt, *cb))
+                                })
+                                .collect::<BTreeMap<_, _>>();
+                            if callbacks.is_empty() {
+                                None
+                            } else {
+                                Some((span_idx, callbacks))
+                            }
+                        })
+                        .collect();
+
+                if !node_span_callbacks.is_empty() {
+                    span_callbacks.insert(node_id, node_span_callbacks);
+                    let tag_id = node_tag_id.unwrap_or_else(|| new_tag_id());
+                    node_tag_id = Some(tag_id);
+                }
+            }
+
             if node.get_is_draggable() {
                 let tag_id = node_tag_id.unwrap_or_else(|| new_tag_id());
                 draggable_tags.insert(tag_id, node_id);
@@ -409,5 +443,6 @@ pub fn ui_state_from_dom<T>(
         not_default_callbacks,
         window_callbacks,
         window_default_callbacks,
+        span_callbacks,
     }
 }
@@ -319,6 +319,9 @@ pub struct WindowState {
     pub request_user_attention: bool,
     /// Set the windows Wayland theme. Irrelevant on other platforms, set to `None`
     pub wayland_theme: Option<WaylandTheme>,
+    /// Light or dark theme, used for evaluating `@media (prefers-color-scheme)` queries.
+    /// NOTE: not (yet) synchronized with the OS setting, has to be set by the application
+    pub theme: WindowTheme,
 }
 
 impl WindowState {
@@ -345,6 +348,19 @@ pub enum FullScreenMode {
     FastWindowed,
 }
 
+/// Color scheme of the window, see `WindowState::theme`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WindowTheme {
+    Light,
+    Dark,
+}
+
+impl Default for WindowTheme {
+    fn default() -> Self {
+        WindowTheme::Light
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct WaylandTheme {
     /// Primary color when the window is focused
@@ -436,6 +452,7 @@ impl Default for WindowState {
             ime_position: None,
             request_user_attention: false,
             wayland_theme: None,
+            theme: WindowTheme::default(),
         }
     }
 }
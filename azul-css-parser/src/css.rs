@@ -6,9 +6,13 @@ use std::{collections::HashMap, fmt, num::ParseIntError};
 use crate::css_parser;
 pub use crate::css_parser::CssParsingError;
 use azul_css::{
-    CombinedCssPropertyType, Css, CssDeclaration, CssKeyMap, CssNthChildSelector,
-    CssNthChildSelector::*, CssPath, CssPathPseudoSelector, CssPathSelector, CssPropertyType,
-    CssRuleBlock, DynamicCssProperty, NodeTypePath, NodeTypePathParseError, Stylesheet,
+    CombinedCssPropertyType, Css, CssAttributeOperator, CssAttributeSelector, CssColorScheme,
+    CssDeclaration, CssFontFace, CssFontFaceSource, CssKeyMap, CssKeyframe, CssKeyframes,
+    CssMediaFeature, CssMediaOrientation,
+    CssMediaQuery, CssNthChildSelector, CssNthChildSelector::*, CssPath, CssPathPseudoSelector,
+    CssPathSelector, CssPropertyType, CssRuleBlock, DynamicCssProperty, FloatValue, NodeTypePath,
+    NodeTypePathParseError, PercentageValue, StyleFontStretch, StyleFontStyle, StyleFontWeight,
+    Stylesheet,
 };
 
 /// Error that can happen during the parsing of a CSS value
@@ -41,6 +45,8 @@ pub enum CssParseErrorInner<'a> {
     DynamicCssParseError(DynamicCssParseError<'a>),
     /// Error while parsing a pseudo selector (like `:aldkfja`)
     PseudoSelectorParseError(CssPseudoSelectorParseError<'a>),
+    /// Error while parsing an attribute selector (like `[=value]`)
+    InvalidAttributeSelector(&'a str),
     /// The path has to be either `*`, `div`, `p` or something like that
     NodeTypePath(NodeTypePathParseError<'a>),
     /// A certain property has an unknown key, for example: `alsdfkj: 500px` = `unknown CSS key "alsdfkj: 500px"`
@@ -53,6 +59,12 @@ pub enum CssParseErrorInner<'a> {
         key: CombinedCssPropertyType,
         value: &'a str,
     },
+    /// Error while parsing a `@keyframes` rule
+    KeyframesParseError(CssKeyframesParseError<'a>),
+    /// Error while parsing the query of a `@media` rule
+    MediaQueryParseError(CssMediaQueryParseError<'a>),
+    /// Error while parsing a `@font-face` rule
+    FontFaceParseError(CssFontFaceParseError<'a>),
 }
 
 impl_display! { CssParseErrorInner<'a>, {
@@ -61,12 +73,16 @@ impl_display! { CssParseErrorInner<'a>, {
     MalformedCss => "Malformed Css",
     DynamicCssParseError(e) => format!("{}", e),
     PseudoSelectorParseError(e) => format!("Failed to parse pseudo-selector: {}", e),
+    InvalidAttributeSelector(e) => format!("Invalid attribute selector: \"[{}]\"", e),
     NodeTypePath(e) => format!("Failed to parse CSS selector path: {}", e),
     UnknownPropertyKey(k, v) => format!("Unknown CSS key: \"{}: {}\"", k, v),
     VarOnShorthandProperty { key, value } => format!(
         "Error while parsing: \"{}: {};\": var() cannot be used on shorthand properties - use `{}-top` or `{}-x` as the key instead: ",
         key, value, key, key
     ),
+    KeyframesParseError(e) => format!("Failed to parse @keyframes: {}", e),
+    MediaQueryParseError(e) => format!("Failed to parse @media query: {}", e),
+    FontFaceParseError(e) => format!("Failed to parse @font-face: {}", e),
 }}
 
 impl<'a> From<CssSyntaxError> for CssParseErrorInner<'a> {
@@ -78,6 +94,85 @@ impl<'a> From<CssSyntaxError> for CssParseErrorInner<'a> {
 impl_from! { DynamicCssParseError<'a>, CssParseErrorInner::DynamicCssParseError }
 impl_from! { NodeTypePathParseError<'a>, CssParseErrorInner::NodeTypePath }
 impl_from! { CssPseudoSelectorParseError<'a>, CssParseErrorInner::PseudoSelectorParseError }
+impl_from! { CssKeyframesParseError<'a>, CssParseErrorInner::KeyframesParseError }
+impl_from! { CssMediaQueryParseError<'a>, CssParseErrorInner::MediaQueryParseError }
+impl_from! { CssFontFaceParseError<'a>, CssParseErrorInner::FontFaceParseError }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssKeyframesParseError<'a> {
+    /// `@keyframes { ... }` - the name of the animation is missing
+    MissingName,
+    /// Keyframe selectors have to be `from`, `to` or a percentage between `0%` and `100%`
+    InvalidKeyframeSelector(&'a str),
+}
+
+impl_display! { CssKeyframesParseError<'a>, {
+    MissingName => "Missing name of the @keyframes rule",
+    InvalidKeyframeSelector(selector) => format!(
+        "Invalid keyframe selector: \"{}\" - expected \"from\", \"to\" or a percentage", selector
+    ),
+}}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssFontFaceParseError<'a> {
+    /// `@font-face { src: url(a.ttf); }` - the `font-family` descriptor is missing
+    MissingFontFamily,
+    /// `@font-face { font-family: A; }` - the `src` descriptor is missing
+    MissingSource,
+    /// A source has to be `url(...)` or `local(...)`, optionally followed by `format(...)`
+    InvalidSource(&'a str),
+    /// `font-weight` has to be `normal`, `bold` or a number between 1 and 1000
+    InvalidFontWeight(&'a str),
+    /// `font-style` has to be `normal`, `italic` or `oblique`
+    InvalidFontStyle(&'a str),
+    /// `font-stretch` has to be a keyword from `ultra-condensed` to `ultra-expanded`
+    InvalidFontStretch(&'a str),
+}
+
+impl_display! { CssFontFaceParseError<'a>, {
+    MissingFontFamily => "Missing font-family descriptor",
+    MissingSource => "Missing src descriptor",
+    InvalidSource(source) => format!(
+        "Invalid font source: \"{}\" - expected \"url(...)\" or \"local(...)\"", source
+    ),
+    InvalidFontWeight(weight) => format!(
+        "Invalid font-weight: \"{}\" - expected \"normal\", \"bold\" or a number from 1 to 1000", weight
+    ),
+    InvalidFontStyle(style) => format!(
+        "Invalid font-style: \"{}\" - expected \"normal\", \"italic\" or \"oblique\"", style
+    ),
+    InvalidFontStretch(stretch) => format!(
+        "Invalid font-stretch: \"{}\" - expected a keyword such as \"condensed\" or \"expanded\"", stretch
+    ),
+}}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssMediaQueryParseError<'a> {
+    /// `@media screen, { ... }` - one of the comma-separated queries is empty
+    EmptyQuery,
+    /// Only the `screen` and `all` media types are supported, since Azul only renders to screens
+    UnsupportedMediaType(&'a str),
+    /// The query isn't of the form `[only] screen and (feature: value) and ...`
+    MalformedQuery(&'a str),
+    /// The media feature isn't supported, i.e. `(hover: hover)`
+    UnknownFeature(&'a str),
+    /// The value of a media feature is invalid, i.e. `(orientation: diagonal)`
+    InvalidValue { feature: &'a str, value: &'a str },
+}
+
+impl_display! { CssMediaQueryParseError<'a>, {
+    EmptyQuery => "Empty media query",
+    UnsupportedMediaType(media_type) => format!(
+        "Unsupported media type: \"{}\" - expected \"screen\" or \"all\"", media_type
+    ),
+    MalformedQuery(query) => format!(
+        "Malformed media query: \"{}\" - expected \"(feature: value) and ...\"", query
+    ),
+    UnknownFeature(feature) => format!("Unknown media feature: \"{}\"", feature),
+    InvalidValue { feature, value } => format!(
+        "Invalid value for media feature \"{}\": \"{}\"", feature, value
+    ),
+}}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CssPseudoSelectorParseError<'a> {
@@ -85,6 +180,8 @@ pub enum CssPseudoSelectorParseError<'a> {
     UnknownSelector(&'a str, Option<&'a str>),
     InvalidNthChildPattern(&'a str),
     InvalidNthChild(ParseIntError),
+    /// `:not()` only accepts selectors without combinators, such as `:not(div.class)`
+    InvalidNotSelector(&'a str),
 }
 
 impl<'a> From<ParseIntError> for CssPseudoSelectorParseError<'a> {
@@ -110,6 +207,10 @@ impl_display! { CssPseudoSelectorParseError<'a>, {
         number, \"even\" or \"odd\" or a pattern such as \"2n+3\"", selector
     ),
     InvalidNthChild(e) => format!("Invalid :nth-child pseudo-selector: ':{}'", e),
+    InvalidNotSelector(selector) => format!(
+        "Invalid pseudo-selector :not({}) - value has to be a selector \
+        without combinators, such as \"div.class\"", selector
+    ),
 }}
 
 /// Error that can happen during `css_parser::parse_key_value_pair`
@@ -144,17 +245,97 @@ fn pseudo_selector_from_str<'a>(
         "hover" => Ok(CssPathPseudoSelector::Hover),
         "active" => Ok(CssPathPseudoSelector::Active),
         "focus" => Ok(CssPathPseudoSelector::Focus),
+        "empty" => Ok(CssPathPseudoSelector::Empty),
         "nth-child" => {
             let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
             let parsed = parse_nth_child_selector(value)?;
             Ok(CssPathPseudoSelector::NthChild(parsed))
         }
+        "nth-last-child" => {
+            let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
+            let parsed = parse_nth_child_selector(value)?;
+            Ok(CssPathPseudoSelector::NthLastChild(parsed))
+        }
+        "nth-of-type" => {
+            let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
+            let parsed = parse_nth_child_selector(value)?;
+            Ok(CssPathPseudoSelector::NthOfType(parsed))
+        }
+        "nth-last-of-type" => {
+            let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
+            let parsed = parse_nth_child_selector(value)?;
+            Ok(CssPathPseudoSelector::NthLastOfType(parsed))
+        }
         _ => Err(CssPseudoSelectorParseError::UnknownSelector(
             selector, value,
         )),
     }
 }
 
+/// Same as `pseudo_selector_from_str`, but also parses `:not()`, whose
+/// value is a selector itself, i.e. `:not(.class)`
+fn css_path_selector_from_pseudo<'a>(
+    selector: &'a str,
+    value: Option<&'a str>,
+) -> Result<CssPathSelector, CssPseudoSelectorParseError<'a>> {
+    match (selector, value) {
+        ("not", Some(value)) => {
+            let selectors = parse_css_path(value)
+                .map_err(|_| CssPseudoSelectorParseError::InvalidNotSelector(value))?
+                .selectors;
+            if selectors.iter().any(|selector| selector.is_combinator()) {
+                return Err(CssPseudoSelectorParseError::InvalidNotSelector(value));
+            }
+            Ok(CssPathSelector::Not(selectors))
+        }
+        _ => pseudo_selector_from_str(selector, value).map(CssPathSelector::PseudoSelector),
+    }
+}
+
+/// Parses the contents of an attribute selector (between the brackets), i.e.
+/// `type` or `type="text"` - returns `None` if the attribute selector is invalid
+fn parse_attribute_selector(input: &str) -> Option<CssAttributeSelector> {
+    let (name, value) = match input.find('=') {
+        None => (input, None),
+        Some(equals_pos) => {
+            let (name, operator) = match input[..equals_pos].chars().last()? {
+                '~' => (&input[..equals_pos - 1], CssAttributeOperator::ContainsWord),
+                '|' => (&input[..equals_pos - 1], CssAttributeOperator::DashMatch),
+                '^' => (&input[..equals_pos - 1], CssAttributeOperator::StartsWith),
+                '$' => (&input[..equals_pos - 1], CssAttributeOperator::EndsWith),
+                '*' => (&input[..equals_pos - 1], CssAttributeOperator::Contains),
+                _ => (&input[..equals_pos], CssAttributeOperator::Equals),
+            };
+            let value = input[equals_pos + 1..].trim();
+            let value = if value.len() >= 2
+                && (value.starts_with('"') && value.ends_with('"')
+                    || value.starts_with('\'') && value.ends_with('\''))
+            {
+                &value[1..value.len() - 1]
+            } else if value.is_empty() || value.contains(char::is_whitespace) {
+                return None;
+            } else {
+                value
+            };
+            (name, Some((operator, value.to_string())))
+        }
+    };
+
+    let name = name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return None;
+    }
+
+    Some(CssAttributeSelector {
+        name: name.to_string(),
+        value,
+    })
+}
+
 /// Parses the inner value of the `:nth-child` selector, including numbers and patterns.
 ///
 /// I.e.: `"2n+3"` -> `Pattern { repeat: 2, offset: 3 }`
@@ -229,7 +410,11 @@ fn test_css_pseudo_selector_parse() {
         (("hover", None), Hover),
         (("active", None), Active),
         (("focus", None), Focus),
+        (("empty", None), Empty),
         (("nth-child", Some("4")), NthChild(Number(4))),
+        (("nth-last-child", Some("odd")), NthLastChild(Odd)),
+        (("nth-of-type", Some("2")), NthOfType(Number(2))),
+        (("nth-last-of-type", Some("even")), NthLastOfType(Even)),
         (("nth-child", Some("even")), NthChild(Even)),
         (("nth-child", Some("odd")), NthChild(Odd)),
         (
@@ -252,6 +437,7 @@ fn test_css_pseudo_selector_parse() {
         (("asdf", None), UnknownSelector("asdf", None)),
         (("", None), UnknownSelector("", None)),
         (("nth-child", Some("2n+")), InvalidNthChildPattern("2n+")),
+        (("nth-of-type", None), EmptyNthChild),
         // Can't test for ParseIntError because the fields are private.
         // This is an example on why you shouldn't use std::error::Error!
     ];
@@ -265,6 +451,84 @@ fn test_css_pseudo_selector_parse() {
     }
 }
 
+#[test]
+fn test_css_parse_sibling_not_attribute_selectors() {
+    use azul_css::{CssAttributeOperator::*, CssPathSelector::*};
+
+    let attribute = |name: &str, value: Option<(CssAttributeOperator, &str)>| {
+        Attribute(CssAttributeSelector {
+            name: name.to_string(),
+            value: value.map(|(operator, value)| (operator, value.to_string())),
+        })
+    };
+
+    assert_eq!(
+        parse_css_path("div.a + p ~ .b:not(p.c)"),
+        Ok(CssPath {
+            selectors: vec![
+                Type(NodeTypePath::Div),
+                Class("a".to_string()),
+                AdjacentSibling,
+                Type(NodeTypePath::P),
+                GeneralSibling,
+                Class("b".to_string()),
+                Not(vec![Type(NodeTypePath::P), Class("c".to_string())]),
+            ]
+        })
+    );
+
+    assert_eq!(
+        parse_css_path("[type][type=\"text\"][lang|=en][class~='x y'][src^=http]"),
+        Ok(CssPath {
+            selectors: vec![
+                attribute("type", None),
+                attribute("type", Some((Equals, "text"))),
+                attribute("lang", Some((DashMatch, "en"))),
+                attribute("class", Some((ContainsWord, "x y"))),
+                attribute("src", Some((StartsWith, "http"))),
+            ]
+        })
+    );
+
+    assert_eq!(
+        parse_css_path(".a:not(.b .c)"),
+        Err(CssPathParseError::PseudoSelectorParseError(
+            CssPseudoSelectorParseError::InvalidNotSelector(".b .c")
+        ))
+    );
+    assert_eq!(
+        parse_css_path("[=value]"),
+        Err(CssPathParseError::InvalidAttributeSelector("=value"))
+    );
+
+    // Sibling selectors in a full stylesheet
+    let css = new_from_str(".a + .b, .a ~ [data-x$=\"y\"] { color: red; }").unwrap();
+    let paths = css.stylesheets[0]
+        .rules
+        .iter()
+        .map(|rule| rule.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![
+            CssPath {
+                selectors: vec![
+                    Class("a".to_string()),
+                    AdjacentSibling,
+                    Class("b".to_string())
+                ]
+            },
+            CssPath {
+                selectors: vec![
+                    Class("a".to_string()),
+                    GeneralSibling,
+                    attribute("data-x", Some((EndsWith, "y"))),
+                ]
+            },
+        ]
+    );
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ErrorLocation {
     pub original_pos: usize,
@@ -307,17 +571,17 @@ impl<'a> fmt::Display for CssParseError<'a> {
 }
 
 pub fn new_from_str<'a>(css_string: &'a str) -> Result<Css, CssParseError<'a>> {
-    let mut tokenizer = Tokenizer::new(css_string);
-    let (stylesheet, _warnings) = new_from_str_inner(css_string, &mut tokenizer)?;
+    let (stylesheet, _warnings) = new_from_str_inner(css_string)?;
     Ok(Css {
         stylesheets: vec![stylesheet],
     })
 }
 
 /// Returns the location of where the parser is currently in the document
-fn get_error_location(tokenizer: &Tokenizer) -> ErrorLocation {
+/// (`offset` is the position where the tokenized part of the document starts)
+fn get_error_location(tokenizer: &Tokenizer, offset: usize) -> ErrorLocation {
     ErrorLocation {
-        original_pos: tokenizer.pos(),
+        original_pos: offset + tokenizer.pos(),
     }
 }
 
@@ -332,6 +596,8 @@ pub enum CssPathParseError<'a> {
     NodeTypePath(NodeTypePathParseError<'a>),
     /// Error while parsing a pseudo selector (like `:aldkfja`)
     PseudoSelectorParseError(CssPseudoSelectorParseError<'a>),
+    /// Error while parsing an attribute selector (like `[=value]`)
+    InvalidAttributeSelector(&'a str),
 }
 
 impl_from! { NodeTypePathParseError<'a>, CssPathParseError::NodeTypePath }
@@ -399,10 +665,20 @@ pub fn parse_css_path<'a>(input: &'a str) -> Result<CssPath, CssPathParseError<'
             Token::Combinator(Combinator::Space) => {
                 selectors.push(CssPathSelector::Children);
             }
+            Token::Combinator(Combinator::Plus) => {
+                selectors.push(CssPathSelector::AdjacentSibling);
+            }
+            Token::Combinator(Combinator::Tilde) => {
+                selectors.push(CssPathSelector::GeneralSibling);
+            }
+            Token::AttributeSelector(attribute) => {
+                selectors.push(CssPathSelector::Attribute(
+                    parse_attribute_selector(attribute)
+                        .ok_or(CssPathParseError::InvalidAttributeSelector(attribute))?,
+                ));
+            }
             Token::PseudoClass { selector, value } => {
-                selectors.push(CssPathSelector::PseudoSelector(pseudo_selector_from_str(
-                    selector, value,
-                )?));
+                selectors.push(css_path_selector_from_pseudo(selector, value)?);
             }
             Token::EndOfStream => {
                 break;
@@ -426,6 +702,10 @@ pub struct UnparsedCssRuleBlock<'a> {
     pub path: CssPath,
     /// `"justify-content" => "center"`
     pub declarations: HashMap<&'a str, (&'a str, (ErrorLocation, ErrorLocation))>,
+    /// The queries of the `@media` rules the block is nested in
+    pub media_queries: Vec<CssMediaQuery>,
+    /// Whether the declarations are `!important` (with the `!important` already stripped)
+    pub important: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -447,8 +727,55 @@ pub enum CssParseWarnMsgInner<'a> {
 /// but can be useful for debugging.
 fn new_from_str_inner<'a>(
     css_string: &'a str,
-    tokenizer: &mut Tokenizer<'a>,
 ) -> Result<(Stylesheet, Vec<CssParseWarnMsg<'a>>), CssParseError<'a>> {
+    // The tokenizer can't parse `@keyframes`, `@media` and `@font-face` rules, so they are cut
+    // out of the CSS and parsed separately - the remaining parts are tokenized one after another
+    let mut segments = Vec::new();
+    let mut unparsed_keyframes = Vec::new();
+    let mut unparsed_font_faces = Vec::new();
+    split_at_rules(
+        css_string,
+        (0, css_string.len()),
+        &[],
+        &mut segments,
+        &mut unparsed_keyframes,
+        &mut unparsed_font_faces,
+    )?;
+
+    let mut css_blocks = Vec::new();
+    for segment in segments {
+        let mut tokenizer = Tokenizer::new(&css_string[segment.start..segment.end]);
+        css_blocks.append(&mut parse_css_blocks(
+            css_string,
+            segment.start,
+            &segment.media_queries,
+            &mut tokenizer,
+        )?);
+    }
+
+    let (mut stylesheet, mut warnings) = unparsed_css_blocks_to_stylesheet(css_blocks, css_string)?;
+
+    let css_key_map = azul_css::get_css_key_map();
+    stylesheet.keyframes = unparsed_keyframes
+        .into_iter()
+        .map(|keyframes| parse_keyframes(css_string, keyframes, &css_key_map, &mut warnings))
+        .collect::<Result<Vec<_>, _>>()?;
+    stylesheet.font_faces = unparsed_font_faces
+        .into_iter()
+        .map(|body| parse_font_face(css_string, body, &mut warnings))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((stylesheet, warnings))
+}
+
+/// Tokenizes a part of the CSS string (starting at `offset`) into unparsed rule blocks,
+/// the `media_queries` are the queries of the `@media` rules the part is nested in
+fn parse_css_blocks<'a>(
+    css_string: &'a str,
+    offset: usize,
+    media_queries: &[CssMediaQuery],
+    tokenizer: &mut Tokenizer<'a>,
+) -> Result<Vec<UnparsedCssRuleBlock<'a>>, CssParseError<'a>> {
     use azul_simplecss::{Combinator, Token};
 
     let mut css_blocks = Vec::new();
@@ -463,16 +790,21 @@ fn new_from_str_inner<'a>(
     let mut current_paths = Vec::new();
     // Current CSS declarations
     let mut current_rules = HashMap::<&str, (&str, (ErrorLocation, ErrorLocation))>::new();
+    // Current CSS declarations marked with `!important`
+    let mut current_important_rules =
+        HashMap::<&str, (&str, (ErrorLocation, ErrorLocation))>::new();
     // Keep track of the current path during parsing
     let mut last_path = Vec::new();
 
-    let mut last_error_location = ErrorLocation { original_pos: 0 };
+    let mut last_error_location = ErrorLocation {
+        original_pos: offset,
+    };
 
     loop {
         let token = tokenizer.parse_next().map_err(|e| CssParseError {
             css_string,
             error: e.into(),
-            location: (last_error_location, get_error_location(tokenizer)),
+            location: (last_error_location, get_error_location(tokenizer, offset)),
         })?;
 
         macro_rules! check_parser_is_outside_block {
@@ -481,7 +813,7 @@ fn new_from_str_inner<'a>(
                     return Err(CssParseError {
                         css_string,
                         error: CssParseErrorInner::MalformedCss,
-                        location: (last_error_location, get_error_location(tokenizer)),
+                        location: (last_error_location, get_error_location(tokenizer, offset)),
                     });
                 }
             };
@@ -493,7 +825,7 @@ fn new_from_str_inner<'a>(
                     return Err(CssParseError {
                         css_string,
                         error: CssParseErrorInner::MalformedCss,
-                        location: (last_error_location, get_error_location(tokenizer)),
+                        location: (last_error_location, get_error_location(tokenizer, offset)),
                     });
                 }
             };
@@ -517,12 +849,31 @@ fn new_from_str_inner<'a>(
                 check_parser_is_inside_block!();
                 parser_in_block = false;
 
-                css_blocks.extend(current_paths.drain(..).map(|path| UnparsedCssRuleBlock {
-                    path: CssPath { selectors: path },
-                    declarations: current_rules.clone(),
-                }));
+                // `!important` declarations go into a separate block, since
+                // they take precedence over all other declarations in the cascade
+                for path in current_paths.drain(..) {
+                    if !current_rules.is_empty() || current_important_rules.is_empty() {
+                        css_blocks.push(UnparsedCssRuleBlock {
+                            path: CssPath {
+                                selectors: path.clone(),
+                            },
+                            declarations: current_rules.clone(),
+                            media_queries: media_queries.to_vec(),
+                            important: false,
+                        });
+                    }
+                    if !current_important_rules.is_empty() {
+                        css_blocks.push(UnparsedCssRuleBlock {
+                            path: CssPath { selectors: path },
+                            declarations: current_important_rules.clone(),
+                            media_queries: media_queries.to_vec(),
+                            important: true,
+                        });
+                    }
+                }
 
                 current_rules.clear();
+                current_important_rules.clear();
                 last_path.clear(); // technically unnecessary, but just to be sure
             }
 
@@ -537,7 +888,7 @@ fn new_from_str_inner<'a>(
                     NodeTypePath::from_str(div_type).map_err(|e| CssParseError {
                         css_string,
                         error: e.into(),
-                        location: (last_error_location, get_error_location(tokenizer)),
+                        location: (last_error_location, get_error_location(tokenizer, offset)),
                     })?,
                 ));
             }
@@ -557,22 +908,41 @@ fn new_from_str_inner<'a>(
                 check_parser_is_outside_block!();
                 last_path.push(CssPathSelector::Children);
             }
-            Token::PseudoClass { selector, value } => {
+            Token::Combinator(Combinator::Plus) => {
+                check_parser_is_outside_block!();
+                last_path.push(CssPathSelector::AdjacentSibling);
+            }
+            Token::Combinator(Combinator::Tilde) => {
+                check_parser_is_outside_block!();
+                last_path.push(CssPathSelector::GeneralSibling);
+            }
+            Token::AttributeSelector(attribute) => {
                 check_parser_is_outside_block!();
-                last_path.push(CssPathSelector::PseudoSelector(
-                    pseudo_selector_from_str(selector, value).map_err(|e| CssParseError {
+                last_path.push(CssPathSelector::Attribute(
+                    parse_attribute_selector(attribute).ok_or(CssParseError {
                         css_string,
-                        error: e.into(),
-                        location: (last_error_location, get_error_location(tokenizer)),
+                        error: CssParseErrorInner::InvalidAttributeSelector(attribute),
+                        location: (last_error_location, get_error_location(tokenizer, offset)),
                     })?,
                 ));
             }
+            Token::PseudoClass { selector, value } => {
+                check_parser_is_outside_block!();
+                last_path.push(css_path_selector_from_pseudo(selector, value).map_err(|e| {
+                    CssParseError {
+                        css_string,
+                        error: e.into(),
+                        location: (last_error_location, get_error_location(tokenizer, offset)),
+                    }
+                })?);
+            }
             Token::Declaration(key, val) => {
                 check_parser_is_inside_block!();
-                current_rules.insert(
-                    key,
-                    (val, (last_error_location, get_error_location(tokenizer))),
-                );
+                let location = (last_error_location, get_error_location(tokenizer, offset));
+                match strip_important(val) {
+                    Some(val) => current_important_rules.insert(key, (val, location)),
+                    None => current_rules.insert(key, (val, location)),
+                };
             }
             Token::EndOfStream => {
                 // uneven number of open / close braces
@@ -580,21 +950,733 @@ fn new_from_str_inner<'a>(
                     return Err(CssParseError {
                         css_string,
                         error: CssParseErrorInner::UnclosedBlock,
-                        location: (last_error_location, get_error_location(tokenizer)),
+                        location: (last_error_location, get_error_location(tokenizer, offset)),
                     });
                 }
 
                 break;
             }
             _ => {
-                // attributes, lang-attributes and @keyframes are not supported
+                // lang-attributes are not supported
+            }
+        }
+
+        last_error_location = get_error_location(tokenizer, offset);
+    }
+
+    Ok(css_blocks)
+}
+
+/// Strips the `!important` from the end of a CSS value, i.e. `"red !important"` -> `Some("red")`,
+/// returns `None` if the value isn't important
+fn strip_important(value: &str) -> Option<&str> {
+    let value = value.trim_end();
+    let (value, important) = value.split_at(value.rfind('!')?);
+    if important[1..].trim().eq_ignore_ascii_case("important") {
+        Some(value.trim_end())
+    } else {
+        None
+    }
+}
+
+/// `@keyframes` rule that was cut out of the CSS string, but not parsed yet
+#[derive(Debug, Clone, PartialEq)]
+struct UnparsedKeyframes<'a> {
+    name: &'a str,
+    /// Start and end of the contents between the outer braces
+    body: (usize, usize),
+}
+
+/// Part of the CSS string that only contains rule blocks
+#[derive(Debug, Clone, PartialEq)]
+struct CssSegment {
+    start: usize,
+    end: usize,
+    /// The queries of the `@media` rules the segment is nested in
+    media_queries: Vec<CssMediaQuery>,
+}
+
+/// Cuts all `@keyframes`, `@media` and `@font-face` rules out of the (`start`, `end`) part of
+/// the CSS string. The remaining parts are pushed to the `segments`, the contents of `@media`
+/// rules are split recursively, so the segments are in the same order as in the CSS string.
+/// For `@font-face` rules, only the (start, end) of the contents between the braces is stored.
+fn split_at_rules<'a>(
+    css_string: &'a str,
+    (start, end): (usize, usize),
+    media_queries: &[CssMediaQuery],
+    segments: &mut Vec<CssSegment>,
+    keyframes: &mut Vec<UnparsedKeyframes<'a>>,
+    font_faces: &mut Vec<(usize, usize)>,
+) -> Result<(), CssParseError<'a>> {
+    const KEYFRAMES: &str = "@keyframes";
+    const MEDIA: &str = "@media";
+    const FONT_FACE: &str = "@font-face";
+
+    // Nested rules can't be closed outside of the parent rule
+    let css = &css_string[..end];
+    let mut segment_start = start;
+    let mut block_nesting = 0_usize;
+
+    for (pos, c) in CssCharIndices::new(css, start, end) {
+        if pos < segment_start {
+            continue; // still inside of the last at-rule
+        }
+
+        let at_rule = match c {
+            b'{' => {
+                block_nesting += 1;
+                continue;
+            }
+            b'}' => {
+                block_nesting = block_nesting.saturating_sub(1);
+                continue;
+            }
+            b'@' if block_nesting == 0 && css[pos..].starts_with(KEYFRAMES) => KEYFRAMES,
+            b'@' if block_nesting == 0 && css[pos..].starts_with(MEDIA) => MEDIA,
+            b'@' if block_nesting == 0 && css[pos..].starts_with(FONT_FACE) => FONT_FACE,
+            _ => continue,
+        };
+
+        let prelude_start = pos + at_rule.len();
+        let error_location = |end| {
+            (
+                ErrorLocation { original_pos: pos },
+                ErrorLocation { original_pos: end },
+            )
+        };
+
+        let body_start =
+            find_outside_comments(css, prelude_start, b'{').ok_or_else(|| CssParseError {
+                css_string,
+                error: CssParseErrorInner::MalformedCss,
+                location: error_location(end),
+            })? + 1;
+
+        let body_end = find_closing_brace(css, body_start).ok_or_else(|| CssParseError {
+            css_string,
+            error: CssParseErrorInner::UnclosedBlock,
+            location: error_location(end),
+        })?;
+
+        let prelude = trim_css_comments(&css[prelude_start..body_start - 1]);
+
+        segments.push(CssSegment {
+            start: segment_start,
+            end: pos,
+            media_queries: media_queries.to_vec(),
+        });
+
+        if at_rule == KEYFRAMES {
+            if prelude.is_empty() {
+                return Err(CssParseError {
+                    css_string,
+                    error: CssKeyframesParseError::MissingName.into(),
+                    location: error_location(body_start),
+                });
+            }
+
+            keyframes.push(UnparsedKeyframes {
+                name: prelude,
+                body: (body_start, body_end),
+            });
+        } else if at_rule == FONT_FACE {
+            font_faces.push((body_start, body_end));
+        } else {
+            let nested_queries = parse_media_query_list(prelude).map_err(|e| CssParseError {
+                css_string,
+                error: e.into(),
+                location: error_location(body_start),
+            })?;
+
+            split_at_rules(
+                css_string,
+                (body_start, body_end),
+                &combine_media_queries(media_queries, &nested_queries),
+                segments,
+                keyframes,
+                font_faces,
+            )?;
+        }
+
+        segment_start = body_end + 1;
+    }
+
+    segments.push(CssSegment {
+        start: segment_start,
+        end,
+        media_queries: media_queries.to_vec(),
+    });
+
+    Ok(())
+}
+
+/// Combines the queries of nested `@media` rules: the inner rule only applies
+/// if one of the `outer` queries and one of the `inner` queries match
+fn combine_media_queries(outer: &[CssMediaQuery], inner: &[CssMediaQuery]) -> Vec<CssMediaQuery> {
+    if outer.is_empty() {
+        return inner.to_vec();
+    }
+
+    outer
+        .iter()
+        .flat_map(|outer| {
+            inner.iter().map(move |inner| CssMediaQuery {
+                features: outer
+                    .features
+                    .iter()
+                    .chain(inner.features.iter())
+                    .cloned()
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Parses the comma-separated queries of a `@media` rule, i.e.
+/// `screen and (min-width: 600px), (orientation: portrait)`
+fn parse_media_query_list<'a>(
+    input: &'a str,
+) -> Result<Vec<CssMediaQuery>, CssMediaQueryParseError<'a>> {
+    // `@media { ... }` always applies
+    if input.trim().is_empty() {
+        return Ok(vec![CssMediaQuery::default()]);
+    }
+
+    input.split(',').map(parse_media_query).collect()
+}
+
+/// Parses one `@media` query, i.e. `only screen and (min-width: 600px) and (max-width: 800px)`
+fn parse_media_query<'a>(input: &'a str) -> Result<CssMediaQuery, CssMediaQueryParseError<'a>> {
+    use self::CssMediaQueryParseError::*;
+
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(EmptyQuery);
+    }
+
+    let mut features = Vec::new();
+
+    // optional media type, has to be followed by "and" if there are features
+    if !rest.starts_with('(') {
+        rest = rest.strip_prefix("only ").unwrap_or(rest).trim_start();
+        let type_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        match &rest[..type_end] {
+            "screen" | "all" => {}
+            other => return Err(UnsupportedMediaType(other)),
+        }
+        rest = rest[type_end..].trim_start();
+        if rest.is_empty() {
+            return Ok(CssMediaQuery { features });
+        }
+        rest = strip_media_and(rest).ok_or(MalformedQuery(input.trim()))?;
+    }
+
+    loop {
+        let feature_end = match (rest.starts_with('('), rest.find(')')) {
+            (true, Some(end)) => end,
+            _ => return Err(MalformedQuery(input.trim())),
+        };
+        features.push(parse_media_feature(&rest[1..feature_end])?);
+        rest = rest[(feature_end + 1)..].trim_start();
+        if rest.is_empty() {
+            return Ok(CssMediaQuery { features });
+        }
+        rest = strip_media_and(rest).ok_or(MalformedQuery(input.trim()))?;
+    }
+}
+
+/// Strips the "and" between two parts of a media query
+fn strip_media_and(input: &str) -> Option<&str> {
+    let rest = input.strip_prefix("and")?;
+    if rest.starts_with(char::is_whitespace) || rest.starts_with('(') {
+        Some(rest.trim_start())
+    } else {
+        None
+    }
+}
+
+/// Parses the contents of a media feature, i.e. `min-width: 600px`
+fn parse_media_feature<'a>(input: &'a str) -> Result<CssMediaFeature, CssMediaQueryParseError<'a>> {
+    use self::CssMediaQueryParseError::*;
+    use azul_css::CssMediaFeature::*;
+
+    let mut name_value = input.splitn(2, ':');
+    let (feature, value) = match (name_value.next(), name_value.next()) {
+        (Some(feature), Some(value)) => (feature.trim(), value.trim()),
+        _ => return Err(MalformedQuery(input.trim())),
+    };
+
+    let invalid_value = || InvalidValue { feature, value };
+    let length = || css_parser::parse_pixel_value_no_percent(value).map_err(|_| invalid_value());
+    let resolution = || parse_media_resolution(value).ok_or_else(invalid_value);
+
+    match feature {
+        "min-width" => Ok(MinWidth(length()?)),
+        "max-width" => Ok(MaxWidth(length()?)),
+        "min-height" => Ok(MinHeight(length()?)),
+        "max-height" => Ok(MaxHeight(length()?)),
+        "orientation" => match value {
+            "portrait" => Ok(Orientation(CssMediaOrientation::Portrait)),
+            "landscape" => Ok(Orientation(CssMediaOrientation::Landscape)),
+            _ => Err(invalid_value()),
+        },
+        "min-resolution" => Ok(MinResolution(resolution()?)),
+        "max-resolution" => Ok(MaxResolution(resolution()?)),
+        "resolution" => Ok(Resolution(resolution()?)),
+        "prefers-color-scheme" => match value {
+            "light" => Ok(PrefersColorScheme(CssColorScheme::Light)),
+            "dark" => Ok(PrefersColorScheme(CssColorScheme::Dark)),
+            _ => Err(invalid_value()),
+        },
+        _ => Err(UnknownFeature(feature)),
+    }
+}
+
+/// Parses a resolution (`2dppx`, `2x`, `192dpi` or `75.6dpcm`) into dots per logical pixel,
+/// i.e. the hidpi factor - a logical pixel is `1/96` of an inch
+fn parse_media_resolution(input: &str) -> Option<FloatValue> {
+    const UNITS: [(&str, f32); 4] = [
+        ("dppx", 1.0),
+        ("dpcm", 2.54 / 96.0),
+        ("dpi", 1.0 / 96.0),
+        ("x", 1.0),
+    ];
+
+    UNITS.iter().find_map(|(unit, factor)| {
+        let number = input.strip_suffix(unit)?.trim().parse::<f32>().ok()?;
+        Some(FloatValue::new(number * factor))
+    })
+}
+
+/// Parses the contents of a `@keyframes` rule, i.e. `from { width: 0px; } 50%, to { width: 10px; }`
+fn parse_keyframes<'a>(
+    css_string: &'a str,
+    unparsed_keyframes: UnparsedKeyframes<'a>,
+    css_key_map: &CssKeyMap,
+    warnings: &mut Vec<CssParseWarnMsg<'a>>,
+) -> Result<CssKeyframes, CssParseError<'a>> {
+    let (mut pos, body_end) = unparsed_keyframes.body;
+    let body = &css_string[..body_end];
+    let mut keyframes = Vec::<CssKeyframe>::new();
+
+    while !trim_css_comments(&body[pos..]).is_empty() {
+        let block_start = find_outside_comments(body, pos, b'{');
+        let block_end = block_start.and_then(|start| find_outside_comments(body, start, b'}'));
+        let (block_start, block_end) = match (block_start, block_end) {
+            (Some(start), Some(end)) => (start, end),
+            (block_start, _) => {
+                return Err(CssParseError {
+                    css_string,
+                    error: match block_start {
+                        Some(_) => CssParseErrorInner::UnclosedBlock,
+                        None => CssParseErrorInner::MalformedCss,
+                    },
+                    location: (
+                        ErrorLocation { original_pos: pos },
+                        ErrorLocation {
+                            original_pos: body_end,
+                        },
+                    ),
+                });
+            }
+        };
+
+        let location = (
+            ErrorLocation { original_pos: pos },
+            ErrorLocation {
+                original_pos: block_end + 1,
+            },
+        );
+
+        let offsets = trim_css_comments(&body[pos..block_start])
+            .split(',')
+            .map(|selector| parse_keyframe_offset(selector.trim()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| CssParseError {
+                css_string,
+                error: e.into(),
+                location,
+            })?;
+
+        let mut declarations = Vec::new();
+        for declaration in split_declarations(&body[(block_start + 1)..block_end]) {
+            let declaration = trim_css_comments(declaration);
+            if declaration.is_empty() {
+                continue;
+            }
+
+            let mut key_value = declaration.splitn(2, ':');
+            let (key, value) = match (key_value.next(), key_value.next()) {
+                (Some(key), Some(value)) => (key.trim(), value.trim()),
+                _ => {
+                    return Err(CssParseError {
+                        css_string,
+                        error: CssParseErrorInner::MalformedCss,
+                        location,
+                    })
+                }
+            };
+
+            // `!important` declarations are ignored inside of keyframes
+            if strip_important(value).is_some() {
+                continue;
+            }
+
+            parse_css_declaration(
+                key,
+                value,
+                location,
+                css_key_map,
+                warnings,
+                &mut declarations,
+            )
+            .map_err(|e| CssParseError {
+                css_string,
+                error: e,
+                location,
+            })?;
+        }
+
+        // var() can't be changed inside of an animation, so it always resolves to the default
+        let properties = declarations
+            .into_iter()
+            .map(|declaration| match declaration {
+                CssDeclaration::Static(property) => property,
+                CssDeclaration::Dynamic(dynamic) => dynamic.default_value,
+            })
+            .collect::<Vec<_>>();
+
+        for offset in offsets {
+            match keyframes.iter_mut().find(|keyframe| keyframe.offset == offset) {
+                // keyframes with the same offset are merged, later properties win
+                Some(keyframe) => {
+                    keyframe.properties.retain(|old| {
+                        !properties
+                            .iter()
+                            .any(|new| new.get_type() == old.get_type())
+                    });
+                    keyframe.properties.extend(properties.iter().cloned());
+                }
+                None => keyframes.push(CssKeyframe {
+                    offset,
+                    properties: properties.clone(),
+                }),
             }
         }
 
-        last_error_location = get_error_location(tokenizer);
+        pos = block_end + 1;
     }
 
-    unparsed_css_blocks_to_stylesheet(css_blocks, css_string)
+    keyframes.sort_by(|a, b| a.offset.cmp(&b.offset));
+
+    Ok(CssKeyframes {
+        name: unparsed_keyframes.name.to_string(),
+        keyframes,
+    })
+}
+
+/// Parses the descriptors of a `@font-face` rule, the `body` is the (start, end) of the
+/// contents between the braces. Unknown descriptors (i.e. `unicode-range`) are ignored.
+fn parse_font_face<'a>(
+    css_string: &'a str,
+    (body_start, body_end): (usize, usize),
+    warnings: &mut Vec<CssParseWarnMsg<'a>>,
+) -> Result<CssFontFace, CssParseError<'a>> {
+    use self::CssFontFaceParseError::*;
+
+    let location = (
+        ErrorLocation {
+            original_pos: body_start,
+        },
+        ErrorLocation {
+            original_pos: body_end,
+        },
+    );
+    let error = |e: CssParseErrorInner<'a>| CssParseError {
+        css_string,
+        error: e,
+        location,
+    };
+
+    let mut family = None;
+    let mut sources = None;
+    let mut weight = StyleFontWeight::default();
+    let mut style = StyleFontStyle::default();
+    let mut stretch = StyleFontStretch::default();
+
+    for declaration in split_declarations(&css_string[body_start..body_end]) {
+        let declaration = trim_css_comments(declaration);
+        if declaration.is_empty() {
+            continue;
+        }
+
+        let mut key_value = declaration.splitn(2, ':');
+        let (key, value) = match (key_value.next(), key_value.next()) {
+            (Some(key), Some(value)) => (key.trim(), value.trim()),
+            _ => return Err(error(CssParseErrorInner::MalformedCss)),
+        };
+
+        match key {
+            "font-family" => {
+                family = css_parser::parse_style_font_family(value)
+                    .ok()
+                    .and_then(|f| f.fonts.into_iter().next())
+                    .map(|font_id| font_id.0)
+                    .filter(|family| !family.is_empty());
+            }
+            "src" => {
+                sources = Some(
+                    split_font_face_sources(value)
+                        .into_iter()
+                        .map(parse_font_face_source)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| error(e.into()))?,
+                );
+            }
+            "font-weight" => {
+                weight = css_parser::parse_style_font_weight(value)
+                    .map_err(|_| error(InvalidFontWeight(value).into()))?;
+            }
+            "font-style" => {
+                // `oblique 10deg` - the angle is ignored
+                style = value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|s| css_parser::parse_style_font_style(s).ok())
+                    .ok_or(error(InvalidFontStyle(value).into()))?;
+            }
+            "font-stretch" => {
+                stretch = css_parser::parse_style_font_stretch(value)
+                    .map_err(|_| error(InvalidFontStretch(value).into()))?;
+            }
+            _ => warnings.push(CssParseWarnMsg {
+                warning: CssParseWarnMsgInner::UnsupportedKeyValuePair { key, value },
+                location,
+            }),
+        }
+    }
+
+    Ok(CssFontFace {
+        family: family.ok_or(error(MissingFontFamily.into()))?,
+        sources: sources
+            .filter(|s| !s.is_empty())
+            .ok_or(error(MissingSource.into()))?,
+        weight,
+        style,
+        stretch,
+    })
+}
+
+/// Splits the value of the `src` descriptor at every `,` that isn't inside of parentheses
+/// or quotes, i.e. `url("a,b.ttf"), local(Arial)`
+fn split_font_face_sources(input: &str) -> Vec<&str> {
+    let mut sources = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+    let mut last_split = 0;
+
+    for (pos, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                sources.push(input[last_split..pos].trim());
+                last_split = pos + 1;
+            }
+            _ => {}
+        }
+    }
+
+    sources.push(input[last_split..].trim());
+    sources.retain(|source| !source.is_empty());
+    sources
+}
+
+/// Parses one font source, i.e. `url("fonts/Roboto.ttf") format("truetype")` or
+/// `local(Roboto)` - the `format()` hint is ignored
+fn parse_font_face_source<'a>(
+    input: &'a str,
+) -> Result<CssFontFaceSource, CssFontFaceParseError<'a>> {
+    let invalid = || CssFontFaceParseError::InvalidSource(input);
+
+    let open_brace = input.find('(').ok_or_else(invalid)?;
+    let function = &input[..open_brace];
+
+    // Closing brace of the function, braces inside of quotes don't count
+    let mut quote = None;
+    let close_brace = input[open_brace..]
+        .char_indices()
+        .find(|(_, c)| match (quote, *c) {
+            (Some(q), c) if c == q => {
+                quote = None;
+                false
+            }
+            (Some(_), _) => false,
+            (None, '"') | (None, '\'') => {
+                quote = Some(*c);
+                false
+            }
+            (None, c) => c == ')',
+        })
+        .map(|(pos, _)| open_brace + pos)
+        .ok_or_else(invalid)?;
+
+    let remainder = input[(close_brace + 1)..].trim();
+    if !remainder.is_empty() && !remainder.starts_with("format(") {
+        return Err(invalid());
+    }
+
+    let contents = input[(open_brace + 1)..close_brace].trim();
+    let contents = if contents.len() >= 2
+        && ((contents.starts_with('"') && contents.ends_with('"'))
+            || (contents.starts_with('\'') && contents.ends_with('\'')))
+    {
+        &contents[1..(contents.len() - 1)]
+    } else {
+        contents
+    };
+
+    if contents.is_empty() {
+        return Err(invalid());
+    }
+
+    match function {
+        "url" => Ok(CssFontFaceSource::Url(contents.to_string())),
+        "local" => Ok(CssFontFaceSource::Local(contents.to_string())),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a keyframe selector: `from`, `to` or a percentage between `0%` and `100%`
+fn parse_keyframe_offset<'a>(
+    selector: &'a str,
+) -> Result<PercentageValue, CssKeyframesParseError<'a>> {
+    match selector {
+        "from" => Ok(PercentageValue::const_new(0)),
+        "to" => Ok(PercentageValue::const_new(100)),
+        other => other
+            .strip_suffix('%')
+            .and_then(|number| number.trim().parse::<f32>().ok())
+            .filter(|number| *number >= 0.0 && *number <= 100.0)
+            .map(PercentageValue::new)
+            .ok_or(CssKeyframesParseError::InvalidKeyframeSelector(selector)),
+    }
+}
+
+/// Iterates over the bytes of a CSS string, skipping comments and quoted strings
+/// (so that braces inside of comments or strings are ignored)
+struct CssCharIndices<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> CssCharIndices<'a> {
+    fn new(css_string: &'a str, start: usize, end: usize) -> Self {
+        Self {
+            bytes: css_string.as_bytes(),
+            pos: start,
+            end,
+        }
+    }
+}
+
+impl<'a> Iterator for CssCharIndices<'a> {
+    type Item = (usize, u8);
+
+    fn next(&mut self) -> Option<(usize, u8)> {
+        while self.pos < self.end {
+            let c = self.bytes[self.pos];
+            let next = self.bytes.get(self.pos + 1).copied();
+
+            if c == b'/' && next == Some(b'*') {
+                // skip until (and including) the closing "*/"
+                self.pos = (self.pos + 2..self.end)
+                    .find(|i| self.bytes[*i] == b'*' && self.bytes.get(*i + 1) == Some(&b'/'))
+                    .map(|i| i + 2)
+                    .unwrap_or(self.end);
+            } else if c == b'"' || c == b'\'' {
+                // skip until (and including) the closing quote, ignoring escaped quotes
+                let mut i = self.pos + 1;
+                while i < self.end && self.bytes[i] != c {
+                    i += if self.bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                self.pos = i + 1;
+            } else {
+                self.pos += 1;
+                return Some((self.pos - 1, c));
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the position of the first `target` after `start` that isn't inside of a comment
+fn find_outside_comments(css_string: &str, start: usize, target: u8) -> Option<usize> {
+    CssCharIndices::new(css_string, start, css_string.len())
+        .find(|(_, c)| *c == target)
+        .map(|(pos, _)| pos)
+}
+
+/// Returns the position of the brace that closes the block starting at `start`
+fn find_closing_brace(css_string: &str, start: usize) -> Option<usize> {
+    let mut block_nesting = 1_usize;
+    CssCharIndices::new(css_string, start, css_string.len())
+        .find(|(_, c)| {
+            match c {
+                b'{' => block_nesting += 1,
+                b'}' => block_nesting -= 1,
+                _ => {}
+            }
+            block_nesting == 0
+        })
+        .map(|(pos, _)| pos)
+}
+
+/// Splits the contents of a declaration block at every `;` that isn't inside of parentheses
+fn split_declarations(input: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let mut depth = 0_usize;
+    let mut last_split = 0;
+
+    for (pos, c) in CssCharIndices::new(input, 0, input.len()) {
+        match c {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => {
+                declarations.push(&input[last_split..pos]);
+                last_split = pos + 1;
+            }
+            _ => {}
+        }
+    }
+
+    declarations.push(&input[last_split..]);
+    declarations
+}
+
+/// Trims whitespace and leading / trailing comments
+fn trim_css_comments(input: &str) -> &str {
+    let mut input = input.trim();
+    loop {
+        if input.starts_with("/*") {
+            input = match input[2..].find("*/") {
+                Some(end) => input[(end + 4)..].trim(),
+                None => "",
+            };
+        } else if input.ends_with("*/") {
+            input = match input[..(input.len() - 2)].rfind("/*") {
+                Some(start) => input[..start].trim(),
+                None => return input,
+            };
+        } else {
+            return input;
+        }
+    }
 }
 
 fn unparsed_css_blocks_to_stylesheet<'a>(
@@ -632,6 +1714,8 @@ fn unparsed_css_blocks_to_stylesheet<'a>(
             Ok(CssRuleBlock {
                 path: unparsed_css_block.path,
                 declarations,
+                media_queries: unparsed_css_block.media_queries,
+                important: unparsed_css_block.important,
             })
         })
         .collect::<Result<Vec<CssRuleBlock>, CssParseError>>()?;
@@ -771,6 +1855,8 @@ fn test_css_parse_1() {
                 a: 255,
             })),
         ))],
+        media_queries: Vec::new(),
+        important: false,
     }];
 
     assert_eq!(
@@ -802,7 +1888,11 @@ fn test_css_simple_selector_parse() {
                 rules: vec![CssRuleBlock {
                     path: CssPath { selectors: parsed },
                     declarations: Vec::new(),
+                    media_queries: Vec::new(),
+                    important: false,
                 }],
+                keyframes: Vec::new(),
+                font_faces: Vec::new(),
             }],
         }
     );
@@ -860,6 +1950,8 @@ mod stylesheet_parse {
                     selectors: vec![CssPathSelector::Class("my_class".into())],
                 },
                 declarations: vec![CssDeclaration::Static(red.clone())],
+                media_queries: Vec::new(),
+                important: false,
             }];
             test_css(css_1, expected_rules);
         }
@@ -873,12 +1965,16 @@ mod stylesheet_parse {
                         selectors: vec![CssPathSelector::Id("my_id".into())],
                     },
                     declarations: vec![CssDeclaration::Static(red.clone())],
+                    media_queries: Vec::new(),
+                    important: false,
                 },
                 CssRuleBlock {
                     path: CssPath {
                         selectors: vec![CssPathSelector::Class("my_class".into())],
                     },
                     declarations: vec![CssDeclaration::Static(blue.clone())],
+                    media_queries: Vec::new(),
+                    important: false,
                 },
             ];
             test_css(css_2, expected_rules);
@@ -893,6 +1989,8 @@ mod stylesheet_parse {
                         selectors: vec![CssPathSelector::Global],
                     },
                     declarations: vec![CssDeclaration::Static(black.clone())],
+                    media_queries: Vec::new(),
+                    important: false,
                 },
                 CssRuleBlock {
                     path: CssPath {
@@ -902,12 +2000,16 @@ mod stylesheet_parse {
                         ],
                     },
                     declarations: vec![CssDeclaration::Static(red.clone())],
+                    media_queries: Vec::new(),
+                    important: false,
                 },
                 CssRuleBlock {
                     path: CssPath {
                         selectors: vec![CssPathSelector::Class("my_class".into())],
                     },
                     declarations: vec![CssDeclaration::Static(blue.clone())],
+                    media_queries: Vec::new(),
+                    important: false,
                 },
             ];
             test_css(css_3, expected_rules);
@@ -939,6 +2041,8 @@ fn test_multiple_rules() {
                 selectors: vec![Global],
             },
             declarations: Vec::new(),
+            media_queries: Vec::new(),
+            important: false,
         },
         CssRuleBlock {
             path: CssPath {
@@ -950,18 +2054,24 @@ fn test_multiple_rules() {
                 ],
             },
             declarations: Vec::new(),
+            media_queries: Vec::new(),
+            important: false,
         },
         CssRuleBlock {
             path: CssPath {
                 selectors: vec![Global, Type(NodeTypePath::Div), Id("my_id".into())],
             },
             declarations: Vec::new(),
+            media_queries: Vec::new(),
+            important: false,
         },
         CssRuleBlock {
             path: CssPath {
                 selectors: vec![Global, Id("my_id".into())],
             },
             declarations: Vec::new(),
+            media_queries: Vec::new(),
+            important: false,
         },
         CssRuleBlock {
             path: CssPath {
@@ -973,6 +2083,8 @@ fn test_multiple_rules() {
                 ],
             },
             declarations: Vec::new(),
+            media_queries: Vec::new(),
+            important: false,
         },
     ];
 
@@ -1014,6 +2126,8 @@ fn test_case_issue_93() {
             declarations: vec![CssDeclaration::Static(CssProperty::TextColor(
                 CssPropertyValue::Exact(StyleTextColor(color)),
             ))],
+            media_queries: Vec::new(),
+            important: false,
         }
     }
 
@@ -1064,3 +2178,324 @@ fn test_case_issue_93() {
         }
     );
 }
+
+#[test]
+fn test_css_parse_keyframes() {
+    use azul_css::*;
+
+    let parsed_css = new_from_str(
+        "
+        /* @keyframes inside of comments are ignored { */
+        .spinner { animation-name: fade; }
+
+        @keyframes fade {
+            from { width: 0px; background-color: red; }
+            50%, to { width: 100px; }
+            /* keyframes with the same offset are merged */
+            to { width: 200px; }
+        }
+
+        .label { color: black; }
+    ",
+    )
+    .unwrap();
+
+    let keyframe = |offset: isize, properties: Vec<CssProperty>| CssKeyframe {
+        offset: PercentageValue::const_new(offset),
+        properties,
+    };
+
+    assert_eq!(
+        parsed_css,
+        Css {
+            stylesheets: vec![Stylesheet {
+                rules: vec![
+                    CssRuleBlock {
+                        path: CssPath {
+                            selectors: vec![CssPathSelector::Class("spinner".into())],
+                        },
+                        declarations: vec![CssDeclaration::Static(
+                            StyleAnimationName("fade".into()).into()
+                        )],
+                        media_queries: Vec::new(),
+                        important: false,
+                    },
+                    CssRuleBlock {
+                        path: CssPath {
+                            selectors: vec![CssPathSelector::Class("label".into())],
+                        },
+                        declarations: vec![CssDeclaration::Static(
+                            StyleTextColor(ColorU::BLACK).into()
+                        )],
+                        media_queries: Vec::new(),
+                        important: false,
+                    },
+                ],
+                keyframes: vec![CssKeyframes {
+                    name: "fade".into(),
+                    keyframes: vec![
+                        keyframe(
+                            0,
+                            vec![
+                                LayoutWidth::px(0.0).into(),
+                                StyleBackgroundContent::Color(ColorU::RED).into(),
+                            ]
+                        ),
+                        keyframe(50, vec![LayoutWidth::px(100.0).into()]),
+                        keyframe(100, vec![LayoutWidth::px(200.0).into()]),
+                    ],
+                }],
+                font_faces: Vec::new(),
+            }],
+        }
+    );
+
+    assert_eq!(
+        new_from_str("@keyframes { from { width: 0px; } }").map_err(|e| e.error),
+        Err(CssParseErrorInner::KeyframesParseError(
+            CssKeyframesParseError::MissingName
+        ))
+    );
+    assert_eq!(
+        new_from_str("@keyframes fade { 120% { width: 0px; } }").map_err(|e| e.error),
+        Err(CssParseErrorInner::KeyframesParseError(
+            CssKeyframesParseError::InvalidKeyframeSelector("120%")
+        ))
+    );
+    assert_eq!(
+        new_from_str("@keyframes fade { from { width: 0px; }").map_err(|e| e.error),
+        Err(CssParseErrorInner::UnclosedBlock)
+    );
+}
+
+#[test]
+fn test_css_parse_font_face() {
+    use azul_css::*;
+
+    let parsed_css = new_from_str(
+        "
+        @font-face {
+            font-family: \"Open Sans\";
+            src: url(\"fonts/OpenSans-Bold.woff2\") format(\"woff2\"), local('Open Sans Bold');
+            font-weight: bold;
+            font-display: swap;
+        }
+        .label { font-family: \"Open Sans\"; }
+        @media (min-width: 600px) {
+            @font-face {
+                font-family: Mono;
+                src: url(mono.ttf);
+                font-style: oblique 10deg;
+                font-stretch: condensed;
+            }
+        }
+    ",
+    )
+    .unwrap();
+
+    assert_eq!(
+        parsed_css.stylesheets[0].font_faces,
+        vec![
+            CssFontFace {
+                family: "Open Sans".into(),
+                sources: vec![
+                    CssFontFaceSource::Url("fonts/OpenSans-Bold.woff2".into()),
+                    CssFontFaceSource::Local("Open Sans Bold".into()),
+                ],
+                weight: StyleFontWeight::BOLD,
+                style: StyleFontStyle::Normal,
+                stretch: StyleFontStretch::Normal,
+            },
+            CssFontFace {
+                family: "Mono".into(),
+                sources: vec![CssFontFaceSource::Url("mono.ttf".into())],
+                weight: StyleFontWeight::NORMAL,
+                style: StyleFontStyle::Oblique,
+                stretch: StyleFontStretch::Condensed,
+            },
+        ]
+    );
+    assert_eq!(parsed_css.stylesheets[0].rules.len(), 1);
+
+    let font_face_error = |css| {
+        new_from_str(css).map_err(|e| match e.error {
+            CssParseErrorInner::FontFaceParseError(e) => Some(e),
+            _ => None,
+        })
+    };
+
+    assert_eq!(
+        font_face_error("@font-face { src: url(a.ttf); }"),
+        Err(Some(CssFontFaceParseError::MissingFontFamily))
+    );
+    assert_eq!(
+        font_face_error("@font-face { font-family: A; }"),
+        Err(Some(CssFontFaceParseError::MissingSource))
+    );
+    assert_eq!(
+        font_face_error("@font-face { font-family: A; src: a.ttf; }"),
+        Err(Some(CssFontFaceParseError::InvalidSource("a.ttf")))
+    );
+    assert_eq!(
+        font_face_error("@font-face { font-family: A; src: url(a.ttf); font-weight: 1200; }"),
+        Err(Some(CssFontFaceParseError::InvalidFontWeight("1200")))
+    );
+    assert_eq!(
+        font_face_error("@font-face { font-family: A; src: url(a.ttf); font-style: slanted; }"),
+        Err(Some(CssFontFaceParseError::InvalidFontStyle("slanted")))
+    );
+    assert_eq!(
+        font_face_error("@font-face { font-family: A; src: url(a.ttf); font-stretch: wide; }"),
+        Err(Some(CssFontFaceParseError::InvalidFontStretch("wide")))
+    );
+}
+
+#[test]
+fn test_css_parse_media_queries() {
+    use azul_css::*;
+
+    let parsed_css = new_from_str(
+        "
+        .sidebar { width: 100px; }
+
+        @media screen and (min-width: 600px) and (orientation: landscape), (min-resolution: 2x) {
+            .sidebar { width: 200px; }
+
+            @media (prefers-color-scheme: dark) {
+                .sidebar { color: white; }
+            }
+        }
+
+        .footer { color: black; }
+    ",
+    )
+    .unwrap();
+
+    let rule =
+        |class: &str, property: CssProperty, media_queries: Vec<CssMediaQuery>| CssRuleBlock {
+            path: CssPath {
+                selectors: vec![CssPathSelector::Class(class.into())],
+            },
+            declarations: vec![CssDeclaration::Static(property)],
+            media_queries,
+            important: false,
+        };
+
+    let min_width = CssMediaFeature::MinWidth(PixelValueNoPercent(PixelValue::px(600.0)));
+    let landscape = CssMediaFeature::Orientation(CssMediaOrientation::Landscape);
+    let hidpi = CssMediaFeature::MinResolution(FloatValue::new(2.0));
+    let dark = CssMediaFeature::PrefersColorScheme(CssColorScheme::Dark);
+    let query = |features: Vec<CssMediaFeature>| CssMediaQuery { features };
+
+    // rules keep their order in the CSS string, nested queries are combined
+    assert_eq!(
+        parsed_css,
+        Css {
+            stylesheets: vec![Stylesheet {
+                rules: vec![
+                    rule("sidebar", LayoutWidth::px(100.0).into(), Vec::new()),
+                    rule(
+                        "sidebar",
+                        LayoutWidth::px(200.0).into(),
+                        vec![query(vec![min_width, landscape]), query(vec![hidpi])],
+                    ),
+                    rule(
+                        "sidebar",
+                        StyleTextColor(ColorU::WHITE).into(),
+                        vec![
+                            query(vec![min_width, landscape, dark]),
+                            query(vec![hidpi, dark]),
+                        ],
+                    ),
+                    rule("footer", StyleTextColor(ColorU::BLACK).into(), Vec::new()),
+                ],
+                keyframes: Vec::new(),
+                font_faces: Vec::new(),
+            }],
+        }
+    );
+
+    assert_eq!(
+        parse_media_query_list("only screen and (max-height: 10em) and (resolution: 192dpi)"),
+        Ok(vec![query(vec![
+            CssMediaFeature::MaxHeight(PixelValueNoPercent(PixelValue::em(10.0))),
+            CssMediaFeature::Resolution(FloatValue::new(2.0)),
+        ])])
+    );
+    assert_eq!(
+        parse_media_query_list("all"),
+        Ok(vec![CssMediaQuery::default()])
+    );
+    assert_eq!(
+        parse_media_query_list("print"),
+        Err(CssMediaQueryParseError::UnsupportedMediaType("print"))
+    );
+    assert_eq!(
+        parse_media_query_list("screen (min-width: 600px)"),
+        Err(CssMediaQueryParseError::MalformedQuery(
+            "screen (min-width: 600px)"
+        ))
+    );
+    assert_eq!(
+        parse_media_query_list("(hover: hover)"),
+        Err(CssMediaQueryParseError::UnknownFeature("hover"))
+    );
+    assert_eq!(
+        parse_media_query_list("(orientation: diagonal)"),
+        Err(CssMediaQueryParseError::InvalidValue {
+            feature: "orientation",
+            value: "diagonal"
+        })
+    );
+    assert_eq!(
+        new_from_str("@media (min-width: 600px) { .a { width: 0px; }").map_err(|e| e.error),
+        Err(CssParseErrorInner::UnclosedBlock)
+    );
+}
+
+#[test]
+fn test_css_parse_important() {
+    use azul_css::*;
+
+    let parsed_css = new_from_str(
+        "
+        .a, #b { color: red !important; width: 100px; }
+        .a { color: white ! IMPORTANT }
+        @keyframes fade { from { opacity: 0 !important; } to { opacity: 1; } }
+    ",
+    )
+    .unwrap();
+
+    let rule = |selector: CssPathSelector, property: CssProperty, important: bool| CssRuleBlock {
+        path: CssPath {
+            selectors: vec![selector],
+        },
+        declarations: vec![CssDeclaration::Static(property)],
+        media_queries: Vec::new(),
+        important,
+    };
+
+    let a = || CssPathSelector::Class("a".into());
+    let b = || CssPathSelector::Id("b".into());
+
+    // the !important declarations are split into separate blocks
+    assert_eq!(
+        parsed_css.stylesheets[0].rules,
+        vec![
+            rule(a(), LayoutWidth::px(100.0).into(), false),
+            rule(a(), StyleTextColor(ColorU::RED).into(), true),
+            rule(b(), LayoutWidth::px(100.0).into(), false),
+            rule(b(), StyleTextColor(ColorU::RED).into(), true),
+            rule(a(), StyleTextColor(ColorU::WHITE).into(), true),
+        ]
+    );
+
+    // !important is ignored inside of keyframes
+    let keyframes = &parsed_css.stylesheets[0].keyframes[0].keyframes;
+    assert!(keyframes[0].properties.is_empty());
+    assert_eq!(keyframes[1].properties.len(), 1);
+
+    assert_eq!(strip_important("red"), None);
+    assert_eq!(strip_important("red!important"), Some("red"));
+    assert_eq!(strip_important("url(a!b)"), None);
+}
@@ -2,23 +2,36 @@
 
 use azul_css::{
     BackgroundPositionHorizontal, BackgroundPositionVertical, BorderStyle, BoxShadowClipMode,
-    BoxShadowPreDisplayItem, ColorU, CombinedCssPropertyType, CssImageId, CssProperty,
-    CssPropertyType, CssPropertyValue, Direction, DirectionCorner, ExtendMode, FloatValue, FontId,
-    GradientStopPre, GradientType, LayoutAlignContent, LayoutAlignItems, LayoutBottom,
-    LayoutBoxSizing, LayoutDirection, LayoutDisplay, LayoutFlexGrow, LayoutFlexShrink, LayoutFloat,
-    LayoutHeight, LayoutJustifyContent, LayoutLeft, LayoutMarginBottom, LayoutMarginLeft,
-    LayoutMarginRight, LayoutMarginTop, LayoutMaxHeight, LayoutMaxWidth, LayoutMinHeight,
-    LayoutMinWidth, LayoutPaddingBottom, LayoutPaddingLeft, LayoutPaddingRight, LayoutPaddingTop,
-    LayoutPosition, LayoutRight, LayoutTop, LayoutWidth, LayoutWrap, LinearGradient, Overflow,
-    PercentageValue, PixelValue, PixelValueNoPercent, RadialGradient, Shape, SizeMetric,
+    BoxShadowPreDisplayItem, CalcValue, ColorU, CombinedCssPropertyType, CssDuration, CssImageId,
+    CssKeyMap, CssProperty, CssPropertyType, CssPropertyValue, Direction, DirectionCorner,
+    ExtendMode, FloatValue, FontFeature, FontId, FontTag, FontVariation, GradientStopPre,
+    GradientType, GridLine, GridPlacement, GridTemplate, GridTrackBreadth, GridTrackSize,
+    LayoutAlignContent, LayoutAlignItems, LayoutBottom, LayoutBoxSizing, LayoutDirection,
+    LayoutDisplay, LayoutFlexGrow, LayoutFlexShrink, LayoutFloat, LayoutGridColumn,
+    LayoutGridColumnGap, LayoutGridRow, LayoutGridRowGap, LayoutGridTemplateColumns,
+    LayoutGridTemplateRows, LayoutHeight, LayoutJustifyContent, LayoutLeft, LayoutMarginBottom,
+    LayoutMarginLeft, LayoutMarginRight, LayoutMarginTop, LayoutMaxHeight, LayoutMaxWidth,
+    LayoutMinHeight, LayoutMinWidth, LayoutPaddingBottom, LayoutPaddingLeft, LayoutPaddingRight,
+    LayoutPaddingTop, LayoutPosition, LayoutRight, LayoutTop, LayoutWidth, LayoutWrap,
+    LinearGradient, NumericFigure, NumericFraction, NumericSpacing, Overflow, PercentageValue,
+    PixelValue, PixelValueNoPercent, RadialGradient, Shape, SizeMetric, StepPosition,
+    StyleAnimationDelay, StyleAnimationDirection, StyleAnimationDuration, StyleAnimationFillMode,
+    StyleAnimationIterationCount, StyleAnimationName, StyleAnimationTimingFunction,
     StyleBackgroundContent, StyleBackgroundPosition, StyleBackgroundRepeat, StyleBackgroundSize,
     StyleBorderBottomColor, StyleBorderBottomLeftRadius, StyleBorderBottomRightRadius,
     StyleBorderBottomStyle, StyleBorderBottomWidth, StyleBorderLeftColor, StyleBorderLeftStyle,
     StyleBorderLeftWidth, StyleBorderRightColor, StyleBorderRightStyle, StyleBorderRightWidth,
     StyleBorderSide, StyleBorderTopColor, StyleBorderTopLeftRadius, StyleBorderTopRightRadius,
-    StyleBorderTopStyle, StyleBorderTopWidth, StyleCursor, StyleFontFamily, StyleFontSize,
-    StyleLetterSpacing, StyleLineHeight, StyleTabWidth, StyleTextAlignmentHorz, StyleTextColor,
-    StyleWordSpacing,
+    StyleBorderTopStyle, StyleBorderTopWidth, StyleCursor, StyleDropShadow, StyleFilter,
+    StyleFilterFunction, StyleFontFamily, StyleFontFeatureSettings, StyleFontSize,
+    StyleFontStretch, StyleFontStyle, StyleFontVariantNumeric, StyleFontVariationSettings,
+    StyleFontWeight, StyleHyphens, StyleLetterSpacing, StyleLineClamp, StyleLineHeight,
+    StyleOpacity, StyleOverflowWrap, StyleTabWidth, StyleTextAlignmentHorz, StyleTextColor,
+    StyleTextDecorationColor, StyleTextDecorationLine, StyleTextDecorationStyle,
+    StyleTextDecorationThickness, StyleTextDirection, StyleTextOverflow, StyleTextShadow,
+    StyleTimingFunction, StyleTransform, StyleTransformFunction, StyleTransformMatrix2D,
+    StyleTransformOrigin, StyleTransition, StyleTransitionItem, StyleWhiteSpace, StyleWordBreak,
+    StyleWordSpacing, TransitionProperty, MAX_GRID_LINE,
 };
 use std::num::{ParseFloatError, ParseIntError};
 
@@ -123,11 +136,29 @@ pub fn parse_css_property<'a>(
             TextColor => parse_style_text_color(value)?.into(),
             FontSize => parse_style_font_size(value)?.into(),
             FontFamily => parse_style_font_family(value)?.into(),
+            FontWeight => parse_style_font_weight(value)?.into(),
+            FontStyle => parse_style_font_style(value)?.into(),
+            FontStretch => parse_style_font_stretch(value)?.into(),
+            FontFeatureSettings => parse_style_font_feature_settings(value)?.into(),
+            FontVariantNumeric => parse_style_font_variant_numeric(value)?.into(),
+            FontVariationSettings => parse_style_font_variation_settings(value)?.into(),
             TextAlign => parse_layout_text_align(value)?.into(),
+            TextDirection => parse_style_text_direction(value)?.into(),
             LetterSpacing => parse_style_letter_spacing(value)?.into(),
             LineHeight => parse_style_line_height(value)?.into(),
             WordSpacing => parse_style_word_spacing(value)?.into(),
             TabWidth => parse_style_tab_width(value)?.into(),
+            WordBreak => parse_style_word_break(value)?.into(),
+            OverflowWrap => parse_style_overflow_wrap(value)?.into(),
+            Hyphens => parse_style_hyphens(value)?.into(),
+            WhiteSpace => parse_style_white_space(value)?.into(),
+            TextOverflow => parse_style_text_overflow(value)?.into(),
+            LineClamp => parse_style_line_clamp(value)?.into(),
+            TextDecorationLine => parse_style_text_decoration_line(value)?.into(),
+            TextDecorationStyle => parse_style_text_decoration_style(value)?.into(),
+            TextDecorationColor => StyleTextDecorationColor(parse_css_color(value)?).into(),
+            TextDecorationThickness => parse_style_text_decoration_thickness(value)?.into(),
+            TextShadow => parse_style_text_shadow(value)?.into(),
             Cursor => parse_style_cursor(value)?.into(),
 
             Display => parse_layout_display(value)?.into(),
@@ -152,6 +183,13 @@ pub fn parse_css_property<'a>(
             AlignItems => parse_layout_align_items(value)?.into(),
             AlignContent => parse_layout_align_content(value)?.into(),
 
+            GridTemplateColumns => parse_layout_grid_template_columns(value)?.into(),
+            GridTemplateRows => parse_layout_grid_template_rows(value)?.into(),
+            GridColumnGap => parse_layout_grid_column_gap(value)?.into(),
+            GridRowGap => parse_layout_grid_row_gap(value)?.into(),
+            GridColumn => parse_layout_grid_column(value)?.into(),
+            GridRow => parse_layout_grid_row(value)?.into(),
+
             Background => parse_style_background_content(value)?.into(),
             BackgroundImage => StyleBackgroundContent::Image(parse_image(value)?).into(),
             BackgroundColor => StyleBackgroundContent::Color(parse_css_color(value)?).into(),
@@ -214,6 +252,23 @@ pub fn parse_css_property<'a>(
                 parse_style_box_shadow(value)?,
             ))
             .into(),
+
+            Transform => parse_style_transform(value)?.into(),
+            TransformOrigin => parse_style_transform_origin(value)?.into(),
+
+            Opacity => parse_style_opacity(value)?.into(),
+            Filter => parse_style_filter(value)?.into(),
+
+            Transition => parse_style_transition(value)?.into(),
+            AnimationName => parse_style_animation_name(value)?.into(),
+            AnimationDuration => StyleAnimationDuration(parse_css_duration(value)?).into(),
+            AnimationTimingFunction => {
+                StyleAnimationTimingFunction(parse_style_timing_function(value)?).into()
+            }
+            AnimationDelay => StyleAnimationDelay(parse_css_duration(value)?).into(),
+            AnimationIterationCount => parse_style_animation_iteration_count(value)?.into(),
+            AnimationDirection => parse_style_animation_direction(value)?.into(),
+            AnimationFillMode => parse_style_animation_fill_mode(value)?.into(),
         },
     })
 }
@@ -243,67 +298,7 @@ pub fn parse_combined_css_property<'a>(
 ) -> Result<Vec<CssProperty>, CssParsingError<'a>> {
     use self::CombinedCssPropertyType::*;
 
-    let keys = match key {
-        BorderRadius => vec![
-            CssPropertyType::BorderTopLeftRadius,
-            CssPropertyType::BorderTopRightRadius,
-            CssPropertyType::BorderBottomLeftRadius,
-            CssPropertyType::BorderBottomRightRadius,
-        ],
-        Overflow => vec![CssPropertyType::OverflowX, CssPropertyType::OverflowY],
-        Padding => vec![
-            CssPropertyType::PaddingTop,
-            CssPropertyType::PaddingBottom,
-            CssPropertyType::PaddingLeft,
-            CssPropertyType::PaddingRight,
-        ],
-        Margin => vec![
-            CssPropertyType::MarginTop,
-            CssPropertyType::MarginBottom,
-            CssPropertyType::MarginLeft,
-            CssPropertyType::MarginRight,
-        ],
-        Border => vec![
-            CssPropertyType::BorderTopColor,
-            CssPropertyType::BorderRightColor,
-            CssPropertyType::BorderLeftColor,
-            CssPropertyType::BorderBottomColor,
-            CssPropertyType::BorderTopStyle,
-            CssPropertyType::BorderRightStyle,
-            CssPropertyType::BorderLeftStyle,
-            CssPropertyType::BorderBottomStyle,
-            CssPropertyType::BorderTopWidth,
-            CssPropertyType::BorderRightWidth,
-            CssPropertyType::BorderLeftWidth,
-            CssPropertyType::BorderBottomWidth,
-        ],
-        BorderLeft => vec![
-            CssPropertyType::BorderLeftColor,
-            CssPropertyType::BorderLeftStyle,
-            CssPropertyType::BorderLeftWidth,
-        ],
-        BorderRight => vec![
-            CssPropertyType::BorderRightColor,
-            CssPropertyType::BorderRightStyle,
-            CssPropertyType::BorderRightWidth,
-        ],
-        BorderTop => vec![
-            CssPropertyType::BorderTopColor,
-            CssPropertyType::BorderTopStyle,
-            CssPropertyType::BorderTopWidth,
-        ],
-        BorderBottom => vec![
-            CssPropertyType::BorderBottomColor,
-            CssPropertyType::BorderBottomStyle,
-            CssPropertyType::BorderBottomWidth,
-        ],
-        BoxShadow => vec![
-            CssPropertyType::BoxShadowLeft,
-            CssPropertyType::BoxShadowRight,
-            CssPropertyType::BoxShadowTop,
-            CssPropertyType::BoxShadowBottom,
-        ],
-    };
+    let keys = get_combined_css_property_types(key);
 
     match value {
         "auto" => return Ok(keys.into_iter().map(|ty| CssProperty::auto(ty)).collect()),
@@ -424,6 +419,117 @@ pub fn parse_combined_css_property<'a>(
                 CssProperty::BoxShadowBottom(CssPropertyValue::Exact(box_shadow)),
             ])
         }
+        GridGap => {
+            let grid_gap = parse_layout_grid_gap(value)?;
+            Ok(vec![
+                CssProperty::GridRowGap(LayoutGridRowGap(grid_gap.row).into()),
+                CssProperty::GridColumnGap(LayoutGridColumnGap(grid_gap.column).into()),
+            ])
+        }
+        Animation => Ok(parse_style_animation(value)?),
+        TextDecoration => {
+            let text_decoration = parse_style_text_decoration(value)?;
+            Ok(vec![
+                CssProperty::TextDecorationLine(text_decoration.line.into()),
+                match text_decoration.style {
+                    Some(style) => CssProperty::TextDecorationStyle(style.into()),
+                    None => CssProperty::initial(CssPropertyType::TextDecorationStyle),
+                },
+                match text_decoration.color {
+                    Some(color) => {
+                        CssProperty::TextDecorationColor(StyleTextDecorationColor(color).into())
+                    }
+                    None => CssProperty::initial(CssPropertyType::TextDecorationColor),
+                },
+                match text_decoration.thickness {
+                    Some(thickness) => CssProperty::TextDecorationThickness(thickness.into()),
+                    None => CssProperty::initial(CssPropertyType::TextDecorationThickness),
+                },
+            ])
+        }
+    }
+}
+
+/// Returns the properties that a shorthand property expands to
+fn get_combined_css_property_types(key: CombinedCssPropertyType) -> Vec<CssPropertyType> {
+    use self::CombinedCssPropertyType::*;
+
+    match key {
+        BorderRadius => vec![
+            CssPropertyType::BorderTopLeftRadius,
+            CssPropertyType::BorderTopRightRadius,
+            CssPropertyType::BorderBottomLeftRadius,
+            CssPropertyType::BorderBottomRightRadius,
+        ],
+        Overflow => vec![CssPropertyType::OverflowX, CssPropertyType::OverflowY],
+        Padding => vec![
+            CssPropertyType::PaddingTop,
+            CssPropertyType::PaddingBottom,
+            CssPropertyType::PaddingLeft,
+            CssPropertyType::PaddingRight,
+        ],
+        Margin => vec![
+            CssPropertyType::MarginTop,
+            CssPropertyType::MarginBottom,
+            CssPropertyType::MarginLeft,
+            CssPropertyType::MarginRight,
+        ],
+        Border => vec![
+            CssPropertyType::BorderTopColor,
+            CssPropertyType::BorderRightColor,
+            CssPropertyType::BorderLeftColor,
+            CssPropertyType::BorderBottomColor,
+            CssPropertyType::BorderTopStyle,
+            CssPropertyType::BorderRightStyle,
+            CssPropertyType::BorderLeftStyle,
+            CssPropertyType::BorderBottomStyle,
+            CssPropertyType::BorderTopWidth,
+            CssPropertyType::BorderRightWidth,
+            CssPropertyType::BorderLeftWidth,
+            CssPropertyType::BorderBottomWidth,
+        ],
+        BorderLeft => vec![
+            CssPropertyType::BorderLeftColor,
+            CssPropertyType::BorderLeftStyle,
+            CssPropertyType::BorderLeftWidth,
+        ],
+        BorderRight => vec![
+            CssPropertyType::BorderRightColor,
+            CssPropertyType::BorderRightStyle,
+            CssPropertyType::BorderRightWidth,
+        ],
+        BorderTop => vec![
+            CssPropertyType::BorderTopColor,
+            CssPropertyType::BorderTopStyle,
+            CssPropertyType::BorderTopWidth,
+        ],
+        BorderBottom => vec![
+            CssPropertyType::BorderBottomColor,
+            CssPropertyType::BorderBottomStyle,
+            CssPropertyType::BorderBottomWidth,
+        ],
+        BoxShadow => vec![
+            CssPropertyType::BoxShadowLeft,
+            CssPropertyType::BoxShadowRight,
+            CssPropertyType::BoxShadowTop,
+            CssPropertyType::BoxShadowBottom,
+        ],
+        GridGap => vec![CssPropertyType::GridRowGap, CssPropertyType::GridColumnGap],
+        Animation => vec![
+            CssPropertyType::AnimationName,
+            CssPropertyType::AnimationDuration,
+            CssPropertyType::AnimationTimingFunction,
+            CssPropertyType::AnimationDelay,
+            CssPropertyType::AnimationIterationCount,
+            CssPropertyType::AnimationDirection,
+            CssPropertyType::AnimationFillMode,
+        ],
+        TextDecoration => vec![
+            CssPropertyType::TextDecorationLine,
+            CssPropertyType::TextDecorationStyle,
+            CssPropertyType::TextDecorationColor,
+            CssPropertyType::TextDecorationThickness,
+        ],
     }
 }
 
@@ -439,6 +545,7 @@ pub enum CssParsingError<'a> {
     PercentageParseError(PercentageParseError),
     CssImageParseError(CssImageParseError<'a>),
     CssStyleFontFamilyParseError(CssStyleFontFamilyParseError<'a>),
+    FontSettingsParseError(CssFontSettingsParseError<'a>),
     CssBackgroundParseError(CssBackgroundParseError<'a>),
     CssColorParseError(CssColorParseError<'a>),
     CssStyleBorderRadiusParseError(CssStyleBorderRadiusParseError<'a>),
@@ -447,6 +554,10 @@ pub enum CssParsingError<'a> {
     FlexShrinkParseError(FlexShrinkParseError<'a>),
     FlexGrowParseError(FlexGrowParseError<'a>),
     BackgroundPositionParseError(CssBackgroundPositionParseError<'a>),
+    GridParseError(CssGridParseError<'a>),
+    TransformParseError(CssTransformParseError<'a>),
+    FilterParseError(CssFilterParseError<'a>),
+    AnimationParseError(CssAnimationParseError<'a>),
 }
 
 impl_debug_as_display!(CssParsingError<'a>);
@@ -459,6 +570,7 @@ impl_display! { CssParsingError<'a>, {
     PercentageParseError(e) => format!("{}", e),
     CssImageParseError(e) => format!("{}", e),
     CssStyleFontFamilyParseError(e) => format!("{}", e),
+    FontSettingsParseError(e) => format!("{}", e),
     CssBackgroundParseError(e) => format!("{}", e),
     CssColorParseError(e) => format!("{}", e),
     PaddingParseError(e) => format!("{}", e),
@@ -466,6 +578,10 @@ impl_display! { CssParsingError<'a>, {
     FlexShrinkParseError(e) => format!("{}", e),
     FlexGrowParseError(e) => format!("{}", e),
     BackgroundPositionParseError(e) => format!("{}", e),
+    GridParseError(e) => format!("{}", e),
+    TransformParseError(e) => format!("{}", e),
+    FilterParseError(e) => format!("{}", e),
+    AnimationParseError(e) => format!("{}", e),
 }}
 
 impl_from!(
@@ -484,6 +600,10 @@ impl_from!(
     CssStyleFontFamilyParseError<'a>,
     CssParsingError::CssStyleFontFamilyParseError
 );
+impl_from!(
+    CssFontSettingsParseError<'a>,
+    CssParsingError::FontSettingsParseError
+);
 impl_from!(
     CssBackgroundParseError<'a>,
     CssParsingError::CssBackgroundParseError
@@ -509,6 +629,16 @@ impl_from!(
     CssBackgroundPositionParseError<'a>,
     CssParsingError::BackgroundPositionParseError
 );
+impl_from!(CssGridParseError<'a>, CssParsingError::GridParseError);
+impl_from!(
+    CssTransformParseError<'a>,
+    CssParsingError::TransformParseError
+);
+impl_from!(CssFilterParseError<'a>, CssParsingError::FilterParseError);
+impl_from!(
+    CssAnimationParseError<'a>,
+    CssParsingError::AnimationParseError
+);
 
 impl<'a> From<PercentageParseError> for CssParsingError<'a> {
     fn from(e: PercentageParseError) -> Self {
@@ -761,6 +891,7 @@ pub enum PixelParseError<'a> {
     NoValueGiven(&'a str),
     UnsupportedMetric(f32, String, &'a str),
     ValueParseErr(ParseFloatError, String),
+    InvalidCalcExpression(&'a str),
 }
 
 impl_debug_as_display!(PixelParseError<'a>);
@@ -770,18 +901,22 @@ impl_display! { PixelParseError<'a>, {
     NoValueGiven(input) => format!("Expected floating-point pixel value, got: \"{}\"", input),
     UnsupportedMetric(_, metric, input) => format!("Could not parse \"{}\": Metric \"{}\" is not (yet) implemented.", input, metric),
     ValueParseErr(err, number_str) => format!("Could not parse \"{}\" as floating-point value: \"{}\"", number_str, err),
+    InvalidCalcExpression(input) => format!("Invalid calc() expression: \"{}\"", input),
 }}
 
+const LENGTH_METRICS: [(&'static str, SizeMetric); 8] = [
+    ("px", SizeMetric::Px),
+    ("em", SizeMetric::Em),
+    ("rem", SizeMetric::Rem),
+    ("pt", SizeMetric::Pt),
+    ("vw", SizeMetric::Vw),
+    ("vh", SizeMetric::Vh),
+    ("vmin", SizeMetric::Vmin),
+    ("vmax", SizeMetric::Vmax),
+];
+
 pub fn parse_pixel_value<'a>(input: &'a str) -> Result<PixelValue, PixelParseError<'a>> {
-    parse_pixel_value_inner(
-        input,
-        &[
-            ("px", SizeMetric::Px),
-            ("em", SizeMetric::Em),
-            ("pt", SizeMetric::Pt),
-            ("%", SizeMetric::Percent),
-        ],
-    )
+    parse_pixel_value_inner(input, &LENGTH_METRICS, true)
 }
 
 pub fn parse_pixel_value_no_percent<'a>(
@@ -789,18 +924,16 @@ pub fn parse_pixel_value_no_percent<'a>(
 ) -> Result<PixelValueNoPercent, PixelParseError<'a>> {
     Ok(PixelValueNoPercent(parse_pixel_value_inner(
         input,
-        &[
-            ("px", SizeMetric::Px),
-            ("em", SizeMetric::Em),
-            ("pt", SizeMetric::Pt),
-        ],
+        &LENGTH_METRICS,
+        false,
     )?))
 }
 
-/// parse a single value such as "15px"
+/// parse a single value such as "15px" or a `calc()` expression
 fn parse_pixel_value_inner<'a>(
     input: &'a str,
     match_values: &[(&'static str, SizeMetric)],
+    allow_percent: bool,
 ) -> Result<PixelValue, PixelParseError<'a>> {
     let input = input.trim();
 
@@ -808,6 +941,23 @@ fn parse_pixel_value_inner<'a>(
         return Err(PixelParseError::EmptyString);
     }
 
+    if input.starts_with("calc(") {
+        return parse_calc_expression(input, match_values, allow_percent).map(PixelValue::calc);
+    }
+
+    parse_length(input, match_values, allow_percent)
+}
+
+/// Parses a number with a unit, such as `15px` or `50%`
+fn parse_length<'a>(
+    input: &'a str,
+    match_values: &[(&'static str, SizeMetric)],
+    allow_percent: bool,
+) -> Result<PixelValue, PixelParseError<'a>> {
+    if input.is_empty() {
+        return Err(PixelParseError::EmptyString);
+    }
+
     let is_part_of_number = |ch: &char| ch.is_numeric() || *ch == '.' || *ch == '-';
 
     // You can't sub-string pixel values, have to call collect() here!
@@ -837,11 +987,174 @@ fn parse_pixel_value_inner<'a>(
                 None
             }
         })
+        .or_else(|| {
+            if allow_percent && unit_str == "%" {
+                Some(SizeMetric::Percent)
+            } else {
+                None
+            }
+        })
         .ok_or(PixelParseError::UnsupportedMetric(number, unit_str, input))?;
 
     Ok(PixelValue::from_metric(unit, number))
 }
 
+/// Operand of a `calc()` expression, lengths can only be multiplied with or divided by numbers
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum CalcOperand {
+    Number(f32),
+    Length(CalcValue),
+}
+
+/// Parses `calc(100% - 2 * 16px)` - nested parentheses and nested `calc()` are allowed,
+/// `+` and `-` have to be surrounded by whitespace (as required by the CSS spec)
+fn parse_calc_expression<'a>(
+    input: &'a str,
+    match_values: &[(&'static str, SizeMetric)],
+    allow_percent: bool,
+) -> Result<CalcValue, PixelParseError<'a>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        match ch {
+            c if c.is_whitespace() => continue,
+            '(' | ')' | '*' | '/' => tokens.push(&input[start..start + 1]),
+            '+' | '-' if chars.peek().map_or(true, |(_, next)| next.is_whitespace()) => {
+                tokens.push(&input[start..start + 1])
+            }
+            _ => {
+                let mut end = input.len();
+                while let Some((idx, next)) = chars.peek() {
+                    if next.is_whitespace() || "()*/".contains(*next) {
+                        end = *idx;
+                        break;
+                    }
+                    chars.next();
+                }
+                tokens.push(&input[start..end]);
+            }
+        }
+    }
+
+    let mut parser = CalcParser {
+        input,
+        tokens: &tokens,
+        position: 0,
+        match_values,
+        allow_percent,
+    };
+
+    let result = parser.parse_sum()?;
+    if parser.position != tokens.len() {
+        return Err(PixelParseError::InvalidCalcExpression(input));
+    }
+
+    match result {
+        CalcOperand::Length(calc) => Ok(calc),
+        // calc(0) is a valid length
+        CalcOperand::Number(n) if n == 0.0 => Ok(CalcValue::default()),
+        CalcOperand::Number(_) => Err(PixelParseError::InvalidCalcExpression(input)),
+    }
+}
+
+struct CalcParser<'a, 'b> {
+    input: &'a str,
+    tokens: &'b [&'a str],
+    position: usize,
+    match_values: &'b [(&'static str, SizeMetric)],
+    allow_percent: bool,
+}
+
+impl<'a, 'b> CalcParser<'a, 'b> {
+    fn next_token(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_token(&self) -> Option<&'a str> {
+        self.tokens.get(self.position).cloned()
+    }
+
+    fn error(&self) -> PixelParseError<'a> {
+        PixelParseError::InvalidCalcExpression(self.input)
+    }
+
+    /// sum := product (("+" | "-") product)*
+    fn parse_sum(&mut self) -> Result<CalcOperand, PixelParseError<'a>> {
+        use self::CalcOperand::*;
+
+        let mut result = self.parse_product()?;
+        while let Some(op) = self.peek_token() {
+            if op != "+" && op != "-" {
+                break;
+            }
+            self.position += 1;
+            let rhs = self.parse_product()?;
+            result = match (result, rhs) {
+                (Number(a), Number(b)) if op == "+" => Number(a + b),
+                (Number(a), Number(b)) => Number(a - b),
+                (Length(a), Length(b)) if op == "+" => Length(a.add(&b)),
+                (Length(a), Length(b)) => Length(a.subtract(&b)),
+                // Adding a number to a length is invalid
+                _ => return Err(self.error()),
+            };
+        }
+        Ok(result)
+    }
+
+    /// product := value (("*" | "/") value)*
+    fn parse_product(&mut self) -> Result<CalcOperand, PixelParseError<'a>> {
+        use self::CalcOperand::*;
+
+        let mut result = self.parse_value()?;
+        while let Some(op) = self.peek_token() {
+            if op != "*" && op != "/" {
+                break;
+            }
+            self.position += 1;
+            let rhs = self.parse_value()?;
+            result = match (result, rhs) {
+                (Number(a), Number(b)) if op == "*" => Number(a * b),
+                (Length(a), Number(b)) | (Number(b), Length(a)) if op == "*" => {
+                    Length(a.multiply(b))
+                }
+                (_, Number(b)) if b == 0.0 => return Err(self.error()),
+                (Number(a), Number(b)) => Number(a / b),
+                (Length(a), Number(b)) => Length(a.multiply(1.0 / b)),
+                // Lengths can't be multiplied with each other or be used as a divisor
+                _ => return Err(self.error()),
+            };
+        }
+        Ok(result)
+    }
+
+    /// value := number | length | "(" sum ")" | "calc(" sum ")"
+    fn parse_value(&mut self) -> Result<CalcOperand, PixelParseError<'a>> {
+        let token = self.next_token().ok_or(self.error())?;
+
+        if token == "calc" || token == "(" {
+            if token == "calc" && self.next_token() != Some("(") {
+                return Err(self.error());
+            }
+            let inner = self.parse_sum()?;
+            return match self.next_token() {
+                Some(")") => Ok(inner),
+                _ => Err(self.error()),
+            };
+        }
+
+        if let Ok(number) = token.parse::<f32>() {
+            return Ok(CalcOperand::Number(number));
+        }
+
+        // Nested calc() values are resolved by the parser, so this can't return a Calc metric
+        parse_length(token, self.match_values, self.allow_percent)
+            .map(|length| CalcOperand::Length(CalcValue::from_pixel_value(length)))
+            .map_err(|_| self.error())
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum PercentageParseError {
     ValueParseErr(ParseFloatError),
@@ -2368,29 +2681,892 @@ pub fn parse_style_line_height(input: &str) -> Result<StyleLineHeight, Percentag
 
 typed_pixel_value_parser!(parse_style_font_size, StyleFontSize);
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub enum CssStyleFontFamilyParseError<'a> {
-    InvalidStyleFontFamily(&'a str),
-    UnclosedQuotes(&'a str),
+typed_pixel_value_parser!(parse_layout_grid_column_gap, LayoutGridColumnGap);
+typed_pixel_value_parser!(parse_layout_grid_row_gap, LayoutGridRowGap);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssGridParseError<'a> {
+    InvalidTrackSize(&'a str),
+    InvalidRepeatCount(&'a str),
+    FlexibleMinimum(&'a str),
+    InvalidGridLine(&'a str),
+    TooManyValues(&'a str),
+    EmptyValue,
+    ParenthesisParseError(ParenthesisParseError<'a>),
+    PixelParseError(PixelParseError<'a>),
 }
 
-impl_display! {CssStyleFontFamilyParseError<'a>, {
-    InvalidStyleFontFamily(val) => format!("Invalid font-family: \"{}\"", val),
-    UnclosedQuotes(val) => format!("Unclosed quotes: \"{}\"", val),
+impl_display! { CssGridParseError<'a>, {
+    InvalidTrackSize(e) => format!("Invalid grid track size: \"{}\"", e),
+    InvalidRepeatCount(e) => format!("Invalid repeat() count: \"{}\" - expected a positive integer, at most {} tracks are allowed", e, MAX_GRID_LINE),
+    FlexibleMinimum(e) => format!("Invalid minmax(): \"{}\" - the minimum can't be a flexible (fr) size", e),
+    InvalidGridLine(e) => format!("Invalid grid line: \"{}\" - expected \"auto\", a line number or \"span <number>\"", e),
+    TooManyValues(e) => format!("Too many values: \"{}\"", e),
+    EmptyValue => format!("Expected at least one value"),
+    ParenthesisParseError(e) => format!("{}", e),
+    PixelParseError(e) => format!("{}", e),
 }}
 
-impl<'a> From<UnclosedQuotesError<'a>> for CssStyleFontFamilyParseError<'a> {
-    fn from(err: UnclosedQuotesError<'a>) -> Self {
-        CssStyleFontFamilyParseError::UnclosedQuotes(err.0)
+impl_from!(
+    ParenthesisParseError<'a>,
+    CssGridParseError::ParenthesisParseError
+);
+impl_from!(PixelParseError<'a>, CssGridParseError::PixelParseError);
+
+/// Represents a parsed `grid-gap` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridGap {
+    pub row: PixelValue,
+    pub column: PixelValue,
+}
+
+/// Parses a `grid-gap` value, such as `"10px"` or `"10px 5%"` (row gap, then column gap)
+pub fn parse_layout_grid_gap<'a>(input: &'a str) -> Result<LayoutGridGap, CssGridParseError<'a>> {
+    let mut input_iter = input.split_whitespace();
+    let row = parse_pixel_value(input_iter.next().ok_or(CssGridParseError::EmptyValue)?)?;
+    let column = match input_iter.next() {
+        Some(column) => parse_pixel_value(column)?,
+        None => row,
+    };
+
+    if input_iter.next().is_some() {
+        return Err(CssGridParseError::TooManyValues(input));
     }
+
+    Ok(LayoutGridGap { row, column })
 }
 
-/// Parses a `StyleFontFamily` declaration from a `&str`
-///
-/// # Example
-///
-/// ```rust
-/// # extern crate azul_css;
+/// Splits the input at every `separator` that isn't enclosed in parentheses
+fn split_top_level<F: Fn(char) -> bool>(input: &str, separator: F) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0_usize;
+    let mut last_split = 0;
+
+    for (idx, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && separator(c) => {
+                parts.push(&input[last_split..idx]);
+                last_split = idx + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&input[last_split..]);
+    parts
+        .into_iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Parses a single track breadth: `100px`, `20%`, `1fr` or `auto`
+fn parse_grid_track_breadth<'a>(input: &'a str) -> Result<GridTrackBreadth, CssGridParseError<'a>> {
+    let input = input.trim();
+
+    if input == "auto" {
+        return Ok(GridTrackBreadth::Auto);
+    }
+
+    if let Some(fr) = input.strip_suffix("fr") {
+        return match fr.parse::<f32>() {
+            Ok(fr) if fr >= 0.0 => Ok(GridTrackBreadth::Fraction(FloatValue::new(fr))),
+            _ => Err(CssGridParseError::InvalidTrackSize(input)),
+        };
+    }
+
+    Ok(GridTrackBreadth::Fixed(parse_pixel_value(input)?))
+}
+
+/// Parses a single track size, i.e. a track breadth or a `minmax(min, max)`
+fn parse_grid_track_size<'a>(input: &'a str) -> Result<GridTrackSize, CssGridParseError<'a>> {
+    if !input.starts_with("minmax(") {
+        let breadth = parse_grid_track_breadth(input)?;
+        return Ok(GridTrackSize::minmax(breadth, breadth));
+    }
+
+    let (_, min_max) = parse_parentheses(input, &["minmax"])?;
+    match split_top_level(min_max, |c| c == ',').as_slice() {
+        [min, max] => match parse_grid_track_breadth(min)? {
+            GridTrackBreadth::Fraction(_) => Err(CssGridParseError::FlexibleMinimum(input)),
+            min => Ok(GridTrackSize::minmax(min, parse_grid_track_breadth(max)?)),
+        },
+        _ => Err(CssGridParseError::InvalidTrackSize(input)),
+    }
+}
+
+/// Parses a `grid-template-columns` / `grid-template-rows` value. `repeat()` is
+/// only supported with a fixed number of repetitions and expanded while parsing,
+/// templates with more than `MAX_GRID_LINE` tracks are rejected.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_grid_template;
+/// # use azul_css::{GridTemplate, GridTrackBreadth, GridTrackSize, PixelValue};
+/// assert_eq!(
+///     parse_grid_template("200px repeat(2, 1fr) minmax(100px, auto)"),
+///     Ok(GridTemplate {
+///         tracks: vec![
+///             GridTrackSize::px(200.0),
+///             GridTrackSize::fr(1.0),
+///             GridTrackSize::fr(1.0),
+///             GridTrackSize::minmax(
+///                 GridTrackBreadth::Fixed(PixelValue::px(100.0)),
+///                 GridTrackBreadth::Auto,
+///             ),
+///         ]
+///     })
+/// );
+/// ```
+pub fn parse_grid_template<'a>(input: &'a str) -> Result<GridTemplate, CssGridParseError<'a>> {
+    let mut tracks = Vec::new();
+
+    for track in split_top_level(input, char::is_whitespace) {
+        if !track.starts_with("repeat(") {
+            tracks.push(parse_grid_track_size(track)?);
+            continue;
+        }
+
+        let (_, repeat) = parse_parentheses(track, &["repeat"])?;
+        let (count, repeated_tracks) = match split_top_level(repeat, |c| c == ',').as_slice() {
+            [count, repeated_tracks] => (*count, *repeated_tracks),
+            _ => return Err(CssGridParseError::InvalidTrackSize(track)),
+        };
+
+        let repeat_count = match count.parse::<usize>() {
+            Ok(c) if c > 0 && c <= MAX_GRID_LINE as usize => c,
+            _ => return Err(CssGridParseError::InvalidRepeatCount(count)),
+        };
+
+        let repeated_tracks = split_top_level(repeated_tracks, char::is_whitespace)
+            .into_iter()
+            .map(parse_grid_track_size)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if repeated_tracks.is_empty() {
+            return Err(CssGridParseError::EmptyValue);
+        }
+
+        if tracks.len() + repeat_count * repeated_tracks.len() > MAX_GRID_LINE as usize {
+            return Err(CssGridParseError::InvalidRepeatCount(count));
+        }
+
+        for _ in 0..repeat_count {
+            tracks.extend(repeated_tracks.iter().cloned());
+        }
+    }
+
+    if tracks.is_empty() {
+        return Err(CssGridParseError::EmptyValue);
+    }
+
+    Ok(GridTemplate { tracks })
+}
+
+pub fn parse_layout_grid_template_columns<'a>(
+    input: &'a str,
+) -> Result<LayoutGridTemplateColumns, CssGridParseError<'a>> {
+    parse_grid_template(input).map(LayoutGridTemplateColumns)
+}
+
+pub fn parse_layout_grid_template_rows<'a>(
+    input: &'a str,
+) -> Result<LayoutGridTemplateRows, CssGridParseError<'a>> {
+    parse_grid_template(input).map(LayoutGridTemplateRows)
+}
+
+/// Parses the start or end of a `grid-column` / `grid-row`: `auto`, `2`, `-1` or `span 2`.
+/// Line numbers and spans are clamped to `MAX_GRID_LINE`
+fn parse_grid_line<'a>(input: &'a str) -> Result<GridLine, CssGridParseError<'a>> {
+    let input = input.trim();
+
+    if input == "auto" {
+        return Ok(GridLine::Auto);
+    }
+
+    if let Some(span) = input.strip_prefix("span") {
+        return match span.trim().parse::<u32>() {
+            Ok(span) if span > 0 => Ok(GridLine::Span(span.min(MAX_GRID_LINE as u32))),
+            _ => Err(CssGridParseError::InvalidGridLine(input)),
+        };
+    }
+
+    match input.parse::<i32>() {
+        Ok(line) if line != 0 => Ok(GridLine::Line(line.clamp(-MAX_GRID_LINE, MAX_GRID_LINE))),
+        _ => Err(CssGridParseError::InvalidGridLine(input)),
+    }
+}
+
+/// Parses a `grid-column` / `grid-row` value, such as `"1 / 3"`, `"2 / span 2"` or `"span 2"`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_grid_placement;
+/// # use azul_css::{GridLine, GridPlacement};
+/// assert_eq!(
+///     parse_grid_placement("1 / span 2"),
+///     Ok(GridPlacement { start: GridLine::Line(1), end: GridLine::Span(2) })
+/// );
+/// ```
+pub fn parse_grid_placement<'a>(input: &'a str) -> Result<GridPlacement, CssGridParseError<'a>> {
+    let mut input_iter = input.split('/');
+    let start = parse_grid_line(input_iter.next().ok_or(CssGridParseError::EmptyValue)?)?;
+    let end = match input_iter.next() {
+        Some(end) => parse_grid_line(end)?,
+        None => GridLine::Auto,
+    };
+
+    if input_iter.next().is_some() {
+        return Err(CssGridParseError::TooManyValues(input));
+    }
+
+    Ok(GridPlacement { start, end })
+}
+
+pub fn parse_layout_grid_column<'a>(
+    input: &'a str,
+) -> Result<LayoutGridColumn, CssGridParseError<'a>> {
+    parse_grid_placement(input).map(LayoutGridColumn)
+}
+
+pub fn parse_layout_grid_row<'a>(input: &'a str) -> Result<LayoutGridRow, CssGridParseError<'a>> {
+    parse_grid_placement(input).map(LayoutGridRow)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssTransformParseError<'a> {
+    WrongNumberOfArguments(&'a str),
+    InvalidNumber(&'a str),
+    InvalidAngle(&'a str),
+    InvalidOrigin(&'a str),
+    TooManyValues(&'a str),
+    EmptyValue,
+    ParenthesisParseError(ParenthesisParseError<'a>),
+    PixelParseError(PixelParseError<'a>),
+}
+
+impl_display! { CssTransformParseError<'a>, {
+    WrongNumberOfArguments(e) => format!("Wrong number of arguments for transform function: \"{}\"", e),
+    InvalidNumber(e) => format!("Invalid number: \"{}\"", e),
+    InvalidAngle(e) => format!("Invalid angle: \"{}\" - expected a number followed by \"deg\", \"rad\", \"grad\" or \"turn\"", e),
+    InvalidOrigin(e) => format!("Invalid transform-origin: \"{}\"", e),
+    TooManyValues(e) => format!("Too many values: \"{}\"", e),
+    EmptyValue => format!("Expected at least one value"),
+    ParenthesisParseError(e) => format!("{}", e),
+    PixelParseError(e) => format!("{}", e),
+}}
+
+impl_from!(
+    ParenthesisParseError<'a>,
+    CssTransformParseError::ParenthesisParseError
+);
+impl_from!(PixelParseError<'a>, CssTransformParseError::PixelParseError);
+
+/// Parses a `transform` attribute, such as `"translate(10px, 50%) rotate(45deg) scale(2)"`
+pub fn parse_style_transform<'a>(
+    input: &'a str,
+) -> Result<StyleTransform, CssTransformParseError<'a>> {
+    let functions = split_top_level(input, char::is_whitespace)
+        .into_iter()
+        .map(parse_transform_function)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if functions.is_empty() {
+        return Err(CssTransformParseError::EmptyValue);
+    }
+
+    Ok(StyleTransform(functions))
+}
+
+/// Parses a single transform function, such as `"rotate(45deg)"`
+fn parse_transform_function<'a>(
+    input: &'a str,
+) -> Result<StyleTransformFunction, CssTransformParseError<'a>> {
+    use self::StyleTransformFunction::*;
+
+    let (function, args) = parse_parentheses(
+        input,
+        &[
+            "matrix",
+            "translate",
+            "translateX",
+            "translateY",
+            "rotate",
+            "scale",
+            "scaleX",
+            "scaleY",
+            "skew",
+            "skewX",
+            "skewY",
+        ],
+    )?;
+
+    let args = args.split(',').map(|a| a.trim()).collect::<Vec<_>>();
+    let zero = PixelValue::px(0.0);
+    let one = FloatValue::const_new(1);
+
+    Ok(match (function, args.as_slice()) {
+        ("matrix", [a, b, c, d, e, f]) => Matrix(StyleTransformMatrix2D {
+            a: parse_transform_number(a)?,
+            b: parse_transform_number(b)?,
+            c: parse_transform_number(c)?,
+            d: parse_transform_number(d)?,
+            e: parse_transform_number(e)?,
+            f: parse_transform_number(f)?,
+        }),
+        ("translate", [x]) => Translate(parse_pixel_value(x)?, zero),
+        ("translate", [x, y]) => Translate(parse_pixel_value(x)?, parse_pixel_value(y)?),
+        ("translateX", [x]) => Translate(parse_pixel_value(x)?, zero),
+        ("translateY", [y]) => Translate(zero, parse_pixel_value(y)?),
+        ("rotate", [angle]) => Rotate(parse_angle(angle)?),
+        ("scale", [xy]) => {
+            let xy = parse_transform_number(xy)?;
+            Scale(xy, xy)
+        }
+        ("scale", [x, y]) => Scale(parse_transform_number(x)?, parse_transform_number(y)?),
+        ("scaleX", [x]) => Scale(parse_transform_number(x)?, one),
+        ("scaleY", [y]) => Scale(one, parse_transform_number(y)?),
+        ("skew", [x]) => Skew(parse_angle(x)?, FloatValue::default()),
+        ("skew", [x, y]) => Skew(parse_angle(x)?, parse_angle(y)?),
+        ("skewX", [x]) => Skew(parse_angle(x)?, FloatValue::default()),
+        ("skewY", [y]) => Skew(FloatValue::default(), parse_angle(y)?),
+        _ => return Err(CssTransformParseError::WrongNumberOfArguments(input)),
+    })
+}
+
+fn parse_transform_number<'a>(input: &'a str) -> Result<FloatValue, CssTransformParseError<'a>> {
+    parse_float_value(input).map_err(|_| CssTransformParseError::InvalidNumber(input))
+}
+
+/// Parses an angle (`"45deg"`, `"0.5turn"`, `"3.14rad"`, `"100grad"` or `"0"`) into degrees
+fn parse_angle<'a>(input: &'a str) -> Result<FloatValue, CssTransformParseError<'a>> {
+    let (number, degrees_per_unit) = if let Some(deg) = input.strip_suffix("deg") {
+        (deg, 1.0)
+    } else if let Some(grad) = input.strip_suffix("grad") {
+        (grad, 0.9)
+    } else if let Some(rad) = input.strip_suffix("rad") {
+        (rad, 180.0 / ::std::f32::consts::PI)
+    } else if let Some(turn) = input.strip_suffix("turn") {
+        (turn, 360.0)
+    } else if input == "0" {
+        (input, 0.0)
+    } else {
+        return Err(CssTransformParseError::InvalidAngle(input));
+    };
+
+    let number = number
+        .parse::<f32>()
+        .map_err(|_| CssTransformParseError::InvalidAngle(input))?;
+
+    Ok(FloatValue::new(number * degrees_per_unit))
+}
+
+/// Parses a `transform-origin` attribute, such as `"center"`, `"left top"` or `"10px 50%"`
+pub fn parse_style_transform_origin<'a>(
+    input: &'a str,
+) -> Result<StyleTransformOrigin, CssTransformParseError<'a>> {
+    let mut whitespace_iter = input.split_whitespace();
+    let first = whitespace_iter
+        .next()
+        .ok_or(CssTransformParseError::EmptyValue)?;
+    let second = whitespace_iter.next();
+
+    if whitespace_iter.next().is_some() {
+        return Err(CssTransformParseError::TooManyValues(input));
+    }
+
+    let is_vertical_keyword = |s: &str| s == "top" || s == "bottom";
+    let is_horizontal_keyword = |s: &str| s == "left" || s == "right";
+
+    // "top left" is the same as "left top", a single vertical keyword centers horizontally
+    let (x, y) = match second {
+        None if is_vertical_keyword(first) => ("center", first),
+        None => (first, "center"),
+        Some(second) if is_vertical_keyword(first) || is_horizontal_keyword(second) => {
+            (second, first)
+        }
+        Some(second) => (first, second),
+    };
+
+    let parse_component = |component: &'a str, start: &str, end: &str| {
+        Ok(match component {
+            s if s == start => PixelValue::percent(0.0),
+            "center" => PixelValue::percent(50.0),
+            s if s == end => PixelValue::percent(100.0),
+            "left" | "right" | "top" | "bottom" => {
+                return Err(CssTransformParseError::InvalidOrigin(input))
+            }
+            other => parse_pixel_value(other)?,
+        })
+    };
+
+    Ok(StyleTransformOrigin {
+        x: parse_component(x, "left", "right")?,
+        y: parse_component(y, "top", "bottom")?,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssFilterParseError<'a> {
+    InvalidAmount(&'a str),
+    WrongNumberOfArguments(&'a str),
+    EmptyValue,
+    ParenthesisParseError(ParenthesisParseError<'a>),
+    PixelParseError(PixelParseError<'a>),
+    ColorParseError(CssColorParseError<'a>),
+}
+
+impl_display! { CssFilterParseError<'a>, {
+    InvalidAmount(e) => format!("Invalid amount: \"{}\" - expected a positive number or percentage", e),
+    WrongNumberOfArguments(e) => format!("Wrong number of arguments for filter function: \"{}\"", e),
+    EmptyValue => format!("Expected at least one value"),
+    ParenthesisParseError(e) => format!("{}", e),
+    PixelParseError(e) => format!("{}", e),
+    ColorParseError(e) => format!("{}", e),
+}}
+
+impl_from!(
+    ParenthesisParseError<'a>,
+    CssFilterParseError::ParenthesisParseError
+);
+impl_from!(PixelParseError<'a>, CssFilterParseError::PixelParseError);
+impl_from!(CssColorParseError<'a>, CssFilterParseError::ColorParseError);
+
+/// Parses an `opacity` attribute, such as `"0.5"` or `"50%"` - values outside
+/// of the range `0.0` - `1.0` are clamped
+pub fn parse_style_opacity<'a>(input: &'a str) -> Result<StyleOpacity, CssFilterParseError<'a>> {
+    let opacity = parse_filter_amount(input)?;
+    Ok(StyleOpacity(FloatValue::new(opacity.get().min(1.0))))
+}
+
+/// Parses a `filter` attribute, such as `"blur(5px) grayscale(100%)"`
+pub fn parse_style_filter<'a>(input: &'a str) -> Result<StyleFilter, CssFilterParseError<'a>> {
+    let functions = split_top_level(input, char::is_whitespace)
+        .into_iter()
+        .map(parse_filter_function)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if functions.is_empty() {
+        return Err(CssFilterParseError::EmptyValue);
+    }
+
+    Ok(StyleFilter(functions))
+}
+
+/// Parses a single filter function, such as `"blur(5px)"`
+fn parse_filter_function<'a>(
+    input: &'a str,
+) -> Result<StyleFilterFunction, CssFilterParseError<'a>> {
+    use self::StyleFilterFunction::*;
+
+    let (function, args) =
+        parse_parentheses(input, &["blur", "grayscale", "brightness", "drop-shadow"])?;
+    let args = args.trim();
+
+    // Missing arguments use the defaults from the CSS spec
+    Ok(match function {
+        "blur" if args.is_empty() => Blur(PixelValueNoPercent(PixelValue::const_px(0))),
+        "blur" => Blur(parse_pixel_value_no_percent(args)?),
+        "grayscale" if args.is_empty() => Grayscale(FloatValue::const_new(1)),
+        "grayscale" => Grayscale(FloatValue::new(parse_filter_amount(args)?.get().min(1.0))),
+        "brightness" if args.is_empty() => Brightness(FloatValue::const_new(1)),
+        "brightness" => Brightness(parse_filter_amount(args)?),
+        "drop-shadow" => DropShadow(parse_drop_shadow(args)?),
+        _ => unreachable!(),
+    })
+}
+
+/// Parses a positive number or percentage (`"0.5"` or `"50%"`) into a number (`0.5`)
+fn parse_filter_amount<'a>(input: &'a str) -> Result<FloatValue, CssFilterParseError<'a>> {
+    let input = input.trim();
+    let amount = match input.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().map(|p| p / 100.0),
+        None => input.parse::<f32>(),
+    }
+    .map_err(|_| CssFilterParseError::InvalidAmount(input))?;
+
+    if amount < 0.0 {
+        return Err(CssFilterParseError::InvalidAmount(input));
+    }
+
+    Ok(FloatValue::new(amount))
+}
+
+/// Parses the arguments of a `drop-shadow()` function: two or three lengths
+/// (offset-x, offset-y, blur-radius) and an optional color (default: black) before or after
+/// the lengths
+fn parse_drop_shadow<'a>(input: &'a str) -> Result<StyleDropShadow, CssFilterParseError<'a>> {
+    let mut components = split_top_level(input, char::is_whitespace);
+
+    let color = match (components.first(), components.last()) {
+        (Some(first), _) if parse_pixel_value_no_percent(first).is_err() => {
+            Some(parse_css_color(components.remove(0))?)
+        }
+        (_, Some(last)) if parse_pixel_value_no_percent(last).is_err() => {
+            Some(parse_css_color(components.pop().unwrap())?)
+        }
+        _ => None,
+    };
+
+    let zero = PixelValueNoPercent(PixelValue::const_px(0));
+    let (offset_x, offset_y, blur_radius) = match components.as_slice() {
+        [x, y] => (*x, *y, None),
+        [x, y, blur] => (*x, *y, Some(*blur)),
+        _ => return Err(CssFilterParseError::WrongNumberOfArguments(input)),
+    };
+
+    Ok(StyleDropShadow {
+        offset: [
+            parse_pixel_value_no_percent(offset_x)?,
+            parse_pixel_value_no_percent(offset_y)?,
+        ],
+        color: color.unwrap_or(ColorU::BLACK),
+        blur_radius: match blur_radius {
+            Some(blur) => parse_pixel_value_no_percent(blur)?,
+            None => zero,
+        },
+    })
+}
+
+/// Parses a `text-shadow` attribute: a comma-separated list of shadows, such as
+/// `"1px 1px 2px black, 0 0 1em red"`, each with the arguments of a `drop-shadow()`
+pub fn parse_style_text_shadow<'a>(
+    input: &'a str,
+) -> Result<StyleTextShadow, CssShadowParseError<'a>> {
+    let shadows = split_top_level(input, |c| c == ',')
+        .into_iter()
+        .map(|shadow| {
+            parse_drop_shadow(shadow).map_err(|e| match e {
+                CssFilterParseError::PixelParseError(e) => CssShadowParseError::ValueParseErr(e),
+                CssFilterParseError::ColorParseError(e) => CssShadowParseError::ColorParseError(e),
+                _ => CssShadowParseError::InvalidSingleStatement(shadow),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if shadows.is_empty() {
+        return Err(CssShadowParseError::InvalidSingleStatement(input));
+    }
+
+    Ok(StyleTextShadow(shadows))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssAnimationParseError<'a> {
+    InvalidDuration(&'a str),
+    InvalidTimingFunction(&'a str),
+    InvalidIterationCount(&'a str),
+    InvalidAnimationName(&'a str),
+    UnknownTransitionProperty(&'a str),
+    UnexpectedValue(&'a str),
+    MultipleAnimations(&'a str),
+    EmptyValue,
+}
+
+impl_display! { CssAnimationParseError<'a>, {
+    InvalidDuration(e) => format!("Invalid duration: \"{}\" - expected a number followed by \"s\" or \"ms\"", e),
+    InvalidTimingFunction(e) => format!("Invalid timing function: \"{}\"", e),
+    InvalidIterationCount(e) => format!("Invalid iteration count: \"{}\" - expected a positive number or \"infinite\"", e),
+    InvalidAnimationName(e) => format!("Invalid animation name: \"{}\"", e),
+    UnknownTransitionProperty(e) => format!("Unknown CSS key in transition: \"{}\"", e),
+    UnexpectedValue(e) => format!("Unexpected value: \"{}\"", e),
+    MultipleAnimations(e) => format!("Only one animation per node is supported: \"{}\"", e),
+    EmptyValue => format!("Expected at least one value"),
+}}
+
+/// Parses a duration, such as `"300ms"` or `"1.5s"`
+pub fn parse_css_duration<'a>(input: &'a str) -> Result<CssDuration, CssAnimationParseError<'a>> {
+    let input = input.trim();
+
+    let (number, milliseconds_per_unit) = if let Some(ms) = input.strip_suffix("ms") {
+        (ms, 1.0)
+    } else if let Some(s) = input.strip_suffix("s") {
+        (s, 1000.0)
+    } else if input == "0" {
+        (input, 0.0)
+    } else {
+        return Err(CssAnimationParseError::InvalidDuration(input));
+    };
+
+    match number.parse::<f32>() {
+        Ok(number) if number >= 0.0 => Ok(CssDuration::from_millis(
+            (number * milliseconds_per_unit).round() as u32,
+        )),
+        _ => Err(CssAnimationParseError::InvalidDuration(input)),
+    }
+}
+
+/// Parses a timing function, such as `"ease-in"`, `"cubic-bezier(0.1, 0.7, 1.0, 0.1)"`
+/// or `"steps(4, end)"`
+pub fn parse_style_timing_function<'a>(
+    input: &'a str,
+) -> Result<StyleTimingFunction, CssAnimationParseError<'a>> {
+    use self::StyleTimingFunction::*;
+
+    let input = input.trim();
+
+    match input {
+        "linear" => return Ok(Linear),
+        "ease" => return Ok(Ease),
+        "ease-in" => return Ok(EaseIn),
+        "ease-out" => return Ok(EaseOut),
+        "ease-in-out" => return Ok(EaseInOut),
+        "step-start" => return Ok(Steps(1, StepPosition::Start)),
+        "step-end" => return Ok(Steps(1, StepPosition::End)),
+        _ => {}
+    }
+
+    let (function, args) = parse_parentheses(input, &["cubic-bezier", "steps"])
+        .map_err(|_| CssAnimationParseError::InvalidTimingFunction(input))?;
+    let args = args.split(',').map(|arg| arg.trim()).collect::<Vec<_>>();
+    let number = |arg: &str| {
+        parse_float_value(arg).map_err(|_| CssAnimationParseError::InvalidTimingFunction(input))
+    };
+
+    match (function, args.as_slice()) {
+        ("cubic-bezier", [x1, y1, x2, y2]) => {
+            let (x1, x2) = (number(x1)?, number(x2)?);
+            // the x values have to be in [0, 1], otherwise the curve is not a function of time
+            if [x1, x2].iter().any(|x| x.get() < 0.0 || x.get() > 1.0) {
+                return Err(CssAnimationParseError::InvalidTimingFunction(input));
+            }
+            Ok(CubicBezier(x1, number(y1)?, x2, number(y2)?))
+        }
+        ("steps", [count, position @ ..]) if position.len() <= 1 => {
+            let count = match count.parse::<u32>() {
+                Ok(count) if count > 0 => count,
+                _ => return Err(CssAnimationParseError::InvalidTimingFunction(input)),
+            };
+            let position = match position.first() {
+                None | Some(&"end") | Some(&"jump-end") => StepPosition::End,
+                Some(&"start") | Some(&"jump-start") => StepPosition::Start,
+                Some(_) => return Err(CssAnimationParseError::InvalidTimingFunction(input)),
+            };
+            Ok(Steps(count, position))
+        }
+        _ => Err(CssAnimationParseError::InvalidTimingFunction(input)),
+    }
+}
+
+/// Parses a `transition` attribute, such as `"opacity 300ms ease-in, width 1s linear 0.5s"`.
+///
+/// Each item consists of the property (default: `all`), the duration, the timing function
+/// and the delay, in any order (the first duration is the duration, the second one the delay).
+/// Shorthand properties (such as `margin`) are expanded into one item per property.
+pub fn parse_style_transition<'a>(
+    input: &'a str,
+) -> Result<StyleTransition, CssAnimationParseError<'a>> {
+    let css_key_map = azul_css::get_css_key_map();
+    let mut items = Vec::new();
+
+    for item in split_top_level(input, |c| c == ',') {
+        let mut property = None;
+        let mut durations = Vec::new();
+        let mut timing_function = None;
+
+        for value in split_top_level(item, char::is_whitespace) {
+            if let Ok(duration) = parse_css_duration(value) {
+                if durations.len() == 2 {
+                    return Err(CssAnimationParseError::UnexpectedValue(value));
+                }
+                durations.push(duration);
+            } else if let (None, Ok(function)) =
+                (timing_function, parse_style_timing_function(value))
+            {
+                timing_function = Some(function);
+            } else if property.is_none() {
+                property = Some(parse_transition_property(value, &css_key_map)?);
+            } else {
+                return Err(CssAnimationParseError::UnexpectedValue(value));
+            }
+        }
+
+        let properties = property.unwrap_or_else(|| vec![TransitionProperty::All]);
+        items.extend(properties.into_iter().map(|property| StyleTransitionItem {
+            property,
+            duration: durations.get(0).copied().unwrap_or_default(),
+            timing_function: timing_function.unwrap_or_default(),
+            delay: durations.get(1).copied().unwrap_or_default(),
+        }));
+    }
+
+    if items.is_empty() {
+        return Err(CssAnimationParseError::EmptyValue);
+    }
+
+    Ok(StyleTransition(items))
+}
+
+/// Parses the property of a transition - shorthands expand to multiple properties
+fn parse_transition_property<'a>(
+    input: &'a str,
+    css_key_map: &CssKeyMap,
+) -> Result<Vec<TransitionProperty>, CssAnimationParseError<'a>> {
+    if input == "all" {
+        return Ok(vec![TransitionProperty::All]);
+    }
+
+    let property_types = if let Some(key) = CssPropertyType::from_str(input, css_key_map) {
+        vec![key]
+    } else if let Some(key) = CombinedCssPropertyType::from_str(input, css_key_map) {
+        get_combined_css_property_types(key)
+    } else {
+        return Err(CssAnimationParseError::UnknownTransitionProperty(input));
+    };
+
+    Ok(property_types
+        .into_iter()
+        .map(|property_type| match property_type {
+            // all background properties are stored as `BackgroundImage`, see `CssProperty::get_type`
+            CssPropertyType::Background | CssPropertyType::BackgroundColor => {
+                CssPropertyType::BackgroundImage
+            }
+            other => other,
+        })
+        .map(TransitionProperty::Property)
+        .collect())
+}
+
+/// Parses an `animation-name` attribute, i.e. the name of a `@keyframes` rule
+pub fn parse_style_animation_name<'a>(
+    input: &'a str,
+) -> Result<StyleAnimationName, CssAnimationParseError<'a>> {
+    let input = input.trim();
+    let is_identifier = !input.is_empty()
+        && !input.starts_with(|c: char| c.is_ascii_digit())
+        && input
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+
+    if is_identifier {
+        Ok(StyleAnimationName(input.to_string()))
+    } else {
+        Err(CssAnimationParseError::InvalidAnimationName(input))
+    }
+}
+
+/// Parses an `animation-iteration-count` attribute, such as `"infinite"` or `"2.5"`
+pub fn parse_style_animation_iteration_count<'a>(
+    input: &'a str,
+) -> Result<StyleAnimationIterationCount, CssAnimationParseError<'a>> {
+    let input = input.trim();
+
+    if input == "infinite" {
+        return Ok(StyleAnimationIterationCount::Infinite);
+    }
+
+    match parse_float_value(input) {
+        Ok(count) if count.get() >= 0.0 => Ok(StyleAnimationIterationCount::Count(count)),
+        _ => Err(CssAnimationParseError::InvalidIterationCount(input)),
+    }
+}
+
+multi_type_parser!(
+    parse_style_animation_direction,
+    StyleAnimationDirection,
+    ["normal", Normal],
+    ["reverse", Reverse],
+    ["alternate", Alternate],
+    ["alternate-reverse", AlternateReverse]
+);
+
+multi_type_parser!(
+    parse_style_animation_fill_mode,
+    StyleAnimationFillMode,
+    ["forwards", Forwards],
+    ["backwards", Backwards],
+    ["both", Both]
+);
+
+/// Parses an `animation` shorthand, such as `"fade 1s ease-in 0.5s infinite alternate"`,
+/// into all `animation-*` properties (the properties that aren't specified are reset).
+/// The values can be in any order, the first duration is the duration, the second one
+/// is the delay.
+pub fn parse_style_animation<'a>(
+    input: &'a str,
+) -> Result<Vec<CssProperty>, CssAnimationParseError<'a>> {
+    if split_top_level(input, |c| c == ',').len() > 1 {
+        return Err(CssAnimationParseError::MultipleAnimations(input));
+    }
+
+    let mut name = None;
+    let mut durations = Vec::new();
+    let mut timing_function = None;
+    let mut iteration_count = None;
+    let mut direction = None;
+    let mut fill_mode = None;
+
+    for value in split_top_level(input, char::is_whitespace) {
+        if let Ok(duration) = parse_css_duration(value) {
+            if durations.len() == 2 {
+                return Err(CssAnimationParseError::UnexpectedValue(value));
+            }
+            durations.push(duration);
+        } else if let (None, Ok(t)) = (timing_function, parse_style_timing_function(value)) {
+            timing_function = Some(t);
+        } else if let (None, Ok(i)) = (
+            iteration_count,
+            parse_style_animation_iteration_count(value),
+        ) {
+            iteration_count = Some(i);
+        } else if let (None, Ok(d)) = (direction, parse_style_animation_direction(value)) {
+            direction = Some(d);
+        } else if let (None, Ok(f)) = (fill_mode, parse_style_animation_fill_mode(value)) {
+            fill_mode = Some(f);
+        } else if name.is_none() {
+            name = Some(match value {
+                "none" => CssPropertyValue::None,
+                other => CssPropertyValue::Exact(parse_style_animation_name(other)?),
+            });
+        } else {
+            return Err(CssAnimationParseError::UnexpectedValue(value));
+        }
+    }
+
+    Ok(vec![
+        CssProperty::AnimationName(name.unwrap_or(CssPropertyValue::None)),
+        StyleAnimationDuration(durations.get(0).copied().unwrap_or_default()).into(),
+        StyleAnimationTimingFunction(timing_function.unwrap_or_default()).into(),
+        StyleAnimationDelay(durations.get(1).copied().unwrap_or_default()).into(),
+        iteration_count.unwrap_or_default().into(),
+        direction.unwrap_or_default().into(),
+        CssProperty::AnimationFillMode(
+            fill_mode.map_or(CssPropertyValue::None, CssPropertyValue::Exact),
+        ),
+    ])
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CssStyleFontFamilyParseError<'a> {
+    InvalidStyleFontFamily(&'a str),
+    UnclosedQuotes(&'a str),
+}
+
+impl_display! {CssStyleFontFamilyParseError<'a>, {
+    InvalidStyleFontFamily(val) => format!("Invalid font-family: \"{}\"", val),
+    UnclosedQuotes(val) => format!("Unclosed quotes: \"{}\"", val),
+}}
+
+impl<'a> From<UnclosedQuotesError<'a>> for CssStyleFontFamilyParseError<'a> {
+    fn from(err: UnclosedQuotesError<'a>) -> Self {
+        CssStyleFontFamilyParseError::UnclosedQuotes(err.0)
+    }
+}
+
+/// Parses a `StyleFontFamily` declaration from a `&str`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
 /// # extern crate azul_css_parser;
 /// # use azul_css_parser::parse_style_font_family;
 /// # use azul_css::{StyleFontFamily, FontId};
@@ -2420,6 +3596,342 @@ pub fn parse_style_font_family<'a>(
     Ok(StyleFontFamily { fonts: fonts })
 }
 
+/// Parses a `StyleFontWeight` declaration from a `&str`, either `normal`, `bold`
+/// or a number between `1` and `1000`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_font_weight;
+/// # use azul_css::StyleFontWeight;
+/// assert_eq!(parse_style_font_weight("bold"), Ok(StyleFontWeight::BOLD));
+/// assert_eq!(parse_style_font_weight("300"), Ok(StyleFontWeight(300)));
+/// ```
+pub fn parse_style_font_weight<'a>(input: &'a str) -> Result<StyleFontWeight, InvalidValueErr<'a>> {
+    let input = input.trim();
+    match input {
+        "normal" => Ok(StyleFontWeight::NORMAL),
+        "bold" => Ok(StyleFontWeight::BOLD),
+        number => match number.parse::<u16>() {
+            Ok(weight) if (1..=1000).contains(&weight) => Ok(StyleFontWeight(weight)),
+            _ => Err(InvalidValueErr(input)),
+        },
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum CssFontSettingsParseError<'a> {
+    InvalidTag(&'a str),
+    InvalidValue(&'a str),
+    UnclosedQuotes(&'a str),
+}
+
+impl_display! {CssFontSettingsParseError<'a>, {
+    InvalidTag(val) => format!("Invalid font feature / variation tag, expected 4 characters: \"{}\"", val),
+    InvalidValue(val) => format!("Invalid font feature / variation value: \"{}\"", val),
+    UnclosedQuotes(val) => format!("Unclosed quotes: \"{}\"", val),
+}}
+
+impl<'a> From<UnclosedQuotesError<'a>> for CssFontSettingsParseError<'a> {
+    fn from(err: UnclosedQuotesError<'a>) -> Self {
+        CssFontSettingsParseError::UnclosedQuotes(err.0)
+    }
+}
+
+/// Splits a `"tag" value` pair of `font-feature-settings` / `font-variation-settings` into
+/// the tag (4 printable ASCII characters in quotes) and the (possibly empty) value
+fn parse_font_tag_and_value<'a>(
+    input: &'a str,
+) -> Result<(FontTag, &'a str), CssFontSettingsParseError<'a>> {
+    let input = input.trim();
+    let quote = match input.chars().next() {
+        Some(c) if c == '"' || c == '\'' => c,
+        _ => return Err(CssFontSettingsParseError::InvalidTag(input)),
+    };
+    let tag_end = match input[1..].find(quote) {
+        Some(idx) => idx + 2,
+        None => return Err(CssFontSettingsParseError::UnclosedQuotes(input)),
+    };
+
+    let tag = strip_quotes(&input[..tag_end])?.0;
+    let tag_bytes = tag.as_bytes();
+    if tag_bytes.len() != 4 || !tag_bytes.iter().all(|b| (0x20..=0x7E).contains(b)) {
+        return Err(CssFontSettingsParseError::InvalidTag(tag));
+    }
+
+    let tag = FontTag([tag_bytes[0], tag_bytes[1], tag_bytes[2], tag_bytes[3]]);
+    Ok((tag, input[tag_end..].trim()))
+}
+
+/// Parses a `font-feature-settings` attribute: `normal` or a comma-separated list of
+/// feature tags, each followed by an optional value (`on`, `off` or a number, default `on`)
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_font_feature_settings;
+/// # use azul_css::{StyleFontFeatureSettings, FontFeature, FontTag};
+/// assert_eq!(
+///     parse_style_font_feature_settings("\"tnum\", \"liga\" off"),
+///     Ok(StyleFontFeatureSettings(vec![
+///         FontFeature { tag: FontTag(*b"tnum"), value: 1 },
+///         FontFeature { tag: FontTag(*b"liga"), value: 0 },
+///     ]))
+/// );
+/// ```
+pub fn parse_style_font_feature_settings<'a>(
+    input: &'a str,
+) -> Result<StyleFontFeatureSettings, CssFontSettingsParseError<'a>> {
+    if input.trim() == "normal" {
+        return Ok(StyleFontFeatureSettings::default());
+    }
+
+    let features = input
+        .split(',')
+        .map(|feature| {
+            let (tag, value) = parse_font_tag_and_value(feature)?;
+            let value = match value {
+                "" | "on" => 1,
+                "off" => 0,
+                number => number
+                    .parse::<u32>()
+                    .map_err(|_| CssFontSettingsParseError::InvalidValue(number))?,
+            };
+            Ok(FontFeature { tag, value })
+        })
+        .collect::<Result<Vec<_>, CssFontSettingsParseError>>()?;
+
+    Ok(StyleFontFeatureSettings(features))
+}
+
+/// Parses a `font-variant-numeric` attribute: `normal` or a combination of one figure
+/// value (`lining-nums`, `oldstyle-nums`), one spacing value (`proportional-nums`,
+/// `tabular-nums`), one fraction value (`diagonal-fractions`, `stacked-fractions`),
+/// `ordinal` and `slashed-zero`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_font_variant_numeric;
+/// # use azul_css::{StyleFontVariantNumeric, NumericSpacing};
+/// assert_eq!(
+///     parse_style_font_variant_numeric("tabular-nums slashed-zero"),
+///     Ok(StyleFontVariantNumeric {
+///         spacing: NumericSpacing::Tabular,
+///         slashed_zero: true,
+///         ..Default::default()
+///     })
+/// );
+/// ```
+pub fn parse_style_font_variant_numeric<'a>(
+    input: &'a str,
+) -> Result<StyleFontVariantNumeric, InvalidValueErr<'a>> {
+    let input = input.trim();
+    let mut numeric = StyleFontVariantNumeric::default();
+
+    if input == "normal" {
+        return Ok(numeric);
+    }
+
+    // Every value may only be specified once
+    let mut figure = None;
+    let mut spacing = None;
+    let mut fraction = None;
+    let mut ordinal = false;
+    let mut slashed_zero = false;
+
+    for component in input.split_whitespace() {
+        use std::mem;
+        let is_duplicate = match component {
+            "lining-nums" => figure.replace(NumericFigure::Lining).is_some(),
+            "oldstyle-nums" => figure.replace(NumericFigure::OldStyle).is_some(),
+            "proportional-nums" => spacing.replace(NumericSpacing::Proportional).is_some(),
+            "tabular-nums" => spacing.replace(NumericSpacing::Tabular).is_some(),
+            "diagonal-fractions" => fraction.replace(NumericFraction::Diagonal).is_some(),
+            "stacked-fractions" => fraction.replace(NumericFraction::Stacked).is_some(),
+            "ordinal" => mem::replace(&mut ordinal, true),
+            "slashed-zero" => mem::replace(&mut slashed_zero, true),
+            _ => return Err(InvalidValueErr(component)),
+        };
+        if is_duplicate {
+            return Err(InvalidValueErr(input));
+        }
+    }
+
+    numeric.figure = figure.unwrap_or_default();
+    numeric.spacing = spacing.unwrap_or_default();
+    numeric.fraction = fraction.unwrap_or_default();
+    numeric.ordinal = ordinal;
+    numeric.slashed_zero = slashed_zero;
+    Ok(numeric)
+}
+
+/// Parses a `font-variation-settings` attribute: `normal` or a comma-separated
+/// list of variation axis tags, each followed by the value of the axis
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_font_variation_settings;
+/// # use azul_css::{StyleFontVariationSettings, FontVariation, FontTag, FloatValue};
+/// assert_eq!(
+///     parse_style_font_variation_settings("\"wght\" 650"),
+///     Ok(StyleFontVariationSettings(vec![
+///         FontVariation { tag: FontTag(*b"wght"), value: FloatValue::new(650.0) },
+///     ]))
+/// );
+/// ```
+pub fn parse_style_font_variation_settings<'a>(
+    input: &'a str,
+) -> Result<StyleFontVariationSettings, CssFontSettingsParseError<'a>> {
+    if input.trim() == "normal" {
+        return Ok(StyleFontVariationSettings::default());
+    }
+
+    let variations = input
+        .split(',')
+        .map(|variation| {
+            let (tag, value) = parse_font_tag_and_value(variation)?;
+            let value = parse_float_value(value)
+                .map_err(|_| CssFontSettingsParseError::InvalidValue(value))?;
+            Ok(FontVariation { tag, value })
+        })
+        .collect::<Result<Vec<_>, CssFontSettingsParseError>>()?;
+
+    Ok(StyleFontVariationSettings(variations))
+}
+
+/// Parses a `line-clamp` attribute: the maximum number of lines, which has to be at least `1`
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_line_clamp;
+/// # use azul_css::StyleLineClamp;
+/// assert_eq!(parse_style_line_clamp("3"), Ok(StyleLineClamp(3)));
+/// ```
+pub fn parse_style_line_clamp<'a>(input: &'a str) -> Result<StyleLineClamp, InvalidValueErr<'a>> {
+    let input = input.trim();
+    match input.parse::<usize>() {
+        Ok(lines) if lines > 0 => Ok(StyleLineClamp(lines)),
+        _ => Err(InvalidValueErr(input)),
+    }
+}
+
+/// Parses a `text-decoration-line` attribute: any combination of
+/// `underline`, `overline` and `line-through`, separated by whitespace
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate azul_css;
+/// # extern crate azul_css_parser;
+/// # use azul_css_parser::parse_style_text_decoration_line;
+/// # use azul_css::StyleTextDecorationLine;
+/// assert_eq!(
+///     parse_style_text_decoration_line("underline line-through"),
+///     Ok(StyleTextDecorationLine { underline: true, overline: false, line_through: true })
+/// );
+/// ```
+pub fn parse_style_text_decoration_line<'a>(
+    input: &'a str,
+) -> Result<StyleTextDecorationLine, InvalidValueErr<'a>> {
+    let mut line = StyleTextDecorationLine::default();
+
+    for component in input.split_whitespace() {
+        if !set_text_decoration_line(&mut line, component) {
+            return Err(InvalidValueErr(input));
+        }
+    }
+
+    if line == StyleTextDecorationLine::default() {
+        return Err(InvalidValueErr(input));
+    }
+
+    Ok(line)
+}
+
+/// Sets the line of a `text-decoration-line` keyword, returns false if the
+/// keyword isn't one of `underline`, `overline` or `line-through`
+fn set_text_decoration_line(line: &mut StyleTextDecorationLine, keyword: &str) -> bool {
+    match keyword {
+        "underline" => line.underline = true,
+        "overline" => line.overline = true,
+        "line-through" => line.line_through = true,
+        _ => return false,
+    }
+    true
+}
+
+/// Parses a `text-decoration-thickness` attribute: either `from-font` or a length
+pub fn parse_style_text_decoration_thickness<'a>(
+    input: &'a str,
+) -> Result<StyleTextDecorationThickness, PixelParseError<'a>> {
+    match input.trim() {
+        "from-font" => Ok(StyleTextDecorationThickness::FromFont),
+        length => Ok(StyleTextDecorationThickness::Length(
+            parse_pixel_value_no_percent(length)?,
+        )),
+    }
+}
+
+/// Represents a parsed `text-decoration` shorthand - components that aren't
+/// specified are reset to their initial values
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextDecoration {
+    pub line: StyleTextDecorationLine,
+    pub style: Option<StyleTextDecorationStyle>,
+    pub color: Option<ColorU>,
+    pub thickness: Option<StyleTextDecorationThickness>,
+}
+
+/// Parses a `text-decoration` shorthand, such as `"underline dotted red"` or
+/// `"underline overline 2px"` - the components may be specified in any order
+pub fn parse_style_text_decoration<'a>(
+    input: &'a str,
+) -> Result<StyleTextDecoration, InvalidValueErr<'a>> {
+    let mut text_decoration = StyleTextDecoration {
+        line: StyleTextDecorationLine::default(),
+        style: None,
+        color: None,
+        thickness: None,
+    };
+
+    for component in split_top_level(input, char::is_whitespace) {
+        if set_text_decoration_line(&mut text_decoration.line, component) {
+            continue;
+        }
+
+        if let Ok(style) = parse_style_text_decoration_style(component) {
+            if text_decoration.style.replace(style).is_some() {
+                return Err(InvalidValueErr(input));
+            }
+        } else if let Ok(thickness) = parse_style_text_decoration_thickness(component) {
+            if text_decoration.thickness.replace(thickness).is_some() {
+                return Err(InvalidValueErr(input));
+            }
+        } else if let Ok(color) = parse_css_color(component) {
+            if text_decoration.color.replace(color).is_some() {
+                return Err(InvalidValueErr(input));
+            }
+        } else {
+            return Err(InvalidValueErr(input));
+        }
+    }
+
+    Ok(text_decoration)
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd)]
 pub enum ParenthesisParseError<'a> {
     UnclosedBraces,
@@ -2554,7 +4066,10 @@ multi_type_parser!(
     parse_layout_display,
     LayoutDisplay,
     ["flex", Flex],
-    ["inline", Inline]
+    ["inline", Inline],
+    ["grid", Grid],
+    ["block", Block],
+    ["none", None]
 );
 
 multi_type_parser!(
@@ -2625,7 +4140,9 @@ multi_type_parser!(
     LayoutPosition,
     ["static", Static],
     ["absolute", Absolute],
-    ["relative", Relative]
+    ["relative", Relative],
+    ["fixed", Fixed],
+    ["sticky", Sticky]
 );
 
 multi_type_parser!(
@@ -2637,6 +4154,28 @@ multi_type_parser!(
     ["hidden", Hidden]
 );
 
+multi_type_parser!(
+    parse_style_font_style,
+    StyleFontStyle,
+    ["normal", Normal],
+    ["italic", Italic],
+    ["oblique", Oblique]
+);
+
+multi_type_parser!(
+    parse_style_font_stretch,
+    StyleFontStretch,
+    ["ultra-condensed", UltraCondensed],
+    ["extra-condensed", ExtraCondensed],
+    ["condensed", Condensed],
+    ["semi-condensed", SemiCondensed],
+    ["normal", Normal],
+    ["semi-expanded", SemiExpanded],
+    ["expanded", Expanded],
+    ["extra-expanded", ExtraExpanded],
+    ["ultra-expanded", UltraExpanded]
+);
+
 multi_type_parser!(
     parse_layout_text_align,
     StyleTextAlignmentHorz,
@@ -2645,6 +4184,63 @@ multi_type_parser!(
     ["right", Right]
 );
 
+multi_type_parser!(
+    parse_style_text_direction,
+    StyleTextDirection,
+    ["ltr", Ltr],
+    ["rtl", Rtl]
+);
+
+multi_type_parser!(
+    parse_style_word_break,
+    StyleWordBreak,
+    ["normal", Normal],
+    ["break-all", BreakAll],
+    ["keep-all", KeepAll]
+);
+
+multi_type_parser!(
+    parse_style_overflow_wrap,
+    StyleOverflowWrap,
+    ["normal", Normal],
+    ["break-word", BreakWord],
+    ["anywhere", Anywhere]
+);
+
+multi_type_parser!(
+    parse_style_hyphens,
+    StyleHyphens,
+    ["none", None],
+    ["manual", Manual],
+    ["auto", Auto]
+);
+
+multi_type_parser!(
+    parse_style_white_space,
+    StyleWhiteSpace,
+    ["normal", Normal],
+    ["nowrap", NoWrap],
+    ["pre", Pre],
+    ["pre-wrap", PreWrap]
+);
+
+multi_type_parser!(
+    parse_style_text_overflow,
+    StyleTextOverflow,
+    ["clip", Clip],
+    ["ellipsis", Ellipsis]
+);
+
+multi_type_parser!(
+    parse_style_text_decoration_style,
+    StyleTextDecorationStyle,
+    ["solid", Solid],
+    ["double", Double],
+    ["dotted", Dotted],
+    ["dashed", Dashed],
+    ["wavy", Wavy]
+);
+
 #[cfg(test)]
 mod css_tests {
     use super::*;
@@ -2798,128 +4394,410 @@ mod css_tests {
                     PixelValueNoPercent(PixelValue::px(5.0)),
                     PixelValueNoPercent(PixelValue::px(10.0))
                 ],
-                color: ColorU {
-                    r: 136,
-                    g: 136,
-                    b: 136,
-                    a: 255
-                },
-                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
-                spread_radius: PixelValueNoPercent(PixelValue::px(0.0)),
-                clip_mode: BoxShadowClipMode::Inset,
-            })
+                color: ColorU {
+                    r: 136,
+                    g: 136,
+                    b: 136,
+                    a: 255
+                },
+                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
+                spread_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+                clip_mode: BoxShadowClipMode::Inset,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_box_shadow_9() {
+        assert_eq!(
+            parse_style_box_shadow("5px 10px 5px 10px #888888"),
+            Ok(BoxShadowPreDisplayItem {
+                offset: [
+                    PixelValueNoPercent(PixelValue::px(5.0)),
+                    PixelValueNoPercent(PixelValue::px(10.0)),
+                ],
+                color: ColorU {
+                    r: 136,
+                    g: 136,
+                    b: 136,
+                    a: 255
+                },
+                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
+                spread_radius: PixelValueNoPercent(PixelValue::px(10.0)),
+                clip_mode: BoxShadowClipMode::Outset,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_box_shadow_10() {
+        assert_eq!(
+            parse_style_box_shadow("5px 10px 5px 10px #888888 inset"),
+            Ok(BoxShadowPreDisplayItem {
+                offset: [
+                    PixelValueNoPercent(PixelValue::px(5.0)),
+                    PixelValueNoPercent(PixelValue::px(10.0))
+                ],
+                color: ColorU {
+                    r: 136,
+                    g: 136,
+                    b: 136,
+                    a: 255
+                },
+                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
+                spread_radius: PixelValueNoPercent(PixelValue::px(10.0)),
+                clip_mode: BoxShadowClipMode::Inset,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_border_1() {
+        assert_eq!(
+            parse_style_border("5px solid red"),
+            Ok(StyleBorderSide {
+                border_width: PixelValue::px(5.0),
+                border_style: BorderStyle::Solid,
+                border_color: ColorU {
+                    r: 255,
+                    g: 0,
+                    b: 0,
+                    a: 255
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_border_2() {
+        assert_eq!(
+            parse_style_border("double"),
+            Ok(StyleBorderSide {
+                border_width: PixelValue::px(3.0),
+                border_style: BorderStyle::Double,
+                border_color: ColorU {
+                    r: 0,
+                    g: 0,
+                    b: 0,
+                    a: 255
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_border_3() {
+        assert_eq!(
+            parse_style_border("1px solid rgb(51, 153, 255)"),
+            Ok(StyleBorderSide {
+                border_width: PixelValue::px(1.0),
+                border_style: BorderStyle::Solid,
+                border_color: ColorU {
+                    r: 51,
+                    g: 153,
+                    b: 255,
+                    a: 255
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_linear_gradient_1() {
+        assert_eq!(
+            parse_style_background_content("linear-gradient(red, yellow)"),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    }
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_linear_gradient_2() {
+        assert_eq!(
+            parse_style_background_content("linear-gradient(red, lime, blue, yellow)"),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(33.333332)),
+                        color: ColorU {
+                            r: 0,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(66.666664)),
+                        color: ColorU {
+                            r: 0,
+                            g: 0,
+                            b: 255,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(99.9999)), // note: not 100%, but close enough
+                        color: ColorU {
+                            r: 255,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    }
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_linear_gradient_3() {
+        assert_eq!(
+            parse_style_background_content(
+                "repeating-linear-gradient(50deg, blue, yellow, #00FF00)"
+            ),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::Angle(50.0.into()),
+                extend_mode: ExtendMode::Repeat,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 0,
+                            g: 0,
+                            b: 255,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(50.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 0,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    }
+                ],
+            }))
         );
     }
 
     #[test]
-    fn test_parse_box_shadow_9() {
+    fn test_parse_linear_gradient_4() {
         assert_eq!(
-            parse_style_box_shadow("5px 10px 5px 10px #888888"),
-            Ok(BoxShadowPreDisplayItem {
-                offset: [
-                    PixelValueNoPercent(PixelValue::px(5.0)),
-                    PixelValueNoPercent(PixelValue::px(10.0)),
+            parse_style_background_content("linear-gradient(to bottom right, red, yellow)"),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::FromTo(
+                    DirectionCorner::TopLeft,
+                    DirectionCorner::BottomRight
+                ),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    }
                 ],
-                color: ColorU {
-                    r: 136,
-                    g: 136,
-                    b: 136,
-                    a: 255
-                },
-                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
-                spread_radius: PixelValueNoPercent(PixelValue::px(10.0)),
-                clip_mode: BoxShadowClipMode::Outset,
-            })
+            }))
         );
     }
 
     #[test]
-    fn test_parse_box_shadow_10() {
+    fn test_parse_linear_gradient_5() {
         assert_eq!(
-            parse_style_box_shadow("5px 10px 5px 10px #888888 inset"),
-            Ok(BoxShadowPreDisplayItem {
-                offset: [
-                    PixelValueNoPercent(PixelValue::px(5.0)),
-                    PixelValueNoPercent(PixelValue::px(10.0))
+            parse_style_background_content("linear-gradient(0.42rad, red, yellow)"),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::Angle(FloatValue::new(24.0642)),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    }
                 ],
-                color: ColorU {
-                    r: 136,
-                    g: 136,
-                    b: 136,
-                    a: 255
-                },
-                blur_radius: PixelValueNoPercent(PixelValue::px(5.0)),
-                spread_radius: PixelValueNoPercent(PixelValue::px(10.0)),
-                clip_mode: BoxShadowClipMode::Inset,
-            })
+            }))
         );
     }
 
     #[test]
-    fn test_parse_css_border_1() {
+    fn test_parse_linear_gradient_6() {
         assert_eq!(
-            parse_style_border("5px solid red"),
-            Ok(StyleBorderSide {
-                border_width: PixelValue::px(5.0),
-                border_style: BorderStyle::Solid,
-                border_color: ColorU {
-                    r: 255,
-                    g: 0,
-                    b: 0,
-                    a: 255
-                },
-            })
+            parse_style_background_content("linear-gradient(12.93grad, red, yellow)"),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::Angle(FloatValue::new(11.637)),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 255,
+                            b: 0,
+                            a: 255
+                        },
+                    }
+                ],
+            }))
         );
     }
 
     #[test]
-    fn test_parse_css_border_2() {
+    fn test_parse_linear_gradient_7() {
         assert_eq!(
-            parse_style_border("double"),
-            Ok(StyleBorderSide {
-                border_width: PixelValue::px(3.0),
-                border_style: BorderStyle::Double,
-                border_color: ColorU {
-                    r: 0,
-                    g: 0,
-                    b: 0,
-                    a: 255
-                },
-            })
+            parse_style_background_content(
+                "linear-gradient(to right, rgba(255,0, 0,1) 0%,rgba(0,0,0, 0) 100%)"
+            ),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::FromTo(DirectionCorner::Left, DirectionCorner::Right),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 0,
+                            g: 0,
+                            b: 0,
+                            a: 0
+                        },
+                    }
+                ],
+            }))
         );
     }
 
     #[test]
-    fn test_parse_css_border_3() {
+    fn test_parse_linear_gradient_8() {
         assert_eq!(
-            parse_style_border("1px solid rgb(51, 153, 255)"),
-            Ok(StyleBorderSide {
-                border_width: PixelValue::px(1.0),
-                border_style: BorderStyle::Solid,
-                border_color: ColorU {
-                    r: 51,
-                    g: 153,
-                    b: 255,
-                    a: 255
-                },
-            })
+            parse_style_background_content("linear-gradient(to bottom, rgb(255,0, 0),rgb(0,0,0))"),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 255,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 0,
+                            g: 0,
+                            b: 0,
+                            a: 255
+                        },
+                    }
+                ],
+            }))
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_1() {
+    fn test_parse_linear_gradient_9() {
         assert_eq!(
-            parse_style_background_content("linear-gradient(red, yellow)"),
+            parse_style_background_content("linear-gradient(10deg, rgb(10, 30, 20), yellow)"),
             Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
+                direction: Direction::Angle(FloatValue::new(10.0)),
                 extend_mode: ExtendMode::Clamp,
                 stops: vec![
                     GradientStopPre {
                         offset: Some(PercentageValue::new(0.0)),
                         color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
+                            r: 10,
+                            g: 30,
+                            b: 20,
                             a: 255
                         },
                     },
@@ -2938,9 +4816,45 @@ mod css_tests {
     }
 
     #[test]
-    fn test_parse_linear_gradient_2() {
+    fn test_parse_linear_gradient_10() {
+        assert_eq!(
+            parse_style_background_content(
+                "linear-gradient(50deg, rgba(10, 30, 20, 0.93), hsla(40deg, 80%, 30%, 0.1))"
+            ),
+            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
+                direction: Direction::Angle(FloatValue::new(50.0)),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(0.0)),
+                        color: ColorU {
+                            r: 10,
+                            g: 30,
+                            b: 20,
+                            a: 238
+                        },
+                    },
+                    GradientStopPre {
+                        offset: Some(PercentageValue::new(100.0)),
+                        color: ColorU {
+                            r: 138,
+                            g: 97,
+                            b: 15,
+                            a: 25
+                        },
+                    }
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_linear_gradient_11() {
+        // wacky whitespace on purpose
         assert_eq!(
-            parse_style_background_content("linear-gradient(red, lime, blue, yellow)"),
+            parse_style_background_content(
+                "linear-gradient(to bottom,rgb(255,0, 0)0%, rgb( 0 , 255 , 0 ) 10% ,blue   100%  )"
+            ),
             Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
                 direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
                 extend_mode: ExtendMode::Clamp,
@@ -2955,7 +4869,7 @@ mod css_tests {
                         },
                     },
                     GradientStopPre {
-                        offset: Some(PercentageValue::new(33.333332)),
+                        offset: Some(PercentageValue::new(10.0)),
                         color: ColorU {
                             r: 0,
                             g: 255,
@@ -2964,22 +4878,13 @@ mod css_tests {
                         },
                     },
                     GradientStopPre {
-                        offset: Some(PercentageValue::new(66.666664)),
+                        offset: Some(PercentageValue::new(100.0)),
                         color: ColorU {
                             r: 0,
                             g: 0,
                             b: 255,
                             a: 255
                         },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(99.9999)), // note: not 100%, but close enough
-                        color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
                     }
                 ],
             }))
@@ -2987,37 +4892,35 @@ mod css_tests {
     }
 
     #[test]
-    fn test_parse_linear_gradient_3() {
+    fn test_parse_radial_gradient_1() {
         assert_eq!(
-            parse_style_background_content(
-                "repeating-linear-gradient(50deg, blue, yellow, #00FF00)"
-            ),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::Angle(50.0.into()),
-                extend_mode: ExtendMode::Repeat,
+            parse_style_background_content("radial-gradient(circle, lime, blue, yellow)"),
+            Ok(StyleBackgroundContent::RadialGradient(RadialGradient {
+                shape: Shape::Circle,
+                extend_mode: ExtendMode::Clamp,
                 stops: vec![
                     GradientStopPre {
                         offset: Some(PercentageValue::new(0.0)),
                         color: ColorU {
                             r: 0,
-                            g: 0,
-                            b: 255,
+                            g: 255,
+                            b: 0,
                             a: 255
                         },
                     },
                     GradientStopPre {
                         offset: Some(PercentageValue::new(50.0)),
                         color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
+                            r: 0,
+                            g: 0,
+                            b: 255,
                             a: 255
                         },
                     },
                     GradientStopPre {
                         offset: Some(PercentageValue::new(100.0)),
                         color: ColorU {
-                            r: 0,
+                            r: 255,
                             g: 255,
                             b: 0,
                             a: 255
@@ -3028,922 +4931,1334 @@ mod css_tests {
         );
     }
 
+    // This test currently fails, but it's not that important to fix right now
+    /*
+    #[test]
+    fn test_parse_radial_gradient_2() {
+        assert_eq!(parse_style_background_content("repeating-radial-gradient(circle, red 10%, blue 50%, lime, yellow)"),
+            Ok(ParsedGradient::RadialGradient(RadialGradient {
+                shape: Shape::Circle,
+                extend_mode: ExtendMode::Repeat,
+                stops: vec![
+                GradientStopPre {
+                    offset: Some(0.1),
+                    color: ColorF { r: 1.0, g: 0.0, b: 0.0, a: 1.0 },
+                },
+                GradientStopPre {
+                    offset: Some(0.5),
+                    color: ColorF { r: 0.0, g: 0.0, b: 1.0, a: 1.0 },
+                },
+                GradientStopPre {
+                    offset: Some(0.75),
+                    color: ColorF { r: 0.0, g: 1.0, b: 0.0, a: 1.0 },
+                },
+                GradientStopPre {
+                    offset: Some(1.0),
+                    color: ColorF { r: 1.0, g: 1.0, b: 0.0, a: 1.0 },
+                }],
+        })));
+    }
+    */
+
+    #[test]
+    fn test_parse_css_color_1() {
+        assert_eq!(
+            parse_css_color("#F0F8FF"),
+            Ok(ColorU {
+                r: 240,
+                g: 248,
+                b: 255,
+                a: 255
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_2() {
+        assert_eq!(
+            parse_css_color("#F0F8FF00"),
+            Ok(ColorU {
+                r: 240,
+                g: 248,
+                b: 255,
+                a: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_3() {
+        assert_eq!(
+            parse_css_color("#EEE"),
+            Ok(ColorU {
+                r: 238,
+                g: 238,
+                b: 238,
+                a: 255
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_4() {
+        assert_eq!(
+            parse_css_color("rgb(192, 14, 12)"),
+            Ok(ColorU {
+                r: 192,
+                g: 14,
+                b: 12,
+                a: 255
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_5() {
+        assert_eq!(
+            parse_css_color("rgb(283, 8, 105)"),
+            Err(CssColorParseError::IntValueParseErr(
+                "283".parse::<u8>().err().unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_6() {
+        assert_eq!(
+            parse_css_color("rgba(192, 14, 12, 80)"),
+            Err(CssColorParseError::FloatValueOutOfRange(80.0))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_7() {
+        assert_eq!(
+            parse_css_color("rgba( 0,127,     255   , 0.25  )"),
+            Ok(ColorU {
+                r: 0,
+                g: 127,
+                b: 255,
+                a: 64
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_8() {
+        assert_eq!(
+            parse_css_color("rgba( 1 ,2,3, 1.0)"),
+            Ok(ColorU {
+                r: 1,
+                g: 2,
+                b: 3,
+                a: 255
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_9() {
+        assert_eq!(
+            parse_css_color("rgb("),
+            Err(CssColorParseError::UnclosedColor("rgb("))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_10() {
+        assert_eq!(
+            parse_css_color("rgba("),
+            Err(CssColorParseError::UnclosedColor("rgba("))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_11() {
+        assert_eq!(
+            parse_css_color("rgba(123, 36, 92, 0.375"),
+            Err(CssColorParseError::UnclosedColor("rgba(123, 36, 92, 0.375"))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_12() {
+        assert_eq!(
+            parse_css_color("rgb()"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Red
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_13() {
+        assert_eq!(
+            parse_css_color("rgb(10)"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Green
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_14() {
+        assert_eq!(
+            parse_css_color("rgb(20, 30)"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Blue
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_15() {
+        assert_eq!(
+            parse_css_color("rgb(30, 40,)"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Blue
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_16() {
+        assert_eq!(
+            parse_css_color("rgba(40, 50, 60)"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Alpha
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_17() {
+        assert_eq!(
+            parse_css_color("rgba(50, 60, 70, )"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Alpha
+            ))
+        );
+    }
+
     #[test]
-    fn test_parse_linear_gradient_4() {
+    fn test_parse_css_color_18() {
         assert_eq!(
-            parse_style_background_content("linear-gradient(to bottom right, red, yellow)"),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::FromTo(
-                    DirectionCorner::TopLeft,
-                    DirectionCorner::BottomRight
-                ),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsl(0deg, 100%, 100%)"),
+            Ok(ColorU {
+                r: 255,
+                g: 255,
+                b: 255,
+                a: 255
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_5() {
+    fn test_parse_css_color_19() {
         assert_eq!(
-            parse_style_background_content("linear-gradient(0.42rad, red, yellow)"),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::Angle(FloatValue::new(24.0642)),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsl(0deg, 100%, 50%)"),
+            Ok(ColorU {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_6() {
+    fn test_parse_css_color_20() {
         assert_eq!(
-            parse_style_background_content("linear-gradient(12.93grad, red, yellow)"),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::Angle(FloatValue::new(11.637)),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsl(170deg, 50%, 75%)"),
+            Ok(ColorU {
+                r: 160,
+                g: 224,
+                b: 213,
+                a: 255
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_7() {
+    fn test_parse_css_color_21() {
         assert_eq!(
-            parse_style_background_content(
-                "linear-gradient(to right, rgba(255,0, 0,1) 0%,rgba(0,0,0, 0) 100%)"
-            ),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::FromTo(DirectionCorner::Left, DirectionCorner::Right),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 0,
-                            g: 0,
-                            b: 0,
-                            a: 0
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsla(190deg, 50%, 75%, 1.0)"),
+            Ok(ColorU {
+                r: 160,
+                g: 213,
+                b: 224,
+                a: 255
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_8() {
+    fn test_parse_css_color_22() {
         assert_eq!(
-            parse_style_background_content("linear-gradient(to bottom, rgb(255,0, 0),rgb(0,0,0))"),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 0,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsla(120deg, 0%, 25%, 0.25)"),
+            Ok(ColorU {
+                r: 64,
+                g: 64,
+                b: 64,
+                a: 64
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_9() {
+    fn test_parse_css_color_23() {
         assert_eq!(
-            parse_style_background_content("linear-gradient(10deg, rgb(10, 30, 20), yellow)"),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::Angle(FloatValue::new(10.0)),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 10,
-                            g: 30,
-                            b: 20,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsla(120deg, 0%, 0%, 0.5)"),
+            Ok(ColorU {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 128
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_10() {
+    fn test_parse_css_color_24() {
         assert_eq!(
-            parse_style_background_content(
-                "linear-gradient(50deg, rgba(10, 30, 20, 0.93), hsla(40deg, 80%, 30%, 0.1))"
-            ),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::Angle(FloatValue::new(50.0)),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 10,
-                            g: 30,
-                            b: 20,
-                            a: 238
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 138,
-                            g: 97,
-                            b: 15,
-                            a: 25
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsla(60.9deg, 80.3%, 40%, 0.5)"),
+            Ok(ColorU {
+                r: 182,
+                g: 184,
+                b: 20,
+                a: 128
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_css_color_25() {
+        assert_eq!(
+            parse_css_color("hsla(60.9rad, 80.3%, 40%, 0.5)"),
+            Ok(ColorU {
+                r: 45,
+                g: 20,
+                b: 184,
+                a: 128
+            })
         );
     }
 
     #[test]
-    fn test_parse_linear_gradient_11() {
-        // wacky whitespace on purpose
+    fn test_parse_css_color_26() {
         assert_eq!(
-            parse_style_background_content(
-                "linear-gradient(to bottom,rgb(255,0, 0)0%, rgb( 0 , 255 , 0 ) 10% ,blue   100%  )"
-            ),
-            Ok(StyleBackgroundContent::LinearGradient(LinearGradient {
-                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 0,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(10.0)),
-                        color: ColorU {
-                            r: 0,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 0,
-                            g: 0,
-                            b: 255,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_css_color("hsla(60.9grad, 80.3%, 40%, 0.5)"),
+            Ok(ColorU {
+                r: 184,
+                g: 170,
+                b: 20,
+                a: 128
+            })
         );
     }
 
     #[test]
-    fn test_parse_radial_gradient_1() {
+    fn test_parse_direction() {
+        let first_input = "60.9grad";
+        let e = FloatValue::new(
+            first_input
+                .split("grad")
+                .next()
+                .unwrap()
+                .parse::<f32>()
+                .expect("Parseable float")
+                / 400.0
+                * 360.0,
+        );
+        assert_eq!(e, FloatValue::new(60.9 / 400.0 * 360.0));
         assert_eq!(
-            parse_style_background_content("radial-gradient(circle, lime, blue, yellow)"),
-            Ok(StyleBackgroundContent::RadialGradient(RadialGradient {
-                shape: Shape::Circle,
-                extend_mode: ExtendMode::Clamp,
-                stops: vec![
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(0.0)),
-                        color: ColorU {
-                            r: 0,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(50.0)),
-                        color: ColorU {
-                            r: 0,
-                            g: 0,
-                            b: 255,
-                            a: 255
-                        },
-                    },
-                    GradientStopPre {
-                        offset: Some(PercentageValue::new(100.0)),
-                        color: ColorU {
-                            r: 255,
-                            g: 255,
-                            b: 0,
-                            a: 255
-                        },
-                    }
-                ],
-            }))
+            parse_direction("60.9grad"),
+            Ok(Direction::Angle(FloatValue::new(60.9 / 400.0 * 360.0)))
         );
     }
 
-    // This test currently fails, but it's not that important to fix right now
-    /*
     #[test]
-    fn test_parse_radial_gradient_2() {
-        assert_eq!(parse_style_background_content("repeating-radial-gradient(circle, red 10%, blue 50%, lime, yellow)"),
-            Ok(ParsedGradient::RadialGradient(RadialGradient {
-                shape: Shape::Circle,
-                extend_mode: ExtendMode::Repeat,
-                stops: vec![
-                GradientStopPre {
-                    offset: Some(0.1),
-                    color: ColorF { r: 1.0, g: 0.0, b: 0.0, a: 1.0 },
-                },
-                GradientStopPre {
-                    offset: Some(0.5),
-                    color: ColorF { r: 0.0, g: 0.0, b: 1.0, a: 1.0 },
-                },
-                GradientStopPre {
-                    offset: Some(0.75),
-                    color: ColorF { r: 0.0, g: 1.0, b: 0.0, a: 1.0 },
-                },
-                GradientStopPre {
-                    offset: Some(1.0),
-                    color: ColorF { r: 1.0, g: 1.0, b: 0.0, a: 1.0 },
-                }],
-        })));
+    fn test_parse_float_value() {
+        assert_eq!(parse_float_value("60.9"), Ok(FloatValue::new(60.9)));
     }
-    */
 
     #[test]
-    fn test_parse_css_color_1() {
+    fn test_parse_css_color_27() {
         assert_eq!(
-            parse_css_color("#F0F8FF"),
+            parse_css_color("hsla(240, 0%, 0%, 0.5)"),
             Ok(ColorU {
-                r: 240,
-                g: 248,
-                b: 255,
-                a: 255
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 128
             })
         );
     }
 
     #[test]
-    fn test_parse_css_color_2() {
+    fn test_parse_css_color_28() {
         assert_eq!(
-            parse_css_color("#F0F8FF00"),
-            Ok(ColorU {
-                r: 240,
-                g: 248,
-                b: 255,
-                a: 0
-            })
+            parse_css_color("hsla(240deg, 0, 0%, 0.5)"),
+            Err(CssColorParseError::InvalidPercentage(
+                PercentageParseError::NoPercentSign
+            ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_3() {
+    fn test_parse_css_color_29() {
         assert_eq!(
-            parse_css_color("#EEE"),
-            Ok(ColorU {
-                r: 238,
-                g: 238,
-                b: 238,
-                a: 255
-            })
+            parse_css_color("hsla(240deg, 0%, 0, 0.5)"),
+            Err(CssColorParseError::InvalidPercentage(
+                PercentageParseError::NoPercentSign
+            ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_4() {
+    fn test_parse_css_color_30() {
         assert_eq!(
-            parse_css_color("rgb(192, 14, 12)"),
-            Ok(ColorU {
-                r: 192,
-                g: 14,
-                b: 12,
-                a: 255
-            })
+            parse_css_color("hsla(240deg, 0%, 0%, )"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Alpha
+            ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_5() {
+    fn test_parse_css_color_31() {
         assert_eq!(
-            parse_css_color("rgb(283, 8, 105)"),
-            Err(CssColorParseError::IntValueParseErr(
-                "283".parse::<u8>().err().unwrap()
+            parse_css_color("hsl(, 0%, 0%, )"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Hue
             ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_6() {
+    fn test_parse_css_color_32() {
         assert_eq!(
-            parse_css_color("rgba(192, 14, 12, 80)"),
-            Err(CssColorParseError::FloatValueOutOfRange(80.0))
+            parse_css_color("hsl(240deg ,  )"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Saturation
+            ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_7() {
+    fn test_parse_css_color_33() {
         assert_eq!(
-            parse_css_color("rgba( 0,127,     255   , 0.25  )"),
-            Ok(ColorU {
-                r: 0,
-                g: 127,
-                b: 255,
-                a: 64
-            })
+            parse_css_color("hsl(240deg, 0%,  )"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Lightness
+            ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_8() {
+    fn test_parse_css_color_34() {
         assert_eq!(
-            parse_css_color("rgba( 1 ,2,3, 1.0)"),
-            Ok(ColorU {
-                r: 1,
-                g: 2,
-                b: 3,
-                a: 255
-            })
+            parse_css_color("hsl(240deg, 0%, 0%,  )"),
+            Err(CssColorParseError::ExtraArguments(""))
         );
     }
 
     #[test]
-    fn test_parse_css_color_9() {
+    fn test_parse_css_color_35() {
         assert_eq!(
-            parse_css_color("rgb("),
-            Err(CssColorParseError::UnclosedColor("rgb("))
+            parse_css_color("hsla(240deg, 0%, 0%  )"),
+            Err(CssColorParseError::MissingColorComponent(
+                CssColorComponent::Alpha
+            ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_10() {
+    fn test_parse_css_color_36() {
         assert_eq!(
-            parse_css_color("rgba("),
-            Err(CssColorParseError::UnclosedColor("rgba("))
+            parse_css_color("rgb(255,0, 0)"),
+            Ok(ColorU {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 255
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_11() {
+    fn test_parse_pixel_value_1() {
+        assert_eq!(parse_pixel_value("15px"), Ok(PixelValue::px(15.0)));
+    }
+
+    #[test]
+    fn test_parse_pixel_value_2() {
+        assert_eq!(parse_pixel_value("1.2em"), Ok(PixelValue::em(1.2)));
+    }
+
+    #[test]
+    fn test_parse_pixel_value_3() {
+        assert_eq!(parse_pixel_value("11pt"), Ok(PixelValue::pt(11.0)));
+    }
+
+    #[test]
+    fn test_parse_pixel_value_4() {
         assert_eq!(
-            parse_css_color("rgba(123, 36, 92, 0.375"),
-            Err(CssColorParseError::UnclosedColor("rgba(123, 36, 92, 0.375"))
+            parse_pixel_value("aslkfdjasdflk"),
+            Err(PixelParseError::NoValueGiven("aslkfdjasdflk"))
         );
     }
 
     #[test]
-    fn test_parse_css_color_12() {
+    fn test_parse_pixel_value_relative_units() {
+        assert_eq!(parse_pixel_value("2rem"), Ok(PixelValue::rem(2.0)));
+        assert_eq!(parse_pixel_value("50vw"), Ok(PixelValue::vw(50.0)));
+        assert_eq!(parse_pixel_value("10vh"), Ok(PixelValue::vh(10.0)));
+        assert_eq!(parse_pixel_value("5vmin"), Ok(PixelValue::vmin(5.0)));
+        assert_eq!(parse_pixel_value("5vmax"), Ok(PixelValue::vmax(5.0)));
         assert_eq!(
-            parse_css_color("rgb()"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Red
+            parse_pixel_value_no_percent("50%"),
+            Err(PixelParseError::UnsupportedMetric(
+                50.0,
+                "%".to_string(),
+                "50%"
             ))
         );
     }
 
     #[test]
-    fn test_parse_css_color_13() {
+    fn test_parse_pixel_value_calc() {
         assert_eq!(
-            parse_css_color("rgb(10)"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Green
-            ))
+            parse_pixel_value("calc(100% - 32px)"),
+            Ok(PixelValue::calc(CalcValue {
+                percent: FloatValue::new(100.0),
+                px: FloatValue::new(-32.0),
+                ..Default::default()
+            }))
         );
-    }
-
-    #[test]
-    fn test_parse_css_color_14() {
+        // terms with the same unit are summed up, numbers are multiplied into the lengths
         assert_eq!(
-            parse_css_color("rgb(20, 30)"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Blue
-            ))
+            parse_pixel_value("calc(2 * (1em + 4px) - 1em / 2 + calc(10vw - -2px))"),
+            Ok(PixelValue::calc(CalcValue {
+                em: FloatValue::new(1.5),
+                px: FloatValue::new(10.0),
+                vw: FloatValue::new(10.0),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            parse_pixel_value("calc(100% - 32px)")
+                .unwrap()
+                .to_pixels(200.0),
+            168.0
         );
-    }
 
-    #[test]
-    fn test_parse_css_color_15() {
+        let invalid = [
+            "calc(100%-32px)",
+            "calc(10px * 2px)",
+            "calc(10px + 2)",
+            "calc(10px / 0)",
+            "calc(5 / 10px)",
+            "calc(10px",
+            "calc(10px))",
+            "calc()",
+        ];
+        for input in invalid.iter() {
+            assert_eq!(
+                parse_pixel_value(input),
+                Err(PixelParseError::InvalidCalcExpression(input))
+            );
+        }
+
         assert_eq!(
-            parse_css_color("rgb(30, 40,)"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Blue
-            ))
+            parse_pixel_value_no_percent("calc(100% - 32px)"),
+            Err(PixelParseError::InvalidCalcExpression("calc(100% - 32px)"))
         );
     }
 
     #[test]
-    fn test_parse_css_color_16() {
+    fn test_parse_style_border_radius_1() {
         assert_eq!(
-            parse_css_color("rgba(40, 50, 60)"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Alpha
-            ))
+            parse_style_border_radius("15px"),
+            Ok(StyleBorderRadius {
+                top_left: PixelValue::px(15.0),
+                top_right: PixelValue::px(15.0),
+                bottom_left: PixelValue::px(15.0),
+                bottom_right: PixelValue::px(15.0),
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_17() {
+    fn test_parse_style_border_radius_2() {
         assert_eq!(
-            parse_css_color("rgba(50, 60, 70, )"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Alpha
-            ))
+            parse_style_border_radius("15px 50px"),
+            Ok(StyleBorderRadius {
+                top_left: PixelValue::px(15.0),
+                bottom_right: PixelValue::px(15.0),
+                top_right: PixelValue::px(50.0),
+                bottom_left: PixelValue::px(50.0),
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_18() {
+    fn test_parse_style_border_radius_3() {
         assert_eq!(
-            parse_css_color("hsl(0deg, 100%, 100%)"),
-            Ok(ColorU {
-                r: 255,
-                g: 255,
-                b: 255,
-                a: 255
+            parse_style_border_radius("15px 50px 30px"),
+            Ok(StyleBorderRadius {
+                top_left: PixelValue::px(15.0),
+                bottom_right: PixelValue::px(30.0),
+                top_right: PixelValue::px(50.0),
+                bottom_left: PixelValue::px(50.0),
             })
         );
     }
 
     #[test]
-    fn test_parse_css_color_19() {
+    fn test_parse_style_border_radius_4() {
         assert_eq!(
-            parse_css_color("hsl(0deg, 100%, 50%)"),
-            Ok(ColorU {
-                r: 255,
-                g: 0,
-                b: 0,
-                a: 255
+            parse_style_border_radius("15px 50px 30px 5px"),
+            Ok(StyleBorderRadius {
+                top_left: PixelValue::px(15.0),
+                bottom_right: PixelValue::px(30.0),
+                top_right: PixelValue::px(50.0),
+                bottom_left: PixelValue::px(5.0),
             })
         );
     }
 
     #[test]
-    fn test_parse_css_color_20() {
+    fn test_parse_style_font_family_1() {
         assert_eq!(
-            parse_css_color("hsl(170deg, 50%, 75%)"),
-            Ok(ColorU {
-                r: 160,
-                g: 224,
-                b: 213,
-                a: 255
+            parse_style_font_family("\"Webly Sleeky UI\", monospace"),
+            Ok(StyleFontFamily {
+                fonts: vec![FontId("Webly Sleeky UI".into()), FontId("monospace".into()),]
             })
         );
     }
 
     #[test]
-    fn test_parse_css_color_21() {
+    fn test_parse_style_font_family_2() {
         assert_eq!(
-            parse_css_color("hsla(190deg, 50%, 75%, 1.0)"),
-            Ok(ColorU {
-                r: 160,
-                g: 213,
-                b: 224,
-                a: 255
+            parse_style_font_family("'Webly Sleeky UI'"),
+            Ok(StyleFontFamily {
+                fonts: vec![FontId("Webly Sleeky UI".into()),]
             })
         );
     }
 
     #[test]
-    fn test_parse_css_color_22() {
+    fn test_parse_style_font_weight_style_stretch() {
         assert_eq!(
-            parse_css_color("hsla(120deg, 0%, 25%, 0.25)"),
-            Ok(ColorU {
-                r: 64,
-                g: 64,
-                b: 64,
-                a: 64
-            })
+            parse_style_font_weight("normal"),
+            Ok(StyleFontWeight::NORMAL)
+        );
+        assert_eq!(parse_style_font_weight("bold"), Ok(StyleFontWeight::BOLD));
+        assert_eq!(parse_style_font_weight("100"), Ok(StyleFontWeight(100)));
+        assert_eq!(parse_style_font_weight("0"), Err(InvalidValueErr("0")));
+        assert_eq!(
+            parse_style_font_weight("bolder"),
+            Err(InvalidValueErr("bolder"))
+        );
+        assert_eq!(parse_style_font_style("italic"), Ok(StyleFontStyle::Italic));
+        assert_eq!(
+            parse_style_font_stretch("semi-condensed"),
+            Ok(StyleFontStretch::SemiCondensed)
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::FontWeight, "inherit"),
+            Ok(CssProperty::FontWeight(CssPropertyValue::Inherit))
         );
     }
 
     #[test]
-    fn test_parse_css_color_23() {
+    fn test_parse_style_text_direction() {
         assert_eq!(
-            parse_css_color("hsla(120deg, 0%, 0%, 0.5)"),
-            Ok(ColorU {
-                r: 0,
-                g: 0,
-                b: 0,
-                a: 128
-            })
+            parse_style_text_direction("rtl"),
+            Ok(StyleTextDirection::Rtl)
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::TextDirection, "ltr"),
+            Ok(CssProperty::TextDirection(CssPropertyValue::Exact(
+                StyleTextDirection::Ltr
+            )))
+        );
+        assert_eq!(
+            parse_style_text_direction("auto"),
+            Err(InvalidValueErr("auto"))
         );
     }
 
     #[test]
-    fn test_parse_css_color_24() {
+    fn test_parse_style_line_breaking() {
         assert_eq!(
-            parse_css_color("hsla(60.9deg, 80.3%, 40%, 0.5)"),
-            Ok(ColorU {
-                r: 182,
-                g: 184,
-                b: 20,
-                a: 128
-            })
+            parse_style_word_break("break-all"),
+            Ok(StyleWordBreak::BreakAll)
+        );
+        assert_eq!(
+            parse_style_overflow_wrap("break-word"),
+            Ok(StyleOverflowWrap::BreakWord)
         );
+        assert_eq!(parse_style_hyphens("auto"), Ok(StyleHyphens::Auto));
+        assert_eq!(
+            parse_css_property(CssPropertyType::Hyphens, "manual"),
+            Ok(CssProperty::Hyphens(CssPropertyValue::Exact(
+                StyleHyphens::Manual
+            )))
+        );
+        assert_eq!(parse_style_hyphens("all"), Err(InvalidValueErr("all")));
     }
 
     #[test]
-    fn test_parse_css_color_25() {
+    fn test_parse_style_text_truncation() {
         assert_eq!(
-            parse_css_color("hsla(60.9rad, 80.3%, 40%, 0.5)"),
-            Ok(ColorU {
-                r: 45,
-                g: 20,
-                b: 184,
-                a: 128
-            })
+            parse_style_white_space("pre-wrap"),
+            Ok(StyleWhiteSpace::PreWrap)
+        );
+        assert_eq!(
+            parse_style_text_overflow("ellipsis"),
+            Ok(StyleTextOverflow::Ellipsis)
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::LineClamp, "2"),
+            Ok(CssProperty::LineClamp(CssPropertyValue::Exact(
+                StyleLineClamp(2)
+            )))
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::LineClamp, "none"),
+            Ok(CssProperty::LineClamp(CssPropertyValue::None))
         );
+        assert_eq!(parse_style_line_clamp("0"), Err(InvalidValueErr("0")));
     }
 
     #[test]
-    fn test_parse_css_color_26() {
+    fn test_parse_style_text_decoration() {
         assert_eq!(
-            parse_css_color("hsla(60.9grad, 80.3%, 40%, 0.5)"),
-            Ok(ColorU {
-                r: 184,
-                g: 170,
-                b: 20,
-                a: 128
+            parse_style_text_decoration("dotted underline rgb(255, 0, 0) 2px"),
+            Ok(StyleTextDecoration {
+                line: StyleTextDecorationLine {
+                    underline: true,
+                    overline: false,
+                    line_through: false,
+                },
+                style: Some(StyleTextDecorationStyle::Dotted),
+                color: Some(ColorU {
+                    r: 255,
+                    g: 0,
+                    b: 0,
+                    a: 255
+                }),
+                thickness: Some(StyleTextDecorationThickness::Length(PixelValueNoPercent(
+                    PixelValue::px(2.0)
+                ))),
             })
         );
+        assert_eq!(
+            parse_combined_css_property(CombinedCssPropertyType::TextDecoration, "line-through"),
+            Ok(vec![
+                CssProperty::TextDecorationLine(CssPropertyValue::Exact(StyleTextDecorationLine {
+                    underline: false,
+                    overline: false,
+                    line_through: true,
+                })),
+                CssProperty::TextDecorationStyle(CssPropertyValue::Initial),
+                CssProperty::TextDecorationColor(CssPropertyValue::Initial),
+                CssProperty::TextDecorationThickness(CssPropertyValue::Initial),
+            ])
+        );
+        assert_eq!(
+            parse_style_text_decoration("underline solid wavy"),
+            Err(InvalidValueErr("underline solid wavy"))
+        );
+        assert_eq!(
+            parse_style_text_decoration_line("underline blink"),
+            Err(InvalidValueErr("underline blink"))
+        );
+        assert_eq!(
+            parse_style_text_decoration_thickness("from-font"),
+            Ok(StyleTextDecorationThickness::FromFont)
+        );
     }
 
     #[test]
-    fn test_parse_direction() {
-        let first_input = "60.9grad";
-        let e = FloatValue::new(
-            first_input
-                .split("grad")
-                .next()
-                .unwrap()
-                .parse::<f32>()
-                .expect("Parseable float")
-                / 400.0
-                * 360.0,
+    fn test_parse_style_text_shadow() {
+        assert_eq!(
+            parse_style_text_shadow("1px 2px, red 0px 0px 3px"),
+            Ok(StyleTextShadow(vec![
+                StyleDropShadow {
+                    offset: [
+                        PixelValueNoPercent(PixelValue::px(1.0)),
+                        PixelValueNoPercent(PixelValue::px(2.0)),
+                    ],
+                    color: ColorU::BLACK,
+                    blur_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+                },
+                StyleDropShadow {
+                    offset: [
+                        PixelValueNoPercent(PixelValue::px(0.0)),
+                        PixelValueNoPercent(PixelValue::px(0.0)),
+                    ],
+                    color: ColorU {
+                        r: 255,
+                        g: 0,
+                        b: 0,
+                        a: 255
+                    },
+                    blur_radius: PixelValueNoPercent(PixelValue::px(3.0)),
+                },
+            ]))
         );
-        assert_eq!(e, FloatValue::new(60.9 / 400.0 * 360.0));
         assert_eq!(
-            parse_direction("60.9grad"),
-            Ok(Direction::Angle(FloatValue::new(60.9 / 400.0 * 360.0)))
+            parse_style_text_shadow("1px"),
+            Err(CssShadowParseError::InvalidSingleStatement("1px"))
         );
     }
 
     #[test]
-    fn test_parse_float_value() {
-        assert_eq!(parse_float_value("60.9"), Ok(FloatValue::new(60.9)));
-    }
+    fn test_parse_style_font_features() {
+        assert_eq!(
+            parse_style_font_feature_settings("'smcp', \"liga\" 0, \"salt\" 2"),
+            Ok(StyleFontFeatureSettings(vec![
+                FontFeature {
+                    tag: FontTag(*b"smcp"),
+                    value: 1
+                },
+                FontFeature {
+                    tag: FontTag(*b"liga"),
+                    value: 0
+                },
+                FontFeature {
+                    tag: FontTag(*b"salt"),
+                    value: 2
+                },
+            ]))
+        );
+        assert_eq!(
+            parse_style_font_feature_settings("normal"),
+            Ok(StyleFontFeatureSettings::default())
+        );
+        assert_eq!(
+            parse_style_font_feature_settings("\"liga"),
+            Err(CssFontSettingsParseError::UnclosedQuotes("\"liga"))
+        );
+        assert_eq!(
+            parse_style_font_feature_settings("\"ligatures\""),
+            Err(CssFontSettingsParseError::InvalidTag("ligatures"))
+        );
+        assert_eq!(
+            parse_style_font_feature_settings("\"liga\" yes"),
+            Err(CssFontSettingsParseError::InvalidValue("yes"))
+        );
 
-    #[test]
-    fn test_parse_css_color_27() {
+        let numeric = parse_style_font_variant_numeric("slashed-zero tabular-nums oldstyle-nums");
         assert_eq!(
-            parse_css_color("hsla(240, 0%, 0%, 0.5)"),
-            Ok(ColorU {
-                r: 0,
-                g: 0,
-                b: 0,
-                a: 128
-            })
+            numeric.map(|n| n.get_font_features()),
+            Ok(vec![
+                FontFeature::enabled(*b"onum"),
+                FontFeature::enabled(*b"tnum"),
+                FontFeature::enabled(*b"zero"),
+            ])
+        );
+        assert_eq!(
+            parse_style_font_variant_numeric("tabular-nums proportional-nums"),
+            Err(InvalidValueErr("tabular-nums proportional-nums"))
+        );
+
+        assert_eq!(
+            parse_css_property(
+                CssPropertyType::FontVariationSettings,
+                "\"wght\" 650, 'wdth' 87.5"
+            ),
+            Ok(CssProperty::FontVariationSettings(CssPropertyValue::Exact(
+                StyleFontVariationSettings(vec![
+                    FontVariation {
+                        tag: FontTag(*b"wght"),
+                        value: FloatValue::new(650.0)
+                    },
+                    FontVariation {
+                        tag: FontTag(*b"wdth"),
+                        value: FloatValue::new(87.5)
+                    },
+                ])
+            )))
+        );
+        assert_eq!(
+            parse_style_font_variation_settings("\"wght\""),
+            Err(CssFontSettingsParseError::InvalidValue(""))
         );
     }
 
     #[test]
-    fn test_parse_css_color_28() {
+    fn test_parse_background_image() {
         assert_eq!(
-            parse_css_color("hsla(240deg, 0, 0%, 0.5)"),
-            Err(CssColorParseError::InvalidPercentage(
-                PercentageParseError::NoPercentSign
-            ))
+            parse_style_background_content("image(\"Cat 01\")"),
+            Ok(StyleBackgroundContent::Image(CssImageId(String::from(
+                "Cat 01"
+            ))))
         );
     }
 
     #[test]
-    fn test_parse_css_color_29() {
+    fn test_parse_padding_1() {
         assert_eq!(
-            parse_css_color("hsla(240deg, 0%, 0, 0.5)"),
-            Err(CssColorParseError::InvalidPercentage(
-                PercentageParseError::NoPercentSign
-            ))
+            parse_layout_padding("10px"),
+            Ok(LayoutPadding {
+                top: PixelValue::px(10.0),
+                right: PixelValue::px(10.0),
+                bottom: PixelValue::px(10.0),
+                left: PixelValue::px(10.0),
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_30() {
+    fn test_parse_padding_2() {
         assert_eq!(
-            parse_css_color("hsla(240deg, 0%, 0%, )"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Alpha
-            ))
+            parse_layout_padding("25px 50px"),
+            Ok(LayoutPadding {
+                top: PixelValue::px(25.0),
+                right: PixelValue::px(50.0),
+                bottom: PixelValue::px(25.0),
+                left: PixelValue::px(50.0),
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_31() {
+    fn test_parse_padding_3() {
         assert_eq!(
-            parse_css_color("hsl(, 0%, 0%, )"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Hue
-            ))
+            parse_layout_padding("25px 50px 75px"),
+            Ok(LayoutPadding {
+                top: PixelValue::px(25.0),
+                right: PixelValue::px(50.0),
+                left: PixelValue::px(50.0),
+                bottom: PixelValue::px(75.0),
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_32() {
+    fn test_parse_padding_4() {
         assert_eq!(
-            parse_css_color("hsl(240deg ,  )"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Saturation
-            ))
+            parse_layout_padding("25px 50px 75px 100px"),
+            Ok(LayoutPadding {
+                top: PixelValue::px(25.0),
+                right: PixelValue::px(50.0),
+                bottom: PixelValue::px(75.0),
+                left: PixelValue::px(100.0),
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_33() {
+    fn test_parse_grid_template_1() {
         assert_eq!(
-            parse_css_color("hsl(240deg, 0%,  )"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Lightness
-            ))
+            parse_grid_template("repeat(2, 100px minmax(50px, 1fr)) auto"),
+            Ok(GridTemplate {
+                tracks: vec![
+                    GridTrackSize::px(100.0),
+                    GridTrackSize::minmax(
+                        GridTrackBreadth::Fixed(PixelValue::px(50.0)),
+                        GridTrackBreadth::Fraction(FloatValue::new(1.0)),
+                    ),
+                    GridTrackSize::px(100.0),
+                    GridTrackSize::minmax(
+                        GridTrackBreadth::Fixed(PixelValue::px(50.0)),
+                        GridTrackBreadth::Fraction(FloatValue::new(1.0)),
+                    ),
+                    GridTrackSize::auto(),
+                ]
+            })
         );
     }
 
     #[test]
-    fn test_parse_css_color_34() {
+    fn test_parse_grid_template_2() {
         assert_eq!(
-            parse_css_color("hsl(240deg, 0%, 0%,  )"),
-            Err(CssColorParseError::ExtraArguments(""))
+            parse_grid_template("minmax(1fr, 100px)"),
+            Err(CssGridParseError::FlexibleMinimum("minmax(1fr, 100px)"))
         );
-    }
-
-    #[test]
-    fn test_parse_css_color_35() {
         assert_eq!(
-            parse_css_color("hsla(240deg, 0%, 0%  )"),
-            Err(CssColorParseError::MissingColorComponent(
-                CssColorComponent::Alpha
-            ))
+            parse_grid_template("repeat(0, 1fr)"),
+            Err(CssGridParseError::InvalidRepeatCount("0"))
+        );
+        assert_eq!(
+            parse_grid_template("repeat(100000000, 1px)"),
+            Err(CssGridParseError::InvalidRepeatCount("100000000"))
+        );
+        assert_eq!(
+            parse_grid_template("repeat(6000, 1px) repeat(6000, 1px)"),
+            Err(CssGridParseError::InvalidRepeatCount("6000"))
+        );
+        assert_eq!(
+            parse_grid_template("repeat(10000, 1px)").map(|t| t.tracks.len()),
+            Ok(10_000)
         );
     }
 
     #[test]
-    fn test_parse_css_color_36() {
+    fn test_parse_grid_placement() {
         assert_eq!(
-            parse_css_color("rgb(255,0, 0)"),
-            Ok(ColorU {
-                r: 255,
-                g: 0,
-                b: 0,
-                a: 255
+            parse_grid_placement("span 2"),
+            Ok(GridPlacement {
+                start: GridLine::Span(2),
+                end: GridLine::Auto
+            })
+        );
+        assert_eq!(
+            parse_grid_placement("1 / -1"),
+            Ok(GridPlacement {
+                start: GridLine::Line(1),
+                end: GridLine::Line(-1)
+            })
+        );
+        assert_eq!(
+            parse_grid_placement("0 / 2"),
+            Err(CssGridParseError::InvalidGridLine("0"))
+        );
+        assert_eq!(
+            parse_grid_placement("-2147483648 / 2147483647"),
+            Ok(GridPlacement {
+                start: GridLine::Line(-MAX_GRID_LINE),
+                end: GridLine::Line(MAX_GRID_LINE)
+            })
+        );
+        assert_eq!(
+            parse_grid_placement("100000000 / span 4294967295"),
+            Ok(GridPlacement {
+                start: GridLine::Line(MAX_GRID_LINE),
+                end: GridLine::Span(MAX_GRID_LINE as u32)
             })
         );
     }
 
     #[test]
-    fn test_parse_pixel_value_1() {
-        assert_eq!(parse_pixel_value("15px"), Ok(PixelValue::px(15.0)));
+    fn test_parse_grid_gap() {
+        assert_eq!(
+            parse_layout_grid_gap("10px 5%"),
+            Ok(LayoutGridGap {
+                row: PixelValue::px(10.0),
+                column: PixelValue::percent(5.0),
+            })
+        );
+        assert_eq!(parse_layout_display("grid"), Ok(LayoutDisplay::Grid));
     }
 
     #[test]
-    fn test_parse_pixel_value_2() {
-        assert_eq!(parse_pixel_value("1.2em"), Ok(PixelValue::em(1.2)));
+    fn test_parse_layout_display() {
+        assert_eq!(parse_layout_display("block"), Ok(LayoutDisplay::Block));
+        assert_eq!(parse_layout_display("none"), Ok(LayoutDisplay::None));
+        assert_eq!(parse_layout_display("table"), Err(InvalidValueErr("table")));
     }
 
     #[test]
-    fn test_parse_pixel_value_3() {
-        assert_eq!(parse_pixel_value("11pt"), Ok(PixelValue::pt(11.0)));
+    fn test_parse_layout_position() {
+        assert_eq!(parse_layout_position("fixed"), Ok(LayoutPosition::Fixed));
+        assert_eq!(parse_layout_position("sticky"), Ok(LayoutPosition::Sticky));
+        assert_eq!(
+            parse_layout_position("-webkit-sticky"),
+            Err(InvalidValueErr("-webkit-sticky"))
+        );
     }
-
     #[test]
-    fn test_parse_pixel_value_4() {
+    fn test_parse_style_transform() {
+        use azul_css::StyleTransformFunction::*;
         assert_eq!(
-            parse_pixel_value("aslkfdjasdflk"),
-            Err(PixelParseError::NoValueGiven("aslkfdjasdflk"))
+            parse_style_transform("translate(10px, 50%) rotate(0.25turn) scale(2)"),
+            Ok(StyleTransform(vec![
+                Translate(PixelValue::px(10.0), PixelValue::percent(50.0)),
+                Rotate(FloatValue::new(90.0)),
+                Scale(FloatValue::new(2.0), FloatValue::new(2.0)),
+            ]))
+        );
+        assert_eq!(
+            parse_style_transform("translateY(5px)  scaleX(0.5) skewX(30deg)"),
+            Ok(StyleTransform(vec![
+                Translate(PixelValue::px(0.0), PixelValue::px(5.0)),
+                Scale(FloatValue::new(0.5), FloatValue::new(1.0)),
+                Skew(FloatValue::new(30.0), FloatValue::new(0.0)),
+            ]))
+        );
+        assert_eq!(
+            parse_style_transform("matrix(1, 0, 0, 1, 20, 30)"),
+            Ok(StyleTransform(vec![Matrix(StyleTransformMatrix2D {
+                a: FloatValue::new(1.0),
+                b: FloatValue::new(0.0),
+                c: FloatValue::new(0.0),
+                d: FloatValue::new(1.0),
+                e: FloatValue::new(20.0),
+                f: FloatValue::new(30.0),
+            })]))
+        );
+        assert_eq!(
+            parse_style_transform("rotate(45)"),
+            Err(CssTransformParseError::InvalidAngle("45"))
+        );
+        assert_eq!(
+            parse_style_transform("matrix(1, 0, 0)"),
+            Err(CssTransformParseError::WrongNumberOfArguments(
+                "matrix(1, 0, 0)"
+            ))
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::Transform, "none"),
+            Ok(CssProperty::Transform(CssPropertyValue::None))
         );
     }
 
     #[test]
-    fn test_parse_style_border_radius_1() {
+    fn test_parse_style_transform_origin() {
         assert_eq!(
-            parse_style_border_radius("15px"),
-            Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                top_right: PixelValue::px(15.0),
-                bottom_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(15.0),
+            parse_style_transform_origin("top left"),
+            Ok(StyleTransformOrigin {
+                x: PixelValue::percent(0.0),
+                y: PixelValue::percent(0.0),
             })
         );
-    }
-
-    #[test]
-    fn test_parse_style_border_radius_2() {
         assert_eq!(
-            parse_style_border_radius("15px 50px"),
-            Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(15.0),
-                top_right: PixelValue::px(50.0),
-                bottom_left: PixelValue::px(50.0),
+            parse_style_transform_origin("bottom"),
+            Ok(StyleTransformOrigin {
+                x: PixelValue::percent(50.0),
+                y: PixelValue::percent(100.0),
             })
         );
-    }
-
-    #[test]
-    fn test_parse_style_border_radius_3() {
         assert_eq!(
-            parse_style_border_radius("15px 50px 30px"),
-            Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(30.0),
-                top_right: PixelValue::px(50.0),
-                bottom_left: PixelValue::px(50.0),
+            parse_style_transform_origin("10px 20%"),
+            Ok(StyleTransformOrigin {
+                x: PixelValue::px(10.0),
+                y: PixelValue::percent(20.0),
             })
         );
+        assert_eq!(
+            parse_style_transform_origin("left right"),
+            Err(CssTransformParseError::InvalidOrigin("left right"))
+        );
     }
 
     #[test]
-    fn test_parse_style_border_radius_4() {
+    fn test_parse_style_opacity() {
         assert_eq!(
-            parse_style_border_radius("15px 50px 30px 5px"),
-            Ok(StyleBorderRadius {
-                top_left: PixelValue::px(15.0),
-                bottom_right: PixelValue::px(30.0),
-                top_right: PixelValue::px(50.0),
-                bottom_left: PixelValue::px(5.0),
-            })
+            parse_style_opacity("0.5"),
+            Ok(StyleOpacity(FloatValue::new(0.5)))
+        );
+        assert_eq!(
+            parse_style_opacity("25%"),
+            Ok(StyleOpacity(FloatValue::new(0.25)))
+        );
+        assert_eq!(
+            parse_style_opacity("1.5"),
+            Ok(StyleOpacity(FloatValue::new(1.0)))
+        );
+        assert_eq!(
+            parse_style_opacity("-1"),
+            Err(CssFilterParseError::InvalidAmount("-1"))
         );
     }
 
     #[test]
-    fn test_parse_style_font_family_1() {
+    fn test_parse_style_filter() {
         assert_eq!(
-            parse_style_font_family("\"Webly Sleeky UI\", monospace"),
-            Ok(StyleFontFamily {
-                fonts: vec![FontId("Webly Sleeky UI".into()), FontId("monospace".into()),]
-            })
+            parse_style_filter("blur(5px) grayscale(50%) brightness(1.5)"),
+            Ok(StyleFilter(vec![
+                StyleFilterFunction::Blur(PixelValueNoPercent(PixelValue::px(5.0))),
+                StyleFilterFunction::Grayscale(FloatValue::new(0.5)),
+                StyleFilterFunction::Brightness(FloatValue::new(1.5)),
+            ]))
+        );
+        assert_eq!(
+            parse_style_filter("drop-shadow(rgba(0, 0, 0, 0.5) 2px 4px 6px)"),
+            Ok(StyleFilter(vec![StyleFilterFunction::DropShadow(
+                StyleDropShadow {
+                    offset: [
+                        PixelValueNoPercent(PixelValue::px(2.0)),
+                        PixelValueNoPercent(PixelValue::px(4.0)),
+                    ],
+                    color: ColorU {
+                        r: 0,
+                        g: 0,
+                        b: 0,
+                        a: 128
+                    },
+                    blur_radius: PixelValueNoPercent(PixelValue::px(6.0)),
+                }
+            )]))
+        );
+        assert_eq!(
+            parse_style_filter("drop-shadow(2px 4px)"),
+            Ok(StyleFilter(vec![StyleFilterFunction::DropShadow(
+                StyleDropShadow {
+                    offset: [
+                        PixelValueNoPercent(PixelValue::px(2.0)),
+                        PixelValueNoPercent(PixelValue::px(4.0)),
+                    ],
+                    color: ColorU::BLACK,
+                    blur_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+                }
+            )]))
+        );
+        assert_eq!(
+            parse_style_filter("grayscale()"),
+            Ok(StyleFilter(vec![StyleFilterFunction::Grayscale(
+                FloatValue::new(1.0)
+            )]))
+        );
+        assert_eq!(
+            parse_style_filter("drop-shadow(2px)"),
+            Err(CssFilterParseError::WrongNumberOfArguments("2px"))
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::Filter, "none"),
+            Ok(CssProperty::Filter(CssPropertyValue::None))
         );
     }
 
     #[test]
-    fn test_parse_style_font_family_2() {
+    fn test_parse_css_duration() {
         assert_eq!(
-            parse_style_font_family("'Webly Sleeky UI'"),
-            Ok(StyleFontFamily {
-                fonts: vec![FontId("Webly Sleeky UI".into()),]
-            })
+            parse_css_duration("300ms"),
+            Ok(CssDuration::from_millis(300))
+        );
+        assert_eq!(
+            parse_css_duration("1.5s"),
+            Ok(CssDuration::from_millis(1500))
+        );
+        assert_eq!(parse_css_duration("0"), Ok(CssDuration::from_millis(0)));
+        assert_eq!(
+            parse_css_duration("300"),
+            Err(CssAnimationParseError::InvalidDuration("300"))
+        );
+        assert_eq!(
+            parse_css_duration("-1s"),
+            Err(CssAnimationParseError::InvalidDuration("-1s"))
         );
     }
 
     #[test]
-    fn test_parse_background_image() {
+    fn test_parse_style_timing_function() {
+        use azul_css::StyleTimingFunction::*;
+
+        assert_eq!(parse_style_timing_function("ease-in-out"), Ok(EaseInOut));
         assert_eq!(
-            parse_style_background_content("image(\"Cat 01\")"),
-            Ok(StyleBackgroundContent::Image(CssImageId(String::from(
-                "Cat 01"
-            ))))
+            parse_style_timing_function("step-start"),
+            Ok(Steps(1, StepPosition::Start))
+        );
+        assert_eq!(
+            parse_style_timing_function("steps(4)"),
+            Ok(Steps(4, StepPosition::End))
+        );
+        assert_eq!(
+            parse_style_timing_function("cubic-bezier(0.1, 0.7, 1.0, 0.1)"),
+            Ok(CubicBezier(
+                FloatValue::new(0.1),
+                FloatValue::new(0.7),
+                FloatValue::new(1.0),
+                FloatValue::new(0.1)
+            ))
+        );
+        assert_eq!(
+            parse_style_timing_function("cubic-bezier(2, 0, 1, 1)"),
+            Err(CssAnimationParseError::InvalidTimingFunction(
+                "cubic-bezier(2, 0, 1, 1)"
+            ))
         );
     }
 
     #[test]
-    fn test_parse_padding_1() {
+    fn test_parse_style_transition() {
         assert_eq!(
-            parse_layout_padding("10px"),
-            Ok(LayoutPadding {
-                top: PixelValue::px(10.0),
-                right: PixelValue::px(10.0),
-                bottom: PixelValue::px(10.0),
-                left: PixelValue::px(10.0),
-            })
+            parse_style_transition("width 1s, background-color 300ms ease-in 0.5s"),
+            Ok(StyleTransition(vec![
+                StyleTransitionItem {
+                    property: TransitionProperty::Property(CssPropertyType::Width),
+                    duration: CssDuration::from_millis(1000),
+                    timing_function: StyleTimingFunction::Ease,
+                    delay: CssDuration::from_millis(0),
+                },
+                StyleTransitionItem {
+                    property: TransitionProperty::Property(CssPropertyType::BackgroundImage),
+                    duration: CssDuration::from_millis(300),
+                    timing_function: StyleTimingFunction::EaseIn,
+                    delay: CssDuration::from_millis(500),
+                },
+            ]))
         );
-    }
 
-    #[test]
-    fn test_parse_padding_2() {
+        // the property defaults to "all", shorthands are expanded
         assert_eq!(
-            parse_layout_padding("25px 50px"),
-            Ok(LayoutPadding {
-                top: PixelValue::px(25.0),
-                right: PixelValue::px(50.0),
-                bottom: PixelValue::px(25.0),
-                left: PixelValue::px(50.0),
-            })
+            parse_style_transition("2s linear"),
+            Ok(StyleTransition(vec![StyleTransitionItem {
+                property: TransitionProperty::All,
+                duration: CssDuration::from_millis(2000),
+                timing_function: StyleTimingFunction::Linear,
+                delay: CssDuration::from_millis(0),
+            }]))
+        );
+        assert_eq!(
+            parse_style_transition("padding 1s").map(|t| t.0.len()),
+            Ok(4)
         );
-    }
 
-    #[test]
-    fn test_parse_padding_3() {
         assert_eq!(
-            parse_layout_padding("25px 50px 75px"),
-            Ok(LayoutPadding {
-                top: PixelValue::px(25.0),
-                right: PixelValue::px(50.0),
-                left: PixelValue::px(50.0),
-                bottom: PixelValue::px(75.0),
-            })
+            parse_style_transition("asdf 1s"),
+            Err(CssAnimationParseError::UnknownTransitionProperty("asdf"))
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::Transition, "none"),
+            Ok(CssProperty::Transition(CssPropertyValue::None))
         );
     }
 
     #[test]
-    fn test_parse_padding_4() {
+    fn test_parse_style_animation() {
         assert_eq!(
-            parse_layout_padding("25px 50px 75px 100px"),
-            Ok(LayoutPadding {
-                top: PixelValue::px(25.0),
-                right: PixelValue::px(50.0),
-                bottom: PixelValue::px(75.0),
-                left: PixelValue::px(100.0),
-            })
+            parse_combined_css_property(
+                CombinedCssPropertyType::Animation,
+                "fade 1s ease-in 500ms infinite alternate forwards"
+            ),
+            Ok(vec![
+                StyleAnimationName("fade".into()).into(),
+                StyleAnimationDuration(CssDuration::from_millis(1000)).into(),
+                StyleAnimationTimingFunction(StyleTimingFunction::EaseIn).into(),
+                StyleAnimationDelay(CssDuration::from_millis(500)).into(),
+                StyleAnimationIterationCount::Infinite.into(),
+                StyleAnimationDirection::Alternate.into(),
+                StyleAnimationFillMode::Forwards.into(),
+            ])
+        );
+
+        // unspecified values are reset to their defaults
+        assert_eq!(
+            parse_style_animation("2 spin 3s"),
+            Ok(vec![
+                StyleAnimationName("spin".into()).into(),
+                StyleAnimationDuration(CssDuration::from_millis(3000)).into(),
+                StyleAnimationTimingFunction(StyleTimingFunction::Ease).into(),
+                StyleAnimationDelay(CssDuration::from_millis(0)).into(),
+                StyleAnimationIterationCount::Count(FloatValue::new(2.0)).into(),
+                StyleAnimationDirection::Normal.into(),
+                CssProperty::AnimationFillMode(CssPropertyValue::None),
+            ])
+        );
+
+        assert_eq!(
+            parse_style_animation("fade 1s, spin 2s"),
+            Err(CssAnimationParseError::MultipleAnimations(
+                "fade 1s, spin 2s"
+            ))
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::AnimationIterationCount, "-1"),
+            Err(CssParsingError::AnimationParseError(
+                CssAnimationParseError::InvalidIterationCount("-1")
+            ))
+        );
+        assert_eq!(
+            parse_css_property(CssPropertyType::AnimationName, "1abc"),
+            Err(CssParsingError::AnimationParseError(
+                CssAnimationParseError::InvalidAnimationName("1abc")
+            ))
         );
     }
 }
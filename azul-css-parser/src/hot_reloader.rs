@@ -1,8 +1,8 @@
 //! Provides an implementation of the HotReloadHandler from the `azul_css` crate, allowing CSS
 //! files to be dynamically reloaded at runtime.
 
-use azul_css::{Css, HotReloadHandler};
-use std::path::PathBuf;
+use azul_css::{Css, CssFontFaceSource, HotReloadHandler};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
@@ -50,8 +50,28 @@ impl HotReloadHandler for HotReloader {
             )
         })?;
 
-        css::new_from_str(&reloaded_css)
-            .map_err(|e| format!("Error parsing CSS file: \"{}\":\r\n{}", file_name, e))
+        let mut css = css::new_from_str(&reloaded_css)
+            .map_err(|e| format!("Error parsing CSS file: \"{}\":\r\n{}", file_name, e))?;
+
+        // Fonts of @font-face rules are shipped alongside the stylesheet,
+        // so relative paths are relative to the CSS file, not the working directory
+        if let Some(css_dir) = self.file_path.parent() {
+            let font_faces = css
+                .stylesheets
+                .iter_mut()
+                .flat_map(|stylesheet| stylesheet.font_faces.iter_mut());
+            for font_face in font_faces {
+                for source in font_face.sources.iter_mut() {
+                    if let CssFontFaceSource::Url(path) = source {
+                        if Path::new(path.as_str()).is_relative() {
+                            *path = css_dir.join(path.as_str()).to_string_lossy().into_owned();
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(css)
     }
 
     fn get_reload_interval(&self) -> Duration {
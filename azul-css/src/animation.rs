@@ -0,0 +1,562 @@
+//! Interpolation of CSS properties and easing functions, used by CSS transitions
+//! and `@keyframes` animations
+
+use crate::{css::CssPropertyValue, css_properties::*};
+
+/// Properties whose values can be interpolated during a transition or an animation
+pub trait Interpolate: Sized {
+    /// Returns the value between `self` (`t = 0.0`) and `other` (`t = 1.0`). Note that `t` can
+    /// be outside of that range for easing functions that overshoot. Returns `None` if the
+    /// values can't be interpolated, in which case the value changes discretely.
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self>;
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+impl Interpolate for FloatValue {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(FloatValue::new(lerp(self.get(), other.get(), t)))
+    }
+}
+
+impl Interpolate for PercentageValue {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(PercentageValue::new(lerp(self.get(), other.get(), t)))
+    }
+}
+
+impl Interpolate for PixelValue {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        // "0" is parsed as "0px", but can be interpolated with any metric
+        let metric = if self.metric == other.metric || self.number.get() == 0.0 {
+            other.metric
+        } else if other.number.get() == 0.0 {
+            self.metric
+        } else {
+            // can't interpolate between "10px" and "50%" without knowing the layout
+            return None;
+        };
+        Some(PixelValue::from_metric(
+            metric,
+            lerp(self.number.get(), other.number.get(), t),
+        ))
+    }
+}
+
+impl Interpolate for PixelValueNoPercent {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(PixelValueNoPercent(self.0.interpolate(&other.0, t)?))
+    }
+}
+
+impl Interpolate for ColorU {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        let channel = |from: u8, to: u8| {
+            lerp(f32::from(from), f32::from(to), t)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Some(ColorU {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+            a: channel(self.a, other.a),
+        })
+    }
+}
+
+impl Interpolate for GradientStopPre {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        let offset = match (self.offset, other.offset) {
+            (Some(a), Some(b)) => Some(a.interpolate(&b, t)?),
+            (None, None) => None,
+            _ => return None,
+        };
+        Some(GradientStopPre {
+            offset,
+            color: self.color.interpolate(&other.color, t)?,
+        })
+    }
+}
+
+/// Interpolates the gradient stops pairwise, both gradients must have the same number of stops
+fn interpolate_gradient_stops(
+    a: &[GradientStopPre],
+    b: &[GradientStopPre],
+    t: f32,
+) -> Option<Vec<GradientStopPre>> {
+    if a.len() != b.len() {
+        return None;
+    }
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| a.interpolate(b, t))
+        .collect()
+}
+
+impl Interpolate for LinearGradient {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.extend_mode != other.extend_mode {
+            return None;
+        }
+        let direction = match (self.direction, other.direction) {
+            (Direction::Angle(a), Direction::Angle(b)) => Direction::Angle(a.interpolate(&b, t)?),
+            (a, b) if a == b => a,
+            _ => return None,
+        };
+        Some(LinearGradient {
+            direction,
+            extend_mode: self.extend_mode,
+            stops: interpolate_gradient_stops(&self.stops, &other.stops, t)?,
+        })
+    }
+}
+
+impl Interpolate for RadialGradient {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.shape != other.shape || self.extend_mode != other.extend_mode {
+            return None;
+        }
+        Some(RadialGradient {
+            shape: self.shape,
+            extend_mode: self.extend_mode,
+            stops: interpolate_gradient_stops(&self.stops, &other.stops, t)?,
+        })
+    }
+}
+
+impl Interpolate for StyleBackgroundContent {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        use self::StyleBackgroundContent::*;
+        match (self, other) {
+            (Color(a), Color(b)) => Some(Color(a.interpolate(b, t)?)),
+            (LinearGradient(a), LinearGradient(b)) => Some(LinearGradient(a.interpolate(b, t)?)),
+            (RadialGradient(a), RadialGradient(b)) => Some(RadialGradient(a.interpolate(b, t)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Interpolate for StyleBackgroundSize {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        match (self, other) {
+            (
+                StyleBackgroundSize::ExactSize(a_x, a_y),
+                StyleBackgroundSize::ExactSize(b_x, b_y),
+            ) => Some(StyleBackgroundSize::ExactSize(
+                a_x.interpolate(b_x, t)?,
+                a_y.interpolate(b_y, t)?,
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Interpolate for BoxShadowPreDisplayItem {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.clip_mode != other.clip_mode {
+            return None;
+        }
+        Some(BoxShadowPreDisplayItem {
+            offset: [
+                self.offset[0].interpolate(&other.offset[0], t)?,
+                self.offset[1].interpolate(&other.offset[1], t)?,
+            ],
+            color: self.color.interpolate(&other.color, t)?,
+            blur_radius: self.blur_radius.interpolate(&other.blur_radius, t)?,
+            spread_radius: self.spread_radius.interpolate(&other.spread_radius, t)?,
+            clip_mode: self.clip_mode,
+        })
+    }
+}
+
+impl Interpolate for StyleTransformFunction {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        use self::StyleTransformFunction::*;
+        match (self, other) {
+            (Matrix(a), Matrix(b)) => Some(Matrix(StyleTransformMatrix2D {
+                a: a.a.interpolate(&b.a, t)?,
+                b: a.b.interpolate(&b.b, t)?,
+                c: a.c.interpolate(&b.c, t)?,
+                d: a.d.interpolate(&b.d, t)?,
+                e: a.e.interpolate(&b.e, t)?,
+                f: a.f.interpolate(&b.f, t)?,
+            })),
+            (Translate(a_x, a_y), Translate(b_x, b_y)) => Some(Translate(
+                a_x.interpolate(b_x, t)?,
+                a_y.interpolate(b_y, t)?,
+            )),
+            (Rotate(a), Rotate(b)) => Some(Rotate(a.interpolate(b, t)?)),
+            (Scale(a_x, a_y), Scale(b_x, b_y)) => {
+                Some(Scale(a_x.interpolate(b_x, t)?, a_y.interpolate(b_y, t)?))
+            }
+            (Skew(a_x, a_y), Skew(b_x, b_y)) => {
+                Some(Skew(a_x.interpolate(b_x, t)?, a_y.interpolate(b_y, t)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Interpolate for StyleTransform {
+    /// Only transforms with the same list of functions (i.e. `rotate(0deg) scale(1)` and
+    /// `rotate(90deg) scale(2)`) are interpolated, no matrix decomposition is done
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| a.interpolate(b, t))
+            .collect::<Option<Vec<_>>>()
+            .map(StyleTransform)
+    }
+}
+
+impl Interpolate for StyleTransformOrigin {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(StyleTransformOrigin {
+            x: self.x.interpolate(&other.x, t)?,
+            y: self.y.interpolate(&other.y, t)?,
+        })
+    }
+}
+
+impl Interpolate for StyleFontWeight {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        let weight = lerp(f32::from(self.0), f32::from(other.0), t).round();
+        Some(StyleFontWeight(weight.clamp(1.0, 1000.0) as u16))
+    }
+}
+
+impl Interpolate for StyleDropShadow {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        Some(StyleDropShadow {
+            offset: [
+                self.offset[0].interpolate(&other.offset[0], t)?,
+                self.offset[1].interpolate(&other.offset[1], t)?,
+            ],
+            color: self.color.interpolate(&other.color, t)?,
+            blur_radius: self.blur_radius.interpolate(&other.blur_radius, t)?,
+        })
+    }
+}
+
+impl Interpolate for StyleFilterFunction {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        use self::StyleFilterFunction::*;
+        match (self, other) {
+            (Blur(a), Blur(b)) => Some(Blur(a.interpolate(b, t)?)),
+            (Grayscale(a), Grayscale(b)) => Some(Grayscale(a.interpolate(b, t)?)),
+            (Brightness(a), Brightness(b)) => Some(Brightness(a.interpolate(b, t)?)),
+            (DropShadow(a), DropShadow(b)) => Some(DropShadow(a.interpolate(b, t)?)),
+            _ => None,
+        }
+    }
+}
+
+impl Interpolate for StyleFilter {
+    /// Same as transforms, only filters with the same list of functions are interpolated
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| a.interpolate(b, t))
+            .collect::<Option<Vec<_>>>()
+            .map(StyleFilter)
+    }
+}
+
+impl<T: Interpolate> Interpolate for CssPropertyValue<T> {
+    fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+        match (self, other) {
+            (CssPropertyValue::Exact(a), CssPropertyValue::Exact(b)) => {
+                Some(CssPropertyValue::Exact(a.interpolate(b, t)?))
+            }
+            // "auto", "none", etc. can't be interpolated
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_interpolate_newtype {
+    ($($struct:ident),+) => {
+        $(
+            impl Interpolate for $struct {
+                fn interpolate(&self, other: &Self, t: f32) -> Option<Self> {
+                    Some($struct(self.0.interpolate(&other.0, t)?))
+                }
+            }
+        )+
+    };
+}
+
+impl_interpolate_newtype!(
+    StyleTextColor,
+    StyleFontSize,
+    StyleLetterSpacing,
+    StyleLineHeight,
+    StyleWordSpacing,
+    StyleTabWidth,
+    LayoutWidth,
+    LayoutHeight,
+    LayoutMinWidth,
+    LayoutMinHeight,
+    LayoutMaxWidth,
+    LayoutMaxHeight,
+    LayoutTop,
+    LayoutRight,
+    LayoutLeft,
+    LayoutBottom,
+    LayoutFlexGrow,
+    LayoutFlexShrink,
+    LayoutGridColumnGap,
+    LayoutGridRowGap,
+    LayoutPaddingTop,
+    LayoutPaddingLeft,
+    LayoutPaddingRight,
+    LayoutPaddingBottom,
+    LayoutMarginTop,
+    LayoutMarginLeft,
+    LayoutMarginRight,
+    LayoutMarginBottom,
+    StyleBorderTopLeftRadius,
+    StyleBorderTopRightRadius,
+    StyleBorderBottomLeftRadius,
+    StyleBorderBottomRightRadius,
+    StyleBorderTopColor,
+    StyleBorderRightColor,
+    StyleBorderLeftColor,
+    StyleBorderBottomColor,
+    StyleBorderTopWidth,
+    StyleBorderRightWidth,
+    StyleBorderLeftWidth,
+    StyleBorderBottomWidth,
+    StyleOpacity
+);
+
+impl CssProperty {
+    /// Interpolates between two values of the same property, see `Interpolate::interpolate`.
+    /// Returns `None` if the properties have different types or if the property type
+    /// can't be interpolated at all (i.e. `display` or `font-family`).
+    pub fn interpolate(&self, other: &CssProperty, t: f32) -> Option<CssProperty> {
+        use crate::css_properties::CssProperty::*;
+
+        macro_rules! interpolate_variants {
+            ($($variant:ident),+) => {
+                match (self, other) {
+                    $(
+                        ($variant(a), $variant(b)) => a.interpolate(b, t).map($variant),
+                    )+
+                    _ => None,
+                }
+            };
+        }
+
+        interpolate_variants!(
+            TextColor,
+            FontSize,
+            FontWeight,
+            LetterSpacing,
+            LineHeight,
+            WordSpacing,
+            TabWidth,
+            Width,
+            Height,
+            MinWidth,
+            MinHeight,
+            MaxWidth,
+            MaxHeight,
+            Top,
+            Right,
+            Left,
+            Bottom,
+            FlexGrow,
+            FlexShrink,
+            GridColumnGap,
+            GridRowGap,
+            BackgroundContent,
+            BackgroundSize,
+            PaddingTop,
+            PaddingLeft,
+            PaddingRight,
+            PaddingBottom,
+            MarginTop,
+            MarginLeft,
+            MarginRight,
+            MarginBottom,
+            BorderTopLeftRadius,
+            BorderTopRightRadius,
+            BorderBottomLeftRadius,
+            BorderBottomRightRadius,
+            BorderTopColor,
+            BorderRightColor,
+            BorderLeftColor,
+            BorderBottomColor,
+            BorderTopWidth,
+            BorderRightWidth,
+            BorderLeftWidth,
+            BorderBottomWidth,
+            BoxShadowLeft,
+            BoxShadowRight,
+            BoxShadowTop,
+            BoxShadowBottom,
+            Transform,
+            TransformOrigin,
+            Opacity,
+            Filter
+        )
+    }
+}
+
+impl StyleTimingFunction {
+    /// Maps the linear progress of a transition or an animation (`0.0` to `1.0`)
+    /// to the eased progress, which can be outside of that range for some curves
+    pub fn evaluate(&self, progress: f32) -> f32 {
+        use self::StyleTimingFunction::*;
+
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            Linear => progress,
+            Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, progress),
+            EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, progress),
+            EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, progress),
+            EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, progress),
+            CubicBezier(x1, y1, x2, y2) => {
+                cubic_bezier(x1.get(), y1.get(), x2.get(), y2.get(), progress)
+            }
+            Steps(steps, position) => {
+                let steps = (*steps).max(1) as f32;
+                let step = match position {
+                    StepPosition::Start => (progress * steps).ceil(),
+                    StepPosition::End => (progress * steps).floor(),
+                };
+                (step / steps).min(1.0)
+            }
+        }
+    }
+}
+
+/// Evaluates the y value of a cubic bezier curve from `(0, 0)` to `(1, 1)`
+/// with the control points `(x1, y1)` and `(x2, y2)` at the given `x`
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    fn bezier(p1: f32, p2: f32, t: f32) -> f32 {
+        let inv = 1.0 - t;
+        3.0 * inv * inv * t * p1 + 3.0 * inv * t * t * p2 + t * t * t
+    }
+
+    // x(t) is monotonic if x1 and x2 are in [0, 1], so the bisection always converges
+    let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+    let (mut low, mut high, mut t) = (0.0, 1.0, x);
+
+    for _ in 0..32 {
+        let current_x = bezier(x1, x2, t);
+        if (current_x - x).abs() < 0.00001 {
+            break;
+        }
+        if current_x < x {
+            low = t;
+        } else {
+            high = t;
+        }
+        t = (low + high) / 2.0;
+    }
+
+    bezier(y1, y2, t)
+}
+
+#[test]
+fn test_timing_function_evaluate() {
+    use self::StyleTimingFunction::*;
+
+    let approx_eq = |a: f32, b: f32| (a - b).abs() < 0.001;
+
+    for function in &[Linear, Ease, EaseIn, EaseOut, EaseInOut] {
+        assert!(approx_eq(function.evaluate(0.0), 0.0));
+        assert!(approx_eq(function.evaluate(1.0), 1.0));
+    }
+
+    assert!(approx_eq(Linear.evaluate(0.3), 0.3));
+    assert!(approx_eq(EaseInOut.evaluate(0.5), 0.5));
+    assert!(EaseIn.evaluate(0.25) < 0.25);
+    assert!(EaseOut.evaluate(0.25) > 0.25);
+
+    assert_eq!(Steps(4, StepPosition::End).evaluate(0.3), 0.25);
+    assert_eq!(Steps(4, StepPosition::Start).evaluate(0.3), 0.5);
+    assert_eq!(Steps(1, StepPosition::End).evaluate(0.99), 0.0);
+}
+
+#[test]
+fn test_css_property_interpolate() {
+    assert_eq!(
+        CssProperty::from(LayoutWidth::px(100.0)).interpolate(&LayoutWidth::px(200.0).into(), 0.25),
+        Some(LayoutWidth::px(125.0).into())
+    );
+
+    // "0" can be interpolated with other metrics, "10px" and "50%" can't
+    assert_eq!(
+        CssProperty::from(LayoutWidth::px(0.0))
+            .interpolate(&LayoutWidth(PixelValue::percent(50.0)).into(), 0.5),
+        Some(LayoutWidth(PixelValue::percent(25.0)).into())
+    );
+    assert_eq!(
+        CssProperty::from(LayoutWidth::px(10.0))
+            .interpolate(&LayoutWidth(PixelValue::percent(50.0)).into(), 0.5),
+        None
+    );
+
+    let black = StyleBackgroundContent::Color(ColorU::BLACK);
+    let white = StyleBackgroundContent::Color(ColorU::WHITE);
+    assert_eq!(
+        CssProperty::from(black).interpolate(&white.into(), 0.5),
+        Some(
+            StyleBackgroundContent::Color(ColorU {
+                r: 128,
+                g: 128,
+                b: 128,
+                a: 255
+            })
+            .into()
+        )
+    );
+
+    assert_eq!(
+        CssProperty::from(StyleFontWeight::NORMAL).interpolate(&StyleFontWeight::BOLD.into(), 0.5),
+        Some(StyleFontWeight(550).into())
+    );
+
+    assert_eq!(
+        CssProperty::from(StyleOpacity(FloatValue::new(0.0)))
+            .interpolate(&StyleOpacity(FloatValue::new(1.0)).into(), 0.25),
+        Some(StyleOpacity(FloatValue::new(0.25)).into())
+    );
+
+    // filters with the same functions are interpolated per function
+    let from = StyleFilter(vec![StyleFilterFunction::Grayscale(FloatValue::new(0.0))]);
+    let to = StyleFilter(vec![StyleFilterFunction::Grayscale(FloatValue::new(1.0))]);
+    assert_eq!(
+        CssProperty::from(from.clone()).interpolate(&to.into(), 0.5),
+        Some(StyleFilter(vec![StyleFilterFunction::Grayscale(FloatValue::new(0.5))]).into())
+    );
+    let blur = StyleFilter(vec![StyleFilterFunction::Blur(PixelValueNoPercent(
+        PixelValue::px(5.0),
+    ))]);
+    assert_eq!(CssProperty::from(from).interpolate(&blur.into(), 0.5), None);
+
+    // different property types and non-numeric properties change discretely
+    assert_eq!(
+        CssProperty::from(LayoutWidth::px(10.0)).interpolate(&LayoutHeight::px(20.0).into(), 0.5),
+        None
+    );
+    assert_eq!(
+        CssProperty::from(LayoutDisplay::Block).interpolate(&LayoutDisplay::Flex.into(), 0.5),
+        None
+    );
+}
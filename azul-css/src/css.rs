@@ -1,5 +1,8 @@
 //! Types and methods used to describe the style of an application
-use crate::css_properties::{CssProperty, CssPropertyType};
+use crate::css_properties::{
+    CssProperty, CssPropertyType, FloatValue, PercentageValue, PixelValueNoPercent,
+    StyleFontStretch, StyleFontStyle, StyleFontWeight,
+};
 use std::fmt;
 
 /// Css stylesheet - contains a parsed CSS stylesheet in "rule blocks",
@@ -16,14 +19,122 @@ pub struct Css {
 pub struct Stylesheet {
     /// The style rules making up the document - for example, de-duplicated CSS rules
     pub rules: Vec<CssRuleBlock>,
+    /// The `@keyframes` rules of the document, referenced by the `animation-name` property
+    pub keyframes: Vec<CssKeyframes>,
+    /// The `@font-face` rules of the document, referenced by the `font-family` property
+    pub font_faces: Vec<CssFontFace>,
 }
 
 impl From<Vec<CssRuleBlock>> for Stylesheet {
     fn from(rules: Vec<CssRuleBlock>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            keyframes: Vec::new(),
+            font_faces: Vec::new(),
+        }
     }
 }
 
+/// One `@keyframes` rule, i.e. `@keyframes fade { from { opacity: 0; } to { opacity: 1; } }`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CssKeyframes {
+    /// Name of the animation, as used in the `animation-name` property
+    pub name: String,
+    /// The keyframes, sorted by their offset
+    pub keyframes: Vec<CssKeyframe>,
+}
+
+/// One keyframe of a `@keyframes` rule, i.e. `50% { width: 100px; }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssKeyframe {
+    /// Position of the keyframe in the animation (`from` = `0%`, `to` = `100%`)
+    pub offset: PercentageValue,
+    /// The properties at this keyframe - `var()` properties are resolved to their default value
+    pub properties: Vec<CssProperty>,
+}
+
+/// One `@font-face` rule, i.e. `@font-face { font-family: "Roboto"; src: url(Roboto.ttf); }`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssFontFace {
+    /// Name of the font family, as used in the `font-family` property
+    pub family: String,
+    /// The sources of the font, in order of preference
+    pub sources: Vec<CssFontFaceSource>,
+    /// `font-weight` descriptor of the face
+    pub weight: StyleFontWeight,
+    /// `font-style` descriptor of the face
+    pub style: StyleFontStyle,
+    /// `font-stretch` descriptor of the face
+    pub stretch: StyleFontStretch,
+}
+
+impl CssFontFace {
+    /// Whether the face has a normal weight, style and stretch, i.e. whether
+    /// it's the face to use for the family if no other weight or style is requested
+    pub fn is_regular(&self) -> bool {
+        self.weight == StyleFontWeight::NORMAL
+            && self.style == StyleFontStyle::Normal
+            && self.stretch == StyleFontStretch::Normal
+    }
+}
+
+/// One source in the `src` descriptor of a `@font-face` rule
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CssFontFaceSource {
+    /// `url(fonts/Roboto.ttf)` - path to the font file. Relative paths are relative to the
+    /// working directory, or to the stylesheet when it's loaded by the hot-reloader
+    Url(String),
+    /// `local(Roboto)` - name of a font installed on the system
+    Local(String),
+}
+
+/// One query of a `@media` rule, i.e. `screen and (min-width: 600px) and (orientation: landscape)`.
+/// The query matches if all of its features match (an empty query always matches).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssMediaQuery {
+    pub features: Vec<CssMediaFeature>,
+}
+
+/// One feature of a `@media` query, i.e. `(min-width: 600px)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CssMediaFeature {
+    /// `(min-width: 600px)` - the width of the window is at least 600 logical pixels
+    MinWidth(PixelValueNoPercent),
+    /// `(max-width: 600px)` - the width of the window is at most 600 logical pixels
+    MaxWidth(PixelValueNoPercent),
+    /// `(min-height: 400px)`
+    MinHeight(PixelValueNoPercent),
+    /// `(max-height: 400px)`
+    MaxHeight(PixelValueNoPercent),
+    /// `(orientation: portrait)` or `(orientation: landscape)`
+    Orientation(CssMediaOrientation),
+    /// `(min-resolution: 2dppx)` - the resolution is stored in dots per logical pixel,
+    /// i.e. as the hidpi factor of the window
+    MinResolution(FloatValue),
+    /// `(max-resolution: 96dpi)`
+    MaxResolution(FloatValue),
+    /// `(resolution: 2x)`
+    Resolution(FloatValue),
+    /// `(prefers-color-scheme: dark)`
+    PrefersColorScheme(CssColorScheme),
+}
+
+/// Value of the `orientation` media feature
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CssMediaOrientation {
+    /// The window is at least as high as it is wide
+    Portrait,
+    /// The window is wider than it is high
+    Landscape,
+}
+
+/// Value of the `prefers-color-scheme` media feature
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CssColorScheme {
+    Light,
+    Dark,
+}
+
 /// Contains one parsed `key: value` pair, static or dynamic
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CssDeclaration {
@@ -235,6 +346,13 @@ pub struct CssRuleBlock {
     /// `"justify-content: center"` =>
     /// `CssDeclaration::Static(CssProperty::JustifyContent(LayoutJustifyContent::Center))`
     pub declarations: Vec<CssDeclaration>,
+    /// The queries of the `@media` rules this block is nested in - the block only applies
+    /// if one of the queries matches the window. Blocks outside of `@media` rules have no queries.
+    pub media_queries: Vec<CssMediaQuery>,
+    /// Whether the declarations are `!important`, i.e. take precedence over all
+    /// declarations that aren't `!important`, regardless of their specificity - the
+    /// parser puts the `!important` declarations of a block into a separate `CssRuleBlock`
+    pub important: bool,
 }
 
 pub type CssContentGroup<'a> = Vec<&'a CssPathSelector>;
@@ -338,10 +456,30 @@ pub enum CssPathSelector {
     Id(String),
     /// `:something`
     PseudoSelector(CssPathPseudoSelector),
+    /// `:not(div.something)` - the selectors can't contain combinators
+    Not(Vec<CssPathSelector>),
+    /// `[something]`, `[something="value"]`, etc.
+    Attribute(CssAttributeSelector),
     /// Represents the `>` selector
     DirectChildren,
     /// Represents the ` ` selector
     Children,
+    /// Represents the `+` selector
+    AdjacentSibling,
+    /// Represents the `~` selector
+    GeneralSibling,
+}
+
+impl CssPathSelector {
+    /// Returns whether the selector is a combinator (` `, `>`, `+` or `~`)
+    /// that splits the CSS path into groups
+    pub fn is_combinator(&self) -> bool {
+        use self::CssPathSelector::*;
+        match self {
+            DirectChildren | Children | AdjacentSibling | GeneralSibling => true,
+            _ => false,
+        }
+    }
 }
 
 impl Default for CssPathSelector {
@@ -359,8 +497,88 @@ impl fmt::Display for CssPathSelector {
             Class(c) => write!(f, ".{}", c),
             Id(i) => write!(f, "#{}", i),
             PseudoSelector(p) => write!(f, ":{}", p),
+            Not(selectors) => {
+                write!(f, ":not(")?;
+                for selector in selectors {
+                    write!(f, "{}", selector)?;
+                }
+                write!(f, ")")
+            }
+            Attribute(a) => write!(f, "{}", a),
             DirectChildren => write!(f, ">"),
             Children => write!(f, " "),
+            AdjacentSibling => write!(f, "+"),
+            GeneralSibling => write!(f, "~"),
+        }
+    }
+}
+
+/// Attribute selector, i.e. `[name]` or `[name^="value"]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CssAttributeSelector {
+    /// Name of the attribute
+    pub name: String,
+    /// How the value of the attribute has to match, `None` if
+    /// the node only has to have the attribute (`[name]`)
+    pub value: Option<(CssAttributeOperator, String)>,
+}
+
+impl fmt::Display for CssAttributeSelector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            Some((operator, value)) => write!(f, "[{}{}\"{}\"]", self.name, operator, value),
+            None => write!(f, "[{}]", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CssAttributeOperator {
+    /// `[name="value"]` - the value is exactly `value`
+    Equals,
+    /// `[name~="value"]` - the value is a whitespace-separated list that contains `value`
+    ContainsWord,
+    /// `[name|="value"]` - the value is `value` or starts with `value-`
+    DashMatch,
+    /// `[name^="value"]` - the value starts with `value`
+    StartsWith,
+    /// `[name$="value"]` - the value ends with `value`
+    EndsWith,
+    /// `[name*="value"]` - the value contains `value`
+    Contains,
+}
+
+impl CssAttributeOperator {
+    /// Returns whether the value of an attribute matches the value of the selector
+    pub fn matches(&self, attribute_value: &str, selector_value: &str) -> bool {
+        use self::CssAttributeOperator::*;
+        match self {
+            Equals => attribute_value == selector_value,
+            ContainsWord => attribute_value
+                .split_whitespace()
+                .any(|word| word == selector_value),
+            DashMatch => {
+                attribute_value == selector_value
+                    || (attribute_value.starts_with(selector_value)
+                        && attribute_value[selector_value.len()..].starts_with('-'))
+            }
+            StartsWith => !selector_value.is_empty() && attribute_value.starts_with(selector_value),
+            EndsWith => !selector_value.is_empty() && attribute_value.ends_with(selector_value),
+            Contains => !selector_value.is_empty() && attribute_value.contains(selector_value),
+        }
+    }
+}
+
+impl fmt::Display for CssAttributeOperator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::CssAttributeOperator::*;
+        match self {
+            Equals => write!(f, "="),
+            ContainsWord => write!(f, "~="),
+            DashMatch => write!(f, "|="),
+            StartsWith => write!(f, "^="),
+            EndsWith => write!(f, "$="),
+            Contains => write!(f, "*="),
         }
     }
 }
@@ -373,6 +591,14 @@ pub enum CssPathPseudoSelector {
     Last,
     /// `:nth-child`
     NthChild(CssNthChildSelector),
+    /// `:nth-last-child` - same as `:nth-child`, but counts from the last child
+    NthLastChild(CssNthChildSelector),
+    /// `:nth-of-type` - same as `:nth-child`, but only counts siblings of the same node type
+    NthOfType(CssNthChildSelector),
+    /// `:nth-last-of-type` - same as `:nth-of-type`, but counts from the last sibling
+    NthLastOfType(CssNthChildSelector),
+    /// `:empty` - element has no children
+    Empty,
     /// `:hover` - mouse is over element
     Hover,
     /// `:active` - mouse is pressed and over element
@@ -408,6 +634,10 @@ impl fmt::Display for CssPathPseudoSelector {
             First => write!(f, "first"),
             Last => write!(f, "last"),
             NthChild(u) => write!(f, "nth-child({})", u),
+            NthLastChild(u) => write!(f, "nth-last-child({})", u),
+            NthOfType(u) => write!(f, "nth-of-type({})", u),
+            NthLastOfType(u) => write!(f, "nth-last-of-type({})", u),
+            Empty => write!(f, "empty"),
             Hover => write!(f, "hover"),
             Active => write!(f, "active"),
             Focus => write!(f, "focus"),
@@ -437,6 +667,23 @@ impl Css {
         }
     }
 
+    /// Returns the `@keyframes` rule with the given name - if multiple rules
+    /// have the same name, the last one wins
+    pub fn get_keyframes(&self, name: &str) -> Option<&CssKeyframes> {
+        self.stylesheets
+            .iter()
+            .rev()
+            .flat_map(|stylesheet| stylesheet.keyframes.iter().rev())
+            .find(|keyframes| keyframes.name == name)
+    }
+
+    /// Returns all `@font-face` rules of all stylesheets, in source order
+    pub fn get_font_faces(&self) -> impl Iterator<Item = &CssFontFace> {
+        self.stylesheets
+            .iter()
+            .flat_map(|stylesheet| stylesheet.font_faces.iter())
+    }
+
     pub fn rules<'a>(&'a self) -> RuleIterator<'a> {
         RuleIterator {
             current_stylesheet: 0,
@@ -476,51 +723,36 @@ impl Stylesheet {
         Default::default()
     }
 
-    /// Sort the style rules by their weight, so that the rules are applied in the correct order.
+    /// Sort the style rules by their weight, so that the rules are applied in the correct order:
+    /// `!important` rules come last, rules with the same specificity keep their source order.
     /// Should always be called when a new style is loaded from an external source.
     pub fn sort_by_specificity(&mut self) {
         self.rules
-            .sort_by(|a, b| get_specificity(&a.path).cmp(&get_specificity(&b.path)));
+            .sort_by_key(|rule| (rule.important, get_specificity(&rule.path)));
     }
 }
 
-/// Returns specificity of the given css path. Further information can be found on
+/// Returns specificity of the given css path as (ids, classes, types) - attribute selectors
+/// and pseudo-selectors count as classes, the selectors inside of a `:not()` count as if
+/// they weren't negated. Further information can be found on
 /// [the w3 website](http://www.w3.org/TR/selectors/#specificity).
-fn get_specificity(path: &CssPath) -> (usize, usize, usize, usize) {
-    let id_count = path
-        .selectors
-        .iter()
-        .filter(|x| {
-            if let CssPathSelector::Id(_) = x {
-                true
-            } else {
-                false
-            }
-        })
-        .count();
-    let class_count = path
-        .selectors
-        .iter()
-        .filter(|x| {
-            if let CssPathSelector::Class(_) = x {
-                true
-            } else {
-                false
-            }
-        })
-        .count();
-    let div_count = path
-        .selectors
-        .iter()
-        .filter(|x| {
-            if let CssPathSelector::Type(_) = x {
-                true
-            } else {
-                false
-            }
-        })
-        .count();
-    (id_count, class_count, div_count, path.selectors.len())
+pub fn get_specificity(path: &CssPath) -> (usize, usize, usize) {
+    let mut specificity = (0, 0, 0);
+    add_specificity(&path.selectors, &mut specificity);
+    specificity
+}
+
+fn add_specificity(selectors: &[CssPathSelector], specificity: &mut (usize, usize, usize)) {
+    use self::CssPathSelector::*;
+    for selector in selectors {
+        match selector {
+            Id(_) => specificity.0 += 1,
+            Class(_) | Attribute(_) | PseudoSelector(_) => specificity.1 += 1,
+            Type(_) => specificity.2 += 1,
+            Not(selectors) => add_specificity(selectors, specificity),
+            Global | DirectChildren | Children | AdjacentSibling | GeneralSibling => {}
+        }
+    }
 }
 
 #[test]
@@ -530,25 +762,44 @@ fn test_specificity() {
         get_specificity(&CssPath {
             selectors: vec![Id("hello".into())]
         }),
-        (1, 0, 0, 1)
+        (1, 0, 0)
     );
     assert_eq!(
         get_specificity(&CssPath {
             selectors: vec![Class("hello".into())]
         }),
-        (0, 1, 0, 1)
+        (0, 1, 0)
     );
     assert_eq!(
         get_specificity(&CssPath {
             selectors: vec![Type(NodeTypePath::Div)]
         }),
-        (0, 0, 1, 1)
+        (0, 0, 1)
     );
     assert_eq!(
         get_specificity(&CssPath {
             selectors: vec![Id("hello".into()), Type(NodeTypePath::Div)]
         }),
-        (1, 0, 1, 2)
+        (1, 0, 1)
+    );
+    // "* > div.a:hover + [type]:not(#b.c)", the universal selector and combinators don't count
+    assert_eq!(
+        get_specificity(&CssPath {
+            selectors: vec![
+                Global,
+                DirectChildren,
+                Type(NodeTypePath::Div),
+                Class("a".into()),
+                PseudoSelector(CssPathPseudoSelector::Hover),
+                AdjacentSibling,
+                Attribute(CssAttributeSelector {
+                    name: "type".into(),
+                    value: None,
+                }),
+                Not(vec![Id("b".into()), Class("c".into())]),
+            ]
+        }),
+        (1, 4, 1)
     );
 }
 
@@ -566,6 +817,8 @@ fn test_specificity_sort() {
                     selectors: vec![Global],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
@@ -577,18 +830,24 @@ fn test_specificity_sort() {
                     ],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
                     selectors: vec![Global, Type(Div), Id("my_id".into())],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
                     selectors: vec![Global, Id("my_id".into())],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
@@ -600,8 +859,12 @@ fn test_specificity_sort() {
                     ],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
         ],
+        keyframes: Vec::new(),
+        font_faces: Vec::new(),
     };
 
     input_style.sort_by_specificity();
@@ -614,18 +877,24 @@ fn test_specificity_sort() {
                     selectors: vec![Global],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
                     selectors: vec![Global, Id("my_id".into())],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
                     selectors: vec![Global, Type(Div), Id("my_id".into())],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
@@ -637,6 +906,8 @@ fn test_specificity_sort() {
                     ],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
             CssRuleBlock {
                 path: CssPath {
@@ -648,8 +919,12 @@ fn test_specificity_sort() {
                     ],
                 },
                 declarations: Vec::new(),
+                media_queries: Vec::new(),
+                important: false,
             },
         ],
+        keyframes: Vec::new(),
+        font_faces: Vec::new(),
     };
 
     assert_eq!(input_style, expected_style);
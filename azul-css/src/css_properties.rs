@@ -3,12 +3,13 @@
 use crate::css::CssPropertyValue;
 use std::collections::BTreeMap;
 use std::fmt;
+use std::time::Duration;
 
-/// Currently hard-coded: Height of one em in pixels
+/// Default height of one em in pixels, used if the font size is unknown
 pub const EM_HEIGHT: f32 = 16.0;
 pub const PT_TO_PX: f32 = 96.0 / 72.0;
 
-const COMBINED_CSS_PROPERTIES_KEY_MAP: [(CombinedCssPropertyType, &'static str); 10] = [
+const COMBINED_CSS_PROPERTIES_KEY_MAP: [(CombinedCssPropertyType, &'static str); 13] = [
     (CombinedCssPropertyType::BorderRadius, "border-radius"),
     (CombinedCssPropertyType::Overflow, "overflow"),
     (CombinedCssPropertyType::Padding, "padding"),
@@ -19,21 +20,57 @@ const COMBINED_CSS_PROPERTIES_KEY_MAP: [(CombinedCssPropertyType, &'static str);
     (CombinedCssPropertyType::BorderTop, "border-top"),
     (CombinedCssPropertyType::BorderBottom, "border-bottom"),
     (CombinedCssPropertyType::BoxShadow, "box-shadow"),
+    (CombinedCssPropertyType::GridGap, "grid-gap"),
+    (CombinedCssPropertyType::Animation, "animation"),
+    (CombinedCssPropertyType::TextDecoration, "text-decoration"),
 ];
 
 /// Map between CSS keys and a statically typed enum
-const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str); 66] = [
+const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str); 102] = [
     (CssPropertyType::Display, "display"),
     (CssPropertyType::Float, "float"),
     (CssPropertyType::BoxSizing, "box-sizing"),
     (CssPropertyType::TextColor, "color"),
     (CssPropertyType::FontSize, "font-size"),
     (CssPropertyType::FontFamily, "font-family"),
+    (CssPropertyType::FontWeight, "font-weight"),
+    (CssPropertyType::FontStyle, "font-style"),
+    (CssPropertyType::FontStretch, "font-stretch"),
+    (
+        CssPropertyType::FontFeatureSettings,
+        "font-feature-settings",
+    ),
+    (CssPropertyType::FontVariantNumeric, "font-variant-numeric"),
+    (
+        CssPropertyType::FontVariationSettings,
+        "font-variation-settings",
+    ),
     (CssPropertyType::TextAlign, "text-align"),
+    (CssPropertyType::TextDirection, "direction"),
     (CssPropertyType::LetterSpacing, "letter-spacing"),
     (CssPropertyType::LineHeight, "line-height"),
     (CssPropertyType::WordSpacing, "word-spacing"),
     (CssPropertyType::TabWidth, "tab-width"),
+    (CssPropertyType::WordBreak, "word-break"),
+    (CssPropertyType::OverflowWrap, "overflow-wrap"),
+    (CssPropertyType::Hyphens, "hyphens"),
+    (CssPropertyType::WhiteSpace, "white-space"),
+    (CssPropertyType::TextOverflow, "text-overflow"),
+    (CssPropertyType::LineClamp, "line-clamp"),
+    (CssPropertyType::TextDecorationLine, "text-decoration-line"),
+    (
+        CssPropertyType::TextDecorationStyle,
+        "text-decoration-style",
+    ),
+    (
+        CssPropertyType::TextDecorationColor,
+        "text-decoration-color",
+    ),
+    (
+        CssPropertyType::TextDecorationThickness,
+        "text-decoration-thickness",
+    ),
+    (CssPropertyType::TextShadow, "text-shadow"),
     (CssPropertyType::Cursor, "cursor"),
     (CssPropertyType::Width, "width"),
     (CssPropertyType::Height, "height"),
@@ -53,6 +90,15 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str); 66] = [
     (CssPropertyType::JustifyContent, "justify-content"),
     (CssPropertyType::AlignItems, "align-items"),
     (CssPropertyType::AlignContent, "align-content"),
+    (
+        CssPropertyType::GridTemplateColumns,
+        "grid-template-columns",
+    ),
+    (CssPropertyType::GridTemplateRows, "grid-template-rows"),
+    (CssPropertyType::GridColumnGap, "grid-column-gap"),
+    (CssPropertyType::GridRowGap, "grid-row-gap"),
+    (CssPropertyType::GridColumn, "grid-column"),
+    (CssPropertyType::GridRow, "grid-row"),
     (CssPropertyType::OverflowX, "overflow-x"),
     (CssPropertyType::OverflowY, "overflow-y"),
     (CssPropertyType::PaddingTop, "padding-top"),
@@ -101,13 +147,25 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str); 66] = [
     (CssPropertyType::BoxShadowRight, "box-shadow-right"),
     (CssPropertyType::BoxShadowLeft, "box-shadow-left"),
     (CssPropertyType::BoxShadowBottom, "box-shadow-bottom"),
+    (CssPropertyType::Transform, "transform"),
+    (CssPropertyType::TransformOrigin, "transform-origin"),
+    (CssPropertyType::Opacity, "opacity"),
+    (CssPropertyType::Filter, "filter"),
+    (CssPropertyType::Transition, "transition"),
+    (CssPropertyType::AnimationName, "animation-name"),
+    (CssPropertyType::AnimationDuration, "animation-duration"),
+    (CssPropertyType::AnimationTimingFunction, "animation-timing-function"),
+    (CssPropertyType::AnimationDelay, "animation-delay"),
+    (CssPropertyType::AnimationIterationCount, "animation-iteration-count"),
+    (CssPropertyType::AnimationDirection, "animation-direction"),
+    (CssPropertyType::AnimationFillMode, "animation-fill-mode"),
 ];
 
 // The following types are present in webrender, however, azul-css should not
 // depend on webrender, just to have the same types, azul-css should be a standalone crate.
 
 /// Only used for calculations: Rectangle (x, y, width, height) in layout space.
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
 pub struct LayoutRect {
     pub origin: LayoutPoint,
     pub size: LayoutSize,
@@ -208,7 +266,7 @@ impl LayoutRect {
 }
 
 /// Only used for calculations: Size (width, height) in layout space.
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
 pub struct LayoutSize {
     pub width: f32,
     pub height: f32,
@@ -226,7 +284,7 @@ impl LayoutSize {
 }
 
 /// Only used for calculations: Point coordinate (x, y) in layout space.
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, PartialOrd)]
 pub struct LayoutPoint {
     pub x: f32,
     pub y: f32,
@@ -243,6 +301,106 @@ impl LayoutPoint {
     }
 }
 
+/// Only used for calculations: 2D affine transform in layout space. Points are
+/// row vectors (same as `euclid::Transform2D`), so a point is transformed as
+/// `x' = x * m11 + y * m21 + m31` and `y' = x * m12 + y * m22 + m32`.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct LayoutTransform {
+    pub m11: f32,
+    pub m12: f32,
+    pub m21: f32,
+    pub m22: f32,
+    pub m31: f32,
+    pub m32: f32,
+}
+
+impl Default for LayoutTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl LayoutTransform {
+    #[inline(always)]
+    pub const fn new(m11: f32, m12: f32, m21: f32, m22: f32, m31: f32, m32: f32) -> Self {
+        Self {
+            m11,
+            m12,
+            m21,
+            m22,
+            m31,
+            m32,
+        }
+    }
+    #[inline(always)]
+    pub const fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+    #[inline(always)]
+    pub const fn translation(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+    #[inline(always)]
+    pub const fn scale(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+    /// Clockwise rotation (since the y axis points down), in degrees
+    #[inline]
+    pub fn rotation(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+    /// Skew along the x and y axis, in degrees
+    #[inline]
+    pub fn skew(x_degrees: f32, y_degrees: f32) -> Self {
+        Self::new(
+            1.0,
+            y_degrees.to_radians().tan(),
+            x_degrees.to_radians().tan(),
+            1.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// Returns a transform that first applies `self`, then `other`
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            m11: self.m11 * other.m11 + self.m12 * other.m21,
+            m12: self.m11 * other.m12 + self.m12 * other.m22,
+            m21: self.m21 * other.m11 + self.m22 * other.m21,
+            m22: self.m21 * other.m12 + self.m22 * other.m22,
+            m31: self.m31 * other.m11 + self.m32 * other.m21 + other.m31,
+            m32: self.m31 * other.m12 + self.m32 * other.m22 + other.m32,
+        }
+    }
+
+    #[inline]
+    pub fn transform_point(&self, p: LayoutPoint) -> LayoutPoint {
+        LayoutPoint::new(
+            p.x * self.m11 + p.y * self.m21 + self.m31,
+            p.x * self.m12 + p.y * self.m22 + self.m32,
+        )
+    }
+
+    /// Returns the inverse transform, `None` if the transform can't be inverted (i.e. `scale(0)`)
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.m11 * self.m22 - self.m12 * self.m21;
+        if det == 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            m11: self.m22 / det,
+            m12: -self.m12 / det,
+            m21: -self.m21 / det,
+            m22: self.m11 / det,
+            m31: (self.m21 * self.m32 - self.m22 * self.m31) / det,
+            m32: (self.m12 * self.m31 - self.m11 * self.m32) / det,
+        })
+    }
+}
+
 /// Represents a parsed pair of `5px, 10px` values - useful for border radius calculation
 #[derive(Default, Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
 pub struct PixelSize {
@@ -567,6 +725,12 @@ macro_rules! impl_pixel_value {
                 $struct(PixelValue::pt(value))
             }
         }
+
+        impl ToAbsoluteUnits for $struct {
+            fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+                $struct(self.0.to_absolute_units(context))
+            }
+        }
     };
 }
 
@@ -602,6 +766,9 @@ pub enum CombinedCssPropertyType {
     BorderBottom,
     Padding,
     BoxShadow,
+    GridGap,
+    Animation,
+    TextDecoration,
 }
 
 impl fmt::Display for CombinedCssPropertyType {
@@ -666,12 +833,30 @@ pub enum CssPropertyType {
     TextColor,
     FontSize,
     FontFamily,
+    FontWeight,
+    FontStyle,
+    FontStretch,
+    FontFeatureSettings,
+    FontVariantNumeric,
+    FontVariationSettings,
     TextAlign,
+    TextDirection,
 
     LetterSpacing,
     LineHeight,
     WordSpacing,
     TabWidth,
+    WordBreak,
+    OverflowWrap,
+    Hyphens,
+    WhiteSpace,
+    TextOverflow,
+    LineClamp,
+    TextDecorationLine,
+    TextDecorationStyle,
+    TextDecorationColor,
+    TextDecorationThickness,
+    TextShadow,
     Cursor,
 
     Display,
@@ -698,6 +883,13 @@ pub enum CssPropertyType {
     AlignItems,
     AlignContent,
 
+    GridTemplateColumns,
+    GridTemplateRows,
+    GridColumnGap,
+    GridRowGap,
+    GridColumn,
+    GridRow,
+
     OverflowX,
     OverflowY,
 
@@ -742,6 +934,21 @@ pub enum CssPropertyType {
     BoxShadowRight,
     BoxShadowTop,
     BoxShadowBottom,
+
+    Transform,
+    TransformOrigin,
+
+    Opacity,
+    Filter,
+
+    Transition,
+    AnimationName,
+    AnimationDuration,
+    AnimationTimingFunction,
+    AnimationDelay,
+    AnimationIterationCount,
+    AnimationDirection,
+    AnimationFillMode,
 }
 
 impl CssPropertyType {
@@ -774,7 +981,16 @@ impl CssPropertyType {
     pub fn is_inheritable(&self) -> bool {
         use self::CssPropertyType::*;
         match self {
-            TextColor | FontFamily | FontSize | LineHeight | TextAlign => true,
+            TextColor | FontFamily | FontSize | FontWeight | FontStyle | FontStretch
+            | LineHeight | TextAlign | TextDirection | WordBreak | OverflowWrap | Hyphens
+            | WhiteSpace | TextShadow => true,
+            FontFeatureSettings | FontVariantNumeric | FontVariationSettings => true,
+            // Not inherited in CSS, but text decorations have to be propagated
+            // from the element to the text nodes inside of it
+            TextDecorationLine
+            | TextDecorationStyle
+            | TextDecorationColor
+            | TextDecorationThickness => true,
             _ => false,
         }
     }
@@ -812,7 +1028,19 @@ impl CssPropertyType {
             | BoxShadowLeft
             | BoxShadowRight
             | BoxShadowTop
-            | BoxShadowBottom => false,
+            | BoxShadowBottom
+            | Transform
+            | TransformOrigin
+            | Opacity
+            | Filter
+            | Transition
+            | AnimationName
+            | AnimationDuration
+            | AnimationTimingFunction
+            | AnimationDelay
+            | AnimationIterationCount
+            | AnimationDirection
+            | AnimationFillMode => false,
             _ => true,
         }
     }
@@ -835,12 +1063,30 @@ pub enum CssProperty {
     TextColor(CssPropertyValue<StyleTextColor>),
     FontSize(CssPropertyValue<StyleFontSize>),
     FontFamily(CssPropertyValue<StyleFontFamily>),
+    FontWeight(CssPropertyValue<StyleFontWeight>),
+    FontStyle(CssPropertyValue<StyleFontStyle>),
+    FontStretch(CssPropertyValue<StyleFontStretch>),
+    FontFeatureSettings(CssPropertyValue<StyleFontFeatureSettings>),
+    FontVariantNumeric(CssPropertyValue<StyleFontVariantNumeric>),
+    FontVariationSettings(CssPropertyValue<StyleFontVariationSettings>),
     TextAlign(CssPropertyValue<StyleTextAlignmentHorz>),
+    TextDirection(CssPropertyValue<StyleTextDirection>),
 
     LetterSpacing(CssPropertyValue<StyleLetterSpacing>),
     LineHeight(CssPropertyValue<StyleLineHeight>),
     WordSpacing(CssPropertyValue<StyleWordSpacing>),
     TabWidth(CssPropertyValue<StyleTabWidth>),
+    WordBreak(CssPropertyValue<StyleWordBreak>),
+    OverflowWrap(CssPropertyValue<StyleOverflowWrap>),
+    Hyphens(CssPropertyValue<StyleHyphens>),
+    WhiteSpace(CssPropertyValue<StyleWhiteSpace>),
+    TextOverflow(CssPropertyValue<StyleTextOverflow>),
+    LineClamp(CssPropertyValue<StyleLineClamp>),
+    TextDecorationLine(CssPropertyValue<StyleTextDecorationLine>),
+    TextDecorationStyle(CssPropertyValue<StyleTextDecorationStyle>),
+    TextDecorationColor(CssPropertyValue<StyleTextDecorationColor>),
+    TextDecorationThickness(CssPropertyValue<StyleTextDecorationThickness>),
+    TextShadow(CssPropertyValue<StyleTextShadow>),
     Cursor(CssPropertyValue<StyleCursor>),
 
     Display(CssPropertyValue<LayoutDisplay>),
@@ -868,6 +1114,13 @@ pub enum CssProperty {
     AlignItems(CssPropertyValue<LayoutAlignItems>),
     AlignContent(CssPropertyValue<LayoutAlignContent>),
 
+    GridTemplateColumns(CssPropertyValue<LayoutGridTemplateColumns>),
+    GridTemplateRows(CssPropertyValue<LayoutGridTemplateRows>),
+    GridColumnGap(CssPropertyValue<LayoutGridColumnGap>),
+    GridRowGap(CssPropertyValue<LayoutGridRowGap>),
+    GridColumn(CssPropertyValue<LayoutGridColumn>),
+    GridRow(CssPropertyValue<LayoutGridRow>),
+
     BackgroundContent(CssPropertyValue<StyleBackgroundContent>),
     BackgroundPosition(CssPropertyValue<StyleBackgroundPosition>),
     BackgroundSize(CssPropertyValue<StyleBackgroundSize>),
@@ -910,6 +1163,21 @@ pub enum CssProperty {
     BoxShadowRight(CssPropertyValue<BoxShadowPreDisplayItem>),
     BoxShadowTop(CssPropertyValue<BoxShadowPreDisplayItem>),
     BoxShadowBottom(CssPropertyValue<BoxShadowPreDisplayItem>),
+
+    Transform(CssPropertyValue<StyleTransform>),
+    TransformOrigin(CssPropertyValue<StyleTransformOrigin>),
+
+    Opacity(CssPropertyValue<StyleOpacity>),
+    Filter(CssPropertyValue<StyleFilter>),
+
+    Transition(CssPropertyValue<StyleTransition>),
+    AnimationName(CssPropertyValue<StyleAnimationName>),
+    AnimationDuration(CssPropertyValue<StyleAnimationDuration>),
+    AnimationTimingFunction(CssPropertyValue<StyleAnimationTimingFunction>),
+    AnimationDelay(CssPropertyValue<StyleAnimationDelay>),
+    AnimationIterationCount(CssPropertyValue<StyleAnimationIterationCount>),
+    AnimationDirection(CssPropertyValue<StyleAnimationDirection>),
+    AnimationFillMode(CssPropertyValue<StyleAnimationFillMode>),
 }
 
 macro_rules! css_property_from_type {
@@ -918,7 +1186,24 @@ macro_rules! css_property_from_type {
             CssPropertyType::TextColor => CssProperty::TextColor(CssPropertyValue::$content_type),
             CssPropertyType::FontSize => CssProperty::FontSize(CssPropertyValue::$content_type),
             CssPropertyType::FontFamily => CssProperty::FontFamily(CssPropertyValue::$content_type),
+            CssPropertyType::FontWeight => CssProperty::FontWeight(CssPropertyValue::$content_type),
+            CssPropertyType::FontStyle => CssProperty::FontStyle(CssPropertyValue::$content_type),
+            CssPropertyType::FontStretch => {
+                CssProperty::FontStretch(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::FontFeatureSettings => {
+                CssProperty::FontFeatureSettings(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::FontVariantNumeric => {
+                CssProperty::FontVariantNumeric(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::FontVariationSettings => {
+                CssProperty::FontVariationSettings(CssPropertyValue::$content_type)
+            }
             CssPropertyType::TextAlign => CssProperty::TextAlign(CssPropertyValue::$content_type),
+            CssPropertyType::TextDirection => {
+                CssProperty::TextDirection(CssPropertyValue::$content_type)
+            }
             CssPropertyType::LetterSpacing => {
                 CssProperty::LetterSpacing(CssPropertyValue::$content_type)
             }
@@ -927,6 +1212,29 @@ macro_rules! css_property_from_type {
                 CssProperty::WordSpacing(CssPropertyValue::$content_type)
             }
             CssPropertyType::TabWidth => CssProperty::TabWidth(CssPropertyValue::$content_type),
+            CssPropertyType::WordBreak => CssProperty::WordBreak(CssPropertyValue::$content_type),
+            CssPropertyType::OverflowWrap => {
+                CssProperty::OverflowWrap(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::Hyphens => CssProperty::Hyphens(CssPropertyValue::$content_type),
+            CssPropertyType::WhiteSpace => CssProperty::WhiteSpace(CssPropertyValue::$content_type),
+            CssPropertyType::TextOverflow => {
+                CssProperty::TextOverflow(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::LineClamp => CssProperty::LineClamp(CssPropertyValue::$content_type),
+            CssPropertyType::TextDecorationLine => {
+                CssProperty::TextDecorationLine(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::TextDecorationStyle => {
+                CssProperty::TextDecorationStyle(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::TextDecorationColor => {
+                CssProperty::TextDecorationColor(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::TextDecorationThickness => {
+                CssProperty::TextDecorationThickness(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::TextShadow => CssProperty::TextShadow(CssPropertyValue::$content_type),
             CssPropertyType::Cursor => CssProperty::Cursor(CssPropertyValue::$content_type),
             CssPropertyType::Display => CssProperty::Display(CssPropertyValue::$content_type),
             CssPropertyType::Float => CssProperty::Float(CssPropertyValue::$content_type),
@@ -955,6 +1263,20 @@ macro_rules! css_property_from_type {
             CssPropertyType::AlignContent => {
                 CssProperty::AlignContent(CssPropertyValue::$content_type)
             }
+            CssPropertyType::GridTemplateColumns => {
+                CssProperty::GridTemplateColumns(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::GridTemplateRows => {
+                CssProperty::GridTemplateRows(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::GridColumnGap => {
+                CssProperty::GridColumnGap(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::GridRowGap => {
+                CssProperty::GridRowGap(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::GridColumn => CssProperty::GridColumn(CssPropertyValue::$content_type),
+            CssPropertyType::GridRow => CssProperty::GridRow(CssPropertyValue::$content_type),
             CssPropertyType::OverflowX => CssProperty::OverflowX(CssPropertyValue::$content_type),
             CssPropertyType::OverflowY => CssProperty::OverflowY(CssPropertyValue::$content_type),
             CssPropertyType::PaddingTop => CssProperty::PaddingTop(CssPropertyValue::$content_type),
@@ -1053,6 +1375,34 @@ macro_rules! css_property_from_type {
             CssPropertyType::BoxShadowBottom => {
                 CssProperty::BoxShadowBottom(CssPropertyValue::$content_type)
             }
+            CssPropertyType::Transform => CssProperty::Transform(CssPropertyValue::$content_type),
+            CssPropertyType::TransformOrigin => {
+                CssProperty::TransformOrigin(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::Opacity => CssProperty::Opacity(CssPropertyValue::$content_type),
+            CssPropertyType::Filter => CssProperty::Filter(CssPropertyValue::$content_type),
+            CssPropertyType::Transition => CssProperty::Transition(CssPropertyValue::$content_type),
+            CssPropertyType::AnimationName => {
+                CssProperty::AnimationName(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::AnimationDuration => {
+                CssProperty::AnimationDuration(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::AnimationTimingFunction => {
+                CssProperty::AnimationTimingFunction(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::AnimationDelay => {
+                CssProperty::AnimationDelay(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::AnimationIterationCount => {
+                CssProperty::AnimationIterationCount(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::AnimationDirection => {
+                CssProperty::AnimationDirection(CssPropertyValue::$content_type)
+            }
+            CssPropertyType::AnimationFillMode => {
+                CssProperty::AnimationFillMode(CssPropertyValue::$content_type)
+            }
         }
     }};
 }
@@ -1064,11 +1414,29 @@ impl CssProperty {
             CssProperty::TextColor(_) => CssPropertyType::TextColor,
             CssProperty::FontSize(_) => CssPropertyType::FontSize,
             CssProperty::FontFamily(_) => CssPropertyType::FontFamily,
+            CssProperty::FontWeight(_) => CssPropertyType::FontWeight,
+            CssProperty::FontStyle(_) => CssPropertyType::FontStyle,
+            CssProperty::FontStretch(_) => CssPropertyType::FontStretch,
+            CssProperty::FontFeatureSettings(_) => CssPropertyType::FontFeatureSettings,
+            CssProperty::FontVariantNumeric(_) => CssPropertyType::FontVariantNumeric,
+            CssProperty::FontVariationSettings(_) => CssPropertyType::FontVariationSettings,
             CssProperty::TextAlign(_) => CssPropertyType::TextAlign,
+            CssProperty::TextDirection(_) => CssPropertyType::TextDirection,
             CssProperty::LetterSpacing(_) => CssPropertyType::LetterSpacing,
             CssProperty::LineHeight(_) => CssPropertyType::LineHeight,
             CssProperty::WordSpacing(_) => CssPropertyType::WordSpacing,
             CssProperty::TabWidth(_) => CssPropertyType::TabWidth,
+            CssProperty::WordBreak(_) => CssPropertyType::WordBreak,
+            CssProperty::OverflowWrap(_) => CssPropertyType::OverflowWrap,
+            CssProperty::Hyphens(_) => CssPropertyType::Hyphens,
+            CssProperty::WhiteSpace(_) => CssPropertyType::WhiteSpace,
+            CssProperty::TextOverflow(_) => CssPropertyType::TextOverflow,
+            CssProperty::LineClamp(_) => CssPropertyType::LineClamp,
+            CssProperty::TextDecorationLine(_) => CssPropertyType::TextDecorationLine,
+            CssProperty::TextDecorationStyle(_) => CssPropertyType::TextDecorationStyle,
+            CssProperty::TextDecorationColor(_) => CssPropertyType::TextDecorationColor,
+            CssProperty::TextDecorationThickness(_) => CssPropertyType::TextDecorationThickness,
+            CssProperty::TextShadow(_) => CssPropertyType::TextShadow,
             CssProperty::Cursor(_) => CssPropertyType::Cursor,
             CssProperty::Display(_) => CssPropertyType::Display,
             CssProperty::Float(_) => CssPropertyType::Float,
@@ -1092,6 +1460,13 @@ impl CssProperty {
             CssProperty::AlignItems(_) => CssPropertyType::AlignItems,
             CssProperty::AlignContent(_) => CssPropertyType::AlignContent,
 
+            CssProperty::GridTemplateColumns(_) => CssPropertyType::GridTemplateColumns,
+            CssProperty::GridTemplateRows(_) => CssPropertyType::GridTemplateRows,
+            CssProperty::GridColumnGap(_) => CssPropertyType::GridColumnGap,
+            CssProperty::GridRowGap(_) => CssPropertyType::GridRowGap,
+            CssProperty::GridColumn(_) => CssPropertyType::GridColumn,
+            CssProperty::GridRow(_) => CssPropertyType::GridRow,
+
             CssProperty::BackgroundContent(_) => CssPropertyType::BackgroundImage, // TODO: wrong!
             CssProperty::BackgroundPosition(_) => CssPropertyType::BackgroundPosition,
             CssProperty::BackgroundSize(_) => CssPropertyType::BackgroundSize,
@@ -1127,6 +1502,18 @@ impl CssProperty {
             CssProperty::BoxShadowRight(_) => CssPropertyType::BoxShadowRight,
             CssProperty::BoxShadowTop(_) => CssPropertyType::BoxShadowTop,
             CssProperty::BoxShadowBottom(_) => CssPropertyType::BoxShadowBottom,
+            CssProperty::Transform(_) => CssPropertyType::Transform,
+            CssProperty::TransformOrigin(_) => CssPropertyType::TransformOrigin,
+            CssProperty::Opacity(_) => CssPropertyType::Opacity,
+            CssProperty::Filter(_) => CssPropertyType::Filter,
+            CssProperty::Transition(_) => CssPropertyType::Transition,
+            CssProperty::AnimationName(_) => CssPropertyType::AnimationName,
+            CssProperty::AnimationDuration(_) => CssPropertyType::AnimationDuration,
+            CssProperty::AnimationTimingFunction(_) => CssPropertyType::AnimationTimingFunction,
+            CssProperty::AnimationDelay(_) => CssPropertyType::AnimationDelay,
+            CssProperty::AnimationIterationCount(_) => CssPropertyType::AnimationIterationCount,
+            CssProperty::AnimationDirection(_) => CssPropertyType::AnimationDirection,
+            CssProperty::AnimationFillMode(_) => CssPropertyType::AnimationFillMode,
         }
     }
 
@@ -1160,11 +1547,35 @@ macro_rules! impl_from_css_prop {
 impl_from_css_prop!(StyleTextColor, CssProperty::TextColor);
 impl_from_css_prop!(StyleFontSize, CssProperty::FontSize);
 impl_from_css_prop!(StyleFontFamily, CssProperty::FontFamily);
+impl_from_css_prop!(StyleFontWeight, CssProperty::FontWeight);
+impl_from_css_prop!(StyleFontStyle, CssProperty::FontStyle);
+impl_from_css_prop!(StyleFontStretch, CssProperty::FontStretch);
+impl_from_css_prop!(StyleFontFeatureSettings, CssProperty::FontFeatureSettings);
+impl_from_css_prop!(StyleFontVariantNumeric, CssProperty::FontVariantNumeric);
+impl_from_css_prop!(
+    StyleFontVariationSettings,
+    CssProperty::FontVariationSettings
+);
 impl_from_css_prop!(StyleTextAlignmentHorz, CssProperty::TextAlign);
+impl_from_css_prop!(StyleTextDirection, CssProperty::TextDirection);
 impl_from_css_prop!(StyleLetterSpacing, CssProperty::LetterSpacing);
 impl_from_css_prop!(StyleLineHeight, CssProperty::LineHeight);
 impl_from_css_prop!(StyleWordSpacing, CssProperty::WordSpacing);
 impl_from_css_prop!(StyleTabWidth, CssProperty::TabWidth);
+impl_from_css_prop!(StyleWordBreak, CssProperty::WordBreak);
+impl_from_css_prop!(StyleOverflowWrap, CssProperty::OverflowWrap);
+impl_from_css_prop!(StyleHyphens, CssProperty::Hyphens);
+impl_from_css_prop!(StyleWhiteSpace, CssProperty::WhiteSpace);
+impl_from_css_prop!(StyleTextOverflow, CssProperty::TextOverflow);
+impl_from_css_prop!(StyleLineClamp, CssProperty::LineClamp);
+impl_from_css_prop!(StyleTextDecorationLine, CssProperty::TextDecorationLine);
+impl_from_css_prop!(StyleTextDecorationStyle, CssProperty::TextDecorationStyle);
+impl_from_css_prop!(StyleTextDecorationColor, CssProperty::TextDecorationColor);
+impl_from_css_prop!(
+    StyleTextDecorationThickness,
+    CssProperty::TextDecorationThickness
+);
+impl_from_css_prop!(StyleTextShadow, CssProperty::TextShadow);
 impl_from_css_prop!(StyleCursor, CssProperty::Cursor);
 impl_from_css_prop!(LayoutDisplay, CssProperty::Display);
 impl_from_css_prop!(LayoutFloat, CssProperty::Float);
@@ -1187,6 +1598,12 @@ impl_from_css_prop!(LayoutFlexShrink, CssProperty::FlexShrink);
 impl_from_css_prop!(LayoutJustifyContent, CssProperty::JustifyContent);
 impl_from_css_prop!(LayoutAlignItems, CssProperty::AlignItems);
 impl_from_css_prop!(LayoutAlignContent, CssProperty::AlignContent);
+impl_from_css_prop!(LayoutGridTemplateColumns, CssProperty::GridTemplateColumns);
+impl_from_css_prop!(LayoutGridTemplateRows, CssProperty::GridTemplateRows);
+impl_from_css_prop!(LayoutGridColumnGap, CssProperty::GridColumnGap);
+impl_from_css_prop!(LayoutGridRowGap, CssProperty::GridRowGap);
+impl_from_css_prop!(LayoutGridColumn, CssProperty::GridColumn);
+impl_from_css_prop!(LayoutGridRow, CssProperty::GridRow);
 impl_from_css_prop!(StyleBackgroundContent, CssProperty::BackgroundContent);
 impl_from_css_prop!(StyleBackgroundPosition, CssProperty::BackgroundPosition);
 impl_from_css_prop!(StyleBackgroundSize, CssProperty::BackgroundSize);
@@ -1221,6 +1638,24 @@ impl_from_css_prop!(StyleBorderTopWidth, CssProperty::BorderTopWidth);
 impl_from_css_prop!(StyleBorderRightWidth, CssProperty::BorderRightWidth);
 impl_from_css_prop!(StyleBorderLeftWidth, CssProperty::BorderLeftWidth);
 impl_from_css_prop!(StyleBorderBottomWidth, CssProperty::BorderBottomWidth);
+impl_from_css_prop!(StyleTransform, CssProperty::Transform);
+impl_from_css_prop!(StyleTransformOrigin, CssProperty::TransformOrigin);
+impl_from_css_prop!(StyleOpacity, CssProperty::Opacity);
+impl_from_css_prop!(StyleFilter, CssProperty::Filter);
+impl_from_css_prop!(StyleTransition, CssProperty::Transition);
+impl_from_css_prop!(StyleAnimationName, CssProperty::AnimationName);
+impl_from_css_prop!(StyleAnimationDuration, CssProperty::AnimationDuration);
+impl_from_css_prop!(
+    StyleAnimationTimingFunction,
+    CssProperty::AnimationTimingFunction
+);
+impl_from_css_prop!(StyleAnimationDelay, CssProperty::AnimationDelay);
+impl_from_css_prop!(
+    StyleAnimationIterationCount,
+    CssProperty::AnimationIterationCount
+);
+impl_from_css_prop!(StyleAnimationDirection, CssProperty::AnimationDirection);
+impl_from_css_prop!(StyleAnimationFillMode, CssProperty::AnimationFillMode);
 
 /// Multiplier for floating point accuracy. Elements such as px or %
 /// are only accurate until a certain number of decimal points, therefore
@@ -1256,7 +1691,11 @@ pub struct PixelValue {
 // Manual Debug implementation, because the auto-generated one is nearly unreadable
 impl fmt::Display for PixelValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}{}", self.number, self.metric)
+        match self.metric {
+            SizeMetric::Calc(calc) if self.number.get() == 1.0 => write!(f, "{}", calc),
+            SizeMetric::Calc(calc) => write!(f, "calc({} * {})", self.number, calc),
+            _ => write!(f, "{}{}", self.number, self.metric),
+        }
     }
 }
 
@@ -1266,8 +1705,14 @@ impl fmt::Display for SizeMetric {
         match self {
             Px => write!(f, "px"),
             Pt => write!(f, "pt"),
-            Em => write!(f, "pt"),
+            Em => write!(f, "em"),
+            Rem => write!(f, "rem"),
+            Vw => write!(f, "vw"),
+            Vh => write!(f, "vh"),
+            Vmin => write!(f, "vmin"),
+            Vmax => write!(f, "vmax"),
             Percent => write!(f, "%"),
+            Calc(calc) => write!(f, "{}", calc),
         }
     }
 }
@@ -1335,6 +1780,37 @@ impl PixelValue {
         Self::from_metric(SizeMetric::Percent, value)
     }
 
+    #[inline]
+    pub fn rem(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Rem, value)
+    }
+
+    #[inline]
+    pub fn vw(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vw, value)
+    }
+
+    #[inline]
+    pub fn vh(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vh, value)
+    }
+
+    #[inline]
+    pub fn vmin(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vmin, value)
+    }
+
+    #[inline]
+    pub fn vmax(value: f32) -> Self {
+        Self::from_metric(SizeMetric::Vmax, value)
+    }
+
+    /// Creates a value from a `calc()` expression
+    #[inline]
+    pub fn calc(calc: CalcValue) -> Self {
+        Self::from_metric(SizeMetric::Calc(calc), 1.0)
+    }
+
     #[inline]
     pub fn from_metric(metric: SizeMetric, value: f32) -> Self {
         Self {
@@ -1343,170 +1819,1024 @@ impl PixelValue {
         }
     }
 
-    /// Returns the value of the SizeMetric in pixels
+    /// Returns the value of the SizeMetric in pixels, resolves `em` and `rem`
+    /// against the default font size and viewport-relative units to 0
     #[inline]
     pub fn to_pixels(&self, percent_resolve: f32) -> f32 {
-        match self.metric {
-            SizeMetric::Px => self.number.get(),
-            SizeMetric::Pt => self.number.get() * PT_TO_PX,
-            SizeMetric::Em => self.number.get() * EM_HEIGHT,
-            SizeMetric::Percent => self.number.get() / 100.0 * percent_resolve,
-        }
+        self.resolve(percent_resolve, &SizeResolveContext::default())
     }
-}
 
-/// Wrapper around FloatValue, represents a percentage instead
-/// of just being a regular floating-point value, i.e `5` = `5%`
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct PercentageValue {
-    number: FloatValue,
-}
+    /// Returns the value of the SizeMetric in pixels
+    #[inline]
+    pub fn resolve(&self, percent_resolve: f32, context: &SizeResolveContext) -> f32 {
+        let (pixels, percent) = self.resolve_relative_units(context);
+        pixels + percent / 100.0 * percent_resolve
+    }
 
-impl fmt::Display for PercentageValue {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}%", self.get())
+    /// Resolves all units except for `%` to pixels, returns `(pixels, percent)`.
+    /// Percentages can only be resolved once the size of the parent is known,
+    /// i.e. during the layout.
+    pub fn resolve_relative_units(&self, context: &SizeResolveContext) -> (f32, f32) {
+        let number = self.number.get();
+        match self.metric {
+            SizeMetric::Percent => (0.0, number),
+            SizeMetric::Calc(calc) => {
+                let (pixels, percent) = calc.resolve_relative_units(context);
+                (pixels * number, percent * number)
+            }
+            other => (number * other.to_pixels(context), 0.0),
+        }
     }
 }
 
-impl PercentageValue {
-    /// Same as `PercentageValue::new()`, but only accepts whole numbers,
-    /// since using `f32` in const fn is not yet stabilized.
-    pub const fn const_new(value: isize) -> Self {
+/// Sizes that relative units (`em`, `rem`, `vw`, `vh`, `vmin`, `vmax`) are resolved against
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct SizeResolveContext {
+    /// Font size of the current node in pixels (`em`)
+    pub font_size_px: f32,
+    /// Font size of the root node in pixels (`rem`)
+    pub root_font_size_px: f32,
+    /// Width of the viewport in pixels (`vw`)
+    pub viewport_width: f32,
+    /// Height of the viewport in pixels (`vh`)
+    pub viewport_height: f32,
+}
+
+impl Default for SizeResolveContext {
+    fn default() -> Self {
         Self {
-            number: FloatValue::const_new(value),
+            font_size_px: EM_HEIGHT,
+            root_font_size_px: EM_HEIGHT,
+            viewport_width: 0.0,
+            viewport_height: 0.0,
         }
     }
+}
 
-    pub fn new(value: f32) -> Self {
-        Self {
-            number: value.into(),
+impl SizeResolveContext {
+    /// Returns the context of the root node with the `font_size` (`None` for the default
+    /// font size), `rem` is resolved against the font size of the root node
+    pub fn root(font_size: Option<PixelValue>, viewport_width: f32, viewport_height: f32) -> Self {
+        let initial = SizeResolveContext {
+            viewport_width,
+            viewport_height,
+            ..Default::default()
+        };
+        let root = initial.child(font_size);
+        SizeResolveContext {
+            root_font_size_px: root.font_size_px,
+            ..root
         }
     }
 
-    pub fn get(&self) -> f32 {
-        self.number.get()
+    /// Returns the context of a child node with the `font_size` (`None` if the font size
+    /// is inherited): `em` and `%` of the `font-size` are relative to the font size of the
+    /// parent node, so the font sizes have to be resolved from the root node downwards
+    pub fn child(&self, font_size: Option<PixelValue>) -> Self {
+        SizeResolveContext {
+            font_size_px: font_size
+                .map(|font_size| font_size.resolve(self.font_size_px, self))
+                .unwrap_or(self.font_size_px),
+            ..*self
+        }
     }
 }
 
-/// Wrapper around an f32 value that is internally casted to an isize,
-/// in order to provide hash-ability (to avoid numerical instability).
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct FloatValue {
-    pub number: isize,
+/// Values that can contain lengths relative to the font size or the viewport
+pub trait ToAbsoluteUnits {
+    /// Returns the value with `em`, `rem`, `vw`, `vh`, `vmin` and `vmax` resolved to `px`.
+    /// Percentages are kept, they can only be resolved once the layout is known.
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self;
 }
 
-impl fmt::Display for FloatValue {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.get())
+impl ToAbsoluteUnits for PixelValue {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        match self.metric {
+            SizeMetric::Px | SizeMetric::Pt | SizeMetric::Percent => *self,
+            _ => match self.resolve_relative_units(context) {
+                (pixels, percent) if percent == 0.0 => PixelValue::px(pixels),
+                (pixels, percent) => PixelValue::calc(CalcValue {
+                    px: FloatValue::new(pixels),
+                    percent: FloatValue::new(percent),
+                    ..CalcValue::default()
+                }),
+            },
+        }
     }
 }
 
-impl Default for FloatValue {
-    fn default() -> Self {
-        const DEFAULT_FLV: FloatValue = FloatValue::const_new(0);
-        DEFAULT_FLV
+impl ToAbsoluteUnits for PixelValueNoPercent {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        PixelValueNoPercent(self.0.to_absolute_units(context))
     }
 }
 
-impl FloatValue {
-    /// Same as `FloatValue::new()`, but only accepts whole numbers,
-    /// since using `f32` in const fn is not yet stabilized.
-    pub const fn const_new(value: isize) -> Self {
-        Self {
-            number: value * FP_PRECISION_MULTIPLIER_CONST,
+impl<T: ToAbsoluteUnits> ToAbsoluteUnits for CssPropertyValue<T> {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        match self {
+            CssPropertyValue::Exact(value) => {
+                CssPropertyValue::Exact(value.to_absolute_units(context))
+            }
+            CssPropertyValue::Auto => CssPropertyValue::Auto,
+            CssPropertyValue::None => CssPropertyValue::None,
+            CssPropertyValue::Initial => CssPropertyValue::Initial,
+            CssPropertyValue::Inherit => CssPropertyValue::Inherit,
         }
     }
+}
 
-    pub fn new(value: f32) -> Self {
-        Self {
-            number: (value * FP_PRECISION_MULTIPLIER) as isize,
+impl ToAbsoluteUnits for StyleBackgroundPosition {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        StyleBackgroundPosition {
+            horizontal: match self.horizontal {
+                BackgroundPositionHorizontal::Exact(e) => {
+                    BackgroundPositionHorizontal::Exact(e.to_absolute_units(context))
+                }
+                other => other,
+            },
+            vertical: match self.vertical {
+                BackgroundPositionVertical::Exact(e) => {
+                    BackgroundPositionVertical::Exact(e.to_absolute_units(context))
+                }
+                other => other,
+            },
         }
     }
+}
 
-    pub fn get(&self) -> f32 {
-        self.number as f32 / FP_PRECISION_MULTIPLIER
+impl ToAbsoluteUnits for StyleBackgroundSize {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        match self {
+            StyleBackgroundSize::ExactSize(w, h) => StyleBackgroundSize::ExactSize(
+                w.to_absolute_units(context),
+                h.to_absolute_units(context),
+            ),
+            other => *other,
+        }
     }
 }
 
-impl From<f32> for FloatValue {
-    fn from(val: f32) -> Self {
-        Self::new(val)
+impl ToAbsoluteUnits for StyleTransform {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        StyleTransform(
+            self.0
+                .iter()
+                .map(|function| match function {
+                    StyleTransformFunction::Translate(x, y) => StyleTransformFunction::Translate(
+                        x.to_absolute_units(context),
+                        y.to_absolute_units(context),
+                    ),
+                    other => *other,
+                })
+                .collect(),
+        )
     }
 }
 
-/// Enum representing the metric associated with a number (px, pt, em, etc.)
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum SizeMetric {
-    Px,
-    Pt,
-    Em,
-    Percent,
+impl ToAbsoluteUnits for StyleTransformOrigin {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        StyleTransformOrigin {
+            x: self.x.to_absolute_units(context),
+            y: self.y.to_absolute_units(context),
+        }
+    }
 }
 
-impl Default for SizeMetric {
-    fn default() -> Self {
-        SizeMetric::Px
+impl ToAbsoluteUnits for StyleDropShadow {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        StyleDropShadow {
+            offset: [
+                self.offset[0].to_absolute_units(context),
+                self.offset[1].to_absolute_units(context),
+            ],
+            color: self.color,
+            blur_radius: self.blur_radius.to_absolute_units(context),
+        }
     }
 }
 
-/// Represents a `background-size` attribute
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum StyleBackgroundSize {
-    ExactSize(PixelValue, PixelValue),
-    Contain,
-    Cover,
+impl ToAbsoluteUnits for StyleFilter {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        StyleFilter(
+            self.0
+                .iter()
+                .map(|function| match function {
+                    StyleFilterFunction::Blur(radius) => {
+                        StyleFilterFunction::Blur(radius.to_absolute_units(context))
+                    }
+                    StyleFilterFunction::DropShadow(shadow) => {
+                        StyleFilterFunction::DropShadow(shadow.to_absolute_units(context))
+                    }
+                    other => *other,
+                })
+                .collect(),
+        )
+    }
 }
 
-/// Represents a `background-position` attribute
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct StyleBackgroundPosition {
-    pub horizontal: BackgroundPositionHorizontal,
-    pub vertical: BackgroundPositionVertical,
+impl ToAbsoluteUnits for StyleTextShadow {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        StyleTextShadow(
+            self.0
+                .iter()
+                .map(|shadow| shadow.to_absolute_units(context))
+                .collect(),
+        )
+    }
 }
 
-impl Default for StyleBackgroundPosition {
-    fn default() -> Self {
-        StyleBackgroundPosition {
-            horizontal: BackgroundPositionHorizontal::Left,
-            vertical: BackgroundPositionVertical::Top,
+impl ToAbsoluteUnits for StyleTextDecorationThickness {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        match self {
+            StyleTextDecorationThickness::Length(l) => {
+                StyleTextDecorationThickness::Length(l.to_absolute_units(context))
+            }
+            StyleTextDecorationThickness::FromFont => StyleTextDecorationThickness::FromFont,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum BackgroundPositionHorizontal {
-    Left,
-    Center,
-    Right,
-    Exact(PixelValue),
+impl ToAbsoluteUnits for BoxShadowPreDisplayItem {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        BoxShadowPreDisplayItem {
+            offset: [
+                self.offset[0].to_absolute_units(context),
+                self.offset[1].to_absolute_units(context),
+            ],
+            blur_radius: self.blur_radius.to_absolute_units(context),
+            spread_radius: self.spread_radius.to_absolute_units(context),
+            ..*self
+        }
+    }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum BackgroundPositionVertical {
-    Top,
-    Center,
-    Bottom,
-    Exact(PixelValue),
+impl ToAbsoluteUnits for GridTemplate {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        let resolve_breadth = |breadth: GridTrackBreadth| match breadth {
+            GridTrackBreadth::Fixed(f) => GridTrackBreadth::Fixed(f.to_absolute_units(context)),
+            other => other,
+        };
+        GridTemplate {
+            tracks: self
+                .tracks
+                .iter()
+                .map(|track| GridTrackSize {
+                    min: resolve_breadth(track.min),
+                    max: resolve_breadth(track.max),
+                })
+                .collect(),
+        }
+    }
 }
 
-/// Represents a `background-repeat` attribute
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum StyleBackgroundRepeat {
-    NoRepeat,
-    Repeat,
-    RepeatX,
-    RepeatY,
+impl ToAbsoluteUnits for LayoutGridTemplateColumns {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        LayoutGridTemplateColumns(self.0.to_absolute_units(context))
+    }
 }
 
-impl Default for StyleBackgroundRepeat {
-    fn default() -> Self {
-        StyleBackgroundRepeat::Repeat
+impl ToAbsoluteUnits for LayoutGridTemplateRows {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        LayoutGridTemplateRows(self.0.to_absolute_units(context))
     }
 }
 
-/// Represents a `color` attribute
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct StyleTextColor(pub ColorU);
+impl ToAbsoluteUnits for CssProperty {
+    /// Properties without lengths are returned unchanged. Note that the `context` has to
+    /// contain the resolved font size of the node, the `font-size` property itself is
+    /// replaced by it (see `SizeResolveContext::child`).
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        use self::CssProperty::*;
+
+        macro_rules! resolve_variants {
+            ($($variant:ident),+) => {
+                match self {
+                    FontSize(CssPropertyValue::Exact(_)) => FontSize(CssPropertyValue::Exact(
+                        StyleFontSize(PixelValue::px(context.font_size_px)),
+                    )),
+                    $(
+                        $variant(v) => $variant(v.to_absolute_units(context)),
+                    )+
+                    other => other.clone(),
+                }
+            };
+        }
+
+        resolve_variants!(
+            FontSize,
+            LetterSpacing,
+            WordSpacing,
+            TextDecorationThickness,
+            TextShadow,
+            Width,
+            Height,
+            MinWidth,
+            MinHeight,
+            MaxWidth,
+            MaxHeight,
+            Top,
+            Right,
+            Left,
+            Bottom,
+            GridTemplateColumns,
+            GridTemplateRows,
+            GridColumnGap,
+            GridRowGap,
+            BackgroundPosition,
+            BackgroundSize,
+            PaddingTop,
+            PaddingLeft,
+            PaddingRight,
+            PaddingBottom,
+            MarginTop,
+            MarginLeft,
+            MarginRight,
+            MarginBottom,
+            BorderTopLeftRadius,
+            BorderTopRightRadius,
+            BorderBottomLeftRadius,
+            BorderBottomRightRadius,
+            BorderTopWidth,
+            BorderRightWidth,
+            BorderLeftWidth,
+            BorderBottomWidth,
+            BoxShadowLeft,
+            BoxShadowRight,
+            BoxShadowTop,
+            BoxShadowBottom,
+            Transform,
+            TransformOrigin,
+            Filter
+        )
+    }
+}
+
+impl<T: ToAbsoluteUnits> ToAbsoluteUnits for Option<T> {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        self.as_ref().map(|value| value.to_absolute_units(context))
+    }
+}
+
+/// Resolves the listed fields of a `RectStyle` or `RectLayout`, the other fields are cloned
+macro_rules! resolve_fields {
+    ($struct:ident, $self:ident, $context:ident, [$($field:ident),+], $($rest:tt)*) => {
+        $struct {
+            $(
+                $field: $self.$field.to_absolute_units($context),
+            )+
+            $($rest)*
+        }
+    };
+}
+
+impl ToAbsoluteUnits for RectStyle {
+    /// Same as `CssProperty::to_absolute_units`, the `font_size` is replaced by the font
+    /// size of the `context`
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        let font_size = match self.font_size {
+            Some(CssPropertyValue::Exact(_)) => Some(CssPropertyValue::Exact(StyleFontSize(
+                PixelValue::px(context.font_size_px),
+            ))),
+            other => other,
+        };
+
+        resolve_fields!(
+            RectStyle,
+            self,
+            context,
+            [
+                background_position,
+                background_size,
+                letter_spacing,
+                word_spacing,
+                text_decoration_thickness,
+                text_shadow,
+                box_shadow_left,
+                box_shadow_right,
+                box_shadow_top,
+                box_shadow_bottom,
+                border_top_left_radius,
+                border_top_right_radius,
+                border_bottom_left_radius,
+                border_bottom_right_radius,
+                transform,
+                transform_origin,
+                filter
+            ],
+            font_size,
+            ..self.clone()
+        )
+    }
+}
+
+impl ToAbsoluteUnits for RectLayout {
+    fn to_absolute_units(&self, context: &SizeResolveContext) -> Self {
+        resolve_fields!(
+            RectLayout,
+            self,
+            context,
+            [
+                width,
+                height,
+                min_width,
+                min_height,
+                max_width,
+                max_height,
+                top,
+                bottom,
+                right,
+                left,
+                padding_top,
+                padding_bottom,
+                padding_left,
+                padding_right,
+                margin_top,
+                margin_bottom,
+                margin_left,
+                margin_right,
+                border_top_width,
+                border_left_width,
+                border_right_width,
+                border_bottom_width,
+                grid_template_columns,
+                grid_template_rows,
+                grid_column_gap,
+                grid_row_gap
+            ],
+            ..self.clone()
+        )
+    }
+}
+
+/// Parsed `calc()` expression, i.e. `calc(100% - 2em + 4px)`
+///
+/// Since `calc()` can only add lengths and multiply / divide them by plain numbers,
+/// every expression can be simplified to a sum with one factor per unit, which
+/// keeps the value `Copy` and hashable. `pt` values are stored as `px`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalcValue {
+    pub px: FloatValue,
+    pub em: FloatValue,
+    pub rem: FloatValue,
+    pub vw: FloatValue,
+    pub vh: FloatValue,
+    pub vmin: FloatValue,
+    pub vmax: FloatValue,
+    pub percent: FloatValue,
+}
+
+impl fmt::Display for CalcValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let terms = [
+            (self.px, "px"),
+            (self.em, "em"),
+            (self.rem, "rem"),
+            (self.vw, "vw"),
+            (self.vh, "vh"),
+            (self.vmin, "vmin"),
+            (self.vmax, "vmax"),
+            (self.percent, "%"),
+        ];
+
+        write!(f, "calc(")?;
+        let mut is_first = true;
+        for (factor, unit) in terms.iter().filter(|(factor, _)| factor.number != 0) {
+            let factor = factor.get();
+            if is_first {
+                write!(f, "{}{}", factor, unit)?;
+            } else if factor < 0.0 {
+                write!(f, " - {}{}", -factor, unit)?;
+            } else {
+                write!(f, " + {}{}", factor, unit)?;
+            }
+            is_first = false;
+        }
+        if is_first {
+            write!(f, "0px")?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl CalcValue {
+    /// Converts a single value into a `calc()` sum
+    pub fn from_pixel_value(value: PixelValue) -> Self {
+        let number = value.number.get();
+        let mut calc = CalcValue::default();
+        match value.metric {
+            SizeMetric::Px => calc.px = FloatValue::new(number),
+            SizeMetric::Pt => calc.px = FloatValue::new(number * PT_TO_PX),
+            SizeMetric::Em => calc.em = FloatValue::new(number),
+            SizeMetric::Rem => calc.rem = FloatValue::new(number),
+            SizeMetric::Vw => calc.vw = FloatValue::new(number),
+            SizeMetric::Vh => calc.vh = FloatValue::new(number),
+            SizeMetric::Vmin => calc.vmin = FloatValue::new(number),
+            SizeMetric::Vmax => calc.vmax = FloatValue::new(number),
+            SizeMetric::Percent => calc.percent = FloatValue::new(number),
+            SizeMetric::Calc(inner) => calc = inner.multiply(number),
+        }
+        calc
+    }
+
+    /// Adds the factors of two sums
+    pub fn add(&self, other: &CalcValue) -> Self {
+        self.combine(other, |a, b| a + b)
+    }
+
+    /// Subtracts the factors of `other` from the factors of `self`
+    pub fn subtract(&self, other: &CalcValue) -> Self {
+        self.combine(other, |a, b| a - b)
+    }
+
+    /// Multiplies all factors with a number
+    pub fn multiply(&self, factor: f32) -> Self {
+        self.combine(&CalcValue::default(), |a, _| a * factor)
+    }
+
+    /// Returns whether the expression contains a `%` term
+    pub fn has_percent(&self) -> bool {
+        self.percent.number != 0
+    }
+
+    /// Resolves all units except for `%` to pixels, returns `(pixels, percent)`
+    pub fn resolve_relative_units(&self, context: &SizeResolveContext) -> (f32, f32) {
+        let pixels = self.px.get()
+            + self.em.get() * SizeMetric::Em.to_pixels(context)
+            + self.rem.get() * SizeMetric::Rem.to_pixels(context)
+            + self.vw.get() * SizeMetric::Vw.to_pixels(context)
+            + self.vh.get() * SizeMetric::Vh.to_pixels(context)
+            + self.vmin.get() * SizeMetric::Vmin.to_pixels(context)
+            + self.vmax.get() * SizeMetric::Vmax.to_pixels(context);
+        (pixels, self.percent.get())
+    }
+
+    fn combine<F: Fn(f32, f32) -> f32>(&self, other: &CalcValue, f: F) -> Self {
+        let c = |a: FloatValue, b: FloatValue| FloatValue::new(f(a.get(), b.get()));
+        Self {
+            px: c(self.px, other.px),
+            em: c(self.em, other.em),
+            rem: c(self.rem, other.rem),
+            vw: c(self.vw, other.vw),
+            vh: c(self.vh, other.vh),
+            vmin: c(self.vmin, other.vmin),
+            vmax: c(self.vmax, other.vmax),
+            percent: c(self.percent, other.percent),
+        }
+    }
+}
+
+/// Wrapper around FloatValue, represents a percentage instead
+/// of just being a regular floating-point value, i.e `5` = `5%`
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PercentageValue {
+    number: FloatValue,
+}
+
+impl fmt::Display for PercentageValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}%", self.get())
+    }
+}
+
+impl PercentageValue {
+    /// Same as `PercentageValue::new()`, but only accepts whole numbers,
+    /// since using `f32` in const fn is not yet stabilized.
+    pub const fn const_new(value: isize) -> Self {
+        Self {
+            number: FloatValue::const_new(value),
+        }
+    }
+
+    pub fn new(value: f32) -> Self {
+        Self {
+            number: value.into(),
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        self.number.get()
+    }
+}
+
+/// Wrapper around an f32 value that is internally casted to an isize,
+/// in order to provide hash-ability (to avoid numerical instability).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FloatValue {
+    pub number: isize,
+}
+
+impl fmt::Display for FloatValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+impl Default for FloatValue {
+    fn default() -> Self {
+        const DEFAULT_FLV: FloatValue = FloatValue::const_new(0);
+        DEFAULT_FLV
+    }
+}
+
+impl FloatValue {
+    /// Same as `FloatValue::new()`, but only accepts whole numbers,
+    /// since using `f32` in const fn is not yet stabilized.
+    pub const fn const_new(value: isize) -> Self {
+        Self {
+            number: value * FP_PRECISION_MULTIPLIER_CONST,
+        }
+    }
+
+    pub fn new(value: f32) -> Self {
+        Self {
+            number: (value * FP_PRECISION_MULTIPLIER) as isize,
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        self.number as f32 / FP_PRECISION_MULTIPLIER
+    }
+}
+
+impl From<f32> for FloatValue {
+    fn from(val: f32) -> Self {
+        Self::new(val)
+    }
+}
+
+/// Enum representing the metric associated with a number (px, pt, em, etc.)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SizeMetric {
+    Px,
+    Pt,
+    /// Relative to the font size of the node
+    Em,
+    /// Relative to the font size of the root node
+    Rem,
+    /// 1% of the viewport width
+    Vw,
+    /// 1% of the viewport height
+    Vh,
+    /// 1% of the smaller viewport dimension
+    Vmin,
+    /// 1% of the larger viewport dimension
+    Vmax,
+    Percent,
+    /// `calc()` expression, the number of the `PixelValue` is a factor for the whole sum
+    Calc(CalcValue),
+}
+
+impl SizeMetric {
+    /// Returns the size of one unit in pixels, `%` and `calc()` return 0
+    pub fn to_pixels(&self, context: &SizeResolveContext) -> f32 {
+        use self::SizeMetric::*;
+        match self {
+            Px => 1.0,
+            Pt => PT_TO_PX,
+            Em => context.font_size_px,
+            Rem => context.root_font_size_px,
+            Vw => context.viewport_width / 100.0,
+            Vh => context.viewport_height / 100.0,
+            Vmin => context.viewport_width.min(context.viewport_height) / 100.0,
+            Vmax => context.viewport_width.max(context.viewport_height) / 100.0,
+            Percent | Calc(_) => 0.0,
+        }
+    }
+}
+
+impl Default for SizeMetric {
+    fn default() -> Self {
+        SizeMetric::Px
+    }
+}
+
+/// Represents a `background-size` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleBackgroundSize {
+    ExactSize(PixelValue, PixelValue),
+    Contain,
+    Cover,
+}
+
+/// Represents a `background-position` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleBackgroundPosition {
+    pub horizontal: BackgroundPositionHorizontal,
+    pub vertical: BackgroundPositionVertical,
+}
+
+impl Default for StyleBackgroundPosition {
+    fn default() -> Self {
+        StyleBackgroundPosition {
+            horizontal: BackgroundPositionHorizontal::Left,
+            vertical: BackgroundPositionVertical::Top,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BackgroundPositionHorizontal {
+    Left,
+    Center,
+    Right,
+    Exact(PixelValue),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BackgroundPositionVertical {
+    Top,
+    Center,
+    Bottom,
+    Exact(PixelValue),
+}
+
+/// Represents a `background-repeat` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleBackgroundRepeat {
+    NoRepeat,
+    Repeat,
+    RepeatX,
+    RepeatY,
+}
+
+impl Default for StyleBackgroundRepeat {
+    fn default() -> Self {
+        StyleBackgroundRepeat::Repeat
+    }
+}
+
+/// Represents a `transform` attribute, i.e. `transform: translate(10px, 5%) rotate(45deg)`.
+/// The functions are applied from left to right, as in the CSS spec.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransform(pub Vec<StyleTransformFunction>);
+
+/// One function of a `transform` attribute. `translateX()`, `scaleY()`, etc.
+/// are stored as their two-argument versions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTransformFunction {
+    /// `matrix(a, b, c, d, e, f)`
+    Matrix(StyleTransformMatrix2D),
+    /// `translate(x, y)` - percentages are relative to the size of the element itself
+    Translate(PixelValue, PixelValue),
+    /// `rotate(angle)` - clockwise rotation, in degrees
+    Rotate(FloatValue),
+    /// `scale(x, y)`
+    Scale(FloatValue, FloatValue),
+    /// `skew(x-angle, y-angle)`, in degrees
+    Skew(FloatValue, FloatValue),
+}
+
+/// Arguments of a `matrix(a, b, c, d, e, f)` function, the translation (`e`, `f`) is in pixels
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransformMatrix2D {
+    pub a: FloatValue,
+    pub b: FloatValue,
+    pub c: FloatValue,
+    pub d: FloatValue,
+    pub e: FloatValue,
+    pub f: FloatValue,
+}
+
+/// Represents a `transform-origin` attribute, relative to the top left corner
+/// of the element - default: `50% 50%` (center of the element)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransformOrigin {
+    pub x: PixelValue,
+    pub y: PixelValue,
+}
+
+impl StyleTransform {
+    /// Resolves the transform functions of an element with the bounds `rect` into one
+    /// transform, which is relative to the coordinate space of `rect` (not to the element)
+    pub fn get_layout_transform(
+        &self,
+        origin: StyleTransformOrigin,
+        rect: LayoutRect,
+    ) -> LayoutTransform {
+        use self::StyleTransformFunction::*;
+
+        let origin_x = rect.origin.x + origin.x.to_pixels(rect.size.width);
+        let origin_y = rect.origin.y + origin.y.to_pixels(rect.size.height);
+
+        // The rightmost function is applied first
+        let transform = self.0.iter().rev().fold(
+            LayoutTransform::translation(-origin_x, -origin_y),
+            |transform, function| {
+                transform.then(&match function {
+                    Matrix(m) => LayoutTransform::new(
+                        m.a.get(),
+                        m.b.get(),
+                        m.c.get(),
+                        m.d.get(),
+                        m.e.get(),
+                        m.f.get(),
+                    ),
+                    Translate(x, y) => LayoutTransform::translation(
+                        x.to_pixels(rect.size.width),
+                        y.to_pixels(rect.size.height),
+                    ),
+                    Rotate(degrees) => LayoutTransform::rotation(degrees.get()),
+                    Scale(x, y) => LayoutTransform::scale(x.get(), y.get()),
+                    Skew(x, y) => LayoutTransform::skew(x.get(), y.get()),
+                })
+            },
+        );
+
+        transform.then(&LayoutTransform::translation(origin_x, origin_y))
+    }
+}
+
+impl Default for StyleTransformOrigin {
+    fn default() -> Self {
+        StyleTransformOrigin {
+            x: PixelValue::percent(50.0),
+            y: PixelValue::percent(50.0),
+        }
+    }
+}
+
+/// Represents an `opacity` attribute, from `0.0` (transparent) to `1.0` (opaque).
+/// Applies to the element and all of its children.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleOpacity(pub FloatValue);
+
+impl Default for StyleOpacity {
+    fn default() -> Self {
+        StyleOpacity(FloatValue::const_new(1))
+    }
+}
+
+/// Represents a `filter` attribute, i.e. `filter: blur(5px) grayscale(100%)`.
+/// The filters are applied from left to right to the element and all of its children.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFilter(pub Vec<StyleFilterFunction>);
+
+/// One function of a `filter` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleFilterFunction {
+    /// `blur(radius)` - gaussian blur with the radius as the standard deviation
+    Blur(PixelValueNoPercent),
+    /// `grayscale(amount)` - `0.0` leaves the input unchanged, `1.0` is completely gray
+    Grayscale(FloatValue),
+    /// `brightness(amount)` - `1.0` leaves the input unchanged, `0.0` is completely black
+    Brightness(FloatValue),
+    /// `drop-shadow(offset-x offset-y blur-radius color)`
+    DropShadow(StyleDropShadow),
+}
+
+/// Arguments of a `drop-shadow()` filter - same as a `box-shadow`, but without
+/// spread radius and inset, since the shadow follows the shape of the content
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleDropShadow {
+    pub offset: [PixelValueNoPercent; 2],
+    pub color: ColorU,
+    pub blur_radius: PixelValueNoPercent,
+}
+
+/// Duration of a transition or an animation, i.e. `300ms` or `1.5s`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CssDuration {
+    pub milliseconds: u32,
+}
+
+impl fmt::Display for CssDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}ms", self.milliseconds)
+    }
+}
+
+impl CssDuration {
+    pub const fn from_millis(milliseconds: u32) -> Self {
+        Self { milliseconds }
+    }
+
+    pub fn to_duration(&self) -> Duration {
+        Duration::from_millis(u64::from(self.milliseconds))
+    }
+}
+
+/// Easing function of a transition or an animation, i.e. `ease-in` or `steps(4, end)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTimingFunction {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// `cubic-bezier(x1, y1, x2, y2)`
+    CubicBezier(FloatValue, FloatValue, FloatValue, FloatValue),
+    /// `steps(count, start | end)` - `step-start` and `step-end` are stored as `steps(1, ...)`
+    Steps(u32, StepPosition),
+}
+
+impl Default for StyleTimingFunction {
+    fn default() -> Self {
+        StyleTimingFunction::Ease
+    }
+}
+
+/// Whether a `steps()` timing function jumps at the start or at the end of each step
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StepPosition {
+    Start,
+    End,
+}
+
+/// Represents a `transition` attribute, i.e. `transition: opacity 300ms ease-in, width 1s`
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransition(pub Vec<StyleTransitionItem>);
+
+/// One (comma-separated) item of a `transition` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTransitionItem {
+    pub property: TransitionProperty,
+    pub duration: CssDuration,
+    pub timing_function: StyleTimingFunction,
+    pub delay: CssDuration,
+}
+
+/// The property that a transition applies to: `all` or one CSS key
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TransitionProperty {
+    All,
+    Property(CssPropertyType),
+}
+
+impl TransitionProperty {
+    pub fn applies_to(&self, property_type: CssPropertyType) -> bool {
+        match self {
+            TransitionProperty::All => true,
+            TransitionProperty::Property(p) => *p == property_type,
+        }
+    }
+}
+
+impl StyleTransition {
+    /// Returns the transition of a property - if multiple items apply to
+    /// the same property, the last one wins (same as in browsers)
+    pub fn get_item(&self, property_type: CssPropertyType) -> Option<&StyleTransitionItem> {
+        self.0
+            .iter()
+            .rev()
+            .find(|item| item.property.applies_to(property_type))
+    }
+}
+
+/// Represents an `animation-name` attribute, i.e. the name of a `@keyframes` rule
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleAnimationName(pub String);
+
+/// Represents an `animation-duration` attribute
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleAnimationDuration(pub CssDuration);
+
+/// Represents an `animation-timing-function` attribute
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleAnimationTimingFunction(pub StyleTimingFunction);
+
+/// Represents an `animation-delay` attribute
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleAnimationDelay(pub CssDuration);
+
+/// Represents an `animation-iteration-count` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleAnimationIterationCount {
+    Count(FloatValue),
+    Infinite,
+}
+
+impl Default for StyleAnimationIterationCount {
+    fn default() -> Self {
+        StyleAnimationIterationCount::Count(FloatValue::const_new(1))
+    }
+}
+
+/// Represents an `animation-direction` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleAnimationDirection {
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+impl Default for StyleAnimationDirection {
+    fn default() -> Self {
+        StyleAnimationDirection::Normal
+    }
+}
+
+/// Represents an `animation-fill-mode` attribute (`none` is stored as `CssPropertyValue::None`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleAnimationFillMode {
+    Forwards,
+    Backwards,
+    Both,
+}
+
+/// Represents a `color` attribute
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextColor(pub ColorU);
 
 derive_debug_zero!(StyleTextColor);
 derive_display_zero!(StyleTextColor);
@@ -2193,6 +3523,9 @@ pub enum LayoutAxis {
 pub enum LayoutDisplay {
     Flex,
     Inline,
+    Grid,
+    Block,
+    None,
 }
 
 impl Default for LayoutDisplay {
@@ -2201,6 +3534,174 @@ impl Default for LayoutDisplay {
     }
 }
 
+/// One side of the size of a grid track: either a fixed size,
+/// a fraction of the free space (`fr`) or `auto` (sized by the content)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GridTrackBreadth {
+    Fixed(PixelValue),
+    Fraction(FloatValue),
+    Auto,
+}
+
+impl fmt::Display for GridTrackBreadth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::GridTrackBreadth::*;
+        match self {
+            Fixed(p) => write!(f, "{}", p),
+            Fraction(fr) => write!(f, "{}fr", fr),
+            Auto => write!(f, "auto"),
+        }
+    }
+}
+
+/// Size of a single column or row in a `grid-template-columns` / `grid-template-rows`,
+/// `100px` is stored as `minmax(100px, 100px)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridTrackSize {
+    pub min: GridTrackBreadth,
+    pub max: GridTrackBreadth,
+}
+
+impl GridTrackSize {
+    #[inline]
+    pub fn px(value: f32) -> Self {
+        Self::fixed(PixelValue::px(value))
+    }
+
+    #[inline]
+    pub fn percent(value: f32) -> Self {
+        Self::fixed(PixelValue::percent(value))
+    }
+
+    #[inline]
+    pub fn fr(value: f32) -> Self {
+        let fraction = GridTrackBreadth::Fraction(FloatValue::new(value));
+        Self {
+            min: fraction,
+            max: fraction,
+        }
+    }
+
+    #[inline]
+    pub fn auto() -> Self {
+        Self {
+            min: GridTrackBreadth::Auto,
+            max: GridTrackBreadth::Auto,
+        }
+    }
+
+    #[inline]
+    pub fn minmax(min: GridTrackBreadth, max: GridTrackBreadth) -> Self {
+        Self { min, max }
+    }
+
+    #[inline]
+    fn fixed(value: PixelValue) -> Self {
+        Self {
+            min: GridTrackBreadth::Fixed(value),
+            max: GridTrackBreadth::Fixed(value),
+        }
+    }
+}
+
+impl fmt::Display for GridTrackSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "minmax({}, {})", self.min, self.max)
+        }
+    }
+}
+
+/// List of tracks of a `grid-template-columns` / `grid-template-rows` attribute,
+/// `repeat()` is already expanded at parse time
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridTemplate {
+    pub tracks: Vec<GridTrackSize>,
+}
+
+impl fmt::Display for GridTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tracks = self
+            .tracks
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "{}", tracks.join(" "))
+    }
+}
+
+/// Represents a `grid-template-columns` attribute
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridTemplateColumns(pub GridTemplate);
+/// Represents a `grid-template-rows` attribute
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridTemplateRows(pub GridTemplate);
+
+/// Represents a `grid-column-gap` attribute
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridColumnGap(pub PixelValue);
+/// Represents a `grid-row-gap` attribute
+#[derive(Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridRowGap(pub PixelValue);
+
+impl_pixel_value!(LayoutGridColumnGap);
+impl_pixel_value!(LayoutGridRowGap);
+
+/// Largest grid line number, span and `repeat()` count. Like in browsers, larger values
+/// are clamped, so that a single line of CSS can't create millions of grid tracks
+pub const MAX_GRID_LINE: i32 = 10_000;
+
+/// Start or end line of a grid item, i.e. the `2` or the `span 2` in `grid-column: 2 / span 2`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GridLine {
+    /// Automatic placement / a span of one track
+    Auto,
+    /// 1-based line number, negative numbers count backwards from the
+    /// end of the explicit grid (`-1` is the last line)
+    Line(i32),
+    /// Item spans the given number of tracks
+    Span(u32),
+}
+
+impl Default for GridLine {
+    fn default() -> Self {
+        GridLine::Auto
+    }
+}
+
+impl fmt::Display for GridLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::GridLine::*;
+        match self {
+            Auto => write!(f, "auto"),
+            Line(l) => write!(f, "{}", l),
+            Span(s) => write!(f, "span {}", s),
+        }
+    }
+}
+
+/// Placement of a grid item on one axis (`grid-column` or `grid-row`)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridPlacement {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+impl fmt::Display for GridPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} / {}", self.start, self.end)
+    }
+}
+
+/// Represents a `grid-column` attribute
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridColumn(pub GridPlacement);
+/// Represents a `grid-row` attribute
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayoutGridRow(pub GridPlacement);
+
 /// Represents a `float` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LayoutFloat {
@@ -2222,6 +3723,8 @@ pub enum LayoutPosition {
     Static,
     Relative,
     Absolute,
+    Fixed,
+    Sticky,
 }
 
 impl Default for LayoutPosition {
@@ -2365,6 +3868,186 @@ impl Default for StyleTextAlignmentHorz {
     }
 }
 
+/// Base direction of the text and inline layout (`direction` property) - default: `Ltr`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTextDirection {
+    Ltr,
+    Rtl,
+}
+
+impl Default for StyleTextDirection {
+    fn default() -> Self {
+        StyleTextDirection::Ltr
+    }
+}
+
+/// Where the lines of a text may break between the letters of
+/// a word (`word-break` property) - default: `Normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleWordBreak {
+    /// Lines only break at the break opportunities of the text (spaces,
+    /// after hyphens, between CJK characters, etc.)
+    Normal,
+    /// Lines can break between any two letters
+    BreakAll,
+    /// Lines don't break between CJK characters, only at spaces and punctuation
+    KeepAll,
+}
+
+impl Default for StyleWordBreak {
+    fn default() -> Self {
+        StyleWordBreak::Normal
+    }
+}
+
+/// Whether the lines of a text may break inside of a word that is too long
+/// to fit on a line by itself (`overflow-wrap` property) - default: `Normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleOverflowWrap {
+    /// Long words overflow the line
+    Normal,
+    /// Long words are broken at an arbitrary point if there is no other break opportunity
+    BreakWord,
+    /// Same as `BreakWord` for the line breaking, the difference between the
+    /// two only affects the min-content size, which isn't computed from the text
+    Anywhere,
+}
+
+impl Default for StyleOverflowWrap {
+    fn default() -> Self {
+        StyleOverflowWrap::Normal
+    }
+}
+
+/// How words are hyphenated when the line breaks inside of them (`hyphens` property)
+/// - default: `Manual`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleHyphens {
+    /// Words are never hyphenated, soft hyphens (`U+00AD`) are ignored
+    None,
+    /// Words are only hyphenated at soft hyphens (`U+00AD`)
+    Manual,
+    /// Words are hyphenated at soft hyphens and at the hyphenation points
+    /// of the hyphenation dictionary
+    Auto,
+}
+
+impl Default for StyleHyphens {
+    fn default() -> Self {
+        StyleHyphens::Manual
+    }
+}
+
+/// How the whitespace of a text is handled and whether
+/// its lines wrap (`white-space` property) - default: `Normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleWhiteSpace {
+    /// Sequences of whitespace (including newlines) collapse into a single space, lines wrap
+    Normal,
+    /// Same as `Normal`, but lines don't wrap
+    NoWrap,
+    /// Whitespace is preserved, lines only break at newlines
+    Pre,
+    /// Whitespace is preserved, lines wrap
+    PreWrap,
+}
+
+impl StyleWhiteSpace {
+    /// Returns whether sequences of whitespace collapse into a single space
+    pub fn collapses_whitespace(&self) -> bool {
+        match self {
+            StyleWhiteSpace::Normal | StyleWhiteSpace::NoWrap => true,
+            StyleWhiteSpace::Pre | StyleWhiteSpace::PreWrap => false,
+        }
+    }
+
+    /// Returns whether lines wrap when they exceed the width of the text
+    pub fn wraps(&self) -> bool {
+        match self {
+            StyleWhiteSpace::Normal | StyleWhiteSpace::PreWrap => true,
+            StyleWhiteSpace::NoWrap | StyleWhiteSpace::Pre => false,
+        }
+    }
+}
+
+impl Default for StyleWhiteSpace {
+    fn default() -> Self {
+        StyleWhiteSpace::Normal
+    }
+}
+
+/// How a line of text that overflows the width of the text
+/// is cut off (`text-overflow` property) - default: `Clip`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTextOverflow {
+    /// The line is clipped at the edge of the rectangle
+    Clip,
+    /// The line is cut off before the edge of the rectangle and ends with an ellipsis (`…`)
+    Ellipsis,
+}
+
+impl Default for StyleTextOverflow {
+    fn default() -> Self {
+        StyleTextOverflow::Clip
+    }
+}
+
+/// Represents a `line-clamp` attribute: the maximum number of lines of a text,
+/// the last visible line ends with an ellipsis (`…`) if the text is cut off
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleLineClamp(pub usize);
+
+/// Represents a `text-decoration-line` attribute: the lines that are drawn along
+/// the text, i.e. `underline line-through` (`none` if no line is drawn)
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextDecorationLine {
+    pub underline: bool,
+    pub overline: bool,
+    pub line_through: bool,
+}
+
+/// Style of the text decoration lines (`text-decoration-style` property) - default: `Solid`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTextDecorationStyle {
+    Solid,
+    Double,
+    Dotted,
+    Dashed,
+    Wavy,
+}
+
+impl Default for StyleTextDecorationStyle {
+    fn default() -> Self {
+        StyleTextDecorationStyle::Solid
+    }
+}
+
+/// Represents a `text-decoration-color` attribute - if it isn't set,
+/// the lines are drawn in the color of the text
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextDecorationColor(pub ColorU);
+
+/// Thickness of the text decoration lines (`text-decoration-thickness` property)
+/// - default: `FromFont`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleTextDecorationThickness {
+    /// The thickness of the underline / strikeout line of the font
+    FromFont,
+    Length(PixelValueNoPercent),
+}
+
+impl Default for StyleTextDecorationThickness {
+    fn default() -> Self {
+        StyleTextDecorationThickness::FromFont
+    }
+}
+
+/// Represents a `text-shadow` attribute: shadows of the glyphs and the text decoration lines,
+/// the first shadow is drawn on top. The arguments of each shadow are the same as the
+/// arguments of a `drop-shadow()` filter.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleTextShadow(pub Vec<StyleDropShadow>);
+
 /// Vertical text alignment enum (top, center, bottom) - default: `Center`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum StyleTextAlignmentVert {
@@ -2388,12 +4071,30 @@ pub struct RectStyle {
     pub background_repeat: Option<CssPropertyValue<StyleBackgroundRepeat>>,
     pub font_size: Option<CssPropertyValue<StyleFontSize>>,
     pub font_family: Option<CssPropertyValue<StyleFontFamily>>,
+    pub font_weight: Option<CssPropertyValue<StyleFontWeight>>,
+    pub font_style: Option<CssPropertyValue<StyleFontStyle>>,
+    pub font_stretch: Option<CssPropertyValue<StyleFontStretch>>,
+    pub font_feature_settings: Option<CssPropertyValue<StyleFontFeatureSettings>>,
+    pub font_variant_numeric: Option<CssPropertyValue<StyleFontVariantNumeric>>,
+    pub font_variation_settings: Option<CssPropertyValue<StyleFontVariationSettings>>,
     pub text_color: Option<CssPropertyValue<StyleTextColor>>,
     pub text_align: Option<CssPropertyValue<StyleTextAlignmentHorz>>,
+    pub direction: Option<CssPropertyValue<StyleTextDirection>>,
     pub line_height: Option<CssPropertyValue<StyleLineHeight>>,
     pub letter_spacing: Option<CssPropertyValue<StyleLetterSpacing>>,
     pub word_spacing: Option<CssPropertyValue<StyleWordSpacing>>,
     pub tab_width: Option<CssPropertyValue<StyleTabWidth>>,
+    pub word_break: Option<CssPropertyValue<StyleWordBreak>>,
+    pub overflow_wrap: Option<CssPropertyValue<StyleOverflowWrap>>,
+    pub hyphens: Option<CssPropertyValue<StyleHyphens>>,
+    pub white_space: Option<CssPropertyValue<StyleWhiteSpace>>,
+    pub text_overflow: Option<CssPropertyValue<StyleTextOverflow>>,
+    pub line_clamp: Option<CssPropertyValue<StyleLineClamp>>,
+    pub text_decoration_line: Option<CssPropertyValue<StyleTextDecorationLine>>,
+    pub text_decoration_style: Option<CssPropertyValue<StyleTextDecorationStyle>>,
+    pub text_decoration_color: Option<CssPropertyValue<StyleTextDecorationColor>>,
+    pub text_decoration_thickness: Option<CssPropertyValue<StyleTextDecorationThickness>>,
+    pub text_shadow: Option<CssPropertyValue<StyleTextShadow>>,
     pub cursor: Option<CssPropertyValue<StyleCursor>>,
 
     pub box_shadow_left: Option<CssPropertyValue<BoxShadowPreDisplayItem>>,
@@ -2415,10 +4116,16 @@ pub struct RectStyle {
     pub border_top_right_radius: Option<CssPropertyValue<StyleBorderTopRightRadius>>,
     pub border_bottom_left_radius: Option<CssPropertyValue<StyleBorderBottomLeftRadius>>,
     pub border_bottom_right_radius: Option<CssPropertyValue<StyleBorderBottomRightRadius>>,
+
+    pub transform: Option<CssPropertyValue<StyleTransform>>,
+    pub transform_origin: Option<CssPropertyValue<StyleTransformOrigin>>,
+
+    pub opacity: Option<CssPropertyValue<StyleOpacity>>,
+    pub filter: Option<CssPropertyValue<StyleFilter>>,
 }
 
 // Layout constraints for a given rectangle, such as "width", "min-width", "height", etc.
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RectLayout {
     pub display: Option<CssPropertyValue<LayoutDisplay>>,
     pub float: Option<CssPropertyValue<LayoutFloat>>,
@@ -2462,6 +4169,13 @@ pub struct RectLayout {
     pub justify_content: Option<CssPropertyValue<LayoutJustifyContent>>,
     pub align_items: Option<CssPropertyValue<LayoutAlignItems>>,
     pub align_content: Option<CssPropertyValue<LayoutAlignContent>>,
+
+    pub grid_template_columns: Option<CssPropertyValue<LayoutGridTemplateColumns>>,
+    pub grid_template_rows: Option<CssPropertyValue<LayoutGridTemplateRows>>,
+    pub grid_column_gap: Option<CssPropertyValue<LayoutGridColumnGap>>,
+    pub grid_row_gap: Option<CssPropertyValue<LayoutGridRowGap>>,
+    pub grid_column: Option<CssPropertyValue<LayoutGridColumn>>,
+    pub grid_row: Option<CssPropertyValue<LayoutGridRow>>,
 }
 
 /// Holds info necessary for layouting / styling scrollbars (-webkit-scrollbar)
@@ -2633,3 +4347,195 @@ impl FontId {
         &self.0
     }
 }
+
+/// Represents a `font-weight` attribute, from `1` (thinnest) to `1000` (boldest)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFontWeight(pub u16);
+
+impl StyleFontWeight {
+    /// `font-weight: normal`
+    pub const NORMAL: StyleFontWeight = StyleFontWeight(400);
+    /// `font-weight: bold`
+    pub const BOLD: StyleFontWeight = StyleFontWeight(700);
+
+    /// Returns whether the weight is `600` (semi-bold) or higher
+    pub fn is_bold(&self) -> bool {
+        self.0 >= 600
+    }
+}
+
+impl Default for StyleFontWeight {
+    fn default() -> Self {
+        StyleFontWeight::NORMAL
+    }
+}
+
+/// Represents a `font-style` attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleFontStyle {
+    Normal,
+    Italic,
+    Oblique,
+}
+
+impl Default for StyleFontStyle {
+    fn default() -> Self {
+        StyleFontStyle::Normal
+    }
+}
+
+/// Represents a `font-stretch` attribute, ordered from the narrowest to the widest face
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleFontStretch {
+    UltraCondensed,
+    ExtraCondensed,
+    Condensed,
+    SemiCondensed,
+    Normal,
+    SemiExpanded,
+    Expanded,
+    ExtraExpanded,
+    UltraExpanded,
+}
+
+impl Default for StyleFontStretch {
+    fn default() -> Self {
+        StyleFontStretch::Normal
+    }
+}
+
+/// Tag of an OpenType feature (i.e. `liga`) or of a variation axis of a font (i.e. `wght`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontTag(pub [u8; 4]);
+
+impl FontTag {
+    /// Returns the tag as a big-endian `u32`, the way the font tables store it
+    pub fn as_u32(&self) -> u32 {
+        u32::from_be_bytes(self.0)
+    }
+}
+
+impl fmt::Display for FontTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.0))
+    }
+}
+
+/// OpenType feature that is enabled (`value: 1`) or disabled (`value: 0`) when shaping
+/// the text - values larger than `1` select one of the alternates of the feature
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontFeature {
+    pub tag: FontTag,
+    pub value: u32,
+}
+
+impl FontFeature {
+    /// Returns the feature with the `tag`, enabled (`value: 1`)
+    pub const fn enabled(tag: [u8; 4]) -> Self {
+        Self {
+            tag: FontTag(tag),
+            value: 1,
+        }
+    }
+}
+
+/// Represents a `font-feature-settings` attribute, i.e. `"tnum", "liga" 0`
+/// (empty for `normal`). The features override the features of `font-variant-numeric`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFontFeatureSettings(pub Vec<FontFeature>);
+
+/// Glyphs of the digits (`lining-nums` / `oldstyle-nums`) - default: `Normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NumericFigure {
+    Normal,
+    Lining,
+    OldStyle,
+}
+
+impl Default for NumericFigure {
+    fn default() -> Self {
+        NumericFigure::Normal
+    }
+}
+
+/// Widths of the digits (`proportional-nums` / `tabular-nums`) - default: `Normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NumericSpacing {
+    Normal,
+    Proportional,
+    /// All digits have the same width, so that the digits of numbers line up in columns
+    Tabular,
+}
+
+impl Default for NumericSpacing {
+    fn default() -> Self {
+        NumericSpacing::Normal
+    }
+}
+
+/// Glyphs of fractions (`diagonal-fractions` / `stacked-fractions`) - default: `Normal`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NumericFraction {
+    Normal,
+    Diagonal,
+    Stacked,
+}
+
+impl Default for NumericFraction {
+    fn default() -> Self {
+        NumericFraction::Normal
+    }
+}
+
+/// Represents a `font-variant-numeric` attribute, i.e. `tabular-nums slashed-zero`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFontVariantNumeric {
+    pub figure: NumericFigure,
+    pub spacing: NumericSpacing,
+    pub fraction: NumericFraction,
+    pub ordinal: bool,
+    pub slashed_zero: bool,
+}
+
+impl StyleFontVariantNumeric {
+    /// Returns the OpenType features that have to be enabled for the values
+    pub fn get_font_features(&self) -> Vec<FontFeature> {
+        let mut features = Vec::new();
+
+        match self.figure {
+            NumericFigure::Normal => {}
+            NumericFigure::Lining => features.push(FontFeature::enabled(*b"lnum")),
+            NumericFigure::OldStyle => features.push(FontFeature::enabled(*b"onum")),
+        }
+        match self.spacing {
+            NumericSpacing::Normal => {}
+            NumericSpacing::Proportional => features.push(FontFeature::enabled(*b"pnum")),
+            NumericSpacing::Tabular => features.push(FontFeature::enabled(*b"tnum")),
+        }
+        match self.fraction {
+            NumericFraction::Normal => {}
+            NumericFraction::Diagonal => features.push(FontFeature::enabled(*b"frac")),
+            NumericFraction::Stacked => features.push(FontFeature::enabled(*b"afrc")),
+        }
+        if self.ordinal {
+            features.push(FontFeature::enabled(*b"ordn"));
+        }
+        if self.slashed_zero {
+            features.push(FontFeature::enabled(*b"zero"));
+        }
+
+        features
+    }
+}
+
+/// Value of a variation axis of a variable font, i.e. `wght` = `650.0`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontVariation {
+    pub tag: FontTag,
+    pub value: FloatValue,
+}
+
+/// Represents a `font-variation-settings` attribute, i.e. `"wght" 650, "wdth" 80`
+/// (empty for `normal`). Axes that the font doesn't have are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StyleFontVariationSettings(pub Vec<FontVariation>);
@@ -1,9 +1,11 @@
 //! Provides datatypes used to describe an application's style using the Azul GUI framework.
 
+mod animation;
 mod css;
 mod css_properties;
 mod hot_reload;
 
+pub use crate::animation::*;
 pub use crate::css::*;
 pub use crate::css_properties::*;
 pub use crate::hot_reload::*;
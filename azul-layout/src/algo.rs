@@ -20,7 +20,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use std::{collections::BTreeMap, f32};
+use std::{cmp::Ordering, collections::BTreeMap, f32};
 
 use azul_core::{
     dom::NodeId,
@@ -32,7 +32,8 @@ use azul_core::{
 use azul_css::{LayoutPoint, LayoutRect, LayoutSize};
 
 use crate::{
-    geometry::{Offsets, Rect, RectSize, Size},
+    geometry::{Offsets, Rect, RectOrigin, RectSize, Size},
+    grid::compute_grid,
     number::{
         MinMax,
         Number::{self, *},
@@ -167,10 +168,40 @@ pub(crate) fn compute<T: GetTextLayout>(
 
     // Until now, all divs have the correct layout relative to their siblings, but not relative to their parents
     for (_, parent_id) in node_hierarchy.get_parents_sorted_by_depth() {
-        let parent_rect_origin = node_rects[parent_id].origin;
+        let parent_rect = node_rects[parent_id];
         for child_id in parent_id.children(node_hierarchy) {
-            node_rects[child_id].origin.x += parent_rect_origin.x;
-            node_rects[child_id].origin.y += parent_rect_origin.y;
+            // Fixed nodes are skipped by the layout of their parent, they are laid out relative
+            // to the window here, once the position of their parent is known
+            if node_styles[child_id].position_type == PositionType::Fixed {
+                if node_styles[child_id].display != Display::None {
+                    compute_absolute_child(
+                        child_id,
+                        node_hierarchy,
+                        node_styles,
+                        &mut node_rects,
+                        &mut resolved_text_layout_options,
+                        rect_contents,
+                        root_size,
+                        Offsets {
+                            top: 0.0,
+                            left: 0.0,
+                            bottom: 0.0,
+                            right: 0.0,
+                        },
+                        RectOrigin {
+                            x: parent_rect.origin.x
+                                + parent_rect.padding.left
+                                + parent_rect.border_widths.left,
+                            y: parent_rect.origin.y
+                                + parent_rect.padding.top
+                                + parent_rect.border_widths.top,
+                        },
+                    );
+                }
+                continue;
+            }
+            node_rects[child_id].origin.x += parent_rect.origin.x;
+            node_rects[child_id].origin.y += parent_rect.origin.y;
         }
     }
 
@@ -209,7 +240,7 @@ fn resolve_offsets(input: Offsets<f32>) -> ResolvedOffsets {
     }
 }
 
-fn compute_internal<T: GetTextLayout>(
+pub(crate) fn compute_internal<T: GetTextLayout>(
     node_id: NodeId,
     node_hierarchy: &NodeHierarchy,
     node_styles: &NodeDataContainer<Style>,
@@ -232,7 +263,7 @@ fn compute_internal<T: GetTextLayout>(
         return;
     }
 
-    let dir = parent_node_style.flex_direction;
+    let dir = parent_node_style.resolved_flex_direction();
     let is_row = dir.is_row();
     let is_column = dir.is_column();
     let is_wrap_reverse = parent_node_style.flex_wrap == FlexWrap::WrapReverse;
@@ -357,7 +388,13 @@ fn compute_internal<T: GetTextLayout>(
                     DEFAULT_FONT_SIZE_PX, DEFAULT_LETTER_SPACING, DEFAULT_WORD_SPACING,
                 };
 
-                let text_holes = Vec::new(); // TODO: All children that have float:left / float:right!
+                let text_holes = get_float_holes(
+                    node_id,
+                    node_hierarchy,
+                    node_styles,
+                    node_rects,
+                    available_space.width,
+                );
                 let rect_style = &node_styles[node_id];
                 let parent_id = node_hierarchy[node_id].parent.unwrap_or(NodeId::ZERO);
                 let parent_style = &node_styles[parent_id];
@@ -370,7 +407,7 @@ fn compute_internal<T: GetTextLayout>(
                         available_space.width.to_option()
                     },
                     leading: None, // TODO!
-                    holes: text_holes.clone(),
+                    holes: text_holes,
                     font_size_px: rect_style
                         .font_size_px
                         .to_pixels(DEFAULT_FONT_SIZE_PX as f32),
@@ -382,6 +419,11 @@ fn compute_internal<T: GetTextLayout>(
                         .map(|ls| ls.to_pixels(DEFAULT_WORD_SPACING)),
                     line_height: rect_style.line_height,
                     tab_width: rect_style.tab_width,
+                    white_space: rect_style.white_space,
+                    text_overflow: rect_style.text_overflow,
+                    max_lines: rect_style.line_clamp,
+                    font_features: rect_style.font_features.clone(),
+                    font_variations: rect_style.font_variations.clone(),
                 };
 
                 let layouted_inline_text = t.get_text_layout(&text_layout_options);
@@ -419,6 +461,77 @@ fn compute_internal<T: GetTextLayout>(
         }
     });
 
+    // Blocks are laid out in normal flow. This also has to handle leaf nodes: unlike a flex
+    // item, an empty block doesn't fill its parent, it is only as high as its content
+    if parent_node_style.display == Display::Block {
+        let inner_width = parent_width
+            .or_else(node_size.width)
+            .or_else(parent_size.width - margin.horizontal())
+            - padding_border.horizontal();
+        let inner_height = parent_height.or_else(node_size.height) - padding_border.vertical();
+
+        let (block_content_size, block_margin) = compute_block(
+            node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            Size {
+                width: inner_width,
+                height: inner_height,
+            },
+            content_size,
+            margin,
+            padding_border,
+            perform_layout,
+        );
+
+        let block_width =
+            inner_width.or_else(block_content_size.width) + padding_border.horizontal();
+        let block_height = parent_height.or_else(node_size.height).or_else(
+            (block_content_size.height + padding_border.vertical())
+                .maybe_max(
+                    parent_node_style
+                        .min_size
+                        .height
+                        .resolve(parent_size.height),
+                )
+                .maybe_min(
+                    parent_node_style
+                        .max_size
+                        .height
+                        .resolve(parent_size.height),
+                ),
+        );
+
+        if perform_layout {
+            compute_absolute_children(
+                node_id,
+                node_hierarchy,
+                node_styles,
+                node_rects,
+                resolved_text_layout_options,
+                rect_contents,
+                Size {
+                    width: Number::Defined(block_width),
+                    height: Number::Defined(block_height),
+                },
+                padding_border,
+                border,
+            );
+        }
+
+        node_rects[node_id].size = RectSize {
+            width: Number::Defined(block_width),
+            height: Number::Defined(block_height),
+        };
+        node_rects[node_id].margin = resolve_offsets(block_margin);
+        node_rects[node_id].padding = resolve_offsets(padding);
+        node_rects[node_id].border_widths = resolve_offsets(border);
+        return;
+    }
+
     // If this is a leaf node we can skip a lot of this function
     if node_hierarchy[node_id].first_child.is_none() {
         let (parent_node_width, parent_node_height) = match content_size {
@@ -436,6 +549,38 @@ fn compute_internal<T: GetTextLayout>(
         return;
     }
 
+    if parent_node_style.display == Display::Grid {
+        // Unlike the flex container, the grid container only fills the available height if
+        // it has a definite height, otherwise the rows are sized to fit their content
+        let grid_size = compute_grid(
+            node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            Size {
+                width: parent_width
+                    .or_else(node_size.width)
+                    .or_else(parent_size.width - margin.horizontal())
+                    - padding_border.horizontal(),
+                height: parent_height.or_else(node_size.height) - padding_border.vertical(),
+            },
+            padding_border,
+            border,
+            perform_layout,
+        );
+
+        node_rects[node_id].size = RectSize {
+            width: Number::Defined(grid_size.width + padding_border.horizontal()),
+            height: Number::Defined(grid_size.height + padding_border.vertical()),
+        };
+        node_rects[node_id].margin = resolve_offsets(margin);
+        node_rects[node_id].padding = resolve_offsets(padding);
+        node_rects[node_id].border_widths = resolve_offsets(border);
+        return;
+    }
+
     let node_inner_size = Size {
         width: parent_width.or_else(parent_size.width) - padding_border.horizontal(),
         height: parent_height.or_else(parent_size.height) - padding_border.vertical(),
@@ -464,8 +609,11 @@ fn compute_internal<T: GetTextLayout>(
 
     let mut flex_items: Vec<FlexItem> = node_id
         .children(node_hierarchy)
-        .filter(|child_id| node_styles[*child_id].position_type != PositionType::Absolute)
-        .filter(|child_id| node_styles[*child_id].display == Display::Flex)
+        .filter(|child_id| !node_styles[*child_id].position_type.is_out_of_flow())
+        .filter(|child_id| match node_styles[*child_id].display {
+            Display::Flex | Display::Grid | Display::Block => true,
+            Display::Inline | Display::None => false,
+        })
         .map(|child_id| {
             let child_style = &node_styles[child_id];
 
@@ -487,9 +635,13 @@ fn compute_internal<T: GetTextLayout>(
                     height: child_style.max_size.height.resolve(node_inner_size.height),
                 },
 
+                // the offsets of sticky items are only applied when scrolling
                 position: child_style
                     .position
-                    .map(|p| p.resolve(node_inner_size.width)),
+                    .map(|p| match child_style.position_type {
+                        PositionType::Sticky => Undefined,
+                        _ => p.resolve(node_inner_size.width),
+                    }),
                 margin: child_style
                     .margin
                     .map(|m| m.resolve(node_inner_size.width).or_else(0.0)),
@@ -1763,6 +1915,592 @@ fn compute_internal<T: GetTextLayout>(
     node_rects[node_id].border_widths = resolve_offsets(border);
 }
 
+/// Adjoining vertical margins, which collapse into a single margin: the largest
+/// positive margin plus the smallest (most negative) margin
+#[derive(Debug, Default, Copy, Clone)]
+struct CollapsedMargin {
+    positive: f32,
+    negative: f32,
+}
+
+impl CollapsedMargin {
+    fn new(margin: f32) -> Self {
+        let mut collapsed = Self::default();
+        collapsed.add(margin);
+        collapsed
+    }
+
+    fn add(&mut self, margin: f32) {
+        self.positive = self.positive.max(margin);
+        self.negative = self.negative.min(margin);
+    }
+
+    fn get(&self) -> f32 {
+        self.positive + self.negative
+    }
+}
+
+/// Float that has already been placed inside of a block, `rect` is its margin
+/// box, relative to the content box of the block
+#[derive(Debug, Copy, Clone)]
+struct PlacedFloat {
+    float: Float,
+    rect: LayoutRect,
+}
+
+/// Lays out the in-flow children of a `display: block` node: every child takes the full
+/// width of the block (unless it has a width) and is placed below its previous sibling.
+///
+/// The vertical margins between siblings collapse. If the block itself is in the normal
+/// flow of a block parent and has no padding / border at the top (or bottom), the margin
+/// of its first (or last) child collapses with its own margin, which is why this function
+/// returns the used margins of the block next to the size of its content box.
+///
+/// Floating children are taken out of the flow and pushed to the left / right edge of
+/// the block, the text of the following siblings flows around them (see `get_float_holes`).
+fn compute_block<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<
+        NodeId,
+        (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect),
+    >,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    inner_size: Size<Number>,
+    content_size: Option<RectSize>,
+    margin: Offsets<f32>,
+    padding_border: Offsets<f32>,
+    perform_layout: bool,
+) -> (Size<f32>, Offsets<f32>) {
+    let block_style = &node_styles[node_id];
+
+    // Flex and grid items (and absolutely positioned nodes) establish a
+    // new formatting context, their children's margins never collapse with theirs
+    let is_in_block_flow = !block_style.position_type.is_out_of_flow()
+        && node_hierarchy[node_id]
+            .parent
+            .map(|parent_id| node_styles[parent_id].display == Display::Block)
+            .unwrap_or(false);
+
+    let collapse_top = is_in_block_flow && padding_border.top == 0.0;
+    let collapse_bottom =
+        is_in_block_flow && padding_border.bottom == 0.0 && inner_size.height.is_undefined();
+
+    let children = node_id
+        .children(node_hierarchy)
+        .filter(|child_id| {
+            !node_styles[*child_id].position_type.is_out_of_flow()
+                && node_styles[*child_id].display != Display::None
+        })
+        .collect::<Vec<_>>();
+
+    // A block without children is sized by its text / image content
+    if children.is_empty() {
+        let content_size = content_size.unwrap_or(RectSize {
+            width: Number::Defined(0.0),
+            height: Number::Defined(0.0),
+        });
+        return (
+            Size {
+                width: inner_size
+                    .width
+                    .or_else(content_size.width.unwrap_or_zero()),
+                height: content_size.height.unwrap_or_zero(),
+            },
+            margin,
+        );
+    }
+
+    let mut used_margin = margin;
+    // Margins that haven't been placed yet, since the next margin could still collapse with them
+    let mut pending_margin = if collapse_top {
+        CollapsedMargin::new(margin.top)
+    } else {
+        CollapsedMargin::default()
+    };
+    let mut is_at_top = collapse_top;
+    // Bottom edge of the last child, relative to the content box
+    let mut cursor = 0.0;
+    let mut max_child_width: f32 = 0.0;
+    let mut floats = Vec::<PlacedFloat>::new();
+
+    for child_id in children {
+        let child_style = &node_styles[child_id];
+        let child_margin = child_style
+            .margin
+            .map(|m| m.resolve(inner_size.width).or_else(0.0));
+
+        let child_width = child_style
+            .size
+            .width
+            .resolve(inner_size.width)
+            .maybe_max(child_style.min_size.width.resolve(inner_size.width))
+            .maybe_min(child_style.max_size.width.resolve(inner_size.width));
+
+        if child_style.float != Float::None {
+            // Floats without a width shrink to fit their content
+            compute_internal(
+                child_id,
+                node_hierarchy,
+                node_styles,
+                node_rects,
+                resolved_text_layout_options,
+                rect_contents,
+                Size {
+                    width: child_width,
+                    height: child_style.size.height.resolve(inner_size.height),
+                },
+                Size {
+                    width: if child_width.is_defined() {
+                        inner_size.width
+                    } else {
+                        Number::Undefined
+                    },
+                    height: inner_size.height,
+                },
+                perform_layout,
+            );
+
+            let float_size = LayoutSize::new(
+                node_rects[child_id].size.width.unwrap_or_zero() + child_margin.horizontal(),
+                node_rects[child_id].size.height.unwrap_or_zero() + child_margin.vertical(),
+            );
+            let flow_y = if is_at_top {
+                0.0
+            } else {
+                cursor + pending_margin.get()
+            };
+            let float_rect = place_float(
+                &floats,
+                child_style.float,
+                float_size,
+                flow_y,
+                inner_size.width,
+            );
+
+            node_rects[child_id].origin = RectOrigin {
+                x: Number::Defined(padding_border.left + float_rect.origin.x + child_margin.left),
+                y: Number::Defined(padding_border.top + float_rect.origin.y + child_margin.top),
+            };
+            floats.push(PlacedFloat {
+                float: child_style.float,
+                rect: float_rect,
+            });
+            max_child_width = max_child_width.max(float_size.width);
+            continue;
+        }
+
+        // Text in the child needs to know where the child is before its layout,
+        // so that it can flow around the preceding floats
+        let mut tentative_margin = pending_margin;
+        tentative_margin.add(child_margin.top);
+        node_rects[child_id].origin = RectOrigin {
+            x: Number::Defined(padding_border.left + child_margin.left),
+            y: Number::Defined(
+                padding_border.top
+                    + if is_at_top {
+                        0.0
+                    } else {
+                        cursor + tentative_margin.get()
+                    },
+            ),
+        };
+
+        compute_internal(
+            child_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            Size {
+                width: child_width.or_else(inner_size.width - child_margin.horizontal()),
+                height: child_style.size.height.resolve(inner_size.height),
+            },
+            inner_size,
+            perform_layout,
+        );
+
+        let child_rect = &node_rects[child_id];
+        let child_size = Size {
+            width: child_rect.size.width.unwrap_or_zero(),
+            height: child_rect.size.height.unwrap_or_zero(),
+        };
+        // Margins of a block child may already contain the collapsed margins of its children
+        let (child_margin_top, child_margin_bottom) =
+            (child_rect.margin.top, child_rect.margin.bottom);
+        let is_empty = child_style.display == Display::Block
+            && child_size.height == 0.0
+            && child_rect.padding.top + child_rect.padding.bottom == 0.0
+            && child_rect.border_widths.top + child_rect.border_widths.bottom == 0.0;
+
+        pending_margin.add(child_margin_top);
+
+        let child_y = if is_empty {
+            // The top and bottom margin of an empty block collapse through it
+            pending_margin.add(child_margin_bottom);
+            if is_at_top {
+                0.0
+            } else {
+                cursor + pending_margin.get()
+            }
+        } else if is_at_top {
+            // The margin of the first child collapses with the margin of this node
+            is_at_top = false;
+            used_margin.top = pending_margin.get();
+            pending_margin = CollapsedMargin::new(child_margin_bottom);
+            cursor = child_size.height;
+            0.0
+        } else {
+            let child_y = cursor + pending_margin.get();
+            pending_margin = CollapsedMargin::new(child_margin_bottom);
+            cursor = child_y + child_size.height;
+            child_y
+        };
+
+        // Horizontal auto margins align a child with a fixed width
+        let free_space = (inner_size.width - child_size.width - child_margin.horizontal())
+            .or_else(0.0)
+            .max(0.0);
+        let auto_margin_offset = match (child_style.margin.left, child_style.margin.right) {
+            (Dimension::Auto, Dimension::Auto) => free_space / 2.0,
+            (Dimension::Auto, _) => free_space,
+            _ => 0.0,
+        };
+
+        // Relative positioning doesn't affect the position of the following siblings,
+        // the offsets of sticky nodes are only applied when scrolling
+        let relative_offset = match child_style.position_type {
+            PositionType::Sticky => Size {
+                width: 0.0,
+                height: 0.0,
+            },
+            _ => Size {
+                width: child_style
+                    .position
+                    .left
+                    .resolve(inner_size.width)
+                    .or_else(0.0)
+                    - child_style
+                        .position
+                        .right
+                        .resolve(inner_size.width)
+                        .or_else(0.0),
+                height: child_style
+                    .position
+                    .top
+                    .resolve(inner_size.height)
+                    .or_else(0.0)
+                    - child_style
+                        .position
+                        .bottom
+                        .resolve(inner_size.height)
+                        .or_else(0.0),
+            },
+        };
+
+        node_rects[child_id].origin = RectOrigin {
+            x: Number::Defined(
+                padding_border.left
+                    + auto_margin_offset
+                    + child_margin.left
+                    + relative_offset.width,
+            ),
+            y: Number::Defined(padding_border.top + child_y + relative_offset.height),
+        };
+
+        max_child_width = max_child_width.max(child_size.width + child_margin.horizontal());
+    }
+
+    let content_height = if is_at_top {
+        // Only empty children, all margins collapsed into the top margin of this node
+        used_margin.top = pending_margin.get();
+        0.0
+    } else if collapse_bottom {
+        // The margin of the last child collapses with the margin of this node
+        pending_margin.add(margin.bottom);
+        used_margin.bottom = pending_margin.get();
+        cursor
+    } else {
+        cursor + pending_margin.get()
+    };
+
+    // Floats overflow the block, unless the block establishes a new formatting context
+    let content_height = if is_in_block_flow {
+        content_height
+    } else {
+        floats
+            .iter()
+            .map(|f| f.rect.origin.y + f.rect.size.height)
+            .fold(content_height, f32::max)
+    };
+
+    (
+        Size {
+            width: inner_size.width.or_else(max_child_width),
+            height: content_height,
+        },
+        used_margin,
+    )
+}
+
+/// Finds the position of a float (`size` is the size of its margin box): as high as possible,
+/// but not above `flow_y` or any previous float, next to the floats that are already there
+fn place_float(
+    floats: &[PlacedFloat],
+    float: Float,
+    size: LayoutSize,
+    flow_y: f32,
+    container_width: Number,
+) -> LayoutRect {
+    let mut y = floats
+        .last()
+        .map(|f| f.rect.origin.y)
+        .unwrap_or(0.0)
+        .max(flow_y);
+
+    loop {
+        let overlapping = floats
+            .iter()
+            .filter(|f| {
+                f.rect.origin.y < y + size.height && f.rect.origin.y + f.rect.size.height > y
+            })
+            .collect::<Vec<_>>();
+
+        let left_edge = overlapping
+            .iter()
+            .filter(|f| f.float == Float::Left)
+            .map(|f| f.rect.origin.x + f.rect.size.width)
+            .fold(0.0, f32::max);
+        let right_edge = overlapping
+            .iter()
+            .filter(|f| f.float == Float::Right)
+            .map(|f| f.rect.origin.x)
+            .fold(container_width.or_else(left_edge + size.width), f32::min);
+
+        if overlapping.is_empty() || right_edge - left_edge >= size.width {
+            let x = match float {
+                Float::Right => right_edge - size.width,
+                _ => left_edge,
+            };
+            return LayoutRect::new(LayoutPoint::new(x, y), size);
+        }
+
+        // Doesn't fit next to the other floats, try again below the first one that ends
+        y = overlapping
+            .iter()
+            .map(|f| f.rect.origin.y + f.rect.size.height)
+            .fold(f32::INFINITY, f32::min);
+    }
+}
+
+/// Returns the regions that the text of a node has to flow around: the margin boxes of
+/// the floats that precede the node in the same block, relative to the origin of the node.
+///
+/// The holes extend to the edge of the text (`max_width`), so that the text never
+/// starts to the left of a left float or continues to the right of a right float.
+fn get_float_holes(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &NodeDataContainer<Rect>,
+    max_width: Number,
+) -> Vec<LayoutRect> {
+    let is_in_block = node_hierarchy[node_id]
+        .parent
+        .map(|parent_id| node_styles[parent_id].display == Display::Block)
+        .unwrap_or(false);
+
+    if !is_in_block || node_styles[node_id].float != Float::None {
+        return Vec::new();
+    }
+
+    let text_origin = node_rects[node_id].origin;
+    let (text_x, text_y) = (
+        text_origin.x.unwrap_or_zero(),
+        text_origin.y.unwrap_or_zero(),
+    );
+
+    let mut holes = Vec::new();
+    let mut sibling = node_hierarchy[node_id].previous_sibling;
+
+    while let Some(sibling_id) = sibling {
+        sibling = node_hierarchy[sibling_id].previous_sibling;
+
+        let sibling_style = &node_styles[sibling_id];
+        if sibling_style.float == Float::None
+            || sibling_style.display == Display::None
+            || sibling_style.position_type.is_out_of_flow()
+        {
+            continue;
+        }
+
+        let rect = &node_rects[sibling_id];
+        let left = rect.origin.x.unwrap_or_zero() - rect.margin.left - text_x;
+        let right =
+            rect.origin.x.unwrap_or_zero() + rect.size.width.unwrap_or_zero() + rect.margin.right
+                - text_x;
+        let top = rect.origin.y.unwrap_or_zero() - rect.margin.top - text_y;
+        let bottom =
+            rect.origin.y.unwrap_or_zero() + rect.size.height.unwrap_or_zero() + rect.margin.bottom
+                - text_y;
+
+        let (left, right) = match sibling_style.float {
+            Float::Left => (0.0, right),
+            _ => (left.max(0.0), max_width.or_else(right).max(right)),
+        };
+
+        if bottom <= 0.0 || right <= left {
+            continue;
+        }
+
+        holes.push(LayoutRect::new(
+            LayoutPoint::new(left, top),
+            LayoutSize::new(right - left, bottom - top),
+        ));
+    }
+
+    holes.sort_by(|a, b| {
+        (a.origin.y, a.origin.x)
+            .partial_cmp(&(b.origin.y, b.origin.x))
+            .unwrap_or(Ordering::Equal)
+    });
+    holes
+}
+
+/// Lays out the absolutely positioned children of a node, relative to the padding box of
+/// the node (`container_size` is the size of its border box)
+pub(crate) fn compute_absolute_children<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<
+        NodeId,
+        (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect),
+    >,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    container_size: Size<Number>,
+    padding_border: Offsets<f32>,
+    border: Offsets<f32>,
+) {
+    for child_id in node_id.children(node_hierarchy).filter(|child_id| {
+        node_styles[*child_id].position_type == PositionType::Absolute
+            && node_styles[*child_id].display != Display::None
+    }) {
+        compute_absolute_child(
+            child_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            container_size,
+            border,
+            RectOrigin {
+                x: Defined(padding_border.left),
+                y: Defined(padding_border.top),
+            },
+        );
+    }
+}
+
+/// Lays out a single absolutely positioned (or fixed) node inside of a container with
+/// the given border. Axes without an offset fall back to the `static_origin`.
+fn compute_absolute_child<T: GetTextLayout>(
+    child_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<
+        NodeId,
+        (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect),
+    >,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    container_size: Size<Number>,
+    border: Offsets<f32>,
+    static_origin: RectOrigin,
+) {
+    let container_width = container_size.width;
+    let container_height = container_size.height;
+    let child_style = &node_styles[child_id];
+
+    let left = child_style.position.left.resolve(container_width)
+        + child_style.margin.left.resolve(container_width);
+    let right = child_style.position.right.resolve(container_width)
+        + child_style.margin.right.resolve(container_width);
+    let top = child_style.position.top.resolve(container_height)
+        + child_style.margin.top.resolve(container_height);
+    let bottom = child_style.position.bottom.resolve(container_height)
+        + child_style.margin.bottom.resolve(container_height);
+
+    let width = child_style
+        .size
+        .width
+        .resolve(container_width)
+        .maybe_max(child_style.min_size.width.resolve(container_width))
+        .maybe_min(child_style.max_size.width.resolve(container_width))
+        .or_else(if left.is_defined() && right.is_defined() {
+            container_width - left - right - border.horizontal()
+        } else {
+            Undefined
+        });
+
+    let height = child_style
+        .size
+        .height
+        .resolve(container_height)
+        .maybe_max(child_style.min_size.height.resolve(container_height))
+        .maybe_min(child_style.max_size.height.resolve(container_height))
+        .or_else(if top.is_defined() && bottom.is_defined() {
+            container_height - top - bottom - border.vertical()
+        } else {
+            Undefined
+        });
+
+    compute_internal(
+        child_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        Size { width, height },
+        Size {
+            width: container_width,
+            height: container_height,
+        },
+        true,
+    );
+
+    let child_size = node_rects[child_id].size;
+
+    node_rects[child_id].origin = RectOrigin {
+        x: match (left, right) {
+            (Defined(left), _) => Defined(border.left + left),
+            (Undefined, Defined(right)) => Defined(
+                container_width.unwrap_or_zero()
+                    - border.right
+                    - right
+                    - child_size.width.unwrap_or_zero(),
+            ),
+            (Undefined, Undefined) => static_origin.x,
+        },
+        y: match (top, bottom) {
+            (Defined(top), _) => Defined(border.top + top),
+            (Undefined, Defined(bottom)) => Defined(
+                container_height.unwrap_or_zero()
+                    - border.bottom
+                    - bottom
+                    - child_size.height.unwrap_or_zero(),
+            ),
+            (Undefined, Undefined) => static_origin.y,
+        },
+    };
+}
+
 #[inline]
 fn layout_line<T: GetTextLayout>(
     line: &mut FlexLine,
@@ -1881,3 +2619,359 @@ fn layout_item<T: GetTextLayout>(
         + child.margin.main(dir)
         + node_rects[child.node_id].size.main(dir).unwrap_or_zero();
 }
+
+/// Returns a `display: block` node with the given height and vertical margins
+#[cfg(test)]
+fn get_test_block(height: Dimension, margin_top: f32, margin_bottom: f32) -> Style {
+    Style {
+        display: Display::Block,
+        size: Size {
+            width: Dimension::Auto,
+            height,
+        },
+        margin: Offsets {
+            top: Dimension::Pixels(margin_top),
+            bottom: Dimension::Pixels(margin_bottom),
+            ..Offsets::default()
+        },
+        ..Style::default()
+    }
+}
+
+/// Returns a 400px wide `display: block` root node
+#[cfg(test)]
+fn get_test_root() -> Style {
+    Style {
+        size: Size {
+            width: Dimension::Pixels(400.0),
+            height: Dimension::Auto,
+        },
+        ..get_test_block(Dimension::Auto, 0.0, 0.0)
+    }
+}
+
+/// Lays out the `dom` inside of the root node of `get_test_root`
+/// and returns the bounds of all nodes (including the root)
+#[cfg(test)]
+fn solve_test_blocks(dom: &azul_core::dom::Dom<()>, styles: Vec<Style>) -> Vec<LayoutRect> {
+    let styles = std::iter::once(get_test_root()).chain(styles).collect();
+    crate::solve_test_layout(dom, styles, Vec::new(), LayoutSize::new(400.0, 300.0))
+        .internal
+        .into_iter()
+        .map(|rect| rect.bounds)
+        .collect()
+}
+
+#[test]
+fn test_block_sibling_margin_collapse() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..4).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            get_test_block(Dimension::Pixels(50.0), 0.0, 20.0),
+            // max(20px, 30px)
+            get_test_block(Dimension::Pixels(50.0), 30.0, 10.0),
+            // 10px + -10px
+            get_test_block(Dimension::Pixels(50.0), -10.0, -5.0),
+            // min(-5px, -20px)
+            get_test_block(Dimension::Pixels(50.0), -20.0, 0.0),
+        ],
+    );
+
+    assert_eq!(rects[1].origin.y, 0.0);
+    assert_eq!(rects[2].origin.y, 80.0);
+    assert_eq!(rects[3].origin.y, 130.0);
+    assert_eq!(rects[4].origin.y, 160.0);
+    assert_eq!(rects[0].size.height, 210.0);
+}
+
+#[test]
+fn test_block_parent_child_margin_collapse() {
+    use azul_core::dom::Dom;
+
+    //  0: [root]
+    //   |-- 1: [div margin: 10px 0]
+    //   |    |-- 2: [div height: 40px, margin-top: 30px]
+    //   |    |-- 3: [div height: 40px, margin-bottom: 25px]
+    //   |-- 4: [div height: 10px]
+    let dom = Dom::<()>::div()
+        .with_child(Dom::div().with_child(Dom::div()).with_child(Dom::div()))
+        .with_child(Dom::div());
+
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            get_test_block(Dimension::Auto, 10.0, 10.0),
+            get_test_block(Dimension::Pixels(40.0), 30.0, 0.0),
+            get_test_block(Dimension::Pixels(40.0), 0.0, 25.0),
+            get_test_block(Dimension::Pixels(10.0), 0.0, 0.0),
+        ],
+    );
+
+    // The margins of the first and last child collapse with the margins of the parent
+    assert_eq!(rects[1].origin.y, 30.0);
+    assert_eq!(rects[1].size.height, 80.0);
+    assert_eq!(rects[2].origin.y, 30.0);
+    assert_eq!(rects[3].origin.y, 70.0);
+    assert_eq!(rects[4].origin.y, 135.0);
+}
+
+#[test]
+fn test_block_padding_border_prevent_margin_collapse() {
+    use azul_core::dom::Dom;
+
+    let dom = Dom::<()>::div()
+        .with_child(Dom::div().with_child(Dom::div()).with_child(Dom::div()))
+        .with_child(Dom::div());
+
+    // padding-top: 5px, border-bottom: 2px
+    let parent = Style {
+        padding: Offsets {
+            top: Dimension::Pixels(5.0),
+            ..Offsets::default()
+        },
+        border: Offsets {
+            bottom: Dimension::Pixels(2.0),
+            ..Offsets::default()
+        },
+        ..get_test_block(Dimension::Auto, 10.0, 10.0)
+    };
+
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            parent,
+            get_test_block(Dimension::Pixels(40.0), 30.0, 0.0),
+            get_test_block(Dimension::Pixels(40.0), 0.0, 25.0),
+            get_test_block(Dimension::Pixels(10.0), 0.0, 0.0),
+        ],
+    );
+
+    assert_eq!(rects[1].origin.y, 10.0);
+    assert_eq!(rects[2].origin.y, 45.0);
+    assert_eq!(rects[3].origin.y, 85.0);
+    // 5px padding + 30px margin + 2 * 40px + 25px margin + 2px border
+    assert_eq!(rects[1].size.height, 142.0);
+    assert_eq!(rects[4].origin.y, 162.0);
+}
+
+#[test]
+fn test_block_empty_block_collapses_through() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..3).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            get_test_block(Dimension::Pixels(50.0), 0.0, 20.0),
+            // Empty block: its margins collapse with the margins around it
+            get_test_block(Dimension::Auto, 30.0, 10.0),
+            get_test_block(Dimension::Pixels(50.0), 15.0, 0.0),
+        ],
+    );
+
+    assert_eq!(rects[2].size.height, 0.0);
+    assert_eq!(rects[3].origin.y, 80.0);
+    assert_eq!(rects[0].size.height, 130.0);
+}
+
+#[test]
+fn test_block_auto_margin_centering() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..2).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let fixed_width = |margin_left: Dimension, margin_right: Dimension| Style {
+        size: Size {
+            width: Dimension::Pixels(100.0),
+            height: Dimension::Pixels(20.0),
+        },
+        margin: Offsets {
+            left: margin_left,
+            right: margin_right,
+            ..Offsets::default()
+        },
+        ..get_test_block(Dimension::Auto, 0.0, 0.0)
+    };
+
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            // margin: 0 auto
+            fixed_width(Dimension::Auto, Dimension::Auto),
+            // margin-left: auto
+            fixed_width(Dimension::Auto, Dimension::Pixels(0.0)),
+        ],
+    );
+
+    assert_eq!(rects[1].origin.x, 150.0);
+    assert_eq!(rects[1].size.width, 100.0);
+    assert_eq!(rects[2].origin.x, 300.0);
+}
+
+#[test]
+fn test_block_skips_display_none() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..3).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            get_test_block(Dimension::Pixels(50.0), 0.0, 10.0),
+            Style {
+                display: Display::None,
+                ..get_test_block(Dimension::Pixels(100.0), 20.0, 20.0)
+            },
+            get_test_block(Dimension::Pixels(50.0), 0.0, 0.0),
+        ],
+    );
+
+    assert_eq!(rects[2].size, LayoutSize::zero());
+    assert_eq!(rects[3].origin.y, 60.0);
+    assert_eq!(rects[0].size.height, 110.0);
+}
+
+/// Returns a floating node of the given size (with a right margin of `margin_right`)
+#[cfg(test)]
+fn get_test_float(float: Float, width: f32, height: f32, margin_right: f32) -> Style {
+    Style {
+        float,
+        size: Size {
+            width: Dimension::Pixels(width),
+            height: Dimension::Pixels(height),
+        },
+        margin: Offsets {
+            right: Dimension::Pixels(margin_right),
+            ..Offsets::default()
+        },
+        ..get_test_block(Dimension::Auto, 0.0, 0.0)
+    }
+}
+
+#[test]
+fn test_float_left_right_side_by_side() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..3).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            get_test_float(Float::Left, 100.0, 50.0, 0.0),
+            get_test_float(Float::Right, 100.0, 50.0, 0.0),
+            get_test_float(Float::Left, 150.0, 50.0, 0.0),
+        ],
+    );
+
+    assert_eq!(rects[1].origin, LayoutPoint::new(0.0, 0.0));
+    assert_eq!(rects[2].origin, LayoutPoint::new(300.0, 0.0));
+    assert_eq!(rects[3].origin, LayoutPoint::new(100.0, 0.0));
+}
+
+#[test]
+fn test_float_drops_below_if_it_does_not_fit() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..4).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let rects = solve_test_blocks(
+        &dom,
+        vec![
+            get_test_float(Float::Left, 250.0, 50.0, 0.0),
+            get_test_float(Float::Left, 100.0, 30.0, 0.0),
+            // 250px + 100px + 100px > 400px
+            get_test_float(Float::Left, 100.0, 40.0, 0.0),
+            // Only fits below all other floats
+            get_test_float(Float::Left, 300.0, 10.0, 0.0),
+        ],
+    );
+
+    assert_eq!(rects[1].origin, LayoutPoint::new(0.0, 0.0));
+    assert_eq!(rects[2].origin, LayoutPoint::new(250.0, 0.0));
+    // Placed below the float that ends first, next to the first float
+    assert_eq!(rects[3].origin, LayoutPoint::new(250.0, 30.0));
+    assert_eq!(rects[4].origin, LayoutPoint::new(0.0, 70.0));
+}
+
+#[test]
+fn test_float_holes_relative_to_paragraph() {
+    use crate::TestText;
+    use azul_core::dom::Dom;
+
+    //  0: [root]
+    //   |-- 1: [div height: 20px]
+    //   |-- 2: [div float: left, 100x50, margin-right: 10px]
+    //   |-- 3: [div float: right, 80x30]
+    //   |-- 4: [p margin-top: 10px]
+    let dom = (0..4).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let styles = vec![
+        get_test_root(),
+        get_test_block(Dimension::Pixels(20.0), 0.0, 0.0),
+        get_test_float(Float::Left, 100.0, 50.0, 10.0),
+        get_test_float(Float::Right, 80.0, 30.0, 0.0),
+        get_test_block(Dimension::Auto, 10.0, 0.0),
+    ];
+
+    let rects = crate::solve_test_layout(
+        &dom,
+        styles,
+        vec![(NodeId::new(4), TestText(LayoutSize::new(200.0, 20.0)))],
+        LayoutSize::new(400.0, 300.0),
+    );
+
+    let paragraph = &rects[NodeId::new(4)];
+    assert_eq!(paragraph.bounds.origin, LayoutPoint::new(0.0, 30.0));
+
+    // The floats start at y = 20px, 10px above the paragraph
+    let holes = &paragraph
+        .resolved_text_layout_options
+        .as_ref()
+        .unwrap()
+        .0
+        .holes;
+    assert_eq!(
+        holes,
+        &vec![
+            LayoutRect::new(LayoutPoint::new(0.0, -10.0), LayoutSize::new(110.0, 50.0)),
+            LayoutRect::new(LayoutPoint::new(320.0, -10.0), LayoutSize::new(80.0, 30.0)),
+        ]
+    );
+}
+
+#[test]
+fn test_flex_row_direction_rtl() {
+    use azul_core::dom::Dom;
+
+    let dom = (0..2).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let fixed_size = |width: f32| Style {
+        size: Size {
+            width: Dimension::Pixels(width),
+            height: Dimension::Pixels(50.0),
+        },
+        ..Style::default()
+    };
+
+    let solve = |flex_direction: FlexDirection| {
+        let container = Style {
+            display: Display::Flex,
+            direction: Direction::RTL,
+            flex_direction,
+            size: Size {
+                width: Dimension::Pixels(400.0),
+                height: Dimension::Pixels(100.0),
+            },
+            ..Style::default()
+        };
+        let styles = vec![container, fixed_size(100.0), fixed_size(50.0)];
+        let rects =
+            crate::solve_test_layout(&dom, styles, Vec::new(), LayoutSize::new(400.0, 300.0));
+        (
+            rects[NodeId::new(1)].bounds.origin.x,
+            rects[NodeId::new(2)].bounds.origin.x,
+        )
+    };
+
+    // direction: rtl + flex-direction: row starts at the right edge of the container
+    assert_eq!(solve(FlexDirection::Row), (300.0, 250.0));
+    // ... and flex-direction: row-reverse at the left edge
+    assert_eq!(solve(FlexDirection::RowReverse), (0.0, 100.0));
+}
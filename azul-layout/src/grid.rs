@@ -0,0 +1,955 @@
+//! CSS grid layout (`display: grid`), runs alongside the flexbox algorithm in `algo`:
+//! places the grid items (explicit placement + auto-placement), sizes the column and
+//! row tracks and then lays out every item inside of its grid area.
+//!
+//! Compared to the spec, a few things are simplified: `fr` tracks don't grow to fit
+//! their content (`1fr` behaves like `minmax(0, 1fr)`), auto-placement always fills
+//! row by row (`grid-auto-flow: row`) and implicit tracks are always sized as `auto`.
+
+use std::{collections::BTreeMap, f32, iter, ops::Range};
+
+use azul_core::{
+    dom::NodeId,
+    id_tree::{NodeDataContainer, NodeHierarchy},
+    ui_solver::{InlineTextLayout, ResolvedTextLayoutOptions},
+};
+use azul_css::{LayoutRect, MAX_GRID_LINE};
+
+use crate::{
+    algo::{compute_absolute_children, compute_internal},
+    geometry::{Offsets, Rect, RectOrigin, Size},
+    number::{
+        Number::{self, *},
+        OrElse, ToNumber,
+    },
+    style::*,
+    GetTextLayout, RectContent,
+};
+
+/// Grid item, after its grid area has been resolved
+#[derive(Debug)]
+struct GridItem {
+    node_id: NodeId,
+    margin: Offsets<f32>,
+    /// Index of the first column + number of spanned columns
+    column: (usize, usize),
+    /// Index of the first row + number of spanned rows
+    row: (usize, usize),
+}
+
+/// Sizing function of a track with all percentages already resolved
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum MinSizing {
+    Fixed(f32),
+    Content,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum MaxSizing {
+    Fixed(f32),
+    Content,
+    Flexible(f32),
+}
+
+/// Cells that are already taken by a grid item, used during auto-placement
+#[derive(Debug, Default)]
+struct CellOccupancy {
+    rows: Vec<Vec<bool>>,
+}
+
+impl CellOccupancy {
+    fn is_free(&self, row: (usize, usize), column: (usize, usize)) -> bool {
+        span_range(row).all(|r| match self.rows.get(r) {
+            Some(cells) => span_range(column).all(|c| !cells.get(c).cloned().unwrap_or(false)),
+            None => true,
+        })
+    }
+
+    fn occupy(&mut self, row: (usize, usize), column: (usize, usize)) {
+        if self.rows.len() < row.0 + row.1 {
+            self.rows.resize(row.0 + row.1, Vec::new());
+        }
+        for cells in &mut self.rows[span_range(row)] {
+            if cells.len() < column.0 + column.1 {
+                cells.resize(column.0 + column.1, false);
+            }
+            for cell in &mut cells[span_range(column)] {
+                *cell = true;
+            }
+        }
+    }
+}
+
+#[inline]
+fn span_range((start, span): (usize, usize)) -> Range<usize> {
+    start..start + span
+}
+
+/// Lays out the children of a `display: grid` node, returns the size of the content box
+/// of the grid container: `inner_size` if it is defined, otherwise the size of all tracks.
+///
+/// Like in the flex algorithm, the children are positioned relative to their parent.
+pub(crate) fn compute_grid<T: GetTextLayout>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    node_rects: &mut NodeDataContainer<Rect>,
+    resolved_text_layout_options: &mut BTreeMap<
+        NodeId,
+        (ResolvedTextLayoutOptions, InlineTextLayout, LayoutRect),
+    >,
+    rect_contents: &mut BTreeMap<NodeId, RectContent<T>>,
+    inner_size: Size<Number>,
+    padding_border: Offsets<f32>,
+    border: Offsets<f32>,
+    perform_layout: bool,
+) -> Size<f32> {
+    let grid_style = &node_styles[node_id];
+
+    let column_gap = grid_style
+        .grid_gap
+        .width
+        .resolve(inner_size.width)
+        .or_else(0.0);
+    let row_gap = grid_style
+        .grid_gap
+        .height
+        .resolve(inner_size.height)
+        .or_else(0.0);
+
+    let (items, column_count, row_count) =
+        place_items(node_id, node_hierarchy, node_styles, inner_size);
+
+    let column_tracks = resolve_tracks(
+        &grid_style.grid_template_columns,
+        column_count,
+        inner_size.width,
+    );
+    let row_tracks = resolve_tracks(&grid_style.grid_template_rows, row_count, inner_size.height);
+
+    // Size the columns first, items can only be measured in height once their width is known
+
+    let mut column_contributions = Vec::new();
+    for item in items
+        .iter()
+        .filter(|item| spans_content_sized_track(&column_tracks, item.column))
+    {
+        compute_internal(
+            item.node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            Size {
+                width: Undefined,
+                height: Undefined,
+            },
+            Size {
+                width: Undefined,
+                height: Undefined,
+            },
+            false,
+        );
+        let width = node_rects[item.node_id].size.width.unwrap_or_zero();
+        column_contributions.push((item.column, width + item.margin.horizontal()));
+    }
+
+    let column_sizes = size_tracks(
+        &column_tracks,
+        inner_size.width,
+        column_gap,
+        column_contributions,
+    );
+
+    let mut row_contributions = Vec::new();
+    for item in items
+        .iter()
+        .filter(|item| spans_content_sized_track(&row_tracks, item.row))
+    {
+        let area_width = get_span_size(&column_sizes, item.column, column_gap);
+        compute_internal(
+            item.node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            Size {
+                width: Defined(area_width - item.margin.horizontal()),
+                height: Undefined,
+            },
+            Size {
+                width: Defined(area_width),
+                height: Undefined,
+            },
+            false,
+        );
+        let height = node_rects[item.node_id].size.height.unwrap_or_zero();
+        row_contributions.push((item.row, height + item.margin.vertical()));
+    }
+
+    let row_sizes = size_tracks(&row_tracks, inner_size.height, row_gap, row_contributions);
+
+    let content_size = Size {
+        width: inner_size.width.or_else(get_span_size(
+            &column_sizes,
+            (0, column_sizes.len()),
+            column_gap,
+        )),
+        height: inner_size
+            .height
+            .or_else(get_span_size(&row_sizes, (0, row_sizes.len()), row_gap)),
+    };
+
+    if !perform_layout {
+        return content_size;
+    }
+
+    // Every item stretches to fill its grid area
+    for item in &items {
+        let area_width = get_span_size(&column_sizes, item.column, column_gap);
+        let area_height = get_span_size(&row_sizes, item.row, row_gap);
+
+        compute_internal(
+            item.node_id,
+            node_hierarchy,
+            node_styles,
+            node_rects,
+            resolved_text_layout_options,
+            rect_contents,
+            Size {
+                width: Defined(area_width - item.margin.horizontal()),
+                height: Defined(area_height - item.margin.vertical()),
+            },
+            Size {
+                width: Defined(area_width),
+                height: Defined(area_height),
+            },
+            true,
+        );
+
+        node_rects[item.node_id].origin = RectOrigin {
+            x: Defined(
+                padding_border.left
+                    + get_track_offset(&column_sizes, item.column.0, column_gap)
+                    + item.margin.left,
+            ),
+            y: Defined(
+                padding_border.top
+                    + get_track_offset(&row_sizes, item.row.0, row_gap)
+                    + item.margin.top,
+            ),
+        };
+    }
+
+    // Absolutely positioned children are not grid items, they are
+    // positioned relative to the padding box of the grid container
+    compute_absolute_children(
+        node_id,
+        node_hierarchy,
+        node_styles,
+        node_rects,
+        resolved_text_layout_options,
+        rect_contents,
+        Size {
+            width: (content_size.width + padding_border.horizontal()).to_number(),
+            height: (content_size.height + padding_border.vertical()).to_number(),
+        },
+        padding_border,
+        border,
+    );
+
+    content_size
+}
+
+/// Resolves the grid area of every in-flow child: first the items with an explicit
+/// position, then the items that are locked to a row and then all remaining items,
+/// in DOM order. Returns the items + the number of columns and rows of the grid.
+fn place_items(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    node_styles: &NodeDataContainer<Style>,
+    inner_size: Size<Number>,
+) -> (Vec<GridItem>, usize, usize) {
+    let grid_style = &node_styles[node_id];
+    let explicit_columns = grid_style.grid_template_columns.len();
+    let explicit_rows = grid_style.grid_template_rows.len();
+
+    let children = node_id
+        .children(node_hierarchy)
+        .filter(|child_id| {
+            !node_styles[*child_id].position_type.is_out_of_flow()
+                && node_styles[*child_id].display != Display::None
+        })
+        .map(|child_id| {
+            let child_style = &node_styles[child_id];
+            (
+                child_id,
+                child_style
+                    .margin
+                    .map(|m| m.resolve(inner_size.width).or_else(0.0)),
+                resolve_placement(child_style.grid_column, explicit_columns),
+                resolve_placement(child_style.grid_row, explicit_rows),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // The implicit grid has at least as many columns as the widest item needs
+    let column_count = children
+        .iter()
+        .map(|(_, _, (start, span), _)| start.unwrap_or(0) + span)
+        .fold(explicit_columns.max(1), usize::max);
+
+    let mut occupancy = CellOccupancy::default();
+    let mut placed = vec![None; children.len()];
+
+    // 1. Items with a definite row and column
+    for (i, (_, _, column, row)) in children.iter().enumerate() {
+        if let ((Some(column_start), column_span), (Some(row_start), row_span)) = (column, row) {
+            let area = ((*column_start, *column_span), (*row_start, *row_span));
+            occupancy.occupy(area.1, area.0);
+            placed[i] = Some(area);
+        }
+    }
+
+    // 2. Items that are locked to a row, placed in the first column where they fit
+    for (i, (_, _, (column_start, column_span), row)) in children.iter().enumerate() {
+        if let (None, (Some(row_start), row_span)) = (column_start, row) {
+            let row = (*row_start, *row_span);
+            let column_start = (0..=column_count.saturating_sub(*column_span))
+                .find(|c| occupancy.is_free(row, (*c, *column_span)))
+                .unwrap_or(0);
+            let area = ((column_start, *column_span), row);
+            occupancy.occupy(area.1, area.0);
+            placed[i] = Some(area);
+        }
+    }
+
+    // 3. All remaining items, the cursor only moves forward ("sparse" packing)
+    let mut cursor = (0, 0); // (row, column)
+    for (i, (_, _, column, (_, row_span))) in children.iter().enumerate() {
+        if placed[i].is_some() {
+            continue;
+        }
+
+        let area = match column {
+            (Some(column_start), column_span) => {
+                let column = (*column_start, *column_span);
+                if *column_start < cursor.1 {
+                    cursor.0 += 1;
+                }
+                cursor.1 = *column_start;
+                while !occupancy.is_free((cursor.0, *row_span), column) {
+                    cursor.0 += 1;
+                }
+                (column, (cursor.0, *row_span))
+            }
+            (None, column_span) => loop {
+                if cursor.1 + column_span > column_count {
+                    cursor = (cursor.0 + 1, 0);
+                    continue;
+                }
+                if occupancy.is_free((cursor.0, *row_span), (cursor.1, *column_span)) {
+                    let area = ((cursor.1, *column_span), (cursor.0, *row_span));
+                    cursor.1 += column_span;
+                    break area;
+                }
+                cursor.1 += 1;
+            },
+        };
+
+        occupancy.occupy(area.1, area.0);
+        placed[i] = Some(area);
+    }
+
+    let row_count = occupancy.rows.len().max(explicit_rows);
+
+    let items = children
+        .into_iter()
+        .zip(placed.into_iter())
+        .filter_map(|((node_id, margin, _, _), area)| {
+            let (column, row) = area?;
+            Some(GridItem {
+                node_id,
+                margin,
+                column,
+                row,
+            })
+        })
+        .collect();
+
+    (items, column_count, row_count)
+}
+
+/// Resolves the `grid-column` / `grid-row` of an item to a (start track, span) pair,
+/// the start track is `None` if the item has to be auto-placed on this axis
+fn resolve_placement(placement: GridPlacement, explicit_tracks: usize) -> (Option<usize>, usize) {
+    use crate::style::GridLine::*;
+
+    // Line 0 and span 0 are invalid and ignored
+    let normalize = |line: GridLine| match line {
+        Line(0) | Span(0) => Auto,
+        other => other,
+    };
+
+    // Lines start at 1, negative lines count backwards from the end of the explicit grid.
+    // Implicit tracks are only created at the end of the grid, so the index is clamped to 0.
+    // Like in the CSS parser, lines and spans are clamped to +/- MAX_GRID_LINE, so that
+    // the arithmetic below can't overflow and a huge line can't create millions of tracks
+    let explicit_tracks = explicit_tracks.min(MAX_GRID_LINE as usize) as i32;
+    let line_to_index = |line: i32| -> i32 {
+        let line = line.clamp(-MAX_GRID_LINE, MAX_GRID_LINE);
+        if line < 0 {
+            explicit_tracks + 1 + line
+        } else {
+            line - 1
+        }
+    };
+    let to_span = |span: u32| span.min(MAX_GRID_LINE as u32) as usize;
+    let clamp = |index: i32| index.max(0) as usize;
+
+    match (normalize(placement.start), normalize(placement.end)) {
+        (Line(start), Line(end)) => {
+            let (start, end) = (line_to_index(start), line_to_index(end));
+            let (start, end) = if end < start {
+                (end, start)
+            } else {
+                (start, end)
+            };
+            (Some(clamp(start)), (end - start).max(1) as usize)
+        }
+        (Line(start), Span(span)) => (Some(clamp(line_to_index(start))), to_span(span)),
+        (Line(start), Auto) => (Some(clamp(line_to_index(start))), 1),
+        (Span(span), Line(end)) => (
+            Some(clamp(line_to_index(end) - to_span(span) as i32)),
+            to_span(span),
+        ),
+        (Auto, Line(end)) => (Some(clamp(line_to_index(end) - 1)), 1),
+        (Span(span), _) | (Auto, Span(span)) => (None, to_span(span)),
+        (Auto, Auto) => (None, 1),
+    }
+}
+
+/// Returns the explicit tracks + `auto` tracks for the implicit grid, with all
+/// percentages resolved against the size of the grid container
+fn resolve_tracks(
+    template: &[TrackSize],
+    track_count: usize,
+    available_space: Number,
+) -> Vec<(MinSizing, MaxSizing)> {
+    template
+        .iter()
+        .cloned()
+        .chain(iter::repeat(TrackSize::default()))
+        .take(track_count.max(template.len()))
+        .map(|track| {
+            let min = match track.min {
+                // A flexible minimum is invalid CSS, `1fr` shrinks down to 0
+                TrackBreadth::Fraction(_) => MinSizing::Fixed(0.0),
+                TrackBreadth::Auto => MinSizing::Content,
+                fixed => match fixed.resolve(available_space) {
+                    Defined(size) => MinSizing::Fixed(size),
+                    Undefined => MinSizing::Content,
+                },
+            };
+            let max = match track.max {
+                // Without a definite size, there is no free space to distribute
+                TrackBreadth::Fraction(fr) if available_space.is_defined() => {
+                    MaxSizing::Flexible(fr)
+                }
+                TrackBreadth::Fraction(_) | TrackBreadth::Auto => MaxSizing::Content,
+                fixed => match fixed.resolve(available_space) {
+                    Defined(size) => MaxSizing::Fixed(size),
+                    Undefined => MaxSizing::Content,
+                },
+            };
+            (min, max)
+        })
+        .collect()
+}
+
+fn spans_content_sized_track(tracks: &[(MinSizing, MaxSizing)], span: (usize, usize)) -> bool {
+    tracks[span_range(span)]
+        .iter()
+        .any(|(min, max)| *min == MinSizing::Content || *max == MaxSizing::Content)
+}
+
+/// Returns the size of the given tracks, including the gaps between them
+fn get_span_size(track_sizes: &[f32], span: (usize, usize), gap: f32) -> f32 {
+    let tracks: f32 = track_sizes[span_range(span)].iter().sum();
+    tracks + gap * span.1.saturating_sub(1) as f32
+}
+
+/// Returns the distance from the start of the grid to the start of the given track
+fn get_track_offset(track_sizes: &[f32], track: usize, gap: f32) -> f32 {
+    track_sizes[..track].iter().sum::<f32>() + gap * track as f32
+}
+
+/// Sizes the tracks of one axis. `contributions` are the spanned tracks and the
+/// outer size of every item that spans at least one content-sized track.
+fn size_tracks(
+    tracks: &[(MinSizing, MaxSizing)],
+    available_space: Number,
+    gap: f32,
+    mut contributions: Vec<((usize, usize), f32)>,
+) -> Vec<f32> {
+    let free_space = available_space - gap * tracks.len().saturating_sub(1) as f32;
+
+    let mut base_sizes = tracks
+        .iter()
+        .map(|(min, _)| match min {
+            MinSizing::Fixed(size) => *size,
+            MinSizing::Content => 0.0,
+        })
+        .collect::<Vec<_>>();
+
+    let mut growth_limits = tracks
+        .iter()
+        .map(|(_, max)| match max {
+            MaxSizing::Fixed(size) => *size,
+            MaxSizing::Content => 0.0,
+            MaxSizing::Flexible(_) => f32::INFINITY,
+        })
+        .collect::<Vec<_>>();
+
+    // Items spanning a single track first, items spanning multiple
+    // tracks only distribute the size that doesn't fit yet
+    contributions.sort_by_key(|(span, _)| span.1);
+
+    for (span, size) in contributions {
+        let size = size - gap * span.1.saturating_sub(1) as f32;
+        distribute_contribution(&mut base_sizes, span, size, |i| {
+            tracks[i].0 == MinSizing::Content
+        });
+        distribute_contribution(&mut growth_limits, span, size, |i| {
+            tracks[i].1 == MaxSizing::Content
+        });
+    }
+
+    for (limit, base) in growth_limits.iter_mut().zip(base_sizes.iter()) {
+        *limit = limit.max(*base);
+    }
+
+    let free_space = match free_space {
+        Defined(free_space) => free_space,
+        Undefined => {
+            // Indefinite size: every track is as large as its content
+            return base_sizes
+                .iter()
+                .zip(growth_limits.iter())
+                .map(|(base, limit)| if limit.is_finite() { *limit } else { *base })
+                .collect();
+        }
+    };
+
+    // Grow the non-flexible tracks up to their growth limit
+    let used_space: f32 = base_sizes.iter().sum();
+    grow_tracks(&mut base_sizes, &growth_limits, free_space - used_space);
+
+    let flex_factor = |i: usize| match tracks[i].1 {
+        MaxSizing::Flexible(fr) => Some(fr),
+        _ => None,
+    };
+
+    if (0..tracks.len()).any(|i| flex_factor(i).is_some()) {
+        // Find the size of one `fr`: flexible tracks whose base size is larger
+        // than their share are treated as inflexible and the size is recalculated
+        let mut inflexible = (0..tracks.len())
+            .map(|i| flex_factor(i).is_none())
+            .collect::<Vec<_>>();
+
+        loop {
+            let leftover_space = free_space
+                - (0..tracks.len())
+                    .filter(|i| inflexible[*i])
+                    .map(|i| base_sizes[i])
+                    .sum::<f32>();
+            let flex_sum = (0..tracks.len())
+                .filter(|i| !inflexible[*i])
+                .filter_map(flex_factor)
+                .sum::<f32>()
+                .max(1.0);
+            let fr_size = leftover_space.max(0.0) / flex_sum;
+
+            let mut changed = false;
+            for i in 0..tracks.len() {
+                if let (false, Some(fr)) = (inflexible[i], flex_factor(i)) {
+                    if base_sizes[i] > fr_size * fr {
+                        inflexible[i] = true;
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                for i in 0..tracks.len() {
+                    if let (false, Some(fr)) = (inflexible[i], flex_factor(i)) {
+                        base_sizes[i] = fr_size * fr;
+                    }
+                }
+                break;
+            }
+        }
+    } else {
+        // No flexible tracks: the remaining space is distributed to the `auto` tracks
+        let remaining_space = free_space - base_sizes.iter().sum::<f32>();
+        let auto_tracks = (0..tracks.len())
+            .filter(|i| tracks[*i].1 == MaxSizing::Content)
+            .collect::<Vec<_>>();
+        if remaining_space > 0.0 && !auto_tracks.is_empty() {
+            let share = remaining_space / auto_tracks.len() as f32;
+            for i in auto_tracks {
+                base_sizes[i] += share;
+            }
+        }
+    }
+
+    base_sizes
+}
+
+/// Increases the sizes of the spanned tracks that match the `filter` equally
+/// until the tracks (+ the gaps between them) are at least `size` large
+fn distribute_contribution<F: Fn(usize) -> bool>(
+    sizes: &mut [f32],
+    span: (usize, usize),
+    size: f32,
+    filter: F,
+) {
+    let current_size: f32 = sizes[span_range(span)].iter().sum();
+    let missing_size = size - current_size;
+    if missing_size <= 0.0 {
+        return;
+    }
+
+    let targets = span_range(span).filter(|i| filter(*i)).collect::<Vec<_>>();
+    if targets.is_empty() {
+        return;
+    }
+
+    let share = missing_size / targets.len() as f32;
+    for i in targets {
+        sizes[i] += share;
+    }
+}
+
+/// Distributes `free_space` equally to all tracks that haven't reached their growth limit yet
+fn grow_tracks(base_sizes: &mut [f32], growth_limits: &[f32], mut free_space: f32) {
+    const EPSILON: f32 = 0.001;
+
+    while free_space > EPSILON {
+        let growable = (0..base_sizes.len())
+            .filter(|i| growth_limits[*i].is_finite() && base_sizes[*i] < growth_limits[*i])
+            .collect::<Vec<_>>();
+        if growable.is_empty() {
+            return;
+        }
+
+        let share = free_space / growable.len() as f32;
+        for i in growable {
+            let growth = share.min(growth_limits[i] - base_sizes[i]);
+            base_sizes[i] += growth;
+            free_space -= growth;
+        }
+    }
+}
+
+/// Returns a `display: grid` container of 400x300 pixels with the given tracks
+#[cfg(test)]
+fn get_test_grid(columns: Vec<TrackSize>, rows: Vec<TrackSize>) -> Style {
+    Style {
+        display: Display::Grid,
+        size: Size {
+            width: Dimension::Pixels(400.0),
+            height: Dimension::Pixels(300.0),
+        },
+        grid_template_columns: columns,
+        grid_template_rows: rows,
+        ..Style::default()
+    }
+}
+
+#[cfg(test)]
+fn track(breadth: TrackBreadth) -> TrackSize {
+    TrackSize {
+        min: breadth,
+        max: breadth,
+    }
+}
+
+#[cfg(test)]
+fn minmax(min: TrackBreadth, max: TrackBreadth) -> TrackSize {
+    TrackSize { min, max }
+}
+
+/// Lays out the grid container + `children` and returns the (x, y, width, height) of the children
+#[cfg(test)]
+fn solve_test_grid(grid: Style, children: Vec<Style>) -> Vec<(f32, f32, f32, f32)> {
+    use crate::solve_test_layout;
+    use azul_core::dom::Dom;
+    use azul_css::LayoutSize;
+
+    let child_count = children.len();
+    let dom = (0..child_count).fold(Dom::<()>::div(), |dom, _| dom.with_child(Dom::div()));
+    let styles = iter::once(grid).chain(children).collect();
+    let rects = solve_test_layout(&dom, styles, Vec::new(), LayoutSize::new(800.0, 600.0));
+
+    (1..=child_count)
+        .map(|i| {
+            let bounds = rects[NodeId::new(i)].bounds;
+            (
+                bounds.origin.x,
+                bounds.origin.y,
+                bounds.size.width,
+                bounds.size.height,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_grid_fr_distribution() {
+    use self::TrackBreadth::*;
+
+    // grid-template-columns: 100px 1fr 2fr
+    let grid = get_test_grid(
+        vec![
+            track(Pixels(100.0)),
+            track(Fraction(1.0)),
+            track(Fraction(2.0)),
+        ],
+        vec![track(Pixels(50.0))],
+    );
+
+    assert_eq!(
+        solve_test_grid(grid, vec![Style::default(); 3]),
+        vec![
+            (0.0, 0.0, 100.0, 50.0),
+            (100.0, 0.0, 100.0, 50.0),
+            (200.0, 0.0, 200.0, 50.0),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_minmax() {
+    use self::TrackBreadth::*;
+
+    // grid-template-columns: minmax(300px, 1fr) minmax(50px, 1fr) - the first column
+    // is larger than its share of the free space, the second one gets the rest
+    let grid = get_test_grid(
+        vec![
+            minmax(Pixels(300.0), Fraction(1.0)),
+            minmax(Pixels(50.0), Fraction(1.0)),
+        ],
+        vec![minmax(Pixels(20.0), Pixels(60.0))],
+    );
+
+    assert_eq!(
+        solve_test_grid(grid, vec![Style::default(); 2]),
+        vec![(0.0, 0.0, 300.0, 60.0), (300.0, 0.0, 100.0, 60.0)]
+    );
+}
+
+#[test]
+fn test_grid_repeat() {
+    use self::TrackBreadth::*;
+
+    // grid-template-columns: repeat(3, 100px), grid-template-rows: repeat(2, 50px),
+    // `repeat()` is expanded by the CSS parser, so the tracks are simply repeated here
+    let grid = get_test_grid(vec![track(Pixels(100.0)); 3], vec![track(Pixels(50.0)); 2]);
+
+    assert_eq!(
+        solve_test_grid(grid, vec![Style::default(); 4]),
+        vec![
+            (0.0, 0.0, 100.0, 50.0),
+            (100.0, 0.0, 100.0, 50.0),
+            (200.0, 0.0, 100.0, 50.0),
+            (0.0, 50.0, 100.0, 50.0),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_auto_tracks_sized_to_content() {
+    use self::TrackBreadth::*;
+
+    // grid-template-columns: auto 1fr - the auto column is as wide as its widest item
+    let grid = get_test_grid(
+        vec![track(Auto), track(Fraction(1.0))],
+        vec![track(Pixels(50.0)), track(Pixels(50.0))],
+    );
+
+    let fixed_width = |width: f32| Style {
+        size: Size {
+            width: Dimension::Pixels(width),
+            height: Dimension::Auto,
+        },
+        ..Style::default()
+    };
+
+    assert_eq!(
+        solve_test_grid(
+            grid,
+            vec![
+                fixed_width(80.0),
+                Style::default(),
+                fixed_width(120.0),
+                Style::default(),
+            ]
+        ),
+        vec![
+            (0.0, 0.0, 80.0, 50.0),
+            (120.0, 0.0, 280.0, 50.0),
+            (0.0, 50.0, 120.0, 50.0),
+            (120.0, 50.0, 280.0, 50.0),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_gaps() {
+    use self::TrackBreadth::*;
+
+    // grid-column-gap: 10px, grid-row-gap: 20px
+    let grid = Style {
+        grid_gap: Size {
+            width: Dimension::Pixels(10.0),
+            height: Dimension::Pixels(20.0),
+        },
+        ..get_test_grid(
+            vec![track(Pixels(100.0)), track(Pixels(100.0))],
+            vec![track(Pixels(50.0)), track(Pixels(50.0))],
+        )
+    };
+
+    assert_eq!(
+        solve_test_grid(grid, vec![Style::default(); 4]),
+        vec![
+            (0.0, 0.0, 100.0, 50.0),
+            (110.0, 0.0, 100.0, 50.0),
+            (0.0, 70.0, 100.0, 50.0),
+            (110.0, 70.0, 100.0, 50.0),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_explicit_placement_spans() {
+    use self::GridLine::*;
+
+    let grid = get_test_grid(
+        vec![track(TrackBreadth::Pixels(100.0)); 3],
+        vec![track(TrackBreadth::Pixels(50.0)); 2],
+    );
+
+    let placed = |column: GridPlacement, row: GridPlacement| Style {
+        grid_column: column,
+        grid_row: row,
+        ..Style::default()
+    };
+
+    let children = vec![
+        // grid-column: 1 / span 2, grid-row: 1 / 3
+        placed(
+            GridPlacement {
+                start: Line(1),
+                end: Span(2),
+            },
+            GridPlacement {
+                start: Line(1),
+                end: Line(3),
+            },
+        ),
+        // grid-column: -2 / -1, grid-row: 2
+        placed(
+            GridPlacement {
+                start: Line(-2),
+                end: Line(-1),
+            },
+            GridPlacement {
+                start: Line(2),
+                end: Auto,
+            },
+        ),
+    ];
+
+    assert_eq!(
+        solve_test_grid(grid, children),
+        vec![(0.0, 0.0, 200.0, 100.0), (200.0, 50.0, 100.0, 50.0)]
+    );
+}
+
+#[test]
+fn test_grid_auto_placement_into_free_cells() {
+    use self::GridLine::*;
+
+    let grid = get_test_grid(
+        vec![track(TrackBreadth::Pixels(100.0)); 3],
+        vec![track(TrackBreadth::Pixels(50.0)); 2],
+    );
+
+    // grid-column: 2, grid-row: 1 - the other items flow around it
+    let mut children = vec![Style::default(); 5];
+    children[0].grid_column = GridPlacement {
+        start: Line(2),
+        end: Auto,
+    };
+    children[0].grid_row = GridPlacement {
+        start: Line(1),
+        end: Auto,
+    };
+
+    assert_eq!(
+        solve_test_grid(grid, children),
+        vec![
+            (100.0, 0.0, 100.0, 50.0),
+            (0.0, 0.0, 100.0, 50.0),
+            (200.0, 0.0, 100.0, 50.0),
+            (0.0, 50.0, 100.0, 50.0),
+            (100.0, 50.0, 100.0, 50.0),
+        ]
+    );
+}
+
+#[test]
+fn test_grid_extreme_lines_and_spans() {
+    use self::GridLine::*;
+
+    let placement = |start: GridLine, end: GridLine| GridPlacement { start, end };
+
+    // lines and spans are clamped to MAX_GRID_LINE instead of overflowing
+    assert_eq!(
+        resolve_placement(placement(Line(i32::MIN), Line(i32::MAX)), 3),
+        (Some(0), 19_995)
+    );
+    assert_eq!(
+        resolve_placement(placement(Line(100_000_000), Auto), 3),
+        (Some(9_999), 1)
+    );
+    assert_eq!(
+        resolve_placement(placement(Span(u32::MAX), Auto), 3),
+        (None, 10_000)
+    );
+    assert_eq!(
+        resolve_placement(placement(Span(u32::MAX), Line(2)), 3),
+        (Some(0), 10_000)
+    );
+    assert_eq!(
+        resolve_placement(placement(Line(1), Span(u32::MAX)), 3),
+        (Some(0), 10_000)
+    );
+
+    // grid-column: 100000000 - the item is placed after 9995 empty implicit tracks
+    let grid = get_test_grid(
+        vec![track(TrackBreadth::Pixels(100.0)); 4],
+        vec![track(TrackBreadth::Pixels(50.0))],
+    );
+    let mut child = Style::default();
+    child.grid_column = placement(Line(100_000_000), Auto);
+
+    assert_eq!(
+        solve_test_grid(grid, vec![child]),
+        vec![(400.0, 0.0, 0.0, 50.0)]
+    );
+}
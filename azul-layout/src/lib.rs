@@ -26,11 +26,12 @@ use azul_core::{
     id_tree::{NodeDataContainer, NodeHierarchy},
     ui_solver::{InlineTextLayout, PositionedRectangle, ResolvedTextLayoutOptions},
 };
-use azul_css::LayoutRect;
+use azul_css::{LayoutRect, LayoutSize, PixelValue, SizeResolveContext};
 use std::collections::BTreeMap;
 
 mod algo;
 mod geometry;
+mod grid;
 mod number;
 
 pub mod style;
@@ -80,13 +81,16 @@ impl<T: GetTextLayout> RectContent<T> {
 }
 
 impl SolvedUi {
+    /// Solves the layout of the DOM inside of the `bounds`, `viewport` is the size of
+    /// the window, which `vw`, `vh`, `vmin` and `vmax` are resolved against
     pub fn new<T: GetStyle, U: GetTextLayout>(
         bounds: LayoutRect,
+        viewport: LayoutSize,
         node_hierarchy: &NodeHierarchy,
         display_rects: &NodeDataContainer<T>,
         mut rect_contents: BTreeMap<NodeId, RectContent<U>>,
     ) -> Self {
-        let styles = display_rects.transform(|node, node_id| {
+        let mut styles = display_rects.transform(|node, node_id| {
             let image_aspect_ratio = match rect_contents.get(&node_id) {
                 Some(RectContent::Image(w, h)) => Number::Defined(*w as f32 / *h as f32),
                 _ => Number::Undefined,
@@ -97,6 +101,22 @@ impl SolvedUi {
             style
         });
 
+        // Font sizes in `em` and `%` are relative to the font size of the parent, so the
+        // sizes are resolved from the root downwards (parents have a lower NodeId)
+        let mut contexts =
+            NodeDataContainer::new(vec![SizeResolveContext::default(); node_hierarchy.len()]);
+        for node_id in node_hierarchy.linear_iter() {
+            let font_size = Some(styles[node_id].font_size_px);
+            let context = match node_hierarchy[node_id].parent {
+                Some(parent_id) => contexts[parent_id].child(font_size),
+                None => SizeResolveContext::root(font_size, viewport.width, viewport.height),
+            };
+            let style = &mut styles[node_id];
+            style.font_size_px = PixelValue::px(context.font_size_px);
+            style.resolve_relative_units(&context);
+            contexts[node_id] = context;
+        }
+
         let mut solved_rects = algo::compute(
             NodeId::ZERO,
             node_hierarchy,
@@ -116,3 +136,112 @@ impl SolvedUi {
         SolvedUi { solved_rects }
     }
 }
+
+#[cfg(test)]
+impl GetStyle for Style {
+    fn get_style(&self) -> Style {
+        self.clone()
+    }
+}
+
+/// Text for the layout tests: a single line of text with a fixed size
+#[cfg(test)]
+pub(crate) struct TestText(pub(crate) LayoutSize);
+
+#[cfg(test)]
+impl GetTextLayout for TestText {
+    fn get_text_layout(&mut self, _: &ResolvedTextLayoutOptions) -> InlineTextLayout {
+        use azul_core::ui_solver::InlineTextLine;
+        use azul_css::LayoutPoint;
+
+        InlineTextLayout {
+            lines: vec![InlineTextLine {
+                bounds: LayoutRect::new(LayoutPoint::zero(), self.0),
+                word_start: 0,
+                word_end: 0,
+            }],
+        }
+    }
+}
+
+/// Solves the layout of the `dom` in a window of the size `viewport`, `styles` contains
+/// the style of each node of the `dom` and `text` the text of the text nodes
+#[cfg(test)]
+pub(crate) fn solve_test_layout(
+    dom: &azul_core::dom::Dom<()>,
+    styles: Vec<Style>,
+    text: Vec<(NodeId, TestText)>,
+    viewport: LayoutSize,
+) -> NodeDataContainer<PositionedRectangle> {
+    use azul_css::LayoutPoint;
+
+    let rect_contents = text
+        .into_iter()
+        .map(|(node_id, text)| (node_id, RectContent::Text(text)))
+        .collect();
+
+    SolvedUi::new(
+        LayoutRect::new(LayoutPoint::zero(), viewport),
+        viewport,
+        &dom.arena.node_layout,
+        &NodeDataContainer::new(styles),
+        rect_contents,
+    )
+    .solved_rects
+}
+
+#[test]
+fn test_relative_units_width() {
+    use crate::style::{Dimension, Display};
+    use azul_core::dom::Dom;
+    use azul_css::{CalcValue, FloatValue};
+
+    //  0: [div font-size: 20px]
+    //   |-- 1: [div width: calc(100% - 32px)]
+    //   |-- 2: [div width: 10rem]
+    //   |-- 3: [div width: 25vw]
+    //   |-- 4: [div font-size: 2em]
+    //   |    |-- 5: [div width: 5em]
+    let dom = Dom::<()>::div()
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div())
+        .with_child(Dom::div().with_child(Dom::div()));
+
+    let block = |width: Option<PixelValue>| {
+        let mut style = Style {
+            display: Display::Block,
+            ..Style::default()
+        };
+        if let Some(width) = width {
+            style.size.width = Dimension::from(width);
+        }
+        style
+    };
+
+    let calc = PixelValue::calc(CalcValue {
+        px: FloatValue::new(-32.0),
+        percent: FloatValue::new(100.0),
+        ..CalcValue::default()
+    });
+
+    let mut styles = vec![
+        block(None),
+        block(Some(calc)),
+        block(Some(PixelValue::rem(10.0))),
+        block(Some(PixelValue::vw(25.0))),
+        block(None),
+        block(Some(PixelValue::em(5.0))),
+    ];
+    styles[0].font_size_px = PixelValue::px(20.0);
+    styles[4].font_size_px = PixelValue::em(2.0);
+    // Nodes without a font size inherit the font size of their parent
+    styles[5].font_size_px = PixelValue::percent(100.0);
+
+    let rects = solve_test_layout(&dom, styles, Vec::new(), LayoutSize::new(600.0, 400.0));
+
+    assert_eq!(rects[NodeId::new(1)].bounds.size.width, 568.0);
+    assert_eq!(rects[NodeId::new(2)].bounds.size.width, 200.0);
+    assert_eq!(rects[NodeId::new(3)].bounds.size.width, 150.0);
+    assert_eq!(rects[NodeId::new(5)].bounds.size.width, 200.0);
+}
@@ -22,7 +22,10 @@
 
 use crate::geometry::{Offsets, Size};
 use crate::number::Number;
-use azul_css::PixelValue;
+use azul_css::{
+    FontFeature, FontVariation, PixelValue, SizeMetric, SizeResolveContext, StyleTextOverflow,
+    StyleWhiteSpace,
+};
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum AlignItems {
@@ -88,6 +91,8 @@ impl Default for Direction {
 pub enum Display {
     Flex,
     Inline,
+    Grid,
+    Block,
     None,
 }
 
@@ -158,6 +163,11 @@ impl Default for Overflow {
 pub enum PositionType {
     Relative,
     Absolute,
+    /// Positioned relative to the window, out of flow
+    Fixed,
+    /// Laid out like `Relative`, but without the relative offset - the offsets
+    /// are only applied when the nearest scroll frame is scrolled
+    Sticky,
 }
 
 impl Default for PositionType {
@@ -166,6 +176,26 @@ impl Default for PositionType {
     }
 }
 
+impl PositionType {
+    /// Whether the node is taken out of the normal flow (`absolute` or `fixed`)
+    pub(crate) fn is_out_of_flow(self) -> bool {
+        self == PositionType::Absolute || self == PositionType::Fixed
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Float {
+    None,
+    Left,
+    Right,
+}
+
+impl Default for Float {
+    fn default() -> Float {
+        Float::None
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum FlexWrap {
     NoWrap,
@@ -185,6 +215,14 @@ pub enum Dimension {
     Auto,
     Pixels(f32),
     Percent(f32),
+    /// `calc()` expression that mixes fixed sizes with percentages, i.e. `calc(100% - 32px)`
+    Calc {
+        pixels: f32,
+        percent: f32,
+    },
+    /// Size relative to the font size or the viewport, gets resolved to
+    /// `Pixels` or `Calc` by `Style::resolve_relative_units` before the layout
+    Relative(PixelValue),
 }
 
 impl Default for Dimension {
@@ -198,6 +236,10 @@ impl Dimension {
         match self {
             Dimension::Pixels(pixels) => Number::Defined(pixels),
             Dimension::Percent(percent) => parent_width * (percent / 100.0),
+            Dimension::Calc { pixels, percent } => parent_width * (percent / 100.0) + pixels,
+            Dimension::Relative(_) => self
+                .resolve_relative_units(&SizeResolveContext::default())
+                .resolve(parent_width),
             _ => Number::Undefined,
         }
     }
@@ -206,9 +248,49 @@ impl Dimension {
         match self {
             Dimension::Pixels(_) => true,
             Dimension::Percent(_) => true,
+            Dimension::Calc { .. } => true,
+            Dimension::Relative(_) => true,
             _ => false,
         }
     }
+
+    /// Resolves `em`, `rem`, `vw`, etc. to pixels, only percentages are left to be
+    /// resolved against the parent size during the layout
+    pub fn resolve_relative_units(self, context: &SizeResolveContext) -> Dimension {
+        match self {
+            Dimension::Relative(value) => match value.resolve_relative_units(context) {
+                (pixels, percent) if percent == 0.0 => Dimension::Pixels(pixels),
+                (pixels, percent) => Dimension::Calc { pixels, percent },
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<PixelValue> for Dimension {
+    fn from(value: PixelValue) -> Dimension {
+        match value.metric {
+            SizeMetric::Px | SizeMetric::Pt => Dimension::Pixels(value.to_pixels(0.0)),
+            SizeMetric::Percent => Dimension::Percent(value.number.get()),
+            _ => Dimension::Relative(value),
+        }
+    }
+}
+
+impl Offsets<Dimension> {
+    fn resolve_relative_units(&mut self, context: &SizeResolveContext) {
+        self.left = self.left.resolve_relative_units(context);
+        self.right = self.right.resolve_relative_units(context);
+        self.top = self.top.resolve_relative_units(context);
+        self.bottom = self.bottom.resolve_relative_units(context);
+    }
+}
+
+impl Size<Dimension> {
+    fn resolve_relative_units(&mut self, context: &SizeResolveContext) {
+        self.width = self.width.resolve_relative_units(context);
+        self.height = self.height.resolve_relative_units(context);
+    }
 }
 
 impl Default for Offsets<Dimension> {
@@ -243,11 +325,94 @@ impl Default for BoxSizing {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TrackBreadth {
+    Auto,
+    Pixels(f32),
+    Percent(f32),
+    Fraction(f32),
+    /// `calc()` expression that mixes fixed sizes with percentages
+    Calc {
+        pixels: f32,
+        percent: f32,
+    },
+    /// Size relative to the font size or the viewport, see `Dimension::Relative`
+    Relative(PixelValue),
+}
+
+impl Default for TrackBreadth {
+    fn default() -> TrackBreadth {
+        TrackBreadth::Auto
+    }
+}
+
+impl TrackBreadth {
+    /// Resolves fixed and percentage sizes, `auto` and `fr` tracks return `Undefined`
+    pub(crate) fn resolve(self, parent_size: Number) -> Number {
+        match self {
+            TrackBreadth::Pixels(pixels) => Number::Defined(pixels),
+            TrackBreadth::Percent(percent) => parent_size * (percent / 100.0),
+            TrackBreadth::Calc { pixels, percent } => parent_size * (percent / 100.0) + pixels,
+            TrackBreadth::Relative(_) => self
+                .resolve_relative_units(&SizeResolveContext::default())
+                .resolve(parent_size),
+            _ => Number::Undefined,
+        }
+    }
+
+    /// Same as `Dimension::resolve_relative_units`
+    pub fn resolve_relative_units(self, context: &SizeResolveContext) -> TrackBreadth {
+        match self {
+            TrackBreadth::Relative(value) => match value.resolve_relative_units(context) {
+                (pixels, percent) if percent == 0.0 => TrackBreadth::Pixels(pixels),
+                (pixels, percent) => TrackBreadth::Calc { pixels, percent },
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<PixelValue> for TrackBreadth {
+    fn from(value: PixelValue) -> TrackBreadth {
+        match value.metric {
+            SizeMetric::Px | SizeMetric::Pt => TrackBreadth::Pixels(value.to_pixels(0.0)),
+            SizeMetric::Percent => TrackBreadth::Percent(value.number.get()),
+            _ => TrackBreadth::Relative(value),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct TrackSize {
+    pub min: TrackBreadth,
+    pub max: TrackBreadth,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GridLine {
+    Auto,
+    Line(i32),
+    Span(u32),
+}
+
+impl Default for GridLine {
+    fn default() -> GridLine {
+        GridLine::Auto
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct GridPlacement {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+#[derive(Clone, Debug)]
 pub struct Style {
     pub display: Display,
     pub box_sizing: BoxSizing,
     pub position_type: PositionType,
+    pub float: Float,
     pub direction: Direction,
     pub flex_direction: FlexDirection,
     pub flex_wrap: FlexWrap,
@@ -267,11 +432,23 @@ pub struct Style {
     pub min_size: Size<Dimension>,
     pub max_size: Size<Dimension>,
     pub aspect_ratio: Number,
+    pub grid_template_columns: Vec<TrackSize>,
+    pub grid_template_rows: Vec<TrackSize>,
+    /// `width` is the gap between the columns, `height` the gap between the rows
+    pub grid_gap: Size<Dimension>,
+    pub grid_column: GridPlacement,
+    pub grid_row: GridPlacement,
     pub font_size_px: PixelValue,
     pub letter_spacing: Option<PixelValue>,
     pub word_spacing: Option<PixelValue>,
     pub line_height: Option<f32>,
     pub tab_width: Option<f32>,
+    pub white_space: StyleWhiteSpace,
+    pub text_overflow: StyleTextOverflow,
+    /// Maximum number of lines of the text (`line-clamp`)
+    pub line_clamp: Option<usize>,
+    pub font_features: Vec<FontFeature>,
+    pub font_variations: Vec<FontVariation>,
 }
 
 impl Default for Style {
@@ -280,6 +457,7 @@ impl Default for Style {
             display: Default::default(),
             box_sizing: Default::default(),
             position_type: Default::default(),
+            float: Default::default(),
             direction: Default::default(),
             flex_direction: Default::default(),
             flex_wrap: Default::default(),
@@ -299,16 +477,70 @@ impl Default for Style {
             min_size: Default::default(),
             max_size: Default::default(),
             aspect_ratio: Default::default(),
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            grid_gap: Size {
+                width: Dimension::Pixels(0.0),
+                height: Dimension::Pixels(0.0),
+            },
+            grid_column: Default::default(),
+            grid_row: Default::default(),
             font_size_px: PixelValue::const_px(10),
             letter_spacing: None,
             line_height: None,
             word_spacing: None,
             tab_width: None,
+            white_space: Default::default(),
+            text_overflow: Default::default(),
+            line_clamp: None,
+            font_features: Vec::new(),
+            font_variations: Vec::new(),
         }
     }
 }
 
 impl Style {
+    /// Resolves all sizes relative to the font size or the viewport to pixels
+    pub(crate) fn resolve_relative_units(&mut self, context: &SizeResolveContext) {
+        use azul_core::ui_solver::{DEFAULT_LETTER_SPACING, DEFAULT_WORD_SPACING};
+
+        self.position.resolve_relative_units(context);
+        self.margin.resolve_relative_units(context);
+        self.padding.resolve_relative_units(context);
+        self.border.resolve_relative_units(context);
+        self.flex_basis = self.flex_basis.resolve_relative_units(context);
+        self.size.resolve_relative_units(context);
+        self.min_size.resolve_relative_units(context);
+        self.max_size.resolve_relative_units(context);
+        self.grid_gap.resolve_relative_units(context);
+
+        for track in self
+            .grid_template_columns
+            .iter_mut()
+            .chain(self.grid_template_rows.iter_mut())
+        {
+            track.min = track.min.resolve_relative_units(context);
+            track.max = track.max.resolve_relative_units(context);
+        }
+
+        self.letter_spacing = self
+            .letter_spacing
+            .map(|ls| PixelValue::px(ls.resolve(DEFAULT_LETTER_SPACING, context)));
+        self.word_spacing = self
+            .word_spacing
+            .map(|ws| PixelValue::px(ws.resolve(DEFAULT_WORD_SPACING, context)));
+    }
+
+    /// Returns the `flex_direction`, mirrored for `direction: rtl`: the main axis of
+    /// a row starts at the right edge of the container in right-to-left layouts
+    pub(crate) fn resolved_flex_direction(&self) -> FlexDirection {
+        match (self.direction, self.flex_direction) {
+            (Direction::RTL, FlexDirection::Row) => FlexDirection::RowReverse,
+            (Direction::RTL, FlexDirection::RowReverse) => FlexDirection::Row,
+            (_, flex_direction) => flex_direction,
+        }
+    }
+
     pub(crate) fn min_main_size(&self, direction: FlexDirection) -> Dimension {
         match direction {
             FlexDirection::Row | FlexDirection::RowReverse => self.min_size.width,
@@ -5,6 +5,8 @@ use crate::app_resources::FakeRenderApi;
 use crate::window::{FakeDisplay, WindowCreateError, WindowCreateOptions};
 pub use azul_core::app::*;
 #[cfg(not(test))]
+use azul_core::ui_solver::LayoutResult;
+#[cfg(not(test))]
 use azul_core::window::FakeWindow;
 use azul_core::{ui_description::UiDescription, ui_solver::ScrolledNodes, window::WindowId};
 use azul_css::{ColorU, LayoutPoint};
@@ -263,6 +265,13 @@ impl<T: 'static> App<T> {
         self.app_state.windows.insert(window_id, fake_window);
         self.windows.insert(window_id, window);
         self.window_states.insert(window_id, full_window_state);
+
+        // Register the @font-face fonts of all windows, so that their families can be used
+        self.app_state.resources.add_font_faces(
+            self.windows
+                .values()
+                .flat_map(|window| window.css.get_font_faces()),
+        );
     }
 
     /// Start the rendering loop for the currently open windows
@@ -461,11 +470,15 @@ impl<T: 'static> App<T> {
         }
 
         #[cfg(debug_assertions)]
-        let (css_has_reloaded, css_has_error) =
-            match hot_reload_css(&mut self.windows, last_style_reload, false) {
-                Ok(has_reloaded) => (has_reloaded, None),
-                Err(css_error) => (true, Some(css_error)),
-            };
+        let (css_has_reloaded, css_has_error) = match hot_reload_css(
+            &mut self.windows,
+            &mut self.app_state.resources,
+            last_style_reload,
+            false,
+        ) {
+            Ok(has_reloaded) => (has_reloaded, None),
+            Err(css_error) => (true, Some(css_error)),
+        };
 
         #[cfg(not(debug_assertions))]
         let css_has_error: Option<String> = None;
@@ -473,6 +486,13 @@ impl<T: 'static> App<T> {
         #[cfg(not(debug_assertions))]
         let css_has_reloaded = false;
 
+        // The styles of the last frame are invalid if the CSS has changed
+        if css_has_reloaded {
+            for ui_descriptions in ui_description_cache.values_mut() {
+                ui_descriptions.clear();
+            }
+        }
+
         let should_relayout_all_windows = css_has_reloaded
             || single_window_results
                 .iter()
@@ -535,6 +555,23 @@ impl<T: 'static> App<T> {
             }
         }
 
+        // Nothing else changed, but running CSS transitions and animations need a new frame
+        let mut should_animate = false;
+        if !should_relayout_all_windows && !should_redraw_timers_or_tasks {
+            for (current_window_id, window) in self.windows.iter_mut() {
+                should_animate = animate_single_window(
+                    &current_window_id,
+                    window,
+                    &mut self.app_state,
+                    &mut self.fake_display,
+                    ui_state_cache,
+                    ui_description_cache,
+                )
+                .expect("do better")
+                    || should_animate;
+            }
+        }
+
         // TODO: For some reason, the window state and the full window state get out of sync
         for (window_id, full_window_state) in &self.window_states {
             self.windows
@@ -545,7 +582,7 @@ impl<T: 'static> App<T> {
         }
 
         // If there is a re-render necessary, re-render *all* windows
-        if should_rerender_all_windows || should_redraw_timers_or_tasks {
+        if should_rerender_all_windows || should_redraw_timers_or_tasks || should_animate {
             for window in self.windows.values_mut() {
                 render_inner(
                     window,
@@ -601,7 +638,12 @@ fn initialize_ui_state_cache<T>(
     for window_id in window_ids {
         #[cfg(debug_assertions)]
         let mut ui_state = {
-            let (_, css_has_error) = match hot_reload_css(windows, &mut Instant::now(), true) {
+            let (_, css_has_error) = match hot_reload_css(
+                windows,
+                &mut app_state.resources,
+                &mut Instant::now(),
+                true,
+            ) {
                 Ok(has_reloaded) => (has_reloaded, None),
                 Err(css_error) => (true, Some(css_error)),
             };
@@ -652,7 +694,7 @@ fn initialize_ui_description_cache<T>(
 
 /// Run all currently registered timers
 #[must_use]
-fn app_state_run_all_timers<T>(app_state: &mut AppState<T>) -> UpdateScreen {
+pub(crate) fn app_state_run_all_timers<T>(app_state: &mut AppState<T>) -> UpdateScreen {
     use azul_core::callbacks::TimerCallbackInfo;
 
     let mut should_update_screen = DontRedraw;
@@ -682,7 +724,7 @@ fn app_state_run_all_timers<T>(app_state: &mut AppState<T>) -> UpdateScreen {
 
 /// Remove all tasks that have finished executing
 #[must_use]
-fn app_state_clean_up_finished_tasks<T>(app_state: &mut AppState<T>) -> UpdateScreen {
+pub(crate) fn app_state_clean_up_finished_tasks<T>(app_state: &mut AppState<T>) -> UpdateScreen {
     let old_count = app_state.tasks.len();
     let mut timers_to_add = Vec::new();
     app_state.tasks.retain(|task| {
@@ -907,16 +949,21 @@ fn relayout_single_window<T>(
     awakened_tasks: &mut BTreeMap<WindowId, bool>,
 ) -> Result<(), RuntimeError> {
     use azul_core::app::RuntimeError::*;
+    use azul_core::diff::diff_arena;
 
     // Style the DOM (is_mouse_down is necessary for styling :hover, :active + :focus nodes)
     let is_mouse_down = full_window_state.mouse_state.mouse_down();
+    let now = Instant::now();
+
+    // Layouts of the last frame that are still valid for the current frame
+    let mut cached_layouts = BTreeMap::new();
 
     {
         let ui_state_mut = ui_state_cache.get_mut(window_id).ok_or(WindowIndexError)?;
         let ui_description_mut = ui_description_cache
             .get_mut(window_id)
             .ok_or(WindowIndexError)?;
-        *ui_description_mut = ui_state_mut
+        let new_ui_descriptions = ui_state_mut
             .iter_mut()
             .map(|(dom_id, ui_state)| {
                 let hovered_nodes = full_window_state
@@ -924,19 +971,53 @@ fn relayout_single_window<T>(
                     .get(&dom_id)
                     .cloned()
                     .unwrap_or_default();
-                (
-                    dom_id.clone(),
-                    UiDescription::match_css_to_dom(
-                        ui_state,
-                        &window.css,
-                        &mut full_window_state.focused_node,
-                        &mut full_window_state.pending_focus_target,
-                        &hovered_nodes,
-                        is_mouse_down,
-                    ),
-                )
+
+                // Diff against the DOM of the last frame, so that unchanged
+                // nodes can reuse their styles and the layout can be reused
+                let previous_frame = ui_description_mut.get(dom_id).map(|previous| {
+                    let diff = diff_arena(
+                        &previous.ui_descr_arena,
+                        previous.ui_descr_root,
+                        &ui_state.dom.arena,
+                        ui_state.dom.root,
+                    );
+                    (previous, diff)
+                });
+
+                let mut ui_description = UiDescription::match_css_to_dom(
+                    ui_state,
+                    &window.css,
+                    &mut full_window_state.focused_node,
+                    &mut full_window_state.pending_focus_target,
+                    &hovered_nodes,
+                    is_mouse_down,
+                    &full_window_state.size,
+                    full_window_state.theme,
+                    previous_frame
+                        .as_ref()
+                        .map(|(previous, diff)| (*previous, diff)),
+                );
+
+                ui_description.update_animations(
+                    previous_frame
+                        .as_ref()
+                        .map(|(previous, diff)| (*previous, diff)),
+                    &window.css,
+                    now,
+                );
+
+                if let Some((previous, diff)) = &previous_frame {
+                    if ui_description.has_same_layout(previous, diff) {
+                        if let Some(layout_result) = window.internal.layout_result.remove(dom_id) {
+                            cached_layouts.insert(dom_id.clone(), layout_result);
+                        }
+                    }
+                }
+
+                (dom_id.clone(), ui_description)
             })
             .collect();
+        *ui_description_mut = new_ui_descriptions;
     }
 
     let mut fake_window = app_state
@@ -944,9 +1025,14 @@ fn relayout_single_window<T>(
         .get_mut(window_id)
         .ok_or(WindowIndexError)?;
 
-    for (window_id, ui_state_map) in ui_state_cache.iter() {
+    for (ui_window_id, ui_state_map) in ui_state_cache.iter() {
         for (dom_id, ui_state) in ui_state_map.iter() {
-            let ui_description = &ui_description_cache[window_id][dom_id];
+            let ui_description = &ui_description_cache[ui_window_id][dom_id];
+            let cached_layout = if ui_window_id == window_id {
+                cached_layouts.remove(dom_id)
+            } else {
+                None
+            };
             update_display_list(
                 &mut app_state.data,
                 ui_description,
@@ -955,6 +1041,7 @@ fn relayout_single_window<T>(
                 &mut fake_window,
                 fake_display,
                 &mut app_state.resources,
+                cached_layout,
             );
         }
     }
@@ -964,10 +1051,69 @@ fn relayout_single_window<T>(
     Ok(())
 }
 
+/// Evaluates the running CSS transitions and animations of a window and rebuilds the
+/// display list, without calling the `layout()` callback or restyling the DOM.
+/// Returns whether any transition or animation was running.
+#[cfg(not(test))]
+fn animate_single_window<T>(
+    window_id: &WindowId,
+    window: &mut Window<T>,
+    app_state: &mut AppState<T>,
+    fake_display: &mut FakeDisplay,
+    ui_state_cache: &BTreeMap<WindowId, BTreeMap<DomId, UiState<T>>>,
+    ui_description_cache: &mut BTreeMap<WindowId, BTreeMap<DomId, UiDescription<T>>>,
+) -> Result<bool, RuntimeError> {
+    use azul_core::app::RuntimeError::*;
+
+    let ui_descriptions = ui_description_cache
+        .get_mut(window_id)
+        .ok_or(WindowIndexError)?;
+
+    if !ui_descriptions
+        .values()
+        .any(|ui_description| ui_description.is_animating())
+    {
+        return Ok(false);
+    }
+
+    let now = Instant::now();
+    let mut cached_layouts = BTreeMap::new();
+
+    for (dom_id, ui_description) in ui_descriptions.iter_mut() {
+        // Only animated properties that can trigger a relayout invalidate the layout
+        if !ui_description.tick_animations(&window.css, now) {
+            if let Some(layout_result) = window.internal.layout_result.remove(dom_id) {
+                cached_layouts.insert(dom_id.clone(), layout_result);
+            }
+        }
+    }
+
+    let mut fake_window = app_state
+        .windows
+        .get_mut(window_id)
+        .ok_or(WindowIndexError)?;
+
+    for (dom_id, ui_state) in ui_state_cache.get(window_id).ok_or(WindowIndexError)? {
+        update_display_list(
+            &mut app_state.data,
+            &ui_descriptions[dom_id],
+            ui_state,
+            &mut *window,
+            &mut fake_window,
+            fake_display,
+            &mut app_state.resources,
+            cached_layouts.remove(dom_id),
+        );
+    }
+
+    Ok(true)
+}
+
 /// Returns if the CSS has been successfully reloaded
 #[cfg(debug_assertions)]
 fn hot_reload_css<T>(
     windows: &mut BTreeMap<WindowId, Window<T>>,
+    app_resources: &mut AppResources,
     last_style_reload: &mut Instant,
     force_reload: bool,
 ) -> Result<bool, String> {
@@ -1002,6 +1148,14 @@ fn hot_reload_css<T>(
         };
     }
 
+    if has_reloaded {
+        app_resources.add_font_faces(
+            windows
+                .values()
+                .flat_map(|window| window.css.get_font_faces()),
+        );
+    }
+
     Ok(has_reloaded)
 }
 
@@ -1042,7 +1196,7 @@ fn do_hit_test<T>(
 /// Struct returned from the `call_callbacks()` function -
 /// returns important information from the callbacks
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-struct CallCallbackReturn {
+pub(crate) struct CallCallbackReturn {
     /// Whether one or more callbacks say to redraw the screen or not
     pub should_update_screen: UpdateScreen,
     /// Whether one or more callbacks have messed with the current
@@ -1059,7 +1213,7 @@ struct CallCallbackReturn {
 }
 
 /// Returns an bool whether the window should be redrawn or not (true - redraw the screen, false: don't redraw).
-fn call_callbacks<T>(
+pub(crate) fn call_callbacks<T>(
     event: &WindowEvent,
     window_id: &WindowId,
     hit_test_results: Option<&Vec<HitTestItem>>,
@@ -1162,7 +1316,24 @@ fn call_callbacks<T>(
             callbacks_filter_list[&dom_id].nodes_with_callbacks.iter()
         {
             let hit_item = &callback_results.hit_test_item;
-            for callback in callback_results.normal_callbacks.values() {
+            let hit_text_span = get_hit_text_span(
+                &app_state.windows[window_id].layout_result,
+                &dom_id,
+                *node_id,
+                hit_item.as_ref(),
+            );
+
+            // The callbacks of the span under the cursor are called after the node callbacks
+            let span_callbacks = hit_text_span
+                .and_then(|span_idx| callback_results.span_callbacks.get(&span_idx))
+                .into_iter()
+                .flat_map(|callbacks| callbacks.values());
+
+            for callback in callback_results
+                .normal_callbacks
+                .values()
+                .chain(span_callbacks)
+            {
                 let mut new_focus = None;
 
                 if (callback.0)(CallbackInfo {
@@ -1180,6 +1351,7 @@ fn call_callbacks<T>(
                     cursor_in_viewport: hit_item
                         .as_ref()
                         .map(|hi| (hi.point_in_viewport.x, hi.point_in_viewport.y)),
+                    hit_text_span,
                 }) == Redraw
                 {
                     should_update_screen = Redraw;
@@ -1204,6 +1376,27 @@ fn call_callbacks<T>(
     })
 }
 
+/// If the node is a `NodeType::RichText` node, returns the index of the span under the hit-test item
+fn get_hit_text_span(
+    layout_results: &BTreeMap<DomId, azul_core::ui_solver::LayoutResult>,
+    dom_id: &DomId,
+    node_id: NodeId,
+    hit_item: Option<&HitTestItem>,
+) -> Option<usize> {
+    let hit_item = hit_item?;
+    let layout_result = layout_results.get(dom_id)?;
+    let bounds = layout_result.rects.get(node_id)?.bounds;
+    // The point relative to the item is independent of the scroll position
+    let cursor = LayoutPoint::new(
+        bounds.origin.x + hit_item.point_relative_to_item.x,
+        bounds.origin.y + hit_item.point_relative_to_item.y,
+    );
+    layout_result
+        .rich_text_spans
+        .get(&node_id)?
+        .hit_test(&cursor)
+}
+
 /// Build the display list and send it to webrender
 #[cfg(not(test))]
 fn update_display_list<T>(
@@ -1214,6 +1407,7 @@ fn update_display_list<T>(
     fake_window: &mut FakeWindow<T>,
     fake_display: &mut FakeDisplay,
     app_resources: &mut AppResources,
+    cached_layout: Option<LayoutResult>,
 ) {
     use crate::app_resources::add_resources;
     use crate::display_list::{
@@ -1222,7 +1416,8 @@ fn update_display_list<T>(
     };
     use crate::wr_translate::{wr_translate_display_list, wr_translate_pipeline_id};
 
-    let display_list = display_list_from_ui_description(ui_description, ui_state);
+    let display_list =
+        display_list_from_ui_description(ui_description, ui_state, &window.state.size);
 
     // Make sure unused scroll states are garbage collected.
     window.internal.scroll_states.remove_unused_scroll_states();
@@ -1241,10 +1436,14 @@ fn update_display_list<T>(
     } = display_list_to_cached_display_list(
         display_list,
         app_data,
-        window,
+        window.internal.epoch,
+        window.internal.pipeline_id,
+        window.state.size,
+        &window.css,
         fake_window,
         app_resources,
         &mut fake_display.render_api,
+        cached_layout,
     );
 
     unsafe { fake_display.make_current() };
@@ -1316,7 +1515,7 @@ fn convert_window_size(size: &WindowSize) -> (LayoutSize, DeviceIntSize) {
 /// significantly less CPU-intensive to just render the last display list instead of
 /// re-layouting on every single scroll event.
 #[must_use]
-fn update_scroll_state(
+pub(crate) fn update_scroll_state(
     full_window_state: &mut FullWindowState,
     scrolled_nodes: &ScrolledNodes,
     scroll_states: &mut ScrollStates,
@@ -1381,7 +1580,7 @@ fn clean_up_unused_opengl_textures(pipeline_info: PipelineInfo) {
 
 // We don't want the epoch to increase to u32::MAX, since
 // u32::MAX represents an invalid epoch, which could confuse webrender
-fn increase_epoch(old: Epoch) -> Epoch {
+pub(crate) fn increase_epoch(old: Epoch) -> Epoch {
     use std::u32;
     const MAX_ID: u32 = u32::MAX - 1;
     match old.0 {
@@ -1,16 +1,19 @@
 pub use azul_core::app_resources::{
-    AppResources, Au, CssFontId, CssImageId, FontId, FontInstanceKey, FontKey, FontSource,
-    IdNamespace, ImageId, ImageInfo, ImageKey, ImageSource, ImmediateFontId, LoadedFont, RawImage,
-    RawImageFormat, TextCache, TextId,
+    AppResources, Au, CssFontId, CssImageId, FontFaceStyle, FontId, FontInstanceDescriptor,
+    FontInstanceKey, FontKey, FontSource, FontSynthesis, HyphenationLanguage, IdNamespace, ImageId,
+    ImageInfo, ImageKey, ImageSource, ImmediateFontId, LoadedFont, RawImage, RawImageFormat,
+    TextCache, TextId,
 };
 #[cfg(feature = "image_loading")]
 pub use image::{DynamicImage, GenericImageView, ImageError};
-use std::{fmt, io::Error as IoError, path::PathBuf};
+use std::{cell::Cell, fmt, io::Error as IoError, path::PathBuf};
 use webrender::api::{AddFont, AddFontInstance, AddImage, RenderApi, ResourceUpdate};
 pub use webrender::api::{
     ImageData as WrImageData, ImageDescriptor as WrImageDescriptor, ImageFormat as WrImageFormat,
 };
 
+use azul_css::RectStyle;
+
 use crate::{display_list::DisplayList, FastHashMap, FastHashSet};
 
 #[derive(Debug)]
@@ -144,6 +147,48 @@ impl FontImageApi for FakeRenderApi {
     fn flush_scene_builder(&self) {}
 }
 
+/// RenderApi replacement for the `HeadlessApp`: hands out unique keys
+/// without uploading anything, since there is no renderer to upload to.
+#[derive(Debug, Default)]
+pub(crate) struct HeadlessRenderApi {
+    last_key: Cell<u32>,
+}
+
+impl HeadlessRenderApi {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_key(&self) -> u32 {
+        let key = self.last_key.get() + 1;
+        self.last_key.set(key);
+        key
+    }
+}
+
+impl FontImageApi for HeadlessRenderApi {
+    fn new_image_key(&self) -> ImageKey {
+        ImageKey {
+            key: self.next_key(),
+            namespace: IdNamespace(0),
+        }
+    }
+    fn new_font_key(&self) -> FontKey {
+        FontKey {
+            key: self.next_key(),
+            namespace: IdNamespace(0),
+        }
+    }
+    fn new_font_instance_key(&self) -> FontInstanceKey {
+        FontInstanceKey {
+            key: self.next_key(),
+            namespace: IdNamespace(0),
+        }
+    }
+    fn update_resources(&self, _: Vec<ResourceUpdate>) {}
+    fn flush_scene_builder(&self) {}
+}
+
 /// Scans the DisplayList for new images and fonts. After this call, the RenderApi is
 /// guaranteed to know about all FontKeys and FontInstanceKey
 pub(crate) fn add_fonts_and_images<T, U: FontImageApi>(
@@ -151,9 +196,18 @@ pub(crate) fn add_fonts_and_images<T, U: FontImageApi>(
     render_api: &mut U,
     display_list: &DisplayList<T>,
 ) {
-    let font_keys = scan_ui_description_for_font_keys(&app_resources, display_list);
+    let mut font_bytes = LoadedFontBytes::default();
+    let font_keys =
+        scan_ui_description_for_font_keys(&app_resources, &mut font_bytes, display_list);
     let image_keys = scan_ui_description_for_image_keys(&app_resources, display_list);
 
+    app_resources.failed_font_loads.extend(
+        font_bytes
+            .iter()
+            .filter(|(_, bytes)| bytes.is_none())
+            .map(|(font_id, _)| font_id.clone()),
+    );
+
     app_resources
         .last_frame_font_keys
         .extend(font_keys.clone().into_iter());
@@ -162,7 +216,7 @@ pub(crate) fn add_fonts_and_images<T, U: FontImageApi>(
         .extend(image_keys.clone().into_iter());
 
     let add_font_resource_updates =
-        build_add_font_resource_updates(app_resources, render_api, &font_keys);
+        build_add_font_resource_updates(app_resources, render_api, &font_keys, font_bytes);
     let add_image_resource_updates =
         build_add_image_resource_updates(app_resources, render_api, &image_keys);
 
@@ -246,26 +300,79 @@ pub fn image_source_get_bytes(
 
 /// Returns the bytes of the font (loads the font from the system in case it is a `FontSource::System` font).
 /// Also returns the index into the font (in case the font is a font collection).
-pub fn font_source_get_bytes(font_source: &FontSource) -> Result<(Vec<u8>, i32), FontReloadError> {
+///
+/// For system fonts, the face of the family that matches the `face_style` is loaded - if the
+/// family has no bold or italic face, the styles that have to be synthesized are returned.
+/// Embedded fonts and font files only contain one face, so they never need synthesized styles.
+pub fn font_source_get_bytes(
+    font_source: &FontSource,
+    face_style: &FontFaceStyle,
+) -> Result<(Vec<u8>, i32, FontSynthesis), FontReloadError> {
     use std::fs;
     match font_source {
-        FontSource::Embedded(bytes) => Ok((bytes.to_vec(), 0)),
+        FontSource::Embedded(bytes) => Ok((bytes.to_vec(), 0, FontSynthesis::default())),
         FontSource::File(file_path) => fs::read(file_path)
             .map_err(|e| FontReloadError::Io(e, file_path.clone()))
-            .map(|f| (f, 0)),
+            .map(|f| (f, 0, FontSynthesis::default())),
         FontSource::System(id) => {
-            load_system_font(id).ok_or(FontReloadError::FontNotFound(id.clone()))
+            load_system_font(id, face_style).ok_or(FontReloadError::FontNotFound(id.clone()))
         }
     }
 }
 
-/// Scans the display list for all font IDs + their font size
+/// Bytes, font index and synthesized styles of the fonts that were loaded while scanning the
+/// display list, but aren't registered in the RenderApi yet (`None` if the font couldn't be loaded)
+type LoadedFontBytes = FastHashMap<ImmediateFontId, Option<(Vec<u8>, i32, FontSynthesis)>>;
+
+/// Loads the bytes of a font that isn't registered in the RenderApi yet
+fn load_font_bytes(
+    app_resources: &AppResources,
+    im_font_id: &ImmediateFontId,
+) -> Option<(Vec<u8>, i32, FontSynthesis)> {
+    use azul_core::app_resources::ImmediateFontId::*;
+
+    let (font_source, face_style) = match im_font_id {
+        Resolved(font_id, _) => (
+            app_resources.font_sources.get(font_id)?.clone(),
+            FontFaceStyle::default(),
+        ),
+        Unresolved(css_font_id, face_style) => {
+            (FontSource::System(css_font_id.clone()), *face_style)
+        }
+    };
+
+    let (font_bytes, font_index, missing_styles) =
+        match font_source_get_bytes(&font_source, &face_style) {
+            Ok(o) => o,
+            Err(e) => {
+                #[cfg(feature = "logging")]
+                {
+                    warn!(
+                        "Could not load font with ID: {:?} - error: {}",
+                        im_font_id, e
+                    );
+                }
+                return None;
+            }
+        };
+
+    // The face of a registered font was already selected when resolving it
+    let synthesis = match im_font_id {
+        Resolved(_, synthesis) => *synthesis,
+        Unresolved(_, _) => missing_styles,
+    };
+
+    Some((font_bytes, font_index, synthesis))
+}
+
+/// Scans the display list for all font IDs + their font instances (size and OpenType settings)
 fn scan_ui_description_for_font_keys<'a, T>(
     app_resources: &AppResources,
+    font_bytes: &mut LoadedFontBytes,
     display_list: &DisplayList<'a, T>,
-) -> FastHashMap<ImmediateFontId, FastHashSet<Au>> {
+) -> FastHashMap<ImmediateFontId, FastHashSet<FontInstanceDescriptor>> {
+    use crate::display_list::get_span_style;
     use crate::dom::NodeType::*;
-    use crate::ui_solver;
 
     let mut font_keys = FastHashMap::default();
 
@@ -273,26 +380,116 @@ fn scan_ui_description_for_font_keys<'a, T>(
         let node_data = &display_list.ui_descr.ui_descr_arena.node_data[node_id];
         let display_rect = &display_list.rectangles[node_id];
 
-        match node_data.get_node_type() {
-            Text(_) | Label(_) => {
-                let css_font_id = ui_solver::get_font_id(&display_rect.style);
-                let font_id = match app_resources.css_ids_to_font_ids.get(css_font_id) {
-                    Some(s) => ImmediateFontId::Resolved(*s),
-                    None => ImmediateFontId::Unresolved(css_font_id.to_string()),
-                };
-                let font_size = ui_solver::get_font_size(&display_rect.style);
-                font_keys
-                    .entry(font_id)
-                    .or_insert_with(|| FastHashSet::default())
-                    .insert(ui_solver::font_size_to_au(font_size));
+        let text = match node_data.get_node_type() {
+            Label(string) => string.as_str(),
+            Text(text_id) => match app_resources.get_text(text_id) {
+                Some(words) => words.get_str(),
+                None => continue,
+            },
+            RichText(spans) => {
+                for span in spans {
+                    let span_style = get_span_style(display_rect, &span.style);
+                    insert_font_keys(
+                        &mut font_keys,
+                        app_resources,
+                        font_bytes,
+                        &span_style,
+                        span.text.as_str(),
+                    );
+                }
+                continue;
             }
-            _ => {}
-        }
+            _ => continue,
+        };
+
+        insert_font_keys(
+            &mut font_keys,
+            app_resources,
+            font_bytes,
+            &display_rect.style,
+            text,
+        );
     }
 
     font_keys
 }
 
+/// Inserts the fonts (and their font instance) that are required to render the `text` in the given style
+fn insert_font_keys(
+    font_keys: &mut FastHashMap<ImmediateFontId, FastHashSet<FontInstanceDescriptor>>,
+    app_resources: &AppResources,
+    font_bytes: &mut LoadedFontBytes,
+    style: &RectStyle,
+    text: &str,
+) {
+    use crate::ui_solver;
+
+    let font_chain = ui_solver::get_font_fallback_chain(app_resources, style);
+    let font_instance = ui_solver::get_font_instance_descriptor(style);
+
+    for font_id in get_required_fallback_fonts(app_resources, font_bytes, font_chain, text) {
+        font_keys
+            .entry(font_id)
+            .or_insert_with(|| FastHashSet::default())
+            .insert(font_instance.clone());
+    }
+}
+
+/// Returns the fonts of the `font_chain` that have to be loaded in order to render the `text`:
+/// the first font that can be loaded is always required, the following fonts only as long as
+/// the `text` contains characters that the fonts before them don't contain.
+///
+/// The chain is walked in order: fonts that aren't registered yet are loaded (into `font_bytes`)
+/// before deciding whether the next font is needed. Fonts that can't be loaded are skipped.
+fn get_required_fallback_fonts(
+    app_resources: &AppResources,
+    font_bytes: &mut LoadedFontBytes,
+    font_chain: Vec<ImmediateFontId>,
+    text: &str,
+) -> Vec<ImmediateFontId> {
+    use crate::text_shaping::{continues_cluster, HbFont};
+
+    // Combining marks, etc. are rendered with the font of their base character
+    let mut missing_chars = text
+        .chars()
+        .filter(|c| !c.is_ascii() && !c.is_whitespace() && !continues_cluster(*c))
+        .collect::<Vec<_>>();
+    missing_chars.sort();
+    missing_chars.dedup();
+
+    let mut required_fonts = Vec::new();
+
+    for font_id in font_chain {
+        if !required_fonts.is_empty() && missing_chars.is_empty() {
+            break;
+        }
+
+        if app_resources.failed_font_loads.contains(&font_id) {
+            continue;
+        }
+
+        let (bytes, font_index) = match app_resources.get_loaded_font(&font_id) {
+            Some(loaded_font) => (&loaded_font.font_bytes[..], loaded_font.font_index),
+            None => match font_bytes
+                .entry(font_id.clone())
+                .or_insert_with(|| load_font_bytes(app_resources, &font_id))
+            {
+                Some((bytes, font_index, _)) => (&bytes[..], *font_index),
+                None => continue,
+            },
+        };
+
+        if !missing_chars.is_empty() {
+            let hb_font = HbFont::from_bytes(bytes, font_index as u32);
+            missing_chars.retain(|c| !hb_font.has_glyph(*c));
+        }
+
+        required_fonts.push(font_id);
+    }
+
+    required_fonts
+}
+
 /// Scans the display list for all image keys
 fn scan_ui_description_for_image_keys<'a, T>(
     app_resources: &AppResources,
@@ -326,14 +523,14 @@ fn scan_ui_description_for_image_keys<'a, T>(
 #[derive(Clone)]
 pub(crate) enum AddFontMsg {
     Font(LoadedFont),
-    Instance(AddFontInstance, Au),
+    Instance(AddFontInstance, FontInstanceDescriptor),
 }
 
 // Debug, PartialEq, Eq, PartialOrd, Ord
 #[derive(Clone)]
 enum DeleteFontMsg {
     Font(FontKey),
-    Instance(FontInstanceKey, Au),
+    Instance(FontInstanceKey, FontInstanceDescriptor),
 }
 
 // Debug, PartialEq, Eq, PartialOrd, Ord
@@ -396,25 +593,28 @@ impl DeleteImageMsg {
 fn build_add_font_resource_updates<T: FontImageApi>(
     app_resources: &AppResources,
     render_api: &mut T,
-    fonts_in_dom: &FastHashMap<ImmediateFontId, FastHashSet<Au>>,
+    fonts_in_dom: &FastHashMap<ImmediateFontId, FastHashSet<FontInstanceDescriptor>>,
+    mut loaded_font_bytes: LoadedFontBytes,
 ) -> Vec<(ImmediateFontId, AddFontMsg)> {
     use webrender::api::{
         FontInstanceFlags, FontInstanceOptions, FontInstancePlatformOptions, FontRenderMode,
+        SyntheticItalics,
     };
 
     let mut resource_updates = Vec::new();
 
-    for (im_font_id, font_sizes) in fonts_in_dom {
+    for (im_font_id, font_instances) in fonts_in_dom {
         macro_rules! insert_font_instances {
-            ($font_id:expr, $font_key:expr, $font_index:expr, $font_size:expr) => {{
+            ($font_id:expr, $font_key:expr, $font_index:expr, $font_instance:expr, $synthesis:expr) => {{
                 use crate::wr_translate::{
                     translate_au, wr_translate_font_instance_key, wr_translate_font_key,
+                    wr_translate_font_variation,
                 };
 
                 let font_instance_key_exists = app_resources
                     .currently_registered_fonts
                     .get(&$font_id)
-                    .and_then(|loaded_font| loaded_font.font_instances.get(&$font_size))
+                    .and_then(|loaded_font| loaded_font.font_instances.get($font_instance))
                     .is_some();
 
                 if !font_instance_key_exists {
@@ -444,10 +644,18 @@ fn build_add_font_resource_updates<T: FontImageApi>(
                     font_instance_flags.set(FontInstanceFlags::SUBPIXEL_BGR, false);
                     font_instance_flags.set(FontInstanceFlags::NO_AUTOHINT, true);
                     font_instance_flags.set(FontInstanceFlags::LCD_VERTICAL, false);
+                    font_instance_flags.set(FontInstanceFlags::SYNTHETIC_BOLD, $synthesis.bold);
+
+                    let synthetic_italics = if $synthesis.oblique {
+                        SyntheticItalics::enabled()
+                    } else {
+                        SyntheticItalics::disabled()
+                    };
 
                     let options = FontInstanceOptions {
                         render_mode: FontRenderMode::Subpixel,
                         flags: font_instance_flags,
+                        synthetic_italics,
                         ..Default::default()
                     };
 
@@ -457,12 +665,16 @@ fn build_add_font_resource_updates<T: FontImageApi>(
                             AddFontInstance {
                                 key: wr_translate_font_instance_key(font_instance_key),
                                 font_key: wr_translate_font_key($font_key),
-                                glyph_size: translate_au($font_size),
+                                glyph_size: translate_au($font_instance.size),
                                 options: Some(options),
                                 platform_options: Some(platform_options),
-                                variations: Vec::new(),
+                                variations: $font_instance
+                                    .variations
+                                    .iter()
+                                    .map(wr_translate_font_variation)
+                                    .collect(),
                             },
-                            $font_size,
+                            $font_instance.clone(),
                         ),
                     ));
                 }
@@ -471,55 +683,42 @@ fn build_add_font_resource_updates<T: FontImageApi>(
 
         match app_resources.currently_registered_fonts.get(im_font_id) {
             Some(loaded_font) => {
-                for font_size in font_sizes.iter() {
+                for font_instance in font_instances.iter() {
                     insert_font_instances!(
                         im_font_id.clone(),
                         loaded_font.font_key,
                         loaded_font.font_index,
-                        *font_size
+                        font_instance,
+                        loaded_font.synthesis
                     );
                 }
             }
             None => {
-                use azul_core::app_resources::ImmediateFontId::*;
-
-                // If there is no font key, that means there's also no font instances
-                let font_source = match im_font_id {
-                    Resolved(font_id) => match app_resources.font_sources.get(font_id) {
-                        Some(s) => s.clone(),
-                        None => continue,
-                    },
-                    Unresolved(css_font_id) => FontSource::System(css_font_id.clone()),
+                // If there is no font key, that means there's also no font instances.
+                // The bytes were already loaded while scanning the display list
+                let loaded_font = loaded_font_bytes.remove(im_font_id);
+                let (font_bytes, font_index, synthesis) = match loaded_font {
+                    Some(Some(loaded_font)) => loaded_font,
+                    _ => continue,
                 };
 
-                let (font_bytes, font_index) = match font_source_get_bytes(&font_source) {
-                    Ok(o) => o,
-                    Err(e) => {
-                        #[cfg(feature = "logging")]
-                        {
-                            warn!(
-                                "Could not load font with ID: {:?} - error: {}",
-                                im_font_id, e
-                            );
-                        }
-                        continue;
-                    }
-                };
-
-                if !font_sizes.is_empty() {
+                if !font_instances.is_empty() {
                     let font_key = render_api.new_font_key();
 
                     resource_updates.push((
                         im_font_id.clone(),
-                        AddFontMsg::Font(LoadedFont::new(font_key, font_bytes, font_index)),
+                        AddFontMsg::Font(LoadedFont::new(
+                            font_key, font_bytes, font_index, synthesis,
+                        )),
                     ));
 
-                    for font_size in font_sizes {
+                    for font_instance in font_instances {
                         insert_font_instances!(
                             im_font_id.clone(),
                             font_key,
                             font_index,
-                            *font_size
+                            font_instance,
+                            synthesis
                         );
                     }
                 }
@@ -633,17 +832,17 @@ pub(crate) fn add_resources<T: FontImageApi>(
             Font(f) => {
                 app_resources.currently_registered_fonts.insert(
                     font_id,
-                    LoadedFont::new(f.font_key, f.font_bytes, f.font_index),
+                    LoadedFont::new(f.font_key, f.font_bytes, f.font_index, f.synthesis),
                 );
             }
-            Instance(fi, size) => {
+            Instance(fi, font_instance) => {
                 let fi_key = translate_font_instance_key_wr(fi.key);
                 app_resources
                     .currently_registered_fonts
                     .get_mut(&font_id)
                     .unwrap()
                     .font_instances
-                    .insert(size, fi_key);
+                    .insert(font_instance, fi_key);
             }
         }
     }
@@ -660,11 +859,13 @@ fn build_delete_font_resource_updates(
             loaded_font
                 .font_instances
                 .iter()
-                .filter(|(au, _)| app_resources.last_frame_font_keys[font_id].contains(au))
-                .map(|(au, font_instance_key)| {
+                .filter(|(font_instance, _)| {
+                    app_resources.last_frame_font_keys[font_id].contains(font_instance)
+                })
+                .map(|(font_instance, font_instance_key)| {
                     (
                         font_id.clone(),
-                        DeleteFontMsg::Instance(*font_instance_key, *au),
+                        DeleteFontMsg::Instance(*font_instance_key, font_instance.clone()),
                     )
                 }),
         );
@@ -726,12 +927,12 @@ fn delete_resources<T: FontImageApi>(
             Font(_) => {
                 app_resources.currently_registered_fonts.remove(&font_id);
             }
-            Instance(_, size) => {
+            Instance(_, font_instance) => {
                 app_resources
                     .currently_registered_fonts
                     .get_mut(&font_id)
                     .unwrap()
-                    .delete_font_instance(&size);
+                    .delete_font_instance(&font_instance);
             }
         }
     }
@@ -744,11 +945,96 @@ fn decode_image_data(image_data: Vec<u8>) -> Result<(WrImageData, WrImageDescrip
     Ok(prepare_image(decoded)?)
 }
 
-/// Returns the font + the index of the font (in case the font is a collection)
-fn load_system_font(id: &str) -> Option<(Vec<u8>, i32)> {
-    use font_loader::system_fonts::{self, FontPropertyBuilder};
+/// Returns the font + the index of the font (in case the font is a collection), using the
+/// face of the family that matches the `face_style` best. Same as in CSS, the `font-style` is
+/// matched before the `font-weight`: if there is no bold italic face, the italic face is used
+/// and the bold glyphs are synthesized.
+///
+/// Note: font-loader can only query bold and italic / oblique faces, so the exact
+/// `font-weight` and the `font-stretch` can't be selected for system fonts.
+fn load_system_font(id: &str, face_style: &FontFaceStyle) -> Option<(Vec<u8>, i32, FontSynthesis)> {
+    use azul_css::{StyleFontStyle::*, StyleFontWeight};
+    use font_loader::system_fonts;
+
+    let styles = match face_style.style {
+        Normal => vec![Normal],
+        Italic => vec![Italic, Oblique, Normal],
+        Oblique => vec![Oblique, Italic, Normal],
+    };
+    let weights = if face_style.weight.is_bold() {
+        vec![StyleFontWeight::BOLD, StyleFontWeight::NORMAL]
+    } else {
+        vec![StyleFontWeight::NORMAL]
+    };
+
+    for style in styles {
+        for weight in weights.iter() {
+            let mut font_builder = system_font_property_builder(id);
+            if weight.is_bold() {
+                font_builder = font_builder.bold();
+            }
+            font_builder = match style {
+                Normal => font_builder,
+                Italic => font_builder.italic(),
+                Oblique => font_builder.oblique(),
+            };
+
+            if let Some((font_bytes, font_index)) = system_fonts::get(&font_builder.build()) {
+                let available = FontFaceStyle {
+                    weight: *weight,
+                    style,
+                    stretch: face_style.stretch,
+                };
+                let synthesis = FontSynthesis::new(face_style, &available);
+                return Some((font_bytes, font_index, synthesis));
+            }
+        }
+    }
 
-    let font_builder = match id {
+    None
+}
+
+/// System fonts that are used for the characters that none of the fonts of the `font-family`
+/// contains, in order of preference (after the generic `sans-serif` font of the system)
+#[cfg(target_os = "linux")]
+pub(crate) const SYSTEM_FALLBACK_FONTS: &[&str] = &[
+    "sans-serif",
+    "DejaVu Sans",
+    "Noto Sans",
+    "Noto Sans CJK SC",
+    "Noto Color Emoji",
+    "Noto Sans Symbols",
+    "Noto Sans Symbols2",
+    "Symbola",
+];
+
+#[cfg(target_os = "macos")]
+pub(crate) const SYSTEM_FALLBACK_FONTS: &[&str] = &[
+    "sans-serif",
+    "Lucida Grande",
+    "PingFang SC",
+    "Hiragino Sans",
+    "Apple SD Gothic Neo",
+    "Apple Color Emoji",
+    "Apple Symbols",
+];
+
+#[cfg(all(not(target_os = "linux"), not(target_os = "macos")))]
+pub(crate) const SYSTEM_FALLBACK_FONTS: &[&str] = &[
+    "sans-serif",
+    "Arial Unicode MS",
+    "Microsoft YaHei",
+    "Yu Gothic",
+    "Malgun Gothic",
+    "Segoe UI Emoji",
+    "Segoe UI Symbol",
+];
+
+/// Returns the font-loader query for a font family, resolving the generic CSS families
+fn system_font_property_builder(id: &str) -> font_loader::system_fonts::FontPropertyBuilder {
+    use font_loader::system_fonts::FontPropertyBuilder;
+
+    match id {
         "monospace" => {
             #[cfg(target_os = "linux")]
             {
@@ -778,9 +1064,7 @@ fn load_system_font(id: &str) -> Option<(Vec<u8>, i32)> {
         }
         "serif" => FontPropertyBuilder::new().family("Times New Roman"),
         other => FontPropertyBuilder::new().family(other),
-    };
-
-    system_fonts::get(&font_builder.build())
+    }
 }
 
 /// Return the native fonts
@@ -1042,17 +1326,17 @@ fn test_font_gc() {
     assert_eq!(scan_ui_description_for_image_keys(&app_resources, &display_list_frame_3), FastHashSet::default());
 
     assert_eq!(scan_ui_description_for_font_keys(&app_resources, &display_list_frame_1), build_map(vec![
-        (ImmediateFontId::Unresolved("Arial".to_string()), build_set(vec![px_to_au(10.0)])),
-        (ImmediateFontId::Unresolved("Helvetica".to_string()), build_set(vec![px_to_au(10.0)])),
-        (ImmediateFontId::Unresolved("Times New Roman".to_string()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("Arial".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("Helvetica".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("Times New Roman".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
     ]));
     assert_eq!(scan_ui_description_for_font_keys(&app_resources, &display_list_frame_2), build_map(vec![
-        (ImmediateFontId::Unresolved("sans-serif".to_string()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("sans-serif".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
     ]));
     assert_eq!(scan_ui_description_for_font_keys(&app_resources, &display_list_frame_3), build_map(vec![
-        (ImmediateFontId::Unresolved("Arial".to_string()), build_set(vec![px_to_au(10.0)])),
-        (ImmediateFontId::Unresolved("Helvetica".to_string()), build_set(vec![px_to_au(10.0)])),
-        (ImmediateFontId::Unresolved("Times New Roman".to_string()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("Arial".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("Helvetica".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
+        (ImmediateFontId::Unresolved("Times New Roman".to_string(), FontFaceStyle::default()), build_set(vec![px_to_au(10.0)])),
     ]));
 
     let mut fake_render_api = FakeRenderApi::new();
@@ -1,10 +1,11 @@
 use azul_core::{
-    app_resources::{FontInstanceKey, ImageId},
+    app_resources::{FontInstanceKey, FontMetrics, ImageId},
     callbacks::PipelineId,
     display_list::{
         AlphaType, CachedDisplayList, DisplayListFrame, DisplayListMsg, DisplayListScrollFrame,
-        ImageRendering, LayoutRectContent, StyleBorderColors, StyleBorderRadius, StyleBorderStyles,
-        StyleBorderWidths, StyleBoxShadow,
+        FrameFilter, FramePosition, GlyphInstance, ImageRendering, LayoutRectContent, LineStyle,
+        StickyFrame, StickyOffsetBounds, StyleBorderColors, StyleBorderRadius, StyleBorderStyles,
+        StyleBorderWidths, StyleBoxShadow, TextShadow,
     },
     ui_solver::{
         ExternalScrollId, LayoutResult, OverflowingScrollNode, PositionedRectangle,
@@ -13,7 +14,9 @@ use azul_core::{
 };
 use azul_css::{
     BoxShadowClipMode, ColorU, Css, CssProperty, CssPropertyValue, LayoutPoint, LayoutPosition,
-    LayoutRect, LayoutSize, RectLayout, RectStyle,
+    LayoutRect, LayoutSize, LayoutTransform, RectLayout, RectStyle, SizeResolveContext,
+    StyleFilterFunction, StyleTextDecorationLine, StyleTextDecorationStyle,
+    StyleTextDecorationThickness, ToAbsoluteUnits,
 };
 use azul_layout::{style::Style, GetStyle};
 use std::collections::BTreeMap;
@@ -29,7 +32,7 @@ use crate::{
     compositor::new_opengl_texture_id,
     dom::{
         DomId, DomString, NodeData,
-        NodeType::{self, Div, GlTexture, IFrame, Image, Label, Text},
+        NodeType::{self, Div, GlTexture, IFrame, Image, Label, RichText, Text},
         ScrollTagId,
     },
     id_tree::{NodeDataContainer, NodeHierarchy, NodeId},
@@ -37,7 +40,7 @@ use crate::{
     ui_description::{StyledNode, UiDescription},
     ui_solver::do_the_layout,
     ui_state::UiState,
-    window::{FakeWindow, Window, WindowSize},
+    window::{FakeWindow, WindowSize},
     FastHashMap,
 };
 
@@ -106,6 +109,9 @@ pub(crate) struct DisplayRectangle<'a> {
     pub(crate) style: RectStyle,
     /// The layout properties of the node, parsed
     pub(crate) layout: RectLayout,
+    /// The font size and viewport size that the relative units of the node are
+    /// resolved against, the spans of a rich text are resolved against it, too
+    pub(crate) size_resolve_context: SizeResolveContext,
 }
 
 impl<'a> DisplayRectangle<'a> {
@@ -116,16 +122,19 @@ impl<'a> DisplayRectangle<'a> {
             styled_node,
             style: RectStyle::default(),
             layout: RectLayout::default(),
+            size_resolve_context: SizeResolveContext::default(),
         }
     }
 }
 
 impl<'a> GetStyle for DisplayRectangle<'a> {
     fn get_style(&self) -> Style {
-        use azul_core::ui_solver::DEFAULT_FONT_SIZE;
+        use crate::ui_solver::{get_font_features, get_font_variations};
         use azul_css::{
-            LayoutAlignContent, LayoutAlignItems, LayoutBoxSizing, LayoutDirection, LayoutDisplay,
-            LayoutJustifyContent, LayoutWrap, Overflow as LayoutOverflow, PixelValue,
+            GridLine as LayoutGridLine, GridPlacement as LayoutGridPlacement, GridTemplate,
+            GridTrackBreadth, LayoutAlignContent, LayoutAlignItems, LayoutBoxSizing,
+            LayoutDirection, LayoutDisplay, LayoutFloat, LayoutJustifyContent, LayoutWrap,
+            Overflow as LayoutOverflow, PixelValue, StyleTextDirection,
         };
         use azul_layout::{style::*, Number, Offsets, Size};
 
@@ -134,19 +143,57 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
 
         #[inline]
         fn translate_dimension(input: Option<CssPropertyValue<PixelValue>>) -> Dimension {
-            use azul_css::{SizeMetric, EM_HEIGHT, PT_TO_PX};
             match input {
                 None => Dimension::Undefined,
                 Some(CssPropertyValue::Auto) => Dimension::Auto,
                 Some(CssPropertyValue::None) => Dimension::Pixels(0.0),
                 Some(CssPropertyValue::Initial) => Dimension::Undefined,
                 Some(CssPropertyValue::Inherit) => Dimension::Undefined,
-                Some(CssPropertyValue::Exact(pixel_value)) => match pixel_value.metric {
-                    SizeMetric::Px => Dimension::Pixels(pixel_value.number.get()),
-                    SizeMetric::Percent => Dimension::Percent(pixel_value.number.get()),
-                    SizeMetric::Pt => Dimension::Pixels(pixel_value.number.get() * PT_TO_PX),
-                    SizeMetric::Em => Dimension::Pixels(pixel_value.number.get() * EM_HEIGHT),
+                Some(CssPropertyValue::Exact(pixel_value)) => pixel_value.into(),
+            }
+        }
+
+        #[inline]
+        fn translate_grid_template(input: Option<&GridTemplate>) -> Vec<TrackSize> {
+            fn translate_track_breadth(breadth: GridTrackBreadth) -> TrackBreadth {
+                match breadth {
+                    GridTrackBreadth::Auto => TrackBreadth::Auto,
+                    GridTrackBreadth::Fraction(fr) => TrackBreadth::Fraction(fr.get()),
+                    GridTrackBreadth::Fixed(pixel_value) => pixel_value.into(),
+                }
+            }
+
+            match input {
+                Some(template) => template
+                    .tracks
+                    .iter()
+                    .map(|track| TrackSize {
+                        min: translate_track_breadth(track.min),
+                        max: translate_track_breadth(track.max),
+                    })
+                    .collect(),
+                None => Vec::new(),
+            }
+        }
+
+        #[inline]
+        fn translate_grid_placement(
+            input: Option<CssPropertyValue<LayoutGridPlacement>>,
+        ) -> GridPlacement {
+            fn translate_grid_line(line: LayoutGridLine) -> GridLine {
+                match line {
+                    LayoutGridLine::Auto => GridLine::Auto,
+                    LayoutGridLine::Line(l) => GridLine::Line(l),
+                    LayoutGridLine::Span(s) => GridLine::Span(s),
+                }
+            }
+
+            match input {
+                Some(CssPropertyValue::Exact(placement)) => GridPlacement {
+                    start: translate_grid_line(placement.start),
+                    end: translate_grid_line(placement.end),
                 },
+                _ => GridPlacement::default(),
             }
         }
 
@@ -159,6 +206,9 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
                 Some(CssPropertyValue::Inherit) => Display::Flex,
                 Some(CssPropertyValue::Exact(LayoutDisplay::Flex)) => Display::Flex,
                 Some(CssPropertyValue::Exact(LayoutDisplay::Inline)) => Display::Inline,
+                Some(CssPropertyValue::Exact(LayoutDisplay::Grid)) => Display::Grid,
+                Some(CssPropertyValue::Exact(LayoutDisplay::Block)) => Display::Block,
+                Some(CssPropertyValue::Exact(LayoutDisplay::None)) => Display::None,
             },
             box_sizing: match rect_layout
                 .box_sizing
@@ -177,9 +227,23 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
                 Some(LayoutPosition::Static) => PositionType::Relative, // todo - static?
                 Some(LayoutPosition::Relative) => PositionType::Relative,
                 Some(LayoutPosition::Absolute) => PositionType::Absolute,
+                Some(LayoutPosition::Fixed) => PositionType::Fixed,
+                Some(LayoutPosition::Sticky) => PositionType::Sticky,
                 None => PositionType::Relative,
             },
-            direction: Direction::LTR,
+            float: match rect_layout.float {
+                Some(CssPropertyValue::Exact(LayoutFloat::Left)) => Float::Left,
+                Some(CssPropertyValue::Exact(LayoutFloat::Right)) => Float::Right,
+                _ => Float::None,
+            },
+            direction: match rect_style
+                .direction
+                .unwrap_or_default()
+                .get_property_or_default()
+            {
+                Some(StyleTextDirection::Rtl) => Direction::RTL,
+                Some(StyleTextDirection::Ltr) | None => Direction::LTR,
+            },
             flex_direction: match rect_layout
                 .direction
                 .unwrap_or_default()
@@ -369,11 +433,42 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
             align_self: AlignSelf::Auto, // todo!
             flex_basis: Dimension::Auto, // todo!
             aspect_ratio: Number::Undefined,
-            font_size_px: rect_style
-                .font_size
-                .and_then(|fs| fs.get_property_owned())
-                .unwrap_or(DEFAULT_FONT_SIZE)
-                .0,
+            grid_template_columns: translate_grid_template(
+                rect_layout
+                    .grid_template_columns
+                    .as_ref()
+                    .and_then(|prop| prop.get_property())
+                    .map(|c| &c.0),
+            ),
+            grid_template_rows: translate_grid_template(
+                rect_layout
+                    .grid_template_rows
+                    .as_ref()
+                    .and_then(|prop| prop.get_property())
+                    .map(|r| &r.0),
+            ),
+            grid_gap: Size {
+                width: translate_dimension(
+                    rect_layout
+                        .grid_column_gap
+                        .map(|prop| prop.map_property(|g| g.0)),
+                ),
+                height: translate_dimension(
+                    rect_layout
+                        .grid_row_gap
+                        .map(|prop| prop.map_property(|g| g.0)),
+                ),
+            },
+            grid_column: translate_grid_placement(
+                rect_layout
+                    .grid_column
+                    .map(|prop| prop.map_property(|c| c.0)),
+            ),
+            grid_row: translate_grid_placement(
+                rect_layout.grid_row.map(|prop| prop.map_property(|r| r.0)),
+            ),
+            // Also contains the inherited font size if the node doesn't set a font size
+            font_size_px: PixelValue::px(self.size_resolve_context.font_size_px),
             line_height: rect_style
                 .line_height
                 .and_then(|lh| lh.map_property(|lh| lh.0).get_property_owned())
@@ -388,6 +483,20 @@ impl<'a> GetStyle for DisplayRectangle<'a> {
                 .tab_width
                 .and_then(|tw| tw.map_property(|tw| tw.0).get_property_owned())
                 .map(|tw| tw.get()),
+            white_space: rect_style
+                .white_space
+                .and_then(|ws| ws.get_property_owned())
+                .unwrap_or_default(),
+            text_overflow: rect_style
+                .text_overflow
+                .and_then(|to| to.get_property_owned())
+                .unwrap_or_default(),
+            line_clamp: rect_style
+                .line_clamp
+                .and_then(|lc| lc.get_property_owned())
+                .map(|lc| lc.0),
+            font_features: get_font_features(rect_style),
+            font_variations: get_font_variations(rect_style),
         }
     }
 }
@@ -460,31 +569,27 @@ fn sort_children_by_position<'a>(
 ) -> Vec<NodeId> {
     use azul_css::LayoutPosition::*;
 
+    let is_positioned = |id: &NodeId| match rectangles[*id]
+        .layout
+        .position
+        .and_then(|p| p.get_property_or_default())
+        .unwrap_or_default()
+    {
+        Absolute | Fixed | Sticky => true,
+        Static | Relative => false,
+    };
+
     let mut not_absolute_children = parent
         .children(node_hierarchy)
-        .filter(|id| {
-            rectangles[*id]
-                .layout
-                .position
-                .and_then(|p| p.get_property_or_default())
-                .unwrap_or_default()
-                != Absolute
-        })
+        .filter(|id| !is_positioned(id))
         .collect::<Vec<NodeId>>();
 
     let mut absolute_children = parent
         .children(node_hierarchy)
-        .filter(|id| {
-            rectangles[*id]
-                .layout
-                .position
-                .and_then(|p| p.get_property_or_default())
-                .unwrap_or_default()
-                == Absolute
-        })
+        .filter(|id| is_positioned(id))
         .collect::<Vec<NodeId>>();
 
-    // Append the position:absolute children after the regular children
+    // Append the position:absolute / fixed / sticky children after the regular children
     not_absolute_children.append(&mut absolute_children);
     not_absolute_children
 }
@@ -516,9 +621,17 @@ fn get_nodes_that_need_scroll_clip<'a, T: 'a>(
     for (_, parent) in parents {
         let parent_rect = &layouted_rects[*parent];
 
+        // Fixed children are positioned relative to the window, so they can't overflow the parent
         let children_scroll_rect = match parent_rect.bounds.get_scroll_rect(
             parent
                 .children(&node_hierarchy)
+                .filter(|child_id| {
+                    display_list_rects[*child_id]
+                        .layout
+                        .position
+                        .and_then(|p| p.get_property_or_default())
+                        != Some(LayoutPosition::Fixed)
+                })
                 .map(|child_id| layouted_rects[child_id].bounds),
         ) {
             None => continue,
@@ -589,14 +702,24 @@ fn node_needs_to_clip_children(layout: &RectLayout) -> bool {
 ///
 /// This only looks at the user-facing styles of the `UiDescription`, not the actual
 /// layout. The layout is done only in the `into_display_list_builder` step.
+///
+/// Lengths in `em`, `rem`, `vw`, `vh`, `vmin` and `vmax` are resolved to pixels here,
+/// against the font size of the node and the size of the window.
 pub(crate) fn display_list_from_ui_description<'a, T>(
     ui_description: &'a UiDescription<T>,
     ui_state: &UiState<T>,
+    window_size: &WindowSize,
 ) -> DisplayList<'a, T> {
     let arena = &ui_description.ui_descr_arena;
+    let node_hierarchy = &arena.node_layout;
+    let viewport = window_size.dimensions;
 
     let mut override_warnings = Vec::new();
+    let mut contexts =
+        NodeDataContainer::new(vec![SizeResolveContext::default(); node_hierarchy.len()]);
 
+    // Parents have a lower NodeId than their children, so the font size of the parent
+    // is always resolved before the font sizes of its children
     let display_rect_arena = arena.node_data.transform(|_, node_id| {
         let style = &ui_description.styled_nodes[node_id];
         let tag = ui_state.node_ids_to_tag_ids.get(&node_id).map(|tag| *tag);
@@ -606,6 +729,26 @@ pub(crate) fn display_list_from_ui_description<'a, T>(
             node_id,
             &ui_description.dynamic_css_overrides,
         ));
+        // Running transitions and animations override the styled values
+        if let Some(animated_properties) = ui_description.animated_properties.get(&node_id) {
+            for property in animated_properties.values() {
+                apply_style_property(&mut rect.style, &mut rect.layout, property);
+            }
+        }
+
+        let font_size = rect
+            .style
+            .font_size
+            .as_ref()
+            .and_then(|font_size| font_size.get_property().map(|f| f.0));
+        let context = match node_hierarchy[node_id].parent {
+            Some(parent_id) => contexts[parent_id].child(font_size),
+            None => SizeResolveContext::root(font_size, viewport.width, viewport.height),
+        };
+        rect.style = rect.style.to_absolute_units(&context);
+        rect.layout = rect.layout.to_absolute_units(&context);
+        rect.size_resolve_context = context;
+        contexts[node_id] = context;
         rect
     });
 
@@ -634,13 +777,25 @@ pub(crate) struct CachedDisplayListResult {
 }
 
 /// Inserts and solves the top-level DOM (i.e. the DOM with the ID 0)
+///
+/// Only takes the parts of the window that are necessary to build the display list
+/// (epoch, pipeline, size and style), so that the display list can be built for
+/// windows that don't have an OS window attached (see the `headless` module).
+///
+/// If the `cached_layout` of the last frame is given (i.e. the DOM and the layout-relevant
+/// styles didn't change, see `UiDescription::has_same_layout`), the layout step is
+/// skipped if the window size hasn't changed.
 pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
     display_list: DisplayList<'a, T>,
     app_data_access: &mut T,
-    window: &mut Window<T>,
+    epoch: Epoch,
+    pipeline_id: PipelineId,
+    window_size: WindowSize,
+    css: &Css,
     fake_window: &mut FakeWindow<T>,
     app_resources: &mut AppResources,
     render_api: &mut U,
+    cached_layout: Option<LayoutResult>,
 ) -> CachedDisplayListResult {
     use crate::app_resources::add_fonts_and_images;
 
@@ -663,21 +818,27 @@ pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
     //      - Insert the new font keys and image keys into the render API
     //      - Scan all IFrameCallbacks, generate the DomID for each callback
     //      - Repeat while number_of_iframe_callbacks != 0
+    //
+    // The fonts of the @font-face rules are already registered when the CSS is set
+    // (or hot-reloaded), so the font-family names can be resolved to their FontIds
     add_fonts_and_images(app_resources, render_api, &display_list);
 
-    let layout_result = do_the_layout(
-        node_hierarchy,
-        node_data,
-        &display_list.rectangles,
-        &*app_resources,
-        LayoutRect {
-            origin: LayoutPoint::new(0.0, 0.0),
-            size: LayoutSize::new(
-                window.state.size.dimensions.width,
-                window.state.size.dimensions.height,
-            ),
-        },
-    );
+    let bounding_rect = LayoutRect {
+        origin: LayoutPoint::new(0.0, 0.0),
+        size: LayoutSize::new(window_size.dimensions.width, window_size.dimensions.height),
+    };
+
+    let layout_result = match cached_layout {
+        Some(layout_result) if layout_result.bounding_rect == bounding_rect => layout_result,
+        _ => do_the_layout(
+            node_hierarchy,
+            node_data,
+            &display_list.rectangles,
+            &*app_resources,
+            bounding_rect,
+            bounding_rect.size,
+        ),
+    };
 
     let rects_in_rendering_order =
         determine_rendering_order(node_hierarchy, &display_list.rectangles);
@@ -688,7 +849,7 @@ pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
         node_data,
         &layout_result.rects,
         &layout_result.node_depths,
-        window.internal.pipeline_id,
+        pipeline_id,
     );
 
     let mut scrollable_nodes_map = BTreeMap::new();
@@ -700,16 +861,16 @@ pub(crate) fn display_list_to_cached_display_list<'a, T, U: FontImageApi>(
     let mut image_resource_updates = BTreeMap::new();
 
     let root_node = push_rectangles_into_displaylist(
-        window.internal.epoch,
-        window.state.size,
+        epoch,
+        window_size,
         rects_in_rendering_order,
         &DisplayListParametersRef {
             dom_id: root_dom_id,
-            pipeline_id: window.internal.pipeline_id,
+            pipeline_id,
             node_hierarchy,
             node_data,
             display_rectangle_arena: &display_list.rectangles,
-            css: &window.css,
+            css,
         },
         &mut DisplayListParametersMut {
             app_data: app_data_access,
@@ -811,6 +972,16 @@ fn displaylist_handle_rect<'a, 'b, 'c, 'd, 'e, 'f, T, U: FontImageApi>(
             bottom_right: rect.style.border_bottom_right_radius,
         },
         rect: display_list_rect_bounds,
+        position: get_frame_position(
+            &rect.layout,
+            *rect_idx,
+            referenced_content.node_hierarchy,
+            &referenced_mutable_content.layout_result[dom_id],
+            &referenced_mutable_content.scrollable_nodes[dom_id],
+            window_size,
+        ),
+        transform: get_frame_transform(&rect.style, display_list_rect_bounds),
+        filters: get_frame_filters(&rect.style),
         content: Vec::new(),
         children: Vec::new(),
     };
@@ -877,32 +1048,38 @@ fn displaylist_handle_rect<'a, 'b, 'c, 'd, 'e, 'f, T, U: FontImageApi>(
 
     match html_node {
         Div => {}
-        Text(_) | Label(_) => {
+        Text(_) | Label(_) | RichText(_) => {
             if let Some(layouted_glyphs) = referenced_mutable_content.layout_result[dom_id]
                 .layouted_glyph_cache
                 .get(&rect_idx)
                 .cloned()
             {
                 use crate::wr_translate::wr_translate_logical_size;
-                use azul_core::ui_solver::DEFAULT_FONT_COLOR;
 
-                let text_color = rect
-                    .style
-                    .text_color
-                    .and_then(|tc| tc.get_property().cloned())
-                    .unwrap_or(DEFAULT_FONT_COLOR)
-                    .0;
-                let positioned_words = &referenced_mutable_content.layout_result[dom_id]
-                    .positioned_word_cache[&rect_idx];
-                let font_instance_key = positioned_words.1;
-
-                frame.content.push(get_text(
+                let layout_result = &referenced_mutable_content.layout_result[dom_id];
+                let font_instance_keys = &layout_result.positioned_word_cache[&rect_idx].1;
+                let font_metrics = &layout_result.scaled_words[&rect_idx].0.font_metrics;
+
+                // The fonts of each span of a rich text are drawn in the style of the span
+                let font_styles = match (html_node, layout_result.rich_text_spans.get(&rect_idx)) {
+                    (RichText(spans), Some(rich_text_spans)) => rich_text_spans
+                        .font_spans
+                        .iter()
+                        .map(|span_idx| {
+                            get_text_run_style(&get_span_style(rect, &spans[*span_idx].style))
+                        })
+                        .collect(),
+                    _ => vec![get_text_run_style(&rect.style); font_instance_keys.len()],
+                };
+
+                frame.content.extend(get_text(
                     display_list_rect_bounds,
-                    &referenced_mutable_content.layout_result[dom_id].rects[*rect_idx].padding,
+                    &layout_result.rects[*rect_idx].padding,
                     wr_translate_logical_size(window_size.dimensions),
                     layouted_glyphs,
-                    font_instance_key,
-                    text_color,
+                    font_instance_keys,
+                    &font_styles,
+                    font_metrics,
                     &rect.layout,
                 ));
             }
@@ -993,6 +1170,140 @@ fn displaylist_handle_rect<'a, 'b, 'c, 'd, 'e, 'f, T, U: FontImageApi>(
     }
 }
 
+/// Returns whether the frame is fixed to the window (`position: fixed`) or sticks
+/// to the edges of its nearest scroll frame (`position: sticky`)
+fn get_frame_position(
+    rect_layout: &RectLayout,
+    rect_idx: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    layout_result: &LayoutResult,
+    scrollable_nodes: &ScrolledNodes,
+    window_size: &WindowSize,
+) -> FramePosition {
+    match rect_layout
+        .position
+        .and_then(|p| p.get_property_or_default())
+    {
+        Some(LayoutPosition::Fixed) => FramePosition::Fixed,
+        Some(LayoutPosition::Sticky) => {
+            let bounds = layout_result.rects[rect_idx].bounds;
+
+            // The sticky frame can't leave the content box of its parent
+            let containing_block = match node_hierarchy[rect_idx].parent {
+                Some(parent_id) => {
+                    let parent = &layout_result.rects[parent_id];
+                    let left = parent.padding.left + parent.border_widths.left;
+                    let top = parent.padding.top + parent.border_widths.top;
+                    let right = parent.padding.right + parent.border_widths.right;
+                    let bottom = parent.padding.bottom + parent.border_widths.bottom;
+                    LayoutRect::new(
+                        LayoutPoint::new(
+                            parent.bounds.origin.x + left,
+                            parent.bounds.origin.y + top,
+                        ),
+                        LayoutSize::new(
+                            parent.bounds.size.width - left - right,
+                            parent.bounds.size.height - top - bottom,
+                        ),
+                    )
+                }
+                None => bounds,
+            };
+
+            // Percentages are resolved against the size of the nearest scroll frame
+            let mut scroll_frame_size =
+                LayoutSize::new(window_size.dimensions.width, window_size.dimensions.height);
+            let mut current = node_hierarchy[rect_idx].parent;
+            while let Some(parent_id) = current {
+                if scrollable_nodes.overflowing_nodes.contains_key(&parent_id) {
+                    scroll_frame_size = layout_result.rects[parent_id].bounds.size;
+                    break;
+                }
+                current = node_hierarchy[parent_id].parent;
+            }
+
+            FramePosition::Sticky(StickyFrame {
+                top: rect_layout
+                    .top
+                    .and_then(|t| t.get_property_owned())
+                    .map(|t| t.0.to_pixels(scroll_frame_size.height)),
+                right: rect_layout
+                    .right
+                    .and_then(|r| r.get_property_owned())
+                    .map(|r| r.0.to_pixels(scroll_frame_size.width)),
+                bottom: rect_layout
+                    .bottom
+                    .and_then(|b| b.get_property_owned())
+                    .map(|b| b.0.to_pixels(scroll_frame_size.height)),
+                left: rect_layout
+                    .left
+                    .and_then(|l| l.get_property_owned())
+                    .map(|l| l.0.to_pixels(scroll_frame_size.width)),
+                vertical_offset_bounds: StickyOffsetBounds {
+                    min: (containing_block.min_y() - bounds.min_y()).min(0.0),
+                    max: (containing_block.max_y() - bounds.max_y()).max(0.0),
+                },
+                horizontal_offset_bounds: StickyOffsetBounds {
+                    min: (containing_block.min_x() - bounds.min_x()).min(0.0),
+                    max: (containing_block.max_x() - bounds.max_x()).max(0.0),
+                },
+            })
+        }
+        _ => FramePosition::Static,
+    }
+}
+
+/// Resolves the `transform` and `transform-origin` of a rect, relative to its bounds
+fn get_frame_transform(rect_style: &RectStyle, bounds: LayoutRect) -> Option<LayoutTransform> {
+    let transform = rect_style.transform.as_ref()?.get_property()?;
+    let origin = rect_style
+        .transform_origin
+        .and_then(|o| o.get_property_or_default())
+        .unwrap_or_default();
+    Some(transform.get_layout_transform(origin, bounds))
+}
+
+/// Resolves the `filter` functions (in order) and the `opacity` of a rect
+fn get_frame_filters(rect_style: &RectStyle) -> Vec<FrameFilter> {
+    let mut filters = rect_style
+        .filter
+        .as_ref()
+        .and_then(|f| f.get_property())
+        .map(|f| {
+            f.0.iter()
+                .map(|function| match function {
+                    StyleFilterFunction::Blur(radius) => FrameFilter::Blur(radius.to_pixels()),
+                    StyleFilterFunction::Grayscale(amount) => FrameFilter::Grayscale(amount.get()),
+                    StyleFilterFunction::Brightness(amount) => {
+                        FrameFilter::Brightness(amount.get())
+                    }
+                    StyleFilterFunction::DropShadow(shadow) => FrameFilter::DropShadow {
+                        offset: LayoutPoint::new(
+                            shadow.offset[0].to_pixels(),
+                            shadow.offset[1].to_pixels(),
+                        ),
+                        blur_radius: shadow.blur_radius.to_pixels(),
+                        color: shadow.color,
+                    },
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    // opacity is applied last, so that it also applies to the drop shadows
+    let opacity = rect_style
+        .opacity
+        .and_then(|o| o.get_property_or_default())
+        .unwrap_or_default()
+        .0
+        .get();
+    if opacity < 1.0 {
+        filters.push(FrameFilter::Opacity(opacity));
+    }
+
+    filters
+}
+
 #[inline]
 fn call_opengl_callback<'a, 'b, 'c, 'd, 'e, 'f, T, U: FontImageApi>(
     (texture_callback, texture_stack_ptr): &(GlCallback<T>, StackCheckedPointer<T>),
@@ -1150,6 +1461,9 @@ fn call_iframe_callback<'a, 'b, 'c, 'd, 'e, T, U: FontImageApi>(
                 clip_rect: None,
                 rect,
                 border_radius: StyleBorderRadius::default(),
+                position: FramePosition::Static,
+                transform: None,
+                filters: vec![],
                 content: vec![],
                 children: vec![],
             })
@@ -1170,11 +1484,18 @@ fn call_iframe_callback<'a, 'b, 'c, 'd, 'e, T, U: FontImageApi>(
         &mut focus_target,
         &hovered_nodes,
         is_mouse_down,
+        &referenced_mutable_content.fake_window.state.size,
+        referenced_mutable_content.fake_window.state.theme,
+        None,
     );
 
     let iframe_dom_id = ui_description.dom_id.clone();
 
-    let display_list = display_list_from_ui_description(&ui_description, &ui_state);
+    let display_list = display_list_from_ui_description(
+        &ui_description,
+        &ui_state,
+        &referenced_mutable_content.fake_window.state.size,
+    );
 
     app_resources::add_fonts_and_images(
         referenced_mutable_content.app_resources,
@@ -1187,12 +1508,14 @@ fn call_iframe_callback<'a, 'b, 'c, 'd, 'e, T, U: FontImageApi>(
     let node_data = &arena.node_data;
 
     // Insert the DOM into the solver so we can solve the layout of the rectangles
+    let window_size = referenced_mutable_content.fake_window.state.size.dimensions;
     let layout_result_iframe = do_the_layout(
         &node_hierarchy,
         &node_data,
         &display_list.rectangles,
         &*referenced_mutable_content.app_resources,
         rect,
+        LayoutSize::new(window_size.width, window_size.height),
     );
 
     let scrollable_nodes_iframe = get_nodes_that_need_scroll_clip(
@@ -1233,15 +1556,96 @@ fn call_iframe_callback<'a, 'b, 'c, 'd, 'e, T, U: FontImageApi>(
     )
 }
 
+fn get_text_color(rect_style: &RectStyle) -> ColorU {
+    use azul_core::ui_solver::DEFAULT_FONT_COLOR;
+    rect_style
+        .text_color
+        .and_then(|tc| tc.get_property().cloned())
+        .unwrap_or(DEFAULT_FONT_COLOR)
+        .0
+}
+
+/// Resolved style of the glyphs of one font of the font fallback chain of a text
+#[derive(Debug, Clone, PartialEq)]
+struct TextRunStyle {
+    color: ColorU,
+    decoration: TextDecoration,
+    shadows: Vec<TextShadow>,
+}
+
+/// Resolved `text-decoration-*` properties of a text
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct TextDecoration {
+    line: StyleTextDecorationLine,
+    style: StyleTextDecorationStyle,
+    color: ColorU,
+    /// `None` if the thickness is taken from the font
+    thickness: Option<f32>,
+}
+
+fn get_text_run_style(rect_style: &RectStyle) -> TextRunStyle {
+    let color = get_text_color(rect_style);
+
+    let thickness = rect_style
+        .text_decoration_thickness
+        .and_then(|t| t.get_property().cloned())
+        .unwrap_or_default();
+
+    TextRunStyle {
+        color,
+        decoration: TextDecoration {
+            line: rect_style
+                .text_decoration_line
+                .and_then(|l| l.get_property().cloned())
+                .unwrap_or_default(),
+            style: rect_style
+                .text_decoration_style
+                .and_then(|s| s.get_property().cloned())
+                .unwrap_or_default(),
+            // The lines are drawn in the color of the text if no color is set
+            color: rect_style
+                .text_decoration_color
+                .and_then(|c| c.get_property().cloned())
+                .map_or(color, |c| c.0),
+            thickness: match thickness {
+                StyleTextDecorationThickness::FromFont => None,
+                StyleTextDecorationThickness::Length(l) => Some(l.to_pixels()),
+            },
+        },
+        shadows: rect_style
+            .text_shadow
+            .as_ref()
+            .and_then(|s| s.get_property())
+            .map(|s| {
+                s.0.iter()
+                    .map(|shadow| TextShadow {
+                        offset: LayoutPoint::new(
+                            shadow.offset[0].to_pixels(),
+                            shadow.offset[1].to_pixels(),
+                        ),
+                        color: shadow.color,
+                        blur_radius: shadow.blur_radius.to_pixels(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns one text item per run of glyphs that use the same font of the font fallback chain,
+/// plus the text decoration lines. Underlines and overlines are drawn below the glyphs,
+/// line-through lines are drawn above the glyphs. Items with a `text-shadow` are wrapped
+/// in a `LayoutRectContent::Shadowed`.
 fn get_text(
     bounds: LayoutRect,
     padding: &ResolvedOffsets,
     root_window_size: LayoutSize,
     layouted_glyphs: LayoutedGlyphs,
-    font_instance_key: FontInstanceKey,
-    font_color: ColorU,
+    font_instance_keys: &[FontInstanceKey],
+    font_styles: &[TextRunStyle],
+    font_metrics: &[FontMetrics],
     rect_layout: &RectLayout,
-) -> LayoutRectContent {
+) -> Vec<LayoutRectContent> {
     let overflow_horizontal_visible = rect_layout.is_horizontal_overflow_visible();
     let overflow_vertical_visible = rect_layout.is_horizontal_overflow_visible();
 
@@ -1267,12 +1671,176 @@ fn get_text(
         }
     };
 
-    LayoutRectContent::Text {
-        glyphs: layouted_glyphs.glyphs,
-        font_instance_key,
-        color: font_color,
-        glyph_options: None,
-        clip: text_clip_rect,
+    let decoration_runs = layouted_glyphs
+        .lines()
+        .into_iter()
+        .flat_map(|(baseline, glyphs, glyph_fonts)| {
+            get_decoration_runs(glyphs, glyph_fonts, font_styles)
+                .into_iter()
+                .map(move |(font, x_range)| (font, baseline, x_range))
+        })
+        .collect::<Vec<_>>();
+
+    let get_decoration_lines = |is_line_through: bool| {
+        decoration_runs
+            .iter()
+            .flat_map(|(font, baseline, x_range)| {
+                let decoration = font_styles[*font].decoration;
+                let metrics = font_metrics.get(*font).cloned().unwrap_or_default();
+                let underline_thickness =
+                    decoration.thickness.unwrap_or(metrics.underline_thickness);
+                let strikeout_thickness =
+                    decoration.thickness.unwrap_or(metrics.strikeout_thickness);
+
+                let mut lines = Vec::new();
+                if is_line_through {
+                    if decoration.line.line_through {
+                        lines.push((baseline + metrics.strikeout_position, strikeout_thickness));
+                    }
+                } else {
+                    if decoration.line.underline {
+                        lines.push((baseline + metrics.underline_position, underline_thickness));
+                    }
+                    if decoration.line.overline {
+                        lines.push((baseline + metrics.ascender, underline_thickness));
+                    }
+                }
+
+                lines.into_iter().flat_map(move |(top, thickness)| {
+                    get_decoration_line_items(*x_range, top, thickness, &decoration, text_clip_rect)
+                        .into_iter()
+                        .map(move |item| (*font, item))
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut items = get_decoration_lines(false);
+    items.extend(
+        layouted_glyphs
+            .font_runs()
+            .into_iter()
+            .filter_map(|(font, glyphs)| {
+                Some((
+                    font,
+                    LayoutRectContent::Text {
+                        glyphs: glyphs.to_vec(),
+                        font_instance_key: *font_instance_keys.get(font)?,
+                        color: font_styles.get(font)?.color,
+                        glyph_options: None,
+                        clip: text_clip_rect,
+                    },
+                ))
+            }),
+    );
+    items.extend(get_decoration_lines(true));
+
+    // Consecutive items with the same shadows are drawn with the shadows in one group
+    let mut groups = Vec::<(&[TextShadow], Vec<LayoutRectContent>)>::new();
+    for (font, item) in items {
+        let shadows = font_styles
+            .get(font)
+            .map(|style| &style.shadows[..])
+            .unwrap_or(&[]);
+        match groups.last_mut() {
+            Some((group_shadows, group)) if *group_shadows == shadows => group.push(item),
+            _ => groups.push((shadows, vec![item])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .flat_map(|(shadows, group)| {
+            if shadows.is_empty() {
+                group
+            } else {
+                vec![LayoutRectContent::Shadowed {
+                    shadows: shadows.to_vec(),
+                    content: group,
+                }]
+            }
+        })
+        .collect()
+}
+
+/// Splits the glyphs of a line into runs of consecutive glyphs with the same text
+/// decoration, returns the font of the run (for the metrics of the lines) + the
+/// horizontal extent of the run. Runs without decoration lines are skipped.
+fn get_decoration_runs(
+    glyphs: &[GlyphInstance],
+    glyph_fonts: &[usize],
+    font_styles: &[TextRunStyle],
+) -> Vec<(usize, (f32, f32))> {
+    let decoration_of = |font: usize| font_styles.get(font).map(|style| style.decoration);
+    let mut runs = Vec::new();
+    let mut run_start = 0;
+
+    for glyph_idx in 1..=glyphs.len() {
+        if glyph_idx < glyphs.len()
+            && decoration_of(glyph_fonts[glyph_idx]) == decoration_of(glyph_fonts[run_start])
+        {
+            continue;
+        }
+
+        let run_glyphs = &glyphs[run_start..glyph_idx];
+        let run_fonts = &glyph_fonts[run_start..glyph_idx];
+        run_start = glyph_idx;
+
+        let has_lines = decoration_of(run_fonts[0]).map_or(false, |decoration| {
+            decoration.line != StyleTextDecorationLine::default()
+        });
+        if !has_lines {
+            continue;
+        }
+
+        // The metrics are taken from the primary font, if the run contains any glyphs
+        // of the primary font, so that the lines don't jump at fallback glyphs
+        let font = run_fonts.iter().cloned().min().unwrap_or(0);
+        let x_start = run_glyphs
+            .iter()
+            .map(|g| g.point.x)
+            .fold(f32::MAX, f32::min);
+        let x_end = run_glyphs
+            .iter()
+            .map(|g| g.point.x + g.size.width)
+            .fold(f32::MIN, f32::max);
+        runs.push((font, (x_start, x_end)));
+    }
+
+    runs
+}
+
+/// Returns the line items of one text decoration line, `top` is the top edge of the line
+fn get_decoration_line_items(
+    (x_start, x_end): (f32, f32),
+    top: f32,
+    thickness: f32,
+    decoration: &TextDecoration,
+    clip: Option<LayoutRect>,
+) -> Vec<LayoutRectContent> {
+    let line = |top: f32, height: f32, style: LineStyle| LayoutRectContent::Line {
+        bounds: LayoutRect::new(
+            LayoutPoint::new(x_start, top),
+            LayoutSize::new(x_end - x_start, height),
+        ),
+        thickness,
+        color: decoration.color,
+        style,
+        clip,
+    };
+
+    match decoration.style {
+        StyleTextDecorationStyle::Solid => vec![line(top, thickness, LineStyle::Solid)],
+        StyleTextDecorationStyle::Double => vec![
+            line(top, thickness, LineStyle::Solid),
+            line(top + 2.0 * thickness, thickness, LineStyle::Solid),
+        ],
+        StyleTextDecorationStyle::Dotted => vec![line(top, thickness, LineStyle::Dotted)],
+        StyleTextDecorationStyle::Dashed => vec![line(top, thickness, LineStyle::Dashed)],
+        // The wave oscillates around the position of the line
+        StyleTextDecorationStyle::Wavy => {
+            vec![line(top - thickness, 3.0 * thickness, LineStyle::Wavy)]
+        }
     }
 }
 
@@ -1338,6 +1906,26 @@ fn populate_css_properties(
         .collect()
 }
 
+/// Returns the style of a span of a `NodeType::RichText` node, i.e. the
+/// style of the node, overridden by the style properties of the span
+pub(crate) fn get_span_style(rect: &DisplayRectangle, span_style: &[CssProperty]) -> RectStyle {
+    let mut style = rect.style.clone();
+    // Layout properties can't be set on spans, since they are laid out inline
+    let mut layout = RectLayout::default();
+
+    let font_size = span_style.iter().rev().find_map(|property| match property {
+        CssProperty::FontSize(font_size) => font_size.get_property().map(|f| f.0),
+        _ => None,
+    });
+    let context = rect.size_resolve_context.child(font_size);
+
+    for property in span_style {
+        let property = property.to_absolute_units(&context);
+        apply_style_property(&mut style, &mut layout, &property);
+    }
+    style
+}
+
 fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property: &CssProperty) {
     use azul_css::CssProperty::*;
 
@@ -1349,12 +1937,30 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         TextColor(c) => style.text_color = Some(*c),
         FontSize(fs) => style.font_size = Some(*fs),
         FontFamily(ff) => style.font_family = Some(ff.clone()),
+        FontWeight(fw) => style.font_weight = Some(*fw),
+        FontStyle(fs) => style.font_style = Some(*fs),
+        FontStretch(fs) => style.font_stretch = Some(*fs),
+        FontFeatureSettings(ffs) => style.font_feature_settings = Some(ffs.clone()),
+        FontVariantNumeric(fvn) => style.font_variant_numeric = Some(*fvn),
+        FontVariationSettings(fvs) => style.font_variation_settings = Some(fvs.clone()),
         TextAlign(ta) => style.text_align = Some(*ta),
+        TextDirection(td) => style.direction = Some(*td),
 
         LetterSpacing(ls) => style.letter_spacing = Some(*ls),
         LineHeight(lh) => style.line_height = Some(*lh),
         WordSpacing(ws) => style.word_spacing = Some(*ws),
         TabWidth(tw) => style.tab_width = Some(*tw),
+        WordBreak(wb) => style.word_break = Some(*wb),
+        OverflowWrap(ow) => style.overflow_wrap = Some(*ow),
+        Hyphens(h) => style.hyphens = Some(*h),
+        WhiteSpace(ws) => style.white_space = Some(*ws),
+        TextOverflow(to) => style.text_overflow = Some(*to),
+        LineClamp(lc) => style.line_clamp = Some(*lc),
+        TextDecorationLine(tdl) => style.text_decoration_line = Some(*tdl),
+        TextDecorationStyle(tds) => style.text_decoration_style = Some(*tds),
+        TextDecorationColor(tdc) => style.text_decoration_color = Some(*tdc),
+        TextDecorationThickness(tdt) => style.text_decoration_thickness = Some(*tdt),
+        TextShadow(ts) => style.text_shadow = Some(ts.clone()),
         Cursor(c) => style.cursor = Some(*c),
 
         Width(w) => layout.width = Some(*w),
@@ -1378,6 +1984,13 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         AlignItems(ai) => layout.align_items = Some(*ai),
         AlignContent(ac) => layout.align_content = Some(*ac),
 
+        GridTemplateColumns(gtc) => layout.grid_template_columns = Some(gtc.clone()),
+        GridTemplateRows(gtr) => layout.grid_template_rows = Some(gtr.clone()),
+        GridColumnGap(gcg) => layout.grid_column_gap = Some(*gcg),
+        GridRowGap(grg) => layout.grid_row_gap = Some(*grg),
+        GridColumn(gc) => layout.grid_column = Some(*gc),
+        GridRow(gr) => layout.grid_row = Some(*gr),
+
         BackgroundContent(bc) => style.background = Some(bc.clone()),
         BackgroundPosition(bp) => style.background_position = Some(*bp),
         BackgroundSize(bs) => style.background_size = Some(*bs),
@@ -1420,6 +2033,23 @@ fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property
         BoxShadowRight(bsr) => style.box_shadow_right = Some(*bsr),
         BoxShadowTop(bst) => style.box_shadow_top = Some(*bst),
         BoxShadowBottom(bsb) => style.box_shadow_bottom = Some(*bsb),
+
+        Transform(t) => style.transform = Some(t.clone()),
+        TransformOrigin(to) => style.transform_origin = Some(*to),
+
+        Opacity(o) => style.opacity = Some(*o),
+        Filter(f) => style.filter = Some(f.clone()),
+
+        // Evaluated by the animation engine, the animated values
+        // are applied via `UiDescription::animated_properties`
+        Transition(_)
+        | AnimationName(_)
+        | AnimationDuration(_)
+        | AnimationTimingFunction(_)
+        | AnimationDelay(_)
+        | AnimationIterationCount(_)
+        | AnimationDirection(_)
+        | AnimationFillMode(_) => {}
     }
 }
 
@@ -0,0 +1,1069 @@
+//! Headless application runner - runs the `Layout` -> style -> layout -> display list
+//! pipeline of an application against a virtual window, without opening an OS window
+//! or creating an OpenGL context / WebRender renderer.
+//!
+//! This is mostly useful for testing: A `HeadlessApp` can step frames, receive
+//! (synthetic) window events and expose the resulting DOM, layout and display list,
+//! so that tests can run on CI machines without a display or a GPU.
+//!
+//! ```no_run
+//! # use azul::prelude::*;
+//! # use azul::headless::HeadlessApp;
+//! # struct MyDataModel { }
+//! # impl Layout for MyDataModel {
+//! #     fn layout(&self, _: LayoutInfo<Self>) -> Dom<Self> { Dom::div() }
+//! # }
+//! let mut window_state = WindowState::default();
+//! window_state.size.dimensions = LogicalSize::new(800.0, 600.0);
+//!
+//! let mut app = HeadlessApp::new(MyDataModel { });
+//! let window_id = app.add_window(window_state, Css::new());
+//! app.step().unwrap();
+//! assert!(app.get_layout_result(&window_id).is_some());
+//! ```
+//!
+//! Note that `GlTexture` nodes can't be rendered in a `HeadlessApp`, since there
+//! is no OpenGL context to render into: calling any OpenGL function on the
+//! `FakeWindow::gl_context` will panic.
+
+use azul_core::{
+    display_list::CachedDisplayList,
+    query::DomQuery,
+    ui_description::UiDescription,
+    ui_solver::{ExternalScrollId, LayoutResult, ScrolledNodes},
+    window::{FakeWindow, WindowId},
+};
+use azul_css::{Css, LayoutPoint, LayoutSize};
+use glium::glutin::event::WindowEvent;
+use std::{collections::BTreeMap, path::PathBuf, time::Instant};
+use webrender::api::Epoch;
+
+use crate::{
+    app::{
+        app_state_clean_up_finished_tasks, app_state_run_all_timers, call_callbacks,
+        increase_epoch, update_scroll_state, AppState, RuntimeError,
+    },
+    app_resources::HeadlessRenderApi,
+    callbacks::{FocusTarget, HitTestItem, LayoutCallback, PipelineId, Redraw, ScrollPosition},
+    dom::{DomId, NodeId},
+    traits::Layout,
+    ui_state::UiState,
+    window::{
+        clear_scroll_state, full_window_state_from_normal_state, full_window_state_to_window_state,
+        update_full_window_state, update_window_state, FullWindowState, LogicalPosition,
+        LogicalSize, ScrollStates, VirtualKeyCode, WindowState,
+    },
+};
+
+pub use glium::glutin::event::MouseButton;
+
+/// Application that runs without any OS window or GPU, see the module-level documentation
+pub struct HeadlessApp<T> {
+    /// The global application state, same as `App::app_state`
+    pub app_state: AppState<T>,
+    /// The `Layout::layout()` callback, see `App::layout_callback`
+    layout_callback: LayoutCallback<T>,
+    /// The virtual windows of this application
+    windows: BTreeMap<WindowId, HeadlessWindow<T>>,
+    /// Hands out font and image keys, but doesn't render anything
+    render_api: HeadlessRenderApi,
+}
+
+/// Virtual window - stores everything that the `Window` stores,
+/// except for the OS window handle and the OpenGL context
+struct HeadlessWindow<T> {
+    /// Stylesheet of the window, sorted by specificity
+    css: Css,
+    /// Current state of the virtual window (size, DPI, mouse and keyboard state)
+    state: FullWindowState,
+    /// Events that will be processed on the next `step()`
+    pending_events: Vec<WindowEvent>,
+    /// DOMs of the last frame
+    ui_states: BTreeMap<DomId, UiState<T>>,
+    /// Styled DOMs of the last frame
+    ui_descriptions: BTreeMap<DomId, UiDescription<T>>,
+    /// Display list of the last frame
+    cached_display_list: CachedDisplayList,
+    /// Layouted rectangles of the last frame
+    layout_result: BTreeMap<DomId, LayoutResult>,
+    /// Nodes that overflow their parents and are able to scroll
+    scrolled_nodes: BTreeMap<DomId, ScrolledNodes>,
+    /// Current scroll positions of the scrollable nodes
+    scroll_states: ScrollStates,
+    epoch: Epoch,
+    pipeline_id: PipelineId,
+    /// Whether the window has to be re-layouted on the next frame, regardless of events
+    needs_relayout: bool,
+}
+
+/// High-level input event for a virtual window, see `HeadlessApp::send_input`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntheticEvent {
+    /// Moves the mouse cursor to the position (relative to the top left corner of the window)
+    MouseMove(LogicalPosition),
+    /// Moves the mouse cursor out of the window
+    MouseLeave,
+    /// Presses a mouse button down at the current cursor position
+    MouseDown(MouseButton),
+    /// Releases a mouse button at the current cursor position
+    MouseUp(MouseButton),
+    /// Presses a key down (does not generate any text input, see `Text`)
+    KeyDown(VirtualKeyCode),
+    /// Releases a key
+    KeyUp(VirtualKeyCode),
+    /// Types the text, one character per frame
+    Text(String),
+    /// Scrolls the nodes under the cursor by the given amount of pixels
+    /// (positive values scroll down / to the right)
+    Scroll { x: f32, y: f32 },
+    /// Drags a file over the window
+    HoverFile(PathBuf),
+    /// Drags a file out of the window again
+    HoverFileCancelled,
+    /// Drops a file onto the window at the current cursor position
+    DropFile(PathBuf),
+}
+
+impl<T> HeadlessWindow<T> {
+    /// Returns the scroll position of each scroll frame, for hit-testing
+    fn get_scroll_positions(&self) -> BTreeMap<ExternalScrollId, LayoutPoint> {
+        self.scroll_states
+            .0
+            .iter()
+            .map(|(scroll_id, scroll_state)| (*scroll_id, scroll_state.get()))
+            .collect()
+    }
+
+    /// Same as `WindowInternal::get_current_scroll_states`
+    fn get_current_scroll_states(&self) -> BTreeMap<DomId, BTreeMap<NodeId, ScrollPosition>> {
+        self.scrolled_nodes
+            .iter()
+            .filter_map(|(dom_id, scrolled_nodes)| {
+                let layout_result = self.layout_result.get(dom_id)?;
+                let ui_state = self.ui_states.get(dom_id)?;
+
+                let scroll_positions = scrolled_nodes
+                    .overflowing_nodes
+                    .iter()
+                    .filter_map(|(node_id, overflowing_node)| {
+                        let scroll_location = self
+                            .scroll_states
+                            .get_scroll_position(&overflowing_node.parent_external_scroll_id)?;
+                        let parent_node = ui_state.dom.arena.node_layout[*node_id]
+                            .parent
+                            .unwrap_or(NodeId::ZERO);
+                        let scroll_position = ScrollPosition {
+                            scroll_frame_rect: overflowing_node.child_rect,
+                            parent_rect: layout_result.rects[parent_node].to_layouted_rectangle(),
+                            scroll_location,
+                        };
+                        Some((*node_id, scroll_position))
+                    })
+                    .collect();
+
+                Some((dom_id.clone(), scroll_positions))
+            })
+            .collect()
+    }
+
+    /// Returns the hit-tested items under the current cursor, in back-to-front order
+    fn do_hit_test(&self) -> Option<Vec<HitTestItem>> {
+        let cursor = self.state.mouse_state.cursor_pos.get_position()?;
+        Some(self.cached_display_list.hit_test(
+            self.pipeline_id,
+            LayoutPoint::new(cursor.x, cursor.y),
+            &self.get_scroll_positions(),
+        ))
+    }
+}
+
+impl<T: Layout> HeadlessApp<T> {
+    /// Creates a new, empty headless application. Windows have to be added via `add_window`.
+    pub fn new(initial_data: T) -> Self {
+        Self {
+            app_state: AppState::new(initial_data),
+            layout_callback: T::layout,
+            windows: BTreeMap::new(),
+            render_api: HeadlessRenderApi::new(),
+        }
+    }
+}
+
+impl<T> HeadlessApp<T> {
+    /// Adds a virtual window with the given size / DPI (via `WindowState::size`) and
+    /// stylesheet. The window is layouted on the next call to `step()`.
+    pub fn add_window(&mut self, window_state: WindowState, mut css: Css) -> WindowId {
+        use gleam::gl::GlFns;
+
+        css.sort_by_specificity();
+
+        let window_id = WindowId::new();
+
+        // Null function pointers: the context can be handed out to callbacks,
+        // but every OpenGL call on it will panic (there is no GPU to call into).
+        let gl_context = unsafe { GlFns::load_with(|_| ::std::ptr::null()) };
+
+        let window_size = LayoutSize::new(
+            window_state.size.dimensions.width,
+            window_state.size.dimensions.height,
+        );
+
+        self.app_state.windows.insert(
+            window_id,
+            FakeWindow {
+                state: window_state.clone(),
+                default_callbacks: BTreeMap::new(),
+                gl_context,
+                cached_display_list: CachedDisplayList::empty(window_size),
+                scrolled_nodes: BTreeMap::new(),
+                layout_result: BTreeMap::new(),
+            },
+        );
+
+        self.windows.insert(
+            window_id,
+            HeadlessWindow {
+                css,
+                cached_display_list: CachedDisplayList::empty(window_size),
+                state: full_window_state_from_normal_state(window_state),
+                pending_events: Vec::new(),
+                ui_states: BTreeMap::new(),
+                ui_descriptions: BTreeMap::new(),
+                layout_result: BTreeMap::new(),
+                scrolled_nodes: BTreeMap::new(),
+                scroll_states: ScrollStates::new(),
+                epoch: Epoch(0),
+                pipeline_id: PipelineId::new(),
+                needs_relayout: true,
+            },
+        );
+
+        // Register the @font-face fonts of all windows, so that their families can be used
+        self.app_state.resources.add_font_faces(
+            self.windows
+                .values()
+                .flat_map(|window| window.css.get_font_faces()),
+        );
+
+        window_id
+    }
+
+    /// Removes a virtual window, returns whether the window existed
+    pub fn remove_window(&mut self, window_id: &WindowId) -> bool {
+        self.app_state.windows.remove(window_id);
+        self.windows.remove(window_id).is_some()
+    }
+
+    /// Returns the IDs of all currently open virtual windows
+    pub fn get_window_ids(&self) -> Vec<WindowId> {
+        self.windows.keys().cloned().collect()
+    }
+
+    /// Queues a window event for the given window, which will be processed
+    /// (hit-tested and dispatched to the callbacks) on the next `step()`
+    pub fn send_event(
+        &mut self,
+        window_id: &WindowId,
+        event: WindowEvent,
+    ) -> Result<(), RuntimeError> {
+        self.windows
+            .get_mut(window_id)
+            .ok_or(RuntimeError::WindowIndexError)?
+            .pending_events
+            .push(event);
+        Ok(())
+    }
+
+    /// Sends a high-level input event to the window. The event is translated into the same
+    /// window events that a real window would receive and every window event is processed
+    /// in its own frame (see `step()`), so the callbacks see the same sequence of window states
+    /// as with a real user. Returns whether any of the frames re-layouted the windows.
+    pub fn send_input(
+        &mut self,
+        window_id: &WindowId,
+        input: SyntheticEvent,
+    ) -> Result<bool, RuntimeError> {
+        let window_state = &self
+            .windows
+            .get(window_id)
+            .ok_or(RuntimeError::WindowIndexError)?
+            .state;
+
+        let mut relayouted = false;
+        for event in synthetic_event_to_window_events(input, window_state) {
+            self.send_event(window_id, event)?;
+            relayouted = self.step()? || relayouted;
+        }
+
+        Ok(relayouted)
+    }
+
+    /// Moves the mouse cursor to the position and clicks the left mouse button
+    pub fn click(
+        &mut self,
+        window_id: &WindowId,
+        position: LogicalPosition,
+    ) -> Result<bool, RuntimeError> {
+        let mut relayouted = self.send_input(window_id, SyntheticEvent::MouseMove(position))?;
+        relayouted |= self.send_input(window_id, SyntheticEvent::MouseDown(MouseButton::Left))?;
+        relayouted |= self.send_input(window_id, SyntheticEvent::MouseUp(MouseButton::Left))?;
+        Ok(relayouted)
+    }
+
+    /// Presses and releases a key
+    pub fn press_key(
+        &mut self,
+        window_id: &WindowId,
+        key: VirtualKeyCode,
+    ) -> Result<bool, RuntimeError> {
+        let relayouted = self.send_input(window_id, SyntheticEvent::KeyDown(key))?;
+        Ok(self.send_input(window_id, SyntheticEvent::KeyUp(key))? || relayouted)
+    }
+
+    /// Types the text into the window, one character per frame
+    pub fn type_text(&mut self, window_id: &WindowId, text: &str) -> Result<bool, RuntimeError> {
+        self.send_input(window_id, SyntheticEvent::Text(text.to_string()))
+    }
+
+    /// Resizes the virtual window to the new logical size - the
+    /// window is re-layouted on the next call to `step()`
+    pub fn set_window_size(
+        &mut self,
+        window_id: &WindowId,
+        size: LogicalSize,
+    ) -> Result<(), RuntimeError> {
+        let window = self
+            .windows
+            .get_mut(window_id)
+            .ok_or(RuntimeError::WindowIndexError)?;
+        window.state.size.dimensions = size;
+        window.needs_relayout = true;
+        self.sync_fake_window_state(window_id)
+    }
+
+    /// Sets the DPI factor of the virtual window - the window
+    /// is re-layouted on the next call to `step()`
+    pub fn set_hidpi_factor(
+        &mut self,
+        window_id: &WindowId,
+        hidpi_factor: f32,
+    ) -> Result<(), RuntimeError> {
+        let window = self
+            .windows
+            .get_mut(window_id)
+            .ok_or(RuntimeError::WindowIndexError)?;
+        window.state.size.hidpi_factor = hidpi_factor;
+        window.state.size.winit_hidpi_factor = hidpi_factor;
+        window.needs_relayout = true;
+        self.sync_fake_window_state(window_id)
+    }
+
+    /// Forces a re-layout of all windows on the next call to `step()`
+    pub fn request_relayout(&mut self) {
+        for window in self.windows.values_mut() {
+            window.needs_relayout = true;
+        }
+    }
+
+    /// Runs one frame: processes all pending events (hit-testing + calling the callbacks),
+    /// runs the timers, cleans up finished tasks and - if necessary - re-layouts all windows.
+    ///
+    /// If nothing else changed, but CSS transitions or animations are running, only the
+    /// animations are evaluated and the display lists are rebuilt (without restyling).
+    ///
+    /// Returns whether the windows have been re-layouted or re-rendered in this frame. Windows
+    /// that receive a close event are removed from the application.
+    pub fn step(&mut self) -> Result<bool, RuntimeError> {
+        let mut should_relayout = false;
+        let mut windows_to_close = Vec::new();
+
+        let window_ids = self.get_window_ids();
+
+        for window_id in &window_ids {
+            let (window_should_close, window_should_relayout) = self.process_events(window_id)?;
+            if window_should_close {
+                windows_to_close.push(*window_id);
+            }
+            should_relayout = should_relayout || window_should_relayout;
+        }
+
+        for window_id in &windows_to_close {
+            self.remove_window(window_id);
+        }
+
+        let should_redraw_timers = app_state_run_all_timers(&mut self.app_state);
+        let should_redraw_tasks = app_state_clean_up_finished_tasks(&mut self.app_state);
+        should_relayout =
+            should_relayout || should_redraw_timers == Redraw || should_redraw_tasks == Redraw;
+
+        if !should_relayout {
+            let mut is_animating = false;
+            for window_id in self.get_window_ids() {
+                is_animating = self.animate_window(&window_id)? || is_animating;
+            }
+            return Ok(is_animating);
+        }
+
+        // Same as the `App`: if there is a relayout necessary, re-layout *all* windows
+        for window_id in self.get_window_ids() {
+            self.relayout_window(&window_id)?;
+        }
+
+        {
+            use crate::app_resources::garbage_collect_fonts_and_images;
+            garbage_collect_fonts_and_images(&mut self.app_state.resources, &mut self.render_api);
+        }
+
+        Ok(true)
+    }
+
+    /// Calls `step()` until no more re-layouts happen or `max_frames` frames have been run.
+    /// Returns how many frames were run - note that infinite CSS animations never become idle.
+    pub fn run_until_idle(&mut self, max_frames: usize) -> Result<usize, RuntimeError> {
+        for frame in 0..max_frames {
+            if !self.step()? {
+                return Ok(frame + 1);
+            }
+        }
+        Ok(max_frames)
+    }
+
+    /// Returns the current state of the virtual window
+    pub fn get_window_state(&self, window_id: &WindowId) -> Option<WindowState> {
+        self.windows
+            .get(window_id)
+            .map(|w| full_window_state_to_window_state(&w.state))
+    }
+
+    /// Returns the display list of the last frame
+    pub fn get_display_list(&self, window_id: &WindowId) -> Option<&CachedDisplayList> {
+        self.windows.get(window_id).map(|w| &w.cached_display_list)
+    }
+
+    /// Returns the layouted rectangles of the last frame
+    pub fn get_layout_result(
+        &self,
+        window_id: &WindowId,
+    ) -> Option<&BTreeMap<DomId, LayoutResult>> {
+        self.windows.get(window_id).map(|w| &w.layout_result)
+    }
+
+    /// Returns the DOMs of the last frame
+    pub fn get_ui_state(&self, window_id: &WindowId) -> Option<&BTreeMap<DomId, UiState<T>>> {
+        self.windows.get(window_id).map(|w| &w.ui_states)
+    }
+
+    /// Returns the styled DOMs of the last frame
+    pub fn get_ui_description(
+        &self,
+        window_id: &WindowId,
+    ) -> Option<&BTreeMap<DomId, UiDescription<T>>> {
+        self.windows.get(window_id).map(|w| &w.ui_descriptions)
+    }
+
+    /// Returns a query over the styled and layouted DOMs of the last frame,
+    /// for finding nodes via CSS paths in test assertions
+    pub fn query(&self, window_id: &WindowId) -> Option<DomQuery<T>> {
+        self.windows
+            .get(window_id)
+            .map(|w| DomQuery::new(&w.ui_descriptions, &w.layout_result))
+    }
+
+    /// Returns the currently focused node, if any
+    pub fn get_focused_node(&self, window_id: &WindowId) -> Option<(DomId, NodeId)> {
+        self.windows.get(window_id)?.state.focused_node.clone()
+    }
+
+    /// Hit-tests the display list of the last frame at the given position
+    /// (in logical window coordinates), returns the items in back-to-front order
+    pub fn hit_test(&self, window_id: &WindowId, position: LogicalPosition) -> Vec<HitTestItem> {
+        match self.windows.get(window_id) {
+            Some(window) => window.cached_display_list.hit_test(
+                window.pipeline_id,
+                LayoutPoint::new(position.x, position.y),
+                &window.get_scroll_positions(),
+            ),
+            None => Vec::new(),
+        }
+    }
+
+    /// Renders the display list of the last frame into an image (on the CPU),
+    /// at the current DPI and scroll positions of the window
+    #[cfg(feature = "software_rendering")]
+    pub fn render_to_image(
+        &self,
+        window_id: &WindowId,
+    ) -> Option<crate::software_renderer::RgbaImage> {
+        use crate::software_renderer::{render_display_list, SoftwareRenderOptions};
+
+        let window = self.windows.get(window_id)?;
+        let options = SoftwareRenderOptions {
+            hidpi_factor: window.state.size.hidpi_factor,
+            scroll_positions: window.get_scroll_positions(),
+            ..SoftwareRenderOptions::default()
+        };
+
+        Some(render_display_list(
+            &window.cached_display_list,
+            &self.app_state.resources,
+            &options,
+        ))
+    }
+
+    /// Copies the window state of the headless window into the `FakeWindow`
+    /// (which is the window state that is visible to the callbacks)
+    fn sync_fake_window_state(&mut self, window_id: &WindowId) -> Result<(), RuntimeError> {
+        let window = self
+            .windows
+            .get(window_id)
+            .ok_or(RuntimeError::WindowIndexError)?;
+        self.app_state
+            .windows
+            .get_mut(window_id)
+            .ok_or(RuntimeError::WindowIndexError)?
+            .state = full_window_state_to_window_state(&window.state);
+        Ok(())
+    }
+
+    /// Same as `hit_test_single_window` in the `App`: updates the window state from the
+    /// pending events, calls the callbacks and scrolls the hit nodes.
+    ///
+    /// Returns (whether the window should close, whether the window should be re-layouted)
+    fn process_events(&mut self, window_id: &WindowId) -> Result<(bool, bool), RuntimeError> {
+        use azul_core::app::RuntimeError::*;
+
+        let window = self.windows.get_mut(window_id).ok_or(WindowIndexError)?;
+        let events = window.pending_events.drain(..).collect::<Vec<_>>();
+
+        let (frame_event_info, window_should_close) =
+            update_window_state(&mut window.state, &events);
+
+        // There is no OS window to query, so take the new size and DPI from the events
+        if let Some(new_size) = frame_event_info.new_window_size {
+            window.state.size.dimensions = new_size;
+        }
+
+        if let Some(dpi) = frame_event_info.new_dpi_factor {
+            window.state.size.winit_hidpi_factor = dpi;
+            window.state.size.hidpi_factor = dpi;
+        }
+
+        let mut should_relayout = window.needs_relayout
+            || frame_event_info.is_resize_event
+            || events
+                .iter()
+                .any(|event| event == &WindowEvent::RedrawRequested);
+
+        if events.is_empty() {
+            return Ok((window_should_close, should_relayout));
+        }
+
+        let scroll_states = window.get_current_scroll_states();
+        let mut scrolled_nodes = BTreeMap::new();
+        let mut new_focus_target: Option<FocusTarget> = None;
+        let hit_test_results = window.do_hit_test();
+
+        if frame_event_info.should_hittest {
+            for event in events.iter() {
+                self.app_state
+                    .windows
+                    .get_mut(window_id)
+                    .ok_or(WindowIndexError)?
+                    .state = full_window_state_to_window_state(&window.state);
+
+                let callback_result = call_callbacks(
+                    event,
+                    window_id,
+                    hit_test_results.as_ref(),
+                    &mut window.state,
+                    &scroll_states,
+                    &mut scrolled_nodes,
+                    &window.ui_states,
+                    &mut self.app_state,
+                )?;
+
+                if callback_result.should_update_screen == Redraw
+                    || callback_result.needs_redraw_anyways
+                    || callback_result.needs_relayout_anyways
+                {
+                    should_relayout = true;
+                }
+
+                if let Some(overwrites_focus) = callback_result.callbacks_overwrites_focus {
+                    new_focus_target = Some(overwrites_focus);
+                }
+            }
+        }
+
+        // Scroll nodes from input (mouse scroll) events
+        if let Some(hit_test_results) = &hit_test_results {
+            for scrolled_nodes in window.scrolled_nodes.values() {
+                let _ = update_scroll_state(
+                    &mut window.state,
+                    scrolled_nodes,
+                    &mut window.scroll_states,
+                    hit_test_results,
+                );
+            }
+        }
+
+        // Scroll nodes that were scrolled via the callbacks
+        for (dom_id, callback_scrolled_nodes) in scrolled_nodes {
+            if let Some(scrolled_nodes) = window.scrolled_nodes.get(&dom_id) {
+                for (scroll_node_id, scroll_position) in &callback_scrolled_nodes {
+                    if let Some(overflowing_node) =
+                        scrolled_nodes.overflowing_nodes.get(&scroll_node_id)
+                    {
+                        window
+                            .scroll_states
+                            .set_scroll_position(&overflowing_node, *scroll_position);
+                    }
+                }
+            }
+        }
+
+        window.state.pending_focus_target = new_focus_target;
+
+        // Apply the changes that the callbacks made to the window state
+        // (there is no OS window to synchronize with)
+        let fake_window_state = self
+            .app_state
+            .windows
+            .get(window_id)
+            .ok_or(WindowIndexError)?
+            .state
+            .clone();
+        if fake_window_state.size != window.state.size {
+            should_relayout = true;
+        }
+        let current_window_state = window.state.clone();
+        update_full_window_state(&mut window.state, &fake_window_state);
+        window.state.previous_window_state = Some(Box::new(current_window_state));
+
+        // Reset the scroll amount to 0 (for the next frame)
+        clear_scroll_state(&mut window.state);
+
+        self.app_state
+            .windows
+            .get_mut(window_id)
+            .ok_or(WindowIndexError)?
+            .state = full_window_state_to_window_state(&window.state);
+
+        Ok((window_should_close, should_relayout))
+    }
+
+    /// Same as `relayout_single_window` + `update_display_list` in the `App`, except
+    /// that the display list is stored instead of being sent to WebRender
+    fn relayout_window(&mut self, window_id: &WindowId) -> Result<(), RuntimeError> {
+        use crate::ui_state::ui_state_from_app_state;
+        use azul_core::{app::RuntimeError::*, diff::diff_arena};
+
+        // Call the Layout::layout() fn, get the DOM
+        let mut ui_state =
+            ui_state_from_app_state(&mut self.app_state, window_id, None, self.layout_callback)?;
+
+        // Since this is the root DOM of the window, set the DomID to 0
+        ui_state.dom_id = DomId::ROOT_ID;
+
+        let window = self.windows.get_mut(window_id).ok_or(WindowIndexError)?;
+
+        window.ui_states = BTreeMap::new();
+        window.ui_states.insert(ui_state.dom_id.clone(), ui_state);
+
+        // Style the DOM (is_mouse_down is necessary for styling :hover, :active + :focus nodes)
+        let is_mouse_down = window.state.mouse_state.mouse_down();
+        let now = Instant::now();
+        let css = &window.css;
+        let full_window_state = &mut window.state;
+        let previous_ui_descriptions = &window.ui_descriptions;
+        let previous_layout_result = &mut window.layout_result;
+
+        // Layouts of the last frame that are still valid for the current frame
+        let mut cached_layouts = BTreeMap::new();
+
+        let ui_descriptions = window
+            .ui_states
+            .iter_mut()
+            .map(|(dom_id, ui_state)| {
+                let hovered_nodes = full_window_state
+                    .hovered_nodes
+                    .get(&dom_id)
+                    .cloned()
+                    .unwrap_or_default();
+
+                // Diff against the DOM of the last frame, so that unchanged
+                // nodes can reuse their styles and the layout can be reused
+                let previous_frame = previous_ui_descriptions.get(dom_id).map(|previous| {
+                    let diff = diff_arena(
+                        &previous.ui_descr_arena,
+                        previous.ui_descr_root,
+                        &ui_state.dom.arena,
+                        ui_state.dom.root,
+                    );
+                    (previous, diff)
+                });
+
+                let mut ui_description = UiDescription::match_css_to_dom(
+                    ui_state,
+                    css,
+                    &mut full_window_state.focused_node,
+                    &mut full_window_state.pending_focus_target,
+                    &hovered_nodes,
+                    is_mouse_down,
+                    &full_window_state.size,
+                    full_window_state.theme,
+                    previous_frame
+                        .as_ref()
+                        .map(|(previous, diff)| (*previous, diff)),
+                );
+
+                ui_description.update_animations(
+                    previous_frame
+                        .as_ref()
+                        .map(|(previous, diff)| (*previous, diff)),
+                    css,
+                    now,
+                );
+
+                if let Some((previous, diff)) = &previous_frame {
+                    if ui_description.has_same_layout(previous, diff) {
+                        if let Some(layout_result) = previous_layout_result.remove(dom_id) {
+                            cached_layouts.insert(dom_id.clone(), layout_result);
+                        }
+                    }
+                }
+
+                (dom_id.clone(), ui_description)
+            })
+            .collect();
+        window.ui_descriptions = ui_descriptions;
+        window.needs_relayout = false;
+
+        self.update_display_list(window_id, cached_layouts)
+    }
+
+    /// Evaluates the running CSS transitions and animations of a window and rebuilds the
+    /// display list, without calling the `layout()` callback or restyling the DOM.
+    /// Returns whether any transition or animation was running.
+    fn animate_window(&mut self, window_id: &WindowId) -> Result<bool, RuntimeError> {
+        use azul_core::app::RuntimeError::*;
+
+        let window = self.windows.get_mut(window_id).ok_or(WindowIndexError)?;
+
+        if !window
+            .ui_descriptions
+            .values()
+            .any(|ui_description| ui_description.is_animating())
+        {
+            return Ok(false);
+        }
+
+        let now = Instant::now();
+        let mut cached_layouts = BTreeMap::new();
+
+        for (dom_id, ui_description) in window.ui_descriptions.iter_mut() {
+            // Only animated properties that can trigger a relayout invalidate the layout
+            if !ui_description.tick_animations(&window.css, now) {
+                if let Some(layout_result) = window.layout_result.remove(dom_id) {
+                    cached_layouts.insert(dom_id.clone(), layout_result);
+                }
+            }
+        }
+
+        self.update_display_list(window_id, cached_layouts)?;
+
+        Ok(true)
+    }
+
+    /// Builds the display list of the styled DOMs, reusing the `cached_layouts` of the
+    /// last frame, and stores it in the window
+    fn update_display_list(
+        &mut self,
+        window_id: &WindowId,
+        mut cached_layouts: BTreeMap<DomId, LayoutResult>,
+    ) -> Result<(), RuntimeError> {
+        use crate::{
+            app_resources::add_resources,
+            display_list::{
+                display_list_from_ui_description, display_list_to_cached_display_list,
+                CachedDisplayListResult,
+            },
+        };
+        use azul_core::app::RuntimeError::*;
+
+        let window = self.windows.get_mut(window_id).ok_or(WindowIndexError)?;
+
+        let fake_window = self
+            .app_state
+            .windows
+            .get_mut(window_id)
+            .ok_or(WindowIndexError)?;
+
+        for (dom_id, ui_state) in window.ui_states.iter() {
+            let ui_description = &window.ui_descriptions[dom_id];
+            let display_list =
+                display_list_from_ui_description(ui_description, ui_state, &window.state.size);
+
+            // Make sure unused scroll states are garbage collected.
+            window.scroll_states.remove_unused_scroll_states();
+
+            DomId::reset();
+
+            let CachedDisplayListResult {
+                cached_display_list,
+                scrollable_nodes,
+                image_resource_updates,
+                layout_result,
+            } = display_list_to_cached_display_list(
+                display_list,
+                &mut self.app_state.data,
+                window.epoch,
+                window.pipeline_id,
+                window.state.size,
+                &window.css,
+                fake_window,
+                &mut self.app_state.resources,
+                &mut self.render_api,
+                cached_layouts.remove(dom_id),
+            );
+
+            for (_dom_id, image_resource_updates) in image_resource_updates {
+                add_resources(
+                    &mut self.app_state.resources,
+                    &mut self.render_api,
+                    Vec::new(),
+                    image_resource_updates,
+                );
+            }
+
+            window.layout_result = layout_result;
+            window.scrolled_nodes = scrollable_nodes;
+            window.cached_display_list = cached_display_list;
+        }
+
+        fake_window.layout_result = window.layout_result.clone();
+        fake_window.scrolled_nodes = window.scrolled_nodes.clone();
+        fake_window.cached_display_list = window.cached_display_list.clone();
+        fake_window.state = full_window_state_to_window_state(&window.state);
+
+        window.epoch = increase_epoch(window.epoch);
+
+        Ok(())
+    }
+}
+
+/// Translates a `SyntheticEvent` into the window events that winit would send for it
+fn synthetic_event_to_window_events(
+    event: SyntheticEvent,
+    window_state: &FullWindowState,
+) -> Vec<WindowEvent> {
+    use crate::window_state::winit_translate::{
+        translate_logical_position, translate_virtual_keycode,
+    };
+    use glium::glutin::{
+        dpi::LogicalPosition as WinitLogicalPosition,
+        event::{
+            DeviceId, ElementState, KeyboardInput, ModifiersState, MouseScrollDelta, TouchPhase,
+        },
+    };
+
+    // There is no real input device that the events originate from
+    let device_id = unsafe { DeviceId::dummy() };
+
+    let keyboard_state = &window_state.keyboard_state;
+    let modifiers = ModifiersState {
+        shift: keyboard_state.shift_down,
+        ctrl: keyboard_state.ctrl_down,
+        alt: keyboard_state.alt_down,
+        logo: keyboard_state.super_down,
+    };
+
+    let keyboard_input = |key: VirtualKeyCode, state: ElementState| {
+        // winit reports the modifier state after the key has been pressed / released
+        let mut modifiers = modifiers;
+        let is_down = state == ElementState::Pressed;
+        match key {
+            VirtualKeyCode::LShift | VirtualKeyCode::RShift => modifiers.shift = is_down,
+            VirtualKeyCode::LControl | VirtualKeyCode::RControl => modifiers.ctrl = is_down,
+            VirtualKeyCode::LAlt | VirtualKeyCode::RAlt => modifiers.alt = is_down,
+            VirtualKeyCode::LWin | VirtualKeyCode::RWin => modifiers.logo = is_down,
+            _ => {}
+        }
+        WindowEvent::KeyboardInput {
+            device_id,
+            input: KeyboardInput {
+                scancode: 0,
+                state,
+                virtual_keycode: Some(translate_virtual_keycode(key)),
+                modifiers,
+            },
+        }
+    };
+
+    match event {
+        SyntheticEvent::MouseMove(position) => {
+            let cursor_moved = WindowEvent::CursorMoved {
+                device_id,
+                position: translate_logical_position(position),
+                modifiers,
+            };
+            if window_state.mouse_state.cursor_pos.get_position().is_some() {
+                vec![cursor_moved]
+            } else {
+                vec![WindowEvent::CursorEntered { device_id }, cursor_moved]
+            }
+        }
+        SyntheticEvent::MouseLeave => vec![WindowEvent::CursorLeft { device_id }],
+        SyntheticEvent::MouseDown(button) => vec![WindowEvent::MouseInput {
+            device_id,
+            state: ElementState::Pressed,
+            button,
+            modifiers,
+        }],
+        SyntheticEvent::MouseUp(button) => vec![WindowEvent::MouseInput {
+            device_id,
+            state: ElementState::Released,
+            button,
+            modifiers,
+        }],
+        SyntheticEvent::KeyDown(key) => vec![keyboard_input(key, ElementState::Pressed)],
+        SyntheticEvent::KeyUp(key) => vec![keyboard_input(key, ElementState::Released)],
+        SyntheticEvent::Text(text) => text.chars().map(WindowEvent::ReceivedCharacter).collect(),
+        SyntheticEvent::Scroll { x, y } => vec![WindowEvent::MouseWheel {
+            device_id,
+            // the window state inverts the direction of the mouse wheel again
+            delta: MouseScrollDelta::PixelDelta(WinitLogicalPosition::new(-x as f64, -y as f64)),
+            phase: TouchPhase::Moved,
+            modifiers,
+        }],
+        SyntheticEvent::HoverFile(path) => vec![WindowEvent::HoveredFile(path)],
+        SyntheticEvent::HoverFileCancelled => vec![WindowEvent::HoveredFileCancelled],
+        SyntheticEvent::DropFile(path) => vec![WindowEvent::DroppedFile(path)],
+    }
+}
+
+#[test]
+fn test_headless_app_relayout() {
+    use crate::{callbacks::LayoutInfo, dom::Dom};
+
+    struct Data {}
+
+    impl Layout for Data {
+        fn layout(&self, _: LayoutInfo<Self>) -> Dom<Self> {
+            Dom::div()
+                .with_child(Dom::div().with_id("first"))
+                .with_child(Dom::div().with_id("second"))
+        }
+    }
+
+    let mut window_state = WindowState::default();
+    window_state.size.dimensions = LogicalSize::new(400.0, 300.0);
+
+    let mut app = HeadlessApp::new(Data {});
+    let window_id = app.add_window(window_state, Css::new());
+
+    // First frame is always layouted, second frame has nothing to do
+    assert!(app.step().unwrap());
+    assert!(!app.step().unwrap());
+    assert_eq!(
+        app.get_layout_result(&window_id).unwrap()[&DomId::ROOT_ID]
+            .rects
+            .len(),
+        3
+    );
+    assert_eq!(
+        app.get_ui_state(&window_id).unwrap()[&DomId::ROOT_ID]
+            .dom
+            .arena
+            .node_layout
+            .len(),
+        3
+    );
+
+    // Resizing the virtual window triggers a relayout
+    app.set_window_size(&window_id, LogicalSize::new(800.0, 600.0))
+        .unwrap();
+    assert!(app.step().unwrap());
+    assert_eq!(
+        app.get_window_state(&window_id).unwrap().size.dimensions,
+        LogicalSize::new(800.0, 600.0)
+    );
+    assert_eq!(
+        app.app_state.windows[&window_id].state.size.dimensions,
+        LogicalSize::new(800.0, 600.0)
+    );
+}
+
+#[test]
+fn test_headless_app_synthetic_input() {
+    use crate::{
+        callbacks::{CallbackInfo, LayoutInfo},
+        dom::{Dom, EventFilter, HoverEventFilter, On},
+    };
+
+    struct Data {
+        clicks: usize,
+        text: String,
+    }
+
+    impl Layout for Data {
+        fn layout(&self, _: LayoutInfo<Self>) -> Dom<Self> {
+            Dom::div()
+                .with_callback(On::MouseUp, |info: CallbackInfo<Self>| {
+                    info.state.data.clicks += 1;
+                    Redraw
+                })
+                .with_callback(
+                    EventFilter::Hover(HoverEventFilter::TextInput),
+                    |info: CallbackInfo<Self>| {
+                        let current_char = info.get_keyboard_state().current_char;
+                        info.state.data.text.extend(current_char);
+                        Redraw
+                    },
+                )
+        }
+    }
+
+    let mut window_state = WindowState::default();
+    window_state.size.dimensions = LogicalSize::new(400.0, 300.0);
+
+    let mut app = HeadlessApp::new(Data {
+        clicks: 0,
+        text: String::new(),
+    });
+    let window_id = app.add_window(window_state, Css::new());
+    app.step().unwrap();
+
+    assert!(app
+        .click(&window_id, LogicalPosition::new(10.0, 10.0))
+        .unwrap());
+    assert_eq!(app.app_state.data.clicks, 1);
+
+    app.type_text(&window_id, "abc").unwrap();
+    assert_eq!(app.app_state.data.text, "abc");
+
+    // The modifier state is tracked across events
+    app.send_input(&window_id, SyntheticEvent::KeyDown(VirtualKeyCode::LShift))
+        .unwrap();
+    assert!(
+        app.get_window_state(&window_id)
+            .unwrap()
+            .keyboard_state
+            .shift_down
+    );
+    app.send_input(&window_id, SyntheticEvent::KeyUp(VirtualKeyCode::LShift))
+        .unwrap();
+    assert!(
+        !app.get_window_state(&window_id)
+            .unwrap()
+            .keyboard_state
+            .shift_down
+    );
+
+    // Events outside of the window don't hit any node
+    app.send_input(&window_id, SyntheticEvent::MouseLeave)
+        .unwrap();
+    app.send_input(&window_id, SyntheticEvent::MouseUp(MouseButton::Left))
+        .unwrap();
+    assert_eq!(app.app_state.data.clicks, 1);
+}
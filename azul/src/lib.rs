@@ -145,7 +145,7 @@ pub mod widgets {
     pub mod svg {
 
         use azul_core::ui_solver::ResolvedTextLayoutOptions;
-        use azul_css::{LayoutPoint, StyleTextAlignmentHorz};
+        use azul_css::{LayoutPoint, StyleTextAlignmentHorz, StyleTextDirection};
         pub use azul_widgets::svg::*;
 
         pub fn svg_text_layout_from_str(
@@ -159,11 +159,11 @@ pub mod widgets {
 
             text_layout_options.font_size_px = SVG_FAKE_FONT_SIZE;
             let words = text_layout::split_text_into_words(text);
-            let scaled_words = text_layout::words_to_scaled_words(
+            let scaled_words = text_layout::words_to_scaled_words_with_fallback(
                 &words,
-                font_bytes,
-                font_index,
-                SVG_FAKE_FONT_SIZE,
+                &[(font_bytes, font_index)],
+                &text_layout_options,
+                StyleTextDirection::Ltr,
             );
             let word_positions =
                 text_layout::position_words(&words, &scaled_words, &text_layout_options);
@@ -209,6 +209,13 @@ pub use azul_core::diff;
 pub use azul_core::dom;
 /// OpenGL helper functions, necessary to create OpenGL textures, manage contexts, etc.
 pub use azul_core::gl;
+/// Headless application runner - runs the layout pipeline without a window or GPU (for testing)
+pub mod headless;
+/// Queries over the styled and layouted UI (for testing)
+pub use azul_core::query;
+/// CPU rasterizer for display lists (screenshot tests, thumbnails, rendering without a GPU)
+#[cfg(any(feature = "software_rendering", test))]
+pub mod software_renderer;
 /// Handles text layout (modularized, can be used as a standalone module)
 pub mod text_layout;
 /// Main `Layout` trait definition + convenience traits for `Arc<Mutex<T>>`
@@ -250,9 +257,9 @@ pub use azul_core::{FastHashMap, FastHashSet};
 pub mod resources {
     // re-export everything *except* the AppResources (which are exported under the "app" module)
     pub use crate::app_resources::{
-        font_source_get_bytes, image_source_get_bytes, CssFontId, CssImageId, FontId,
-        FontReloadError, FontSource, ImageId, ImageReloadError, ImageSource, LoadedFont, RawImage,
-        RawImageFormat, TextCache, TextId,
+        font_source_get_bytes, image_source_get_bytes, CssFontId, CssImageId, FontFaceStyle,
+        FontId, FontReloadError, FontSource, FontSynthesis, ImageId, ImageReloadError, ImageSource,
+        LoadedFont, RawImage, RawImageFormat, TextCache, TextId,
     };
 }
 
@@ -262,7 +269,7 @@ pub mod prelude {
     pub use crate::callbacks::*;
     pub use crate::dom::{
         Dom, DomHash, DomString, EventFilter, FocusEventFilter, HoverEventFilter, NodeData,
-        NodeType, NotEventFilter, On, TabIndex, WindowEventFilter,
+        NodeType, NotEventFilter, On, TabIndex, TextSpan, WindowEventFilter,
     };
     pub use crate::gl::{
         FragmentShaderCompileError, GLuint, GlApiVersion, GlShader, GlShaderCreateError,
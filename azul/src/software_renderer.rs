@@ -0,0 +1,2272 @@
+//! CPU-only rasterizer for the `CachedDisplayList`, for rendering a UI without OpenGL
+//!
+//! The output of the rasterizer is a `RgbaImage`, which can be compared against
+//! a reference image (for screenshot tests) or encoded into a PNG file.
+//! Rectangles, borders (incl. border radii), box shadows, linear / radial gradients,
+//! images, glyph runs and text decoration lines are supported. Glyphs are rasterized
+//! from the font outlines of the fonts that are currently loaded in the `AppResources`,
+//! so the display list should be rendered before the fonts are garbage-collected again.
+//!
+//! The result is not pixel-identical to the WebRender output (anti-aliasing,
+//! glyph hinting and blurring differ slightly), but close enough for
+//! regression testing and thumbnail generation. `opacity` is supported, but CSS
+//! transforms and the other `filter` functions are not supported yet, such frames
+//! are rendered without them.
+//! Text shadows are drawn without blur and variable fonts are rasterized with the
+//! default values of their variation axes (`font-variation-settings` is ignored).
+
+use azul_core::{
+    app_resources::{AppResources, FontInstanceKey, FontKey, ImageKey, RawImageFormat},
+    display_list::{
+        CachedDisplayList, DisplayListFrame, DisplayListMsg, FrameFilter, FramePosition,
+        GlyphInstance, ImageRendering, LayoutRectContent, LineStyle, RectBackground,
+        StyleBorderColors, StyleBorderRadius, StyleBorderStyles, StyleBorderWidths, StyleBoxShadow,
+        TextShadow,
+    },
+    ui_solver::ExternalScrollId,
+};
+use azul_css::{
+    BorderStyle, BoxShadowClipMode, BoxShadowPreDisplayItem, ColorF, ColorU, CssPropertyValue,
+    ExtendMode, GradientStopPre, LayoutPoint, LayoutRect, LayoutSize, LinearGradient, PixelValue,
+    RadialGradient, Shape, StyleBackgroundPosition, StyleBackgroundRepeat, StyleBackgroundSize,
+};
+use stb_truetype::FontInfo;
+use std::{collections::BTreeMap, io::Error as IoError, mem, path::Path};
+
+use crate::FastHashMap;
+
+/// Rendered image, RGBA8 pixels (not premultiplied), row by row, top to bottom
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Returns the color of the pixel at (x, y) or `None` if the pixel is out of bounds
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<ColorU> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let start = (y * self.width + x) * 4;
+        Some(ColorU {
+            r: self.pixels[start],
+            g: self.pixels[start + 1],
+            b: self.pixels[start + 2],
+            a: self.pixels[start + 3],
+        })
+    }
+
+    /// Encodes the image as a PNG file
+    pub fn encode_png(&self) -> Result<Vec<u8>, IoError> {
+        use image::{png::PNGEncoder, ColorType};
+
+        let mut png_bytes = Vec::new();
+        PNGEncoder::new(&mut png_bytes).encode(
+            &self.pixels,
+            self.width as u32,
+            self.height as u32,
+            ColorType::RGBA(8),
+        )?;
+        Ok(png_bytes)
+    }
+
+    /// Encodes the image as a PNG file and writes it to the given path
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), IoError> {
+        let png_bytes = self.encode_png()?;
+        std::fs::write(path, png_bytes)
+    }
+}
+
+/// Options for rendering a display list
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftwareRenderOptions {
+    /// Scale factor from logical to physical pixels (the output image has
+    /// a size of `display_list_size * hidpi_factor`)
+    pub hidpi_factor: f32,
+    /// Color that the image is cleared with before rendering
+    pub background_color: ColorU,
+    /// Current scroll positions of the scroll frames in the display list
+    pub scroll_positions: BTreeMap<ExternalScrollId, LayoutPoint>,
+}
+
+impl Default for SoftwareRenderOptions {
+    fn default() -> Self {
+        Self {
+            hidpi_factor: 1.0,
+            background_color: ColorU::WHITE,
+            scroll_positions: BTreeMap::new(),
+        }
+    }
+}
+
+/// Renders the display list into an RGBA image. Fonts and images are looked up
+/// in the `app_resources` - missing fonts or images are skipped.
+pub fn render_display_list(
+    display_list: &CachedDisplayList,
+    app_resources: &AppResources,
+    options: &SoftwareRenderOptions,
+) -> RgbaImage {
+    let size = display_list.root.get_size();
+    let width = (size.width * options.hidpi_factor).ceil().max(0.0) as usize;
+    let height = (size.height * options.hidpi_factor).ceil().max(0.0) as usize;
+
+    let mut renderer = Renderer {
+        canvas: Canvas::new(width, height, options.background_color),
+        app_resources,
+        scale: options.hidpi_factor,
+        scroll_positions: &options.scroll_positions,
+        window_rect: LayoutRect::new(LayoutPoint::zero(), size),
+        fonts: FastHashMap::default(),
+        images: FastHashMap::default(),
+    };
+
+    let window_rect = renderer.window_rect;
+    renderer.render_msg(&display_list.root, LayoutPoint::zero(), &[], window_rect);
+    renderer.canvas.into_rgba_image()
+}
+
+/// Moves a rect in layout coordinates by the negative scroll offset
+fn scrolled_rect(rect: LayoutRect, scroll_offset: LayoutPoint) -> LayoutRect {
+    LayoutRect::new(
+        LayoutPoint::new(
+            rect.origin.x - scroll_offset.x,
+            rect.origin.y - scroll_offset.y,
+        ),
+        rect.size,
+    )
+}
+
+/// Rectangle in physical pixels
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Rect {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl Rect {
+    fn new(x0: f32, y0: f32, x1: f32, y1: f32) -> Self {
+        Self { x0, y0, x1, y1 }
+    }
+
+    fn width(&self) -> f32 {
+        self.x1 - self.x0
+    }
+
+    fn height(&self) -> f32 {
+        self.y1 - self.y0
+    }
+
+    fn is_empty(&self) -> bool {
+        self.x1 <= self.x0 || self.y1 <= self.y0
+    }
+
+    fn intersect(&self, other: &Rect) -> Rect {
+        Rect::new(
+            self.x0.max(other.x0),
+            self.y0.max(other.y0),
+            self.x1.min(other.x1),
+            self.y1.min(other.y1),
+        )
+    }
+
+    fn inflate(&self, x: f32, y: f32) -> Rect {
+        Rect::new(self.x0 - x, self.y0 - y, self.x1 + x, self.y1 + y)
+    }
+
+    fn translate(&self, x: f32, y: f32) -> Rect {
+        Rect::new(self.x0 + x, self.y0 + y, self.x1 + x, self.y1 + y)
+    }
+}
+
+/// Rectangle with (elliptical) rounded corners, radii in the order
+/// top-left, top-right, bottom-right, bottom-left
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct RoundedRect {
+    rect: Rect,
+    radii: [(f32, f32); 4],
+}
+
+impl RoundedRect {
+    fn new(rect: Rect, radii: [(f32, f32); 4]) -> Self {
+        // If the radii of two adjacent corners are larger than the side of the rect,
+        // all radii are scaled down proportionally (see CSS Backgrounds 3, 5.5)
+        let [tl, tr, br, bl] = radii;
+        let mut factor: f32 = 1.0;
+        for (sum, side) in &[
+            (tl.0 + tr.0, rect.width()),
+            (bl.0 + br.0, rect.width()),
+            (tl.1 + bl.1, rect.height()),
+            (tr.1 + br.1, rect.height()),
+        ] {
+            if *sum > *side && *sum > 0.0 {
+                factor = factor.min(side.max(0.0) / sum);
+            }
+        }
+        let scale = |r: (f32, f32)| (r.0 * factor, r.1 * factor);
+        Self {
+            rect,
+            radii: [scale(tl), scale(tr), scale(br), scale(bl)],
+        }
+    }
+
+    fn sharp(rect: Rect) -> Self {
+        Self {
+            rect,
+            radii: [(0.0, 0.0); 4],
+        }
+    }
+
+    /// Shrinks the rect by the given side widths, the radii shrink accordingly
+    fn inset(&self, top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        let [tl, tr, br, bl] = self.radii;
+        Self {
+            rect: Rect::new(
+                self.rect.x0 + left,
+                self.rect.y0 + top,
+                self.rect.x1 - right,
+                self.rect.y1 - bottom,
+            ),
+            radii: [
+                ((tl.0 - left).max(0.0), (tl.1 - top).max(0.0)),
+                ((tr.0 - right).max(0.0), (tr.1 - top).max(0.0)),
+                ((br.0 - right).max(0.0), (br.1 - bottom).max(0.0)),
+                ((bl.0 - left).max(0.0), (bl.1 - bottom).max(0.0)),
+            ],
+        }
+    }
+
+    /// Grows the rect by the spread of a box shadow (negative spread shrinks the rect)
+    fn spread(&self, spread: f32) -> Self {
+        let spread_radius = |r: (f32, f32)| {
+            if r.0 <= 0.0 || r.1 <= 0.0 {
+                r
+            } else {
+                ((r.0 + spread).max(0.0), (r.1 + spread).max(0.0))
+            }
+        };
+        let rect = self.rect.inflate(spread, spread);
+        Self {
+            rect: Rect::new(rect.x0, rect.y0, rect.x1.max(rect.x0), rect.y1.max(rect.y0)),
+            radii: [
+                spread_radius(self.radii[0]),
+                spread_radius(self.radii[1]),
+                spread_radius(self.radii[2]),
+                spread_radius(self.radii[3]),
+            ],
+        }
+    }
+
+    /// Approximate signed distance from (x, y) to the outline, negative inside
+    fn signed_distance(&self, x: f32, y: f32) -> f32 {
+        let cx = (self.rect.x0 + self.rect.x1) / 2.0;
+        let cy = (self.rect.y0 + self.rect.y1) / 2.0;
+        let half_width = self.rect.width() / 2.0;
+        let half_height = self.rect.height() / 2.0;
+
+        let (rx, ry) = match (x < cx, y < cy) {
+            (true, true) => self.radii[0],
+            (false, true) => self.radii[1],
+            (false, false) => self.radii[2],
+            (true, false) => self.radii[3],
+        };
+
+        let qx = (x - cx).abs() - half_width;
+        let qy = (y - cy).abs() - half_height;
+
+        if rx > 0.0 && ry > 0.0 && qx > -rx && qy > -ry {
+            // inside the corner box: distance to the ellipse of the corner
+            let dx = (qx + rx) / rx;
+            let dy = (qy + ry) / ry;
+            return ((dx * dx + dy * dy).sqrt() - 1.0) * rx.min(ry);
+        }
+
+        let outside_x = qx.max(0.0);
+        let outside_y = qy.max(0.0);
+        (outside_x * outside_x + outside_y * outside_y).sqrt() + qx.max(qy).min(0.0)
+    }
+
+    /// Anti-aliased coverage of the pixel centered at (x, y)
+    fn coverage(&self, x: f32, y: f32) -> f32 {
+        clamp_unit(0.5 - self.signed_distance(x, y))
+    }
+
+    /// Coverage of the pixel centered at (x, y), blurred with a gaussian of the given sigma
+    fn blurred_coverage(&self, x: f32, y: f32, sigma: f32) -> f32 {
+        if sigma <= 0.0 {
+            self.coverage(x, y)
+        } else {
+            0.5 * (1.0 - erf(self.signed_distance(x, y) / (sigma * std::f32::consts::SQRT_2)))
+        }
+    }
+}
+
+/// Image, decoded into premultiplied colors
+struct DecodedImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<ColorF>,
+}
+
+impl DecodedImage {
+    fn get(&self, x: isize, y: isize) -> ColorF {
+        let x = x.max(0).min(self.width as isize - 1) as usize;
+        let y = y.max(0).min(self.height as isize - 1) as usize;
+        self.pixels[y * self.width + x]
+    }
+
+    /// Samples the image at the given position (in image pixels)
+    fn sample(&self, x: f32, y: f32, image_rendering: ImageRendering) -> ColorF {
+        match image_rendering {
+            ImageRendering::Pixelated | ImageRendering::CrispEdges => {
+                self.get(x.floor() as isize, y.floor() as isize)
+            }
+            ImageRendering::Auto => {
+                // bilinear filtering
+                let x = x - 0.5;
+                let y = y - 0.5;
+                let (x0, y0) = (x.floor(), y.floor());
+                let (fx, fy) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as isize, y0 as isize);
+                let top = mix(self.get(x0, y0), self.get(x0 + 1, y0), fx);
+                let bottom = mix(self.get(x0, y0 + 1), self.get(x0 + 1, y0 + 1), fx);
+                mix(top, bottom, fy)
+            }
+        }
+    }
+}
+
+/// What to fill a (tiled) area with
+enum Paint<'a> {
+    LinearGradient {
+        start: LayoutPoint,
+        end: LayoutPoint,
+        stops: Vec<(f32, ColorF)>,
+        extend_mode: ExtendMode,
+    },
+    RadialGradient {
+        center: LayoutPoint,
+        radius: LayoutSize,
+        stops: Vec<(f32, ColorF)>,
+        extend_mode: ExtendMode,
+    },
+    Image {
+        image: &'a DecodedImage,
+        image_rendering: ImageRendering,
+    },
+}
+
+impl<'a> Paint<'a> {
+    /// Returns the premultiplied color at the position (relative to the tile origin)
+    fn color_at(&self, x: f32, y: f32, tile_size: LayoutSize) -> ColorF {
+        match self {
+            Paint::LinearGradient {
+                start,
+                end,
+                stops,
+                extend_mode,
+            } => {
+                let (dx, dy) = (end.x - start.x, end.y - start.y);
+                let length_squared = dx * dx + dy * dy;
+                let t = if length_squared <= 0.0 {
+                    0.0
+                } else {
+                    ((x - start.x) * dx + (y - start.y) * dy) / length_squared
+                };
+                gradient_color(stops, t, *extend_mode)
+            }
+            Paint::RadialGradient {
+                center,
+                radius,
+                stops,
+                extend_mode,
+            } => {
+                if radius.width <= 0.0 || radius.height <= 0.0 {
+                    return gradient_color(stops, 1.0, *extend_mode);
+                }
+                let dx = (x - center.x) / radius.width;
+                let dy = (y - center.y) / radius.height;
+                gradient_color(stops, (dx * dx + dy * dy).sqrt(), *extend_mode)
+            }
+            Paint::Image {
+                image,
+                image_rendering,
+            } => {
+                if tile_size.width <= 0.0 || tile_size.height <= 0.0 {
+                    return ColorF::TRANSPARENT;
+                }
+                image.sample(
+                    x / tile_size.width * image.width as f32,
+                    y / tile_size.height * image.height as f32,
+                    *image_rendering,
+                )
+            }
+        }
+    }
+}
+
+/// Target of the rendering, stores premultiplied colors
+struct Canvas {
+    width: usize,
+    height: usize,
+    pixels: Vec<ColorF>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize, background_color: ColorU) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![premultiply(background_color.into()); width * height],
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        Rect::new(0.0, 0.0, self.width as f32, self.height as f32)
+    }
+
+    /// Blends a premultiplied color onto the pixel, using "source-over" compositing
+    fn blend(&mut self, x: usize, y: usize, color: ColorF, coverage: f32) {
+        let src_alpha = color.a * coverage;
+        if src_alpha <= 0.0 {
+            return;
+        }
+        let dst = &mut self.pixels[y * self.width + x];
+        let inv = 1.0 - src_alpha;
+        dst.r = color.r * coverage + dst.r * inv;
+        dst.g = color.g * coverage + dst.g * inv;
+        dst.b = color.b * coverage + dst.b * inv;
+        dst.a = src_alpha + dst.a * inv;
+    }
+
+    /// Calls the `shader` for every pixel in the `bounds` that isn't clipped away and
+    /// blends the returned (premultiplied color, coverage) onto the canvas
+    fn fill<F: FnMut(f32, f32) -> Option<(ColorF, f32)>>(
+        &mut self,
+        bounds: Rect,
+        clips: &[RoundedRect],
+        mut shader: F,
+    ) {
+        let bounds = clips
+            .iter()
+            .fold(bounds.intersect(&self.bounds()), |b, clip| {
+                b.intersect(&clip.rect)
+            });
+
+        if bounds.is_empty() {
+            return;
+        }
+
+        let (x_start, x_end) = (bounds.x0.floor() as usize, bounds.x1.ceil() as usize);
+        let (y_start, y_end) = (bounds.y0.floor() as usize, bounds.y1.ceil() as usize);
+
+        for y in y_start..y_end.min(self.height) {
+            for x in x_start..x_end.min(self.width) {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let clip_coverage = clips
+                    .iter()
+                    .fold(1.0, |coverage, clip| coverage * clip.coverage(px, py));
+                if clip_coverage <= 0.0 {
+                    continue;
+                }
+                if let Some((color, coverage)) = shader(px, py) {
+                    self.blend(x, y, color, coverage * clip_coverage);
+                }
+            }
+        }
+    }
+
+    /// Blends the (premultiplied) pixels of a layer of the same size onto the canvas
+    fn composite(&mut self, layer: &Canvas, opacity: f32) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.blend(x, y, layer.pixels[y * self.width + x], opacity);
+            }
+        }
+    }
+
+    fn into_rgba_image(self) -> RgbaImage {
+        let mut pixels = Vec::with_capacity(self.width * self.height * 4);
+        for color in self.pixels {
+            let (r, g, b) = if color.a > 0.0 {
+                (color.r / color.a, color.g / color.a, color.b / color.a)
+            } else {
+                (0.0, 0.0, 0.0)
+            };
+            pixels.extend_from_slice(&[to_u8(r), to_u8(g), to_u8(b), to_u8(color.a)]);
+        }
+        RgbaImage {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
+
+struct Renderer<'a> {
+    canvas: Canvas,
+    app_resources: &'a AppResources,
+    /// HiDPI factor
+    scale: f32,
+    scroll_positions: &'a BTreeMap<ExternalScrollId, LayoutPoint>,
+    /// Viewport of fixed frames, in logical pixels
+    window_rect: LayoutRect,
+    /// Parsed fonts, `None` if the font couldn't be parsed
+    fonts: FastHashMap<FontKey, Option<FontInfo<&'a [u8]>>>,
+    /// Decoded images, `None` if the image couldn't be decoded
+    images: FastHashMap<ImageKey, Option<DecodedImage>>,
+}
+
+impl<'a> Renderer<'a> {
+    /// Converts a rect in layout coordinates into physical pixels, subtracting the scroll offset
+    fn to_device_rect(&self, rect: &LayoutRect, scroll_offset: LayoutPoint) -> Rect {
+        let x0 = (rect.origin.x - scroll_offset.x) * self.scale;
+        let y0 = (rect.origin.y - scroll_offset.y) * self.scale;
+        Rect::new(
+            x0,
+            y0,
+            x0 + rect.size.width * self.scale,
+            y0 + rect.size.height * self.scale,
+        )
+    }
+
+    fn get_rounded_rect(
+        &self,
+        rect: &LayoutRect,
+        border_radius: &StyleBorderRadius,
+        scroll_offset: LayoutPoint,
+    ) -> RoundedRect {
+        // percentages are resolved against the width (horizontal) / height (vertical)
+        let size = rect.size;
+        let scale = self.scale;
+        let get_radius = |radius: Option<PixelValue>| {
+            let radius = radius.unwrap_or_default();
+            (
+                radius.to_pixels(size.width) * scale,
+                radius.to_pixels(size.height) * scale,
+            )
+        };
+
+        RoundedRect::new(
+            self.to_device_rect(rect, scroll_offset),
+            [
+                get_radius(
+                    border_radius
+                        .top_left
+                        .and_then(|r| r.get_property_or_default())
+                        .map(|r| r.0),
+                ),
+                get_radius(
+                    border_radius
+                        .top_right
+                        .and_then(|r| r.get_property_or_default())
+                        .map(|r| r.0),
+                ),
+                get_radius(
+                    border_radius
+                        .bottom_right
+                        .and_then(|r| r.get_property_or_default())
+                        .map(|r| r.0),
+                ),
+                get_radius(
+                    border_radius
+                        .bottom_left
+                        .and_then(|r| r.get_property_or_default())
+                        .map(|r| r.0),
+                ),
+            ],
+        )
+    }
+
+    /// `viewport` is the rect of the nearest scroll frame (or the window) in logical
+    /// window coordinates, which sticky frames stick to
+    fn render_msg(
+        &mut self,
+        msg: &DisplayListMsg,
+        scroll_offset: LayoutPoint,
+        clips: &[RoundedRect],
+        viewport: LayoutRect,
+    ) {
+        // Fixed frames aren't scrolled or clipped by their parents, sticky frames
+        // are moved so that they stick to the edges of the nearest scroll frame
+        let (scroll_offset, clips, viewport) = match msg.get_frame().position {
+            FramePosition::Static => (scroll_offset, clips, viewport),
+            FramePosition::Fixed => (LayoutPoint::zero(), &[][..], self.window_rect),
+            FramePosition::Sticky(sticky) => {
+                let offset =
+                    sticky.get_offset(scrolled_rect(msg.get_frame().rect, scroll_offset), viewport);
+                (
+                    LayoutPoint::new(scroll_offset.x - offset.x, scroll_offset.y - offset.y),
+                    clips,
+                    viewport,
+                )
+            }
+        };
+
+        match msg {
+            DisplayListMsg::Frame(frame) => {
+                self.render_frame(frame, scroll_offset, scroll_offset, false, clips, viewport)
+            }
+            DisplayListMsg::ScrollFrame(scroll_frame) => {
+                // only the children scroll, not the frame itself
+                let scroll_position = self
+                    .scroll_positions
+                    .get(&scroll_frame.scroll_id)
+                    .cloned()
+                    .unwrap_or(LayoutPoint::zero());
+                let children_scroll_offset = LayoutPoint::new(
+                    scroll_offset.x + scroll_position.x,
+                    scroll_offset.y + scroll_position.y,
+                );
+                self.render_frame(
+                    &scroll_frame.frame,
+                    scroll_offset,
+                    children_scroll_offset,
+                    true,
+                    clips,
+                    scrolled_rect(scroll_frame.frame.rect, scroll_offset),
+                )
+            }
+        }
+    }
+
+    fn render_frame(
+        &mut self,
+        frame: &DisplayListFrame,
+        scroll_offset: LayoutPoint,
+        children_scroll_offset: LayoutPoint,
+        clip_children: bool,
+        clips: &[RoundedRect],
+        children_viewport: LayoutRect,
+    ) {
+        let opacity = frame
+            .filters
+            .iter()
+            .fold(1.0, |opacity, filter| match filter {
+                FrameFilter::Opacity(o) => opacity * o,
+                _ => opacity,
+            });
+
+        if opacity <= 0.0 {
+            return;
+        }
+
+        // The frame and its children are rendered into a separate layer, so that
+        // overlapping content doesn't shine through when the layer is faded out
+        let parent_canvas = if opacity < 1.0 {
+            let layer = Canvas::new(self.canvas.width, self.canvas.height, ColorU::TRANSPARENT);
+            Some(mem::replace(&mut self.canvas, layer))
+        } else {
+            None
+        };
+
+        let border_box = self.get_rounded_rect(&frame.rect, &frame.border_radius, scroll_offset);
+
+        // All content, except for outset box shadows, is clipped to the border box
+        let mut content_clips = clips.to_vec();
+        content_clips.push(border_box);
+
+        for content in &frame.content {
+            self.render_content(
+                content,
+                frame,
+                &border_box,
+                scroll_offset,
+                clips,
+                &content_clips,
+            );
+        }
+
+        let mut children_clips = clips.to_vec();
+        if let Some(clip_rect) = &frame.clip_rect {
+            children_clips.push(self.get_rounded_rect(
+                clip_rect,
+                &frame.border_radius,
+                scroll_offset,
+            ));
+        }
+        if clip_children {
+            children_clips.push(border_box);
+        }
+
+        for child in &frame.children {
+            self.render_msg(
+                child,
+                children_scroll_offset,
+                &children_clips,
+                children_viewport,
+            );
+        }
+
+        if let Some(parent_canvas) = parent_canvas {
+            let layer = mem::replace(&mut self.canvas, parent_canvas);
+            self.canvas.composite(&layer, opacity);
+        }
+    }
+
+    /// `clips` are the clips of the frame, the `content_clips` additionally clip to the border box
+    fn render_content(
+        &mut self,
+        content: &LayoutRectContent,
+        frame: &DisplayListFrame,
+        border_box: &RoundedRect,
+        scroll_offset: LayoutPoint,
+        clips: &[RoundedRect],
+        content_clips: &[RoundedRect],
+    ) {
+        match content {
+            LayoutRectContent::Text {
+                glyphs,
+                font_instance_key,
+                color,
+                glyph_options: _,
+                clip,
+            } => {
+                let mut text_clips = content_clips.to_vec();
+                if let Some(clip) = clip {
+                    text_clips.push(RoundedRect::sharp(self.to_device_rect(clip, scroll_offset)));
+                }
+                self.draw_text(
+                    glyphs,
+                    *font_instance_key,
+                    *color,
+                    scroll_offset,
+                    &text_clips,
+                );
+            }
+            LayoutRectContent::Background {
+                content,
+                size,
+                offset,
+                repeat,
+            } => {
+                self.draw_background(
+                    self.to_device_rect(&frame.rect, scroll_offset),
+                    content,
+                    *size,
+                    *offset,
+                    *repeat,
+                    content_clips,
+                );
+            }
+            LayoutRectContent::Image {
+                size,
+                offset,
+                image_rendering,
+                alpha_type: _,
+                image_key,
+                background_color: _,
+            } => {
+                let area = self
+                    .to_device_rect(&frame.rect, scroll_offset)
+                    .translate(offset.x * self.scale, offset.y * self.scale);
+                self.draw_image(*image_key, area, *size, *image_rendering, content_clips);
+            }
+            LayoutRectContent::Border {
+                widths,
+                colors,
+                styles,
+            } => {
+                self.draw_border(
+                    border_box,
+                    frame.rect.size,
+                    widths,
+                    colors,
+                    styles,
+                    content_clips,
+                );
+            }
+            LayoutRectContent::BoxShadow { shadow, clip_mode } => {
+                let shadow_clips = match clip_mode {
+                    BoxShadowClipMode::Outset => clips,
+                    BoxShadowClipMode::Inset => content_clips,
+                };
+                self.draw_box_shadow(border_box, shadow, *clip_mode, shadow_clips);
+            }
+            LayoutRectContent::Line {
+                bounds,
+                thickness,
+                color,
+                style,
+                clip,
+            } => {
+                let mut line_clips = content_clips.to_vec();
+                if let Some(clip) = clip {
+                    line_clips.push(RoundedRect::sharp(self.to_device_rect(clip, scroll_offset)));
+                }
+                self.draw_line(
+                    self.to_device_rect(bounds, scroll_offset),
+                    *thickness * self.scale,
+                    *color,
+                    *style,
+                    &line_clips,
+                );
+            }
+            LayoutRectContent::Shadowed { shadows, content } => {
+                // the shadows are drawn below the content, the first shadow on top
+                for shadow in shadows.iter().rev() {
+                    for item in content {
+                        self.render_content(
+                            &get_shadow_content(item, shadow),
+                            frame,
+                            border_box,
+                            scroll_offset,
+                            clips,
+                            content_clips,
+                        );
+                    }
+                }
+                for item in content {
+                    self.render_content(
+                        item,
+                        frame,
+                        border_box,
+                        scroll_offset,
+                        clips,
+                        content_clips,
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_background(
+        &mut self,
+        device_rect: Rect,
+        background: &RectBackground,
+        background_size: Option<StyleBackgroundSize>,
+        background_position: Option<StyleBackgroundPosition>,
+        background_repeat: Option<StyleBackgroundRepeat>,
+        clips: &[RoundedRect],
+    ) {
+        use crate::wr_translate::{calculate_background_position, calculate_background_size};
+
+        let rect_size = LayoutSize::new(
+            device_rect.width() / self.scale,
+            device_rect.height() / self.scale,
+        );
+        let background_size =
+            calculate_background_size(rect_size, background_size, background.get_content_size());
+        let background_position = calculate_background_position(
+            rect_size,
+            background_position.unwrap_or_default(),
+            background_size,
+        );
+
+        let area = device_rect.translate(
+            background_position.x * self.scale,
+            background_position.y * self.scale,
+        );
+
+        let stops_to_paint = |stops: &[GradientStopPre]| normalize_gradient_stops(stops);
+
+        match background {
+            RectBackground::Color(color) => {
+                let color = premultiply((*color).into());
+                let area = Rect::new(
+                    area.x0,
+                    area.y0,
+                    area.x0 + background_size.width * self.scale,
+                    area.y0 + background_size.height * self.scale,
+                );
+                self.canvas
+                    .fill(area, clips, |x, y| Some((color, box_coverage(&area, x, y))));
+            }
+            RectBackground::LinearGradient(LinearGradient {
+                direction,
+                extend_mode,
+                stops,
+            }) => {
+                let tile = LayoutRect::new(LayoutPoint::zero(), background_size);
+                let (start, end) = direction.to_points(&tile);
+                let paint = Paint::LinearGradient {
+                    start,
+                    end,
+                    stops: stops_to_paint(stops),
+                    extend_mode: *extend_mode,
+                };
+                self.fill_tiled(area, background_size, &paint, clips);
+            }
+            RectBackground::RadialGradient(RadialGradient {
+                shape,
+                extend_mode,
+                stops,
+            }) => {
+                let radius = match shape {
+                    Shape::Ellipse => {
+                        LayoutSize::new(background_size.width / 2.0, background_size.height / 2.0)
+                    }
+                    Shape::Circle => {
+                        let largest_bound_size = background_size.width.max(background_size.height);
+                        LayoutSize::new(largest_bound_size / 2.0, largest_bound_size / 2.0)
+                    }
+                };
+                let paint = Paint::RadialGradient {
+                    center: LayoutPoint::new(
+                        background_size.width / 2.0,
+                        background_size.height / 2.0,
+                    ),
+                    radius,
+                    stops: stops_to_paint(stops),
+                    extend_mode: *extend_mode,
+                };
+                self.fill_tiled(area, background_size, &paint, clips);
+            }
+            RectBackground::Image(image_info) => {
+                let repeat_area = match background_repeat.unwrap_or_default() {
+                    StyleBackgroundRepeat::Repeat => area,
+                    StyleBackgroundRepeat::NoRepeat => Rect::new(
+                        area.x0,
+                        area.y0,
+                        area.x0 + background_size.width * self.scale,
+                        area.y0 + background_size.height * self.scale,
+                    ),
+                    StyleBackgroundRepeat::RepeatX => Rect::new(
+                        area.x0,
+                        area.y0,
+                        area.x1,
+                        area.y0 + background_size.height * self.scale,
+                    ),
+                    StyleBackgroundRepeat::RepeatY => Rect::new(
+                        area.x0,
+                        area.y0,
+                        area.x0 + background_size.width * self.scale,
+                        area.y1,
+                    ),
+                };
+                self.draw_image(
+                    image_info.key,
+                    repeat_area,
+                    background_size,
+                    ImageRendering::Auto,
+                    clips,
+                );
+            }
+        }
+    }
+
+    /// Fills the `area` with the `paint`, repeating the paint every `tile_size` (in layout units)
+    fn fill_tiled(
+        &mut self,
+        area: Rect,
+        tile_size: LayoutSize,
+        paint: &Paint,
+        clips: &[RoundedRect],
+    ) {
+        if tile_size.width <= 0.0 || tile_size.height <= 0.0 {
+            return;
+        }
+
+        let scale = self.scale;
+        self.canvas.fill(area, clips, |x, y| {
+            let tile_x = ((x - area.x0) / scale).rem_euclid(tile_size.width);
+            let tile_y = ((y - area.y0) / scale).rem_euclid(tile_size.height);
+            let color = paint.color_at(tile_x, tile_y, tile_size);
+            Some((color, box_coverage(&area, x, y)))
+        });
+    }
+
+    fn draw_image(
+        &mut self,
+        image_key: ImageKey,
+        area: Rect,
+        tile_size: LayoutSize,
+        image_rendering: ImageRendering,
+        clips: &[RoundedRect],
+    ) {
+        let app_resources = self.app_resources;
+        let image = self
+            .images
+            .entry(image_key)
+            .or_insert_with(|| decode_image(app_resources, image_key));
+
+        let image = match image {
+            Some(s) => s,
+            None => return,
+        };
+
+        let paint = Paint::Image {
+            image,
+            image_rendering,
+        };
+
+        // can't call self.fill_tiled, since self.images is borrowed
+        if tile_size.width <= 0.0 || tile_size.height <= 0.0 {
+            return;
+        }
+
+        let scale = self.scale;
+        self.canvas.fill(area, clips, |x, y| {
+            let tile_x = ((x - area.x0) / scale).rem_euclid(tile_size.width);
+            let tile_y = ((y - area.y0) / scale).rem_euclid(tile_size.height);
+            let color = paint.color_at(tile_x, tile_y, tile_size);
+            Some((color, box_coverage(&area, x, y)))
+        });
+    }
+
+    fn draw_border(
+        &mut self,
+        border_box: &RoundedRect,
+        rect_size: LayoutSize,
+        widths: &StyleBorderWidths,
+        colors: &StyleBorderColors,
+        styles: &StyleBorderStyles,
+        clips: &[RoundedRect],
+    ) {
+        let scale = self.scale;
+
+        // Same as WebRender: a side without a width or style is not drawn
+        let side = |width: Option<f32>, color: Option<ColorU>, style: Option<BorderStyle>| {
+            let width = width.unwrap_or(0.0) * scale;
+            let style = style.unwrap_or(BorderStyle::None);
+            let is_visible =
+                width > 0.0 && style != BorderStyle::None && style != BorderStyle::Hidden;
+            BorderSide {
+                width: if is_visible { width } else { 0.0 },
+                color: premultiply(color.unwrap_or_default().into()),
+                style,
+            }
+        };
+
+        let top = side(
+            widths
+                .top
+                .and_then(|w| w.get_property_or_default())
+                .map(|w| w.0.to_pixels(rect_size.height)),
+            colors
+                .top
+                .and_then(|c| c.get_property_or_default())
+                .map(|c| c.0),
+            styles
+                .top
+                .and_then(|s| s.get_property_or_default())
+                .map(|s| s.0),
+        );
+        let right = side(
+            widths
+                .right
+                .and_then(|w| w.get_property_or_default())
+                .map(|w| w.0.to_pixels(rect_size.width)),
+            colors
+                .right
+                .and_then(|c| c.get_property_or_default())
+                .map(|c| c.0),
+            styles
+                .right
+                .and_then(|s| s.get_property_or_default())
+                .map(|s| s.0),
+        );
+        let bottom = side(
+            widths
+                .bottom
+                .and_then(|w| w.get_property_or_default())
+                .map(|w| w.0.to_pixels(rect_size.height)),
+            colors
+                .bottom
+                .and_then(|c| c.get_property_or_default())
+                .map(|c| c.0),
+            styles
+                .bottom
+                .and_then(|s| s.get_property_or_default())
+                .map(|s| s.0),
+        );
+        let left = side(
+            widths
+                .left
+                .and_then(|w| w.get_property_or_default())
+                .map(|w| w.0.to_pixels(rect_size.width)),
+            colors
+                .left
+                .and_then(|c| c.get_property_or_default())
+                .map(|c| c.0),
+            styles
+                .left
+                .and_then(|s| s.get_property_or_default())
+                .map(|s| s.0),
+        );
+
+        if top.width <= 0.0 && right.width <= 0.0 && bottom.width <= 0.0 && left.width <= 0.0 {
+            return;
+        }
+
+        let outer = *border_box;
+        let inner = outer.inset(top.width, right.width, bottom.width, left.width);
+        let rect = outer.rect;
+
+        self.canvas.fill(rect, clips, |x, y| {
+            let coverage = outer.coverage(x, y) * (1.0 - inner.coverage(x, y));
+            if coverage <= 0.0 {
+                return None;
+            }
+
+            // Select the side by the (width-relative) distance to the edges, so that
+            // the corners are split diagonally between the adjacent sides
+            let candidates = [
+                (&top, (y - rect.y0) / top.width, x - rect.x0),
+                (&right, (rect.x1 - x) / right.width, y - rect.y0),
+                (&bottom, (rect.y1 - y) / bottom.width, x - rect.x0),
+                (&left, (x - rect.x0) / left.width, y - rect.y0),
+            ];
+
+            let (side, depth, position) = candidates
+                .iter()
+                .filter(|(side, _, _)| side.width > 0.0)
+                .fold(
+                    None,
+                    |closest: Option<&(&BorderSide, f32, f32)>, c| match closest {
+                        Some(closest) if closest.1 <= c.1 => Some(closest),
+                        _ => Some(c),
+                    },
+                )?;
+
+            let color = side.get_color(*depth, *position)?;
+            Some((color, coverage))
+        });
+    }
+
+    fn draw_box_shadow(
+        &mut self,
+        border_box: &RoundedRect,
+        box_shadow: &StyleBoxShadow,
+        clip_mode: BoxShadowClipMode,
+        clips: &[RoundedRect],
+    ) {
+        let get_shadow = |side: &Option<CssPropertyValue<BoxShadowPreDisplayItem>>| {
+            side.as_ref()
+                .and_then(|s| s.get_property().cloned())
+                .filter(|s| s.clip_mode == clip_mode)
+        };
+
+        let sides = [
+            get_shadow(&box_shadow.top),
+            get_shadow(&box_shadow.right),
+            get_shadow(&box_shadow.bottom),
+            get_shadow(&box_shadow.left),
+        ];
+
+        // box-shadow: (all sides are the same) - draw one shadow around the rect
+        if sides.iter().all(|s| s.is_some() && *s == sides[0]) {
+            if let Some(shadow) = sides[0] {
+                self.draw_single_box_shadow(border_box, &shadow, None, clips);
+            }
+            return;
+        }
+
+        // box-shadow-top, box-shadow-left, etc. - clip each shadow to its side
+        let rect = border_box.rect;
+        let (cx, cy) = ((rect.x0 + rect.x1) / 2.0, (rect.y0 + rect.y1) / 2.0);
+        let (min, max) = (f32::MIN / 4.0, f32::MAX / 4.0);
+        let side_clips = match clip_mode {
+            BoxShadowClipMode::Outset => [
+                Rect::new(min, min, max, rect.y0),
+                Rect::new(rect.x1, min, max, max),
+                Rect::new(min, rect.y1, max, max),
+                Rect::new(min, min, rect.x0, max),
+            ],
+            BoxShadowClipMode::Inset => [
+                Rect::new(min, min, max, cy),
+                Rect::new(cx, min, max, max),
+                Rect::new(min, cy, max, max),
+                Rect::new(min, min, cx, max),
+            ],
+        };
+
+        for (shadow, side_clip) in sides.iter().zip(side_clips.iter()) {
+            if let Some(shadow) = shadow {
+                self.draw_single_box_shadow(border_box, shadow, Some(*side_clip), clips);
+            }
+        }
+    }
+
+    fn draw_single_box_shadow(
+        &mut self,
+        border_box: &RoundedRect,
+        shadow: &BoxShadowPreDisplayItem,
+        side_clip: Option<Rect>,
+        clips: &[RoundedRect],
+    ) {
+        let scale = self.scale;
+        let offset_x = shadow.offset[0].to_pixels() * scale;
+        let offset_y = shadow.offset[1].to_pixels() * scale;
+        let blur_radius = shadow.blur_radius.to_pixels() * scale;
+        let spread_radius = shadow.spread_radius.to_pixels() * scale;
+        let sigma = blur_radius / 2.0;
+        let color = premultiply(shadow.color.into());
+
+        let mut shadow_rect = border_box.spread(match shadow.clip_mode {
+            BoxShadowClipMode::Outset => spread_radius,
+            BoxShadowClipMode::Inset => -spread_radius,
+        });
+        shadow_rect.rect = shadow_rect.rect.translate(offset_x, offset_y);
+
+        let border_box = *border_box;
+
+        let bounds = match shadow.clip_mode {
+            BoxShadowClipMode::Outset => shadow_rect.rect.inflate(3.0 * sigma, 3.0 * sigma),
+            BoxShadowClipMode::Inset => border_box.rect,
+        };
+        let bounds = match side_clip {
+            Some(c) => bounds.intersect(&c),
+            None => bounds,
+        };
+
+        match shadow.clip_mode {
+            BoxShadowClipMode::Outset => self.canvas.fill(bounds, clips, |x, y| {
+                // the shadow is only visible outside of the element
+                let coverage = shadow_rect.blurred_coverage(x, y, sigma)
+                    * (1.0 - border_box.coverage(x, y))
+                    * side_clip.map(|c| box_coverage(&c, x, y)).unwrap_or(1.0);
+                Some((color, coverage))
+            }),
+            BoxShadowClipMode::Inset => self.canvas.fill(bounds, clips, |x, y| {
+                // the shadow is only visible inside of the element
+                let coverage = (1.0 - shadow_rect.blurred_coverage(x, y, sigma))
+                    * border_box.coverage(x, y)
+                    * side_clip.map(|c| box_coverage(&c, x, y)).unwrap_or(1.0);
+                Some((color, coverage))
+            }),
+        }
+    }
+
+    fn draw_text(
+        &mut self,
+        glyphs: &[GlyphInstance],
+        font_instance_key: FontInstanceKey,
+        color: ColorU,
+        scroll_offset: LayoutPoint,
+        clips: &[RoundedRect],
+    ) {
+        let (font_key, font_size_px) = match get_font(self.app_resources, font_instance_key) {
+            Some(s) => s,
+            None => return,
+        };
+
+        let app_resources = self.app_resources;
+        let font = self.fonts.entry(font_key).or_insert_with(|| {
+            let loaded_font = app_resources
+                .currently_registered_fonts
+                .values()
+                .find(|f| f.font_key == font_key)?;
+            let font_bytes = &loaded_font.font_bytes[..];
+            let font_offset =
+                stb_truetype::get_font_offset_for_index(font_bytes, loaded_font.font_index)?;
+            FontInfo::new(font_bytes, font_offset as usize)
+        });
+
+        let font = match font {
+            Some(s) => s,
+            None => return,
+        };
+
+        let font_scale = font.scale_for_mapping_em_to_pixels(font_size_px * self.scale);
+        let color = premultiply(color.into());
+
+        for glyph in glyphs {
+            let shape = match font.get_glyph_shape(glyph.index) {
+                Some(s) => s,
+                None => continue, // whitespace
+            };
+
+            let origin = LayoutPoint::new(
+                (glyph.point.x - scroll_offset.x) * self.scale,
+                (glyph.point.y - scroll_offset.y) * self.scale,
+            );
+
+            if let Some(glyph_mask) = rasterize_glyph(&shape, font_scale, origin) {
+                self.canvas.fill(glyph_mask.bounds, clips, |x, y| {
+                    Some((color, glyph_mask.coverage(x, y)))
+                });
+            }
+        }
+    }
+
+    /// Draws a horizontal text decoration line, `thickness` is in physical pixels
+    fn draw_line(
+        &mut self,
+        device_rect: Rect,
+        thickness: f32,
+        color: ColorU,
+        style: LineStyle,
+        clips: &[RoundedRect],
+    ) {
+        let color = premultiply(color.into());
+        let thickness = thickness.max(1.0);
+
+        match style {
+            LineStyle::Solid => self.canvas.fill(device_rect, clips, |x, y| {
+                Some((color, box_coverage(&device_rect, x, y)))
+            }),
+            LineStyle::Dotted | LineStyle::Dashed => {
+                let dash_length = match style {
+                    LineStyle::Dashed => thickness * 3.0,
+                    _ => thickness,
+                };
+                self.canvas.fill(device_rect, clips, |x, y| {
+                    if ((x - device_rect.x0) / dash_length).floor() as i64 % 2 == 0 {
+                        Some((color, box_coverage(&device_rect, x, y)))
+                    } else {
+                        None
+                    }
+                })
+            }
+            LineStyle::Wavy => {
+                // sine wave that oscillates between the top and bottom of the rect
+                let amplitude = ((device_rect.height() - thickness) / 2.0).max(0.0);
+                let center_y = (device_rect.y0 + device_rect.y1) / 2.0;
+                let wavelength = (device_rect.height() * 2.0).max(1.0);
+                self.canvas.fill(device_rect, clips, |x, y| {
+                    let phase = (x - device_rect.x0) / wavelength * 2.0 * std::f32::consts::PI;
+                    let wave_y = center_y - amplitude * phase.sin();
+                    let coverage = clamp_unit(thickness / 2.0 + 0.5 - (y - wave_y).abs());
+                    Some((color, coverage))
+                })
+            }
+        }
+    }
+}
+
+/// Returns a copy of the text and lines of the content, moved by the offset
+/// of the shadow and drawn in the color of the shadow
+fn get_shadow_content(content: &LayoutRectContent, shadow: &TextShadow) -> LayoutRectContent {
+    let mut shadow_content = content.clone();
+    match &mut shadow_content {
+        LayoutRectContent::Text { glyphs, color, .. } => {
+            for glyph in glyphs.iter_mut() {
+                glyph.point.x += shadow.offset.x;
+                glyph.point.y += shadow.offset.y;
+            }
+            *color = shadow.color;
+        }
+        LayoutRectContent::Line { bounds, color, .. } => {
+            bounds.origin.x += shadow.offset.x;
+            bounds.origin.y += shadow.offset.y;
+            *color = shadow.color;
+        }
+        LayoutRectContent::Shadowed { content, .. } => {
+            for item in content.iter_mut() {
+                *item = get_shadow_content(item, shadow);
+            }
+        }
+        _ => {}
+    }
+    shadow_content
+}
+
+/// Resolved style of one side of a border
+struct BorderSide {
+    width: f32,
+    color: ColorF,
+    style: BorderStyle,
+}
+
+impl BorderSide {
+    /// Returns the color at the `depth` (0.0 = outer edge, 1.0 = inner edge)
+    /// and the `position` along the side, `None` if the pixel is in a gap
+    fn get_color(&self, depth: f32, position: f32) -> Option<ColorF> {
+        let darken = |c: ColorF| ColorF {
+            r: c.r * (2.0 / 3.0),
+            g: c.g * (2.0 / 3.0),
+            b: c.b * (2.0 / 3.0),
+            a: c.a,
+        };
+
+        match self.style {
+            BorderStyle::None | BorderStyle::Hidden => None,
+            BorderStyle::Solid => Some(self.color),
+            BorderStyle::Double => {
+                if !(1.0 / 3.0..=2.0 / 3.0).contains(&depth) {
+                    Some(self.color)
+                } else {
+                    None
+                }
+            }
+            BorderStyle::Dashed | BorderStyle::Dotted => {
+                let dash_length = match self.style {
+                    BorderStyle::Dashed => self.width * 3.0,
+                    _ => self.width,
+                };
+                if (position / dash_length).floor() as i64 % 2 == 0 {
+                    Some(self.color)
+                } else {
+                    None
+                }
+            }
+            BorderStyle::Groove => Some(if depth < 0.5 {
+                darken(self.color)
+            } else {
+                self.color
+            }),
+            BorderStyle::Ridge => Some(if depth < 0.5 {
+                self.color
+            } else {
+                darken(self.color)
+            }),
+            BorderStyle::Inset | BorderStyle::Outset => Some(self.color),
+        }
+    }
+}
+
+/// Coverage mask of a rasterized glyph
+struct GlyphMask {
+    bounds: Rect,
+    width: usize,
+    coverage: Vec<f32>,
+}
+
+impl GlyphMask {
+    fn coverage(&self, x: f32, y: f32) -> f32 {
+        if x < self.bounds.x0 || y < self.bounds.y0 || x >= self.bounds.x1 {
+            return 0.0;
+        }
+        let local_x = (x - self.bounds.x0).floor() as usize;
+        let local_y = (y - self.bounds.y0).floor() as usize;
+        self.coverage
+            .get(local_y * self.width + local_x)
+            .cloned()
+            .unwrap_or(0.0)
+    }
+}
+
+/// Rasterizes the outline of a glyph at the `origin` (baseline position, in physical pixels)
+fn rasterize_glyph(
+    shape: &[stb_truetype::Vertex],
+    font_scale: f32,
+    origin: LayoutPoint,
+) -> Option<GlyphMask> {
+    use stb_truetype::VertexType;
+
+    // font units (y-up) to physical pixels (y-down)
+    let transform = |x: i16, y: i16| {
+        (
+            origin.x + x as f32 * font_scale,
+            origin.y - y as f32 * font_scale,
+        )
+    };
+
+    let mut min = (f32::MAX, f32::MAX);
+    let mut max = (f32::MIN, f32::MIN);
+    for vertex in shape {
+        let points = [
+            transform(vertex.x, vertex.y),
+            transform(vertex.cx, vertex.cy),
+        ];
+        let point_count = if vertex.vertex_type() == VertexType::CurveTo {
+            2
+        } else {
+            1
+        };
+        for &(x, y) in &points[..point_count] {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+    }
+
+    if min.0 >= max.0 || min.1 >= max.1 {
+        return None;
+    }
+
+    let bounds = Rect::new(min.0.floor(), min.1.floor(), max.0.ceil(), max.1.ceil());
+    let width = bounds.width() as usize + 2;
+    let height = bounds.height() as usize + 1;
+
+    let mut accumulator = CoverageAccumulator::new(width, height);
+    let local = |(x, y): (f32, f32)| (x - bounds.x0, y - bounds.y0);
+
+    let mut current = (0.0, 0.0);
+    let mut contour_start = (0.0, 0.0);
+
+    for vertex in shape {
+        let point = local(transform(vertex.x, vertex.y));
+        match vertex.vertex_type() {
+            VertexType::MoveTo => {
+                accumulator.line(current, contour_start);
+                contour_start = point;
+            }
+            VertexType::LineTo => accumulator.line(current, point),
+            VertexType::CurveTo => {
+                let control = local(transform(vertex.cx, vertex.cy));
+                accumulator.quadratic_curve(current, control, point);
+            }
+        }
+        current = point;
+    }
+
+    accumulator.line(current, contour_start);
+
+    Some(GlyphMask {
+        bounds: Rect::new(
+            bounds.x0,
+            bounds.y0,
+            bounds.x0 + width as f32,
+            bounds.y0 + height as f32,
+        ),
+        width,
+        coverage: accumulator.into_coverage(),
+    })
+}
+
+/// Scanline rasterizer that accumulates the signed area of the outline
+/// per pixel, the coverage of a pixel is the prefix sum of its row
+/// (same technique as in font-rs)
+struct CoverageAccumulator {
+    width: usize,
+    height: usize,
+    area: Vec<f32>,
+}
+
+impl CoverageAccumulator {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            area: vec![0.0; width * height + 4],
+        }
+    }
+
+    fn quadratic_curve(&mut self, p0: (f32, f32), p1: (f32, f32), p2: (f32, f32)) {
+        // flatten the curve into line segments, so that the error stays below 1/16 pixel
+        let dd = (p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1);
+        let deviation = (dd.0 * dd.0 + dd.1 * dd.1).sqrt();
+        let segments = ((deviation * 4.0).sqrt().ceil() as usize).clamp(1, 64);
+
+        let mut previous = p0;
+        for i in 1..=segments {
+            let t = i as f32 / segments as f32;
+            let mt = 1.0 - t;
+            let point = (
+                mt * mt * p0.0 + 2.0 * mt * t * p1.0 + t * t * p2.0,
+                mt * mt * p0.1 + 2.0 * mt * t * p1.1 + t * t * p2.1,
+            );
+            self.line(previous, point);
+            previous = point;
+        }
+    }
+
+    fn line(&mut self, p0: (f32, f32), p1: (f32, f32)) {
+        if (p0.1 - p1.1).abs() <= f32::EPSILON {
+            return;
+        }
+
+        let (direction, p0, p1) = if p0.1 < p1.1 {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+
+        let dxdy = (p1.0 - p0.0) / (p1.1 - p0.1);
+        let mut x = p0.0;
+        let y_start = p0.1.max(0.0) as usize;
+        if p0.1 < 0.0 {
+            x -= p0.1 * dxdy;
+        }
+
+        for y in y_start..self.height.min(p1.1.ceil() as usize) {
+            let line_start = y * self.width;
+            let dy = ((y + 1) as f32).min(p1.1) - (y as f32).max(p0.1);
+            let x_next = x + dxdy * dy;
+            let d = dy * direction;
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x0_i = x0_floor.max(0.0) as usize;
+            let x1_ceil = x1.ceil();
+            let x1_i = x1_ceil.max(0.0) as usize;
+
+            if x1_i <= x0_i + 1 {
+                let x_mid = 0.5 * (x + x_next) - x0_floor;
+                self.add(line_start + x0_i, d - d * x_mid);
+                self.add(line_start + x0_i + 1, d * x_mid);
+            } else {
+                let s = (x1 - x0).recip();
+                let x0_fract = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0_fract) * (1.0 - x0_fract);
+                let x1_fract = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1_fract * x1_fract;
+                self.add(line_start + x0_i, d * a0);
+                if x1_i == x0_i + 2 {
+                    self.add(line_start + x0_i + 1, d * (1.0 - a0 - am));
+                } else {
+                    let a1 = s * (1.5 - x0_fract);
+                    self.add(line_start + x0_i + 1, d * (a1 - a0));
+                    for xi in x0_i + 2..x1_i - 1 {
+                        self.add(line_start + xi, d * s);
+                    }
+                    let a2 = a1 + (x1_i - x0_i - 3) as f32 * s;
+                    self.add(line_start + x1_i - 1, d * (1.0 - a2 - am));
+                }
+                self.add(line_start + x1_i, d * am);
+            }
+
+            x = x_next;
+        }
+    }
+
+    fn add(&mut self, index: usize, value: f32) {
+        if let Some(a) = self.area.get_mut(index) {
+            *a += value;
+        }
+    }
+
+    /// Integrates the accumulated area per row (non-zero winding rule)
+    fn into_coverage(self) -> Vec<f32> {
+        let mut coverage = Vec::with_capacity(self.width * self.height);
+        for row in self.area.chunks(self.width).take(self.height) {
+            let mut sum = 0.0;
+            for area in row {
+                sum += area;
+                coverage.push(sum.abs().min(1.0));
+            }
+        }
+        coverage
+    }
+}
+
+/// Looks up the font and the font size (in logical pixels) of a font instance
+fn get_font(
+    app_resources: &AppResources,
+    font_instance_key: FontInstanceKey,
+) -> Option<(FontKey, f32)> {
+    use app_units::AU_PER_PX;
+
+    app_resources
+        .currently_registered_fonts
+        .values()
+        .find_map(|loaded_font| {
+            loaded_font
+                .font_instances
+                .iter()
+                .find(|(_, key)| **key == font_instance_key)
+                .map(|(font_instance, _)| {
+                    let font_size_px = font_instance.size.0 as f32 / AU_PER_PX as f32;
+                    (loaded_font.font_key, font_size_px)
+                })
+        })
+}
+
+/// Decodes the image that is registered under the `image_key`
+fn decode_image(app_resources: &AppResources, image_key: ImageKey) -> Option<DecodedImage> {
+    use crate::{app_resources::image_source_get_bytes, wr_translate::translate_image_format_wr};
+    use webrender::api::ImageData;
+
+    let (image_id, _) = app_resources
+        .currently_registered_images
+        .iter()
+        .find(|(_, info)| info.key == image_key)?;
+    let image_source = app_resources.image_sources.get(image_id)?;
+    let (image_data, descriptor) = image_source_get_bytes(image_source).ok()?;
+
+    let bytes = match image_data {
+        ImageData::Raw(bytes) => bytes,
+        _ => return None,
+    };
+
+    let width = descriptor.size.width.max(0) as usize;
+    let height = descriptor.size.height.max(0) as usize;
+    let format = translate_image_format_wr(descriptor.format);
+    let bytes_per_pixel = match format {
+        RawImageFormat::R8 => 1,
+        RawImageFormat::BGRA8 | RawImageFormat::RGBA8 => 4,
+        _ => return None, // unsupported format
+    };
+    let stride = descriptor
+        .stride
+        .map(|s| s as usize)
+        .unwrap_or(width * bytes_per_pixel);
+    let offset = descriptor.offset as usize;
+
+    if width == 0
+        || height == 0
+        || bytes.len() < offset + stride * (height - 1) + width * bytes_per_pixel
+    {
+        return None;
+    }
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row = &bytes[offset + y * stride..];
+        for x in 0..width {
+            let p = &row[x * bytes_per_pixel..(x + 1) * bytes_per_pixel];
+            // BGRA8 images are already premultiplied when they are decoded
+            let color = match format {
+                RawImageFormat::R8 => {
+                    let v = p[0] as f32 / 255.0;
+                    ColorF {
+                        r: v,
+                        g: v,
+                        b: v,
+                        a: 1.0,
+                    }
+                }
+                RawImageFormat::BGRA8 => ColorF {
+                    r: p[2] as f32 / 255.0,
+                    g: p[1] as f32 / 255.0,
+                    b: p[0] as f32 / 255.0,
+                    a: p[3] as f32 / 255.0,
+                },
+                _ => premultiply(ColorF {
+                    r: p[0] as f32 / 255.0,
+                    g: p[1] as f32 / 255.0,
+                    b: p[2] as f32 / 255.0,
+                    a: p[3] as f32 / 255.0,
+                }),
+            };
+            pixels.push(color);
+        }
+    }
+
+    Some(DecodedImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Resolves the offsets of the gradient stops into the range 0.0 - 1.0: stops without
+/// an offset are distributed evenly between the neighbouring stops (same as CSS)
+fn normalize_gradient_stops(stops: &[GradientStopPre]) -> Vec<(f32, ColorF)> {
+    let mut offsets = stops
+        .iter()
+        .map(|s| s.offset.map(|o| o.get() / 100.0))
+        .collect::<Vec<_>>();
+
+    if let Some(first) = offsets.first_mut() {
+        first.get_or_insert(0.0);
+    }
+    if let Some(last) = offsets.last_mut() {
+        last.get_or_insert(1.0);
+    }
+
+    let mut i = 0;
+    while i < offsets.len() {
+        if offsets[i].is_some() {
+            i += 1;
+            continue;
+        }
+        // offsets[0] is always set, so i > 0
+        let start = offsets[i - 1].unwrap_or(0.0);
+        let end_index = (i..offsets.len())
+            .find(|j| offsets[*j].is_some())
+            .unwrap_or(offsets.len() - 1);
+        let end = offsets[end_index].unwrap_or(1.0);
+        let count = (end_index - i + 1) as f32;
+        for (n, j) in (i..end_index).enumerate() {
+            offsets[j] = Some(start + (end - start) * (n + 1) as f32 / count);
+        }
+        i = end_index;
+    }
+
+    // offsets must be increasing
+    let mut last_offset = 0.0_f32;
+    stops
+        .iter()
+        .zip(offsets)
+        .map(|(stop, offset)| {
+            last_offset = last_offset.max(offset.unwrap_or(last_offset));
+            (last_offset, premultiply(stop.color.into()))
+        })
+        .collect()
+}
+
+/// Returns the premultiplied color of the gradient at the position `t`
+fn gradient_color(stops: &[(f32, ColorF)], t: f32, extend_mode: ExtendMode) -> ColorF {
+    let t = match extend_mode {
+        ExtendMode::Clamp => t,
+        ExtendMode::Repeat => {
+            let (first, last) = match (stops.first(), stops.last()) {
+                (Some(f), Some(l)) => (f.0, l.0),
+                _ => return ColorF::TRANSPARENT,
+            };
+            let length = last - first;
+            if length <= 0.0 {
+                t
+            } else {
+                first + (t - first).rem_euclid(length)
+            }
+        }
+    };
+
+    let mut previous = match stops.first() {
+        Some(s) => *s,
+        None => return ColorF::TRANSPARENT,
+    };
+
+    if t <= previous.0 {
+        return previous.1;
+    }
+
+    for stop in stops.iter().skip(1) {
+        if t <= stop.0 {
+            let length = stop.0 - previous.0;
+            if length <= 0.0 {
+                return stop.1;
+            }
+            return mix(previous.1, stop.1, (t - previous.0) / length);
+        }
+        previous = *stop;
+    }
+
+    previous.1
+}
+
+/// Coverage of the pixel centered at (x, y) by an axis-aligned rect
+fn box_coverage(rect: &Rect, x: f32, y: f32) -> f32 {
+    let coverage_x = clamp_unit((x + 0.5).min(rect.x1) - (x - 0.5).max(rect.x0));
+    let coverage_y = clamp_unit((y + 0.5).min(rect.y1) - (y - 0.5).max(rect.y0));
+    coverage_x * coverage_y
+}
+
+fn premultiply(color: ColorF) -> ColorF {
+    ColorF {
+        r: color.r * color.a,
+        g: color.g * color.a,
+        b: color.b * color.a,
+        a: color.a,
+    }
+}
+
+fn mix(a: ColorF, b: ColorF, t: f32) -> ColorF {
+    ColorF {
+        r: a.r + (b.r - a.r) * t,
+        g: a.g + (b.g - a.g) * t,
+        b: a.b + (b.b - a.b) * t,
+        a: a.a + (b.a - a.a) * t,
+    }
+}
+
+fn clamp_unit(value: f32) -> f32 {
+    value.clamp(0.0, 1.0)
+}
+
+fn to_u8(value: f32) -> u8 {
+    (clamp_unit(value) * 255.0).round() as u8
+}
+
+/// Error function (Abramowitz & Stegun 7.1.26, max. error 1.5e-7)
+fn erf(x: f32) -> f32 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let y = 1.0
+        - (((((1.061_405_4 * t - 1.453_152) * t) + 1.421_413_7) * t - 0.284_496_7) * t
+            + 0.254_829_6)
+            * t
+            * (-x * x).exp();
+    sign * y
+}
+
+#[cfg(test)]
+fn render_test_frame(frame: DisplayListFrame) -> RgbaImage {
+    render_test_frame_with_resources(frame, &AppResources::default())
+}
+
+#[cfg(test)]
+fn render_test_frame_with_resources(
+    frame: DisplayListFrame,
+    app_resources: &AppResources,
+) -> RgbaImage {
+    let display_list = CachedDisplayList {
+        root: DisplayListMsg::Frame(frame),
+    };
+    render_display_list(
+        &display_list,
+        app_resources,
+        &SoftwareRenderOptions::default(),
+    )
+}
+
+#[cfg(test)]
+fn get_test_background(color: ColorU) -> LayoutRectContent {
+    LayoutRectContent::Background {
+        content: RectBackground::Color(color),
+        size: None,
+        offset: None,
+        repeat: None,
+    }
+}
+
+#[test]
+fn test_render_background_color() {
+    let mut root = DisplayListFrame::root(LayoutSize::new(20.0, 20.0));
+    let mut child = DisplayListFrame::root(LayoutSize::new(10.0, 10.0));
+    child.rect.origin = LayoutPoint::new(5.0, 5.0);
+    child.content.push(get_test_background(ColorU::RED));
+    root.children.push(DisplayListMsg::Frame(child));
+
+    let image = render_test_frame(root);
+    assert_eq!(image.width, 20);
+    assert_eq!(image.height, 20);
+    assert_eq!(image.get_pixel(0, 0), Some(ColorU::WHITE));
+    assert_eq!(image.get_pixel(5, 5), Some(ColorU::RED));
+    assert_eq!(image.get_pixel(14, 14), Some(ColorU::RED));
+    assert_eq!(image.get_pixel(15, 15), Some(ColorU::WHITE));
+    assert_eq!(image.get_pixel(20, 20), None);
+}
+
+#[test]
+fn test_render_border_radius_and_border() {
+    use azul_css::{
+        StyleBorderBottomColor, StyleBorderBottomStyle, StyleBorderBottomWidth,
+        StyleBorderLeftColor, StyleBorderLeftStyle, StyleBorderLeftWidth, StyleBorderRightColor,
+        StyleBorderRightStyle, StyleBorderRightWidth, StyleBorderTopColor,
+        StyleBorderTopLeftRadius, StyleBorderTopStyle, StyleBorderTopWidth,
+    };
+
+    let mut frame = DisplayListFrame::root(LayoutSize::new(40.0, 40.0));
+    frame.border_radius.top_left = Some(CssPropertyValue::Exact(StyleBorderTopLeftRadius(
+        PixelValue::px(20.0),
+    )));
+    frame.content.push(get_test_background(ColorU::RED));
+    frame.content.push(LayoutRectContent::Border {
+        widths: StyleBorderWidths {
+            top: Some(CssPropertyValue::Exact(StyleBorderTopWidth(
+                PixelValue::px(4.0),
+            ))),
+            right: Some(CssPropertyValue::Exact(StyleBorderRightWidth(
+                PixelValue::px(4.0),
+            ))),
+            bottom: Some(CssPropertyValue::Exact(StyleBorderBottomWidth(
+                PixelValue::px(4.0),
+            ))),
+            left: Some(CssPropertyValue::Exact(StyleBorderLeftWidth(
+                PixelValue::px(4.0),
+            ))),
+        },
+        colors: StyleBorderColors {
+            top: Some(CssPropertyValue::Exact(StyleBorderTopColor(ColorU::BLACK))),
+            right: Some(CssPropertyValue::Exact(StyleBorderRightColor(
+                ColorU::BLACK,
+            ))),
+            bottom: Some(CssPropertyValue::Exact(StyleBorderBottomColor(
+                ColorU::BLACK,
+            ))),
+            left: Some(CssPropertyValue::Exact(StyleBorderLeftColor(ColorU::BLACK))),
+        },
+        styles: StyleBorderStyles {
+            top: Some(CssPropertyValue::Exact(StyleBorderTopStyle(
+                BorderStyle::Solid,
+            ))),
+            right: Some(CssPropertyValue::Exact(StyleBorderRightStyle(
+                BorderStyle::Solid,
+            ))),
+            bottom: Some(CssPropertyValue::Exact(StyleBorderBottomStyle(
+                BorderStyle::Solid,
+            ))),
+            left: Some(CssPropertyValue::Exact(StyleBorderLeftStyle(
+                BorderStyle::Solid,
+            ))),
+        },
+    });
+
+    let image = render_test_frame(frame);
+
+    // rounded top left corner is clipped away
+    assert_eq!(image.get_pixel(1, 1), Some(ColorU::WHITE));
+    // border
+    assert_eq!(image.get_pixel(39, 20), Some(ColorU::BLACK));
+    assert_eq!(image.get_pixel(20, 1), Some(ColorU::BLACK));
+    // content
+    assert_eq!(image.get_pixel(20, 20), Some(ColorU::RED));
+}
+
+#[test]
+fn test_render_linear_gradient() {
+    use azul_css::{Direction, DirectionCorner};
+
+    let mut frame = DisplayListFrame::root(LayoutSize::new(100.0, 10.0));
+    frame.content.push(LayoutRectContent::Background {
+        content: RectBackground::LinearGradient(LinearGradient {
+            direction: Direction::FromTo(DirectionCorner::Left, DirectionCorner::Right),
+            extend_mode: ExtendMode::Clamp,
+            stops: vec![
+                GradientStopPre {
+                    offset: None,
+                    color: ColorU::BLACK,
+                },
+                GradientStopPre {
+                    offset: None,
+                    color: ColorU::WHITE,
+                },
+            ],
+        }),
+        size: None,
+        offset: None,
+        repeat: None,
+    });
+
+    let image = render_test_frame(frame);
+    let left = image.get_pixel(0, 5).unwrap();
+    let middle = image.get_pixel(50, 5).unwrap();
+    let right = image.get_pixel(99, 5).unwrap();
+    assert!(left.r < 5);
+    assert!(middle.r > 120 && middle.r < 136);
+    assert!(right.r > 250);
+}
+
+#[test]
+fn test_render_box_shadow() {
+    use azul_css::PixelValueNoPercent;
+
+    let shadow = Some(CssPropertyValue::Exact(BoxShadowPreDisplayItem {
+        offset: [
+            PixelValueNoPercent(PixelValue::px(0.0)),
+            PixelValueNoPercent(PixelValue::px(0.0)),
+        ],
+        color: ColorU::BLACK,
+        blur_radius: PixelValueNoPercent(PixelValue::px(0.0)),
+        spread_radius: PixelValueNoPercent(PixelValue::px(5.0)),
+        clip_mode: BoxShadowClipMode::Outset,
+    }));
+
+    let mut root = DisplayListFrame::root(LayoutSize::new(40.0, 40.0));
+    let mut child = DisplayListFrame::root(LayoutSize::new(20.0, 20.0));
+    child.rect.origin = LayoutPoint::new(10.0, 10.0);
+    child.content.push(LayoutRectContent::BoxShadow {
+        shadow: StyleBoxShadow {
+            top: shadow,
+            right: shadow,
+            bottom: shadow,
+            left: shadow,
+        },
+        clip_mode: BoxShadowClipMode::Outset,
+    });
+    root.children.push(DisplayListMsg::Frame(child));
+
+    let image = render_test_frame(root);
+    // shadow is painted outside of the element, but not inside
+    assert_eq!(image.get_pixel(7, 20), Some(ColorU::BLACK));
+    assert_eq!(image.get_pixel(20, 20), Some(ColorU::WHITE));
+    assert_eq!(image.get_pixel(2, 20), Some(ColorU::WHITE));
+}
+
+#[test]
+fn test_render_text_decoration_line() {
+    let line = |style| LayoutRectContent::Line {
+        bounds: LayoutRect::new(LayoutPoint::new(0.0, 4.0), LayoutSize::new(40.0, 2.0)),
+        thickness: 2.0,
+        color: ColorU::BLACK,
+        style,
+        clip: None,
+    };
+
+    let mut frame = DisplayListFrame::root(LayoutSize::new(40.0, 20.0));
+    frame.content.push(LayoutRectContent::Shadowed {
+        shadows: vec![TextShadow {
+            offset: LayoutPoint::new(0.0, 10.0),
+            color: ColorU::RED,
+            blur_radius: 0.0,
+        }],
+        content: vec![line(LineStyle::Solid)],
+    });
+
+    let image = render_test_frame(frame);
+    assert_eq!(image.get_pixel(20, 4), Some(ColorU::BLACK));
+    assert_eq!(image.get_pixel(20, 5), Some(ColorU::BLACK));
+    assert_eq!(image.get_pixel(20, 9), Some(ColorU::WHITE));
+    // shadow
+    assert_eq!(image.get_pixel(20, 14), Some(ColorU::RED));
+
+    let mut frame = DisplayListFrame::root(LayoutSize::new(40.0, 20.0));
+    frame.content.push(line(LineStyle::Dashed));
+
+    let image = render_test_frame(frame);
+    assert_eq!(image.get_pixel(1, 4), Some(ColorU::BLACK));
+    assert_eq!(image.get_pixel(7, 4), Some(ColorU::WHITE));
+    assert_eq!(image.get_pixel(13, 4), Some(ColorU::BLACK));
+}
+
+#[test]
+fn test_render_radial_gradient() {
+    let mut frame = DisplayListFrame::root(LayoutSize::new(40.0, 40.0));
+    frame.content.push(LayoutRectContent::Background {
+        content: RectBackground::RadialGradient(RadialGradient {
+            shape: Shape::Ellipse,
+            extend_mode: ExtendMode::Clamp,
+            stops: vec![
+                GradientStopPre {
+                    offset: None,
+                    color: ColorU::BLACK,
+                },
+                GradientStopPre {
+                    offset: None,
+                    color: ColorU::WHITE,
+                },
+            ],
+        }),
+        size: None,
+        offset: None,
+        repeat: None,
+    });
+
+    let image = render_test_frame(frame);
+    let center = image.get_pixel(20, 20).unwrap();
+    let halfway_x = image.get_pixel(30, 20).unwrap();
+    let halfway_y = image.get_pixel(20, 30).unwrap();
+    let corner = image.get_pixel(0, 0).unwrap();
+    assert!(center.r < 10);
+    assert!(halfway_x.r > 120 && halfway_x.r < 150);
+    assert_eq!(halfway_x, halfway_y);
+    // outside of the ellipse, the last stop is extended
+    assert_eq!(corner, ColorU::WHITE);
+}
+
+#[test]
+fn test_render_image() {
+    use azul_core::{
+        app_resources::{IdNamespace, ImageDescriptor, ImageId, ImageInfo, ImageSource, RawImage},
+        display_list::AlphaType,
+    };
+
+    // 2x2 BGRA image: red, green / blue, black
+    let pixels = vec![
+        0, 0, 255, 255, 0, 255, 0, 255, //
+        255, 0, 0, 255, 0, 0, 0, 255,
+    ];
+    let image_id = ImageId::new();
+    let image_key = ImageKey {
+        namespace: IdNamespace(0),
+        key: 1,
+    };
+    let mut app_resources = AppResources::default();
+    app_resources.image_sources.insert(
+        image_id,
+        ImageSource::Raw(RawImage {
+            pixels,
+            image_dimensions: (2, 2),
+            data_format: RawImageFormat::BGRA8,
+        }),
+    );
+    app_resources.currently_registered_images.insert(
+        image_id,
+        ImageInfo {
+            key: image_key,
+            descriptor: ImageDescriptor {
+                format: RawImageFormat::BGRA8,
+                dimensions: (2, 2),
+                stride: None,
+                offset: 0,
+                is_opaque: true,
+                allow_mipmaps: true,
+            },
+        },
+    );
+
+    let mut frame = DisplayListFrame::root(LayoutSize::new(20.0, 20.0));
+    frame.content.push(LayoutRectContent::Image {
+        size: LayoutSize::new(20.0, 20.0),
+        offset: LayoutPoint::zero(),
+        image_rendering: ImageRendering::Pixelated,
+        alpha_type: AlphaType::PremultipliedAlpha,
+        image_key,
+        background_color: ColorU::TRANSPARENT,
+    });
+
+    let green = ColorU {
+        r: 0,
+        g: 255,
+        b: 0,
+        a: 255,
+    };
+    let blue = ColorU {
+        r: 0,
+        g: 0,
+        b: 255,
+        a: 255,
+    };
+
+    // each image pixel is scaled up to 10x10 pixels
+    let image = render_test_frame_with_resources(frame, &app_resources);
+    assert_eq!(image.get_pixel(0, 0), Some(ColorU::RED));
+    assert_eq!(image.get_pixel(9, 9), Some(ColorU::RED));
+    assert_eq!(image.get_pixel(10, 0), Some(green));
+    assert_eq!(image.get_pixel(0, 10), Some(blue));
+    assert_eq!(image.get_pixel(19, 19), Some(ColorU::BLACK));
+}
+
+#[test]
+fn test_render_glyph_run() {
+    use azul_core::app_resources::{
+        Au, FontId, FontInstanceDescriptor, FontSynthesis, IdNamespace, ImmediateFontId, LoadedFont,
+    };
+
+    const FONT_SIZE_PX: i32 = 40;
+    const BASELINE: usize = 45;
+
+    let font_bytes = include_bytes!("../../assets/fonts/KoHo-Light.ttf");
+    let font_key = FontKey {
+        namespace: IdNamespace(0),
+        key: 1,
+    };
+    let font_instance_key = FontInstanceKey {
+        namespace: IdNamespace(0),
+        key: 2,
+    };
+    let mut loaded_font =
+        LoadedFont::new(font_key, font_bytes.to_vec(), 0, FontSynthesis::default());
+    loaded_font.font_instances.insert(
+        FontInstanceDescriptor::from_size(Au(FONT_SIZE_PX * app_units::AU_PER_PX)),
+        font_instance_key,
+    );
+    let mut app_resources = AppResources::default();
+    app_resources.currently_registered_fonts.insert(
+        ImmediateFontId::Resolved(FontId::new(), FontSynthesis::default()),
+        loaded_font,
+    );
+
+    let glyph_index = FontInfo::new(&font_bytes[..], 0)
+        .unwrap()
+        .find_glyph_index('H' as u32);
+    assert_ne!(glyph_index, 0);
+
+    let mut frame = DisplayListFrame::root(LayoutSize::new(60.0, 60.0));
+    frame.content.push(LayoutRectContent::Text {
+        glyphs: vec![GlyphInstance {
+            index: glyph_index,
+            point: LayoutPoint::new(10.0, BASELINE as f32),
+            size: LayoutSize::new(30.0, 40.0),
+        }],
+        font_instance_key,
+        color: ColorU::BLACK,
+        glyph_options: None,
+        clip: None,
+    });
+
+    let image = render_test_frame_with_resources(frame, &app_resources);
+    let is_inked = |x: usize, y: usize| image.get_pixel(x, y).unwrap() != ColorU::WHITE;
+
+    // the "H" is drawn on top of the baseline, starting at the glyph origin
+    assert!((0..60).any(|y| (0..60).any(|x| image.get_pixel(x, y).unwrap().r < 64)));
+    assert!((0..60).all(|x| (BASELINE + 1..60).all(|y| !is_inked(x, y))));
+    assert!((0..60).all(|y| (0..10).all(|x| !is_inked(x, y))));
+}
+
+#[test]
+fn test_render_opacity() {
+    let mut root = DisplayListFrame::root(LayoutSize::new(20.0, 20.0));
+    let mut panel = DisplayListFrame::root(LayoutSize::new(20.0, 10.0));
+    panel.filters.push(FrameFilter::Opacity(0.5));
+    panel.content.push(get_test_background(ColorU::RED));
+    let mut child = DisplayListFrame::root(LayoutSize::new(10.0, 10.0));
+    child.content.push(get_test_background(ColorU::BLACK));
+    panel.children.push(DisplayListMsg::Frame(child));
+    root.children.push(DisplayListMsg::Frame(panel));
+
+    let image = render_test_frame(root);
+    let half_red = ColorU {
+        r: 255,
+        g: 128,
+        b: 128,
+        a: 255,
+    };
+    let half_black = ColorU {
+        r: 128,
+        g: 128,
+        b: 128,
+        a: 255,
+    };
+    assert_eq!(image.get_pixel(15, 5), Some(half_red));
+    // the panel is faded out as a whole, so the red background doesn't shine through the child
+    assert_eq!(image.get_pixel(5, 5), Some(half_black));
+    assert_eq!(image.get_pixel(5, 15), Some(ColorU::WHITE));
+}
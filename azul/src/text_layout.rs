@@ -5,14 +5,17 @@ pub(crate) use azul_core::ui_solver::{
 };
 pub use azul_core::{
     app_resources::{
-        ClusterInfo, ClusterIterator, GlyphIndex, GlyphInfo, GlyphPosition, IndexOfLineBreak,
-        LayoutedGlyphs, LineBreaks, LineLength, RemainingSpaceToRight, ScaledWord, ScaledWords,
-        Word, WordIndex, WordPositions, WordType, Words,
+        ClusterInfo, ClusterIterator, GlyphIndex, GlyphInfo, GlyphPosition, HyphenationLanguage,
+        IndexOfLineBreak, LayoutedGlyphs, LineBreaks, LineLength, RemainingSpaceToRight,
+        ScaledWord, ScaledWords, Word, WordIndex, WordPositions, WordType, Words,
     },
     display_list::GlyphInstance,
     ui_solver::{InlineTextLayout, ResolvedTextLayoutOptions, TextLayoutOptions},
 };
-use azul_css::{LayoutPoint, LayoutRect, LayoutSize};
+use azul_css::{
+    LayoutPoint, LayoutRect, LayoutSize, StyleHyphens, StyleOverflowWrap, StyleTextDirection,
+    StyleTextOverflow, StyleWhiteSpace, StyleWordBreak,
+};
 
 /// Whether the text overflows the parent rectangle, and if yes, by how many pixels,
 /// necessary for determining if / how to show a scrollbar + aligning / centering text.
@@ -24,15 +27,36 @@ pub enum TextOverflow {
     InBounds(f32),
 }
 
+/// Soft hyphen, marks a position where a word can be hyphenated (`hyphens: manual`)
+const SOFT_HYPHEN: char = '\u{00AD}';
+
 /// Splits the text by whitespace into logical units (word, tab, return, whitespace).
+///
+/// Words are also split at the line break opportunities of the Unicode line breaking
+/// algorithm (UAX #14) that are inside of them, i.e. after the `/` of an URL, after a
+/// hyphen or between two CJK characters (`WordType::BreakOpportunity`). Soft hyphens
+/// split the word as well (`WordType::Hyphen`).
 pub fn split_text_into_words(text: &str) -> Words {
     use unicode_normalization::UnicodeNormalization;
+    use xi_unicode::LineBreakIterator;
 
     // Necessary because we need to handle both \n and \r\n characters
     // If we just look at the characters one-by-one, this wouldn't be possible.
     let normalized_string = text.nfc().collect::<String>();
     let normalized_chars = normalized_string.chars().collect::<Vec<char>>();
 
+    // Whether the line can break before the character at the index
+    let char_byte_offsets = normalized_string
+        .char_indices()
+        .map(|(byte_offset, _)| byte_offset)
+        .collect::<Vec<_>>();
+    let mut break_opportunities = vec![false; normalized_chars.len()];
+    for (byte_offset, _is_hard_break) in LineBreakIterator::new(&normalized_string) {
+        if let Ok(char_idx) = char_byte_offsets.binary_search(&byte_offset) {
+            break_opportunities[char_idx] = true;
+        }
+    }
+
     let mut words = Vec::new();
 
     // Instead of storing the actual word, the word is only stored as an index instead,
@@ -48,6 +72,38 @@ pub fn split_text_into_words(text: &str) -> Words {
         let ch = *ch;
         let current_char_is_whitespace = ch == ' ' || ch == '\t' || ch == '\r' || ch == '\n';
 
+        // Split the current word at soft hyphens and at break opportunities
+        if ch == SOFT_HYPHEN {
+            if ch_idx > current_word_start {
+                words.push(Word {
+                    start: current_word_start,
+                    end: ch_idx,
+                    word_type: WordType::Word,
+                });
+            }
+            words.push(Word {
+                start: ch_idx,
+                end: ch_idx + 1,
+                word_type: WordType::Hyphen,
+            });
+            current_word_start = ch_idx + 1;
+        } else if !current_char_is_whitespace
+            && ch_idx > current_word_start
+            && break_opportunities[ch_idx]
+        {
+            words.push(Word {
+                start: current_word_start,
+                end: ch_idx,
+                word_type: WordType::Word,
+            });
+            words.push(Word {
+                start: ch_idx,
+                end: ch_idx,
+                word_type: WordType::BreakOpportunity,
+            });
+            current_word_start = ch_idx;
+        }
+
         let should_push_delimiter = match ch {
             ' ' => Some(Word {
                 start: last_char_idx + 1,
@@ -80,7 +136,10 @@ pub fn split_text_into_words(text: &str) -> Words {
         };
 
         // Character is a whitespace or the character is the last character in the text (end of text)
-        let should_push_word = if current_char_is_whitespace && !last_char_was_whitespace {
+        let should_push_word = if current_char_is_whitespace
+            && !last_char_was_whitespace
+            && current_word_start < ch_idx
+        {
             Some(Word {
                 start: current_word_start,
                 end: ch_idx,
@@ -129,6 +188,278 @@ pub fn split_text_into_words(text: &str) -> Words {
     }
 }
 
+/// Joins the words of the spans of a rich text paragraph, so that the
+/// spans can be laid out as one text block.
+///
+/// Note that a word that continues across a span boundary is split into two
+/// words (without a space between them), which are kept on the same line.
+pub fn join_span_words(span_words: &[Words]) -> Words {
+    let mut joined = Words {
+        items: Vec::new(),
+        internal_str: String::new(),
+        internal_chars: Vec::new(),
+    };
+
+    for words in span_words {
+        let offset = joined.internal_chars.len();
+        joined.items.extend(words.items.iter().map(|word| Word {
+            start: word.start + offset,
+            end: word.end + offset,
+            word_type: word.word_type,
+        }));
+        joined.internal_str.push_str(&words.internal_str);
+        joined
+            .internal_chars
+            .extend(words.internal_chars.iter().cloned());
+    }
+
+    joined
+}
+
+/// Options that control where the lines of a text can break, see `apply_line_break_options`
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct LineBreakOptions {
+    pub white_space: StyleWhiteSpace,
+    pub word_break: StyleWordBreak,
+    pub overflow_wrap: StyleOverflowWrap,
+    pub hyphens: StyleHyphens,
+    /// Dictionary that the words are hyphenated with if `hyphens` is `Auto`
+    pub hyphenation_language: HyphenationLanguage,
+}
+
+/// Adjusts the whitespace and the break opportunities of the words (see
+/// `split_text_into_words`) to the `white-space`, `word-break`, `overflow-wrap`
+/// and `hyphens` of the text:
+///
+/// - `white-space: normal` and `nowrap` collapse every sequence of spaces, tabs and
+///   returns into a single `WordType::Space`, leading whitespace is removed
+/// - `word-break: break-all` allows breaks between any two characters of a word,
+///   `keep-all` removes the break opportunities between two letters (i.e. CJK characters)
+/// - `overflow-wrap: break-word` inserts `WordType::EmergencyBreak`s between the characters
+/// - `hyphens: none` removes the soft hyphens, `hyphens: auto` additionally inserts the
+///   hyphenation points of the dictionary as `WordType::Hyphen`s
+pub fn apply_line_break_options(words: &Words, options: &LineBreakOptions) -> Words {
+    use crate::text_shaping::continues_cluster;
+
+    let chars = &words.internal_chars;
+    let is_letter = |ch_idx: usize| chars.get(ch_idx).map_or(false, |c| c.is_alphanumeric());
+    let mut items = Vec::with_capacity(words.items.len());
+
+    for word in words.items.iter() {
+        match word.word_type {
+            WordType::Word => {}
+            WordType::Space | WordType::Tab | WordType::Return
+                if options.white_space.collapses_whitespace() =>
+            {
+                match items.last_mut() {
+                    None => {}
+                    Some(Word {
+                        word_type: WordType::Space,
+                        end,
+                        ..
+                    }) => *end = word.end,
+                    Some(_) => items.push(Word {
+                        word_type: WordType::Space,
+                        ..*word
+                    }),
+                }
+                continue;
+            }
+            WordType::Hyphen if options.hyphens == StyleHyphens::None => continue,
+            WordType::BreakOpportunity
+                if options.word_break == StyleWordBreak::KeepAll
+                    && word.start > 0
+                    && is_letter(word.start - 1)
+                    && is_letter(word.start) =>
+            {
+                continue
+            }
+            _ => {
+                items.push(*word);
+                continue;
+            }
+        }
+
+        let hyphenation_points = match options.hyphens {
+            StyleHyphens::Auto => {
+                hyphenate(&chars[word.start..word.end], options.hyphenation_language)
+            }
+            _ => Vec::new(),
+        };
+
+        // Split the word between the clusters where the line may break
+        let mut fragment_start = word.start;
+        for ch_idx in (word.start + 1)..word.end {
+            if continues_cluster(chars[ch_idx]) {
+                continue;
+            }
+            let word_type = if hyphenation_points.contains(&(ch_idx - word.start)) {
+                WordType::Hyphen
+            } else if options.word_break == StyleWordBreak::BreakAll {
+                WordType::BreakOpportunity
+            } else if options.overflow_wrap != StyleOverflowWrap::Normal {
+                WordType::EmergencyBreak
+            } else {
+                continue;
+            };
+            items.push(Word {
+                start: fragment_start,
+                end: ch_idx,
+                word_type: WordType::Word,
+            });
+            items.push(Word {
+                start: ch_idx,
+                end: ch_idx,
+                word_type,
+            });
+            fragment_start = ch_idx;
+        }
+
+        items.push(Word {
+            start: fragment_start,
+            end: word.end,
+            word_type: WordType::Word,
+        });
+    }
+
+    Words {
+        items,
+        internal_str: words.internal_str.clone(),
+        internal_chars: words.internal_chars.clone(),
+    }
+}
+
+/// Returns the offsets of the characters of the `word` before which the word can be
+/// hyphenated, according to the hyphenation dictionary of the `language`. Only words
+/// that consist of letters are hyphenated.
+#[cfg(feature = "hyphenation")]
+fn hyphenate(word: &[char], language: HyphenationLanguage) -> Vec<usize> {
+    use hyphenation::{Hyphenator, Language, Load, Standard};
+    use std::{collections::BTreeMap, sync::Mutex};
+
+    lazy_static! {
+        /// Dictionaries are only loaded once they are used (`None` if loading failed)
+        static ref DICTIONARIES: Mutex<BTreeMap<HyphenationLanguage, Option<Standard>>> =
+            Mutex::new(BTreeMap::new());
+    }
+
+    if !word.iter().all(|c| c.is_alphabetic()) {
+        return Vec::new();
+    }
+
+    // The patterns of the dictionaries are lowercase
+    let lowercase_word = word
+        .iter()
+        .flat_map(|c| c.to_lowercase())
+        .collect::<String>();
+    let char_byte_offsets = lowercase_word
+        .char_indices()
+        .map(|(byte_offset, _)| byte_offset)
+        .collect::<Vec<_>>();
+    if char_byte_offsets.len() != word.len() {
+        return Vec::new();
+    }
+
+    let mut dictionaries = match DICTIONARIES.lock() {
+        Ok(d) => d,
+        Err(_) => return Vec::new(),
+    };
+
+    let dictionary = dictionaries.entry(language).or_insert_with(|| {
+        let language = match language {
+            HyphenationLanguage::EnglishUS => Language::EnglishUS,
+            HyphenationLanguage::EnglishGB => Language::EnglishGB,
+            HyphenationLanguage::German => Language::German1996,
+            HyphenationLanguage::French => Language::French,
+            HyphenationLanguage::Spanish => Language::Spanish,
+            HyphenationLanguage::Italian => Language::Italian,
+            HyphenationLanguage::Dutch => Language::Dutch,
+        };
+        Standard::from_embedded(language).ok()
+    });
+
+    match dictionary {
+        Some(dictionary) => dictionary
+            .hyphenate(&lowercase_word)
+            .breaks
+            .iter()
+            .filter_map(|byte_offset| char_byte_offsets.binary_search(byte_offset).ok())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(not(feature = "hyphenation"))]
+fn hyphenate(_word: &[char], _language: HyphenationLanguage) -> Vec<usize> {
+    Vec::new()
+}
+
+/// Joins the scaled words of the spans of a rich text paragraph. Each span is scaled with
+/// its own font fallback chain, given as the number of fonts in the chain: the fonts of all
+/// spans are concatenated, so the `glyph_fonts` are offset by the fonts of the previous spans.
+///
+/// The space advance and baseline are taken from the span with the largest font size,
+/// so that the lines of the paragraph don't overlap.
+pub fn join_span_scaled_words(span_scaled_words: &[(&ScaledWords, usize)]) -> ScaledWords {
+    use std::cmp::Ordering;
+
+    let largest_span = span_scaled_words
+        .iter()
+        .map(|(scaled_words, _)| *scaled_words)
+        .max_by(|a, b| {
+            a.font_size_px
+                .partial_cmp(&b.font_size_px)
+                .unwrap_or(Ordering::Equal)
+        });
+
+    let mut joined = match largest_span {
+        Some(largest) => ScaledWords {
+            font_size_px: largest.font_size_px,
+            baseline_px: largest.baseline_px,
+            items: Vec::new(),
+            longest_word_width: 0.0,
+            space_advance_px: largest.space_advance_px,
+            space_codepoint: largest.space_codepoint,
+            hyphen_glyphs: Vec::new(),
+            ellipsis_glyphs: Vec::new(),
+            font_metrics: Vec::new(),
+        },
+        None => ScaledWords::default(),
+    };
+
+    let mut font_offset = 0;
+
+    for (scaled_words, number_of_fonts) in span_scaled_words {
+        joined
+            .items
+            .extend(scaled_words.items.iter().map(|scaled_word| {
+                ScaledWord {
+                    glyph_fonts: scaled_word
+                        .glyph_fonts
+                        .iter()
+                        .map(|font| font + font_offset)
+                        .collect(),
+                    ..scaled_word.clone()
+                }
+            }));
+        joined.longest_word_width = joined
+            .longest_word_width
+            .max(scaled_words.longest_word_width);
+        joined
+            .hyphen_glyphs
+            .extend(scaled_words.hyphen_glyphs.iter().cloned());
+        joined
+            .ellipsis_glyphs
+            .extend(scaled_words.ellipsis_glyphs.iter().cloned());
+        joined
+            .font_metrics
+            .extend(scaled_words.font_metrics.iter().cloned());
+        font_offset += number_of_fonts;
+    }
+
+    joined
+}
+
 /// Takes a text broken into semantic items and a font instance and
 /// scales the font accordingly.
 pub fn words_to_scaled_words(
@@ -137,72 +468,154 @@ pub fn words_to_scaled_words(
     font_index: u32,
     font_size_px: f32,
 ) -> ScaledWords {
-    use crate::text_shaping::{self, HbBuffer, HbFont, HbScaledFont, HB_SCALE_FACTOR};
-    use std::char;
-    use std::mem;
+    let text_layout_options = ResolvedTextLayoutOptions {
+        font_size_px,
+        ..Default::default()
+    };
+    words_to_scaled_words_with_fallback(
+        words,
+        &[(font_bytes, font_index)],
+        &text_layout_options,
+        StyleTextDirection::Ltr,
+    )
+}
 
-    let hb_font = HbFont::from_bytes(font_bytes, font_index);
-    let hb_scaled_font = HbScaledFont::from_font(&hb_font, font_size_px);
+/// Same as `words_to_scaled_words`, but takes a font fallback chain: the first font of the
+/// `fonts` (bytes + font index) is the primary font, characters that are missing in the
+/// primary font are shaped with the first of the following fonts that contains them.
+///
+/// The `direction` is the base direction of the text: the bidi levels of the words are
+/// resolved relative to it and the glyphs of each word are stored in visual order.
+///
+/// The words are shaped with the font size, OpenType features and variation axis values
+/// of the `text_layout_options`, the other options only influence the layout of the words.
+///
+/// The font of each glyph is stored in `ScaledWord::glyph_fonts`. Panics if `fonts` is empty.
+pub fn words_to_scaled_words_with_fallback(
+    words: &Words,
+    fonts: &[(&[u8], u32)],
+    text_layout_options: &ResolvedTextLayoutOptions,
+    direction: StyleTextDirection,
+) -> ScaledWords {
+    use crate::text_shaping::{
+        self, FallbackGlyph, HbBuffer, HbFont, HbScaledFont, HB_SCALE_FACTOR,
+    };
+    use std::{char, iter};
+    use unicode_bidi::{BidiInfo, Level};
+
+    let font_size_px = text_layout_options.font_size_px;
+    let hb_fonts = fonts
+        .iter()
+        .map(|(font_bytes, font_index)| HbFont::from_bytes(font_bytes, *font_index))
+        .collect::<Vec<_>>();
+    let hb_scaled_fonts = hb_fonts
+        .iter()
+        .map(|hb_font| HbScaledFont::from_font(hb_font, text_layout_options))
+        .collect::<Vec<_>>();
 
-    // Get the dimensions of the space glyph
+    // Get the dimensions of the space glyph (of the primary font)
     let hb_space_buffer = HbBuffer::from_str(" ");
-    let hb_shaped_space = text_shaping::shape_word_hb(&hb_space_buffer, &hb_scaled_font);
+    let hb_shaped_space = text_shaping::shape_word_hb(&hb_space_buffer, &hb_scaled_fonts[0]);
     let space_advance_px = hb_shaped_space.glyph_positions[0].x_advance as f32 / HB_SCALE_FACTOR;
     let space_codepoint = hb_shaped_space.glyph_infos[0].codepoint;
 
+    // Get the hyphen and ellipsis glyph of every font, which are inserted at the end of
+    // hyphenated / cut off lines
+    let get_glyph_of_every_font = |character: &str| {
+        let hb_buffer = HbBuffer::from_str(character);
+        hb_scaled_fonts
+            .iter()
+            .map(|hb_scaled_font| {
+                let hb_shaped_glyph = text_shaping::shape_word_hb(&hb_buffer, hb_scaled_font);
+                (
+                    hb_shaped_glyph.glyph_infos[0].codepoint,
+                    hb_shaped_glyph.glyph_positions[0].x_advance as f32 / HB_SCALE_FACTOR,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let hyphen_glyphs = get_glyph_of_every_font("-");
+    let ellipsis_glyphs = get_glyph_of_every_font("\u{2026}");
+    let font_metrics = hb_fonts
+        .iter()
+        .map(|hb_font| hb_font.get_font_metrics(font_size_px))
+        .collect();
+
     let internal_str = words.internal_str.replace(char::is_whitespace, " ");
 
-    let hb_buffer_entire_paragraph = HbBuffer::from_str(&internal_str);
-    let hb_shaped_entire_paragraph =
-        text_shaping::shape_word_hb(&hb_buffer_entire_paragraph, &hb_scaled_font);
-
-    let mut shaped_word_positions = Vec::<Vec<GlyphPosition>>::new();
-    let mut shaped_word_infos = Vec::<Vec<GlyphInfo>>::new();
-    let mut current_word_positions = Vec::new();
-    let mut current_word_infos = Vec::new();
-
-    for i in 0..hb_shaped_entire_paragraph.glyph_positions.len() {
-        let glyph_info = hb_shaped_entire_paragraph.glyph_infos[i];
-        let glyph_position = hb_shaped_entire_paragraph.glyph_positions[i];
-
-        let is_space = glyph_info.codepoint == space_codepoint;
-        if is_space {
-            shaped_word_positions.push(current_word_positions.clone());
-            shaped_word_infos.push(current_word_infos.clone());
-            current_word_positions.clear();
-            current_word_infos.clear();
-        } else {
-            // azul-core::GlyphInfo and hb_position_t have the same size / layout
-            // (both are repr(C)), so it's safe to just transmute them here
-            current_word_positions.push(unsafe { mem::transmute(glyph_position) });
-            current_word_infos.push(unsafe { mem::transmute(glyph_info) });
+    let base_level = match direction {
+        StyleTextDirection::Ltr => Level::ltr(),
+        StyleTextDirection::Rtl => Level::rtl(),
+    };
+    let bidi_info = BidiInfo::new(&internal_str, Some(base_level));
+    let bidi_levels = bidi_info
+        .levels
+        .iter()
+        .map(|level| level.number())
+        .collect::<Vec<_>>();
+
+    let shaped_paragraph =
+        text_shaping::shape_text_with_fallback(&internal_str, &hb_scaled_fonts, &bidi_levels);
+
+    // Assign the glyphs to the words via the character that the glyph was shaped from,
+    // glyphs of the characters between the words (spaces, soft hyphens) are dropped
+    let char_byte_offsets = internal_str
+        .char_indices()
+        .map(|(byte_offset, _)| byte_offset)
+        .chain(iter::once(internal_str.len()))
+        .collect::<Vec<_>>();
+    let mut word_of_byte = vec![None; internal_str.len()];
+    let text_words = words
+        .items
+        .iter()
+        .filter(|w| w.word_type == WordType::Word)
+        .collect::<Vec<_>>();
+    for (word_idx, word) in text_words.iter().enumerate() {
+        let word_bytes = char_byte_offsets[word.start]..char_byte_offsets[word.end];
+        for word_of in &mut word_of_byte[word_bytes] {
+            *word_of = Some(word_idx);
         }
     }
 
-    if !current_word_positions.is_empty() {
-        shaped_word_positions.push(current_word_positions);
-        shaped_word_infos.push(current_word_infos);
+    let mut shaped_words = vec![Vec::<FallbackGlyph>::new(); text_words.len()];
+    for glyph in shaped_paragraph {
+        if let Some(Some(word_idx)) = word_of_byte.get(glyph.glyph_info.cluster as usize) {
+            shaped_words[*word_idx].push(glyph);
+        }
     }
 
     let mut longest_word_width = 0.0_f32;
 
-    let scaled_words = words
-        .items
+    let scaled_words = shaped_words
         .iter()
-        .filter(|w| w.word_type == WordType::Word)
-        .enumerate()
-        .filter_map(|(word_idx, word)| {
-            let hb_glyph_positions = shaped_word_positions.get(word_idx)?.clone();
-            let hb_glyph_infos = shaped_word_infos.get(word_idx)?.clone();
+        .map(|logical_word| {
+            let glyph_levels = logical_word
+                .iter()
+                .map(|g| g.bidi_level)
+                .collect::<Vec<_>>();
+            let shaped_word = reorder_visually(&glyph_levels)
+                .into_iter()
+                .map(|glyph_idx| logical_word[glyph_idx])
+                .collect::<Vec<_>>();
+            let hb_glyph_positions = shaped_word
+                .iter()
+                .map(|g| g.glyph_position)
+                .collect::<Vec<_>>();
             let hb_word_width = text_shaping::get_word_visual_width_hb(&hb_glyph_positions);
 
             longest_word_width = longest_word_width.max(hb_word_width.abs());
 
-            Some(ScaledWord {
-                glyph_infos: hb_glyph_infos,
+            ScaledWord {
+                glyph_infos: shaped_word.iter().map(|g| g.glyph_info).collect(),
                 glyph_positions: hb_glyph_positions,
+                glyph_fonts: shaped_word.iter().map(|g| g.font).collect(),
+                bidi_level: glyph_levels
+                    .iter()
+                    .cloned()
+                    .min()
+                    .unwrap_or(base_level.number()),
                 word_width: hb_word_width,
-            })
+            }
         })
         .collect();
 
@@ -213,11 +626,53 @@ pub fn words_to_scaled_words(
         longest_word_width: longest_word_width,
         space_advance_px,
         space_codepoint,
+        hyphen_glyphs,
+        ellipsis_glyphs,
+        font_metrics,
+    }
+}
+
+/// Returns the (logical) indices of the items in visual order, given the bidi level of
+/// each item (rule L2 of the Unicode bidi algorithm): starting at the highest level, every
+/// sequence of items at that level or higher is reversed, down to the lowest odd level.
+pub fn reorder_visually(levels: &[u8]) -> Vec<usize> {
+    let mut visual_order = (0..levels.len()).collect::<Vec<_>>();
+
+    let highest_level = levels.iter().cloned().max().unwrap_or(0);
+    let lowest_odd_level = match levels.iter().cloned().filter(|l| l % 2 == 1).min() {
+        Some(l) => l,
+        None => return visual_order,
+    };
+
+    for level in (lowest_odd_level..=highest_level).rev() {
+        let mut start = 0;
+        while start < visual_order.len() {
+            if levels[visual_order[start]] < level {
+                start += 1;
+                continue;
+            }
+            let mut end = start;
+            while end < visual_order.len() && levels[visual_order[end]] >= level {
+                end += 1;
+            }
+            visual_order[start..end].reverse();
+            start = end;
+        }
     }
+
+    visual_order
 }
 
 /// Positions the words on the screen (does not layout any glyph positions!), necessary for estimating
 /// the intrinsic width + height of the text content.
+///
+/// The lines break at the last break opportunity (space, tab, `WordType::BreakOpportunity` or
+/// `WordType::Hyphen`) before the word that doesn't fit on the line anymore: words that aren't
+/// separated by a break opportunity are moved onto the next line together. Only if such a
+/// group of words doesn't fit on a line by itself, it is broken at its emergency breaks.
+///
+/// If the text doesn't wrap (`white-space: nowrap` or `pre`), the lines only break at
+/// returns and overflow the `max_horizontal_width` instead.
 pub fn position_words(
     words: &Words,
     scaled_words: &ScaledWords,
@@ -240,9 +695,16 @@ pub fn position_words(
     let letter_spacing_px = text_layout_options
         .letter_spacing
         .unwrap_or(DEFAULT_LETTER_SPACING);
+    let max_line_width = if text_layout_options.white_space.wraps() {
+        text_layout_options.max_horizontal_width
+    } else {
+        None
+    };
 
     let mut line_breaks = Vec::new();
+    let mut hyphenated_words = Vec::new();
     let mut word_positions = Vec::new();
+    let mut word_advances = Vec::new();
 
     let mut line_number = 0;
     let mut line_caret_x = 0.0;
@@ -256,7 +718,7 @@ pub fn position_words(
                 font_size_px,
                 line_height_px,
                 &text_layout_options.holes[..],
-                text_layout_options.max_horizontal_width,
+                max_line_width,
             );
 
             if let LineCaretIntersection::PushCaretOntoNextLine(_, _) = caret_intersection {
@@ -275,106 +737,185 @@ pub fn position_words(
         advance_caret!(line_caret_x);
     }
 
-    // NOTE: word_idx increases only on words, not on other symbols!
-    let mut word_idx = 0;
-
-    macro_rules! handle_word {() => ({
-
-        let scaled_word = match scaled_words.items.get(word_idx) {
-            Some(s) => s,
-            None => continue,
-        };
-
+    // Calculates where the caret would be after the word
+    let get_word_advance = |word_idx: usize| {
+        let scaled_word = scaled_words.items.get(word_idx)?;
         let reserved_letter_spacing_px = match text_layout_options.letter_spacing {
             None => 0.0,
-            Some(spacing_multiplier) => spacing_multiplier * scaled_word.number_of_clusters().saturating_sub(1) as f32,
+            Some(spacing_multiplier) => {
+                spacing_multiplier * scaled_word.number_of_clusters().saturating_sub(1) as f32
+            }
         };
+        Some(scaled_word.word_width + reserved_letter_spacing_px)
+    };
 
-        // Calculate where the caret would be for the next word
-        let word_advance_x = scaled_word.word_width + reserved_letter_spacing_px;
+    let get_hyphen_advance = |word_idx: usize| {
+        scaled_words
+            .items
+            .get(word_idx)
+            .and_then(|scaled_word| get_hyphen_glyph(scaled_words, scaled_word))
+            .map_or(0.0, |(_, _, hyphen_advance)| hyphen_advance)
+    };
 
-        let mut new_caret_x = line_caret_x + word_advance_x;
+    // Whether the previous item is a `Hyphen`, i.e. whether the previous word
+    // has to be followed by a hyphen if the line breaks before the next word
+    let mut is_after_hyphen = false;
 
-        // NOTE: Slightly modified "advance_caret!(new_caret_x);" - due to line breaking behaviour
+    // Places the words (given as their advances) as one unit: if they don't fit onto the
+    // current line anymore, the line breaks before the first word. `$reserved_px` is
+    // space after the words that has to be free, but that the caret doesn't advance by.
+    macro_rules! place_words {
+        ($word_advances:expr, $reserved_px:expr) => {{
+            let unit_advances: &[f32] = $word_advances;
+            let mut new_caret_x = line_caret_x + unit_advances.iter().sum::<f32>() + $reserved_px;
 
-        let caret_intersection = caret_intersects_with_holes(
-            new_caret_x,
-            line_number,
-            font_size_px,
-            line_height_px,
-            &text_layout_options.holes,
-            text_layout_options.max_horizontal_width,
-        );
+            let caret_intersection = caret_intersects_with_holes(
+                new_caret_x,
+                line_number,
+                font_size_px,
+                line_height_px,
+                &text_layout_options.holes,
+                max_line_width,
+            );
 
-        let mut is_line_break = false;
-        if let LineCaretIntersection::PushCaretOntoNextLine(_, _) = caret_intersection {
-            line_breaks.push((current_word_idx, line_caret_x));
-            is_line_break = true;
-        }
+            let is_line_break = match caret_intersection {
+                LineCaretIntersection::PushCaretOntoNextLine(_, _) => true,
+                _ => false,
+            };
+
+            if is_line_break {
+                let mut line_length = line_caret_x;
+                if is_after_hyphen && current_word_idx > 0 {
+                    hyphenated_words.push(current_word_idx - 1);
+                    line_length += get_hyphen_advance(current_word_idx - 1);
+                }
+                line_breaks.push((current_word_idx, line_length));
+            }
+
+            // If there was a line break, the words are positioned after the line break happened
+            let mut word_caret_x = line_caret_x;
+            advance_caret(&mut new_caret_x, &mut line_number, caret_intersection);
+            if is_line_break {
+                word_caret_x = new_caret_x;
+            }
 
-        if !is_line_break {
             let line_caret_y = get_line_y_position(line_number, font_size_px, line_height_px);
-            word_positions.push(LayoutPoint::new(line_caret_x, line_caret_y));
-        }
+            for word_advance_x in unit_advances {
+                word_positions.push(LayoutPoint::new(word_caret_x, line_caret_y));
+                word_advances.push(*word_advance_x);
+                word_caret_x += word_advance_x;
+            }
 
-        // Correct and advance the line caret position
-        advance_caret(
-            &mut new_caret_x,
-            &mut line_number,
-            caret_intersection,
-        );
+            line_caret_x = match caret_intersection {
+                LineCaretIntersection::AdvanceCaretTo(_) => new_caret_x,
+                _ => word_caret_x,
+            };
 
-        line_caret_x = new_caret_x;
+            // NOTE: Word index is increased after pushing, since word indices are 1-indexed
+            // (so that paragraphs can be selected via "(0..word_index)").
+            current_word_idx += unit_advances.len();
+            is_after_hyphen = false;
+        }};
+    }
 
-        // If there was a line break, the position needs to be determined after the line break happened
-        if is_line_break {
-            let line_caret_y = get_line_y_position(line_number, font_size_px, line_height_px);
-            word_positions.push(LayoutPoint::new(line_caret_x, line_caret_y));
-            // important! - if the word is pushed onto the next line, the caret has to be
-            // advanced by that words width!
-            line_caret_x += word_advance_x;
-        }
+    let mut item_idx = 0;
 
-        // NOTE: Word index is increased before pushing, since word indices are 1-indexed
-        // (so that paragraphs can be selected via "(0..word_index)").
-        word_idx += 1;
-        current_word_idx = word_idx;
-    })}
+    while let Some(word) = words.items.get(item_idx) {
+        // The last item is a bit special: Any trailing Return, Space or Tab characters are ignored
+        let is_last_item = item_idx + 1 == words.items.len();
 
-    // The last word is a bit special: Any text must have at least one line break!
-    for word in words.items.iter().take(words.items.len().saturating_sub(1)) {
         match word.word_type {
             Word => {
-                handle_word!();
+                // Collect the words up to the next break opportunity, split
+                // into units at the emergency breaks between them
+                let mut units = vec![0];
+                while let Some(item) = words.items.get(item_idx) {
+                    match item.word_type {
+                        Word => {
+                            if let Some(unit) = units.last_mut() {
+                                *unit += 1;
+                            }
+                        }
+                        EmergencyBreak => units.push(0),
+                        _ => break,
+                    }
+                    item_idx += 1;
+                }
+
+                let number_of_words = units.iter().sum::<usize>();
+                let advances = match (current_word_idx..current_word_idx + number_of_words)
+                    .map(get_word_advance)
+                    .collect::<Option<Vec<_>>>()
+                {
+                    Some(s) => s,
+                    None => break,
+                };
+
+                let fits_on_a_line = max_line_width
+                    .map_or(true, |max_width| advances.iter().sum::<f32>() <= max_width);
+                if fits_on_a_line {
+                    units = vec![number_of_words];
+                }
+
+                // In case that the line breaks at the following hyphen, the hyphen has
+                // to fit onto the line as well
+                let reserved_hyphen_px = match words.items.get(item_idx) {
+                    Some(next) if next.word_type == Hyphen => {
+                        get_hyphen_advance(current_word_idx + number_of_words - 1)
+                    }
+                    _ => 0.0,
+                };
+
+                let mut unit_start = 0;
+                for (unit_idx, unit_len) in units.iter().enumerate() {
+                    let is_last_unit = unit_idx + 1 == units.len();
+                    let unit_advances = &advances[unit_start..unit_start + unit_len];
+                    if is_last_unit {
+                        place_words!(unit_advances, reserved_hyphen_px);
+                    } else {
+                        place_words!(unit_advances, 0.0);
+                    }
+                    unit_start += unit_len;
+                }
+
+                continue;
             }
-            Return => {
+            Return if !is_last_item => {
                 line_breaks.push((current_word_idx, line_caret_x));
                 line_number += 1;
                 let mut new_caret_x = 0.0;
                 advance_caret!(new_caret_x);
                 line_caret_x = new_caret_x;
             }
-            Space => {
+            Space if !is_last_item => {
                 let mut new_caret_x = line_caret_x + word_spacing_px;
                 advance_caret!(new_caret_x);
                 line_caret_x = new_caret_x;
             }
-            Tab => {
+            Tab if !is_last_item => {
                 let mut new_caret_x = line_caret_x + word_spacing_px + tab_width_px;
                 advance_caret!(new_caret_x);
                 line_caret_x = new_caret_x;
             }
+            _ => {}
         }
+
+        is_after_hyphen =
+            word.word_type == Hyphen && item_idx > 0 && words.items[item_idx - 1].word_type == Word;
+        item_idx += 1;
     }
 
-    // Handle the last word, but ignore any last Return, Space or Tab characters
-    for word in &words.items[words.items.len().saturating_sub(1)..] {
-        if word.word_type == Word {
-            handle_word!();
-        }
+    if !words.items.is_empty() {
         line_breaks.push((current_word_idx, line_caret_x));
     }
 
+    reorder_lines_visually(
+        &line_breaks,
+        &word_advances,
+        scaled_words,
+        &mut word_positions,
+    );
+
     let trailing = line_caret_x;
     let number_of_lines = line_number + 1;
     let number_of_words = current_word_idx + 1;
@@ -397,15 +938,79 @@ pub fn position_words(
         content_size,
         word_positions,
         line_breaks,
+        hyphenated_words,
+    }
+}
+
+/// Returns the font, glyph index and advance of the hyphen that is inserted after the
+/// `scaled_word` if the line is hyphenated after it: the hyphen is rendered in the font
+/// of the last glyph of the word, `None` if that font doesn't contain a hyphen
+fn get_hyphen_glyph(
+    scaled_words: &ScaledWords,
+    scaled_word: &ScaledWord,
+) -> Option<(usize, u32, f32)> {
+    let font = scaled_word.glyph_fonts.last().cloned().unwrap_or(0);
+    get_glyph_in_font(&scaled_words.hyphen_glyphs, font)
+}
+
+/// Returns the font, glyph index and advance of an inserted glyph (see
+/// `ScaledWords::hyphen_glyphs`) in the `font`, `None` if that font doesn't contain it
+fn get_glyph_in_font(glyphs: &[(u32, f32)], font: usize) -> Option<(usize, u32, f32)> {
+    match glyphs.get(font)? {
+        (0, _) => None,
+        (codepoint, advance) => Some((font, *codepoint, *advance)),
+    }
+}
+
+/// Reorders the words of each line from logical into visual order, using the bidi levels
+/// of the words. The gaps between the words (spaces, tabs) stay in place, only the
+/// words are moved. Lines that only contain left-to-right words are not touched.
+fn reorder_lines_visually(
+    line_breaks: &[(usize, f32)],
+    word_advances: &[f32],
+    scaled_words: &ScaledWords,
+    word_positions: &mut [LayoutPoint],
+) {
+    let mut line_start = 0;
+
+    for (line_end, _) in line_breaks {
+        let line_end = (*line_end).min(word_positions.len());
+        if line_end <= line_start {
+            continue;
+        }
+
+        let levels = scaled_words.items[line_start..line_end]
+            .iter()
+            .map(|w| w.bidi_level)
+            .collect::<Vec<_>>();
+
+        if levels.iter().any(|l| *l > 0) {
+            let positions = &mut word_positions[line_start..line_end];
+            let advances = &word_advances[line_start..line_end];
+            let gaps = (1..positions.len())
+                .map(|i| positions[i].x - (positions[i - 1].x + advances[i - 1]))
+                .collect::<Vec<_>>();
+
+            let mut caret_x = positions[0].x;
+            for (visual_idx, logical_idx) in reorder_visually(&levels).into_iter().enumerate() {
+                positions[logical_idx].x = caret_x;
+                caret_x += advances[logical_idx] + gaps.get(visual_idx).cloned().unwrap_or(0.0);
+            }
+        }
+
+        line_start = line_end;
     }
 }
 
 /// Returns the (left-aligned!) bounding boxes of the indidividual text lines
+///
+/// If the text has a `line-clamp` (`max_lines`), the lines after it are cut off.
 pub fn word_positions_to_inline_text_layout(
     word_positions: &WordPositions,
     scaled_words: &ScaledWords,
 ) -> InlineTextLayout {
     use azul_core::ui_solver::InlineTextLine;
+    use std::usize;
 
     let font_size_px = word_positions.text_layout_options.font_size_px;
     let space_advance = scaled_words.space_advance_px;
@@ -415,6 +1020,10 @@ pub fn word_positions_to_inline_text_layout(
             .line_height
             .unwrap_or(DEFAULT_LINE_HEIGHT);
     let content_width = word_positions.content_size.width;
+    let max_lines = word_positions
+        .text_layout_options
+        .max_lines
+        .unwrap_or(usize::MAX);
 
     let mut last_word_index = 0;
 
@@ -422,6 +1031,7 @@ pub fn word_positions_to_inline_text_layout(
         lines: word_positions
             .line_breaks
             .iter()
+            .take(max_lines)
             .enumerate()
             .map(|(line_number, (word_idx, line_length))| {
                 let start_word_idx = last_word_index;
@@ -446,6 +1056,11 @@ pub fn word_positions_to_inline_text_layout(
     }
 }
 
+/// Returns the positioned glyphs of the text.
+///
+/// Lines that are wider than the `max_horizontal_width` are cut off and end with an
+/// ellipsis if the text has `text-overflow: ellipsis`, the last line ends with an ellipsis
+/// if the following lines were cut off by the `line-clamp`.
 pub fn get_layouted_glyphs(
     word_positions: &WordPositions,
     scaled_words: &ScaledWords,
@@ -454,41 +1069,188 @@ pub fn get_layouted_glyphs(
 ) -> LayoutedGlyphs {
     use crate::text_shaping;
 
-    let letter_spacing_px = word_positions
-        .text_layout_options
-        .letter_spacing
-        .unwrap_or(0.0);
+    let text_layout_options = &word_positions.text_layout_options;
+    let letter_spacing_px = text_layout_options.letter_spacing.unwrap_or(0.0);
+    let is_clamped = inline_text_layout.lines.len() < word_positions.line_breaks.len();
+
     let mut all_glyphs = Vec::with_capacity(scaled_words.items.len());
+    let mut all_glyph_fonts = Vec::with_capacity(scaled_words.items.len());
+    let mut line_baselines = Vec::with_capacity(inline_text_layout.lines.len());
 
-    for line in inline_text_layout.lines.iter() {
+    for (line_idx, line) in inline_text_layout.lines.iter().enumerate() {
         let line_x = line.bounds.origin.x;
         let line_y = line.bounds.origin.y;
 
+        let is_overflowing = text_layout_options
+            .max_horizontal_width
+            .map_or(false, |max_width| line.bounds.size.width > max_width);
+        let has_ellipsis = (is_clamped && line_idx + 1 == inline_text_layout.lines.len())
+            || (is_overflowing && text_layout_options.text_overflow == StyleTextOverflow::Ellipsis);
+
         let scaled_words_in_this_line = &scaled_words.items[line.word_start..line.word_end];
         let word_positions_in_this_line =
             &word_positions.word_positions[line.word_start..line.word_end];
 
-        for (scaled_word, word_position) in scaled_words_in_this_line
+        // Glyphs are positioned relative to the start of the line first,
+        // so that they can be cut off at the max. width of the line
+        let mut line_glyphs = Vec::new();
+        let mut line_glyph_fonts = Vec::new();
+
+        for (word_idx, (scaled_word, word_position)) in scaled_words_in_this_line
             .iter()
             .zip(word_positions_in_this_line.iter())
+            .enumerate()
         {
             let mut glyphs = text_shaping::get_glyph_instances_hb(
                 &scaled_word.glyph_infos,
                 &scaled_word.glyph_positions,
             );
             for (glyph, cluster_info) in glyphs.iter_mut().zip(scaled_word.cluster_iter()) {
-                glyph.point.x += origin.x
-                    + line_x
-                    + word_position.x
-                    + (letter_spacing_px * cluster_info.cluster_idx as f32);
-                glyph.point.y += origin.y + line_y;
+                glyph.point.x +=
+                    word_position.x + (letter_spacing_px * cluster_info.cluster_idx as f32);
+            }
+
+            line_glyphs.append(&mut glyphs);
+            line_glyph_fonts.extend(scaled_word.glyph_fonts.iter().cloned());
+
+            // Insert the hyphen after the last word of a hyphenated line
+            let is_hyphenated = word_positions
+                .hyphenated_words
+                .binary_search(&(line.word_start + word_idx))
+                .is_ok();
+            let hyphen_glyph = if is_hyphenated && !has_ellipsis {
+                get_hyphen_glyph(scaled_words, scaled_word)
+            } else {
+                None
+            };
+            if let Some((font, codepoint, hyphen_advance)) = hyphen_glyph {
+                let letter_spacing =
+                    letter_spacing_px * scaled_word.number_of_clusters().saturating_sub(1) as f32;
+                line_glyphs.push(GlyphInstance {
+                    index: codepoint,
+                    point: LayoutPoint::new(
+                        word_position.x + scaled_word.word_width + letter_spacing,
+                        0.0,
+                    ),
+                    size: LayoutSize::new(hyphen_advance, 0.0),
+                });
+                line_glyph_fonts.push(font);
             }
+        }
+
+        if has_ellipsis {
+            insert_ellipsis(
+                &mut line_glyphs,
+                &mut line_glyph_fonts,
+                &scaled_words.ellipsis_glyphs,
+                text_layout_options.max_horizontal_width,
+            );
+        }
+
+        for glyph in line_glyphs.iter_mut() {
+            glyph.point.x += origin.x + line_x;
+            glyph.point.y += origin.y + line_y;
+        }
+
+        line_baselines.push((all_glyphs.len(), origin.y + line_y));
+        all_glyphs.append(&mut line_glyphs);
+        all_glyph_fonts.append(&mut line_glyph_fonts);
+    }
 
-            all_glyphs.append(&mut glyphs);
+    LayoutedGlyphs {
+        glyphs: all_glyphs,
+        glyph_fonts: all_glyph_fonts,
+        line_baselines,
+    }
+}
+
+/// Removes the glyphs at the end of a line (positioned relative to the start of the line)
+/// until the line fits into the `max_width` together with the ellipsis, then appends the
+/// ellipsis, in the font of the last remaining glyph
+fn insert_ellipsis(
+    line_glyphs: &mut Vec<GlyphInstance>,
+    line_glyph_fonts: &mut Vec<usize>,
+    ellipsis_glyphs: &[(u32, f32)],
+    max_width: Option<f32>,
+) {
+    let get_ellipsis_glyph = |glyph_fonts: &[usize]| {
+        get_glyph_in_font(ellipsis_glyphs, glyph_fonts.last().cloned().unwrap_or(0))
+    };
+
+    while let Some(last_glyph) = line_glyphs.last() {
+        let ellipsis_advance =
+            get_ellipsis_glyph(line_glyph_fonts).map_or(0.0, |(_, _, advance)| advance);
+        let fits_on_line = max_width.map_or(true, |max_width| {
+            last_glyph.point.x + last_glyph.size.width + ellipsis_advance <= max_width
+        });
+        if fits_on_line {
+            break;
         }
+        line_glyphs.pop();
+        line_glyph_fonts.pop();
     }
 
-    LayoutedGlyphs { glyphs: all_glyphs }
+    let ellipsis_x = line_glyphs
+        .last()
+        .map_or(0.0, |glyph| glyph.point.x + glyph.size.width);
+
+    if let Some((font, codepoint, ellipsis_advance)) = get_ellipsis_glyph(line_glyph_fonts) {
+        line_glyphs.push(GlyphInstance {
+            index: codepoint,
+            point: LayoutPoint::new(ellipsis_x, 0.0),
+            size: LayoutSize::new(ellipsis_advance, 0.0),
+        });
+        line_glyph_fonts.push(font);
+    }
+}
+
+/// Returns the bounds of each word (in word order), in the same coordinate space as the
+/// glyphs of `get_layouted_glyphs`. Each word covers the full height of its line, so that
+/// there are no gaps between the lines when hit-testing the words.
+pub fn get_word_bounds(
+    word_positions: &WordPositions,
+    scaled_words: &ScaledWords,
+    inline_text_layout: &InlineTextLayout,
+    origin: LayoutPoint,
+) -> Vec<LayoutRect> {
+    let font_size_px = word_positions.text_layout_options.font_size_px;
+    let line_height_px = scaled_words.space_advance_px
+        * word_positions
+            .text_layout_options
+            .line_height
+            .unwrap_or(DEFAULT_LINE_HEIGHT);
+    let letter_spacing_px = word_positions
+        .text_layout_options
+        .letter_spacing
+        .unwrap_or(0.0);
+
+    let mut word_bounds = Vec::with_capacity(scaled_words.items.len());
+
+    for line in inline_text_layout.lines.iter() {
+        let scaled_words_in_this_line = &scaled_words.items[line.word_start..line.word_end];
+        let word_positions_in_this_line =
+            &word_positions.word_positions[line.word_start..line.word_end];
+
+        for (scaled_word, word_position) in scaled_words_in_this_line
+            .iter()
+            .zip(word_positions_in_this_line.iter())
+        {
+            let letter_spacing =
+                letter_spacing_px * scaled_word.number_of_clusters().saturating_sub(1) as f32;
+            word_bounds.push(LayoutRect::new(
+                LayoutPoint::new(
+                    origin.x + line.bounds.origin.x + word_position.x,
+                    origin.y + line.bounds.origin.y - font_size_px,
+                ),
+                LayoutSize::new(
+                    scaled_word.word_width + letter_spacing,
+                    font_size_px + line_height_px,
+                ),
+            ));
+        }
+    }
+
+    word_bounds
 }
 
 pub fn word_item_is_return(item: &Word) -> bool {
@@ -520,7 +1282,7 @@ enum LineCaretIntersection {
 /// - `line_caret_x`: The current horizontal caret position
 /// - `line_number`: The current line number
 /// - `holes`: Whether the text should respect any rectangular regions
-///    where the text can't flow (preparation for inline / float layout).
+///    where the text can't flow (i.e. floating elements).
 /// - `max_width`: Does the text have a restriction on how wide it can be (in pixels)
 fn caret_intersects_with_holes(
     line_caret_x: f32,
@@ -541,48 +1303,30 @@ fn caret_intersects_with_holes(
         }
     }
 
-    for hole in holes {
-        let mut should_move_caret = false;
-        let mut current_line_advance = 0;
-        let mut new_line_number = line_number + current_line_advance;
-        let mut current_caret = LayoutPoint::new(
+    // Move the caret past every hole it is in: either to the right side of the hole
+    // or - if the hole extends to the end of the line - to the start of the next line.
+    // Repeat until the caret is free, since the new position can be inside of another hole.
+    loop {
+        let caret = LayoutPoint::new(
             new_line_caret_x.unwrap_or(line_caret_x),
-            get_line_y_position(new_line_number, font_size_px, line_height_px),
+            get_line_y_position(line_number + line_advance, font_size_px, line_height_px),
         );
 
-        // NOTE: holes need to be sorted by Y origin (from smallest to largest Y),
-        // and be sorted from left to right
-        while hole.contains(&current_caret) {
-            should_move_caret = true;
-            if let Some(max_width) = max_width {
-                if hole.origin.x + hole.size.width >= max_width {
-                    // Need to break the line here
-                    current_line_advance += 1;
-                    new_line_number = line_number + current_line_advance;
-                    current_caret = LayoutPoint::new(
-                        new_line_caret_x.unwrap_or(line_caret_x),
-                        get_line_y_position(new_line_number, font_size_px, line_height_px),
-                    );
-                } else {
-                    new_line_number = line_number + current_line_advance;
-                    current_caret = LayoutPoint::new(
-                        hole.origin.x + hole.size.width,
-                        get_line_y_position(new_line_number, font_size_px, line_height_px),
-                    );
-                }
-            } else {
-                // No max width, so no need to break the line, move the caret to the right side of the hole
-                new_line_number = line_number + current_line_advance;
-                current_caret = LayoutPoint::new(
-                    hole.origin.x + hole.size.width,
-                    get_line_y_position(new_line_number, font_size_px, line_height_px),
-                );
-            }
-        }
+        let hole = match holes.iter().find(|hole| hole.contains(&caret)) {
+            Some(s) => s,
+            None => break,
+        };
 
-        if should_move_caret {
-            new_line_caret_x = Some(current_caret.x);
-            line_advance += current_line_advance;
+        let hole_right = hole.origin.x + hole.size.width;
+        match max_width {
+            Some(max_width) if hole_right >= max_width => {
+                // Need to break the line here
+                new_line_caret_x = Some(0.0);
+                line_advance += 1;
+            }
+            _ => {
+                new_line_caret_x = Some(hole_right);
+            }
         }
     }
 
@@ -747,6 +1491,164 @@ fn test_split_words() {
     assert_words(&words_single_str_expected, &words_single_str);
 }
 
+#[test]
+fn test_join_span_words() {
+    let joined = join_span_words(&[
+        split_text_into_words("Hello "),
+        split_text_into_words("bold"),
+        split_text_into_words(" world"),
+    ]);
+
+    assert_eq!(joined.get_str(), "Hello bold world");
+    assert_eq!(
+        joined
+            .items
+            .iter()
+            .filter(|word| word.word_type == WordType::Word)
+            .map(|word| joined.get_substr(word))
+            .collect::<Vec<_>>(),
+        vec!["Hello", "bold", "world"]
+    );
+}
+
+#[test]
+fn test_line_break_opportunities() {
+    fn word_types(words: &Words) -> Vec<(String, WordType)> {
+        words
+            .items
+            .iter()
+            .map(|word| (words.get_substr(word), word.word_type))
+            .collect()
+    }
+
+    fn word(s: &str) -> (String, WordType) {
+        (s.to_string(), WordType::Word)
+    }
+
+    let opportunity = (String::new(), WordType::BreakOpportunity);
+
+    // URLs can break after the slash, CJK text between the characters
+    let words = split_text_into_words("azul.rs/guide 你好");
+    assert_eq!(
+        word_types(&words),
+        vec![
+            word("azul.rs/"),
+            opportunity.clone(),
+            word("guide"),
+            (" ".to_string(), WordType::Space),
+            word("你"),
+            opportunity.clone(),
+            word("好"),
+        ]
+    );
+
+    // `keep-all` keeps the CJK characters together, `hyphens: none` ignores soft hyphens
+    let words = split_text_into_words("extra\u{AD}ordinary 你好");
+    assert_eq!(
+        word_types(&words)[..3].to_vec(),
+        vec![
+            word("extra"),
+            ("\u{AD}".to_string(), WordType::Hyphen),
+            word("ordinary"),
+        ]
+    );
+    let kept_words = apply_line_break_options(
+        &words,
+        &LineBreakOptions {
+            word_break: StyleWordBreak::KeepAll,
+            hyphens: StyleHyphens::None,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        word_types(&kept_words),
+        vec![
+            word("extra"),
+            word("ordinary"),
+            (" ".to_string(), WordType::Space),
+            word("你"),
+            word("好"),
+        ]
+    );
+
+    // `break-all` breaks between all characters, `overflow-wrap` only in an emergency
+    let words = split_text_into_words("abc");
+    let broken_words = apply_line_break_options(
+        &words,
+        &LineBreakOptions {
+            word_break: StyleWordBreak::BreakAll,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        word_types(&broken_words),
+        vec![
+            word("a"),
+            opportunity.clone(),
+            word("b"),
+            opportunity.clone(),
+            word("c"),
+        ]
+    );
+    let wrapped_words = apply_line_break_options(
+        &words,
+        &LineBreakOptions {
+            overflow_wrap: StyleOverflowWrap::BreakWord,
+            ..Default::default()
+        },
+    );
+    assert_eq!(wrapped_words.items[1].word_type, WordType::EmergencyBreak);
+}
+
+#[test]
+fn test_collapse_whitespace() {
+    let words = split_text_into_words("  a \n\t b");
+    let get_word_types = |words: &Words| {
+        words
+            .items
+            .iter()
+            .map(|word| word.word_type)
+            .collect::<Vec<_>>()
+    };
+
+    // `white-space: normal` collapses the whitespace between the words, leading whitespace
+    // is removed
+    let collapsed_words = apply_line_break_options(&words, &LineBreakOptions::default());
+    assert_eq!(
+        get_word_types(&collapsed_words),
+        vec![WordType::Word, WordType::Space, WordType::Word]
+    );
+    assert_eq!(
+        collapsed_words.get_substr(&collapsed_words.items[1]),
+        " \n\t "
+    );
+
+    // `white-space: pre` preserves all whitespace
+    let preserved_words = apply_line_break_options(
+        &words,
+        &LineBreakOptions {
+            white_space: StyleWhiteSpace::Pre,
+            ..Default::default()
+        },
+    );
+    assert_eq!(get_word_types(&preserved_words), get_word_types(&words));
+}
+
+#[cfg(feature = "hyphenation")]
+#[test]
+fn test_hyphenate() {
+    let word = "hyphenation".chars().collect::<Vec<_>>();
+    let hyphenation_points = hyphenate(&word, HyphenationLanguage::EnglishUS);
+    assert!(!hyphenation_points.is_empty());
+    assert!(hyphenation_points
+        .iter()
+        .all(|point| *point > 0 && *point < word.len()));
+
+    // Words that aren't made of letters are not hyphenated
+    let word = "hyphenation2".chars().collect::<Vec<_>>();
+    assert!(hyphenate(&word, HyphenationLanguage::EnglishUS).is_empty());
+}
+
 #[test]
 fn test_get_line_y_position() {
     assert_eq!(get_line_y_position(0, 20.0, 0.0), 20.0);
@@ -913,3 +1815,246 @@ fn test_caret_intersects_with_holes_4() {
 
     assert_eq!(result, LineCaretIntersection::NoIntersection);
 }
+
+// Scenario 5:
+//
+// +----------------+
+// |+------+   +--->+--+
+// ||      |        |  |
+// |+------+        +--+
+// |                |
+// +----------------+
+// rectangles: 100x50 @ 0x, 0y (float: left) and 50x50 @ 150x, 0y (float: right)
+// max-width: 200px, line-height 1.0, font-size: 20
+// cursor is at: 170x, 20y (inside of the right float)
+// expect cursor to advance to 100x, 40y (+= 1 line, right of the left float)
+//
+#[test]
+fn test_caret_intersects_with_holes_5() {
+    let line_caret_x = 170.0;
+    let line_number = 0;
+    let font_size_px = 20.0;
+    let line_height_px = 0.0;
+    let max_width = Some(200.0);
+    let holes = vec![
+        LayoutRect::new(LayoutPoint::new(0.0, 0.0), LayoutSize::new(100.0, 50.0)),
+        LayoutRect::new(LayoutPoint::new(150.0, 0.0), LayoutSize::new(50.0, 50.0)),
+    ];
+
+    let result = caret_intersects_with_holes(
+        line_caret_x,
+        line_number,
+        font_size_px,
+        line_height_px,
+        &holes,
+        max_width,
+    );
+
+    assert_eq!(
+        result,
+        LineCaretIntersection::PushCaretOntoNextLine(1, 100.0)
+    );
+}
+
+#[test]
+fn test_words_to_scaled_words_with_fallback() {
+    // weblysleekuil only contains latin characters, KoHo contains thai characters
+    const LATIN_FONT: &[u8] = include_bytes!("../../assets/fonts/weblysleekuil.ttf");
+    const THAI_FONT: &[u8] = include_bytes!("../../assets/fonts/KoHo-Light.ttf");
+
+    let words = split_text_into_words("Hello สวัสดี world");
+    let scaled_words = words_to_scaled_words_with_fallback(
+        &words,
+        &[(LATIN_FONT, 0), (THAI_FONT, 0)],
+        &ResolvedTextLayoutOptions {
+            font_size_px: 16.0,
+            ..Default::default()
+        },
+        StyleTextDirection::Ltr,
+    );
+
+    let fonts_per_word = scaled_words
+        .items
+        .iter()
+        .map(|word| {
+            let mut fonts = word.glyph_fonts.clone();
+            fonts.dedup();
+            fonts
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(fonts_per_word, vec![vec![0], vec![1], vec![0]]);
+
+    // Without fallback, the thai word is shaped with the primary font
+    let scaled_words = words_to_scaled_words(&words, LATIN_FONT, 0, 16.0);
+    assert!(scaled_words.items[1].glyph_fonts.iter().all(|f| *f == 0));
+}
+
+#[test]
+fn test_position_words_hyphenation() {
+    const LATIN_FONT: &[u8] = include_bytes!("../../assets/fonts/weblysleekuil.ttf");
+
+    let words = split_text_into_words("extra\u{AD}ordinary");
+    let scaled_words = words_to_scaled_words(&words, LATIN_FONT, 0, 16.0);
+    assert_eq!(scaled_words.items.len(), 2);
+
+    // Only the first part of the word + the hyphen fit onto the first line
+    let first_word_width = scaled_words.items[0].word_width;
+    let hyphen_advance = scaled_words.hyphen_glyphs[0].1;
+    let text_layout_options = ResolvedTextLayoutOptions {
+        font_size_px: 16.0,
+        max_horizontal_width: Some(first_word_width + hyphen_advance + 1.0),
+        ..Default::default()
+    };
+
+    let word_positions = position_words(&words, &scaled_words, &text_layout_options);
+    assert_eq!(word_positions.hyphenated_words, vec![0]);
+    assert_eq!(word_positions.line_breaks.len(), 2);
+    assert_eq!(word_positions.line_breaks[0].0, 1);
+    assert_eq!(word_positions.word_positions[1].x, 0.0);
+
+    // Without the soft hyphen, the parts of the word stay together
+    let words = apply_line_break_options(
+        &words,
+        &LineBreakOptions {
+            hyphens: StyleHyphens::None,
+            ..Default::default()
+        },
+    );
+    let word_positions = position_words(&words, &scaled_words, &text_layout_options);
+    assert!(word_positions.hyphenated_words.is_empty());
+    assert_eq!(
+        word_positions.word_positions[0].y,
+        word_positions.word_positions[1].y
+    );
+}
+
+#[test]
+fn test_layouted_glyphs_ellipsis() {
+    const LATIN_FONT: &[u8] = include_bytes!("../../assets/fonts/weblysleekuil.ttf");
+
+    let words = split_text_into_words("overflowing text");
+    let scaled_words = words_to_scaled_words(&words, LATIN_FONT, 0, 16.0);
+    let (ellipsis_codepoint, ellipsis_advance) = scaled_words.ellipsis_glyphs[0];
+    let max_width = scaled_words.items[0].word_width;
+
+    let get_glyphs = |text_layout_options: &ResolvedTextLayoutOptions| {
+        let word_positions = position_words(&words, &scaled_words, text_layout_options);
+        let inline_text_layout =
+            word_positions_to_inline_text_layout(&word_positions, &scaled_words);
+        let layouted_glyphs = get_layouted_glyphs(
+            &word_positions,
+            &scaled_words,
+            &inline_text_layout,
+            LayoutPoint::zero(),
+        );
+        (inline_text_layout, layouted_glyphs)
+    };
+
+    // `nowrap` keeps the text on one line, the end of the line is replaced with an ellipsis
+    let (inline_text_layout, layouted_glyphs) = get_glyphs(&ResolvedTextLayoutOptions {
+        font_size_px: 16.0,
+        max_horizontal_width: Some(max_width),
+        white_space: StyleWhiteSpace::NoWrap,
+        text_overflow: StyleTextOverflow::Ellipsis,
+        ..Default::default()
+    });
+    assert_eq!(inline_text_layout.lines.len(), 1);
+    let ellipsis = layouted_glyphs.glyphs.last().unwrap();
+    assert_eq!(ellipsis.index, ellipsis_codepoint);
+    assert!(ellipsis.point.x + ellipsis_advance <= max_width);
+
+    // With `text-overflow: clip`, the glyphs overflow the line
+    let (_, clipped_glyphs) = get_glyphs(&ResolvedTextLayoutOptions {
+        font_size_px: 16.0,
+        max_horizontal_width: Some(max_width),
+        white_space: StyleWhiteSpace::NoWrap,
+        ..Default::default()
+    });
+    assert!(clipped_glyphs.glyphs.len() > layouted_glyphs.glyphs.len());
+    assert!(clipped_glyphs
+        .glyphs
+        .iter()
+        .all(|glyph| glyph.index != ellipsis_codepoint));
+
+    // `line-clamp` cuts off the second line, the first line ends with an ellipsis
+    let (inline_text_layout, layouted_glyphs) = get_glyphs(&ResolvedTextLayoutOptions {
+        font_size_px: 16.0,
+        max_horizontal_width: Some(max_width),
+        max_lines: Some(1),
+        ..Default::default()
+    });
+    assert_eq!(inline_text_layout.lines.len(), 1);
+    assert_eq!(
+        layouted_glyphs.glyphs.last().map(|glyph| glyph.index),
+        Some(ellipsis_codepoint)
+    );
+}
+
+#[test]
+fn test_font_metrics() {
+    const LATIN_FONT: &[u8] = include_bytes!("../../assets/fonts/weblysleekuil.ttf");
+
+    let words = split_text_into_words("text");
+    let scaled_words = words_to_scaled_words(&words, LATIN_FONT, 0, 16.0);
+    let metrics = scaled_words.font_metrics[0];
+
+    // y points down: the underline is below the baseline, the strikeout line and the
+    // ascender are above the baseline
+    assert!(metrics.underline_position > 0.0);
+    assert!(metrics.strikeout_position < 0.0);
+    assert!(metrics.ascender < metrics.strikeout_position);
+    assert!(metrics.underline_thickness > 0.0);
+    assert!(metrics.strikeout_thickness > 0.0);
+}
+
+#[test]
+fn test_font_features() {
+    use azul_css::{FontFeature, NumericSpacing, StyleFontVariantNumeric};
+
+    const LATIN_FONT: &[u8] = include_bytes!("../../assets/fonts/weblysleekuil.ttf");
+
+    let words = split_text_into_words("111 888");
+    let get_word_widths = |font_features: Vec<FontFeature>| {
+        let text_layout_options = ResolvedTextLayoutOptions {
+            font_size_px: 16.0,
+            font_features,
+            ..Default::default()
+        };
+        let scaled_words = words_to_scaled_words_with_fallback(
+            &words,
+            &[(LATIN_FONT, 0)],
+            &text_layout_options,
+            StyleTextDirection::Ltr,
+        );
+        scaled_words
+            .items
+            .iter()
+            .map(|word| word.word_width)
+            .collect::<Vec<_>>()
+    };
+
+    // The "1" of the font is narrower than the other digits
+    let proportional_widths = get_word_widths(Vec::new());
+    assert!(proportional_widths[0] < proportional_widths[1]);
+
+    // With `font-variant-numeric: tabular-nums`, all digits have the same width
+    let tabular_widths = get_word_widths(
+        StyleFontVariantNumeric {
+            spacing: NumericSpacing::Tabular,
+            ..Default::default()
+        }
+        .get_font_features(),
+    );
+    assert!((tabular_widths[0] - tabular_widths[1]).abs() < 0.01);
+}
+
+#[test]
+fn test_reorder_visually() {
+    // left-to-right text is not reordered
+    assert_eq!(reorder_visually(&[0, 0, 0]), vec![0, 1, 2]);
+    // a right-to-left run inside left-to-right text is reversed
+    assert_eq!(reorder_visually(&[0, 1, 1, 0]), vec![0, 2, 1, 3]);
+    // right-to-left text with an embedded left-to-right run
+    assert_eq!(reorder_visually(&[1, 2, 2, 1]), vec![3, 1, 2, 0]);
+    assert_eq!(reorder_visually(&[]), Vec::<usize>::new());
+}
@@ -1,18 +1,29 @@
 //! Contains functions for laying out single words (uses HarfBuzz for context-aware font shaping).
 //! Right now, words are laid out on a word-per-word basis, no inter-word font shaping is done.
+//!
+//! Characters that are missing in the primary font are shaped with the next font of the
+//! fallback chain that contains them, right-to-left runs (as resolved by the Unicode bidi
+//! algorithm) are shaped right-to-left, see `shape_text_with_fallback`.
+//!
+//! The OpenType features and variation axes of the `ResolvedTextLayoutOptions`
+//! (`font-feature-settings`, `font-variant-numeric`, `font-variation-settings`)
+//! are applied to every `HbScaledFont`.
 
 use azul_core::{
-    app_resources::{GlyphInfo, GlyphPosition},
+    app_resources::{FontMetrics, GlyphInfo, GlyphPosition},
     display_list::GlyphInstance,
+    ui_solver::ResolvedTextLayoutOptions,
 };
 use azul_css::{LayoutPoint, LayoutSize};
 use harfbuzz_sys::{
-    hb_blob_create, hb_blob_destroy, hb_blob_t, hb_buffer_add_utf8,
+    hb_blob_create, hb_blob_destroy, hb_blob_get_data, hb_blob_t, hb_buffer_add_utf8,
     hb_buffer_allocation_successful, hb_buffer_create, hb_buffer_destroy,
     hb_buffer_get_glyph_infos, hb_buffer_get_glyph_positions, hb_buffer_guess_segment_properties,
-    hb_buffer_t, hb_face_create, hb_face_destroy, hb_face_t, hb_feature_t, hb_font_create,
-    hb_font_destroy, hb_font_set_scale, hb_font_t, hb_glyph_info_t, hb_glyph_position_t,
-    hb_memory_mode_t, hb_ot_font_set_funcs, hb_shape, hb_tag_t, HB_MEMORY_MODE_READONLY,
+    hb_buffer_set_direction, hb_buffer_t, hb_codepoint_t, hb_face_create, hb_face_destroy,
+    hb_face_get_upem, hb_face_reference_table, hb_face_t, hb_feature_t, hb_font_create,
+    hb_font_destroy, hb_font_get_glyph, hb_font_set_scale, hb_font_set_variations, hb_font_t,
+    hb_glyph_info_t, hb_glyph_position_t, hb_memory_mode_t, hb_ot_font_set_funcs, hb_shape,
+    hb_tag_t, hb_variation_t, HB_DIRECTION_LTR, HB_DIRECTION_RTL, HB_MEMORY_MODE_READONLY,
 };
 use std::{
     ops::Deref,
@@ -44,6 +55,11 @@ const LIGA_TAG: hb_tag_t = create_hb_tag(('l', 'i', 'g', 'a'));
 // Contextual ligature substitution
 const CLIG_TAG: hb_tag_t = create_hb_tag(('c', 'l', 'i', 'g'));
 
+// Font tables that the text decoration metrics are read from
+const POST_TAG: hb_tag_t = create_hb_tag(('p', 'o', 's', 't'));
+const OS_2_TAG: hb_tag_t = create_hb_tag(('O', 'S', '/', '2'));
+const HHEA_TAG: hb_tag_t = create_hb_tag(('h', 'h', 'e', 'a'));
+
 const FEATURE_KERNING_OFF: hb_feature_t = hb_feature_t {
     tag: KERN_TAG,
     value: 0,
@@ -129,6 +145,65 @@ impl<'a> HbFont<'a> {
             hb_font,
         }
     }
+
+    /// Returns whether the font has a glyph for the character `c`
+    pub fn has_glyph(&self, c: char) -> bool {
+        let mut glyph: hb_codepoint_t = 0;
+        unsafe { hb_font_get_glyph(self.hb_font, c as hb_codepoint_t, 0, &mut glyph) != 0 }
+    }
+
+    /// Returns the position and thickness of the underline, strikeout line and
+    /// overline of the font, scaled to the `font_size_px`. Values that are missing
+    /// in the font are approximated from the font size.
+    pub fn get_font_metrics(&self, font_size_px: f32) -> FontMetrics {
+        let units_per_em = unsafe { hb_face_get_upem(self.hb_face) }.max(1) as f32;
+        let scale = |font_units: i16| font_units as f32 * font_size_px / units_per_em;
+
+        // See https://docs.microsoft.com/en-us/typography/opentype/spec/post,
+        // .../os2 and .../hhea for the offsets of the values in the tables
+        let (underline_position, underline_thickness) =
+            self.read_table(POST_TAG, |post| (read_i16(post, 8), read_i16(post, 10)));
+        let (strikeout_thickness, strikeout_position) =
+            self.read_table(OS_2_TAG, |os_2| (read_i16(os_2, 26), read_i16(os_2, 28)));
+        let ascender = self.read_table(HHEA_TAG, |hhea| read_i16(hhea, 4));
+
+        let default_thickness = font_size_px / 14.0;
+        let thickness_or_default = |thickness: Option<i16>| {
+            thickness
+                .filter(|t| *t > 0)
+                .map_or(default_thickness, scale)
+        };
+
+        // Font tables are y-up, the positions are converted to be y-down
+        FontMetrics {
+            underline_position: underline_position.map_or(font_size_px * 0.1, |p| -scale(p)),
+            underline_thickness: thickness_or_default(underline_thickness),
+            strikeout_position: strikeout_position.map_or(font_size_px * -0.3, |p| -scale(p)),
+            strikeout_thickness: thickness_or_default(strikeout_thickness),
+            ascender: ascender.map_or(font_size_px * -0.8, |a| -scale(a)),
+        }
+    }
+
+    /// Calls `f` with the bytes of the table of the font (empty if the font doesn't have it)
+    fn read_table<T, F: FnOnce(&[u8]) -> T>(&self, tag: hb_tag_t, f: F) -> T {
+        let hb_table = unsafe { hb_face_reference_table(self.hb_face, tag) };
+        let mut table_len: c_uint = 0;
+        let table_ptr = unsafe { hb_blob_get_data(hb_table, &mut table_len) };
+        let table = if table_ptr.is_null() {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(table_ptr as *const u8, table_len as usize) }
+        };
+        let result = f(table);
+        unsafe { hb_blob_destroy(hb_table) };
+        result
+    }
+}
+
+/// Reads a big-endian `i16` at the `offset` of a font table
+fn read_i16(table: &[u8], offset: usize) -> Option<i16> {
+    let bytes = table.get(offset..offset + 2)?;
+    Some(i16::from_be_bytes([bytes[0], bytes[1]]))
 }
 
 impl<'a> Drop for HbFont<'a> {
@@ -144,14 +219,60 @@ impl<'a> Drop for HbFont<'a> {
 pub struct HbScaledFont<'a> {
     pub font: &'a HbFont<'a>,
     pub font_size_px: f32,
+    /// Features that the text is shaped with: the `ACTIVE_HB_FEATURES`, followed by the
+    /// features of the text (later features override earlier features with the same tag)
+    features: Vec<hb_feature_t>,
 }
 
 impl<'a> HbScaledFont<'a> {
-    /// Create a `HbScaledFont` from a
-    pub fn from_font(font: &'a HbFont<'a>, font_size_px: f32) -> Self {
+    /// Create a `HbScaledFont` from a font, with the font size, OpenType features
+    /// and variation axis values of the `text_layout_options`
+    pub fn from_font(
+        font: &'a HbFont<'a>,
+        text_layout_options: &ResolvedTextLayoutOptions,
+    ) -> Self {
+        let font_size_px = text_layout_options.font_size_px;
         let px = (font_size_px * HB_SCALE_FACTOR) as i32;
         unsafe { hb_font_set_scale(font.hb_font, px, px) };
-        Self { font, font_size_px }
+
+        // Axes that the font doesn't have are ignored by HarfBuzz
+        let variations = text_layout_options
+            .font_variations
+            .iter()
+            .map(|variation| hb_variation_t {
+                tag: variation.tag.as_u32(),
+                value: variation.value.get(),
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            hb_font_set_variations(
+                font.hb_font,
+                variations.as_ptr(),
+                variations.len() as c_uint,
+            )
+        };
+
+        let features = ACTIVE_HB_FEATURES
+            .iter()
+            .cloned()
+            .chain(
+                text_layout_options
+                    .font_features
+                    .iter()
+                    .map(|feature| hb_feature_t {
+                        tag: feature.tag.as_u32(),
+                        value: feature.value,
+                        start: 0,
+                        end: u32::MAX,
+                    }),
+            )
+            .collect();
+
+        Self {
+            font,
+            font_size_px,
+            features,
+        }
     }
 }
 
@@ -181,6 +302,19 @@ impl<'a> HbBuffer<'a> {
 
         Self { words, hb_buffer }
     }
+
+    /// Same as `from_str`, but sets the direction of the text instead of guessing it
+    /// from the script (i.e. for runs whose direction was resolved by the bidi algorithm)
+    pub fn from_str_with_direction(words: &'a str, is_rtl: bool) -> Self {
+        let buffer = Self::from_str(words);
+        let direction = if is_rtl {
+            HB_DIRECTION_RTL
+        } else {
+            HB_DIRECTION_LTR
+        };
+        unsafe { hb_buffer_set_direction(buffer.hb_buffer, direction) };
+        buffer
+    }
 }
 
 impl<'a> Drop for HbBuffer<'a> {
@@ -224,13 +358,13 @@ pub(crate) fn shape_word_hb<'a>(
     text: &'a HbBuffer<'a>,
     scaled_font: &'a HbScaledFont<'a>,
 ) -> HbShapedWord<'a> {
-    let features = if ACTIVE_HB_FEATURES.is_empty() {
+    let features = if scaled_font.features.is_empty() {
         ptr::null()
     } else {
-        &ACTIVE_HB_FEATURES as *const _
+        scaled_font.features.as_ptr()
     };
 
-    let num_features = ACTIVE_HB_FEATURES.len() as u32;
+    let num_features = scaled_font.features.len() as u32;
 
     unsafe {
         hb_shape(
@@ -304,3 +438,134 @@ pub(crate) fn get_glyph_instances_hb(
         })
         .collect()
 }
+
+/// Run of text that is shaped with a single font of the fallback chain, in a single direction
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontRun {
+    /// Index of the font in the fallback chain
+    pub font: usize,
+    /// Bidi embedding level of the run (odd levels are right-to-left)
+    pub bidi_level: u8,
+    /// Byte offset of the first character of the run
+    pub start: usize,
+    /// Byte offset of the end of the run (exclusive)
+    pub end: usize,
+}
+
+/// Returns whether the character can't start a new cluster, i.e. whether it has to be
+/// rendered with the same font as the previous character (combining marks, zero-width
+/// joiners, variation selectors and emoji skin tone modifiers)
+pub(crate) fn continues_cluster(c: char) -> bool {
+    use unicode_normalization::char::canonical_combining_class;
+    match c {
+        '\u{200C}' | '\u{200D}' | '\u{FE00}'..='\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}' => true,
+        '\u{E0100}'..='\u{E01EF}' => true,
+        other => canonical_combining_class(other) != 0,
+    }
+}
+
+/// Splits the `text` into runs of characters that can be shaped with the same font: every
+/// cluster is assigned to the first font of the `fonts` that contains its base character.
+/// Whitespace and clusters that no font contains continue the current run (or use
+/// the primary font at the start of the text), so that they don't break up runs.
+///
+/// Runs are also split where the bidi level (`bidi_levels`, one level per byte) changes.
+pub(crate) fn split_text_into_font_runs(
+    text: &str,
+    fonts: &[&HbFont],
+    bidi_levels: &[u8],
+) -> Vec<FontRun> {
+    let mut runs = Vec::<FontRun>::new();
+
+    for (byte_idx, c) in text.char_indices() {
+        let current_font = runs.last().map(|run| run.font);
+        let bidi_level = bidi_levels.get(byte_idx).cloned().unwrap_or(0);
+
+        let font = if c.is_whitespace() || continues_cluster(c) {
+            current_font
+        } else {
+            fonts
+                .iter()
+                .position(|font| font.has_glyph(c))
+                .or(current_font)
+        }
+        .unwrap_or(0);
+
+        match runs.last_mut() {
+            Some(run) if run.font == font && run.bidi_level == bidi_level => {
+                run.end = byte_idx + c.len_utf8()
+            }
+            _ => runs.push(FontRun {
+                font,
+                bidi_level,
+                start: byte_idx,
+                end: byte_idx + c.len_utf8(),
+            }),
+        }
+    }
+
+    runs
+}
+
+/// Shaped glyph of a text that was shaped with a font fallback chain
+#[derive(Debug, Copy, Clone)]
+pub struct FallbackGlyph {
+    /// Index of the font in the fallback chain that the glyph was shaped with
+    pub font: usize,
+    /// Bidi embedding level of the character that the glyph was shaped from
+    pub bidi_level: u8,
+    pub glyph_info: GlyphInfo,
+    pub glyph_position: GlyphPosition,
+}
+
+/// Shapes the `text`, using the first of the `scaled_fonts` as the primary font and the
+/// other fonts as fallbacks for the characters that are missing in the primary font.
+///
+/// Every run of the text is shaped separately (see `split_text_into_font_runs`), runs with
+/// an odd `bidi_levels` are shaped right-to-left. The clusters of the returned glyphs are
+/// byte offsets into the entire `text`.
+///
+/// The glyphs are returned in logical order: HarfBuzz returns the glyphs of right-to-left
+/// runs in visual order, so the glyphs of these runs are reversed. Reordering the glyphs
+/// visually is done later on, after the text is split into words and lines.
+pub(crate) fn shape_text_with_fallback(
+    text: &str,
+    scaled_fonts: &[HbScaledFont],
+    bidi_levels: &[u8],
+) -> Vec<FallbackGlyph> {
+    use std::mem;
+
+    let fonts = scaled_fonts.iter().map(|f| f.font).collect::<Vec<_>>();
+    let mut glyphs = Vec::new();
+
+    for run in split_text_into_font_runs(text, &fonts, bidi_levels) {
+        let is_rtl = run.bidi_level % 2 == 1;
+        let hb_buffer = HbBuffer::from_str_with_direction(&text[run.start..run.end], is_rtl);
+        let hb_shaped_run = shape_word_hb(&hb_buffer, &scaled_fonts[run.font]);
+        let run_start = glyphs.len();
+
+        for (glyph_info, glyph_position) in hb_shaped_run
+            .glyph_infos
+            .iter()
+            .zip(hb_shaped_run.glyph_positions.iter())
+        {
+            // azul-core::GlyphInfo and hb_glyph_info_t have the same size / layout
+            // (both are repr(C)), so it's safe to just transmute them here
+            let mut glyph_info: GlyphInfo = unsafe { mem::transmute(*glyph_info) };
+            let glyph_position: GlyphPosition = unsafe { mem::transmute(*glyph_position) };
+            glyph_info.cluster += run.start as u32;
+            glyphs.push(FallbackGlyph {
+                font: run.font,
+                bidi_level: run.bidi_level,
+                glyph_info,
+                glyph_position,
+            });
+        }
+
+        if is_rtl {
+            glyphs[run_start..].reverse();
+        }
+    }
+
+    glyphs
+}
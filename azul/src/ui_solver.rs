@@ -1,20 +1,24 @@
 use azul_core::{
-    app_resources::{Au, FontInstanceKey},
-    ui_solver::{InlineTextLayout, LayoutResult, PositionedRectangle, ResolvedTextLayoutOptions},
+    app_resources::{Au, FontFaceStyle, FontInstanceDescriptor, FontInstanceKey, ImmediateFontId},
+    ui_solver::{
+        InlineTextLayout, LayoutResult, PositionedRectangle, ResolvedTextLayoutOptions,
+        RichTextSpans,
+    },
 };
 use azul_css::{
-    LayoutRect, LayoutSize, RectLayout, RectStyle, StyleFontSize, StyleTextAlignmentHorz,
-    StyleTextAlignmentVert,
+    CssPropertyValue, FontFeature, FontVariation, LayoutRect, LayoutSize, RectLayout, RectStyle,
+    StyleFontSize, StyleHyphens, StyleTextAlignmentHorz, StyleTextAlignmentVert,
+    StyleTextDirection,
 };
 use azul_layout::{GetTextLayout, RectContent};
 use std::{collections::BTreeMap, f32};
 
 use crate::{
     app_resources::AppResources,
-    display_list::DisplayRectangle,
-    dom::{NodeData, NodeType},
+    display_list::{get_span_style, DisplayRectangle},
+    dom::{NodeData, NodeType, TextSpan},
     id_tree::{NodeDataContainer, NodeHierarchy, NodeId},
-    text_layout::{LayoutedGlyphs, ScaledWords, WordPositions, Words},
+    text_layout::{LayoutedGlyphs, LineBreakOptions, ScaledWords, WordPositions, Words},
 };
 
 type PixelSize = f32;
@@ -57,13 +61,96 @@ pub(crate) fn px_to_au(px: f32) -> Au {
     Au(target_app_units.min(MAX_AU).max(MIN_AU).0)
 }
 
-pub(crate) fn get_font_id(rect_style: &RectStyle) -> &str {
+/// Returns the families of the `font-family` property, in order of preference
+pub(crate) fn get_font_families(rect_style: &RectStyle) -> Vec<&str> {
     use azul_core::ui_solver::DEFAULT_FONT_ID;
-    let font_id = rect_style
+    let families = rect_style
         .font_family
         .as_ref()
-        .and_then(|family| family.get_property()?.fonts.get(0));
-    font_id.map(|f| f.get_str()).unwrap_or(DEFAULT_FONT_ID)
+        .and_then(|family| family.get_property())
+        .map(|family| family.fonts.iter().map(|f| f.get_str()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if families.is_empty() {
+        vec![DEFAULT_FONT_ID]
+    } else {
+        families
+    }
+}
+
+/// Returns the font fallback chain of a text: the families of the `font-family`,
+/// followed by the fallback fonts of the system (for CJK, emoji, symbols, etc.)
+pub(crate) fn get_font_fallback_chain(
+    app_resources: &AppResources,
+    rect_style: &RectStyle,
+) -> Vec<ImmediateFontId> {
+    use crate::app_resources::SYSTEM_FALLBACK_FONTS;
+
+    let face_style = get_font_face_style(rect_style);
+    let mut font_chain = Vec::new();
+
+    for family in get_font_families(rect_style)
+        .into_iter()
+        .chain(SYSTEM_FALLBACK_FONTS.iter().cloned())
+    {
+        let font_id = app_resources.resolve_font_id(family, &face_style);
+        if !font_chain.contains(&font_id) {
+            font_chain.push(font_id);
+        }
+    }
+
+    font_chain
+}
+
+pub(crate) fn get_font_face_style(rect_style: &RectStyle) -> FontFaceStyle {
+    FontFaceStyle {
+        weight: rect_style
+            .font_weight
+            .and_then(|fw| fw.get_property().cloned())
+            .unwrap_or_default(),
+        style: rect_style
+            .font_style
+            .and_then(|fs| fs.get_property().cloned())
+            .unwrap_or_default(),
+        stretch: rect_style
+            .font_stretch
+            .and_then(|fs| fs.get_property().cloned())
+            .unwrap_or_default(),
+    }
+}
+
+pub(crate) fn get_text_direction(rect_style: &RectStyle) -> StyleTextDirection {
+    rect_style
+        .direction
+        .and_then(|d| d.get_property().cloned())
+        .unwrap_or_default()
+}
+
+pub(crate) fn get_line_break_options(
+    app_resources: &AppResources,
+    rect_style: &RectStyle,
+) -> LineBreakOptions {
+    LineBreakOptions {
+        white_space: rect_style
+            .white_space
+            .and_then(|ws| ws.get_property().cloned())
+            .unwrap_or_default(),
+        word_break: rect_style
+            .word_break
+            .and_then(|wb| wb.get_property().cloned())
+            .unwrap_or_default(),
+        overflow_wrap: rect_style
+            .overflow_wrap
+            .and_then(|ow| ow.get_property().cloned())
+            .unwrap_or_default(),
+        // "hyphens: auto" is parsed as CssPropertyValue::Auto, not as StyleHyphens::Auto
+        hyphens: match rect_style.hyphens {
+            Some(CssPropertyValue::Auto) => StyleHyphens::Auto,
+            h => h
+                .and_then(|h| h.get_property().cloned())
+                .unwrap_or_default(),
+        },
+        hyphenation_language: app_resources.hyphenation_language,
+    }
 }
 
 pub(crate) fn get_font_size(rect_style: &RectStyle) -> StyleFontSize {
@@ -74,6 +161,41 @@ pub(crate) fn get_font_size(rect_style: &RectStyle) -> StyleFontSize {
         .unwrap_or(DEFAULT_FONT_SIZE)
 }
 
+/// Returns the OpenType features that the text is shaped with: the features of the
+/// `font-variant-numeric`, followed by the `font-feature-settings` (which override them)
+pub(crate) fn get_font_features(rect_style: &RectStyle) -> Vec<FontFeature> {
+    let mut features = rect_style
+        .font_variant_numeric
+        .and_then(|fvn| fvn.get_property().map(|fvn| fvn.get_font_features()))
+        .unwrap_or_default();
+    if let Some(ffs) = rect_style
+        .font_feature_settings
+        .as_ref()
+        .and_then(|ffs| ffs.get_property())
+    {
+        features.extend(ffs.0.iter().cloned());
+    }
+    features
+}
+
+pub(crate) fn get_font_variations(rect_style: &RectStyle) -> Vec<FontVariation> {
+    rect_style
+        .font_variation_settings
+        .as_ref()
+        .and_then(|fvs| fvs.get_property())
+        .map(|fvs| fvs.0.clone())
+        .unwrap_or_default()
+}
+
+/// Returns the size and OpenType settings of the font instances that the text is rendered with
+pub(crate) fn get_font_instance_descriptor(rect_style: &RectStyle) -> FontInstanceDescriptor {
+    FontInstanceDescriptor {
+        size: font_size_to_au(get_font_size(rect_style)),
+        features: get_font_features(rect_style),
+        variations: get_font_variations(rect_style),
+    }
+}
+
 pub struct InlineText<'a> {
     words: &'a Words,
     scaled_words: &'a ScaledWords,
@@ -100,6 +222,7 @@ pub(crate) fn do_the_layout<'a, 'b, T>(
     display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
     app_resources: &'b AppResources,
     bounding_rect: LayoutRect,
+    viewport: LayoutSize,
 ) -> LayoutResult {
     use azul_layout::SolvedUi;
 
@@ -109,12 +232,19 @@ pub(crate) fn do_the_layout<'a, 'b, T>(
     // 4. copy UI and re-layout again, then copy result to all children of the overflowing rects
     // 5. return to caller, caller will do final text layout (not the job of the layout engine)
 
-    let word_cache = create_word_cache(app_resources, node_data);
-    let scaled_words = create_scaled_words(app_resources, &word_cache, display_rects);
+    let word_cache = create_word_cache(app_resources, node_data, display_rects);
+    let (scaled_words, mut rich_text_spans) =
+        create_scaled_words(app_resources, &word_cache, node_data, display_rects);
     let mut solved_ui = {
         let rect_contents =
             create_rect_contents_cache(&word_cache, &scaled_words, node_data, app_resources);
-        SolvedUi::new(bounding_rect, node_hierarchy, display_rects, rect_contents)
+        SolvedUi::new(
+            bounding_rect,
+            viewport,
+            node_hierarchy,
+            display_rects,
+            rect_contents,
+        )
     };
 
     // TODO: overflowing rects!
@@ -128,79 +258,206 @@ pub(crate) fn do_the_layout<'a, 'b, T>(
         &positioned_word_cache,
         &display_rects,
         &mut solved_ui.solved_rects,
+        &mut rich_text_spans,
     );
     let node_depths = node_hierarchy.get_parents_sorted_by_depth();
 
     // TODO: Set the final content sizes on layouted_rects!
 
     LayoutResult {
+        bounding_rect,
         rects: solved_ui.solved_rects,
         word_cache,
         scaled_words,
         positioned_word_cache,
         layouted_glyph_cache,
+        rich_text_spans,
         node_depths,
     }
 }
 
-fn create_word_cache<T>(
+/// Splits the texts into words, with the whitespace and break opportunities
+/// of their `white-space`, `word-break`, `overflow-wrap` and `hyphens` style
+fn create_word_cache<'a, T>(
     app_resources: &AppResources,
     node_data: &NodeDataContainer<NodeData<T>>,
+    display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
 ) -> BTreeMap<NodeId, Words> {
-    use crate::text_layout::split_text_into_words;
+    use crate::text_layout::{self, apply_line_break_options, split_text_into_words};
     node_data
         .linear_iter()
-        .filter_map(|node_id| match &node_data[node_id].get_node_type() {
-            NodeType::Label(string) => Some((node_id, split_text_into_words(string.as_str()))),
-            NodeType::Text(text_id) => app_resources
-                .get_text(text_id)
-                .map(|words| (node_id, words.clone())),
-            _ => None,
+        .filter_map(|node_id| {
+            let style = &display_rects[node_id].style;
+            let line_break_options = get_line_break_options(app_resources, style);
+            let words = match &node_data[node_id].get_node_type() {
+                NodeType::Label(string) => apply_line_break_options(
+                    &split_text_into_words(string.as_str()),
+                    &line_break_options,
+                ),
+                NodeType::Text(text_id) => {
+                    apply_line_break_options(app_resources.get_text(text_id)?, &line_break_options)
+                }
+                NodeType::RichText(spans) => text_layout::join_span_words(&split_spans_into_words(
+                    app_resources,
+                    spans,
+                    &display_rects[node_id],
+                )),
+                _ => return None,
+            };
+            Some((node_id, words))
+        })
+        .collect()
+}
+
+/// Splits the text of each span into words, with the line break options of the span
+fn split_spans_into_words<T>(
+    app_resources: &AppResources,
+    spans: &[TextSpan<T>],
+    rect: &DisplayRectangle,
+) -> Vec<Words> {
+    use crate::text_layout::{apply_line_break_options, split_text_into_words};
+    spans
+        .iter()
+        .map(|span| {
+            let span_style = get_span_style(rect, &span.style);
+            apply_line_break_options(
+                &split_text_into_words(span.text.as_str()),
+                &get_line_break_options(app_resources, &span_style),
+            )
         })
         .collect()
 }
 
-fn create_scaled_words<'a>(
+/// Shapes the words of all text nodes. The spans of a `NodeType::RichText` node are
+/// shaped with their own style and joined afterwards, the spans of the words and font
+/// instances are returned separately.
+fn create_scaled_words<'a, T>(
     app_resources: &AppResources,
     words: &BTreeMap<NodeId, Words>,
+    node_data: &NodeDataContainer<NodeData<T>>,
     display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
-) -> BTreeMap<NodeId, (ScaledWords, FontInstanceKey)> {
-    use crate::app_resources::ImmediateFontId;
-    use crate::text_layout::words_to_scaled_words;
+) -> (
+    BTreeMap<NodeId, (ScaledWords, Vec<FontInstanceKey>)>,
+    BTreeMap<NodeId, RichTextSpans>,
+) {
+    use crate::text_layout::join_span_scaled_words;
+
+    let mut scaled_words = BTreeMap::new();
+    let mut rich_text_spans = BTreeMap::new();
+
+    for (node_id, words) in words.iter() {
+        let rect = &display_rects[*node_id];
+        let style = &rect.style;
+
+        let spans = match node_data[*node_id].get_node_type() {
+            NodeType::RichText(spans) => spans,
+            _ => {
+                if let Some(s) = scale_words(app_resources, words, style) {
+                    scaled_words.insert(*node_id, s);
+                }
+                continue;
+            }
+        };
+
+        // If any span can't be shaped, the words don't line up anymore
+        let span_scaled_words = match split_spans_into_words(app_resources, spans, rect)
+            .iter()
+            .zip(spans.iter())
+            .map(|(span_words, span)| {
+                let span_style = get_span_style(rect, &span.style);
+                scale_words(app_resources, span_words, &span_style)
+            })
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(s) => s,
+            None => continue,
+        };
+
+        let mut spans_of_node = RichTextSpans::default();
+        for (span_idx, (span_words, font_instance_keys)) in span_scaled_words.iter().enumerate() {
+            spans_of_node
+                .word_spans
+                .extend(span_words.items.iter().map(|_| span_idx));
+            spans_of_node
+                .font_spans
+                .extend(font_instance_keys.iter().map(|_| span_idx));
+        }
+
+        let joined_scaled_words = join_span_scaled_words(
+            &span_scaled_words
+                .iter()
+                .map(|(span_words, keys)| (span_words, keys.len()))
+                .collect::<Vec<_>>(),
+        );
+        let joined_font_instance_keys = span_scaled_words
+            .into_iter()
+            .flat_map(|(_, keys)| keys)
+            .collect();
+
+        scaled_words.insert(*node_id, (joined_scaled_words, joined_font_instance_keys));
+        rich_text_spans.insert(*node_id, spans_of_node);
+    }
+
+    (scaled_words, rich_text_spans)
+}
+
+/// Shapes the words with the font fallback chain of the `style`, returns `None`
+/// if none of the fonts of the chain are loaded
+fn scale_words(
+    app_resources: &AppResources,
+    words: &Words,
+    style: &RectStyle,
+) -> Option<(ScaledWords, Vec<FontInstanceKey>)> {
+    use crate::text_layout::words_to_scaled_words_with_fallback;
     use azul_core::ui_solver::DEFAULT_FONT_SIZE_PX;
 
-    words
-        .iter()
-        .filter_map(|(node_id, words)| {
-            let style = &display_rects[*node_id].style;
-            let font_size = get_font_size(&style);
-            let font_size_au = font_size_to_au(font_size);
-            let css_font_id = get_font_id(&style);
-            let font_id = match app_resources.get_css_font_id(css_font_id) {
-                Some(s) => ImmediateFontId::Resolved(*s),
-                None => ImmediateFontId::Unresolved(css_font_id.to_string()),
-            };
+    let font_size = get_font_size(style);
+    let font_instance = get_font_instance_descriptor(style);
 
-            let loaded_font = app_resources.get_loaded_font(&font_id)?;
-            let font_instance_key = loaded_font.font_instances.get(&font_size_au)?;
+    // Fonts of the chain that aren't loaded (because the text doesn't need
+    // them or because they aren't installed) are skipped
+    let loaded_fonts = get_font_fallback_chain(app_resources, style)
+        .iter()
+        .filter_map(|font_id| {
+            let loaded_font = app_resources.get_loaded_font(font_id)?;
+            let font_instance_key = loaded_font.font_instances.get(&font_instance)?;
+            Some((loaded_font, *font_instance_key))
+        })
+        .collect::<Vec<_>>();
 
-            let font_bytes = &loaded_font.font_bytes;
-            let font_index = loaded_font.font_index as u32;
+    if loaded_fonts.is_empty() {
+        return None;
+    }
 
-            let scaled_words = words_to_scaled_words(
-                words,
-                font_bytes,
-                font_index,
-                font_size.0.to_pixels(DEFAULT_FONT_SIZE_PX as f32),
-            );
-            Some((*node_id, (scaled_words, *font_instance_key)))
+    let fonts = loaded_fonts
+        .iter()
+        .map(|(loaded_font, _)| {
+            (
+                loaded_font.font_bytes.as_slice(),
+                loaded_font.font_index as u32,
+            )
         })
-        .collect()
+        .collect::<Vec<_>>();
+    let font_instance_keys = loaded_fonts.iter().map(|(_, key)| *key).collect();
+
+    let text_layout_options = ResolvedTextLayoutOptions {
+        font_size_px: font_size.0.to_pixels(DEFAULT_FONT_SIZE_PX as f32),
+        font_features: font_instance.features,
+        font_variations: font_instance.variations,
+        ..Default::default()
+    };
+    let scaled_words = words_to_scaled_words_with_fallback(
+        words,
+        &fonts,
+        &text_layout_options,
+        get_text_direction(style),
+    );
+    Some((scaled_words, font_instance_keys))
 }
 
 fn create_rect_contents_cache<'a, T>(
     words: &'a BTreeMap<NodeId, Words>,
-    scaled_words: &'a BTreeMap<NodeId, (ScaledWords, FontInstanceKey)>,
+    scaled_words: &'a BTreeMap<NodeId, (ScaledWords, Vec<FontInstanceKey>)>,
     display_rects: &NodeDataContainer<NodeData<T>>,
     app_resources: &AppResources,
 ) -> BTreeMap<NodeId, RectContent<InlineText<'a>>> {
@@ -212,7 +469,7 @@ fn create_rect_contents_cache<'a, T>(
                 let (w, h) = app_resources.get_image_info(&id)?.get_dimensions();
                 Some((node_id, RectContent::Image(w, h)))
             }
-            Text(_) | Label(_) => Some((
+            Text(_) | Label(_) | RichText(_) => Some((
                 node_id,
                 RectContent::Text(InlineText {
                     words: words.get(&node_id)?,
@@ -226,32 +483,33 @@ fn create_rect_contents_cache<'a, T>(
 
 fn create_word_positions<'a>(
     words: &BTreeMap<NodeId, Words>,
-    scaled_words: &BTreeMap<NodeId, (ScaledWords, FontInstanceKey)>,
+    scaled_words: &BTreeMap<NodeId, (ScaledWords, Vec<FontInstanceKey>)>,
     layouted_rects: &NodeDataContainer<PositionedRectangle>,
-) -> BTreeMap<NodeId, (WordPositions, FontInstanceKey)> {
+) -> BTreeMap<NodeId, (WordPositions, Vec<FontInstanceKey>)> {
     use crate::text_layout;
     words
         .iter()
         .filter_map(|(node_id, words)| {
-            let (scaled_words, font_instance_key) = scaled_words.get(&node_id)?;
+            let (scaled_words, font_instance_keys) = scaled_words.get(&node_id)?;
             let (text_layout_options, _, _) = layouted_rects[*node_id]
                 .resolved_text_layout_options
                 .as_ref()?;
             let positioned_words =
                 text_layout::position_words(words, scaled_words, text_layout_options);
-            Some((*node_id, (positioned_words, *font_instance_key)))
+            Some((*node_id, (positioned_words, font_instance_keys.clone())))
         })
         .collect()
 }
 
 fn get_glyphs<'a>(
     node_hierarchy: &NodeHierarchy,
-    scaled_words: &BTreeMap<NodeId, (ScaledWords, FontInstanceKey)>,
-    positioned_word_cache: &BTreeMap<NodeId, (WordPositions, FontInstanceKey)>,
+    scaled_words: &BTreeMap<NodeId, (ScaledWords, Vec<FontInstanceKey>)>,
+    positioned_word_cache: &BTreeMap<NodeId, (WordPositions, Vec<FontInstanceKey>)>,
     display_rects: &NodeDataContainer<DisplayRectangle<'a>>,
     positioned_rectangles: &mut NodeDataContainer<PositionedRectangle>,
+    rich_text_spans: &mut BTreeMap<NodeId, RichTextSpans>,
 ) -> BTreeMap<NodeId, LayoutedGlyphs> {
-    use crate::text_layout::get_layouted_glyphs;
+    use crate::text_layout::{get_layouted_glyphs, get_word_bounds};
 
     scaled_words
         .iter()
@@ -278,6 +536,17 @@ fn get_glyphs<'a>(
                 &inline_text_layout,
                 bounds.origin,
             );
+
+            // The spans are hit-tested by the bounds of their words
+            if let Some(spans) = rich_text_spans.get_mut(node_id) {
+                spans.word_bounds = get_word_bounds(
+                    word_positions,
+                    scaled_words,
+                    &inline_text_layout,
+                    bounds.origin,
+                );
+            }
+
             Some((*node_id, glyphs))
         })
         .collect()
@@ -288,7 +557,13 @@ fn determine_text_alignment(
     rect_style: &RectStyle,
     rect_layout: &RectLayout,
 ) -> (StyleTextAlignmentHorz, StyleTextAlignmentVert) {
-    let mut horz_alignment = StyleTextAlignmentHorz::default();
+    // Right-to-left text starts at the right edge of the rectangle
+    let (horz_start, horz_end) = match get_text_direction(rect_style) {
+        StyleTextDirection::Ltr => (StyleTextAlignmentHorz::Left, StyleTextAlignmentHorz::Right),
+        StyleTextDirection::Rtl => (StyleTextAlignmentHorz::Right, StyleTextAlignmentHorz::Left),
+    };
+
+    let mut horz_alignment = horz_start;
     let mut vert_alignment = StyleTextAlignmentVert::default();
 
     if let Some(align_items) = rect_layout
@@ -312,8 +587,8 @@ fn determine_text_alignment(
         use azul_css::LayoutJustifyContent;
         // Horizontal text alignment
         match justify_content {
-            LayoutJustifyContent::Start => horz_alignment = StyleTextAlignmentHorz::Left,
-            LayoutJustifyContent::End => horz_alignment = StyleTextAlignmentHorz::Right,
+            LayoutJustifyContent::Start => horz_alignment = horz_start,
+            LayoutJustifyContent::End => horz_alignment = horz_end,
             _ => horz_alignment = StyleTextAlignmentHorz::Center,
         }
     }
@@ -409,7 +684,7 @@ mod layout_tests {
         let arena = get_testing_hierarchy();
         let mut arena_data = vec![RectLayout::default(); arena.len()];
         for (id, rect) in constraints {
-            arena_data[*id] = *rect;
+            arena_data[*id] = rect.clone();
         }
         (
             arena,
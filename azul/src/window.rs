@@ -672,7 +672,7 @@ pub(crate) fn synchronize_window_state_with_os_window(
 
 /// Reverse function of `full_window_state_to_window_state` - overwrites all
 /// fields of the `FullWindowState` with the fields of the `WindowState`
-fn update_full_window_state(full_window_state: &mut FullWindowState, window_state: &WindowState) {
+pub(crate) fn update_full_window_state(full_window_state: &mut FullWindowState, window_state: &WindowState) {
     full_window_state.title = window_state.title.clone();
     full_window_state.size = window_state.size;
     full_window_state.position = window_state.position;
@@ -688,6 +688,7 @@ fn update_full_window_state(full_window_state: &mut FullWindowState, window_stat
     full_window_state.ime_position = window_state.ime_position;
     full_window_state.request_user_attention = window_state.request_user_attention;
     full_window_state.wayland_theme = window_state.wayland_theme;
+    full_window_state.theme = window_state.theme;
 }
 
 fn synchronize_mouse_state(
@@ -748,6 +749,7 @@ pub(crate) fn full_window_state_to_window_state(
         ime_position: full_window_state.ime_position,
         request_user_attention: full_window_state.request_user_attention,
         wayland_theme: full_window_state.wayland_theme,
+        theme: full_window_state.theme,
     }
 }
 
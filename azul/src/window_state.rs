@@ -12,7 +12,7 @@ use crate::{
 use azul_core::callbacks::FocusTarget;
 pub use azul_core::window::{
     AcceleratorKey, DebugState, KeyboardState, LogicalPosition, LogicalSize, MouseState,
-    PhysicalPosition, PhysicalSize, WaylandTheme, WindowSize, WindowState,
+    PhysicalPosition, PhysicalSize, WaylandTheme, WindowSize, WindowState, WindowTheme,
 };
 use glium::glutin::{
     dpi::LogicalPosition as WinitLogicalPosition,
@@ -96,6 +96,176 @@ pub(crate) mod winit_translate {
             RowResize => WinitMouseCursorType::RowResize,
         }
     }
+
+    use azul_core::window::VirtualKeyCode;
+    use glium::glutin::event::VirtualKeyCode as WinitVirtualKeyCode;
+
+    pub(crate) fn translate_virtual_keycode(input: VirtualKeyCode) -> WinitVirtualKeyCode {
+        use azul_core::window::VirtualKeyCode::*;
+        match input {
+            Key1 => WinitVirtualKeyCode::Key1,
+            Key2 => WinitVirtualKeyCode::Key2,
+            Key3 => WinitVirtualKeyCode::Key3,
+            Key4 => WinitVirtualKeyCode::Key4,
+            Key5 => WinitVirtualKeyCode::Key5,
+            Key6 => WinitVirtualKeyCode::Key6,
+            Key7 => WinitVirtualKeyCode::Key7,
+            Key8 => WinitVirtualKeyCode::Key8,
+            Key9 => WinitVirtualKeyCode::Key9,
+            Key0 => WinitVirtualKeyCode::Key0,
+            A => WinitVirtualKeyCode::A,
+            B => WinitVirtualKeyCode::B,
+            C => WinitVirtualKeyCode::C,
+            D => WinitVirtualKeyCode::D,
+            E => WinitVirtualKeyCode::E,
+            F => WinitVirtualKeyCode::F,
+            G => WinitVirtualKeyCode::G,
+            H => WinitVirtualKeyCode::H,
+            I => WinitVirtualKeyCode::I,
+            J => WinitVirtualKeyCode::J,
+            K => WinitVirtualKeyCode::K,
+            L => WinitVirtualKeyCode::L,
+            M => WinitVirtualKeyCode::M,
+            N => WinitVirtualKeyCode::N,
+            O => WinitVirtualKeyCode::O,
+            P => WinitVirtualKeyCode::P,
+            Q => WinitVirtualKeyCode::Q,
+            R => WinitVirtualKeyCode::R,
+            S => WinitVirtualKeyCode::S,
+            T => WinitVirtualKeyCode::T,
+            U => WinitVirtualKeyCode::U,
+            V => WinitVirtualKeyCode::V,
+            W => WinitVirtualKeyCode::W,
+            X => WinitVirtualKeyCode::X,
+            Y => WinitVirtualKeyCode::Y,
+            Z => WinitVirtualKeyCode::Z,
+            Escape => WinitVirtualKeyCode::Escape,
+            F1 => WinitVirtualKeyCode::F1,
+            F2 => WinitVirtualKeyCode::F2,
+            F3 => WinitVirtualKeyCode::F3,
+            F4 => WinitVirtualKeyCode::F4,
+            F5 => WinitVirtualKeyCode::F5,
+            F6 => WinitVirtualKeyCode::F6,
+            F7 => WinitVirtualKeyCode::F7,
+            F8 => WinitVirtualKeyCode::F8,
+            F9 => WinitVirtualKeyCode::F9,
+            F10 => WinitVirtualKeyCode::F10,
+            F11 => WinitVirtualKeyCode::F11,
+            F12 => WinitVirtualKeyCode::F12,
+            F13 => WinitVirtualKeyCode::F13,
+            F14 => WinitVirtualKeyCode::F14,
+            F15 => WinitVirtualKeyCode::F15,
+            F16 => WinitVirtualKeyCode::F16,
+            F17 => WinitVirtualKeyCode::F17,
+            F18 => WinitVirtualKeyCode::F18,
+            F19 => WinitVirtualKeyCode::F19,
+            F20 => WinitVirtualKeyCode::F20,
+            F21 => WinitVirtualKeyCode::F21,
+            F22 => WinitVirtualKeyCode::F22,
+            F23 => WinitVirtualKeyCode::F23,
+            F24 => WinitVirtualKeyCode::F24,
+            Snapshot => WinitVirtualKeyCode::Snapshot,
+            Scroll => WinitVirtualKeyCode::Scroll,
+            Pause => WinitVirtualKeyCode::Pause,
+            Insert => WinitVirtualKeyCode::Insert,
+            Home => WinitVirtualKeyCode::Home,
+            Delete => WinitVirtualKeyCode::Delete,
+            End => WinitVirtualKeyCode::End,
+            PageDown => WinitVirtualKeyCode::PageDown,
+            PageUp => WinitVirtualKeyCode::PageUp,
+            Left => WinitVirtualKeyCode::Left,
+            Up => WinitVirtualKeyCode::Up,
+            Right => WinitVirtualKeyCode::Right,
+            Down => WinitVirtualKeyCode::Down,
+            Back => WinitVirtualKeyCode::Back,
+            Return => WinitVirtualKeyCode::Return,
+            Space => WinitVirtualKeyCode::Space,
+            Compose => WinitVirtualKeyCode::Compose,
+            Caret => WinitVirtualKeyCode::Caret,
+            Numlock => WinitVirtualKeyCode::Numlock,
+            Numpad0 => WinitVirtualKeyCode::Numpad0,
+            Numpad1 => WinitVirtualKeyCode::Numpad1,
+            Numpad2 => WinitVirtualKeyCode::Numpad2,
+            Numpad3 => WinitVirtualKeyCode::Numpad3,
+            Numpad4 => WinitVirtualKeyCode::Numpad4,
+            Numpad5 => WinitVirtualKeyCode::Numpad5,
+            Numpad6 => WinitVirtualKeyCode::Numpad6,
+            Numpad7 => WinitVirtualKeyCode::Numpad7,
+            Numpad8 => WinitVirtualKeyCode::Numpad8,
+            Numpad9 => WinitVirtualKeyCode::Numpad9,
+            AbntC1 => WinitVirtualKeyCode::AbntC1,
+            AbntC2 => WinitVirtualKeyCode::AbntC2,
+            Add => WinitVirtualKeyCode::Add,
+            Apostrophe => WinitVirtualKeyCode::Apostrophe,
+            Apps => WinitVirtualKeyCode::Apps,
+            At => WinitVirtualKeyCode::At,
+            Ax => WinitVirtualKeyCode::Ax,
+            Backslash => WinitVirtualKeyCode::Backslash,
+            Calculator => WinitVirtualKeyCode::Calculator,
+            Capital => WinitVirtualKeyCode::Capital,
+            Colon => WinitVirtualKeyCode::Colon,
+            Comma => WinitVirtualKeyCode::Comma,
+            Convert => WinitVirtualKeyCode::Convert,
+            Decimal => WinitVirtualKeyCode::Decimal,
+            Divide => WinitVirtualKeyCode::Divide,
+            Equals => WinitVirtualKeyCode::Equals,
+            Grave => WinitVirtualKeyCode::Grave,
+            Kana => WinitVirtualKeyCode::Kana,
+            Kanji => WinitVirtualKeyCode::Kanji,
+            LAlt => WinitVirtualKeyCode::LAlt,
+            LBracket => WinitVirtualKeyCode::LBracket,
+            LControl => WinitVirtualKeyCode::LControl,
+            LShift => WinitVirtualKeyCode::LShift,
+            LWin => WinitVirtualKeyCode::LWin,
+            Mail => WinitVirtualKeyCode::Mail,
+            MediaSelect => WinitVirtualKeyCode::MediaSelect,
+            MediaStop => WinitVirtualKeyCode::MediaStop,
+            Minus => WinitVirtualKeyCode::Minus,
+            Multiply => WinitVirtualKeyCode::Multiply,
+            Mute => WinitVirtualKeyCode::Mute,
+            MyComputer => WinitVirtualKeyCode::MyComputer,
+            NavigateForward => WinitVirtualKeyCode::NavigateForward,
+            NavigateBackward => WinitVirtualKeyCode::NavigateBackward,
+            NextTrack => WinitVirtualKeyCode::NextTrack,
+            NoConvert => WinitVirtualKeyCode::NoConvert,
+            NumpadComma => WinitVirtualKeyCode::NumpadComma,
+            NumpadEnter => WinitVirtualKeyCode::NumpadEnter,
+            NumpadEquals => WinitVirtualKeyCode::NumpadEquals,
+            OEM102 => WinitVirtualKeyCode::OEM102,
+            Period => WinitVirtualKeyCode::Period,
+            PlayPause => WinitVirtualKeyCode::PlayPause,
+            Power => WinitVirtualKeyCode::Power,
+            PrevTrack => WinitVirtualKeyCode::PrevTrack,
+            RAlt => WinitVirtualKeyCode::RAlt,
+            RBracket => WinitVirtualKeyCode::RBracket,
+            RControl => WinitVirtualKeyCode::RControl,
+            RShift => WinitVirtualKeyCode::RShift,
+            RWin => WinitVirtualKeyCode::RWin,
+            Semicolon => WinitVirtualKeyCode::Semicolon,
+            Slash => WinitVirtualKeyCode::Slash,
+            Sleep => WinitVirtualKeyCode::Sleep,
+            Stop => WinitVirtualKeyCode::Stop,
+            Subtract => WinitVirtualKeyCode::Subtract,
+            Sysrq => WinitVirtualKeyCode::Sysrq,
+            Tab => WinitVirtualKeyCode::Tab,
+            Underline => WinitVirtualKeyCode::Underline,
+            Unlabeled => WinitVirtualKeyCode::Unlabeled,
+            VolumeDown => WinitVirtualKeyCode::VolumeDown,
+            VolumeUp => WinitVirtualKeyCode::VolumeUp,
+            Wake => WinitVirtualKeyCode::Wake,
+            WebBack => WinitVirtualKeyCode::WebBack,
+            WebFavorites => WinitVirtualKeyCode::WebFavorites,
+            WebForward => WinitVirtualKeyCode::WebForward,
+            WebHome => WinitVirtualKeyCode::WebHome,
+            WebRefresh => WinitVirtualKeyCode::WebRefresh,
+            WebSearch => WinitVirtualKeyCode::WebSearch,
+            WebStop => WinitVirtualKeyCode::WebStop,
+            Yen => WinitVirtualKeyCode::Yen,
+            Copy => WinitVirtualKeyCode::Copy,
+            Paste => WinitVirtualKeyCode::Paste,
+            Cut => WinitVirtualKeyCode::Cut,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -134,6 +304,8 @@ pub(crate) struct FullWindowState {
     pub request_user_attention: bool,
     /// Set the windows Wayland theme. Irrelevant on other platforms, set to `None`
     pub wayland_theme: Option<WaylandTheme>,
+    /// Light or dark theme, used for evaluating `@media (prefers-color-scheme)` queries
+    pub theme: WindowTheme,
 
     // --
     /// Previous window state, used for determining mouseout, etc. events
@@ -170,6 +342,7 @@ impl Default for FullWindowState {
             ime_position: None,
             request_user_attention: false,
             wayland_theme: None,
+            theme: WindowTheme::default(),
 
             // --
             previous_window_state: None,
@@ -218,6 +391,7 @@ pub(crate) fn full_window_state_from_normal_state(window_state: WindowState) ->
         mouse_state: window_state.mouse_state,
         keyboard_state: window_state.keyboard_state,
         debug_state: window_state.debug_state,
+        theme: window_state.theme,
         ..Default::default()
     }
 }
@@ -236,6 +410,9 @@ pub(crate) struct DetermineCallbackResult<T> {
     pub(crate) hit_test_item: Option<HitTestItem>,
     pub(crate) default_callbacks: BTreeMap<EventFilter, DefaultCallbackId>,
     pub(crate) normal_callbacks: BTreeMap<EventFilter, Callback<T>>,
+    /// Callbacks of the spans of a `NodeType::RichText` node, by span index - only the
+    /// callbacks of the span that is under the cursor are called
+    pub(crate) span_callbacks: BTreeMap<usize, BTreeMap<EventFilter, Callback<T>>>,
 }
 
 impl<T> Default for DetermineCallbackResult<T> {
@@ -244,6 +421,7 @@ impl<T> Default for DetermineCallbackResult<T> {
             hit_test_item: None,
             default_callbacks: BTreeMap::new(),
             normal_callbacks: BTreeMap::new(),
+            span_callbacks: BTreeMap::new(),
         }
     }
 }
@@ -254,6 +432,7 @@ impl<T> Clone for DetermineCallbackResult<T> {
             hit_test_item: self.hit_test_item.clone(),
             default_callbacks: self.default_callbacks.clone(),
             normal_callbacks: self.normal_callbacks.clone(),
+            span_callbacks: self.span_callbacks.clone(),
         }
     }
 }
@@ -277,8 +456,8 @@ impl<T> fmt::Debug for DetermineCallbackResult<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "{:?}, {:?}, {:?}",
-            self.hit_test_item, self.default_callbacks, self.normal_callbacks
+            "{:?}, {:?}, {:?}, {:?}",
+            self.hit_test_item, self.default_callbacks, self.normal_callbacks, self.span_callbacks
         )
     }
 }
@@ -504,6 +683,36 @@ pub(crate) fn determine_callbacks<T>(
         );
     }
 
+    // Insert the hover events of the text spans
+    for (hover_node_id, hit_test_item) in &new_hit_node_ids {
+        let node_span_callbacks = match ui_state.span_callbacks.get(hover_node_id) {
+            Some(s) => s,
+            None => continue,
+        };
+
+        for (span_idx, span_hover_callbacks) in node_span_callbacks {
+            let normal_span_callbacks = current_hover_events
+                .iter()
+                .filter_map(|current_hover_event| {
+                    let callback = span_hover_callbacks.get(current_hover_event)?;
+                    Some((EventFilter::Hover(*current_hover_event), *callback))
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            if normal_span_callbacks.is_empty() {
+                continue;
+            }
+
+            let callback_result = nodes_with_callbacks
+                .entry(*hover_node_id)
+                .or_insert_with(|| DetermineCallbackResult::default());
+            callback_result.hit_test_item = Some(hit_test_item.clone());
+            callback_result
+                .span_callbacks
+                .insert(*span_idx, normal_span_callbacks);
+        }
+    }
+
     // Insert (normal + default) focus events
     if let Some(current_focused_node) = &window_state.focused_node {
         insert_callbacks!(
@@ -1137,6 +1346,9 @@ fn window_should_close(event: &WindowEvent, frame_event_info: &mut FrameEventInf
         | WindowEvent::ReceivedCharacter(_)
         | WindowEvent::MouseWheel { .. }
         | WindowEvent::MouseInput { .. }
+        | WindowEvent::HoveredFile(_)
+        | WindowEvent::DroppedFile(_)
+        | WindowEvent::HoveredFileCancelled
         | WindowEvent::Touch(_) => {
             frame_event_info.should_hittest = true;
         }
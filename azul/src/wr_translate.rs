@@ -13,8 +13,8 @@ use azul_core::{
     callbacks::{HidpiAdjustedBounds, HitTestItem, PipelineId},
     display_list::{
         AlphaType, CachedDisplayList, DisplayListFrame, DisplayListMsg, DisplayListScrollFrame,
-        FontInstanceFlags, FontRenderMode, GlyphInstance, GlyphOptions, ImageRendering,
-        LayoutRectContent, StyleBorderRadius,
+        FontInstanceFlags, FontRenderMode, FrameFilter, FramePosition, GlyphInstance, GlyphOptions,
+        ImageRendering, LayoutRectContent, LineStyle, StickyOffsetBounds, StyleBorderRadius,
     },
     ui_solver::ExternalScrollId,
     window::LogicalSize,
@@ -23,10 +23,11 @@ use azul_core::{
 use azul_css::{
     BorderSide as CssBorderSide, BorderStyle as CssBorderStyle,
     BoxShadowClipMode as CssBoxShadowClipMode, ColorF as CssColorF, ColorU as CssColorU,
-    ExtendMode as CssExtendMode, LayoutPoint, LayoutPoint as CssLayoutPoint, LayoutRect,
-    LayoutRect as CssLayoutRect, LayoutSideOffsets as CssLayoutSideOffsets, LayoutSize,
-    LayoutSize as CssLayoutSize,
+    ExtendMode as CssExtendMode, FontVariation, LayoutPoint, LayoutPoint as CssLayoutPoint,
+    LayoutRect, LayoutRect as CssLayoutRect, LayoutSideOffsets as CssLayoutSideOffsets, LayoutSize,
+    LayoutSize as CssLayoutSize, LayoutTransform,
 };
+use euclid::SideOffsets2D;
 use glium::glutin::{
     event::VirtualKeyCode as WinitVirtualKeyCode, window::CursorIcon as WinitCursorType,
 };
@@ -35,16 +36,24 @@ use webrender::api::{
     BorderStyle as WrBorderStyle, BoxShadowClipMode as WrBoxShadowClipMode,
     BuiltDisplayList as WrBuiltDisplayList, ColorF as WrColorF, ColorU as WrColorU,
     DisplayListBuilder as WrDisplayListBuilder, ExtendMode as WrExtendMode,
-    ExternalScrollId as WrExternalScrollId, FontInstanceFlags as WrFontInstanceFlags,
-    FontInstanceKey as WrFontInstanceKey, FontKey as WrFontKey, FontRenderMode as WrFontRenderMode,
+    ExternalScrollId as WrExternalScrollId, FilterOp as WrFilterOp,
+    FontInstanceFlags as WrFontInstanceFlags, FontInstanceKey as WrFontInstanceKey,
+    FontKey as WrFontKey, FontRenderMode as WrFontRenderMode, FontVariation as WrFontVariation,
     GlyphInstance as WrGlyphInstance, GlyphOptions as WrGlyphOptions, HitTestItem as WrHitTestItem,
     IdNamespace as WrIdNamespace, ImageDescriptor as WrImageDescriptor,
     ImageFormat as WrImageFormat, ImageKey as WrImageKey, ImageRendering as WrImageRendering,
     LayoutPoint as WrLayoutPoint, LayoutPrimitiveInfo as WrLayoutPrimitiveInfo,
     LayoutRect as WrLayoutRect, LayoutSideOffsets as WrLayoutSideOffsets,
-    LayoutSize as WrLayoutSize, PipelineId as WrPipelineId, SpaceAndClipInfo as WrSpaceAndClipInfo,
+    LayoutSize as WrLayoutSize, LayoutTransform as WrLayoutTransform,
+    LayoutVector2D as WrLayoutVector2D, LineStyle as WrLineStyle, MixBlendMode as WrMixBlendMode,
+    PipelineId as WrPipelineId, PropertyBinding as WrPropertyBinding, RasterSpace as WrRasterSpace,
+    ReferenceFrameKind as WrReferenceFrameKind, SpaceAndClipInfo as WrSpaceAndClipInfo,
+    StickyOffsetBounds as WrStickyOffsetBounds, TransformStyle as WrTransformStyle,
 };
 
+// Shared with the software renderer, so that backgrounds are positioned the same way
+pub(crate) use self::background::{calculate_background_position, calculate_background_size};
+
 #[inline(always)]
 pub(crate) fn wr_translate_hittest_item(input: WrHitTestItem) -> HitTestItem {
     HitTestItem {
@@ -160,6 +169,14 @@ pub(crate) const fn wr_translate_font_instance_key(
     )
 }
 
+#[inline(always)]
+pub(crate) fn wr_translate_font_variation(font_variation: &FontVariation) -> WrFontVariation {
+    WrFontVariation {
+        tag: font_variation.tag.as_u32(),
+        value: font_variation.value.get(),
+    }
+}
+
 #[inline(always)]
 pub(crate) const fn wr_translate_image_key(image_key: ImageKey) -> WrImageKey {
     WrImageKey(
@@ -215,6 +232,16 @@ pub fn wr_translate_extend_mode(input: CssExtendMode) -> WrExtendMode {
     }
 }
 
+#[inline(always)]
+pub fn wr_translate_line_style(input: LineStyle) -> WrLineStyle {
+    match input {
+        LineStyle::Solid => WrLineStyle::Solid,
+        LineStyle::Dotted => WrLineStyle::Dotted,
+        LineStyle::Dashed => WrLineStyle::Dashed,
+        LineStyle::Wavy => WrLineStyle::Wavy,
+    }
+}
+
 #[inline(always)]
 pub fn wr_translate_border_style(input: CssBorderStyle) -> WrBorderStyle {
     match input {
@@ -681,6 +708,40 @@ pub(crate) fn wr_translate_display_list(
     builder.finalize().2
 }
 
+#[inline(always)]
+fn wr_translate_sticky_offset_bounds(input: StickyOffsetBounds) -> WrStickyOffsetBounds {
+    WrStickyOffsetBounds::new(input.min, input.max)
+}
+
+#[inline]
+fn wr_translate_layout_transform(input: LayoutTransform) -> WrLayoutTransform {
+    WrLayoutTransform::row_major(
+        input.m11, input.m12, 0.0, 0.0, input.m21, input.m22, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+        input.m31, input.m32, 0.0, 1.0,
+    )
+}
+
+#[inline]
+fn wr_translate_frame_filter(input: FrameFilter) -> WrFilterOp {
+    match input {
+        FrameFilter::Opacity(opacity) => {
+            WrFilterOp::Opacity(WrPropertyBinding::Value(opacity), opacity)
+        }
+        FrameFilter::Blur(radius) => WrFilterOp::Blur(radius),
+        FrameFilter::Grayscale(amount) => WrFilterOp::Grayscale(amount),
+        FrameFilter::Brightness(amount) => WrFilterOp::Brightness(amount),
+        FrameFilter::DropShadow {
+            offset,
+            blur_radius,
+            color,
+        } => WrFilterOp::DropShadow(
+            WrLayoutVector2D::new(offset.x, offset.y),
+            blur_radius,
+            wr_translate_color_u(color).into(),
+        ),
+    }
+}
+
 #[inline]
 fn push_display_list_msg(
     builder: &mut WrDisplayListBuilder,
@@ -688,9 +749,80 @@ fn push_display_list_msg(
     space_and_clip: &WrSpaceAndClipInfo,
 ) {
     use azul_core::display_list::DisplayListMsg::*;
+
+    let space_and_clip = match msg.get_frame().position {
+        FramePosition::Static => *space_and_clip,
+        // Fixed frames are not scrolled or clipped by their parent frames
+        FramePosition::Fixed => WrSpaceAndClipInfo::root_scroll(builder.pipeline_id),
+        // WebRender moves sticky frames relative to the nearest scroll frame
+        FramePosition::Sticky(sticky) => WrSpaceAndClipInfo {
+            spatial_id: builder.define_sticky_frame(
+                space_and_clip.spatial_id,
+                wr_translate_layout_rect(msg.get_frame().rect),
+                SideOffsets2D::new(sticky.top, sticky.right, sticky.bottom, sticky.left),
+                wr_translate_sticky_offset_bounds(sticky.vertical_offset_bounds),
+                wr_translate_sticky_offset_bounds(sticky.horizontal_offset_bounds),
+                WrLayoutVector2D::zero(),
+            ),
+            clip_id: space_and_clip.clip_id,
+        },
+    };
+
+    // The transform is relative to the layout coordinate space (not to the frame),
+    // so the reference frame has to start at the origin
+    let transform = msg.get_frame().transform;
+    let space_and_clip = match transform {
+        None => space_and_clip,
+        Some(transform) => WrSpaceAndClipInfo {
+            spatial_id: builder.push_reference_frame(
+                &WrLayoutRect::new(
+                    WrLayoutPoint::zero(),
+                    wr_translate_layout_size(msg.get_size()),
+                ),
+                space_and_clip.spatial_id,
+                WrTransformStyle::Flat,
+                WrPropertyBinding::Value(wr_translate_layout_transform(transform)),
+                WrReferenceFrameKind::Transform,
+            ),
+            clip_id: space_and_clip.clip_id,
+        },
+    };
+
+    // Opacity and filters apply to the frame and all of its children,
+    // so the whole subtree is rendered into one stacking context
+    let filters = msg
+        .get_frame()
+        .filters
+        .iter()
+        .map(|f| wr_translate_frame_filter(*f))
+        .collect::<Vec<_>>();
+
+    if !filters.is_empty() {
+        builder.push_stacking_context(
+            &WrLayoutPrimitiveInfo::new(WrLayoutRect::new(
+                WrLayoutPoint::zero(),
+                wr_translate_layout_size(msg.get_size()),
+            )),
+            space_and_clip.spatial_id,
+            /* clip_id: */ None,
+            WrTransformStyle::Flat,
+            WrMixBlendMode::Normal,
+            &filters,
+            WrRasterSpace::Screen,
+        );
+    }
+
     match msg {
-        Frame(f) => push_frame(builder, f, space_and_clip),
-        ScrollFrame(sf) => push_scroll_frame(builder, sf, space_and_clip),
+        Frame(f) => push_frame(builder, f, &space_and_clip),
+        ScrollFrame(sf) => push_scroll_frame(builder, sf, &space_and_clip),
+    }
+
+    if !filters.is_empty() {
+        builder.pop_stacking_context();
+    }
+
+    if transform.is_some() {
+        builder.pop_reference_frame();
     }
 }
 
@@ -751,7 +883,9 @@ fn push_frame(
                 clip_id,
             }
         }
-        None => WrSpaceAndClipInfo::root_scroll(builder.pipeline_id),
+        // Children have to stay in the space of the parent scroll frame,
+        // otherwise sticky children can't find their scroll frame
+        None => *root_space_and_clip,
     };
 
     for child in frame.children {
@@ -947,6 +1081,31 @@ fn push_display_list_content(
                 radii,
             );
         }
+        Line {
+            bounds,
+            thickness,
+            color,
+            style,
+            clip,
+        } => {
+            text::push_line(
+                builder,
+                info,
+                space_and_clip,
+                bounds,
+                thickness,
+                color,
+                style,
+                clip,
+            );
+        }
+        Shadowed { shadows, content } => {
+            text::push_shadows(builder, info, space_and_clip, &shadows);
+            for item in content {
+                push_display_list_content(builder, item, info, radii, space_and_clip);
+            }
+            builder.pop_all_shadows();
+        }
     }
 }
 
@@ -954,12 +1113,13 @@ mod text {
 
     use azul_core::{
         app_resources::FontInstanceKey,
-        display_list::{GlyphInstance, GlyphOptions},
+        display_list::{GlyphInstance, GlyphOptions, LineStyle, TextShadow},
     };
     use azul_css::{ColorU, LayoutRect};
     use webrender::api::{
         DisplayListBuilder as WrDisplayListBuilder, LayoutPrimitiveInfo as WrLayoutPrimitiveInfo,
-        SpaceAndClipInfo as WrSpaceAndClipInfo,
+        LayoutVector2D as WrLayoutVector2D, LineOrientation as WrLineOrientation,
+        Shadow as WrShadow, SpaceAndClipInfo as WrSpaceAndClipInfo,
     };
 
     pub(super) fn push_text(
@@ -993,6 +1153,57 @@ mod text {
             glyph_options.map(wr_translate_glyph_options),
         );
     }
+
+    pub(super) fn push_line(
+        builder: &mut WrDisplayListBuilder,
+        info: &WrLayoutPrimitiveInfo,
+        space_and_clip: &WrSpaceAndClipInfo,
+        bounds: LayoutRect,
+        thickness: f32,
+        color: ColorU,
+        style: LineStyle,
+        clip: Option<LayoutRect>,
+    ) {
+        use super::{wr_translate_color_u, wr_translate_layout_rect, wr_translate_line_style};
+
+        let mut info = *info;
+        info.rect = wr_translate_layout_rect(bounds);
+        if let Some(clip_rect) = clip {
+            info.clip_rect = wr_translate_layout_rect(clip_rect);
+        }
+
+        builder.push_line(
+            &info,
+            space_and_clip,
+            thickness,
+            WrLineOrientation::Horizontal,
+            &wr_translate_color_u(color).into(),
+            wr_translate_line_style(style),
+        );
+    }
+
+    /// Pushes the shadows of the following items, until the shadows are popped again
+    pub(super) fn push_shadows(
+        builder: &mut WrDisplayListBuilder,
+        info: &WrLayoutPrimitiveInfo,
+        space_and_clip: &WrSpaceAndClipInfo,
+        shadows: &[TextShadow],
+    ) {
+        use super::wr_translate_color_u;
+
+        // WebRender draws the shadow that is pushed last on top
+        for shadow in shadows.iter().rev() {
+            builder.push_shadow(
+                info,
+                space_and_clip,
+                WrShadow {
+                    offset: WrLayoutVector2D::new(shadow.offset.x, shadow.offset.y),
+                    color: wr_translate_color_u(shadow.color).into(),
+                    blur_radius: shadow.blur_radius,
+                },
+            );
+        }
+    }
 }
 
 mod background {
@@ -1087,8 +1298,16 @@ mod background {
 
         let background_position = background_position.unwrap_or_default();
         let _background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let offset = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(
+            super::translate_layout_size_wr(info.rect.size),
+            background_size,
+            content_size,
+        );
+        let offset = calculate_background_position(
+            super::translate_layout_size_wr(info.rect.size),
+            background_position,
+            background_size,
+        );
 
         let mut offset_info = *info;
         offset_info.rect.origin.x += offset.x;
@@ -1149,8 +1368,16 @@ mod background {
 
         let background_position = background_position.unwrap_or_default();
         let _background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let offset = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(
+            super::translate_layout_size_wr(info.rect.size),
+            background_size,
+            content_size,
+        );
+        let offset = calculate_background_position(
+            super::translate_layout_size_wr(info.rect.size),
+            background_position,
+            background_size,
+        );
 
         let mut offset_info = *info;
         offset_info.rect.origin.x += offset.x;
@@ -1198,9 +1425,16 @@ mod background {
 
         let background_position = background_position.unwrap_or_default();
         let background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let background_position =
-            calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(
+            super::translate_layout_size_wr(info.rect.size),
+            background_size,
+            content_size,
+        );
+        let background_position = calculate_background_position(
+            super::translate_layout_size_wr(info.rect.size),
+            background_position,
+            background_size,
+        );
         let background_repeat_info =
             get_background_repeat_info(info, background_repeat, background_size);
 
@@ -1241,8 +1475,16 @@ mod background {
 
         let background_position = background_position.unwrap_or_default();
         let _background_repeat = background_repeat.unwrap_or_default();
-        let background_size = calculate_background_size(info, background_size, content_size);
-        let offset = calculate_background_position(info, background_position, background_size);
+        let background_size = calculate_background_size(
+            super::translate_layout_size_wr(info.rect.size),
+            background_size,
+            content_size,
+        );
+        let offset = calculate_background_position(
+            super::translate_layout_size_wr(info.rect.size),
+            background_position,
+            background_size,
+        );
 
         let mut offset_info = *info;
         offset_info.rect.origin.x += offset.x;
@@ -1291,12 +1533,12 @@ mod background {
     }
 
     /// Transform a background size such as "cover" or "contain" into actual pixels
-    fn calculate_background_size(
-        info: &WrLayoutPrimitiveInfo,
+    pub(crate) fn calculate_background_size(
+        rect_size: LayoutSize,
         bg_size: Option<StyleBackgroundSize>,
         content_size: Option<(f32, f32)>,
     ) -> LayoutSize {
-        let content_size = content_size.unwrap_or((info.rect.size.width, info.rect.size.height));
+        let content_size = content_size.unwrap_or((rect_size.width, rect_size.height));
 
         let bg_size = match bg_size {
             None => return LayoutSize::new(content_size.0 as f32, content_size.1 as f32),
@@ -1304,14 +1546,14 @@ mod background {
         };
 
         let content_aspect_ratio = Ratio {
-            width: info.rect.size.width / content_size.0 as f32,
-            height: info.rect.size.height / content_size.1 as f32,
+            width: rect_size.width / content_size.0 as f32,
+            height: rect_size.height / content_size.1 as f32,
         };
 
         let ratio = match bg_size {
             StyleBackgroundSize::ExactSize(w, h) => {
-                let w = w.to_pixels(info.rect.size.width);
-                let h = h.to_pixels(info.rect.size.height);
+                let w = w.to_pixels(rect_size.width);
+                let h = h.to_pixels(rect_size.height);
                 w.min(h)
             }
             StyleBackgroundSize::Contain => {
@@ -1326,16 +1568,16 @@ mod background {
     }
 
     /// Transforma background-position attribute into pixel coordinates
-    fn calculate_background_position(
-        info: &WrLayoutPrimitiveInfo,
+    pub(crate) fn calculate_background_position(
+        rect_size: LayoutSize,
         background_position: StyleBackgroundPosition,
         background_size: LayoutSize,
     ) -> LayoutPoint {
         use azul_css::BackgroundPositionHorizontal;
         use azul_css::BackgroundPositionVertical;
 
-        let width = info.rect.size.width;
-        let height = info.rect.size.height;
+        let width = rect_size.width;
+        let height = rect_size.height;
 
         let horizontal_offset = match background_position.horizontal {
             BackgroundPositionHorizontal::Right => 0.0,
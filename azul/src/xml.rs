@@ -1026,6 +1026,14 @@ fn render_single_dom_node_to_string<T>(dom: &Dom<T>, existing_str: &mut String)
         existing_str.push_str(&format!(".with_class({})", class));
     }
 
+    for (name, value) in head.get_attributes().iter() {
+        existing_str.push_str(&format!(
+            ".with_attribute({:?}, {:?})",
+            name.as_str(),
+            value.as_str()
+        ));
+    }
+
     if let Some(tab_index) = head.get_tab_index() {
         use crate::dom::TabIndex::*;
         existing_str.push_str(&format!(